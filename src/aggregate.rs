@@ -0,0 +1,148 @@
+// src/aggregate.rs
+//
+// Group the rows of a dataset and summarize their numeric columns: row
+// count, then average and total per column. Purely local (no scraping);
+// the Team Summary page is Players grouped by team.
+
+use crate::config::options::PageKind;
+use crate::store::DataSet;
+use crate::typed::TypedColumns;
+
+/// A group while summing: key, row count, per stat column (sum, filled cells).
+type Group = (String, usize, Vec<(f64, usize)>);
+
+/// Whole numbers without decimals, others to two places.
+pub(crate) fn fmt_num(x: f64) -> String {
+    if x.fract() == 0.0 { format!("{}", x as i64) }
+    else { format!("{}", (x * 100.0).round() / 100.0) }
+}
+
+/// Columns (other than `skip`) with at least one filled cell, all numbers.
+pub fn numeric_columns(ds: &DataSet, skip: &[usize]) -> Vec<usize> {
+    TypedColumns::inferred(ds).numeric_columns().into_iter()
+        .filter(|c| !skip.contains(c))
+        .collect()
+}
+
+/// One row per distinct value of `group_col` (in order of first appearance):
+/// the group, its row count (`count_header`), then "<col> avg" and
+/// "<col> total" for each numeric column not in `skip`. Blank cells don't
+/// count towards an average.
+pub fn summarize(ds: &DataSet, group_col: usize, count_header: &str, skip: &[usize]) -> DataSet {
+    summarize_typed(ds, &TypedColumns::inferred(ds), group_col, count_header, skip)
+}
+
+/// `summarize` with the column types already parsed (e.g. from the page
+/// schema); the stat columns are the numeric ones of `typed`.
+pub fn summarize_typed(
+    ds: &DataSet,
+    typed: &TypedColumns,
+    group_col: usize,
+    count_header: &str,
+    skip: &[usize],
+) -> DataSet {
+    let mut skip = skip.to_vec();
+    skip.push(group_col);
+    let stats: Vec<usize> = typed.numeric_columns().into_iter()
+        .filter(|c| !skip.contains(c))
+        .collect();
+
+    let header = |c: usize| -> String {
+        ds.headers.as_ref().and_then(|h| h.get(c)).cloned()
+            .unwrap_or_else(|| format!("Col {}", c + 1))
+    };
+    let mut headers = vec![header(group_col), s!(count_header)];
+    for &c in &stats {
+        headers.push(format!("{} avg", header(c)));
+        headers.push(format!("{} total", header(c)));
+    }
+
+    let mut groups: Vec<Group> = Vec::new();
+    for (ri, r) in ds.rows.iter().enumerate() {
+        let Some(key) = r.get(group_col).filter(|k| !k.is_empty()) else { continue };
+        let ix = match groups.iter().position(|(k, ..)| k == key) {
+            Some(ix) => ix,
+            None => {
+                groups.push((key.clone(), 0, vec![(0.0, 0); stats.len()]));
+                groups.len() - 1
+            }
+        };
+        let g = &mut groups[ix];
+        g.1 += 1;
+        for (si, &c) in stats.iter().enumerate() {
+            if let Some(x) = typed.number(ri, c) {
+                g.2[si].0 += x;
+                g.2[si].1 += 1;
+            }
+        }
+    }
+
+    let rows = groups.into_iter().map(|(key, n, sums)| {
+        let mut row = vec![key, n.to_string()];
+        for (sum, filled) in sums {
+            row.push(if filled == 0 { String::new() } else { fmt_num(sum / filled as f64) });
+            row.push(if filled == 0 { String::new() } else { fmt_num(sum) });
+        }
+        row
+    }).collect();
+
+    DataSet { headers: Some(headers), rows }
+}
+
+/// Players columns that aren't stats: name, number, race, the player id
+/// and the derived columns (the team column is left to the caller).
+pub(crate) fn players_non_stats(players: &DataSet) -> Vec<usize> {
+    let mut skip = vec![0, 1, 2];
+    skip.extend(players.header_index(crate::scrape::PLAYER_ID_HEADER));
+    skip.extend(crate::derive::columns(PageKind::Players).iter().filter_map(|c| players.header_index(c.name)));
+    skip
+}
+
+/// Players stat columns: the numeric ones that aren't `players_non_stats`
+/// or the team (compared, ranked and charted per player).
+pub(crate) fn players_stat_columns(players: &DataSet, typed: &TypedColumns) -> Vec<usize> {
+    let mut skip = players_non_stats(players);
+    skip.push(3);
+    typed.numeric_columns().into_iter().filter(|c| !skip.contains(c)).collect()
+}
+
+/// Team Summary: Players grouped by team. Name, number, race, the player
+/// id and the derived columns aren't summarized.
+pub fn team_summary(players: &DataSet) -> DataSet {
+    let skip = players_non_stats(players);
+    summarize_typed(players, &TypedColumns::for_page(PageKind::Players, players), 3, "Players", &skip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ds(headers: &[&str], rows: &[&[&str]]) -> DataSet {
+        DataSet {
+            headers: Some(headers.iter().map(|h| s!(*h)).collect()),
+            rows: rows.iter().map(|r| r.iter().map(|c| s!(*c)).collect()).collect(),
+        }
+    }
+
+    #[test]
+    fn groups_in_first_seen_order_with_avg_and_total() {
+        let players = ds(&["Name", "#", "Team", "TV", "Note"], &[
+            &["Al", "#1", "Reds", "100", "x"],
+            &["Bo", "#2", "Blues", "80", ""],
+            &["Cy", "#3", "Reds", "", ""],
+            &["Di", "#4", "Reds", "105", "y"],
+        ]);
+        let sum = summarize(&players, 2, "Players", &[0, 1]);
+        assert_eq!(sum.headers.unwrap(), vec!["Team", "Players", "TV avg", "TV total"]);
+        assert_eq!(sum.rows, vec![
+            vec![s!("Reds"), s!("3"), s!("102.5"), s!("205")],
+            vec![s!("Blues"), s!("1"), s!("80"), s!("80")],
+        ]);
+    }
+
+    #[test]
+    fn text_and_empty_columns_are_not_summarized() {
+        let d = ds(&["Team", "A", "B", "C"], &[&["Reds", "1", "x", ""], &["Reds", "2", "3", ""]]);
+        assert_eq!(numeric_columns(&d, &[0]), vec![1]);
+    }
+}
@@ -0,0 +1,99 @@
+// src/alias.rs
+//
+// Player name aliases: spellings the user has resolved to one canonical
+// name ("Grimtooth" → "Grim Tooth"), kept in `.store/settings` as
+// `alias.<normalized variant>=<canonical>`. Variants are compared by
+// `join::name_key`, so case and punctuation don't need their own entries.
+//
+// Scraped data is rewritten to the canonical names before it's merged into
+// the cache, so merges and change reports see one name per player; joins
+// look names up through the map as well, for data cached before an alias
+// existed.
+
+use std::collections::BTreeMap;
+
+use crate::{config::options::PageKind, join::name_key, store::{self, DataSet}};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Aliases {
+    /// Normalized variant → canonical name
+    map: BTreeMap<String, String>,
+}
+
+impl Aliases {
+    /// The aliases saved in `.store/settings`.
+    pub fn load() -> Self {
+        Self { map: store::name_aliases() }
+    }
+
+    pub fn is_empty(&self) -> bool { self.map.is_empty() }
+
+    /// (normalized variant, canonical name), sorted by variant.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.map.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Map `variant` to `canonical` (in memory; see `store::save_name_alias`).
+    pub fn insert(&mut self, variant: &str, canonical: &str) {
+        let key = name_key(variant);
+        if !key.is_empty() && key != name_key(canonical) {
+            self.map.insert(key, canonical.trim().to_string());
+        }
+    }
+
+    /// The canonical spelling of `name`, or `name` itself.
+    pub fn canonical<'a>(&'a self, name: &'a str) -> &'a str {
+        if self.map.is_empty() { return name; }
+        self.map.get(&name_key(name)).map(String::as_str).unwrap_or(name)
+    }
+
+    /// Rewrite the player name columns of a `kind` dataset to canonical
+    /// names. Returns the cells changed.
+    pub fn apply(&self, kind: PageKind, ds: &mut DataSet) -> usize {
+        if self.map.is_empty() { return 0; }
+        let cols: Vec<usize> = name_headers(kind).iter().filter_map(|h| ds.header_index(h)).collect();
+        let mut changed = 0;
+        for row in &mut ds.rows {
+            for &c in &cols {
+                let Some(cell) = row.get_mut(c) else { continue };
+                let canonical = self.canonical(cell);
+                if canonical != cell.as_str() {
+                    *cell = canonical.to_string();
+                    changed += 1;
+                }
+            }
+        }
+        changed
+    }
+}
+
+/// Headers of the columns holding player names, per page.
+pub fn name_headers(kind: PageKind) -> &'static [&'static str] {
+    match kind {
+        PageKind::Players => &["Name"],
+        PageKind::Injuries | PageKind::InjuryVictims => &["Victim", "Offender"],
+        PageKind::Transactions => &["Player"],
+        _ => &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrites_variants_in_name_columns_only() {
+        let mut aliases = Aliases::default();
+        aliases.insert("Grimtooth", "Grim Tooth");
+        aliases.insert("grim-tooth", "Grim Tooth"); // same as the canonical once normalized: ignored
+        assert_eq!(aliases.iter().collect::<Vec<_>>(), vec![("grimtooth", "Grim Tooth")]);
+
+        let mut ds = DataSet {
+            headers: Some(["S", "W", "Victim Team", "Victim", "Offender"].map(String::from).to_vec()),
+            rows: vec![["7", "1", "Grimtooth", "GRIMTOOTH", "grimtooth!"].map(String::from).to_vec()],
+        };
+        assert_eq!(aliases.apply(PageKind::Injuries, &mut ds), 2);
+        assert_eq!(ds.rows[0], ["7", "1", "Grimtooth", "Grim Tooth", "Grim Tooth"]);
+        assert_eq!(aliases.canonical("Bo"), "Bo");
+    }
+}
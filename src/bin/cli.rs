@@ -4,6 +4,6 @@ use bb_scrape::cli;
 fn main() {
     if let Err(e) = cli::run() {
         eprintln!("Error: {e}");
-        std::process::exit(1);
+        std::process::exit(cli::exit_code(e.as_ref()));
     }
 }
@@ -1,21 +1,39 @@
 // src/cli.rs
-use std::{env, path::PathBuf};
+use std::{env, path::PathBuf, thread, time::{Duration, Instant, SystemTime}};
 use std::str::FromStr;
 use std::error::Error;
 
 use crate::{ 
+    alias::Aliases,
+    core::{net, select::{select_rows, Selector}},
+    derive,
+    diff::DataDiff,
     file,
+    get_teams,
+    import,
+    integrity,
+    notify,
+    schema,
     scrape,
+    verify::{self, Sampler},
 };
 use crate::{
-    store::{ self, DataSet },
-    progress::Progress,
+    store::{ self, fmt_age, DataSet },
+    progress::{Progress, Summary},
     config::{
+        consts,
         state::AppState, 
         options::{ 
-            ExportType::*, 
+            AppOptions,
+            Backup,
+            ExportOptions,
+            ExportType::{ self, * },
             ExportFormat, 
-            PageKind::{ self, * }
+            FormulaEscape,
+            InjuryParser,
+            NumberFormat,
+            PageKind::{ self, * },
+            TeamSelector,
         },
     },
 };
@@ -25,14 +43,232 @@ pub enum Mode {
     Gui(AppState),
 }
 
+/// CLI-only switches that don't belong in AppState.
+#[derive(Debug, Default)]
+struct CliFlags {
+    /// `--watch <interval>`: re-scrape on this interval until interrupted
+    watch: Option<Duration>,
+    /// `--diff`: print what changed against the cached dataset
+    diff: bool,
+    /// `--plan`: print the requests a scrape would make, then exit
+    plan: bool,
+    /// `--print-week <n>`: write a print view of that week's games, then exit
+    print_week: Option<u32>,
+    /// `--page a,b,…` / `--page all`: the pages to refresh, in order
+    pages: Vec<PageKind>,
+    /// `-o` was given (not allowed with several pages)
+    out: bool,
+    /// `--show-cache <page>`: print the cached dataset, then exit
+    show_cache: Option<PageKind>,
+    /// `--cache-info`: list the cached pages, then exit
+    cache_info: bool,
+    /// `--diagnostics`: print diagnostic info for a bug report, then exit
+    diagnostics: bool,
+    /// `--verify-cache`: check the cached pages for damage, then exit
+    verify_cache: bool,
+    /// `--repair` (with `--verify-cache`): drop the damaged rows
+    repair: bool,
+    /// `--import <page> <file>`: merge a third-party CSV/TSV into the cache, then exit
+    import: Option<(PageKind, PathBuf)>,
+    /// `--season <n>`: use that season's cached data (with `--import`, also
+    /// the imported rows' season)
+    season: Option<u32>,
+    /// `--archive-season <n>`: copy the cached pages into season n's archive, then exit
+    archive_season: Option<u32>,
+    /// `--map` / `--map-file` (with `--import`): page column = file column
+    map: Vec<(String, String)>,
+    /// `--from-cache`: export the cached dataset instead of scraping
+    from_cache: bool,
+    /// `--dry-run`: scrape, but only report what the export would write
+    dry_run: bool,
+    /// `--snapshots <n>`: archive each scrape, keeping the newest n per page
+    snapshots: Option<usize>,
+    /// `--restore-previous <page>`: swap the cache back to before the last scrape, then exit
+    restore_previous: Option<PageKind>,
+    /// `--capture <page>`: save the raw HTML the scrape would fetch as fixtures, then exit
+    capture: Option<PageKind>,
+    /// `--health-check`: parse one page per spec and report missing anchors, then exit
+    health_check: bool,
+    /// `--export-bundle <dir>`: export every cached page into one directory, then exit
+    bundle: Option<PathBuf>,
+    /// `--serve <port|addr>`: serve the cache over HTTP until interrupted
+    serve: Option<String>,
+}
+
+/// `--page all`: every page the CLI can scrape without extra arguments,
+/// teams first so the others find a fresh team list, and the derived pages
+/// straight after the pages they're computed from.
+const ALL_PAGES: [PageKind; 13] = [Teams, Players, TeamSummary, Leaders, TeamInfo, GameResults, Injuries, InjuryVictims, InjurySummary, Standings, ResultsTable, ScheduleMetrics, Transactions];
+
+/// Exit code for an error `run` returned: the `NetError` codes (3–7, see
+/// the help's NOTES) when the site couldn't be reached, else 1.
+pub fn exit_code(e: &(dyn Error + 'static)) -> i32 {
+    net::NetError::find(e).map(net::NetError::exit_code).unwrap_or(1)
+}
+
+/// `--data-dir <dir>`, wherever it is among the arguments (subcommands too).
+fn data_dir_arg(mut args: impl Iterator<Item = String>) -> Result<Option<PathBuf>, String> {
+    while let Some(a) = args.next() {
+        if a == "--data-dir" {
+            let v = args.next().filter(|v| !v.trim().is_empty()).ok_or("Missing value for --data-dir")?;
+            return Ok(Some(PathBuf::from(v)));
+        }
+    }
+    Ok(None)
+}
+
+/// Shortest accepted `--watch` interval (be polite to the site).
+const MIN_WATCH: Duration = Duration::from_secs(60);
+
 pub fn run() -> Result<(), Box<dyn Error>> {
 
+    // The data directory comes before anything reads the cache or settings
+    if let Some(dir) = data_dir_arg(env::args())? {
+        store::set_data_dir(dir);
+    }
+    store::migrate_default_store();
+
+    // Site address, timeout, retries and delay as saved by the GUI
+    if let Err(e) = net::configure(store::net_settings()) {
+        loge!("Settings: {}; using the default site", e);
+    }
+
+    // Subcommands come first and have their own arguments
+    match env::args().nth(1).as_deref() {
+        Some("extract") => return run_extract(env::args().skip(2)),
+        Some("schema") => return run_schema(env::args().skip(2)),
+        Some("verify") => return run_verify(env::args().skip(2)),
+        Some("bench-injuries") => return run_bench_injuries(env::args().skip(2)),
+        _ => {}
+    }
+
     let mut app_state = AppState::default();
-    parse_cli(&mut app_state)?;
-    
+    let flags = parse_cli(&mut app_state)?;
+    app_state.options.export.header_names = store::header_names();
+    if let Some(keep) = flags.snapshots {
+        store::set_snapshot_keep(keep);
+    }
+    if let Some(season) = flags.season {
+        store::select_season(Some(season));
+    }
+
+    // Cache only, no scraping
+    if flags.cache_info {
+        return print_cache_info();
+    }
+    if flags.diagnostics {
+        print!("{}", crate::diagnostics::report());
+        return Ok(());
+    }
+    if let Some(season) = flags.archive_season {
+        if store::selected_season().is_some() {
+            return Err("--archive-season archives the current season's data; leave out --season".into());
+        }
+        let pages = store::archive_season(season)?;
+        println!("Archived {} page(s) as season {} → {}", pages, season, store::season_dir(season).display());
+        return Ok(());
+    }
+    if flags.verify_cache {
+        return verify_cache(flags.repair);
+    }
+    if let Some(page) = flags.show_cache {
+        return show_cache(page, &app_state.options);
+    }
+    if let Some(page) = flags.restore_previous {
+        return restore_previous(page);
+    }
+    if let Some((page, path)) = &flags.import {
+        return import_file(*page, path, &flags);
+    }
+
+    if flags.health_check {
+        return health_check();
+    }
+    if let Some(dir) = &flags.bundle {
+        return export_bundle(dir, &app_state.options);
+    }
+    if let Some(addr) = &flags.serve {
+        return crate::serve::run(addr);
+    }
+
+    // Raw HTML only, no parsing or cache
+    if let Some(page) = flags.capture {
+        let options = &mut app_state.options;
+        options.scrape.page = page;
+        apply_page_defaults(page, options)?;
+        return capture(page, options);
+    }
+
+    if flags.from_cache && (flags.watch.is_some() || flags.diff) {
+        return Err("--from-cache doesn't scrape, so there's nothing to --watch or --diff".into());
+    }
+    if flags.dry_run && flags.watch.is_some() {
+        return Err("--dry-run takes a single run, not --watch".into());
+    }
+
+    if flags.pages.len() > 1 && flags.print_week.is_none() {
+        return run_pages(&flags, &app_state.options);
+    }
+
     let page = app_state.options.scrape.page;
     let options = &mut app_state.options;
+    apply_page_defaults(page, options)?;
+
+    if flags.plan {
+        return print_plan(page, options);
+    }
+
+    if let Some(week) = flags.print_week {
+        return print_week(options, week);
+    }
+
+    if let Some(every) = flags.watch {
+        return watch(page, options, every, flags.diff);
+    }
+
+    if flags.from_cache {
+        return export_cached(page, options, flags.dry_run).map(|_| ());
+    }
+
+    refresh_page(page, options, flags.diff, flags.dry_run).map(|_| ())
+}
 
+/// Several pages (`--page a,b` / `--page all`): scrape, cache and export
+/// each in turn, to its page-default path. A failed page doesn't stop the
+/// rest; the run fails at the end if any did.
+fn run_pages(flags: &CliFlags, base: &AppOptions) -> Result<(), Box<dyn Error>> {
+    if flags.out {
+        return Err("-o/--out takes a single page; with several pages each export goes to its page default".into());
+    }
+    if flags.watch.is_some() {
+        return Err("--watch takes a single page".into());
+    }
+
+    let mut failed: Vec<String> = Vec::new();
+    for &page in &flags.pages {
+        let mut options = base.clone();
+        options.scrape.page = page;
+        let res = apply_page_defaults(page, &mut options).and_then(|_| {
+            if flags.plan { print_plan(page, &options) }
+            else if flags.from_cache { export_cached(page, &mut options, flags.dry_run).map(|_| ()) }
+            else { refresh_page(page, &mut options, flags.diff, flags.dry_run).map(|_| ()) }
+        });
+        if let Err(e) = res {
+            loge!("CLI: Page {} failed: {}", page, e);
+            eprintln!("{}: {}", page, e);
+            failed.push(page.to_string());
+        }
+    }
+
+    match failed.len() {
+        0 => Ok(()),
+        n => Err(format!("{} of {} page(s) failed: {}", n, flags.pages.len(), failed.join(", ")).into()),
+    }
+}
+
+/// Point the export at the page's default directory and file name, unless
+/// the user chose another path.
+fn apply_page_defaults(page: PageKind, options: &mut AppOptions) -> Result<(), Box<dyn Error>> {
     // Ensure default DIR mirrors page (preserve filename/ext if user didn't change DIR)
     // Only flip when current dir is one of the page defaults.
     if options.export.is_current_dir_default_for(PageKind::Players)
@@ -52,10 +288,114 @@ pub fn run() -> Result<(), Box<dyn Error>> {
     if matches!(page, PageKind::Injuries)
         && options.export.is_fully_default_for(PageKind::Injuries)
     {
-        options.export.set_path("injuries");
+        options.export.set_path(crate::config::consts::DEFAULT_INJURIES_FILE);
+    }
+    // Special-case Standings: default filename should be "standings"
+    if matches!(page, PageKind::Standings)
+        && options.export.is_fully_default_for(PageKind::Standings)
+    {
+        options.export.set_path(crate::config::consts::DEFAULT_STANDINGS_FILE);
+    }
+    // Transactions: default filename "transactions"
+    if matches!(page, PageKind::Transactions)
+        && options.export.is_fully_default_for(PageKind::Transactions)
+    {
+        options.export.set_path(crate::config::consts::DEFAULT_TRANSACTIONS_FILE);
+    }
+    // Team Summary: default filename "team_summary"
+    if matches!(page, PageKind::TeamSummary)
+        && options.export.is_fully_default_for(PageKind::TeamSummary)
+    {
+        options.export.set_path(crate::config::consts::DEFAULT_TEAM_SUMMARY_FILE);
+    }
+    // Leaders: default filename "leaders"
+    if matches!(page, PageKind::Leaders)
+        && options.export.is_fully_default_for(PageKind::Leaders)
+    {
+        options.export.set_path(crate::config::consts::DEFAULT_LEADERS_FILE);
+    }
+    // Results Table: default filename "results_table"
+    if matches!(page, PageKind::ResultsTable)
+        && options.export.is_fully_default_for(PageKind::ResultsTable)
+    {
+        options.export.set_path(crate::config::consts::DEFAULT_RESULTS_TABLE_FILE);
+    }
+    // Schedule Metrics: default filename "schedule_metrics"
+    if matches!(page, PageKind::ScheduleMetrics)
+        && options.export.is_fully_default_for(PageKind::ScheduleMetrics)
+    {
+        options.export.set_path(crate::config::consts::DEFAULT_SCHEDULE_METRICS_FILE);
+    }
+    // Team Info: default filename "team_info"
+    if matches!(page, PageKind::TeamInfo)
+        && options.export.is_fully_default_for(PageKind::TeamInfo)
+    {
+        options.export.set_path(crate::config::consts::DEFAULT_TEAM_INFO_FILE);
+    }
+    // Injury Victims: default filename "injury_victims"
+    if matches!(page, PageKind::InjuryVictims)
+        && options.export.is_fully_default_for(PageKind::InjuryVictims)
+    {
+        options.export.set_path(crate::config::consts::DEFAULT_INJURY_VICTIMS_FILE);
     }
+    // Injury Summary: default filename "injury_summary"
+    if matches!(page, PageKind::InjurySummary)
+        && options.export.is_fully_default_for(PageKind::InjurySummary)
+    {
+        options.export.set_path(crate::config::consts::DEFAULT_INJURY_SUMMARY_FILE);
+    }
+    // Player detail: needs an id; default filename "player_<id>"
+    if matches!(page, PageKind::Player) {
+        let id = options.scrape.player_id.ok_or("--page player needs --player-id <id>")?;
+        if options.export.is_fully_default_for(PageKind::Player) {
+            options.export.set_path(&format!("player_{}", id));
+        }
+    }
+    Ok(())
+}
 
+/// Scrape one page, cache it and export it. Returns the files written.
+/// `dry_run`: neither cache nor export, just report what would be written.
+fn refresh_page(page: PageKind, options: &mut AppOptions, diff: bool, dry_run: bool) -> Result<Vec<PathBuf>, Box<dyn Error>> {
     // 1) SCRAPE
+    let cached = store::load_dataset(&page).ok();
+    let mut ds = with_history(page, cached.as_ref(), scrape_page(page, options)?);
+    derive_columns(page, &mut ds);
+
+    if diff {
+        print_diff(page, options, cached.as_ref(), &ds);
+    }
+
+    // 2) Cache the dataset (best-effort), keeping the old one for --restore-previous
+    if !dry_run {
+        keep_previous(page, cached.as_ref());
+        if store::save_dataset(&page, &ds).is_ok() { let _ = store::mark_scraped(&page); }
+        notify_changes(page, options, cached.as_ref(), &ds);
+        // Players' Out until follows the injury log
+        let players = (page == Injuries).then(|| store::load_dataset(&Players).ok()).flatten();
+        if let Some(mut players) = players.filter(|p| !p.rows.is_empty()) {
+            derive::apply(Players, &mut players, Some(&ds));
+            let _ = store::save_dataset(&Players, &players);
+        }
+    }
+
+    // 3) Export according to ExportOptions
+    export_page(page, options, &ds, dry_run)
+}
+
+/// `--from-cache`: export the cached dataset of one page, as `refresh_page`
+/// would after a scrape. Returns the files written.
+fn export_cached(page: PageKind, options: &mut AppOptions, dry_run: bool) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let ds = load_cached(page, options)?;
+    logf!("CLI: Export {} from cache (rows={})", page, ds.row_count());
+    export_page(page, options, &ds, dry_run)
+}
+
+/// Scrape one page, with CLI progress and page default headers filled in.
+fn scrape_page(page: PageKind, options: &AppOptions) -> Result<DataSet, Box<dyn Error>> {
+    if let Some(season) = store::selected_season() {
+        return Err(format!("Season {} is archived; the site only has the current season (use --from-cache)", season).into());
+    }
     let mut cp = CliProgress::default();
 
     let mut ds = match page {
@@ -64,25 +404,75 @@ pub fn run() -> Result<(), Box<dyn Error>> {
         GameResults => {
             let ds = scrape::collect_game_results(Some(&mut cp))?;
             if let Some(first) = ds.rows.get(0).and_then(|r| r.get(0)) {
-                if let Ok(season) = first.trim().parse::<u32>() { let _ = store::save_season(season); }
+                if let Ok(season) = first.trim().parse::<u32>() {
+                    if let Some(old) = store::load_season()?.filter(|&old| old < season && !store::season_dir(old).exists()) {
+                        eprintln!("Season {} began; season {} isn't archived (--archive-season {} before re-scraping the other pages)", season, old, old);
+                    }
+                    let _ = store::save_season(season);
+                }
             }
             ds
         },
         SeasonStats => todo!("CLI: SeasonStats scraper not implemented yet"),
         CareerStats => todo!("CLI: CareerStats scraper not implemented yet"),
-        Injuries => scrape::collect_injuries(Some(&mut cp))?,
+        Injuries => scrape::collect_injuries(&options.scrape, Some(&mut cp))?,
+        Standings => scrape::collect_standings(Some(&mut cp))?,
+        Transactions => scrape::collect_transactions(Some(&mut cp))?,
+        TeamSummary => scrape::collect_team_summary(Some(&mut cp))?,
+        Leaders => {
+            let top = options.scrape.leaders_top.unwrap_or_else(store::leaders_top);
+            scrape::collect_leaders(top, Some(&mut cp))?
+        }
+        ResultsTable => scrape::collect_results_table(Some(&mut cp))?,
+        ScheduleMetrics => scrape::collect_schedule_metrics(Some(&mut cp))?,
+        TeamInfo => scrape::collect_team_info(&options.scrape, Some(&mut cp))?,
+        InjuryVictims => scrape::collect_injury_victims(Some(&mut cp))?,
+        InjurySummary => scrape::collect_injury_summary(Some(&mut cp))?,
+        Player => {
+            let id = options.scrape.player_id.ok_or("--page player needs --player-id <id>")?;
+            scrape::collect_player(id, Some(&mut cp))?
+        }
     };
 
     // Align with GUI: if headers are missing, inject page defaults so exports include headers.
     inject_headers_for_cli(page, &mut ds);
+    let renamed = Aliases::load().apply(page, &mut ds);
+    if renamed > 0 {
+        logf!("CLI: {} player name(s) in {} renamed by alias", renamed, page);
+    }
+    Ok(ds)
+}
 
-    // 2) Cache the dataset (best-effort)
-    let _ = store::save_dataset(&page, &DataSet {
-        headers: ds.headers.clone(),
-        rows: ds.rows.clone(),
-    });
+/// Recompute the page's derived columns (Players' Out until from the
+/// cached Injuries).
+fn derive_columns(page: PageKind, ds: &mut DataSet) {
+    let injuries = match page {
+        Players => store::load_dataset(&Injuries).ok(),
+        _ => None,
+    };
+    if derive::apply(page, ds, injuries.as_ref()) {
+        logd!("CLI: Derived columns of {} recomputed", page);
+    }
+}
 
-    // 3) Export according to ExportOptions
+/// Transactions keep moves the site no longer lists, Team Info the teams
+/// not scraped this time, and Game Results the completed games a bad page
+/// shows blank: fold the scrape into the cached dataset with the page's
+/// merge. Other pages as scraped.
+fn with_history(page: PageKind, cached: Option<&DataSet>, ds: DataSet) -> DataSet {
+    match (page, cached) {
+        (Transactions | TeamInfo | GameResults, Some(old)) => {
+            let mut merged = old.clone();
+            crate::gui::router::page_for(&page).merge(&mut merged, ds);
+            merged
+        }
+        _ => ds,
+    }
+}
+
+/// Export a scraped dataset according to ExportOptions. Returns the files written.
+/// `dry_run`: print the files it would write instead (and return none).
+fn export_page(page: PageKind, options: &mut AppOptions, ds: &DataSet, dry_run: bool) -> Result<Vec<PathBuf>, Box<dyn Error>> {
     let export = &mut options.export;
 
     // Page-agnostic skip optional: players=#, results=match id
@@ -99,18 +489,31 @@ pub fn run() -> Result<(), Box<dyn Error>> {
     let (effective_export_type, team_col) = match page {
         Players => (export.export_type, Some(3usize)),
         GameResults => (export.export_type, None), // use two-column variant
+        Player | Standings | TeamSummary | Leaders | ResultsTable | ScheduleMetrics | TeamInfo | InjurySummary => (SingleFile, None),
         _ => (export.export_type, None),
     };
 
-    // Adjust headers/rows for Game Results when skipping optional match id
-    let (mut headers_to_write, mut rows_to_write) = (ds.headers.clone(), ds.rows.clone());
-    if matches!(page, PageKind::GameResults) && export.skip_optional {
-        if let Some(h) = &mut headers_to_write { if !h.is_empty() { h.pop(); } }
-        for r in &mut rows_to_write { if !r.is_empty() { r.pop(); } }
+    // The user's formula columns: exported, never cached
+    let mut ds = ds.clone();
+    derive::apply_user(page, &mut ds);
+    let (mut headers_to_write, mut rows_to_write) = without_optional(page, export, ds.headers, ds.rows);
+
+    if dry_run {
+        // Team columns as the per-team writers below use them
+        let team_cols = match page {
+            GameResults => vec![2, 5],
+            Injuries | InjuryVictims => vec![2, 8],
+            Transactions => vec![2, 6],
+            _ => vec![team_col.unwrap_or(0)],
+        };
+        print_dry_run(page, options, effective_export_type, &mut headers_to_write, &mut rows_to_write, &team_cols);
+        return Ok(Vec::new());
     }
 
     let written: Vec<PathBuf> = match effective_export_type {
         SingleFile => {
+            let divisions = file::divisions_for(&options.export);
+            file::add_division_columns(page, &mut headers_to_write, &mut rows_to_write, &divisions);
             file::write_export_single(options, &headers_to_write, &rows_to_write)
                 .map(|p| vec![p])?
         }
@@ -118,12 +521,21 @@ pub fn run() -> Result<(), Box<dyn Error>> {
             match page {
                 PageKind::Players => file::write_export_per_team(options, &headers_to_write, &rows_to_write, team_col.unwrap())?,
                 PageKind::GameResults => file::write_export_per_team_results(options, &headers_to_write, &rows_to_write, 2, 5)?,
-                PageKind::Injuries => file::write_export_per_team_results(options, &headers_to_write, &rows_to_write, 2, 8)?,
+                PageKind::Injuries | PageKind::InjuryVictims => file::write_export_per_team_results(options, &headers_to_write, &rows_to_write, 2, 8)?,
+                PageKind::Transactions => file::write_export_per_team_results(options, &headers_to_write, &rows_to_write, 2, 6)?,
                 _ => file::write_export_per_team(options, &headers_to_write, &rows_to_write, team_col.unwrap_or(0))?,
             }
         }
     };
 
+    if let Some(m) = crate::manifest::write_for_export(page, store::selected_season().or(store::load_season().ok().flatten()), &options.export, &written)? {
+        eprintln!("Manifest: {}", m.display());
+    }
+    let written = match effective_export_type {
+        PerTeam => file::zip_export(&options.export, &options.export.out_path(), written)?,
+        SingleFile => written,
+    };
+
     if written.is_empty() {
         eprintln!("Nothing to export.");
     } else if let Some(last) = written.last() {
@@ -132,12 +544,712 @@ pub fn run() -> Result<(), Box<dyn Error>> {
         eprintln!("Export done.");
     }
 
+    Ok(written)
+}
+
+/// `--dry-run`: what `export_page` would write, on stdout. Existing files
+/// that would be overwritten are marked.
+fn print_dry_run(
+    page: PageKind,
+    options: &AppOptions,
+    export_type: ExportType,
+    headers: &mut Option<Vec<String>>,
+    rows: &mut [Vec<String>],
+    team_cols: &[usize],
+) {
+    let divisions = file::divisions_for(&options.export);
+    let mark = |p: &std::path::Path| if p.exists() { "  (overwrites)" } else { "" };
+    println!("Dry run {}: {} row(s), {} format, nothing written", page, rows.len(), options.export.format.ext());
+    match export_type {
+        SingleFile => {
+            file::add_division_columns(page, headers, rows, &divisions);
+            let path = options.export.out_path();
+            let cols = headers.as_ref().map(|h| h.len()).or_else(|| rows.first().map(|r| r.len())).unwrap_or(0);
+            println!("  {} ({} rows, {} columns){}", path.display(), rows.len(), cols, mark(&path));
+        }
+        PerTeam => {
+            let files = file::planned_team_files(options, rows, team_cols, &divisions);
+            println!("  {} file(s) in {}", files.len(), options.export.out_path().display());
+            for (path, n) in &files {
+                println!("  {} ({} rows){}", path.display(), n, mark(path));
+            }
+        }
+    }
+    if options.export.write_manifest {
+        println!("  + manifest.json");
+    }
+}
+
+/// Headers and rows with the optional columns left out when skipping them:
+/// the match id of Game Results, the player id of Players.
+fn without_optional(
+    page: PageKind,
+    export: &ExportOptions,
+    mut headers: Option<Vec<String>>,
+    mut rows: Vec<Vec<String>>,
+) -> (Option<Vec<String>>, Vec<Vec<String>>) {
+    if matches!(page, PageKind::GameResults) && export.skip_optional {
+        if let Some(h) = &mut headers { if !h.is_empty() { h.pop(); } }
+        for r in &mut rows { if !r.is_empty() { r.pop(); } }
+    }
+    if matches!(page, PageKind::Players) && export.skip_optional {
+        file::drop_column_named(&mut headers, &mut rows, scrape::PLAYER_ID_HEADER);
+    }
+    (headers, rows)
+}
+
+/// `--show-cache <page>`: print the cached dataset to stdout in the export
+/// format (`-f`, `-x`, `-s`), narrowed to `-t`/`-i` teams when given. No requests.
+fn show_cache(page: PageKind, options: &AppOptions) -> Result<(), Box<dyn Error>> {
+    let ds = load_cached(page, options)?;
+    let mut options = options.clone();
+    options.scrape.page = page;
+    if options.export.skip_optional && matches!(page, PageKind::Players) {
+        options.export.keep_hash = false;
+    }
+    let (headers, rows) = without_optional(page, &options.export, ds.headers, ds.rows);
+    print!("{}", file::to_export_string(&options, &headers, &rows));
+    Ok(())
+}
+
+/// Keep the dataset a scrape is about to replace, for `--restore-previous`.
+fn keep_previous(page: PageKind, cached: Option<&DataSet>) {
+    let Some(old) = cached.filter(|ds| !ds.rows.is_empty()) else { return };
+    if let Err(e) = store::save_previous(&page, old) {
+        loge!("Cache: Keeping previous {:?} failed: {}", page, e);
+    }
+}
+
+/// `--import <page> <file>`: merge a third-party CSV/TSV (old season
+/// sheets and the like) into the page's cache, its columns mapped by name
+/// or `--map`, `--season` filling the season (and choosing the season's
+/// cache they go into). Rows with a cached key replace those rows. With
+/// `--dry-run` only reports what would change; otherwise
+/// `--restore-previous` undoes it.
+fn import_file(page: PageKind, path: &std::path::Path, flags: &CliFlags) -> Result<(), Box<dyn Error>> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let cached = store::load_dataset(&page).ok().filter(|ds| !ds.rows.is_empty());
+    let (imported, sources) = import::parse_mapped(page, &text, cached.as_ref(), &flags.map, flags.season)?;
+
+    println!("Import {} from {} ({} rows):", page, path.display(), imported.row_count());
+    for (target, source) in &sources {
+        match source {
+            import::Source::Column(c) => println!("  {:<16} ← column {}", target, c + 1),
+            import::Source::Value(v) => println!("  {:<16} = {}", target, v),
+            import::Source::Blank => println!("  {:<16} (blank)", target),
+        }
+    }
+
+    let base = cached.clone().unwrap_or_else(|| DataSet { headers: None, rows: Vec::new() });
+    let key = row_key_for(page, &base, &imported);
+    let mut merged = import::merge(&base, imported, &key);
+    derive_columns(page, &mut merged);
+    let diff = DataDiff::between(&base, &merged, &key);
+    println!("{}: {}", page, diff.summary());
+
+    if flags.dry_run {
+        println!("Dry run: the cache is unchanged");
+        return Ok(());
+    }
+    keep_previous(page, cached.as_ref());
+    let saved = store::save_dataset(&page, &merged)?;
+    logf!("CLI: Imported {} into {} ({}) → {}", path.display(), page, diff.summary(), saved.display());
+    Ok(())
+}
+
+/// `--restore-previous <page>`: undo the last scrape of a page by swapping
+/// the cache with the copy kept before it (running it again redoes the scrape).
+fn restore_previous(page: PageKind) -> Result<(), Box<dyn Error>> {
+    if !store::has_previous(&page) {
+        return Err(format!("No previous {} data to restore", page).into());
+    }
+    let ds = store::restore_previous(&page)?;
+    eprintln!("Restored {}: {} rows (run again to undo)", page, ds.row_count());
+    logf!("Cache: Restored previous {:?} ({} rows)", page, ds.row_count());
+    Ok(())
+}
+
+/// The cached dataset of a page with default headers filled in, narrowed
+/// to the `-t`/`-i` teams when given (a scrape would only fetch those).
+fn load_cached(page: PageKind, options: &AppOptions) -> Result<DataSet, Box<dyn Error>> {
+    let mut ds = store::load_dataset(&page).ok()
+        .filter(|ds| !ds.rows.is_empty())
+        .ok_or_else(|| format!("No cached data for {}; scrape it first", page))?;
+    inject_headers_for_cli(page, &mut ds);
+
+    if let Some(ids) = selected_ids(options) {
+        let teams = get_teams::list();
+        ds.rows = crate::gui::router::page_for(&page).filter_rows_for_selection(&ids, &teams, &ds.rows);
+    }
+    Ok(ds)
+}
+
+/// `--export-bundle <dir>`: every cached page into `dir`, one folder per
+/// page, with the export options given (team selection applies).
+fn export_bundle(dir: &std::path::Path, options: &AppOptions) -> Result<(), Box<dyn Error>> {
+    let datasets: Vec<(PageKind, DataSet)> = file::BUNDLE_PAGES.iter()
+        .filter_map(|&page| load_cached(page, options).ok().map(|mut ds| {
+            derive::apply_user(page, &mut ds);
+            (page, ds)
+        }))
+        .collect();
+    if datasets.is_empty() {
+        return Err("Nothing cached to bundle; scrape first".into());
+    }
+    let pages = datasets.iter().map(|(p, _)| p.to_string()).collect::<Vec<_>>().join(", ");
+    let written = file::export_bundle(options, dir, datasets)?;
+    match written.as_slice() {
+        [zip] if options.export.zip => eprintln!("Bundle: {} ({})", zip.display(), pages),
+        _ => eprintln!("Bundle: {} file(s) in {} ({})", written.len(), dir.display(), pages),
+    }
+    Ok(())
+}
+
+/// `--cache-info`: each page's cached rows, columns, time since it was
+/// scraped (flagged when stale) and parser version.
+fn print_cache_info() -> Result<(), Box<dyn Error>> {
+    let stale_after = store::stale_after_hours();
+    println!("Cache: {} (stale after {}h)", store::store_dir().display(), stale_after);
+    let seasons = store::seasons();
+    if seasons.len() > 1 || store::selected_season().is_some() {
+        let list: Vec<String> = seasons.iter().map(u32::to_string).collect();
+        match store::selected_season() {
+            Some(s) => println!("Season: {} (archived; seasons with data: {})", s, list.join(", ")),
+            None => println!("Season: current (seasons with data: {})", list.join(", ")),
+        }
+    }
+    println!("{:<16} {:>6} {:>5}  {:<16} Parser", "Page", "Rows", "Cols", "Scraped");
+    let now = SystemTime::now();
+    for page in ALL_PAGES.iter().copied().chain([Player]) {
+        let Some(at) = store::saved_at(&page).and_then(|_| store::scraped_at(&page)) else {
+            println!("{:<16} {:>6} {:>5}  {:<16} -", page.to_string(), "-", "-", "never");
+            continue;
+        };
+        let (rows, cols) = store::load_dataset(&page)
+            .map(|ds| (ds.row_count(), ds.header_count()))
+            .unwrap_or((0, 0));
+        let mut age = now.duration_since(at).map(fmt_age).unwrap_or_else(|_| s!("just now"));
+        if store::is_stale(at, stale_after) { age.push_str(" (stale)"); }
+        let parser = match store::CacheMeta::load(&page) {
+            Some(m) if m.is_outdated() => format!("{} (outdated)", m.parser_version),
+            Some(m) => m.parser_version.to_string(),
+            None => s!("?"),
+        };
+        println!("{:<16} {:>6} {:>5}  {:<16} {}", page.to_string(), rows, cols, age, parser);
+    }
+    Ok(())
+}
+
+/// `--verify-cache [--repair]`: each cached page's problems (see
+/// `integrity`); with `repair`, the damaged rows are dropped. Fails while
+/// any problem is left, so scripts can alert on it.
+fn verify_cache(repair: bool) -> Result<(), Box<dyn Error>> {
+    println!("Cache: {}", store::store_dir().display());
+    let mut left = 0usize;
+    for check in integrity::check_cache() {
+        println!("{:<16} {}", check.kind.to_string(), check.summary());
+        for p in &check.problems {
+            println!("  {}", p);
+        }
+        let mut remaining = check.problems.len();
+        if repair && check.repairable() > 0 {
+            let dropped = integrity::repair(check.kind)?;
+            println!("  Repaired: dropped {} row(s); --restore-previous {} undoes it", dropped, check.kind);
+            remaining = remaining.saturating_sub(dropped);
+        }
+        left += remaining;
+    }
+    match left {
+        0 => Ok(()),
+        n if repair => Err(format!("{} problem(s) left that need a re-scrape", n).into()),
+        n => Err(format!("{} problem(s) in the cache (--repair drops the damaged rows)", n).into()),
+    }
+}
+
+/// `--watch`: scrape every `every` until interrupted. Exports (and updates
+/// the cache) only when the scraped data differs from the cached dataset.
+/// A failed cycle is logged and retried on the next one.
+fn watch(page: PageKind, options: &mut AppOptions, every: Duration, diff: bool) -> Result<(), Box<dyn Error>> {
+    eprintln!("Watching {} every {}s (Ctrl+C to stop)", page, every.as_secs());
+    logf!("Watch: Start page={} every={}s", page, every.as_secs());
+
+    let mut cached = store::load_dataset(&page).ok();
+    let mut last = cached.as_ref().map(|ds| ds.content_hash());
+    let mut cycle: u64 = 0;
+
+    loop {
+        cycle += 1;
+        let started = Instant::now();
+
+        let summary = match scrape_page(page, options).map(|ds| with_history(page, cached.as_ref(), ds)) {
+            Ok(ds) if last == Some(ds.content_hash()) => {
+                format!("unchanged ({} rows)", ds.row_count())
+            }
+            Ok(ds) => match export_page(page, options, &ds, false) {
+                Ok(written) => {
+                    if diff { print_diff(page, options, cached.as_ref(), &ds); }
+                    keep_previous(page, cached.as_ref());
+                    match store::save_dataset(&page, &ds) {
+                        Ok(_) => { let _ = store::mark_scraped(&page); }
+                        Err(e) => loge!("Watch: Cache save failed: {}", e),
+                    }
+                    last = Some(ds.content_hash());
+                    let msg = format!("changed ({} rows), exported {} file(s)", ds.row_count(), written.len());
+                    cached = Some(ds);
+                    msg
+                }
+                Err(e) => format!("changed, export failed: {}", e),
+            },
+            Err(e) => format!("scrape failed: {}", e),
+        };
+
+        eprintln!("[watch #{}] {}: {} in {:.1}s", cycle, page, summary, started.elapsed().as_secs_f32());
+        logf!("Watch: Cycle {} page={} {}", cycle, page, summary);
+
+        thread::sleep(every.saturating_sub(started.elapsed()));
+    }
+}
+
+/// Row key for change reports; Teams isn't a GUI page, so it's matched on whole rows.
+/// Player detail rows are keyed on (id, section, field).
+fn row_key_for(page: PageKind, old: &DataSet, new: &DataSet) -> Vec<usize> {
+    match page {
+        Teams => Vec::new(),
+        Player => vec![0, 1, 2],
+        _ => crate::gui::pages::row_key_between(crate::gui::router::page_for(&page), old, new),
+    }
+}
+
+/// `--print-week`: print-friendly HTML of one week's fixtures/results from
+/// the Game Results cache (scraped if there is none; latest season), plus a
+/// text copy on stdout.
+/// Written to `out/results/week_<n>.html`, or `-o` with an .html extension.
+fn print_week(options: &AppOptions, week: u32) -> Result<(), Box<dyn Error>> {
+    let ds = match store::load_dataset(&GameResults) {
+        Ok(ds) if !ds.rows.is_empty() => ds,
+        _ => {
+            let ds = scrape_page(GameResults, options)?;
+            if store::save_dataset(&GameResults, &ds).is_ok() { let _ = store::mark_scraped(&GameResults); }
+            ds
+        }
+    };
+    let sched = crate::schedule::WeekSchedule::from_results(&ds, None, week);
+    if sched.fixtures.is_empty() {
+        let have = crate::schedule::weeks(&ds, sched.season);
+        return Err(format!("No games in week {} (weeks with games: {:?})", week, have).into());
+    }
+
+    let path = if options.export.is_fully_default_for(options.scrape.page) {
+        ExportOptions::default_dir_for(GameResults).join(format!("week_{}.html", week))
+    } else {
+        options.export.out_path().with_extension("html")
+    };
+    if let Some(dir) = path.parent() { std::fs::create_dir_all(dir)?; }
+    file::write_atomic(&path, sched.to_html().as_bytes(), options.export.backup)?;
+
+    print!("{}", sched.to_text());
+    eprintln!("Print view: {}", path.display());
+    Ok(())
+}
+
+/// `--plan`: list the URLs the scrape would request, with the estimated
+/// throttle time. No network IO.
+fn print_plan(page: PageKind, options: &AppOptions) -> Result<(), Box<dyn Error>> {
+    let plan = scrape::plan(page, &options.scrape)?;
+    println!("Plan {}: {} request(s), {} worker(s)", page, plan.urls.len(), plan.workers);
+    for url in &plan.urls {
+        println!("  GET {}", url);
+    }
+    println!("Estimated throttle time: {:.1}s (plus network time)", plan.throttle.as_secs_f32());
+    println!("Would export to: {}", options.export.out_path().display());
+    Ok(())
+}
+
+/// `--health-check`: one line per page and check; an error (non-zero exit)
+/// when any anchor is missing, as the site format has likely changed.
+fn health_check() -> Result<(), Box<dyn Error>> {
+    let report = scrape::health::check_all();
+    for page in &report {
+        println!("{} {} ({})", if page.is_ok() { "OK  " } else { "FAIL" }, page.page, page.path);
+        for c in &page.checks {
+            let mark = if c.ok { "ok" } else { "MISSING" };
+            match c.note.as_str() {
+                "" => println!("    {:<7} {}", mark, c.what),
+                note => println!("    {:<7} {}: {}", mark, c.what, note),
+            }
+        }
+    }
+    let failed: Vec<String> = report.iter().filter(|p| !p.is_ok()).map(|p| p.page.to_string()).collect();
+    logf!("CLI: Health check: {} of {} page(s) failed", failed.len(), report.len());
+    if !failed.is_empty() {
+        return Err(format!("site format may have changed: {}", failed.join(", ")).into());
+    }
     Ok(())
 }
 
+/// `--capture`: fetch the pages the scrape would request and save the raw
+/// HTML, untouched, as `fixtures/<page>/<unix time>-<path>.html`. Nothing is
+/// parsed, cached or exported.
+fn capture(page: PageKind, options: &AppOptions) -> Result<(), Box<dyn Error>> {
+    let plan = scrape::plan(page, &options.scrape)?;
+    if plan.urls.is_empty() {
+        return Err(format!("{} is computed from cached pages; capture those instead", page).into());
+    }
+    let dir = PathBuf::from(consts::DEFAULT_FIXTURES_DIR).join(page.to_string());
+    std::fs::create_dir_all(&dir)?;
+    let stamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let base = net::full_url("");
+    let mut failed = 0;
+    for (i, url) in plan.urls.iter().enumerate() {
+        if i > 0 {
+            thread::sleep(net::settings().delay());
+        }
+        let path = url.strip_prefix(&base).unwrap_or(url);
+        let body = match net::http_get_bytes(path) {
+            Ok(b) => b,
+            Err(e) => {
+                eprintln!("  {}: {}", url, e);
+                failed += 1;
+                continue;
+            }
+        };
+        let out = dir.join(format!("{}-{}.html", stamp, fixture_name(path)));
+        file::write_atomic(&out, &body, Backup::Off)?;
+        logf!("CLI: Captured {} → {}", url, out.display());
+        println!("{} ({} bytes)", out.display(), body.len());
+    }
+
+    match failed {
+        0 => Ok(()),
+        n => Err(format!("{} of {} request(s) failed", n, plan.urls.len()).into()),
+    }
+}
+
+/// File-name part of a captured site path: `/team.php?i=3` → `team_i_3`.
+fn fixture_name(path: &str) -> String {
+    let name: String = path
+        .trim_start_matches('/')
+        .replacen(".php", "", 1)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    match name.trim_matches('_') {
+        "" => s!("index"),
+        n => n.to_string(),
+    }
+}
+
+/// `--diff`: report to stdout what changed versus the cached dataset.
+fn print_diff(page: PageKind, options: &AppOptions, old: Option<&DataSet>, new: &DataSet) {
+    let Some(old) = old else {
+        println!("Diff {}: no cached data to compare against", page);
+        return;
+    };
+    let d = scrape_diff(page, options, old, new);
+    println!("Diff {}: {}", page, d.summary());
+    for line in d.report_lines() {
+        println!("  {}", line);
+    }
+}
+
+/// Team ids the scrape options select (`-t`, `--team-name`, `--division`,
+/// …); None for all teams.
+fn selected_ids(options: &AppOptions) -> Option<Vec<u32>> {
+    match &options.scrape.teams {
+        TeamSelector::All => None,
+        TeamSelector::One(id) => Some(vec![*id]),
+        TeamSelector::Ids(ids) => Some(ids.clone()),
+    }
+}
+
+/// What a scrape changed, among the teams it covered: a scrape of some
+/// teams says nothing about the others, so their cached rows aren't
+/// "removed" (nor, after a merge, "added").
+fn scrape_diff(page: PageKind, options: &AppOptions, old: &DataSet, new: &DataSet) -> DataDiff {
+    let ids = selected_ids(options);
+    let teams = if ids.is_some() { get_teams::list() } else { Vec::new() };
+    diff_within(page, ids.as_deref(), &teams, old, new)
+}
+
+/// `DataDiff` of the rows of teams `ids` (None: every row).
+fn diff_within(page: PageKind, ids: Option<&[u32]>, teams: &[(u32, String)], old: &DataSet, new: &DataSet) -> DataDiff {
+    let narrow = |ds: &DataSet| ids.map(|ids| DataSet {
+        headers: ds.headers.clone(),
+        rows: crate::gui::router::page_for(&page).filter_rows_for_selection(ids, teams, &ds.rows),
+    });
+    let (narrow_old, narrow_new) = (narrow(old), narrow(new));
+    let (old, new) = (narrow_old.as_ref().unwrap_or(old), narrow_new.as_ref().unwrap_or(new));
+    DataDiff::between(old, new, &row_key_for(page, old, new))
+}
+
+/// Webhook summary of what a scrape changed, when a webhook is set
+/// (nothing to compare on a first scrape).
+fn notify_changes(page: PageKind, options: &AppOptions, old: Option<&DataSet>, new: &DataSet) {
+    let Some(old) = old else { return };
+    if notify::webhook_url().is_none() { return; }
+    let d = scrape_diff(page, options, old, new);
+    if notify::notify(page, &d) {
+        eprintln!("Webhook: {} ({})", page, d.summary());
+    }
+}
+
+/// Parse a `--watch` interval: plain number = minutes, or with an s/m/h suffix.
+fn parse_interval(s: &str) -> Result<Duration, Box<dyn Error>> {
+    let s = s.trim().to_ascii_lowercase();
+    let (num, unit) = match s.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        Some((i, _)) => s.split_at(i),
+        None => (s.as_str(), "m"),
+    };
+    let n: u64 = num.parse().map_err(|_| format!("Invalid interval: {}", s))?;
+    let secs = match unit.trim() {
+        "s" => n,
+        "m" | "min" => n.checked_mul(60).ok_or("Interval too large")?,
+        "h" => n.checked_mul(3600).ok_or("Interval too large")?,
+        other => return Err(format!("Unknown interval unit: {}", other).into()),
+    };
+    let d = Duration::from_secs(secs);
+    if d < MIN_WATCH {
+        return Err(format!("Watch interval must be at least {}s", MIN_WATCH.as_secs()).into());
+    }
+    Ok(d)
+}
+
+
+/// `extract`: dump whatever a CSS-like selector matches on a site page (or a
+/// saved HTML file) as a delimited table, to prototype new page extractions.
+fn run_extract(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let (mut url, mut html_file, mut selector, mut out) = (None, None, None, None);
+    let mut format = ExportFormat::Tsv;
+
+    while let Some(a) = args.next() {
+        match a.as_str() {
+            "-u" | "--url" => url = Some(args.next().ok_or("Missing value for --url")?),
+            "--file" => html_file = Some(args.next().ok_or("Missing value for --file")?),
+            "-s" | "--selector" => selector = Some(args.next().ok_or("Missing value for --selector")?),
+            "-f" | "--format" => {
+                let v = args.next().ok_or("Missing value for --format")?;
+                format = ExportFormat::from_str(&v)?;
+            }
+            "-o" | "--out" => out = Some(PathBuf::from(args.next().ok_or("Missing output path")?)),
+            _ => return Err(format!("Unknown extract arg: {}", a).into()),
+        }
+    }
+
+    let selector = Selector::parse(&selector.ok_or("extract: --selector is required")?)?;
+    let html = match (url, html_file) {
+        (Some(u), None) => net::http_get(&u)?,
+        (None, Some(f)) => std::fs::read_to_string(f)?,
+        _ => return Err("extract: give exactly one of --url <path> or --file <html>".into()),
+    };
 
-fn parse_cli(app_state: &mut AppState) -> Result<(), Box<dyn Error>> {
+    let rows = select_rows(&html, &selector);
+    let mut buf: Vec<u8> = Vec::new();
+    match format.delimiter() {
+        Some(sep) => for r in &rows { file::write_row(&mut buf, r, sep)?; },
+        None => {
+            let cells: Vec<Vec<&str>> = rows.iter().map(|r| r.iter().map(String::as_str).collect()).collect();
+            buf = file::markup_table(format, None, &cells).into_bytes();
+        }
+    }
+
+    match out {
+        Some(p) => {
+            file::write_atomic(&p, &buf, Backup::Off)?;
+            eprintln!("Extracted {} row(s) → {}", rows.len(), p.display());
+        }
+        None => {
+            std::io::Write::write_all(&mut std::io::stdout(), &buf)?;
+            eprintln!("Extracted {} row(s)", rows.len());
+        }
+    }
+    Ok(())
+}
+
+/// `schema` subcommand: column reference for every page (or one) as
+/// Markdown (default) or JSON, to stdout or `-o <file>`.
+fn run_schema(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let (mut json, mut page, mut out) = (false, None, None);
+
+    while let Some(a) = args.next() {
+        match a.as_str() {
+            "-f" | "--format" => {
+                json = match args.next().ok_or("Missing value for --format")?.to_ascii_lowercase().as_str() {
+                    "md" | "markdown" => false,
+                    "json" => true,
+                    other => return Err(format!("Unknown schema format: {} (md|json)", other).into()),
+                };
+            }
+            "-p" | "--page" => {
+                let v = args.next().ok_or("Missing value for --page")?;
+                page = Some(PageKind::from_str(&v)?);
+            }
+            "-o" | "--out" => out = Some(PathBuf::from(args.next().ok_or("Missing output path")?)),
+            _ => return Err(format!("Unknown schema arg: {}", a).into()),
+        }
+    }
+
+    let pages: Vec<schema::PageSchema> = match page {
+        Some(k) => vec![*schema::for_page(k).ok_or_else(|| format!("No schema for page {}", k))?],
+        None => schema::registry().to_vec(),
+    };
+    let user = store::user_columns();
+    let text = if json { schema::to_json(&pages, &user) } else { schema::to_markdown(&pages, &user) };
+
+    match out {
+        Some(p) => {
+            file::write_atomic(&p, text.as_bytes(), Backup::Off)?;
+            eprintln!("Schema → {}", p.display());
+        }
+        None => print!("{}", text),
+    }
+    Ok(())
+}
+
+/// `verify`: spot-check the cache against the site. Takes `--sample N`
+/// rows (default 5) of each cached page (or `-p` pages), re-fetches only the
+/// pages those rows came from, and reports rows missing or different.
+/// Fails when anything mismatched, so scripts can alert on it.
+fn run_verify(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let (mut n, mut pages, mut sampler) = (5usize, Vec::new(), Sampler::from_clock());
+
+    while let Some(a) = args.next() {
+        match a.as_str() {
+            "-n" | "--sample" => {
+                n = args.next().ok_or("Missing value for --sample")?.parse()?;
+                if n == 0 { return Err("--sample must be at least 1".into()); }
+            }
+            "-p" | "--page" => pages = parse_pages(&args.next().ok_or("Missing value for --page")?)?,
+            "--seed" => sampler = Sampler::new(args.next().ok_or("Missing value for --seed")?.parse()?),
+            _ => return Err(format!("Unknown verify arg: {}", a).into()),
+        }
+    }
+    if pages.is_empty() {
+        // Transactions keep moves the site has dropped; those would all read as
+        // missing. The derived pages aren't on the site at all.
+        pages = ALL_PAGES.iter().copied().filter(|p| !matches!(p, Transactions | TeamSummary | Leaders | InjuryVictims | InjurySummary | ResultsTable | ScheduleMetrics)).chain([Player]).collect();
+    }
+
+    let mut mismatches = 0usize;
+    for page in pages {
+        let cached = match store::load_dataset(&page) {
+            Ok(ds) if !ds.rows.is_empty() => ds,
+            _ => {
+                println!("{}: no cached data, skipped", page);
+                continue;
+            }
+        };
+        let sample = sampler.pick(cached.rows.len(), n);
+        let fresh = match fetch_for_sample(page, &cached, &sample) {
+            Ok(ds) => ds,
+            Err(e) => {
+                println!("{}: fetch failed: {}", page, e);
+                mismatches += 1;
+                continue;
+            }
+        };
+        let check = verify::check_sample(&cached, &fresh, &sample, &row_key_for(page, &cached, &fresh));
+        println!("{}: {}", page, check.summary());
+        for line in check.diff.report_lines() {
+            println!("  {}", line);
+        }
+        logf!("Verify: page={} {}", page, check.summary());
+        mismatches += check.mismatches();
+    }
+
+    match mismatches {
+        0 => Ok(()),
+        m => Err(format!("{} mismatch(es) between cache and site", m).into()),
+    }
+}
+
+/// `bench-injuries`: time each injuries parser on a saved injury page
+/// (`--capture injuries` writes one) and check the fast ones still match
+/// the slow reference row for row. Team names come from the cached team
+/// list. Fails on any disagreement, so a regression shows up in scripts.
+fn run_bench_injuries(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let (mut html_file, mut runs) = (None, 20usize);
+
+    while let Some(a) = args.next() {
+        match a.as_str() {
+            "--file" => html_file = Some(args.next().ok_or("Missing value for --file")?),
+            "-n" | "--runs" => {
+                runs = args.next().ok_or("Missing value for --runs")?.parse()?;
+                if runs == 0 { return Err("--runs must be at least 1".into()); }
+            }
+            _ => return Err(format!("Unknown bench-injuries arg: {}", a).into()),
+        }
+    }
+
+    let doc = std::fs::read_to_string(html_file.ok_or("bench-injuries: --file <html> is required")?)?;
+    let teams = get_teams::cached().unwrap_or_default();
+    if teams.is_empty() {
+        eprintln!("No cached team list; team names fall back to word splitting (scrape teams first)");
+    }
+
+    let reference = scrape::injuries::parse_doc_with(InjuryParser::Slow, &doc, "", &teams);
+    let mut disagree = Vec::new();
+    for parser in InjuryParser::ALL {
+        let mut times = Vec::with_capacity(runs);
+        let mut rows = Vec::new();
+        for _ in 0..runs {
+            let t = Instant::now();
+            rows = scrape::injuries::parse_doc_with(parser, &doc, "", &teams);
+            times.push(t.elapsed());
+        }
+        times.sort_unstable();
+        let same = rows == reference;
+        if !same {
+            disagree.push(parser.to_string());
+        }
+        println!("{:<9} {:>5} rows  min {:>9.3?}  median {:>9.3?}  {}",
+            parser, rows.len(), times[0], times[runs / 2], if same { "matches slow" } else { "DIFFERS from slow" });
+    }
+
+    if !disagree.is_empty() {
+        return Err(format!("parser output differs from slow: {}", disagree.join(", ")).into());
+    }
+    Ok(())
+}
+
+/// Fresh data covering the `sample` rows of `cached`: the rosters or team
+/// pages of just the sampled teams, the cached player's sheet, or the one
+/// page the other pages come from.
+fn fetch_for_sample(page: PageKind, cached: &DataSet, sample: &[usize]) -> Result<DataSet, Box<dyn Error>> {
+    let mut options = AppOptions::default();
+    let col = |c: usize| -> Vec<&str> {
+        sample.iter().filter_map(|&i| cached.rows.get(i)?.get(c).map(String::as_str)).collect()
+    };
+    match page {
+        Players => {
+            let names = col(3);
+            let ids: Vec<u32> = get_teams::list_or_scrape().into_iter()
+                .filter(|(_, name)| names.contains(&name.as_str()))
+                .map(|(id, _)| id)
+                .collect();
+            if ids.is_empty() {
+                return Err("sampled players' teams are not in the team list".into());
+            }
+            options.scrape.teams = TeamSelector::Ids(ids);
+        }
+        Player => {
+            let id = col(0).first().and_then(|s| s.parse().ok()).ok_or("cached player has no id")?;
+            options.scrape.player_id = Some(id);
+        }
+        TeamInfo => {
+            options.scrape.teams = TeamSelector::Ids(col(0).iter().filter_map(|s| s.parse().ok()).collect());
+        }
+        _ => {}
+    }
+    scrape_page(page, &options)
+}
+
+fn parse_cli(app_state: &mut AppState) -> Result<CliFlags, Box<dyn Error>> {
     let mut args = env::args().skip(1);
+    let mut flags = CliFlags::default();
 
     // IMPORTANT: mutate the real structs, not copies
     let export = &mut app_state.options.export;
@@ -145,13 +1257,18 @@ fn parse_cli(app_state: &mut AppState) -> Result<(), Box<dyn Error>> {
 
     while let Some(a) = args.next() {
         match a.as_str() {
+            "-V" | "--version" => {
+                println!("bb_scrape {} (parser {})", env!("CARGO_PKG_VERSION"), crate::config::consts::PARSER_VERSION);
+                std::process::exit(0);
+            }
+
             "-h" | "--help" => {
                 eprintln!(include_str!("cli_help.txt"));
                 std::process::exit(0);
             }
 
             "-l" | "--list-teams" => {
-                for (id, name) in scrape::list_teams() {
+                for (id, name) in get_teams::list_or_scrape() {
                     println!("{:2}  {}", id, name);
                 }
                 std::process::exit(0);
@@ -159,7 +1276,8 @@ fn parse_cli(app_state: &mut AppState) -> Result<(), Box<dyn Error>> {
 
             "-p" | "--page" => {
                 let v = args.next().ok_or("Missing value for --page")?;
-                scrape.page = PageKind::from_str(&v)?;
+                flags.pages = parse_pages(&v)?;
+                scrape.page = flags.pages[0];
             }
 
             "-t" | "--team" => {
@@ -168,6 +1286,52 @@ fn parse_cli(app_state: &mut AppState) -> Result<(), Box<dyn Error>> {
                 scrape.teams.add(v);
             }
 
+            "--team-name" => {
+                let name = args.next().ok_or("Missing value for --team-name")?;
+                scrape.teams.add(get_teams::find(&get_teams::list_or_scrape(), &name)?);
+            }
+
+            "--teams-preset" => {
+                let name = args.next().ok_or("Missing value for --teams-preset")?;
+                let presets = store::team_presets();
+                let ids = presets.get(name.trim()).ok_or_else(|| {
+                    let saved: Vec<&str> = presets.keys().map(String::as_str).collect();
+                    format!("Unknown team preset: {} (saved: {})", name, if saved.is_empty() { s!("none") } else { saved.join(", ") })
+                })?;
+                scrape.teams.extend(ids.iter().copied());
+            }
+
+            "--division" => {
+                let name = args.next().ok_or("Missing value for --division")?;
+                let groups = get_teams::by_division(&get_teams::list_or_scrape(), &get_teams::divisions());
+                let ids = groups.iter()
+                    .find(|(div, _)| div.eq_ignore_ascii_case(name.trim()))
+                    .map(|(_, ids)| ids)
+                    .ok_or_else(|| if groups.is_empty() {
+                        s!("No divisions in the team list; scrape teams first (-p teams)")
+                    } else {
+                        format!("Unknown division: {} (known: {})", name, groups.keys().cloned().collect::<Vec<_>>().join(", "))
+                    })?;
+                scrape.teams.extend(ids.iter().copied());
+            }
+
+            "--logos" => { scrape.download_logos = true; }
+            "--injury-parser" => {
+                let v = args.next().ok_or("Missing value for --injury-parser")?;
+                scrape.injury_parser = InjuryParser::from_str(&v)?;
+            }
+
+            "--top" => {
+                let v: usize = args.next().ok_or("Missing value for --top")?.parse()?;
+                if v == 0 { return Err("--top must be at least 1".into()); }
+                scrape.leaders_top = Some(v);
+            }
+
+            "--player-id" => {
+                let v: u32 = args.next().ok_or("Missing value for --player-id")?.parse()?;
+                scrape.player_id = Some(v);
+            }
+
             "-i" | "--ids" => {
                 let v = args.next().ok_or("Missing value for --ids")?;
                 let list = parse_ids_list(&v)?;
@@ -177,6 +1341,7 @@ fn parse_cli(app_state: &mut AppState) -> Result<(), Box<dyn Error>> {
             "-o" | "--out" => {
                 let path = args.next().ok_or("Missing output path")?;
                 export.set_path(&path);
+                flags.out = true;
             }
 
             "-f" | "--format" => {
@@ -187,15 +1352,143 @@ fn parse_cli(app_state: &mut AppState) -> Result<(), Box<dyn Error>> {
             "-s" | "--skip-optional" => { export.skip_optional = true; }
             "-x" | "--drop-headers" => { export.include_headers = false; }
             "-m" | "--multi" | "--per-team" => { export.export_type = PerTeam; }
+            "--manifest" => { export.write_manifest = true; }
+            "--by-division" => { export.by_division = true; }
+            "--backup" => { export.backup = Backup::Bak; }
+            "--zip" => { export.zip = true; }
+            "--sort-by" => {
+                let v = args.next().ok_or("Missing column for --sort-by")?;
+                export.sort_by = Some(v);
+            }
+            "--sort-desc" => { export.sort_descending = true; }
+            "--decimal-sep" => {
+                let v = args.next().ok_or("Missing separator for --decimal-sep")?;
+                export.number_format.decimal = NumberFormat::parse_separator(&v)?
+                    .ok_or("--decimal-sep needs a separator")?;
+            }
+            "--thousands-sep" => {
+                let v = args.next().ok_or("Missing separator for --thousands-sep")?;
+                export.number_format.thousands = NumberFormat::parse_separator(&v)?;
+            }
+            "--append" => { export.append = true; }
+            "--delimiter" => {
+                let v = args.next().ok_or("Missing character for --delimiter")?;
+                export.custom_delimiter = Some(ExportOptions::parse_delimiter(&v)?);
+            }
+            "--escape-formulas" => { export.formula_escape = FormulaEscape::Always; }
+            "--no-escape-formulas" => { export.formula_escape = FormulaEscape::Off; }
+            "--chronological" => { export.chronological = true; }
+            "--backup-timestamp" => { export.backup = Backup::Timestamped; }
+            "-d" | "--diff" => { flags.diff = true; }
+            "--plan" => { flags.plan = true; }
+            "--cache-info" => { flags.cache_info = true; }
+            "--diagnostics" => { flags.diagnostics = true; }
+            "--verify-cache" => { flags.verify_cache = true; }
+            "--repair" => { flags.repair = true; }
+            "--from-cache" | "--no-scrape" => { flags.from_cache = true; }
+            "--dry-run" => { flags.dry_run = true; }
+            // Taken before parsing (`data_dir_arg`)
+            "--data-dir" => { args.next(); }
+            "--snapshots" => {
+                let v = args.next().ok_or("Missing value for --snapshots")?;
+                flags.snapshots = Some(v.parse().map_err(|_| format!("Invalid snapshot count: {}", v))?);
+            }
+
+            "--show-cache" => {
+                let v = args.next().ok_or("Missing value for --show-cache")?;
+                flags.show_cache = Some(PageKind::from_str(&v)?);
+            }
+            "--import" => {
+                let page = args.next().ok_or("Missing page for --import")?;
+                let file = args.next().ok_or("Missing file for --import <page> <file>")?;
+                flags.import = Some((PageKind::from_str(&page)?, PathBuf::from(file)));
+            }
+            "--season" => {
+                let v = args.next().ok_or("Missing value for --season")?;
+                flags.season = Some(v.parse().map_err(|_| format!("Invalid season: {}", v))?);
+            }
+            "--archive-season" => {
+                let v = args.next().ok_or("Missing value for --archive-season")?;
+                flags.archive_season = Some(v.parse().map_err(|_| format!("Invalid season: {}", v))?);
+            }
+            "--map" => {
+                let v = args.next().ok_or("Missing value for --map")?;
+                flags.map.extend(import::parse_mapping(&v)?);
+            }
+            "--map-file" => {
+                let v = args.next().ok_or("Missing value for --map-file")?;
+                let text = std::fs::read_to_string(&v).map_err(|e| format!("{}: {}", v, e))?;
+                flags.map.extend(import::parse_mapping(&text)?);
+            }
+            "--restore-previous" => {
+                let v = args.next().ok_or("Missing value for --restore-previous")?;
+                flags.restore_previous = Some(PageKind::from_str(&v)?);
+            }
+            "--health-check" => { flags.health_check = true; }
+            "--webhook" => {
+                let v = args.next().ok_or("Missing value for --webhook")?;
+                notify::set_webhook_url(&v);
+            }
+            "--serve" => {
+                let v = args.next().ok_or("Missing value for --serve (a port, e.g. 8080)")?;
+                flags.serve = Some(crate::serve::bind_addr(&v));
+            }
+            "--export-bundle" => {
+                let v = args.next().ok_or("Missing value for --export-bundle")?;
+                flags.bundle = Some(PathBuf::from(v));
+            }
+            "--capture" => {
+                let v = args.next().ok_or("Missing value for --capture")?;
+                flags.capture = Some(PageKind::from_str(&v)?);
+            }
+
+            "--print-week" => {
+                let v: u32 = args.next().ok_or("Missing value for --print-week")?.parse()?;
+                flags.print_week = Some(v);
+            }
+
+            "-w" | "--watch" => {
+                let v = args.next().ok_or("Missing value for --watch")?;
+                flags.watch = Some(parse_interval(&v)?);
+            }
 
             _ => return Err(format!("Unknown arg: {}", a).into()),
         }
     }
 
+    if flags.repair && !flags.verify_cache {
+        return Err("--repair goes with --verify-cache".into());
+    }
+    if flags.import.is_none() && !flags.map.is_empty() {
+        return Err("--map and --map-file go with --import".into());
+    }
+
+    let nf = export.number_format;
+    if nf.thousands == Some(nf.decimal) {
+        return Err(format!("--decimal-sep and --thousands-sep are both {:?}", nf.decimal).into());
+    }
+
     // Sort and dedup
     scrape.teams.normalize();
 
-    Ok(())
+    Ok(flags)
+}
+
+/// `--page` value: one page, a comma-separated list, or `all` (`ALL_PAGES`).
+/// Duplicates are dropped, first occurrence wins.
+fn parse_pages(s: &str) -> Result<Vec<PageKind>, Box<dyn Error>> {
+    if s.trim().eq_ignore_ascii_case("all") {
+        return Ok(ALL_PAGES.to_vec());
+    }
+    let mut out: Vec<PageKind> = Vec::new();
+    for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let page = PageKind::from_str(part)?;
+        if !out.contains(&page) { out.push(page); }
+    }
+    if out.is_empty() {
+        return Err("Missing value for --page".into());
+    }
+    Ok(out)
 }
 
 fn parse_ids_list(s: &str) -> Result<Vec<u32>, Box<dyn Error>> {
@@ -220,13 +1513,9 @@ fn parse_ids_list(s: &str) -> Result<Vec<u32>, Box<dyn Error>> {
     Ok(out)
 }
 
-/// Fill headers from page defaults when the scraper returns None, mirroring the GUI behavior.
+/// Fill headers from the page schema when the scraper returns None, mirroring the GUI behavior.
 fn inject_headers_for_cli(kind: PageKind, ds: &mut DataSet) {
-    if ds.headers.is_some() { return; }
-    let page = crate::gui::router::page_for(&kind);
-    if let Some(hs) = page.default_headers() {
-        ds.headers = Some(hs.iter().map(|s| s.to_string()).collect());
-    }
+    crate::schema::inject_headers(kind, ds);
 }
 
 /* ---------- CLI progress ---------- */
@@ -256,6 +1545,15 @@ mod tests {
         assert_eq!(v3, vec![3,4,5]);
     }
 
+    #[test]
+    fn finds_data_dir_anywhere_in_the_arguments() {
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>().into_iter();
+        assert_eq!(data_dir_arg(args(&["bb", "-p", "players", "--data-dir", "/tmp/bb"])), Ok(Some(PathBuf::from("/tmp/bb"))));
+        assert_eq!(data_dir_arg(args(&["bb", "verify", "--data-dir", "d"])), Ok(Some(PathBuf::from("d"))));
+        assert_eq!(data_dir_arg(args(&["bb", "-p", "players"])), Ok(None));
+        assert!(data_dir_arg(args(&["bb", "--data-dir"])).is_err());
+    }
+
     // Keep the export gating logic equivalent to run() for testability.
     fn effective_export_for(page: PageKind, requested: ExportType) -> (ExportType, Option<usize>) {
         match page {
@@ -278,6 +1576,36 @@ mod tests {
         assert_eq!(col2, None);
     }
 
+    #[test]
+    fn watch_interval_units_and_minimum() {
+        assert_eq!(parse_interval("15").unwrap(), Duration::from_secs(15 * 60));
+        assert_eq!(parse_interval("90s").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_interval("2h").unwrap(), Duration::from_secs(7200));
+        assert!(parse_interval("10s").is_err()); // below minimum
+        assert!(parse_interval("5x").is_err());
+        assert!(parse_interval("soon").is_err());
+        assert!(parse_interval("9999999999999999h").is_err()); // overflows u64 seconds
+    }
+
+    #[test]
+    fn page_list_all_and_dedup() {
+        assert_eq!(parse_pages("players").unwrap(), vec![Players]);
+        assert_eq!(parse_pages("teams, game-results,teams").unwrap(), vec![Teams, GameResults]);
+        assert_eq!(parse_pages("ALL").unwrap(), ALL_PAGES.to_vec());
+        assert!(parse_pages(" , ").is_err());
+        assert!(parse_pages("players,nope").is_err());
+    }
+
+    #[test]
+    fn page_defaults_give_each_page_its_own_path() {
+        let mut players = AppOptions::default();
+        apply_page_defaults(Players, &mut players).unwrap();
+        let mut standings = AppOptions::default();
+        apply_page_defaults(Standings, &mut standings).unwrap();
+        assert_ne!(players.export.out_path(), standings.export.out_path());
+        assert!(standings.export.out_path().starts_with(ExportOptions::default_dir_for(Standings)));
+    }
+
     #[test]
     fn inject_headers_uses_page_defaults() {
         let mut ds = DataSet { headers: None, rows: vec![vec!["x".into()]] };
@@ -285,6 +1613,52 @@ mod tests {
         assert!(ds.headers.is_some());
         assert_eq!(ds.headers.as_ref().unwrap().get(0).map(|s| s.as_str()), Some("S"));
     }
+
+    #[test]
+    fn skip_optional_drops_match_and_player_ids() {
+        let mut export = ExportOptions::default();
+        let results = (Some(vec![s!("S"), s!("Match id")]), vec![vec![s!("7"), s!("123")]]);
+        assert_eq!(without_optional(GameResults, &export, results.0.clone(), results.1.clone()), results);
+
+        export.skip_optional = true;
+        assert_eq!(without_optional(GameResults, &export, results.0, results.1),
+            (Some(vec![s!("S")]), vec![vec![s!("7")]]));
+        let players = (Some(vec![s!("Name"), s!(scrape::PLAYER_ID_HEADER)]), vec![vec![s!("Al"), s!("11")]]);
+        assert_eq!(without_optional(Players, &export, players.0, players.1),
+            (Some(vec![s!("Name")]), vec![vec![s!("Al")]]));
+    }
+
+    #[test]
+    fn subset_scrape_diff_leaves_other_teams_alone() {
+        let headers = Some(["Name", "#", "Race", "Team", "TV", scrape::PLAYER_ID_HEADER].map(String::from).to_vec());
+        let player = |name: &str, team: &str, tv: &str, id: &str| vec![s!(name), s!("1"), s!("Orc"), s!(team), s!(tv), s!(id)];
+        let teams = vec![(1, s!("Reds")), (2, s!("Blues"))];
+        let cached = DataSet { headers: headers.clone(), rows: vec![player("Grim", "Reds", "100", "11"), player("Ann", "Blues", "90", "12")] };
+        // `-t 1`: only the Reds come back
+        let scraped = DataSet { headers, rows: vec![player("Grim", "Reds", "110", "11")] };
+
+        let d = diff_within(Players, Some(&[1]), &teams, &cached, &scraped);
+        assert!(d.removed.is_empty() && d.added.is_empty());
+        assert_eq!(d.changed.len(), 1);
+
+        // Without the selection the Blues would read as removed
+        assert_eq!(diff_within(Players, None, &teams, &cached, &scraped).removed.len(), 1);
+    }
+
+    #[test]
+    fn cache_age_in_largest_unit() {
+        assert_eq!(fmt_age(Duration::from_secs(42)), "42s ago");
+        assert_eq!(fmt_age(Duration::from_secs(5 * 60 + 59)), "5m ago");
+        assert_eq!(fmt_age(Duration::from_secs(3 * 3_600)), "3h ago");
+        assert_eq!(fmt_age(Duration::from_secs(2 * 86_400 + 1)), "2d ago");
+    }
+
+    #[test]
+    fn fixture_names_from_site_paths() {
+        assert_eq!(fixture_name("/team.php?i=3"), "team_i_3");
+        assert_eq!(fixture_name("/default.php?p=pr&s=4&w=9"), "default_p_pr_s_4_w_9");
+        assert_eq!(fixture_name("/"), "index");
+    }
 }
 
 impl Progress for CliProgress {
@@ -299,8 +1673,16 @@ impl Progress for CliProgress {
         self.done += 1;
         eprintln!("Fetched {}/{}", self.done, self.total);
     }
-    fn item_failed(&mut self, _team_id: u32, team_name: &str) {
-        eprintln!("Failed: {}", team_name);
+    fn item_failed(&mut self, team_id: u32, team_name: &str, error: &str) {
+        self.done += 1;
+        eprintln!("Failed {}/{}: {} (id {}): {}", self.done, self.total, team_name, team_id, error);
+    }
+    fn finish(&mut self, summary: &Summary) {
+        if self.total == 0 { return; }
+        eprintln!("Done: {}", summary.line());
+        if !summary.failed.is_empty() {
+            let ids: Vec<String> = summary.failed.iter().map(|(id, _, _)| id.to_string()).collect();
+            eprintln!("Retry the failed teams with: --ids {}", ids.join(","));
+        }
     }
-    fn finish(&mut self) {}
 }
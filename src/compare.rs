@@ -0,0 +1,138 @@
+// src/compare.rs
+//
+// Roster comparison of two teams for scouting: each team's players with
+// their stat columns, each team's average per stat, and the difference.
+// Purely local, over the cached Players data (like Team Summary).
+
+use crate::aggregate::{fmt_num, players_stat_columns};
+use crate::config::options::PageKind;
+use crate::store::DataSet;
+use crate::typed::TypedColumns;
+
+/// Players team column (schema order: Name, #, Race, Team).
+const TEAM_COL: usize = 3;
+const NAME_COL: usize = 0;
+
+/// Header of the comparison's name column; averages and the difference
+/// are named in it too.
+pub const NAME_HEADER: &str = "Name";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RosterComparison {
+    /// The two teams, as named in the Players data
+    pub teams: [String; 2],
+    /// Stat column headers, in Players order
+    pub stats: Vec<String>,
+    /// Per team: (player name, stat cells)
+    pub rosters: [Vec<(String, Vec<String>)>; 2],
+    /// Per team: average of each stat over the players who have it
+    pub averages: [Vec<Option<f64>>; 2],
+}
+
+impl RosterComparison {
+    /// Average of the first team minus the second's, per stat.
+    pub fn deltas(&self) -> Vec<Option<f64>> {
+        self.averages[0].iter().zip(&self.averages[1])
+            .map(|(a, b)| Some(a.as_ref()? - b.as_ref()?))
+            .collect()
+    }
+
+    /// As one table for export: Team, Name, stats. Both rosters, then a
+    /// row per team with its averages ("Average") and one with the
+    /// difference (Team "Δ", Name "<first> − <second>").
+    pub fn to_dataset(&self) -> DataSet {
+        let mut headers = vec![s!("Team"), s!(NAME_HEADER)];
+        headers.extend(self.stats.iter().cloned());
+
+        let cell = |x: &Option<f64>| x.map(fmt_num).unwrap_or_default();
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        for (team, roster) in self.teams.iter().zip(&self.rosters) {
+            for (name, stats) in roster {
+                let mut row = vec![team.clone(), name.clone()];
+                row.extend(stats.iter().cloned());
+                rows.push(row);
+            }
+        }
+        for (team, avg) in self.teams.iter().zip(&self.averages) {
+            let mut row = vec![team.clone(), s!("Average")];
+            row.extend(avg.iter().map(cell));
+            rows.push(row);
+        }
+        let mut row = vec![s!("Δ"), format!("{} − {}", self.teams[0], self.teams[1])];
+        row.extend(self.deltas().iter().map(|d| match d {
+            Some(d) if *d > 0.0 => format!("+{}", fmt_num(*d)),
+            d => cell(d),
+        }));
+        rows.push(row);
+
+        DataSet { headers: Some(headers), rows }
+    }
+}
+
+/// Compare the rosters of teams `a` and `b` in the Players data. The stats
+/// are the numeric columns other than number, player id and derived ones.
+/// None without headers.
+pub fn rosters(players: &DataSet, a: &str, b: &str) -> Option<RosterComparison> {
+    let headers = players.headers.as_ref()?;
+    let typed = TypedColumns::for_page(PageKind::Players, players);
+    let stats = players_stat_columns(players, &typed);
+
+    let side = |team: &str| {
+        let ix: Vec<usize> = players.rows.iter().enumerate()
+            .filter(|(_, r)| r.get(TEAM_COL).is_some_and(|t| t == team))
+            .map(|(i, _)| i)
+            .collect();
+        let roster: Vec<(String, Vec<String>)> = ix.iter().map(|&i| {
+            let r = &players.rows[i];
+            let cells = stats.iter().map(|&c| r.get(c).cloned().unwrap_or_default()).collect();
+            (r.get(NAME_COL).cloned().unwrap_or_default(), cells)
+        }).collect();
+        let averages: Vec<Option<f64>> = stats.iter().map(|&c| {
+            let xs: Vec<f64> = ix.iter().filter_map(|&i| typed.number(i, c)).collect();
+            (!xs.is_empty()).then(|| xs.iter().sum::<f64>() / xs.len() as f64)
+        }).collect();
+        (roster, averages)
+    };
+    let (ra, aa) = side(a);
+    let (rb, ab) = side(b);
+
+    Some(RosterComparison {
+        teams: [s!(a), s!(b)],
+        stats: stats.iter().map(|&c| headers.get(c).cloned().unwrap_or_default()).collect(),
+        rosters: [ra, rb],
+        averages: [aa, ab],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ds(headers: &[&str], rows: &[&[&str]]) -> DataSet {
+        DataSet {
+            headers: Some(headers.iter().map(|h| s!(*h)).collect()),
+            rows: rows.iter().map(|r| r.iter().map(|c| s!(*c)).collect()).collect(),
+        }
+    }
+
+    #[test]
+    fn averages_and_signed_deltas_per_stat() {
+        let players = ds(&["Name", "#", "Race", "Team", "TV", "SR"], &[
+            &["Al", "#1", "Orc", "Reds", "100", "5"],
+            &["Bo", "#2", "Elf", "Blues", "80", "7"],
+            &["Cy", "#3", "Orc", "Reds", "110", ""],
+            &["Di", "#4", "Elf", "Greens", "1", "1"],
+        ]);
+        let cmp = rosters(&players, "Reds", "Blues").unwrap();
+        assert_eq!(cmp.stats, vec!["TV", "SR"]);
+        assert_eq!(cmp.rosters[0].len(), 2);
+        assert_eq!(cmp.averages[0], vec![Some(105.0), Some(5.0)]);
+        assert_eq!(cmp.deltas(), vec![Some(25.0), Some(-2.0)]);
+
+        let out = cmp.to_dataset();
+        assert_eq!(out.headers.unwrap(), vec!["Team", "Name", "TV", "SR"]);
+        assert_eq!(out.rows.len(), 3 + 3);
+        assert_eq!(out.rows[3], vec!["Reds", "Average", "105", "5"]);
+        assert_eq!(out.rows[5], vec!["Δ", "Reds − Blues", "+25", "-2"]);
+    }
+}
@@ -1,15 +1,42 @@
 // src/config/consts.rs
 
+// GUI window title (scrapes prefix their progress)
+pub const APP_TITLE: &str = "Brutalball Scraper";
+
 // Net config
 pub const HOST: &str = "dozerverse.com";
 pub const PREFIX: &str = "/brutalball/";
 
-// Local cache
+// Socket timeout (default; `net.timeout_secs` in `.store/settings`)
+pub const DEFAULT_TIMEOUT_SECS: u64 = 15;
+
+// Truncated responses: retries (default; `net.retries`), and pause before each (× attempt)
+pub const TRUNCATED_RETRIES: u32 = 2;
+pub const RETRY_PAUSE_MS: u64 = 500;
+
+// Local cache: the default data directory (see `store::store_dir`), the
+// file next to the executable that turns on portable mode, and the log
 pub const STORE_DIR: &str = ".store";
+pub const PORTABLE_MARKER: &str = "bb_scrape.portable";
+pub const LOG_FILE_NAME: &str = "bb_scrape.log";
 pub const STORE_SEP: char = ',';
+// Pages scraped longer ago than this are flagged stale (hours; `.store/settings`)
+pub const DEFAULT_STALE_AFTER_HOURS: u32 = 24;
+// Players per stat on the Leaders page (`.store/settings`)
+pub const DEFAULT_LEADERS_TOP: usize = 5;
+// GUI body text size (points; egui's default) and the range offered
+pub const DEFAULT_FONT_SIZE: f32 = 12.5;
+pub const FONT_SIZE_RANGE: std::ops::RangeInclusive<f32> = 9.0..=24.0;
+
+// Raw HTML saved by --capture, one subdirectory per page
+pub const DEFAULT_FIXTURES_DIR: &str = "fixtures";
 
 // Scrape
 pub const SCRAPE_FLIP_SIDES: bool = false;
+// Bump whenever a scraper's output changes (site-format fixes, new columns).
+// Recorded with each cached page and in manifests, to tell which parser
+// produced a given table.
+pub const PARSER_VERSION: u32 = 3;
 
 // Export
 pub const DEFAULT_OUT_DIR: &str ="out";
@@ -17,11 +44,25 @@ pub const DEFAULT_TEAMS_SUBDIR: &str = "";
 pub const DEFAULT_PLAYERS_SUBDIR: &str = "players";
 pub const DEFAULT_RESULTS_SUBDIR: &str = "results";
 pub const DEFAULT_INJURIES_SUBDIR: &str = "injuries";
+pub const DEFAULT_STANDINGS_SUBDIR: &str = "";
+pub const DEFAULT_TRANSACTIONS_SUBDIR: &str = "transactions";
+
+pub const DEFAULT_BUNDLE_SUBDIR: &str = "bundle";
 
 pub const DEFAULT_TEAMS_FILE: &str = "teams";
+pub const DEFAULT_INJURIES_FILE: &str = "injuries";
+pub const DEFAULT_STANDINGS_FILE: &str = "standings";
+pub const DEFAULT_TRANSACTIONS_FILE: &str = "transactions";
+pub const DEFAULT_TEAM_SUMMARY_FILE: &str = "team_summary";
+pub const DEFAULT_RESULTS_TABLE_FILE: &str = "results_table";
+pub const DEFAULT_SCHEDULE_METRICS_FILE: &str = "schedule_metrics";
+pub const DEFAULT_LEADERS_FILE: &str = "leaders";
+pub const DEFAULT_TEAM_INFO_FILE: &str = "team_info";
+pub const DEFAULT_INJURY_VICTIMS_FILE: &str = "injury_victims";
+pub const DEFAULT_INJURY_SUMMARY_FILE: &str = "injury_summary";
 pub const DEFAULT_FILE: &str = "all";
 
 // Concurrency
 pub const WORKERS: usize = 4;
-pub const REQUEST_PAUSE_MS: u64 = 75; // be polite
+pub const REQUEST_PAUSE_MS: u64 = 75; // be polite (default; `net.delay_ms`)
 pub const JITTER_MS: u64 = 50; // extra 0..50 ms
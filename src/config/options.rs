@@ -1,5 +1,6 @@
 // src/config/options.rs
 use std::{
+    collections::{ BTreeMap, HashMap },
     path::{ Path, PathBuf },
     ffi::OsString,
     str,
@@ -33,6 +34,25 @@ pub enum PageKind {
     CareerStats, 
     GameResults,
     Injuries,
+    Standings,
+    /// Roster moves: signings, cuts, trades
+    Transactions,
+    /// Players aggregated per team (computed locally, no scraping)
+    TeamSummary,
+    /// League table from the game results (computed locally, no scraping)
+    ResultsTable,
+    /// Strength of schedule and more from the game results (computed locally)
+    ScheduleMetrics,
+    /// Top players per stat from the players (computed locally)
+    Leaders,
+    /// Team metadata from each team page: owner, stadium, logo
+    TeamInfo,
+    /// Injuries with the victim's player data (joined locally, no scraping)
+    InjuryVictims,
+    /// Injuries per team, type and week from the injuries (computed locally)
+    InjurySummary,
+    /// Detail page of a single player (player.php?i=<id>)
+    Player,
 }
 
 use PageKind::*;
@@ -48,6 +68,16 @@ impl str::FromStr for PageKind {
             "careerstats"   | "career_stats"   | "career-stats"   => Ok(CareerStats),
             "gameresults"   | "game_results"   | "game-results"   => Ok(GameResults),
             "injuries"      => Ok(Injuries),
+            "player"        => Ok(Player),
+            "standings"     => Ok(Standings),
+            "transactions"  => Ok(Transactions),
+            "teamsummary"   | "team_summary"   | "team-summary"   => Ok(TeamSummary),
+            "resultstable"  | "results_table"  | "results-table"  => Ok(ResultsTable),
+            "schedulemetrics" | "schedule_metrics" | "schedule-metrics" => Ok(ScheduleMetrics),
+            "leaders"       => Ok(Leaders),
+            "teaminfo"      | "team_info"      | "team-info"      => Ok(TeamInfo),
+            "injuryvictims" | "injury_victims" | "injury-victims" => Ok(InjuryVictims),
+            "injurysummary" | "injury_summary" | "injury-summary" => Ok(InjurySummary),
             other => Err(format!("Unknown page: {}", other)),
         }
     }
@@ -62,6 +92,16 @@ impl fmt::Display for PageKind {
             CareerStats  => "career-stats",
             GameResults  => "game-results",
             Injuries     => "injuries",
+            Player       => "player",
+            Standings    => "standings",
+            Transactions => "transactions",
+            TeamSummary  => "team-summary",
+            ResultsTable => "results-table",
+            ScheduleMetrics => "schedule-metrics",
+            Leaders      => "leaders",
+            TeamInfo     => "team-info",
+            InjuryVictims => "injury-victims",
+            InjurySummary => "injury-summary",
         })
     }
 }
@@ -93,6 +133,14 @@ impl TeamSelector {
         }
     }
 
+    pub fn contains(&self, id: u32) -> bool {
+        match self {
+            All => true,
+            One(v) => *v == id,
+            Ids(list) => list.contains(&id),
+        }
+    }
+
     pub fn normalize(&mut self) {
         if let Ids(list) = self {
             list.sort_unstable();
@@ -105,6 +153,14 @@ impl TeamSelector {
 pub struct ScrapeOptions {
     pub page: PageKind,
     pub teams: TeamSelector,
+    /// Which player to fetch for PageKind::Player
+    pub player_id: Option<u32>,
+    /// Team Info: also download each team's logo into `.store/logos/`
+    pub download_logos: bool,
+    /// Which injury-line parser Injuries uses
+    pub injury_parser: InjuryParser,
+    /// Leaders: players per stat (None: the saved setting)
+    pub leaders_top: Option<usize>,
 }
 
 impl Default for ScrapeOptions {
@@ -112,10 +168,54 @@ impl Default for ScrapeOptions {
         Self {
             page: Players,
             teams: All,
+            player_id: None,
+            download_logos: false,
+            injury_parser: InjuryParser::default(),
+            leaders_top: None,
         }
     }
 }
 
+/// The injuries spec's line parsers. They should agree; `Slow` is the
+/// readable reference, `Fast` the default.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum InjuryParser {
+    /// Keyword search on the tag-stripped line
+    Slow,
+    /// Single pass over visible characters, team names by linear scan
+    FastBase,
+    /// As FastBase, team names indexed by first letter
+    #[default]
+    Fast,
+}
+
+impl InjuryParser {
+    pub const ALL: [InjuryParser; 3] = [InjuryParser::Slow, InjuryParser::FastBase, InjuryParser::Fast];
+}
+
+impl str::FromStr for InjuryParser {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "slow" => Ok(InjuryParser::Slow),
+            "fast-base" | "fast_base" | "base" => Ok(InjuryParser::FastBase),
+            "fast" | "fast-idx" | "fast_idx" => Ok(InjuryParser::Fast),
+            other => Err(format!("Unknown injury parser: {} (slow, fast-base, fast)", other)),
+        }
+    }
+}
+
+impl fmt::Display for InjuryParser {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            InjuryParser::Slow => "slow",
+            InjuryParser::FastBase => "fast-base",
+            InjuryParser::Fast => "fast",
+        })
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ExportType {
     SingleFile, 
@@ -124,10 +224,121 @@ pub enum ExportType {
 
 use ExportType::*;
 
+/// What happens to an existing export file that is overwritten.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Backup {
+    /// Replaced
+    #[default]
+    Off,
+    /// Kept as `<name>.bak` (the previous one only)
+    Bak,
+    /// Kept as `<name>.<unix time>.bak` (every previous one)
+    Timestamped,
+}
+
+/// How exports write the numbers in numeric columns (per the page schema;
+/// ids are left alone): e.g. `1234.5`, `1,234.5` or `1.234,5` for
+/// comma-decimal spreadsheet locales.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NumberFormat {
+    pub decimal: char,
+    /// Between groups of three digits; None writes no grouping
+    pub thousands: Option<char>,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self { Self { decimal: '.', thousands: None } }
+}
+
+impl NumberFormat {
+    /// Numbers as scraped: nothing to rewrite.
+    pub fn is_plain(&self) -> bool { *self == Self::default() }
+
+    /// `cell` in this format when it's a plain number (`-1234.5`), else None.
+    pub fn apply(&self, cell: &str) -> Option<String> {
+        let (sign, unsigned) = match cell.strip_prefix('-') {
+            Some(rest) => ("-", rest),
+            None => ("", cell),
+        };
+        let (whole, frac) = match unsigned.split_once('.') {
+            Some((w, f)) => (w, Some(f)),
+            None => (unsigned, None),
+        };
+        let digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+        if !digits(whole) || frac.is_some_and(|f| !digits(f)) { return None; }
+
+        let mut out = s!(sign);
+        for (i, c) in whole.chars().enumerate() {
+            if let Some(t) = self.thousands.filter(|_| i > 0 && (whole.len() - i) % 3 == 0) {
+                out.push(t);
+            }
+            out.push(c);
+        }
+        if let Some(f) = frac {
+            out.push(self.decimal);
+            out.push_str(f);
+        }
+        Some(out)
+    }
+
+    /// Separator from a CLI/GUI name: `.`, `,`, `space`, `'`, `none`.
+    pub fn parse_separator(s: &str) -> Result<Option<char>, String> {
+        match s.trim() {
+            "none" | "" => Ok(None),
+            "space" | " " => Ok(Some(' ')),
+            "." => Ok(Some('.')),
+            "," => Ok(Some(',')),
+            "'" => Ok(Some('\'')),
+            other => Err(format!("Unknown separator: {:?} (use . , ' space none)", other)),
+        }
+    }
+}
+
+/// Cells starting with `=`, `+`, `-` or `@` that aren't numbers would be
+/// taken for formulas when pasted or opened in a spreadsheet (CSV
+/// injection). Escaped cells get a leading `'`, which spreadsheets read as
+/// "text" and don't show.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FormulaEscape {
+    /// Cells as they are
+    Off,
+    /// Clipboard copies only
+    #[default]
+    Clipboard,
+    /// Copies and exported files
+    Always,
+}
+
+impl FormulaEscape {
+    /// Whether a copy (`clipboard`) or a file gets escaped cells.
+    pub fn applies(self, clipboard: bool) -> bool {
+        match self {
+            FormulaEscape::Off => false,
+            FormulaEscape::Clipboard => clipboard,
+            FormulaEscape::Always => true,
+        }
+    }
+
+    /// `cell` with a leading `'` when a spreadsheet could take it for a
+    /// formula: it starts with `= + - @`, a tab or a carriage return, or
+    /// with `= + - @` after leading whitespace. None when it's safe as it
+    /// is, numbers like `-3` (whitespace around them included) too.
+    pub fn escape(cell: &str) -> Option<String> {
+        const TRIGGERS: [char; 4] = ['=', '+', '-', '@'];
+        let risky = (cell.starts_with(['\t', '\r']) || cell.trim_start().starts_with(TRIGGERS))
+            && cell.trim().parse::<f64>().is_err();
+        risky.then(|| format!("'{}", cell))
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ExportFormat {
     Csv,
     Tsv,
+    /// Pipe table, for Discord and other Markdown chats
+    Markdown,
+    /// `[table]` markup, for the league forum
+    BbCode,
     // Json,
     // Toml,
 }
@@ -140,6 +351,8 @@ impl ExportFormat {
         match self { 
             Csv => "csv", 
             Tsv => "tsv",
+            Markdown => "md",
+            BbCode => "txt",
             // Json => "json",
             // Toml => "toml",
          }
@@ -148,9 +361,13 @@ impl ExportFormat {
         match self { 
             Csv => Some(','),
             Tsv => Some('\t'),
+            Markdown | BbCode => None,
             // Json | Toml => None,
          }
     }
+
+    /// Rendered as a formatted table rather than delimited text.
+    pub fn is_markup(&self) -> bool { self.delimiter().is_none() }
 }
 
 impl str::FromStr for ExportFormat {
@@ -160,6 +377,8 @@ impl str::FromStr for ExportFormat {
         match s.trim().to_ascii_lowercase().as_str() {
             "csv" => Ok(Csv),
             "tsv" => Ok(Tsv),
+            "markdown" | "md" => Ok(Markdown),
+            "bbcode" | "bb" => Ok(BbCode),
             other => Err(format!("Unknown format: {}", other)),
         }
     }
@@ -171,12 +390,29 @@ impl fmt::Display for ExportFormat {
             match self {
                 Csv => "csv",
                 Tsv => "tsv",
+                Markdown => "markdown",
+                BbCode => "bbcode",
             }
         )
     }
 }
 
 
+/// Export settings a page keeps for itself (`.store/settings`), used
+/// whenever it's the current tab instead of the shared ones (GUI).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PageExportDefaults {
+    pub format: Option<ExportFormat>,
+    pub include_headers: Option<bool>,
+    pub dir: Option<PathBuf>,
+}
+
+impl PageExportDefaults {
+    pub fn is_empty(&self) -> bool {
+        self.format.is_none() && self.include_headers.is_none() && self.dir.is_none()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ExportOptions {
     pub format: ExportFormat,
@@ -185,8 +421,45 @@ pub struct ExportOptions {
     pub include_headers: bool, 
     pub keep_hash: bool,
     pub skip_optional: bool,
+    /// Also write `manifest.json` listing the exported files
+    pub write_manifest: bool,
+    /// GUI: export/copy with the table's column order and hidden columns
+    pub as_shown: bool,
+    /// Per-team: one subdirectory per division. Single file: add Division column(s).
+    /// Needs division data in the team list; no-op without it.
+    pub by_division: bool,
+    /// Keep the file an export replaces
+    pub backup: Backup,
+    /// Per-team and bundle exports: pack the files into `<dir>.zip` instead
+    pub zip: bool,
+    /// Per-team: sort each file's rows by this column (header name);
+    /// None keeps the scrape order
+    pub sort_by: Option<String>,
+    pub sort_descending: bool,
+    /// Per-team: each file's rows in season/week order (pages with S and W
+    /// columns); `sort_by` then orders the rows within a week
+    pub chronological: bool,
+    /// Header renames per page (canonical → exported name), from
+    /// `.store/settings`; applied when writing files, never to the cache
+    pub header_names: HashMap<PageKind, BTreeMap<String, String>>,
+    /// Decimal and thousands separators of numeric columns
+    pub number_format: NumberFormat,
+    /// Where cells that look like formulas get escaped
+    pub formula_escape: FormulaEscape,
+    /// Single file CSV/TSV: add the rows an existing file doesn't have yet
+    /// (by the page's key columns, else whole rows) instead of rewriting it
+    pub append: bool,
+    /// CSV/TSV: separator instead of the format's own (`,` / tab), e.g.
+    /// `;` or `|`; see `parse_delimiter`
+    pub custom_delimiter: Option<char>,
+    /// Format, headers and DIR per page, over the shared settings
+    pub page_defaults: HashMap<PageKind, PageExportDefaults>,
+    /// The shared settings, kept while a page with its own is current
+    shared: Option<PageExportDefaults>,
 }
 
+fn usable_delimiter(c: char) -> bool { !matches!(c, '"' | '\n' | '\r') }
+
 impl Default for ExportOptions {
     fn default() -> Self {
         Self {
@@ -196,6 +469,21 @@ impl Default for ExportOptions {
             include_headers: true,
             keep_hash: true,
             skip_optional: false,
+            write_manifest: false,
+            as_shown: false,
+            by_division: false,
+            backup: Backup::Off,
+            zip: false,
+            sort_by: None,
+            sort_descending: false,
+            chronological: false,
+            header_names: HashMap::new(),
+            number_format: NumberFormat::default(),
+            formula_escape: FormulaEscape::default(),
+            append: false,
+            custom_delimiter: None,
+            page_defaults: HashMap::new(),
+            shared: None,
         }
     }
 }
@@ -258,7 +546,33 @@ impl ExportOptions {
         }
     }
 
-    pub fn delimiter(&self) -> Option<char> { self.format.delimiter() }
+    /// Field separator of delimited formats: `custom_delimiter` when set
+    /// (and usable), else the format's own. None for Markdown / BBCode.
+    pub fn delimiter(&self) -> Option<char> {
+        let custom = self.custom_delimiter.filter(|&c| usable_delimiter(c));
+        self.format.delimiter().map(|d| custom.unwrap_or(d))
+    }
+
+    /// A single-character delimiter (`tab` and `space` by name). Quotes and
+    /// line breaks are refused: writers quote cells with `"` and
+    /// `file::parse_rows` ends rows at line breaks, so neither can separate
+    /// fields.
+    pub fn parse_delimiter(s: &str) -> Result<char, String> {
+        let c = match s {
+            "tab" | "\\t" => '\t',
+            "space" => ' ',
+            _ => {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => c,
+                    _ => return Err(format!("Delimiter must be a single character, got {:?}", s)),
+                }
+            }
+        };
+        if usable_delimiter(c) { Ok(c) } else {
+            Err(format!("{:?} can't be a delimiter (used for quoting and line breaks)", c))
+        }
+    }
 
     /// Default DIR for a page (public, so UI can reason about defaults).
     pub fn default_dir_for(kind: PageKind) -> PathBuf {
@@ -267,11 +581,45 @@ impl ExportOptions {
             PageKind::GameResults => DEFAULT_RESULTS_SUBDIR,
             PageKind::Teams       => DEFAULT_TEAMS_SUBDIR,
             PageKind::Injuries    => DEFAULT_INJURIES_SUBDIR,
+            PageKind::Standings   => DEFAULT_STANDINGS_SUBDIR,
+            PageKind::Transactions => DEFAULT_TRANSACTIONS_SUBDIR,
+            PageKind::TeamSummary => DEFAULT_TEAMS_SUBDIR,
+            PageKind::ResultsTable => DEFAULT_STANDINGS_SUBDIR,
+            PageKind::ScheduleMetrics => DEFAULT_STANDINGS_SUBDIR,
+            PageKind::Leaders     => DEFAULT_PLAYERS_SUBDIR,
+            PageKind::TeamInfo    => DEFAULT_TEAMS_SUBDIR,
+            PageKind::InjuryVictims => DEFAULT_INJURIES_SUBDIR,
+            PageKind::InjurySummary => DEFAULT_INJURIES_SUBDIR,
             _ => DEFAULT_PLAYERS_SUBDIR, // extend as needed
         };
         PathBuf::from(DEFAULT_OUT_DIR).join(sub)
     }
 
+    /// Default file stem for a page ("all" for the pages split per team).
+    pub fn default_file_for(kind: PageKind) -> &'static str {
+        match kind {
+            PageKind::Teams         => DEFAULT_TEAMS_FILE,
+            PageKind::Injuries      => DEFAULT_INJURIES_FILE,
+            PageKind::Standings     => DEFAULT_STANDINGS_FILE,
+            PageKind::Transactions  => DEFAULT_TRANSACTIONS_FILE,
+            PageKind::TeamSummary   => DEFAULT_TEAM_SUMMARY_FILE,
+            PageKind::ResultsTable  => DEFAULT_RESULTS_TABLE_FILE,
+            PageKind::ScheduleMetrics => DEFAULT_SCHEDULE_METRICS_FILE,
+            PageKind::Leaders       => DEFAULT_LEADERS_FILE,
+            PageKind::TeamInfo      => DEFAULT_TEAM_INFO_FILE,
+            PageKind::InjuryVictims => DEFAULT_INJURY_VICTIMS_FILE,
+            PageKind::InjurySummary => DEFAULT_INJURY_SUMMARY_FILE,
+            _ => DEFAULT_FILE,
+        }
+    }
+
+    /// Point at `dir` with the page's default file name and the format's extension.
+    pub fn set_dir_with_default_file(&mut self, dir: PathBuf, kind: PageKind) {
+        self.out_path.dir = dir;
+        self.out_path.file_stem = OsString::from(Self::default_file_for(kind));
+        self.out_path.file_ext = None;
+    }
+
     /// Set only the DIR to the page-default. Keeps filename/ext as-is.
     pub fn set_default_dir_for_page(&mut self, kind: PageKind) {
         self.out_path.dir = Self::default_dir_for(kind);
@@ -293,6 +641,43 @@ impl ExportOptions {
         norm(self.current_dir()) == norm(&Self::default_dir_for(kind))
     }
 
+    /// Format, headers and DIR as they are now, e.g. to keep as a page's own.
+    pub fn current_page_defaults(&self) -> PageExportDefaults {
+        PageExportDefaults {
+            format: Some(self.format),
+            include_headers: Some(self.include_headers),
+            dir: Some(self.out_path.dir.clone()),
+        }
+    }
+
+    /// Switch to `kind`'s own settings, if it has any. Returns whether it did.
+    pub fn enter_page_defaults(&mut self, kind: PageKind) -> bool {
+        if self.shared.is_none() {
+            self.shared = Some(self.current_page_defaults());
+        }
+        let Some(d) = self.page_defaults.get(&kind).filter(|d| !d.is_empty()).cloned() else { return false };
+        self.apply_page_defaults(&d);
+        true
+    }
+
+    /// Leaving `kind`: back to the shared settings if it had its own, else
+    /// remember the current ones as shared. Returns whether anything changed.
+    pub fn leave_page_defaults(&mut self, kind: PageKind) -> bool {
+        if self.page_defaults.get(&kind).is_none_or(|d| d.is_empty()) {
+            self.shared = Some(self.current_page_defaults());
+            return false;
+        }
+        let Some(shared) = self.shared.clone() else { return false };
+        self.apply_page_defaults(&shared);
+        true
+    }
+
+    fn apply_page_defaults(&mut self, d: &PageExportDefaults) {
+        if let Some(f) = d.format { self.format = f; }
+        if let Some(h) = d.include_headers { self.include_headers = h; }
+        if let Some(dir) = &d.dir { self.out_path.dir = dir.clone(); }
+    }
+
     pub fn is_fully_default_for(&self, kind: PageKind) -> bool {
         if self.export_type != ExportType::SingleFile {
             return false;
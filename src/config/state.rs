@@ -1,5 +1,109 @@
 // src/config/state.rs
-use super::options::AppOptions;
+use std::{collections::HashMap, fmt, str};
+
+use super::options::{AppOptions, PageKind};
+
+/// Game Results filter on whether a game has been played.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GameStatus {
+    #[default]
+    All,
+    /// Both scores filled in
+    Played,
+    /// Scores still blank
+    Upcoming,
+}
+
+/// GUI colors; System follows the OS light/dark setting.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Theme {
+    #[default]
+    System,
+    Dark,
+    Light,
+}
+
+/// Table row height.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Density {
+    Compact,
+    #[default]
+    Normal,
+}
+
+impl str::FromStr for Theme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "system" => Ok(Theme::System),
+            "dark" => Ok(Theme::Dark),
+            "light" => Ok(Theme::Light),
+            other => Err(format!("Unknown theme: {}", other)),
+        }
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Theme::System => "system",
+            Theme::Dark => "dark",
+            Theme::Light => "light",
+        })
+    }
+}
+
+impl str::FromStr for Density {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "compact" => Ok(Density::Compact),
+            "normal" => Ok(Density::Normal),
+            other => Err(format!("Unknown density: {}", other)),
+        }
+    }
+}
+
+impl fmt::Display for Density {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Density::Compact => "compact",
+            Density::Normal => "normal",
+        })
+    }
+}
+
+/// How the GUI looks. Persisted in `.store/settings`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Appearance {
+    pub theme: Theme,
+    pub density: Density,
+    /// Body text size in points; the other text styles scale with it
+    pub font_size: f32,
+}
+
+impl Appearance {
+    /// Row height of the data table (points): the text plus some room,
+    /// 16 and 20 at the default font size.
+    pub fn row_height(&self) -> f32 {
+        self.font_size + match self.density {
+            Density::Compact => 3.5,
+            Density::Normal => 7.5,
+        }
+    }
+}
+
+impl Default for Appearance {
+    fn default() -> Self {
+        Self {
+            theme: Theme::default(),
+            density: Density::default(),
+            font_size: crate::config::consts::DEFAULT_FONT_SIZE,
+        }
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct GuiState {
@@ -16,8 +120,52 @@ pub struct GuiState {
     /// Game Results page -> show/hide Match id column
     pub game_results_show_match_id: bool,
 
+    /// Game Results page -> played or upcoming games only (blank scores = upcoming)
+    pub game_results_status: GameStatus,
+
+    /// Game Results page -> only weeks in this range (inclusive)
+    pub game_results_weeks: Option<(u32, u32)>,
+
+    /// Game Results page -> week for the print view
+    pub print_week: u32,
+
+    /// Players page -> show/hide Player id column
+    pub players_show_id: bool,
+
+    /// Re-scrape the current page when the window regains focus
+    /// and its cache is older than `refresh_after_min`
+    pub refresh_on_focus: bool,
+    pub refresh_after_min: u32,
+
+    /// Tabs of pages scraped longer ago than this (hours) are tinted stale.
+    /// Persisted in `.store/settings`.
+    pub stale_after_hours: u32,
+
+    /// Players per stat on the Leaders page. Persisted in `.store/settings`.
+    pub leaders_top: usize,
+
+    /// Theme, table density and font size
+    pub appearance: Appearance,
+
     /// Fixed width for the Teams side panel
     pub team_panel_width: f32,
+
+    /// Show stat changes since the previous scrape instead of totals
+    pub show_delta: bool,
+
+    /// Columns hidden via the Columns menu, by header name per page.
+    /// Persisted in `.store/settings`.
+    pub hidden_columns: HashMap<PageKind, Vec<String>>,
+
+    /// Log panel -> lowest level shown
+    pub log_level: crate::log::Level,
+
+    /// Table rows picked with click / Ctrl+click / Shift+click, per page.
+    /// Cleared when the page's data changes.
+    pub row_selection: HashMap<PageKind, crate::data::RowSelection>,
+
+    /// Copy/Export -> only the selected rows (within the team filter)
+    pub selected_rows_only: bool,
 }
 
 impl Default for GuiState {
@@ -29,7 +177,21 @@ impl Default for GuiState {
             last_browse_dir: s!(),
             current_page_index: 0,
             game_results_show_match_id: true,
+            game_results_status: GameStatus::All,
+            game_results_weeks: None,
+            print_week: 1,
+            players_show_id: false,
+            refresh_on_focus: false,
+            refresh_after_min: 30,
+            stale_after_hours: crate::config::consts::DEFAULT_STALE_AFTER_HOURS,
+            leaders_top: crate::config::consts::DEFAULT_LEADERS_TOP,
+            appearance: Appearance::default(),
             team_panel_width: 200.0,
+            show_delta: false,
+            hidden_columns: HashMap::new(),
+            log_level: crate::log::Level::Info,
+            row_selection: HashMap::new(),
+            selected_rows_only: false,
         }
     }
 }
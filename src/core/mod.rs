@@ -1,6 +1,7 @@
 // src/core/mod.rs
 
 pub mod html;
+pub mod select;
 pub mod vischars;
 pub mod net;
 pub mod sanitize;
@@ -1,13 +1,179 @@
 // src/core/net.rs
 
-// HTTP/1.1 GET over TCP (std-only)
+// HTTP/1.1 GET over TCP (std-only); JSON POST for webhooks
 
 use std::{
-    io::{BufRead, BufReader, Read, Write},
-    net::TcpStream,
+    fmt,
+    io::{BufRead, BufReader, ErrorKind, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    process::{Command, Stdio},
+    sync::RwLock,
+    thread,
     time::{Duration, Instant},
 };
-use crate::config::consts::{HOST, PREFIX};
+use crate::config::consts::{DEFAULT_TIMEOUT_SECS, HOST, PREFIX, REQUEST_PAUSE_MS, TRUNCATED_RETRIES, RETRY_PAUSE_MS};
+use crate::error::Error;
+
+/// How the site is reached: where, how long to wait, how often to retry,
+/// how long to pause between requests. Defaults from `consts`; the GUI's
+/// Settings window edits them (`.store/settings`, `net.*`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NetSettings {
+    /// `http://host[:port]/prefix/` of the site
+    pub base_url: String,
+    /// Connect, read and write timeout
+    pub timeout_secs: u64,
+    /// Retries of a truncated response
+    pub retries: u32,
+    /// Pause between requests (the per-team scrapes add some jitter)
+    pub delay_ms: u64,
+}
+
+impl Default for NetSettings {
+    fn default() -> Self {
+        Self {
+            base_url: format!("http://{}{}", HOST, PREFIX),
+            timeout_secs: DEFAULT_TIMEOUT_SECS,
+            retries: TRUNCATED_RETRIES,
+            delay_ms: REQUEST_PAUSE_MS,
+        }
+    }
+}
+
+impl NetSettings {
+    /// Host, port and path prefix of `base_url`. Plain http only: GETs go
+    /// over a std socket, without TLS.
+    pub fn site(&self) -> Result<(String, u16, String), String> {
+        match split_url(&self.base_url) {
+            Some(("http", host, port, path)) => Ok((host.to_string(), port, path.to_string())),
+            Some((scheme, ..)) => Err(format!("{}:// isn't supported for the site, only http://", scheme)),
+            None => Err(format!("Not an http URL: {}", self.base_url)),
+        }
+    }
+
+    pub fn timeout(&self) -> Duration { Duration::from_secs(self.timeout_secs.max(1)) }
+
+    pub fn delay(&self) -> Duration { Duration::from_millis(self.delay_ms) }
+}
+
+static SETTINGS: RwLock<Option<NetSettings>> = RwLock::new(None);
+
+/// Use `s` for the requests from now on (startup, and after the Settings
+/// window saves). A base URL that isn't plain http is refused.
+pub fn configure(s: NetSettings) -> Result<(), String> {
+    s.site()?;
+    *SETTINGS.write().unwrap_or_else(|e| e.into_inner()) = Some(s);
+    Ok(())
+}
+
+/// The settings requests use now.
+pub fn settings() -> NetSettings {
+    SETTINGS.read().unwrap_or_else(|e| e.into_inner()).clone().unwrap_or_default()
+}
+
+/// The response body ended early (short read, or an HTML document cut off
+/// mid-way). A network failure, not a page format change.
+#[derive(Debug)]
+pub struct Truncated {
+    pub path: String,
+    pub got: usize,
+    pub expected: Option<usize>,
+}
+
+impl fmt::Display for Truncated {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Truncated response from {} ({} bytes", self.path, self.got)?;
+        if let Some(n) = self.expected { write!(f, " of {}", n)?; }
+        write!(f, ") after {} retries", settings().retries)
+    }
+}
+
+impl std::error::Error for Truncated {}
+
+/// Why a request to the site failed, worded for the user: what happened
+/// and what to do about it. Carried by `Error::Net` with the URL (find it
+/// behind any error with `NetError::find`); the CLI exits with `exit_code`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NetError {
+    /// Connecting, sending or reading took longer than the timeout
+    Timeout,
+    /// The host name didn't resolve
+    Dns(String),
+    ConnectionRefused,
+    /// Any status but 200
+    HttpStatus(u16),
+    /// Not a readable HTTP response
+    Parse(String),
+}
+
+impl NetError {
+    /// The `NetError` in `e` or the errors behind it, if any.
+    pub fn find<'a>(e: &'a (dyn std::error::Error + 'static)) -> Option<&'a NetError> {
+        let mut cur = Some(e);
+        while let Some(err) = cur {
+            if let Some(n) = err.downcast_ref::<NetError>() { return Some(n); }
+            cur = err.source();
+        }
+        None
+    }
+
+    /// CLI exit code (1 is any other error).
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            NetError::Timeout => 3,
+            NetError::Dns(_) => 4,
+            NetError::ConnectionRefused => 5,
+            NetError::HttpStatus(_) => 6,
+            NetError::Parse(_) => 7,
+        }
+    }
+
+    /// A socket error requesting `url`: `Error::Net` for those with a
+    /// variant, `Error::Socket` for the rest.
+    fn from_io(url: &str, e: std::io::Error) -> Error {
+        let source = match e.kind() {
+            ErrorKind::TimedOut | ErrorKind::WouldBlock => NetError::Timeout,
+            ErrorKind::ConnectionRefused => NetError::ConnectionRefused,
+            _ => return Error::Socket { url: url.to_string(), source: e },
+        };
+        Error::Net { url: url.to_string(), source }
+    }
+}
+
+impl fmt::Display for NetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetError::Timeout => write!(f, "The site didn't answer within {}s — it may be busy; try later or raise the timeout in Settings", settings().timeout_secs),
+            NetError::Dns(host) => write!(f, "Couldn't find {} — check your internet connection and the site address in Settings", host),
+            NetError::ConnectionRefused => write!(f, "The site refused the connection — it may be down; try later"),
+            NetError::HttpStatus(429) => write!(f, "Site returned 429 (too many requests) — wait a bit, or raise the delay in Settings"),
+            NetError::HttpStatus(code @ (401 | 403)) => write!(f, "Site returned {} — access denied", code),
+            NetError::HttpStatus(404) => write!(f, "Site returned 404 — page not found; the site may have changed"),
+            NetError::HttpStatus(code @ 500..=599) => write!(f, "Site returned {} — try later", code),
+            NetError::HttpStatus(code) => write!(f, "Site returned {}", code),
+            NetError::Parse(what) => write!(f, "Unreadable response from the site: {}", what),
+        }
+    }
+}
+
+impl std::error::Error for NetError {}
+
+/// The code of a status line ("HTTP/1.1 503 Service Unavailable" → 503).
+fn status_code(line: &str) -> Option<u16> {
+    let mut parts = line.split_whitespace();
+    parts.next().filter(|v| v.starts_with("HTTP/"))?;
+    parts.next()?.parse().ok()
+}
+
+/// Obviously cut-off HTML: an `<html>` document without `</html>` that
+/// also stops inside a table or a tag.
+pub fn looks_truncated(doc: &str) -> bool {
+    let lc = doc.to_ascii_lowercase();
+    if !lc.contains("<html") || lc.contains("</html") { return false; }
+    let open_tables = lc.matches("<table").count() > lc.matches("</table").count();
+    let mid_tag = lc.rfind('<') > lc.rfind('>');
+    open_tables || mid_tag
+}
 
 fn join_prefix_and_path(prefix: &str, path: &str) -> String {
     let pfx = prefix.trim_end_matches('/');
@@ -15,17 +181,77 @@ fn join_prefix_and_path(prefix: &str, path: &str) -> String {
     format!("{}/{}", pfx, pth)
 }
 
-pub fn http_get(path: &str) -> Result<String, Box<dyn std::error::Error>> {
-    let full = join_prefix_and_path(PREFIX, path);
-    logd!("HTTP GET → {}{}", HOST, &full);
+/// Absolute URL `http_get(path)` would request (for logs and `--plan`).
+pub fn full_url(path: &str) -> String {
+    let s = settings();
+    match s.site() {
+        Ok((host, 80, prefix)) => format!("http://{}{}", host, join_prefix_and_path(&prefix, path)),
+        Ok((host, port, prefix)) => format!("http://{}:{}{}", host, port, join_prefix_and_path(&prefix, path)),
+        Err(_) => format!("{}{}", s.base_url, path),
+    }
+}
+
+/// GET a site page. Truncated responses are retried a couple of times
+/// before failing with `Truncated`.
+pub fn http_get(path: &str) -> Result<String, Error> {
+    let retries = settings().retries;
+    let mut attempt = 0;
+    loop {
+        let (body, expected) = get_once(path)?;
+        let body = String::from_utf8_lossy(&body).into_owned();
+        let short = expected.is_some_and(|n| body.len() < n);
+        if !short && !looks_truncated(&body) {
+            return Ok(body);
+        }
+        if attempt == retries {
+            loge!("HTTP GET · truncated response for {} ({} bytes), giving up", path, body.len());
+            return Err(Error::Truncated(Truncated { path: path.to_string(), got: body.len(), expected }));
+        }
+        attempt += 1;
+        loge!("HTTP GET · truncated response for {} ({} bytes), retry {}/{}",
+            path, body.len(), attempt, retries);
+        thread::sleep(Duration::from_millis(RETRY_PAUSE_MS * attempt as u64));
+    }
+}
+
+/// GET a binary file (team logos). A short body is retried like `http_get`.
+pub fn http_get_bytes(path: &str) -> Result<Vec<u8>, Error> {
+    let retries = settings().retries;
+    let mut attempt = 0;
+    loop {
+        let (body, expected) = get_once(path)?;
+        if expected.is_none_or(|n| body.len() >= n) {
+            return Ok(body);
+        }
+        if attempt == retries {
+            return Err(Error::Truncated(Truncated { path: path.to_string(), got: body.len(), expected }));
+        }
+        attempt += 1;
+        thread::sleep(Duration::from_millis(RETRY_PAUSE_MS * attempt as u64));
+    }
+}
+
+/// One request. Returns the body and, when known, the length the server
+/// announced (Content-Length, or usize::MAX for a chunked body cut short).
+fn get_once(path: &str) -> Result<(Vec<u8>, Option<usize>), Error> {
+    let net = settings();
+    let (host, port, prefix) = net.site()?;
+    let full = join_prefix_and_path(&prefix, path);
+    let url = full_url(path);
+    let net_err = |source: NetError| Error::Net { url: url.clone(), source };
+    let io_err = |e: std::io::Error| NetError::from_io(&url, e);
+    logd!("HTTP GET → {}{}", host, &full);
 
     let t0 = Instant::now();
 
     // 1) Connect
     let t_connect0 = Instant::now();
-    let mut s = TcpStream::connect((HOST, 80))?;
-    s.set_read_timeout(Some(Duration::from_secs(15)))?;
-    s.set_write_timeout(Some(Duration::from_secs(15)))?;
+    let addr = (host.as_str(), port).to_socket_addrs().ok()
+        .and_then(|mut a| a.next())
+        .ok_or_else(|| net_err(NetError::Dns(host.clone())))?;
+    let mut s = TcpStream::connect_timeout(&addr, net.timeout()).map_err(io_err)?;
+    s.set_read_timeout(Some(net.timeout())).map_err(io_err)?;
+    s.set_write_timeout(Some(net.timeout())).map_err(io_err)?;
     let dt_connect = t_connect0.elapsed();
     logd!("HTTP GET · connected in {:?}", dt_connect);
 
@@ -33,10 +259,9 @@ pub fn http_get(path: &str) -> Result<String, Box<dyn std::error::Error>> {
     let t_write0 = Instant::now();
     let req = format!(
         "GET {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: bb_scrape/0.4\r\nConnection: close\r\nAccept-Encoding: identity\r\n\r\n",
-        full, HOST
+        full, host
     );
-    s.write_all(req.as_bytes())?;
-    s.flush()?;
+    s.write_all(req.as_bytes()).and_then(|_| s.flush()).map_err(io_err)?;
     let dt_write = t_write0.elapsed();
     logd!("HTTP GET · request sent in {:?}", dt_write);
 
@@ -51,13 +276,13 @@ pub fn http_get(path: &str) -> Result<String, Box<dyn std::error::Error>> {
     loop {
         let mut line = String::new();
         // blocking read of a line
-        let n = br.read_line(&mut line)?;
+        let n = br.read_line(&mut line).map_err(io_err)?;
         if first_byte_at.is_none() && n > 0 {
             first_byte_at = Some(Instant::now());
             logd!("HTTP GET · first byte after {:?}", t_read0.elapsed());
         }
         if n == 0 {
-            return Err("EOF before headers complete".into());
+            return Err(net_err(NetError::Parse(s!("connection closed before the headers"))));
         }
         header_buf.extend_from_slice(line.as_bytes());
         if header_buf.ends_with(b"\r\n\r\n") || header_buf.ends_with(b"\n\n") {
@@ -74,9 +299,13 @@ pub fn http_get(path: &str) -> Result<String, Box<dyn std::error::Error>> {
     let headers = String::from_utf8_lossy(&header_buf);
     let mut lines = headers.split("\r\n").filter(|l| !l.is_empty());
     let status = lines.next().unwrap_or("");
-    if !status.contains("200") {
-        loge!("HTTP GET · status not OK: {}", status);
-        return Err(format!("HTTP error: {} {}{}", status, HOST, full).into());
+    match status_code(status) {
+        Some(200) => {}
+        Some(code) => {
+            loge!("HTTP GET · status not OK: {} {}{}", status, host, full);
+            return Err(net_err(NetError::HttpStatus(code)));
+        }
+        None => return Err(net_err(NetError::Parse(format!("status line {:?}", status)))),
     }
 
     let mut content_length: Option<usize> = None;
@@ -98,12 +327,13 @@ pub fn http_get(path: &str) -> Result<String, Box<dyn std::error::Error>> {
     // 5) Read body (don’t wait for close when CL is present)
     let t_body0 = Instant::now();
     let mut body: Vec<u8> = Vec::new();
+    let mut expected: Option<usize> = content_length;
 
     if let Some(len) = content_length {
         body.reserve_exact(len);
         // read_exact on the underlying reader after the headers already consumed
         let mut take = br.take(len as u64);
-        let _ = take.read_to_end(&mut body)?;
+        let _ = take.read_to_end(&mut body).map_err(io_err)?;
         if body.len() != len {
             loge!(
                 "HTTP GET · short read: expected {} bytes, got {}",
@@ -114,8 +344,13 @@ pub fn http_get(path: &str) -> Result<String, Box<dyn std::error::Error>> {
     } else if chunked {
         loop {
             let mut size_line = String::new();
-            br.read_line(&mut size_line)?;
-            if size_line.is_empty() { break; }
+            br.read_line(&mut size_line).map_err(io_err)?;
+            if size_line.is_empty() {
+                // Connection closed before the terminating 0-size chunk
+                loge!("HTTP GET · chunked body ended early after {} bytes", body.len());
+                expected = Some(usize::MAX);
+                break;
+            }
             let size_hex = size_line.trim();
             let size = usize::from_str_radix(size_hex, 16).unwrap_or(0);
             if size == 0 {
@@ -124,14 +359,19 @@ pub fn http_get(path: &str) -> Result<String, Box<dyn std::error::Error>> {
                 let _ = br.read_exact(&mut _crlf);
                 break;
             }
-            let mut chunk = vec![0u8; size];
-            br.read_exact(&mut chunk)?;
+            let mut chunk = Vec::with_capacity(size);
+            (&mut br).take(size as u64).read_to_end(&mut chunk).map_err(io_err)?;
             body.extend_from_slice(&chunk);
+            if chunk.len() < size {
+                loge!("HTTP GET · chunk cut short: {} of {} bytes", chunk.len(), size);
+                expected = Some(usize::MAX);
+                break;
+            }
             let mut _crlf = [0u8; 2];
             let _ = br.read_exact(&mut _crlf);
         }
     } else {
-        br.read_to_end(&mut body)?;
+        br.read_to_end(&mut body).map_err(io_err)?;
     }
 
     let dt_body = t_body0.elapsed();
@@ -144,12 +384,152 @@ pub fn http_get(path: &str) -> Result<String, Box<dyn std::error::Error>> {
     logd!("HTTP GET · TTFB {:?}", dt_ttfb);
     logd!("HTTP GET ← done total {:?}", total);
 
-    Ok(String::from_utf8_lossy(&body).into_owned())
+    Ok((body, expected))
+}
+
+/// Scheme, host, port and path of an `http://` or `https://` URL.
+fn split_url(url: &str) -> Option<(&str, &str, u16, &str)> {
+    let (scheme, rest) = url.trim().split_once("://")?;
+    let default_port = match scheme { "http" => 80, "https" => 443, _ => return None };
+    let (authority, path) = rest.find('/').map(|i| rest.split_at(i)).unwrap_or((rest, "/"));
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((h, p)) => (h, p.parse().ok()?),
+        None => (authority, default_port),
+    };
+    (!host.is_empty()).then_some((scheme, host, port, path))
+}
+
+/// `url` without its path and query, for logs and errors: webhook paths
+/// carry the secret token.
+pub fn redact_url(url: &str) -> String {
+    match split_url(url) {
+        Some((scheme, host, _, path)) if path.len() > 1 => format!("{}://{}/…", scheme, host),
+        Some((scheme, host, _, _)) => format!("{}://{}", scheme, host),
+        None => s!("<url>"),
+    }
+}
+
+/// `s` as a quoted value in a curl config file.
+fn curl_quote(s: &str) -> String {
+    let escaped = s.replace('\\', "\\\\").replace('"', "\\\"")
+        .replace('\n', "\\n").replace('\r', "\\r").replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
+
+/// POST a JSON body (webhooks). `http://` goes over a plain socket; for
+/// `https://` (Discord and most hosted webhooks) there's no TLS in std, so
+/// it's handed to `curl`, which ships with Windows 10+, macOS and most Linux.
+/// The URL and body go to curl as a config on stdin, not on its command
+/// line, where other users could read the token. Both ways time out after
+/// the network timeout. Fails on a non-2xx status.
+pub fn http_post_json(url: &str, body: &str) -> Result<(), Error> {
+    let net = settings();
+    let shown = redact_url(url);
+    let io_err = |e: std::io::Error| NetError::from_io(&shown, e);
+    let (scheme, host, port, path) = split_url(url).ok_or_else(|| format!("Not an http(s) URL: {}", shown))?;
+    logd!("HTTP POST → {} ({} bytes)", shown, body.len());
+
+    let status = if scheme == "https" {
+        let secs = net.timeout().as_secs().to_string();
+        let mut child = Command::new("curl")
+            .args(["-sS", "-o", "-", "-w", "\n%{http_code}", "-X", "POST",
+                   "-H", "Content-Type: application/json",
+                   "--connect-timeout", &secs, "--max-time", &secs, "-K", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("https needs curl on the PATH ({})", e))?;
+        let config = format!("url = {}\ndata-binary = {}\n", curl_quote(url), curl_quote(body));
+        child.stdin.take().ok_or("curl: no stdin")?.write_all(config.as_bytes()).map_err(io_err)?;
+        let out = child.wait_with_output().map_err(io_err)?;
+        if !out.status.success() {
+            let msg = String::from_utf8_lossy(&out.stderr).replace(url, &shown);
+            return Err(format!("curl: {}", msg.trim()).into());
+        }
+        let stdout = String::from_utf8_lossy(&out.stdout);
+        stdout.lines().last().unwrap_or("").trim().parse::<u16>().unwrap_or(0)
+    } else {
+        let addr = (host, port).to_socket_addrs().ok()
+            .and_then(|mut a| a.next())
+            .ok_or_else(|| Error::Net { url: shown.clone(), source: NetError::Dns(host.to_string()) })?;
+        let mut s = TcpStream::connect_timeout(&addr, net.timeout()).map_err(io_err)?;
+        s.set_read_timeout(Some(net.timeout())).map_err(io_err)?;
+        s.set_write_timeout(Some(net.timeout())).map_err(io_err)?;
+        let req = format!(
+            "POST {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: bb_scrape/0.4\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            path, host, body.len()
+        );
+        s.write_all(req.as_bytes()).map_err(io_err)?;
+        s.write_all(body.as_bytes()).map_err(io_err)?;
+        s.flush().map_err(io_err)?;
+        let mut status_line = String::new();
+        BufReader::new(s).read_line(&mut status_line).map_err(io_err)?;
+        status_line.split_whitespace().nth(1).and_then(|c| c.parse().ok()).unwrap_or(0)
+    };
+
+    if !(200..300).contains(&status) {
+        return Err(Error::Net { url: shown, source: NetError::HttpStatus(status) });
+    }
+    logd!("HTTP POST ← {}", status);
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::join_prefix_and_path;
+    use super::{curl_quote, join_prefix_and_path, looks_truncated, redact_url, split_url, status_code, NetError, NetSettings};
+
+    #[test]
+    fn splits_webhook_urls() {
+        assert_eq!(split_url("https://discord.com/api/webhooks/1/abc"),
+                   Some(("https", "discord.com", 443, "/api/webhooks/1/abc")));
+        assert_eq!(split_url("http://localhost:9000"), Some(("http", "localhost", 9000, "/")));
+        assert_eq!(split_url("ftp://x/y"), None);
+        assert_eq!(split_url("discord.com/api"), None);
+    }
+
+    #[test]
+    fn webhook_tokens_stay_out_of_logs_and_argv() {
+        assert_eq!(redact_url("https://discord.com/api/webhooks/1/secret"), "https://discord.com/…");
+        assert_eq!(redact_url("http://localhost:9000"), "http://localhost");
+        assert_eq!(curl_quote("a\"b\\c\nd"), "\"a\\\"b\\\\c\\nd\"");
+    }
+
+    #[test]
+    fn site_setting_takes_plain_http_only() {
+        let site = |url: &str| NetSettings { base_url: s!(url), ..NetSettings::default() }.site();
+        assert!(NetSettings::default().site().is_ok());
+        assert_eq!(site("http://localhost:8080/bb/"), Ok((s!("localhost"), 8080, s!("/bb/"))));
+        assert!(site("https://example.com/").is_err());
+        assert!(site("example.com").is_err());
+    }
+
+    #[test]
+    fn reads_status_codes_and_finds_net_errors() {
+        assert_eq!(status_code("HTTP/1.1 503 Service Unavailable"), Some(503));
+        assert_eq!(status_code("HTTP/1.0 200 OK"), Some(200));
+        assert_eq!(status_code("<html>"), None);
+
+        let e: Box<dyn std::error::Error> = Box::new(crate::error::Error::Net { url: s!("u"), source: NetError::HttpStatus(503) });
+        let found = NetError::find(e.as_ref());
+        assert_eq!(found, Some(&NetError::HttpStatus(503)));
+        assert_eq!(found.map(NetError::exit_code), Some(6));
+        assert_eq!(found.map(|n| n.to_string()).as_deref(), Some("Site returned 503 — try later"));
+        let other: Box<dyn std::error::Error> = "boom".into();
+        assert_eq!(NetError::find(other.as_ref()), None);
+    }
+
+    #[test]
+    fn detects_cut_off_documents() {
+        let full = "<html><body><table><tr><td>x</td></tr></table></body></html>";
+        assert!(!looks_truncated(full));
+        // Cut inside the table
+        assert!(looks_truncated("<html><body><table><tr><td>x</td>"));
+        // Cut mid-tag
+        assert!(looks_truncated("<html><body><p>x</p><di"));
+        // Fragments without <html> aren't judged
+        assert!(!looks_truncated("<table><tr><td>x"));
+    }
 
     #[test]
     fn join_handles_slashes() {
@@ -13,16 +13,35 @@ pub fn normalize_ws(s: &str) -> String {
     }
     out.trim().to_string()
 }
+/// Longest stem `sanitize_team_filename` returns (in chars).
+const MAX_STEM_CHARS: usize = 64;
+
+/// Device names Windows won't create as files, with any extension.
+const WINDOWS_RESERVED: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL",
+    "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// File stem for a team's export file, safe on Windows, macOS and Linux.
+/// Keeps letters and digits of any script (accents, Cyrillic, Hebrew, …),
+/// `-` and `_`; whitespace runs become `_`. Everything else is dropped:
+/// path separators, quotes, `<>:|?*`, emoji, control and bidi characters.
+/// Reserved device names get a trailing `_`. Falls back to `team_<id>`.
 pub fn sanitize_team_filename(name: &str, id: u32) -> String {
     let mut out = String::with_capacity(name.len());
     let mut last_us = false;
     for ch in name.chars() {
-        if ch.is_ascii_alphanumeric() { out.push(ch); last_us = false; }
+        if ch.is_alphanumeric() { out.push(ch); last_us = false; }
         else if ch.is_whitespace() { if !last_us { out.push('_'); last_us = true; } }
         else if ch=='-' || ch=='_' { if !(last_us && ch=='_') { out.push(ch); } last_us = ch=='_'; }
     }
-    let out = out.trim_matches('_').to_string();
-    if out.is_empty() { format!("team_{}", id) } else { out }
+    let mut out: String = out.trim_matches('_').chars().take(MAX_STEM_CHARS).collect();
+    if out.is_empty() { return format!("team_{}", id); }
+    if WINDOWS_RESERVED.iter().any(|r| r.eq_ignore_ascii_case(&out)) {
+        out.push('_');
+    }
+    out
 }
 
 /// Remove any `[ ... ]` bracket tags (e.g. `[CAPTAIN]`, `[unavailable…]`).
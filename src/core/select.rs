@@ -0,0 +1,354 @@
+// src/core/select.rs
+//
+// Tiny CSS-like selector matching for ad-hoc extraction (`cli extract`).
+// Supports descendant chains of simple selectors: `tag`, `.class`, `#id`,
+// `*`, and combinations like `table.teamroster tr.playerrow td`.
+// No attribute selectors, child combinators or pseudo-classes.
+// `leaf_tables` walks the same element tree for pages without stable
+// classes to select on (player.php).
+//
+// The HTML is parsed leniently into a flat element list with parent links;
+// table/list tags that the site leaves unclosed are closed implicitly.
+
+use super::html::{strip_tags, to_lower};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Simple {
+    tag: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+}
+
+/// A parsed selector: simple selectors joined by descendant combinators.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector(Vec<Simple>);
+
+impl Selector {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut parts = Vec::new();
+        for tok in s.split_whitespace() {
+            if tok.contains(['>', '+', '~', '[', ':']) {
+                return Err(format!("Unsupported selector syntax: {}", tok));
+            }
+            let mut simple = Simple { tag: None, id: None, classes: Vec::new() };
+            // Split on '.' / '#' while keeping which marker started each piece
+            let mut marker = ' ';
+            let mut cur = String::new();
+            let flush = |marker: char, cur: &mut String, simple: &mut Simple| -> Result<(), String> {
+                let v = to_lower(cur);
+                cur.clear();
+                match marker {
+                    ' ' if v.is_empty() || v == "*" => {}
+                    ' ' => simple.tag = Some(v),
+                    '.' if !v.is_empty() => simple.classes.push(v),
+                    '#' if !v.is_empty() => simple.id = Some(v),
+                    _ => return Err(format!("Empty class or id in: {}", tok)),
+                }
+                Ok(())
+            };
+            for ch in tok.chars() {
+                if ch == '.' || ch == '#' {
+                    flush(marker, &mut cur, &mut simple)?;
+                    marker = ch;
+                } else {
+                    cur.push(ch);
+                }
+            }
+            flush(marker, &mut cur, &mut simple)?;
+            parts.push(simple);
+        }
+        if parts.is_empty() {
+            return Err("Empty selector".into());
+        }
+        Ok(Selector(parts))
+    }
+}
+
+#[derive(Debug)]
+struct Element {
+    tag: String,
+    id: Option<String>,
+    classes: Vec<String>,
+    parent: Option<usize>,
+    /// Byte range of the inner HTML (between the open and close tags)
+    inner: (usize, usize),
+}
+
+const VOID: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input",
+    "link", "meta", "param", "source", "track", "wbr",
+];
+
+/// Opening `tag` implicitly closes open elements listed here (up to a boundary).
+fn implicit_close(tag: &str) -> (&'static [&'static str], &'static [&'static str]) {
+    match tag {
+        "td" | "th" => (&["td", "th"], &["tr", "table"]),
+        "tr" => (&["tr", "td", "th"], &["table", "tbody", "thead", "tfoot"]),
+        "tbody" | "thead" | "tfoot" => (&["tbody", "thead", "tfoot", "tr", "td", "th"], &["table"]),
+        "li" => (&["li"], &["ul", "ol"]),
+        "option" => (&["option"], &["select"]),
+        _ => (&[], &[]),
+    }
+}
+
+/// Value of attribute `name` in an open tag's text (`<img src="a.png" …>`), quoted or bare.
+pub fn attr_value(attrs: &str, name: &str) -> Option<String> {
+    let lc = to_lower(attrs);
+    let mut from = 0;
+    while let Some(rel) = lc[from..].find(name) {
+        let at = from + rel;
+        from = at + name.len();
+        // whole-word attribute name followed by '='
+        let before_ok = at == 0 || lc.as_bytes()[at - 1].is_ascii_whitespace();
+        let rest = lc[from..].trim_start();
+        if !before_ok || !rest.starts_with('=') { continue; }
+        let vstart = attrs.len() - rest.len() + 1;
+        let v = attrs[vstart..].trim_start();
+        let val = match v.chars().next() {
+            Some(q @ ('"' | '\'')) => v[1..].split(q).next().unwrap_or(""),
+            _ => v.split(|c: char| c.is_whitespace() || c == '>').next().unwrap_or(""),
+        };
+        return Some(val.to_string());
+    }
+    None
+}
+
+fn parse_elements(html: &str) -> Vec<Element> {
+    let lc = to_lower(html);
+    let mut out: Vec<Element> = Vec::new();
+    let mut open: Vec<usize> = Vec::new();
+    let mut i = 0;
+
+    let close_top = |out: &mut Vec<Element>, open: &mut Vec<usize>, at: usize| {
+        if let Some(ix) = open.pop() { out[ix].inner.1 = at; }
+    };
+
+    while let Some(rel) = lc[i..].find('<') {
+        let lt = i + rel;
+        if lc[lt..].starts_with("<!--") {
+            i = lc[lt..].find("-->").map(|e| lt + e + 3).unwrap_or(lc.len());
+            continue;
+        }
+        let Some(gt_rel) = lc[lt..].find('>') else { break };
+        let gt = lt + gt_rel;
+        let body = &html[lt + 1..gt];
+        i = gt + 1;
+
+        if let Some(name) = body.strip_prefix('/') {
+            let name = to_lower(name.trim());
+            if let Some(pos) = open.iter().rposition(|&ix| out[ix].tag == name) {
+                while open.len() > pos { close_top(&mut out, &mut open, lt); }
+            }
+            continue;
+        }
+        if body.starts_with('!') || body.starts_with('?') { continue; }
+
+        let name_end = body.find(|c: char| c.is_whitespace() || c == '/').unwrap_or(body.len());
+        let tag = to_lower(&body[..name_end]);
+        if tag.is_empty() { continue; }
+        let attrs = &body[name_end..];
+
+        let (closes, boundary) = implicit_close(&tag);
+        while let Some(&top) = open.last() {
+            let t = out[top].tag.as_str();
+            if boundary.contains(&t) || !closes.contains(&t) { break; }
+            close_top(&mut out, &mut open, lt);
+        }
+
+        let classes = attr_value(attrs, "class")
+            .map(|c| c.split_whitespace().map(to_lower).collect())
+            .unwrap_or_default();
+        out.push(Element {
+            tag: tag.clone(),
+            id: attr_value(attrs, "id").map(|v| to_lower(&v)),
+            classes,
+            parent: open.last().copied(),
+            inner: (i, i),
+        });
+        let ix = out.len() - 1;
+
+        if tag == "script" || tag == "style" {
+            let close = format!("</{}", tag);
+            let end = lc[i..].find(&close).map(|e| i + e).unwrap_or(lc.len());
+            out[ix].inner.1 = end;
+            i = end;
+            continue;
+        }
+        if VOID.contains(&tag.as_str()) || attrs.trim_end().ends_with('/') { continue; }
+        open.push(ix);
+    }
+    while !open.is_empty() { close_top(&mut out, &mut open, html.len()); }
+    out
+}
+
+fn simple_matches(s: &Simple, e: &Element) -> bool {
+    s.tag.as_ref().is_none_or(|t| *t == e.tag)
+        && s.id.as_ref().is_none_or(|id| e.id.as_ref() == Some(id))
+        && s.classes.iter().all(|c| e.classes.contains(c))
+}
+
+fn matches(sel: &Selector, els: &[Element], ix: usize) -> bool {
+    let (last, ancestors) = sel.0.split_last().expect("non-empty selector");
+    if !simple_matches(last, &els[ix]) { return false; }
+    let mut cur = els[ix].parent;
+    for s in ancestors.iter().rev() {
+        loop {
+            let Some(p) = cur else { return false };
+            cur = els[p].parent;
+            if simple_matches(s, &els[p]) { break; }
+        }
+    }
+    true
+}
+
+/// Text of every element matching `sel`, in document order.
+pub fn select_text(html: &str, sel: &Selector) -> Vec<String> {
+    let els = parse_elements(html);
+    (0..els.len())
+        .filter(|&ix| matches(sel, &els, ix))
+        .map(|ix| strip_tags(&html[els[ix].inner.0..els[ix].inner.1]))
+        .collect()
+}
+
+/// Matches shaped as a table: cells (`td`/`th`) are grouped by their row,
+/// anything else is one single-cell row per match.
+pub fn select_rows(html: &str, sel: &Selector) -> Vec<Vec<String>> {
+    let els = parse_elements(html);
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut last_tr: Option<usize> = None;
+
+    for ix in (0..els.len()).filter(|&ix| matches(sel, &els, ix)) {
+        let text = strip_tags(&html[els[ix].inner.0..els[ix].inner.1]);
+        let e = &els[ix];
+        let tr = if e.tag == "td" || e.tag == "th" {
+            e.parent.filter(|&p| els[p].tag == "tr")
+        } else {
+            None
+        };
+        match (tr, last_tr) {
+            (Some(t), Some(l)) if t == l => rows.last_mut().unwrap().push(text),
+            _ => rows.push(vec![text]),
+        }
+        last_tr = tr;
+    }
+    rows
+}
+
+/// Text of a table that has no tables nested inside it (layout tables are skipped).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableText {
+    /// `<caption>`, or the nearest `h1`–`h4` between the previous table and this one
+    pub heading: Option<String>,
+    /// Cell text (`td`/`th`) per row, in document order
+    pub rows: Vec<Vec<String>>,
+    /// Leading rows made of `th` cells only
+    pub header_rows: usize,
+}
+
+fn nearest_table(els: &[Element], ix: usize) -> Option<usize> {
+    let mut cur = els[ix].parent;
+    while let Some(p) = cur {
+        if els[p].tag == "table" { return Some(p); }
+        cur = els[p].parent;
+    }
+    None
+}
+
+/// Every innermost `<table>` in the document, in order.
+pub fn leaf_tables(html: &str) -> Vec<TableText> {
+    let els = parse_elements(html);
+    let text = |ix: usize| strip_tags(&html[els[ix].inner.0..els[ix].inner.1]);
+
+    let outer: Vec<usize> = (0..els.len())
+        .filter(|&ix| els[ix].tag == "table")
+        .filter_map(|ix| nearest_table(&els, ix))
+        .collect();
+
+    let mut out = Vec::new();
+    let mut prev_end = 0usize;
+    for t in (0..els.len()).filter(|&ix| els[ix].tag == "table" && !outer.contains(&ix)) {
+        let caption = (t + 1..els.len())
+            .find(|&ix| els[ix].tag == "caption" && nearest_table(&els, ix) == Some(t))
+            .map(text);
+        let heading = caption.or_else(|| {
+            (0..t).rev()
+                .take_while(|&ix| els[ix].inner.0 > prev_end)
+                .find(|&ix| matches!(els[ix].tag.as_str(), "h1" | "h2" | "h3" | "h4"))
+                .map(text)
+        }).filter(|h| !h.is_empty());
+
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        let mut th_only: Vec<bool> = Vec::new();
+        let mut last_tr: Option<usize> = None;
+        for c in (t + 1..els.len()).filter(|&ix| {
+            matches!(els[ix].tag.as_str(), "td" | "th") && nearest_table(&els, ix) == Some(t)
+        }) {
+            let tr = els[c].parent.filter(|&p| els[p].tag == "tr");
+            let is_th = els[c].tag == "th";
+            match (tr, last_tr) {
+                (Some(a), Some(b)) if a == b => {
+                    rows.last_mut().unwrap().push(text(c));
+                    *th_only.last_mut().unwrap() &= is_th;
+                }
+                _ => {
+                    rows.push(vec![text(c)]);
+                    th_only.push(is_th);
+                }
+            }
+            last_tr = tr;
+        }
+
+        prev_end = els[t].inner.1;
+        out.push(TableText {
+            heading,
+            header_rows: th_only.iter().take_while(|&&b| b).count(),
+            rows,
+        });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = r#"
+        <html><body>
+        <table class="teamroster"><tr><th>Name<th>#
+        <tr class="playerrow"><td><a href="x">Alice</a><td>7
+        <tr class="playerrow odd"><td>Bob</td><td>9</td></tr>
+        </table>
+        <div id="Foot" class="note">Done <b>here</b></div>
+        </body></html>"#;
+
+    #[test]
+    fn selects_cells_grouped_by_row() {
+        let sel = Selector::parse("table.teamroster tr.playerrow td").unwrap();
+        assert_eq!(select_rows(DOC, &sel), vec![vec!["Alice", "7"], vec!["Bob", "9"]]);
+    }
+
+    #[test]
+    fn id_class_and_unsupported_syntax() {
+        let sel = Selector::parse("div#foot.note").unwrap();
+        assert_eq!(select_text(DOC, &sel), vec!["Done here"]);
+        assert!(Selector::parse("tr > td").is_err());
+        assert!(Selector::parse("  ").is_err());
+    }
+
+    #[test]
+    fn leaf_tables_skip_layout_and_find_headings() {
+        let doc = r#"
+            <table><tr><td>
+              <h3>Skills</h3>
+              <table><tr><th>ST<th>AG<tr><td>3<td>4</table>
+              <table><caption>Contract</caption><tr><td>Salary<td>50k</tr></table>
+            </td></tr></table>"#;
+        let t = leaf_tables(doc);
+        assert_eq!(t.len(), 2);
+        assert_eq!(t[0].heading.as_deref(), Some("Skills"));
+        assert_eq!(t[0].header_rows, 1);
+        assert_eq!(t[0].rows, vec![vec!["ST", "AG"], vec!["3", "4"]]);
+        assert_eq!(t[1].heading.as_deref(), Some("Contract"));
+        assert_eq!(t[1].rows, vec![vec!["Salary", "50k"]]);
+    }
+}
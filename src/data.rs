@@ -10,43 +10,102 @@
 // Common utilities that make sense at the dataset level live as methods
 // on DataSet itself (see src/store.rs).
 
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::io;
 use std::path::PathBuf;
+use std::sync::OnceLock;
 
 use crate::store::DataSet;
+use crate::typed::TypedColumns;
 use crate::gui::pages::Page;
 use crate::config::options::PageKind;
 
 /// Authoritative, canonical page dataset.
 /// Loaded from cache at startup; updated only by SCRAPE.
+/// Carries the page's user columns (`derive::apply_user`) last; they're
+/// left out of what's saved.
 #[derive(Clone, Debug)]
 pub struct RawData {
     kind: PageKind,
     ds: DataSet,
+    /// How many of the last columns are user columns
+    user_cols: usize,
+    /// Parsed numeric columns, built on first use; reset by the mutators
+    typed: OnceLock<TypedColumns>,
+    /// Rows per team name, built on first use; reset by the mutators
+    team_rows: OnceLock<HashMap<String, usize>>,
 }
 
 impl RawData {
     /// Build from a freshly loaded cache dataset.
-    pub fn new(kind: PageKind, ds: DataSet) -> Self { Self { kind, ds } }
+    pub fn new(kind: PageKind, ds: DataSet) -> Self {
+        let mut raw = Self { kind, ds, user_cols: 0, typed: OnceLock::new(), team_rows: OnceLock::new() };
+        raw.refresh_user_columns();
+        raw
+    }
     pub fn kind(&self) -> PageKind { self.kind }
 
-    /// Read-only view of the dataset.
+    /// Read-only view of the dataset, user columns included.
     pub fn dataset(&self) -> &DataSet { &self.ds }
 
+    /// The dataset without the user columns, as cached.
+    pub fn base_dataset(&self) -> Cow<'_, DataSet> {
+        if self.user_cols == 0 { return Cow::Borrowed(&self.ds); }
+        let mut ds = self.ds.clone();
+        crate::derive::strip_user(&mut ds, self.user_cols);
+        Cow::Owned(ds)
+    }
+
+    /// Recompute the user columns, e.g. after their formulas changed.
+    pub fn refresh_user_columns(&mut self) {
+        crate::derive::strip_user(&mut self.ds, self.user_cols);
+        self.user_cols = crate::derive::apply_user(self.kind, &mut self.ds);
+        self.typed = OnceLock::new();
+        self.team_rows = OnceLock::new();
+    }
+
+    /// The dataset's columns parsed by the page schema (for sorting).
+    pub fn typed(&self) -> &TypedColumns {
+        self.typed.get_or_init(|| TypedColumns::for_page(self.kind, &self.ds))
+    }
+
+    /// How many rows name each team in one of the page's team columns
+    /// (team panel); a game counts for both sides.
+    pub fn team_row_counts(&self) -> &HashMap<String, usize> {
+        self.team_rows.get_or_init(|| {
+            let cols = crate::gui::router::page_for(&self.kind).team_columns();
+            let mut counts: HashMap<String, usize> = HashMap::new();
+            for r in &self.ds.rows {
+                let mut names: Vec<&str> = cols.iter().filter_map(|&c| r.get(c)).map(|t| t.trim()).filter(|t| !t.is_empty()).collect();
+                names.dedup();
+                for name in names {
+                    *counts.entry(name.to_string()).or_default() += 1;
+                }
+            }
+            counts
+        })
+    }
+
     pub fn save(&self) -> io::Result<PathBuf> {
-        crate::store::save_dataset(&self.kind, &self.ds)
+        crate::store::save_dataset(&self.kind, &self.base_dataset())
     }
 
     /// Merge in newly scraped data using the page's merge policy.
     /// This is the *only* mutator; keeps the rest of the app read-only.
     pub fn merge_from_scrape(&mut self, page: &dyn Page, new: DataSet) {
-        page.merge(&mut self.ds, new);
+        self.update(|ds| page.merge(ds, new));
     }
 
-    /// Mutable access for I/O boundaries that require &mut DataSet (rare).
-    /// Prefer `apply_scrape_merge()` for scrape updates instead of mutating directly.
-    pub fn dataset_mut_for_io(&mut self) -> &mut DataSet {
-        &mut self.ds
+    /// Mutable access to the dataset without its user columns, for I/O
+    /// boundaries (derived columns, renames); they're recomputed after.
+    /// Prefer `merge_from_scrape()` for scrape updates.
+    pub fn update<R>(&mut self, f: impl FnOnce(&mut DataSet) -> R) -> R {
+        crate::derive::strip_user(&mut self.ds, self.user_cols);
+        self.user_cols = 0;
+        let out = f(&mut self.ds);
+        self.refresh_user_columns();
+        out
     }
 }
 
@@ -102,11 +161,9 @@ pub struct SelectionView<'a> {
 
 impl<'a> SelectionView<'a> {
     /// Build a filtered view from RawData and a page's selection filter.
-    /// NOTE: Since the Page trait exposes only a row-vector filter and not
-    /// a per-row predicate, we compute indices by comparing filtered rows
-    /// back against the raw rows. This is O(n^2) worst-case but avoids
-    /// retaining duplicate owned data in App. If needed later, we can add
-    /// a predicate API to Page for true O(n) zero-copy selection.
+    /// NOTE: Pages without `filter_row_indices_for_selection` only return
+    /// filtered rows; those are mapped back to raw indices through a
+    /// row → positions lookup (O(n)), so App never keeps the owned copy.
     pub fn from_raw(
         page: &dyn Page,
         raw: &'a RawData,
@@ -122,21 +179,19 @@ impl<'a> SelectionView<'a> {
             return Self { row_ix: ix, raw: ds };
         }
 
-        // Fallback: Get owned rows, then remap to indices: O(n²)
+        // Fallback: Get owned rows, then remap to indices. Equal rows map to
+        // their raw positions in order, so duplicates keep distinct indices.
         let filtered_rows = page.filter_rows_for_selection(sel.ids, sel.teams, &ds.rows);
 
-        let mut row_ix = Vec::with_capacity(filtered_rows.len());
-        let mut used = vec![false; ds.rows.len()];
-        'outer: for fr in &filtered_rows {
-            for (i, rr) in ds.rows.iter().enumerate() {
-                if !used[i] && rr == fr {
-                    used[i] = true;
-                    row_ix.push(i);
-                    continue 'outer;
-                }
-            }
-            // If no match, skip silently (defensive)
+        let mut positions: std::collections::HashMap<&[String], std::collections::VecDeque<usize>> =
+            std::collections::HashMap::new();
+        for (i, rr) in ds.rows.iter().enumerate() {
+            positions.entry(rr.as_slice()).or_default().push_back(i);
         }
+        // If no match, skip silently (defensive)
+        let row_ix: Vec<usize> = filtered_rows.iter()
+            .filter_map(|fr| positions.get_mut(fr.as_slice()).and_then(|q| q.pop_front()))
+            .collect();
 
         Self { row_ix, raw: ds }
     }
@@ -159,4 +214,339 @@ impl<'a> SelectionView<'a> {
     pub fn from_indices(raw: &'a RawData, row_ix: Vec<usize>) -> Self {
         Self { row_ix, raw: raw.dataset() }
     }
-}
\ No newline at end of file
+}
+/// Per-page sort applied on top of the selection view (GUI header click).
+/// Sorting reorders `row_ix` only; canonical data stays in scrape order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SortKey {
+    /// Source column index (not display position)
+    pub col: usize,
+    pub descending: bool,
+}
+
+impl SortKey {
+    /// Header click cycle for `col`: ascending → descending → off.
+    pub fn cycle(current: Option<SortKey>, col: usize) -> Option<SortKey> {
+        match current {
+            Some(k) if k.col == col && !k.descending => Some(SortKey { col, descending: true }),
+            Some(k) if k.col == col => None,
+            _ => Some(SortKey { col, descending: false }),
+        }
+    }
+
+    /// Stable sort of `row_ix` by this key.
+    /// Numeric columns compare by value (a leading '#' is ignored, e.g. "#27");
+    /// cells that don't parse go after the numbers in both directions.
+    /// Text columns compare case-insensitively.
+    ///
+    /// Each cell is parsed / lowercased once up front rather than on every
+    /// comparison, which is what keeps 100k-row sorts quick.
+    pub fn sort_row_ix(&self, rows: &[Vec<String>], row_ix: &mut [usize], numeric: bool) {
+        use std::cmp::Ordering;
+
+        fn number(s: &str) -> Option<f64> {
+            s.trim().trim_start_matches('#').parse::<f64>().ok()
+        }
+
+        let cell = |ix: usize| -> &str {
+            rows.get(ix).and_then(|r| r.get(self.col)).map(|s| s.as_str()).unwrap_or("")
+        };
+        let directed = |ord: Ordering| if self.descending { ord.reverse() } else { ord };
+
+        if numeric {
+            let mut keyed: Vec<(Option<f64>, &str, usize)> =
+                row_ix.iter().map(|&ix| (number(cell(ix)), cell(ix), ix)).collect();
+            keyed.sort_by(|(na, ca, _), (nb, cb, _)| match (na, nb) {
+                (Some(x), Some(y)) => directed(x.partial_cmp(y).unwrap_or(Ordering::Equal)),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => directed(ca.cmp(cb)),
+            });
+            for (slot, (_, _, ix)) in row_ix.iter_mut().zip(keyed) { *slot = ix; }
+        } else {
+            let mut keyed: Vec<(String, usize)> =
+                row_ix.iter().map(|&ix| (cell(ix).to_lowercase(), ix)).collect();
+            keyed.sort_by(|(a, _), (b, _)| directed(a.cmp(b)));
+            for (slot, (_, ix)) in row_ix.iter_mut().zip(keyed) { *slot = ix; }
+        }
+    }
+
+    /// `sort_row_ix` using already parsed numbers when the schema types the
+    /// column as numeric; other columns sort as before.
+    pub fn sort_row_ix_typed(
+        &self,
+        rows: &[Vec<String>],
+        typed: &TypedColumns,
+        row_ix: &mut [usize],
+        numeric: bool,
+    ) {
+        use std::cmp::Ordering;
+
+        if !(numeric && typed.is_numeric(self.col)) {
+            return self.sort_row_ix(rows, row_ix, numeric);
+        }
+        let cell = |ix: usize| -> &str {
+            rows.get(ix).and_then(|r| r.get(self.col)).map(|s| s.as_str()).unwrap_or("")
+        };
+        let directed = |ord: Ordering| if self.descending { ord.reverse() } else { ord };
+        row_ix.sort_by(|&a, &b| match (typed.number(a, self.col), typed.number(b, self.col)) {
+            (Some(x), Some(y)) => directed(x.partial_cmp(&y).unwrap_or(Ordering::Equal)),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => directed(cell(a).cmp(cell(b))),
+        });
+    }
+}
+
+/// Per-row change between a previous snapshot and the current dataset.
+/// Returns one row per `cur.rows` entry (same indices), so a view's `row_ix`
+/// can address it directly.
+///
+/// Rows are matched on `key_cols`. Key and `non_numeric` columns are passed
+/// through; numeric cells become signed differences ("+5", "-1"), blank when
+/// unchanged. Rows without a previous match count from zero.
+pub fn delta_rows(
+    prev: &DataSet,
+    cur: &DataSet,
+    key_cols: &[usize],
+    non_numeric: &[usize],
+) -> Vec<Vec<String>> {
+    use std::collections::HashMap;
+
+    fn number(s: &str) -> Option<f64> {
+        s.trim().trim_start_matches('#').parse::<f64>().ok()
+    }
+
+    fn signed(d: f64) -> String {
+        if d == 0.0 { return String::new(); }
+        let sign = if d > 0.0 { "+" } else { "-" };
+        let a = d.abs();
+        if a.fract() == 0.0 { format!("{}{}", sign, a as i64) }
+        else { format!("{}{}", sign, (a * 100.0).round() / 100.0) }
+    }
+
+    let key = |r: &Vec<String>| -> Vec<String> {
+        key_cols.iter().map(|&c| r.get(c).cloned().unwrap_or_default()).collect()
+    };
+    let by_key: HashMap<Vec<String>, &Vec<String>> =
+        prev.rows.iter().map(|r| (key(r), r)).collect();
+
+    cur.rows.iter().map(|r| {
+        let old = by_key.get(&key(r));
+        r.iter().enumerate().map(|(ci, cell)| {
+            if key_cols.contains(&ci) || non_numeric.contains(&ci) {
+                return cell.clone();
+            }
+            let before = old.and_then(|o| o.get(ci)).map(|s| s.as_str()).unwrap_or("0");
+            match (number(cell), number(before)) {
+                (Some(now), Some(then)) => signed(now - then),
+                (Some(now), None) => signed(now),
+                _ => cell.clone(),
+            }
+        }).collect()
+    }).collect()
+}
+
+/// Free-text row filter from the search box above the table.
+/// Applied after team selection, before sorting.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TextFilter {
+    pub query: String,
+    /// Source column to search; `None` searches every column.
+    pub column: Option<usize>,
+}
+
+impl TextFilter {
+    pub fn is_active(&self) -> bool { !self.query.trim().is_empty() }
+
+    /// Case-insensitive substring match against the row.
+    pub fn matches(&self, row: &[String]) -> bool {
+        let needle = self.query.trim().to_lowercase();
+        if needle.is_empty() { return true; }
+        self.matches_lower(row, &needle, &mut String::new())
+    }
+
+    /// Keep only indices whose rows match.
+    pub fn retain(&self, rows: &[Vec<String>], row_ix: &mut Vec<usize>) {
+        if !self.is_active() { return; }
+        // Lowercase the query once and reuse one buffer for the cells
+        let needle = self.query.trim().to_lowercase();
+        let mut buf = String::new();
+        row_ix.retain(|&ix| rows.get(ix).is_some_and(|r| self.matches_lower(r, &needle, &mut buf)));
+    }
+
+    fn matches_lower(&self, row: &[String], needle: &str, buf: &mut String) -> bool {
+        let hit = |c: &String| {
+            buf.clear();
+            buf.extend(c.chars().flat_map(char::to_lowercase));
+            buf.contains(needle)
+        };
+        match self.column {
+            Some(ci) => row.get(ci).is_some_and(hit),
+            None => row.iter().any(hit),
+        }
+    }
+}
+
+/// Table cells stored once per distinct value: a string pool plus a `u32`
+/// id per cell. Stat tables repeat a lot ("", "0", "+1", team names, races),
+/// so this is a fraction of the size of one `String` per cell. Rows may have
+/// different lengths; cells are read back as borrowed `&str`. Holds the
+/// delta view (`App::delta_rows`) only; `RawData` still keeps owned rows.
+#[derive(Clone, Debug, Default)]
+pub struct CompactTable {
+    /// Distinct values back to back; `spans[id]` is the byte range of `id`
+    pool: String,
+    spans: Vec<(u32, u32)>,
+    cells: Vec<u32>,
+    /// Start of each row in `cells`, plus the end of the last one
+    row_start: Vec<u32>,
+}
+
+impl CompactTable {
+    pub fn from_rows<R, S>(rows: impl IntoIterator<Item = R>) -> Self
+    where
+        R: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        use std::collections::HashMap;
+
+        let mut t = CompactTable { row_start: vec![0], ..Default::default() };
+        // Only lives while building; the pool is the one copy kept
+        let mut ids: HashMap<String, u32> = HashMap::new();
+        for row in rows {
+            for cell in row {
+                let cell = cell.as_ref();
+                let id = match ids.get(cell) {
+                    Some(&id) => id,
+                    None => {
+                        let id = t.spans.len() as u32;
+                        let start = t.pool.len() as u32;
+                        t.pool.push_str(cell);
+                        t.spans.push((start, t.pool.len() as u32));
+                        ids.insert(cell.to_string(), id);
+                        id
+                    }
+                };
+                t.cells.push(id);
+            }
+            t.row_start.push(t.cells.len() as u32);
+        }
+        t.pool.shrink_to_fit();
+        t.spans.shrink_to_fit();
+        t.cells.shrink_to_fit();
+        t
+    }
+
+    /// Number of rows.
+    pub fn len(&self) -> usize { self.row_start.len().saturating_sub(1) }
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Number of distinct cell values stored.
+    pub fn distinct(&self) -> usize { self.spans.len() }
+
+    fn ids(&self, row: usize) -> Option<&[u32]> {
+        let (&a, &b) = (self.row_start.get(row)?, self.row_start.get(row + 1)?);
+        self.cells.get(a as usize..b as usize)
+    }
+
+    fn value(&self, id: u32) -> &str {
+        let (a, b) = self.spans[id as usize];
+        &self.pool[a as usize..b as usize]
+    }
+
+    /// Cell `col` of `row`, borrowed.
+    pub fn cell(&self, row: usize, col: usize) -> Option<&str> {
+        self.ids(row)?.get(col).map(|&id| self.value(id))
+    }
+
+    /// Cells of `row`, borrowed.
+    pub fn row(&self, row: usize) -> Option<impl Iterator<Item = &str> + '_> {
+        Some(self.ids(row)?.iter().map(|&id| self.value(id)))
+    }
+
+    /// Owned copy of every row.
+    pub fn to_rows(&self) -> Vec<Vec<String>> {
+        (0..self.len())
+            .filter_map(|r| self.row(r).map(|cells| cells.map(str::to_string).collect()))
+            .collect()
+    }
+}
+
+/// Rows picked in the table (click, Ctrl+click, Shift+click), as source row
+/// indices so they survive sorting and filtering. Copy/Export "Selected rows
+/// only" uses the picked rows that are still in the view.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RowSelection {
+    rows: std::collections::BTreeSet<usize>,
+    /// Source index of the last plain or Ctrl click (start of Shift ranges)
+    anchor: Option<usize>,
+}
+
+impl RowSelection {
+    pub fn is_empty(&self) -> bool { self.rows.is_empty() }
+    pub fn len(&self) -> usize { self.rows.len() }
+    pub fn contains(&self, src_ix: usize) -> bool { self.rows.contains(&src_ix) }
+    /// Lowest selected source index.
+    pub fn first(&self) -> Option<usize> { self.rows.first().copied() }
+
+    pub fn clear(&mut self) {
+        self.rows.clear();
+        self.anchor = None;
+    }
+
+    /// Apply a click on display row `pos` of `view` (the table's `row_ix`).
+    /// Plain: just this row. Ctrl: toggle it. Shift: the range from the
+    /// anchor instead of the selection; Ctrl+Shift: add the range.
+    pub fn click(&mut self, view: &[usize], pos: usize, ctrl: bool, shift: bool) {
+        let Some(&src) = view.get(pos) else { return };
+        let anchor_pos = self.anchor.and_then(|a| view.iter().position(|&ix| ix == a));
+        match (ctrl, shift, anchor_pos) {
+            (_, true, Some(from)) => {
+                if !ctrl { self.rows.clear(); }
+                let (lo, hi) = if from <= pos { (from, pos) } else { (pos, from) };
+                self.rows.extend(view[lo..=hi].iter().copied());
+            }
+            (true, _, _) => {
+                if !self.rows.remove(&src) { self.rows.insert(src); }
+                self.anchor = Some(src);
+            }
+            _ => {
+                self.rows.clear();
+                self.rows.insert(src);
+                self.anchor = Some(src);
+            }
+        }
+    }
+
+    /// The selected rows of `view`, in view order.
+    pub fn in_view(&self, view: &[usize]) -> Vec<usize> {
+        view.iter().copied().filter(|ix| self.rows.contains(ix)).collect()
+    }
+}
+
+/// Rows looked at when sizing columns from data.
+pub const WIDTH_SAMPLE_ROWS: usize = 200;
+
+/// Initial column widths (px) for a table with no widths yet: per column the
+/// 90th percentile of cell length over the first `WIDTH_SAMPLE_ROWS` rows,
+/// but never narrower than the header. Outliers (long names) don't set the width.
+pub fn sample_column_widths(headers: Option<&[String]>, rows: &[Vec<String>], cols: usize) -> Vec<f32> {
+    const CHAR_W: f32 = 7.0;
+    const PAD: f32 = 14.0;
+    const MIN_W: f32 = 24.0;
+    const MAX_W: f32 = 320.0;
+
+    let sample = &rows[..rows.len().min(WIDTH_SAMPLE_ROWS)];
+    (0..cols)
+        .map(|c| {
+            let mut lens: Vec<usize> = sample.iter()
+                .map(|r| r.get(c).map_or(0, |s| s.chars().count()))
+                .collect();
+            lens.sort_unstable();
+            let p90 = lens.get(lens.len().saturating_sub(1) * 9 / 10).copied().unwrap_or(0);
+            let hdr = headers.and_then(|h| h.get(c)).map_or(0, |s| s.chars().count());
+            (p90.max(hdr) as f32 * CHAR_W + PAD).clamp(MIN_W, MAX_W)
+        })
+        .collect()
+}
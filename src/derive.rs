@@ -0,0 +1,259 @@
+// src/derive.rs
+//
+// Derived columns: values computed locally from the cached data after each
+// scrape, merge or import, and appended after the page's other columns
+// (`PageSchema::derived`). They're cached and exported like any column, but
+// always recomputed, never merged:
+//
+//   Injuries  Returns    W + DUR of each injury
+//   Players   Out until  when the player is back from their latest injury,
+//                        while that's after the current week
+//
+// User columns are formulas the user configured per page (`expr`), appended
+// after those. They're shown and exported but never cached: the GUI keeps
+// them on its in-memory data only, the CLI adds them when exporting.
+
+use std::collections::HashMap;
+
+use crate::{
+    config::options::PageKind,
+    expr::Expr,
+    join::name_key,
+    schema::{self, col_index, ColumnSchema, INJURIES, PLAYERS},
+    scrape::injuries::{EVENT_INJURY, EVENT_RETURN},
+    store::{self, DataSet},
+};
+
+const INJ_SEASON: usize = col_index(&INJURIES, "S");
+const INJ_WEEK: usize = col_index(&INJURIES, "W");
+const INJ_TEAM: usize = col_index(&INJURIES, "Victim Team");
+const INJ_VICTIM: usize = col_index(&INJURIES, "Victim");
+const INJ_DUR: usize = col_index(&INJURIES, "DUR");
+const INJ_TYPE: usize = col_index(&INJURIES, "Type");
+const INJ_EVENT: usize = col_index(&INJURIES, "Event");
+const PLAYERS_NAME: usize = col_index(&PLAYERS, "Name");
+const PLAYERS_TEAM: usize = col_index(&PLAYERS, "Team");
+
+/// The derived columns of `kind` (none for most pages).
+pub fn columns(kind: PageKind) -> &'static [ColumnSchema] {
+    schema::for_page(kind).map(|p| p.derived).unwrap_or(&[])
+}
+
+/// Drop `kind`'s derived columns from `ds`. They're always last, so rows
+/// are cut to the remaining headers; that also tidies up after a merge of a
+/// scrape (without them) into the cache (with them).
+pub fn strip(kind: PageKind, ds: &mut DataSet) {
+    let Some(p) = schema::for_page(kind).filter(|p| !p.derived.is_empty()) else { return };
+    let Some(hs) = ds.headers.as_mut() else { return };
+    let at = p.derived_at(hs);
+    hs.truncate(at);
+    for r in &mut ds.rows {
+        r.truncate(at);
+    }
+}
+
+/// Recompute `kind`'s derived columns in `ds`; Players takes the cached
+/// `injuries`. Returns whether the page has any. Datasets without headers
+/// are left as they are.
+pub fn apply(kind: PageKind, ds: &mut DataSet, injuries: Option<&DataSet>) -> bool {
+    let cols = columns(kind);
+    if cols.is_empty() || ds.headers.is_none() { return false; }
+    strip(kind, ds);
+
+    let values: Vec<Option<u32>> = match kind {
+        PageKind::Injuries => ds.rows.iter().map(|r| returns_week(r)).collect(),
+        PageKind::Players => out_until(&ds.rows, injuries),
+        _ => return false,
+    };
+    if let Some(hs) = ds.headers.as_mut() {
+        hs.extend(cols.iter().map(|c| c.name.to_string()));
+    }
+    for (r, v) in ds.rows.iter_mut().zip(values) {
+        r.push(v.map(|w| w.to_string()).unwrap_or_default());
+    }
+    true
+}
+
+/// A column computed by a user formula.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UserColumn {
+    pub name: String,
+    pub expr: Expr,
+}
+
+/// `kind`'s user columns from `.store/settings`, in name order. Formulas
+/// that don't parse are logged and left out.
+pub fn user_columns(kind: PageKind) -> Vec<UserColumn> {
+    let saved = store::user_columns().remove(&kind).unwrap_or_default();
+    saved.into_iter().filter_map(|(name, formula)| match Expr::parse(&formula) {
+        Ok(expr) => Some(UserColumn { name, expr }),
+        Err(e) => { loge!("User column {:?} of {}: {}", name, kind, e); None }
+    }).collect()
+}
+
+/// Append `kind`'s user columns to `ds` (see `apply_user_columns`).
+pub fn apply_user(kind: PageKind, ds: &mut DataSet) -> usize {
+    apply_user_columns(ds, &user_columns(kind))
+}
+
+/// Append `cols` to `ds`, each computed per row; a formula can use the user
+/// columns before it. Columns named like one `ds` has, or needing one it
+/// lacks, are skipped. Returns how many were appended (`strip_user` takes
+/// them off again). Datasets without headers are left as they are.
+pub fn apply_user_columns(ds: &mut DataSet, cols: &[UserColumn]) -> usize {
+    let Some(headers) = ds.headers.as_mut() else { return 0 };
+    let mut added = 0;
+    for c in cols {
+        if headers.iter().any(|h| h.eq_ignore_ascii_case(&c.name)) {
+            logd!("User column {:?}: a column has that name already", c.name);
+            continue;
+        }
+        if let Some(missing) = c.expr.missing_column(headers) {
+            logd!("User column {:?}: no column {:?}", c.name, missing);
+            continue;
+        }
+        let width = headers.len();
+        for r in &mut ds.rows {
+            r.resize(width, String::new());
+            let v = c.expr.eval(headers, r);
+            r.push(v);
+        }
+        headers.push(c.name.clone());
+        added += 1;
+    }
+    added
+}
+
+/// Drop the last `n` columns of `ds`: the user columns `apply_user` added.
+pub fn strip_user(ds: &mut DataSet, n: usize) {
+    if n == 0 { return; }
+    let Some(hs) = ds.headers.as_mut() else { return };
+    let at = hs.len().saturating_sub(n);
+    hs.truncate(at);
+    for r in &mut ds.rows {
+        r.truncate(at);
+    }
+}
+
+fn num(r: &[String], c: usize) -> Option<u32> {
+    r.get(c)?.trim().parse().ok()
+}
+
+/// Week an Injuries row's victim is back: W + DUR, for injuries with a
+/// duration that didn't kill the player.
+pub fn returns_week(r: &[String]) -> Option<u32> {
+    if r.get(INJ_EVENT).is_some_and(|e| e != EVENT_INJURY) { return None; }
+    if r.get(INJ_TYPE).is_some_and(|t| t.to_ascii_uppercase().contains("KILL")) { return None; }
+    let dur = num(r, INJ_DUR).filter(|&d| d > 0)?;
+    Some(num(r, INJ_WEEK)? + dur)
+}
+
+/// Per Players row: the week the player is back, when they're out now. The
+/// current week is the latest in the injury log (of its latest season); a
+/// Return notice after the injury means they're back already.
+fn out_until(players: &[Vec<String>], injuries: Option<&DataSet>) -> Vec<Option<u32>> {
+    let Some(injuries) = injuries else { return vec![None; players.len()] };
+    let season = injuries.rows.iter().filter_map(|r| num(r, INJ_SEASON)).max();
+    let this_season: Vec<&Vec<String>> = injuries.rows.iter()
+        .filter(|r| num(r, INJ_SEASON) == season)
+        .collect();
+    let current = this_season.iter().filter_map(|r| num(r, INJ_WEEK)).max().unwrap_or(0);
+
+    // (team, name) → (injury week, back week) of the injury keeping them out longest; latest Return notice
+    let mut injured: HashMap<(String, String), (u32, u32)> = HashMap::new();
+    let mut returned: HashMap<(String, String), u32> = HashMap::new();
+    for r in this_season {
+        let (Some(team), Some(victim)) = (r.get(INJ_TEAM), r.get(INJ_VICTIM)) else { continue };
+        let key = (name_key(team), name_key(victim));
+        let week = num(r, INJ_WEEK).unwrap_or(0);
+        if r.get(INJ_EVENT).is_some_and(|e| e == EVENT_RETURN) {
+            let w = returned.entry(key).or_default();
+            *w = (*w).max(week);
+        } else if let Some(back) = returns_week(r) {
+            let e = injured.entry(key).or_default();
+            if back > e.1 { *e = (week, back); }
+        }
+    }
+
+    players.iter().map(|r| {
+        let key = (name_key(r.get(PLAYERS_TEAM)?), name_key(r.get(PLAYERS_NAME)?));
+        let &(week, back) = injured.get(&key)?;
+        let back_already = returned.get(&key).is_some_and(|&rw| rw > week);
+        (back > current && !back_already).then_some(back)
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ds(headers: &[&str], rows: &[&[&str]]) -> DataSet {
+        DataSet {
+            headers: Some(headers.iter().map(|h| s!(*h)).collect()),
+            rows: rows.iter().map(|r| r.iter().map(|c| s!(*c)).collect()).collect(),
+        }
+    }
+
+    #[test]
+    fn returns_week_and_out_until() {
+        let injury = |w: &str, victim: &str, dur: &str, ty: &str, event: &str| -> Vec<String> {
+            ["7", w, "Reds", victim, dur, "", "", ty, "Blues", "Ox", "1", "", event].map(String::from).to_vec()
+        };
+        let mut injuries = DataSet {
+            headers: Some(INJURIES.map(|c| s!(c.name)).to_vec()),
+            rows: vec![
+                injury("3", "Grim", "4", "Broken Ribs", "Injury"),
+                injury("4", "Al", "2", "Concussion", "Injury"),
+                injury("5", "Al", "", "is back", "Return"),
+                injury("5", "Zed", "3", "KILLED", "Injury"),
+                injury("5", "Bo", "0", "Badly Hurt", "Injury"),
+            ],
+        };
+        assert!(apply(PageKind::Injuries, &mut injuries, None));
+        assert_eq!(injuries.headers.as_ref().unwrap().last().unwrap(), "Returns");
+        let returns: Vec<&str> = injuries.rows.iter().map(|r| r.last().unwrap().as_str()).collect();
+        assert_eq!(returns, ["7", "6", "", "", ""]);
+        assert_eq!(schema::validate(PageKind::Injuries, &injuries), Ok(()));
+
+        // Again: recomputed, not appended twice
+        apply(PageKind::Injuries, &mut injuries, None);
+        assert_eq!(injuries.rows[0].len(), INJURIES.len() + 1);
+
+        // Current week 5: Grim out until 7; Al came back early; Bo wasn't out
+        let mut players = ds(&["Name", "#", "Race", "Team", "Player id"], &[
+            &["Grim", "#7", "Orc", "Reds", "11"],
+            &["Al", "#2", "Human", "Reds", "12"],
+            &["Bo", "#3", "Elf", "Reds", "13"],
+            &["Grim", "#9", "Orc", "Blues", "14"],
+        ]);
+        apply(PageKind::Players, &mut players, Some(&injuries));
+        let out: Vec<&str> = players.rows.iter().map(|r| r[5].as_str()).collect();
+        assert_eq!(out, ["7", "", "", ""]);
+        assert_eq!(schema::validate(PageKind::Players, &players), Ok(()));
+
+        assert!(!apply(PageKind::Standings, &mut players, None));
+    }
+
+    #[test]
+    fn user_columns_chain_and_skip_clashes() {
+        let col = |name: &str, formula: &str| UserColumn { name: s!(name), expr: Expr::parse(formula).unwrap() };
+        let mut players = ds(&["Name", "Team", "Kills", "Games"], &[
+            &["Grim", "Reds", "9", "4"],
+            &["Al", "Reds", "", "2"],
+        ]);
+        let cols = [
+            col("KPG", "Kills / Games"),
+            col("Label", "Name & \" \" & KPG"),
+            col("kills", "1"),
+            col("Deaths", "Games - Lost"),
+        ];
+        assert_eq!(apply_user_columns(&mut players, &cols), 2);
+        assert_eq!(players.headers.as_ref().unwrap()[4..], ["KPG", "Label"]);
+        assert_eq!(players.rows[0][4..], ["2.25", "Grim 2.25"]);
+        assert_eq!(players.rows[1][4..], ["", "Al "]);
+
+        strip_user(&mut players, 2);
+        assert_eq!(players.headers.unwrap().len(), 4);
+        assert!(players.rows.iter().all(|r| r.len() == 4));
+    }
+}
@@ -0,0 +1,179 @@
+// src/diagnostics.rs
+//
+// Diagnostic info for bug reports: versions, platform, data folder, the
+// settings, each cached page's rows and last scrape, and the end of the
+// log. Everything goes through `Redactor`: secret settings' values, URL
+// paths and queries, the home folder and the user name are left out. One
+// text blob to paste into an issue; GUI "Copy diagnostic info" (About),
+// CLI `--diagnostics`.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config::consts::{LOG_FILE_NAME, PARSER_VERSION};
+use crate::store::{self, CacheMeta, CACHED_PAGES};
+use crate::trend::fmt_date;
+
+/// Log lines included
+pub const LOG_LINES: usize = 50;
+
+/// Settings whose values never go into the report (keys containing these)
+const SECRET_KEYS: [&str; 4] = ["webhook", "token", "secret", "password"];
+
+pub fn report() -> String {
+    let settings_map = store::load_settings();
+    let redactor = Redactor {
+        home: home_dir(),
+        user: std::env::var("USER").or_else(|_| std::env::var("USERNAME")).ok(),
+        secrets: settings_map.iter()
+            .filter(|(k, v)| is_secret(k) && !v.trim().is_empty())
+            .map(|(_, v)| v.trim().to_string())
+            .collect(),
+    };
+    let clean = |s: &str| redactor.clean(s);
+    let mut out = String::new();
+    let mut line = |s: String| { out.push_str(&s); out.push('\n'); };
+
+    line(format!("bb_scrape {} (parser {})", env!("CARGO_PKG_VERSION"), PARSER_VERSION));
+    line(format!("Platform: {} {}", std::env::consts::OS, std::env::consts::ARCH));
+    line(format!("Data folder: {}{}", clean(&store::store_dir().display().to_string()),
+        if store::is_portable() { " (portable)" } else { "" }));
+
+    line(String::new());
+    line(s!("Settings:"));
+    let mut settings: Vec<(String, String)> = settings_map.into_iter().collect();
+    settings.sort();
+    if settings.is_empty() { line(s!("  (none)")); }
+    for (k, v) in settings {
+        line(format!("  {}={}", k, clean(&sanitize(&k, &v))));
+    }
+
+    line(String::new());
+    line(s!("Cache:"));
+    let now = SystemTime::now();
+    for kind in CACHED_PAGES {
+        if store::saved_at(&kind).is_none() { continue; }
+        let rows = store::load_dataset(&kind).map(|ds| ds.row_count().to_string()).unwrap_or_else(|e| format!("unreadable ({})", e));
+        let scraped = store::scraped_at(&kind).map(|at| {
+            let age = now.duration_since(at).map(store::fmt_age).unwrap_or_else(|_| s!("just now"));
+            format!("{} ({})", fmt_time(at), age)
+        }).unwrap_or_else(|| s!("never"));
+        let parser = CacheMeta::load(&kind).map(|m| m.parser_version.to_string()).unwrap_or_else(|| s!("?"));
+        line(format!("  {:<16} {:>6} rows  scraped {}  parser {}", kind.to_string(), rows, scraped, parser));
+    }
+
+    line(String::new());
+    line(format!("Log (last {} lines):", LOG_LINES));
+    for l in log_tail(LOG_LINES) {
+        line(format!("  {}", clean(&l)));
+    }
+    out
+}
+
+fn is_secret(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    SECRET_KEYS.iter().any(|s| key.contains(s))
+}
+
+/// The value as it may appear in a report.
+fn sanitize(key: &str, value: &str) -> String {
+    if is_secret(key) { s!("<redacted>") } else { value.to_string() }
+}
+
+/// What a report line must not show.
+struct Redactor {
+    home: Option<String>,
+    user: Option<String>,
+    /// Values of secret settings (webhook URLs, tokens), wherever they turn up
+    secrets: Vec<String>,
+}
+
+impl Redactor {
+    fn clean(&self, text: &str) -> String {
+        let mut out = text.to_string();
+        for secret in &self.secrets {
+            out = out.replace(secret.as_str(), "<redacted>");
+        }
+        out = mask_urls(&out);
+        out = shorten_home(&out, self.home.as_deref());
+        if let Some(user) = self.user.as_deref().filter(|u| !u.is_empty()) {
+            for sep in ['/', '\\'] {
+                out = out.replace(&format!("{sep}{user}{sep}"), &format!("{sep}<user>{sep}"));
+            }
+        }
+        out
+    }
+}
+
+/// Every `http(s)://` URL in `text` down to its scheme and host (paths
+/// hold webhook tokens, queries anything).
+fn mask_urls(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(i) = rest.find("http://").into_iter().chain(rest.find("https://")).min() {
+        out.push_str(&rest[..i]);
+        let end = rest[i..].find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | ')' | '>'))
+            .map_or(rest.len(), |e| i + e);
+        out.push_str(&crate::core::net::redact_url(&rest[i..end]));
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// `text` with the home folder replaced by `~` (user names stay private).
+fn shorten_home(text: &str, home: Option<&str>) -> String {
+    match home {
+        Some(h) if !h.is_empty() => text.replace(h, "~"),
+        _ => text.to_string(),
+    }
+}
+
+fn home_dir() -> Option<String> {
+    std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE")).ok()
+}
+
+/// The last `n` lines of the log file, or of this run's log when there's
+/// no file.
+fn log_tail(n: usize) -> Vec<String> {
+    let path = store::store_dir().join(LOG_FILE_NAME);
+    let lines: Vec<String> = match std::fs::read_to_string(&path) {
+        Ok(text) => text.lines().map(String::from).collect(),
+        Err(_) => crate::log::recent().iter().map(|l| l.text()).collect(),
+    };
+    let skip = lines.len().saturating_sub(n);
+    lines.into_iter().skip(skip).collect()
+}
+
+/// "2026-10-16 14:05 UTC"
+fn fmt_time(at: SystemTime) -> String {
+    let secs = at.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    format!("{} {:02}:{:02} UTC", fmt_date(secs), secs / 3600 % 24, secs / 60 % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaves_secrets_and_home_folder_out() {
+        assert_eq!(sanitize("webhook_url", "https://discord.com/api/webhooks/1/abc"), "<redacted>");
+        assert_eq!(sanitize("net.retries", "3"), "3");
+        assert_eq!(shorten_home("/home/ann/bb/.store", Some("/home/ann")), "~/bb/.store");
+        assert_eq!(shorten_home("/srv/bb", None), "/srv/bb");
+
+        // Log lines: secret values, URL paths and queries, home and user name
+        let r = Redactor {
+            home: Some(s!("/home/ann")),
+            user: Some(s!("ann")),
+            secrets: vec![s!("tok3n-value")],
+        };
+        assert_eq!(
+            r.clean("Webhook: POST https://discord.com/api/webhooks/1/abc failed (tok3n-value)"),
+            "Webhook: POST https://discord.com/… failed (<redacted>)"
+        );
+        assert_eq!(r.clean("GET http://x.com/team.php?i=3 ok"), "GET http://x.com/… ok");
+        assert_eq!(r.clean("Saved → /home/ann/bb/out.csv"), "Saved → ~/bb/out.csv");
+        assert_eq!(r.clean(r"Saved → D:\Users\ann\out.csv"), r"Saved → D:\Users\<user>\out.csv");
+        assert_eq!(fmt_time(UNIX_EPOCH + std::time::Duration::from_secs(86_400 + 3_900)), "1970-01-02 01:05 UTC");
+    }
+}
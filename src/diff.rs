@@ -0,0 +1,120 @@
+// src/diff.rs
+//
+// Change detection between two versions of a page's DataSet
+// (cached vs freshly scraped). Rows are matched on per-page key columns
+// (see Page::row_key_columns); everything else is compared cell by cell.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::store::DataSet;
+
+/// A row present in both versions whose non-key cells differ.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RowChange {
+    pub key: Vec<String>,
+    /// (column, before, after)
+    pub cells: Vec<(usize, String, String)>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DataDiff {
+    /// Headers of the new dataset, for naming columns in reports
+    pub headers: Option<Vec<String>>,
+    pub added: Vec<Vec<String>>,
+    pub removed: Vec<Vec<String>>,
+    pub changed: Vec<RowChange>,
+    /// Key columns used for matching (empty = whole row)
+    pub key_cols: Vec<usize>,
+}
+
+impl DataDiff {
+    /// Compare `old` → `new`. Added/changed follow `new` order, removed follow `old` order.
+    /// With empty `key_cols` rows only ever show up as added/removed.
+    pub fn between(old: &DataSet, new: &DataSet, key_cols: &[usize]) -> Self {
+        let key = |r: &Vec<String>| -> Vec<String> {
+            if key_cols.is_empty() { return r.clone(); }
+            key_cols.iter().map(|&c| r.get(c).cloned().unwrap_or_default()).collect()
+        };
+
+        let old_by_key: HashMap<Vec<String>, &Vec<String>> =
+            old.rows.iter().map(|r| (key(r), r)).collect();
+        let new_keys: HashSet<Vec<String>> = new.rows.iter().map(key).collect();
+
+        let mut diff = DataDiff {
+            headers: new.headers.clone().or_else(|| old.headers.clone()),
+            key_cols: key_cols.to_vec(),
+            ..Default::default()
+        };
+
+        for r in &new.rows {
+            let k = key(r);
+            match old_by_key.get(&k) {
+                None => diff.added.push(r.clone()),
+                Some(o) if *o != r => {
+                    let width = r.len().max(o.len());
+                    let cells = (0..width)
+                        .filter(|c| !key_cols.contains(c))
+                        .filter_map(|c| {
+                            let before = o.get(c).cloned().unwrap_or_default();
+                            let after = r.get(c).cloned().unwrap_or_default();
+                            (before != after).then_some((c, before, after))
+                        })
+                        .collect();
+                    diff.changed.push(RowChange { key: k, cells });
+                }
+                Some(_) => {}
+            }
+        }
+
+        diff.removed = old.rows.iter()
+            .filter(|r| !new_keys.contains(&key(r)))
+            .cloned()
+            .collect();
+
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// One-liner, e.g. "2 added, 1 removed, 5 changed".
+    pub fn summary(&self) -> String {
+        if self.is_empty() { return s!("No changes"); }
+        format!("{} added, {} removed, {} changed",
+            self.added.len(), self.removed.len(), self.changed.len())
+    }
+
+    /// Human-readable report lines:
+    /// `+ key`, `- key`, `~ key: Col before → after, …`
+    pub fn report_lines(&self) -> Vec<String> {
+        let mut out = Vec::with_capacity(self.added.len() + self.removed.len() + self.changed.len());
+        for r in &self.added { out.push(format!("+ {}", self.label_row(r))); }
+        for r in &self.removed { out.push(format!("- {}", self.label_row(r))); }
+        for c in &self.changed {
+            let cells: Vec<String> = c.cells.iter()
+                .map(|(ci, b, a)| format!("{} {} → {}", self.col_name(*ci), show(b), show(a)))
+                .collect();
+            out.push(format!("~ {}: {}", c.key.join(" / "), cells.join(", ")));
+        }
+        out
+    }
+
+    fn label_row(&self, r: &[String]) -> String {
+        if self.key_cols.is_empty() { return r.join(" / "); }
+        self.key_cols.iter()
+            .map(|&c| r.get(c).map(|s| s.as_str()).unwrap_or(""))
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+
+    fn col_name(&self, ci: usize) -> String {
+        self.headers.as_ref()
+            .and_then(|h| h.get(ci).cloned())
+            .unwrap_or_else(|| format!("Col {}", ci + 1))
+    }
+}
+
+fn show(cell: &str) -> &str {
+    if cell.is_empty() { "∅" } else { cell }
+}
@@ -0,0 +1,110 @@
+// src/error.rs
+//
+// The crate's error: what failed, with what it failed on (the URL, the
+// team, the file), so messages say where to look and callers can tell a
+// site outage from a format change or a locked file. Scrapers, collectors,
+// the network layer and the export writers return it. Not (yet) the rest:
+// the store keeps `io::Result` (callers wrap it with `Error::io` and the
+// path), and the CLI's argument handling stays on `Box<dyn Error>`, which
+// `?` turns this into.
+
+use std::{fmt, io, path::{Path, PathBuf}};
+
+use crate::core::net::{NetError, Truncated};
+use crate::file::PartialExport;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The site couldn't be reached, or answered with an error status
+    Net { url: String, source: NetError },
+    /// Any other socket error while requesting `url`
+    Socket { url: String, source: io::Error },
+    /// The response stayed cut off after the retries
+    Truncated(Truncated),
+    /// A page didn't look the way its scraper expects (`what`: the page
+    /// or table); usually the site's format changed
+    Parse { what: String, msg: String },
+    /// Reading or writing `path`
+    Io { path: PathBuf, source: io::Error },
+    /// One team's part of a per-team scrape
+    Team { id: u32, source: Box<Error> },
+    /// A per-team export wrote some files but not all
+    PartialExport(PartialExport),
+    /// Anything else: bad options, unsupported pages, …
+    Other(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn parse(what: impl Into<String>, msg: impl Into<String>) -> Self {
+        Error::Parse { what: what.into(), msg: msg.into() }
+    }
+
+    pub fn io(path: &Path, source: io::Error) -> Self {
+        Error::Io { path: path.to_path_buf(), source }
+    }
+
+    /// The network failure behind this error, if it is one.
+    pub fn net(&self) -> Option<&NetError> {
+        match self {
+            Error::Net { source, .. } => Some(source),
+            Error::Team { source, .. } => source.net(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Net { url, source } => write!(f, "{} ({})", source, url),
+            Error::Socket { url, source } => write!(f, "Request to {} failed: {}", url, source),
+            Error::Truncated(t) => t.fmt(f),
+            Error::Parse { what, msg } => write!(f, "{}: {}", what, msg),
+            Error::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            Error::Team { id, source } => write!(f, "Team {}: {}", id, source),
+            Error::PartialExport(p) => p.fmt(f),
+            Error::Other(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Net { source, .. } => Some(source),
+            Error::Socket { source, .. } | Error::Io { source, .. } => Some(source),
+            Error::Team { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<String> for Error {
+    fn from(msg: String) -> Self { Error::Other(msg) }
+}
+
+impl From<&str> for Error {
+    fn from(msg: &str) -> Self { Error::Other(msg.to_string()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_context_and_the_cause() {
+        let e = Error::Team {
+            id: 7,
+            source: Box::new(Error::Net { url: s!("http://x/team.php?i=7"), source: NetError::HttpStatus(503) }),
+        };
+        assert_eq!(e.to_string(), "Team 7: Site returned 503 — try later (http://x/team.php?i=7)");
+        assert_eq!(e.net(), Some(&NetError::HttpStatus(503)));
+        assert_eq!(NetError::find(&e), Some(&NetError::HttpStatus(503)));
+
+        let e = Error::io(Path::new("out/all.tsv"), io::Error::new(io::ErrorKind::PermissionDenied, "denied"));
+        assert_eq!(e.to_string(), "out/all.tsv: denied");
+        assert_eq!(e.net(), None);
+    }
+}
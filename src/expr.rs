@@ -0,0 +1,294 @@
+// src/expr.rs
+//
+// The formula language of user columns (see `derive::apply_user`): one
+// expression per column, evaluated per row over the row's other cells.
+//
+//   12, 0.5         numbers
+//   "vs "           text (a doubled "" is a quote)
+//   TV, [Opp W]     cells by column name, case-insensitive; brackets for
+//                   names that aren't one plain word ([#], [Out until])
+//   + - * / ( )     arithmetic, unary minus
+//   &               joins text: Name & " (" & Team & ")"
+//
+// Arithmetic over a blank or non-numeric cell, or a division by zero, is
+// blank rather than an error, so a column works on partial data. Formulas
+// are kept small (`MAX_DEPTH`, `MAX_TOKENS`) since parsing and evaluating
+// recurse: a pasted monster is a parse error, not a stack overflow.
+
+use crate::aggregate::fmt_num;
+
+/// Most parentheses and unary minuses inside one another.
+const MAX_DEPTH: usize = 64;
+/// Most tokens in a formula (bounds how deep a chain like `1+1+…` nests).
+const MAX_TOKENS: usize = 1_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op { Add, Sub, Mul, Div, Join }
+
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    Num(f64),
+    Text(String),
+    Col(String),
+    Neg(Box<Node>),
+    Bin(Op, Box<Node>, Box<Node>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token { Num(f64), Text(String), Col(String), Op(char), Open, Close }
+
+#[derive(Clone, Debug, PartialEq)]
+enum Value { Num(f64), Text(String), Blank }
+
+impl Value {
+    fn num(&self) -> Option<f64> {
+        match self { Value::Num(x) => Some(*x), _ => None }
+    }
+
+    fn into_text(self) -> String {
+        match self {
+            Value::Num(x) => fmt_num(x),
+            Value::Text(s) => s,
+            Value::Blank => String::new(),
+        }
+    }
+}
+
+/// A parsed formula.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expr {
+    source: String,
+    root: Node,
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, String> {
+    let mut out = Vec::new();
+    let mut chars = src.char_indices().peekable();
+    while let Some((at, c)) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '+' | '-' | '*' | '/' | '&' => out.push(Token::Op(c)),
+            '(' => out.push(Token::Open),
+            ')' => out.push(Token::Close),
+            '[' => {
+                let name: String = chars.by_ref().map(|(_, c)| c).take_while(|&c| c != ']').collect();
+                if !src[at..].contains(']') { return Err(format!("unclosed [ at {}", at + 1)); }
+                out.push(Token::Col(name.trim().to_string()));
+            }
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) if chars.peek().is_some_and(|&(_, c)| c == '"') => { chars.next(); text.push('"'); }
+                        Some((_, '"')) => break,
+                        Some((_, c)) => text.push(c),
+                        None => return Err(format!("unclosed text at {}", at + 1)),
+                    }
+                }
+                out.push(Token::Text(text));
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut end = at + c.len_utf8();
+                while let Some(&(i, d)) = chars.peek().filter(|(_, d)| d.is_ascii_digit() || *d == '.') {
+                    end = i + d.len_utf8();
+                    chars.next();
+                }
+                let n = src[at..end].parse().map_err(|_| format!("bad number {:?}", &src[at..end]))?;
+                out.push(Token::Num(n));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = at + c.len_utf8();
+                while let Some(&(i, d)) = chars.peek().filter(|(_, d)| d.is_alphanumeric() || *d == '_') {
+                    end = i + d.len_utf8();
+                    chars.next();
+                }
+                out.push(Token::Col(src[at..end].to_string()));
+            }
+            c => return Err(format!("unexpected {:?} at {}", c, at + 1)),
+        }
+    }
+    Ok(out)
+}
+
+/// Recursive descent, loosest first: join, sum, product, unary, atom.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Parentheses and unary minuses currently open
+    depth: usize,
+}
+
+impl Parser {
+    /// `parse` one level deeper, failing past `MAX_DEPTH`.
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<Node, String>) -> Result<Node, String> {
+        if self.depth >= MAX_DEPTH {
+            return Err(format!("formula nested more than {} deep", MAX_DEPTH));
+        }
+        self.depth += 1;
+        let node = parse(self);
+        self.depth -= 1;
+        node
+    }
+
+    fn peek_op(&self, ops: &[char]) -> Option<char> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Op(c)) if ops.contains(c) => Some(*c),
+            _ => None,
+        }
+    }
+
+    fn binary(&mut self, ops: &[char], next: fn(&mut Self) -> Result<Node, String>) -> Result<Node, String> {
+        let mut left = next(self)?;
+        while let Some(c) = self.peek_op(ops) {
+            self.pos += 1;
+            let op = match c { '+' => Op::Add, '-' => Op::Sub, '*' => Op::Mul, '/' => Op::Div, _ => Op::Join };
+            left = Node::Bin(op, Box::new(left), Box::new(next(self)?));
+        }
+        Ok(left)
+    }
+
+    fn join(&mut self) -> Result<Node, String> { self.binary(&['&'], Self::sum) }
+    fn sum(&mut self) -> Result<Node, String> { self.binary(&['+', '-'], Self::product) }
+    fn product(&mut self) -> Result<Node, String> { self.binary(&['*', '/'], Self::unary) }
+
+    fn unary(&mut self) -> Result<Node, String> {
+        if self.peek_op(&['-']).is_some() {
+            self.pos += 1;
+            return Ok(Node::Neg(Box::new(self.nested(Self::unary)?)));
+        }
+        self.atom()
+    }
+
+    fn atom(&mut self) -> Result<Node, String> {
+        let token = self.tokens.get(self.pos).cloned().ok_or("unexpected end of formula")?;
+        self.pos += 1;
+        match token {
+            Token::Num(n) => Ok(Node::Num(n)),
+            Token::Text(s) => Ok(Node::Text(s)),
+            Token::Col(c) if c.is_empty() => Err(s!("empty column name []")),
+            Token::Col(c) => Ok(Node::Col(c)),
+            Token::Open => {
+                let inner = self.nested(Self::join)?;
+                match self.tokens.get(self.pos) {
+                    Some(Token::Close) => { self.pos += 1; Ok(inner) }
+                    _ => Err(s!("missing )")),
+                }
+            }
+            Token::Close => Err(s!("unexpected )")),
+            Token::Op(c) => Err(format!("unexpected {}", c)),
+        }
+    }
+}
+
+fn header_index(headers: &[String], name: &str) -> Option<usize> {
+    headers.iter().position(|h| h.trim().eq_ignore_ascii_case(name))
+}
+
+impl Expr {
+    pub fn parse(src: &str) -> Result<Self, String> {
+        let tokens = tokenize(src)?;
+        if tokens.is_empty() { return Err(s!("empty formula")); }
+        if tokens.len() > MAX_TOKENS { return Err(format!("formula longer than {} parts", MAX_TOKENS)); }
+        let mut p = Parser { tokens, pos: 0, depth: 0 };
+        let root = p.join()?;
+        if p.pos < p.tokens.len() { return Err(format!("unexpected {:?} after the formula", p.tokens[p.pos])); }
+        Ok(Self { source: src.trim().to_string(), root })
+    }
+
+    /// The formula as written.
+    pub fn source(&self) -> &str { &self.source }
+
+    /// Columns the formula refers to, in order of appearance.
+    pub fn columns(&self) -> Vec<&str> {
+        fn walk<'a>(n: &'a Node, out: &mut Vec<&'a str>) {
+            match n {
+                Node::Col(c) => out.push(c),
+                Node::Neg(a) => walk(a, out),
+                Node::Bin(_, a, b) => { walk(a, out); walk(b, out); }
+                Node::Num(_) | Node::Text(_) => {}
+            }
+        }
+        let mut out = Vec::new();
+        walk(&self.root, &mut out);
+        out
+    }
+
+    /// The first column the formula needs that `headers` doesn't have.
+    pub fn missing_column(&self, headers: &[String]) -> Option<&str> {
+        self.columns().into_iter().find(|c| header_index(headers, c).is_none())
+    }
+
+    /// Value for one row ("" when blank). Missing columns read as blank.
+    pub fn eval(&self, headers: &[String], row: &[String]) -> String {
+        fn go(n: &Node, headers: &[String], row: &[String]) -> Value {
+            match n {
+                Node::Num(x) => Value::Num(*x),
+                Node::Text(s) => Value::Text(s.clone()),
+                Node::Col(c) => {
+                    let cell = header_index(headers, c).and_then(|i| row.get(i)).map(|s| s.trim()).unwrap_or("");
+                    if cell.is_empty() { return Value::Blank; }
+                    match cell.trim_start_matches('#').parse::<f64>() {
+                        Ok(x) => Value::Num(x),
+                        Err(_) => Value::Text(cell.to_string()),
+                    }
+                }
+                Node::Neg(a) => go(a, headers, row).num().map(|x| Value::Num(-x)).unwrap_or(Value::Blank),
+                Node::Bin(Op::Join, a, b) => {
+                    let mut s = go(a, headers, row).into_text();
+                    s.push_str(&go(b, headers, row).into_text());
+                    Value::Text(s)
+                }
+                Node::Bin(op, a, b) => {
+                    let (Some(x), Some(y)) = (go(a, headers, row).num(), go(b, headers, row).num()) else { return Value::Blank };
+                    let v = match op {
+                        Op::Add => x + y,
+                        Op::Sub => x - y,
+                        Op::Mul => x * y,
+                        _ if y == 0.0 => return Value::Blank,
+                        _ => x / y,
+                    };
+                    Value::Num(v)
+                }
+            }
+        }
+        go(&self.root, headers, row).into_text()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hs(names: &[&str]) -> Vec<String> { names.iter().map(|h| s!(*h)).collect() }
+
+    #[test]
+    fn arithmetic_precedence_columns_and_joins() {
+        let headers = hs(&["Name", "#", "Kills", "Games", "Opp W"]);
+        let row = hs(&["Grim", "#7", "9", "4", ""]);
+        let eval = |src: &str| Expr::parse(src).unwrap().eval(&headers, &row);
+
+        assert_eq!(eval("kills / GAMES"), "2.25");
+        assert_eq!(eval("1 + 2 * 3 - -1"), "8");
+        assert_eq!(eval("(1 + 2) * [#]"), "21");
+        assert_eq!(eval(r#"Name & " ""the"" " & [#]"#), r#"Grim "the" 7"#);
+        // Blank cells and division by zero are blank, not errors
+        assert_eq!(eval("Kills - [Opp W]"), "");
+        assert_eq!(eval("Kills / (Games - 4)"), "");
+        assert_eq!(eval("Name * 2"), "");
+
+        assert_eq!(Expr::parse("Kills / Deaths").unwrap().missing_column(&headers), Some("Deaths"));
+        for bad in ["", "1 +", "(1", "[Opp W", "\"open", "1 2", "Kills $ 2"] {
+            assert!(Expr::parse(bad).is_err(), "{:?} should not parse", bad);
+        }
+    }
+
+    #[test]
+    fn deep_or_huge_formulas_are_parse_errors() {
+        let nested = |n: usize| format!("{}1{}", "(".repeat(n), ")".repeat(n));
+        assert!(Expr::parse(&nested(MAX_DEPTH)).is_ok());
+        assert!(Expr::parse(&nested(MAX_DEPTH + 1)).unwrap_err().contains("nested"));
+        assert!(Expr::parse(&nested(100_000)).is_err());
+        assert!(Expr::parse(&"-".repeat(100_000)).is_err());
+        assert!(Expr::parse(&"1+".repeat(MAX_TOKENS)).unwrap_err().contains("longer"));
+    }
+}
@@ -6,37 +6,72 @@
 // We keep this generic: given canonical headers & rows, and a set of row
 // indices to write, we stream directly to a file. A tiny ColumnProjection
 // handles the "drop last column" case used by Game Results when match id
-// is hidden.
+// is hidden, and arbitrary column lists ("export as shown").
 //
 // Existing functions like `to_export_string` / `write_export_single` can
 // stay as-is. This adds a sibling streaming path for per-team.
 
 use std::{
-    error::Error,
     fs::{self, File},
     io::{self, Write, BufWriter},
     mem::take,
     path::{Path, PathBuf},
-    collections::HashMap,
+    collections::{HashMap, HashSet},
+    sync::atomic::{AtomicU64, Ordering},
 };
 
-use crate::config::options::{ AppOptions, ExportOptions, PageKind, PageKind::{Players, GameResults} };
+use crate::config::options::{ AppOptions, Backup, ExportFormat, ExportOptions, FormulaEscape, PageKind, PageKind::{Players, GameResults} };
 use crate::core::sanitize;
+use crate::error::Error;
+use crate::data::SortKey;
+use crate::get_teams::{self, Divisions};
+use crate::schema::ColumnType;
+use crate::store::DataSet;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ColumnProjection {
     KeepAll,
     DropLast, // used by Game Results when match id is hidden
+    /// Source column indices, in output order (GUI "As shown": reordered, hidden dropped)
+    Columns(Vec<usize>),
+}
+
+impl ColumnProjection {
+    /// Cells of `row` to write, in output order. Missing cells are blank.
+    pub fn cells<'a>(&self, row: &'a [String]) -> Vec<&'a str> {
+        match self {
+            ColumnProjection::KeepAll => row.iter().map(|s| s.as_str()).collect(),
+            ColumnProjection::DropLast => {
+                row[..row.len().saturating_sub(1)].iter().map(|s| s.as_str()).collect()
+            }
+            ColumnProjection::Columns(ix) => {
+                ix.iter().map(|&c| row.get(c).map_or("", |s| s.as_str())).collect()
+            }
+        }
+    }
+
+    /// Owned projection of headers + rows (Copy / single-file export).
+    pub fn apply(
+        &self,
+        headers: &Option<Vec<String>>,
+        rows: &[Vec<String>],
+    ) -> (Option<Vec<String>>, Vec<Vec<String>>) {
+        let own = |r: &[String]| self.cells(r).into_iter().map(String::from).collect::<Vec<_>>();
+        (headers.as_deref().map(own), rows.iter().map(|r| own(r)).collect())
+    }
 }
 
 /* ---------- parsing (for .store) ---------- */
 
 /// Minimal CSV/TSV parser (quotes + CRLF tolerant). std-only.
+/// Blank lines are skipped; a quoted empty cell (`""`) is a real row.
 pub fn parse_rows(text: &str, sep: char) -> Vec<Vec<String>> {
     let mut rows = Vec::new();
     let mut field = s!();
     let mut row = Vec::new();
     let mut in_quotes = false;
+    let mut quoted = false; // current row had a quoted field
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
     let mut chars = text.chars().peekable();
 
     while let Some(ch) = chars.next() {
@@ -51,6 +86,7 @@ pub fn parse_rows(text: &str, sep: char) -> Vec<Vec<String>> {
                     }
                 } else {
                     in_quotes = true;
+                    quoted = true;
                 }
             }
             c if c == sep && !in_quotes => {
@@ -60,11 +96,12 @@ pub fn parse_rows(text: &str, sep: char) -> Vec<Vec<String>> {
             '\n' | '\r' if !in_quotes => {
                 if ch == '\r' && matches!(chars.peek(), Some('\n')) { chars.next(); }
                 row.push(take(&mut field));
-                if !row.is_empty() && !(row.len() == 1 && row[0].is_empty()) {
+                if quoted || !(row.len() == 1 && row[0].is_empty()) {
                     rows.push(take(&mut row));
                 } else {
                     row.clear();
                 }
+                quoted = false;
             }
             _ => field.push(ch),
         }
@@ -76,7 +113,7 @@ pub fn parse_rows(text: &str, sep: char) -> Vec<Vec<String>> {
         // (Same behavior as before; we still don’t create a spurious empty row.)
     }
     row.push(field);
-    if !row.is_empty() && !(row.len() == 1 && row[0].is_empty()) {
+    if quoted || !(row.len() == 1 && row[0].is_empty()) {
         rows.push(row);
     }
 
@@ -90,7 +127,11 @@ fn needs_quotes(field: &str, sep: char) -> bool {
 }
 
 /// Write a single CSV/TSV row to any writer.
+/// A row of one empty cell is written as `""` so it doesn't read back as a blank line.
 pub fn write_row<W: Write>(mut w: W, row: &[String], sep: char) -> io::Result<()> {
+    if let [only] = row {
+        if only.is_empty() { return writeln!(w, "\"\""); }
+    }
     let mut first = true;
     for cell in row {
         if !first { write!(w, "{}", sep)?; } else { first = false; }
@@ -106,12 +147,11 @@ pub fn write_row<W: Write>(mut w: W, row: &[String], sep: char) -> io::Result<()
 
 /// Minimal writer for borrowed cells. Mirrors `write_row` quoting rules.
 fn write_row_strs<W: Write>(mut w: W, row: &[&str], sep: char) -> io::Result<()> {
+    if let [""] = row { return writeln!(w, "\"\""); }
     let mut first = true;
     for cell in row {
         if !first { write!(w, "{}", sep)?; } else { first = false; }
-        // If `needs_quotes` isn't public, inline the same logic here:
-        let needs_q = cell.contains(sep) || cell.contains('"') || cell.contains('\n') || cell.contains('\r');
-        if needs_q {
+        if needs_quotes(cell, sep) {
             let escaped = cell.replace('"', "\"\"");
             write!(w, "\"{}\"", escaped)?;
         } else {
@@ -121,7 +161,7 @@ fn write_row_strs<W: Write>(mut w: W, row: &[&str], sep: char) -> io::Result<()>
     writeln!(w)
 }
 
-/* ---------- export gate (CSV/TSV today) ---------- */
+/* ---------- export gate (CSV/TSV, Markdown/BBCode) ---------- */
 
 /// One gate for all export callers:
 /// - Players: optionally strip hash in col #1, then encode raw
@@ -136,18 +176,55 @@ pub fn to_export_string(
     let page = &o.scrape.page;
 
     let include_headers = e.include_headers;
+    let out_headers = export_headers(e, *page, headers);
+
+    // Cells a spreadsheet would run as formulas, before numbers get
+    // separators that no longer parse
+    let escaped;
+    let (out_headers, rows) = if e.formula_escape.applies(false) && e.delimiter().is_some() {
+        escaped = escape_formulas(rows);
+        (out_headers.as_deref().map(escape_row), &escaped[..])
+    } else {
+        (out_headers, rows)
+    };
+
+    // Numbers in the chosen separators
+    let localized;
+    let rows = if e.number_format.is_plain() { rows } else {
+        localized = localize_numbers(e, *page, headers, rows);
+        &localized[..]
+    };
+
+    // The number column is found by its "#" header, so reordered views
+    // ("As shown") strip the right cells; otherwise canonical column 1
+    let hash_col = headers.as_ref()
+        .and_then(|h| h.iter().position(|x| x.starts_with('#')))
+        .unwrap_or(1);
+    let strip_players_hash = matches!(page, Players) && !e.keep_hash;
+
+    // Markdown / BBCode: a formatted table instead of delimited text
+    if e.format.is_markup() {
+        let h: Option<Vec<&str>> = out_headers.as_ref()
+            .filter(|_| include_headers)
+            .map(|h| h.iter().map(String::as_str).collect());
+        let body: Vec<Vec<&str>> = rows.iter().map(|r| {
+            r.iter().enumerate().map(|(i, cell)| {
+                if strip_players_hash && i == hash_col { cell.strip_prefix('#').unwrap_or(cell) } else { cell.as_str() }
+            }).collect()
+        }).collect();
+        return markup_table(e.format, h.as_deref(), &body);
+    }
+
     let sep = e.delimiter().unwrap();
     let mut buf: Vec<u8> = Vec::new();
 
     if include_headers {
-        if let Some(h) = headers {
+        if let Some(h) = &out_headers {
             // If you prefer, you can also use write_row_strs with borrowed cells:
             let _ = write_row_strs(&mut buf, &h.iter().map(|s| s.as_str()).collect::<Vec<_>>(), sep);
         }
     }
 
-    let strip_players_hash = matches!(page, Players) && !e.keep_hash;
-
     // Reuse a tiny scratch buffer per row to avoid allocations in the hot path
     let mut scratch: Vec<&str> = Vec::new();
 
@@ -155,10 +232,10 @@ pub fn to_export_string(
         scratch.clear();
         scratch.reserve_exact(r.len());
 
-        if strip_players_hash && r.len() > 1 {
+        if strip_players_hash && r.len() > hash_col {
             for (i, cell) in r.iter().enumerate() {
-                // zero-copy: borrow a subslice for col 1
-                let s = if i == 1 { cell.strip_prefix('#').unwrap_or(cell) } else { cell.as_str() };
+                // zero-copy: borrow a subslice for the number column
+                let s = if i == hash_col { cell.strip_prefix('#').unwrap_or(cell) } else { cell.as_str() };
                 scratch.push(s);
             }
             let _ = write_row_strs(&mut buf, &scratch, sep);
@@ -171,6 +248,35 @@ pub fn to_export_string(
     String::from_utf8(buf).unwrap_or_default()
 }
 
+/// `headers` as exports write them: renamed per `ExportOptions::header_names`
+/// for `kind`; headers without a rename stay as they are.
+pub fn export_headers(export: &ExportOptions, kind: PageKind, headers: &Option<Vec<String>>) -> Option<Vec<String>> {
+    let names = export.header_names.get(&kind);
+    headers.as_ref().map(|hs| hs.iter()
+        .map(|h| names.and_then(|n| n.get(h)).unwrap_or(h).clone())
+        .collect())
+}
+
+/// `rows` with the cells a spreadsheet would take for formulas escaped
+/// (see `FormulaEscape`).
+pub fn escape_formulas(rows: &[Vec<String>]) -> Vec<Vec<String>> {
+    rows.iter().map(|r| escape_row(r)).collect()
+}
+
+fn escape_row(row: &[String]) -> Vec<String> {
+    row.iter().map(|cell| FormulaEscape::escape(cell).unwrap_or_else(|| cell.clone())).collect()
+}
+
+/// `rows` with the numbers of numeric columns (schema types, or inferred
+/// for site columns; ids excluded) in `export.number_format`.
+pub fn localize_numbers(export: &ExportOptions, kind: PageKind, headers: &Option<Vec<String>>, rows: &[Vec<String>]) -> Vec<Vec<String>> {
+    let types = crate::schema::column_types(kind, headers.as_deref(), rows);
+    let numeric = |ci: usize| types.get(ci).is_some_and(|t| matches!(t, ColumnType::Integer | ColumnType::Decimal));
+    rows.iter().map(|r| r.iter().enumerate().map(|(ci, cell)| {
+        numeric(ci).then(|| export.number_format.apply(cell.trim())).flatten().unwrap_or_else(|| cell.clone())
+    }).collect()).collect()
+}
+
 pub fn stream_write_table_to_path(
     path: &Path,
     headers: &Option<Vec<String>>,
@@ -178,34 +284,108 @@ pub fn stream_write_table_to_path(
     row_ix: &[usize],
     delim: Option<char>,
     proj: ColumnProjection,
+    backup: Backup,
 ) -> io::Result<PathBuf> {
     let d = delim.unwrap_or(',');
-    let mut w = BufWriter::new(File::create(path)?);
-
-    // Headers (borrowed, streamed)
-    if let Some(hs) = headers.as_ref() {
-        let take = match proj {
-            ColumnProjection::KeepAll => hs.len(),
-            ColumnProjection::DropLast => hs.len().saturating_sub(1),
-        };
-        write_line_iter(&mut w, hs.iter().take(take).map(|s| s.as_str()), d)?;
-    }
-
-    // Rows (borrowed, streamed)
-    for &ix in row_ix {
-        if let Some(row) = raw_rows.get(ix) {
-            let take = match proj {
-                ColumnProjection::KeepAll => row.len(),
-                ColumnProjection::DropLast => row.len().saturating_sub(1),
-            };
-            write_line_iter(&mut w, row.iter().take(take).map(|s| s.as_str()), d)?;
+    write_atomic_with(path, backup, |w| {
+        // Headers (borrowed, streamed)
+        if let Some(hs) = headers.as_ref() {
+            write_line_iter(w, proj.cells(hs), d)?;
         }
-    }
 
-    w.flush()?;
+        // Rows (borrowed, streamed)
+        for &ix in row_ix {
+            if let Some(row) = raw_rows.get(ix) {
+                write_line_iter(w, proj.cells(row), d)?;
+            }
+        }
+        Ok(())
+    })?;
     Ok(path.to_path_buf())
 }
 
+/* ---------- table markup (Markdown / BBCode) ---------- */
+
+/// Render a table for pasting into chat or forum posts: a padded Markdown
+/// pipe table (numeric columns right-aligned) or a BBCode `[table]`.
+/// Delimited formats fall back to TSV-style text.
+pub fn markup_table(format: ExportFormat, headers: Option<&[&str]>, rows: &[Vec<&str>]) -> String {
+    match format {
+        ExportFormat::Markdown => markdown_table(headers, rows),
+        ExportFormat::BbCode => bbcode_table(headers, rows),
+        _ => {
+            let sep = format.delimiter().unwrap_or('\t');
+            let mut buf: Vec<u8> = Vec::new();
+            if let Some(h) = headers { let _ = write_row_strs(&mut buf, h, sep); }
+            for r in rows { let _ = write_row_strs(&mut buf, r, sep); }
+            String::from_utf8(buf).unwrap_or_default()
+        }
+    }
+}
+
+/// One line per cell; line breaks would end the table row.
+fn one_line(cell: &str) -> String {
+    cell.replace("\r\n", " ").replace(['\r', '\n'], " ")
+}
+
+fn markdown_table(headers: Option<&[&str]>, rows: &[Vec<&str>]) -> String {
+    let cols = headers.map(|h| h.len()).unwrap_or(0)
+        .max(rows.iter().map(|r| r.len()).max().unwrap_or(0));
+    if cols == 0 { return String::new(); }
+
+    let clean = |c: &str| one_line(c).replace('|', "\\|");
+    let head: Vec<String> = (0..cols)
+        .map(|ci| headers.and_then(|h| h.get(ci)).map(|c| clean(c)).unwrap_or_default())
+        .collect();
+    let body: Vec<Vec<String>> = rows.iter()
+        .map(|r| (0..cols).map(|ci| r.get(ci).map(|c| clean(c)).unwrap_or_default()).collect())
+        .collect();
+
+    // Right-align columns whose filled cells are all numbers
+    let numeric: Vec<bool> = (0..cols).map(|ci| {
+        let mut filled = body.iter().map(|r| r[ci].trim()).filter(|c| !c.is_empty()).peekable();
+        filled.peek().is_some() && filled.all(|c| c.parse::<f64>().is_ok())
+    }).collect();
+    let widths: Vec<usize> = (0..cols).map(|ci| {
+        body.iter().map(|r| r[ci].chars().count())
+            .chain([head[ci].chars().count(), 3])
+            .max().unwrap_or(3)
+    }).collect();
+
+    let line = |cells: &[String]| -> String {
+        let mut out = s!("|");
+        for (ci, c) in cells.iter().enumerate() {
+            let w = widths[ci];
+            if numeric[ci] { out.push_str(&format!(" {:>w$} |", c)); } else { out.push_str(&format!(" {:<w$} |", c)); }
+        }
+        out.push('\n');
+        out
+    };
+
+    let mut out = line(&head);
+    out.push('|');
+    for ci in 0..cols {
+        let dashes = "-".repeat(widths[ci] + 2);
+        if numeric[ci] { out.push_str(&dashes[1..]); out.push_str(":|"); } else { out.push_str(&dashes); out.push('|'); }
+    }
+    out.push('\n');
+    for r in &body { out.push_str(&line(r)); }
+    out
+}
+
+fn bbcode_table(headers: Option<&[&str]>, rows: &[Vec<&str>]) -> String {
+    let mut out = s!("[table]\n");
+    let mut row = |cells: &[&str], tag: &str| {
+        out.push_str("[tr]");
+        for c in cells { out.push_str(&format!("[{tag}]{}[/{tag}]", one_line(c))); }
+        out.push_str("[/tr]\n");
+    };
+    if let Some(h) = headers { row(h, "th"); }
+    for r in rows { row(r, "td"); }
+    out.push_str("[/table]\n");
+    out
+}
+
 /* ---------- high-level writers ---------- */
 
 /// Write a single export file based on ExportOptions (path, headers policy, delimiter, etc.).
@@ -214,7 +394,7 @@ pub fn write_export_single(
     options: &AppOptions,
     headers: &Option<Vec<String>>,
     rows: &[Vec<String>],
-) -> Result<PathBuf, Box<dyn Error>> {
+) -> Result<PathBuf, Error> {
     let export = &options.export;
     let path = export.out_path();
 
@@ -230,10 +410,70 @@ pub fn write_export_single(
         rows,
     );
 
-    fs::write(&path, contents)?;
+    let contents = match export.delimiter() {
+        Some(sep) if export.append && path.exists() => {
+            let existing = fs::read_to_string(&path).map_err(|e| Error::io(&path, e))?;
+            let key_cols = append_key_columns(options.scrape.page, headers);
+            let (text, added) = append_new_rows(&existing, &contents, sep, export.include_headers, &key_cols)
+                .map_err(|msg| Error::Other(format!("{}: {}", path.display(), msg)))?;
+            logf!("Export: Appending {} new row(s) of {} to {}", added, rows.len(), path.display());
+            text
+        }
+        _ => contents,
+    };
+
+    write_atomic(&path, contents.as_bytes(), export.backup).map_err(|e| Error::io(&path, e))?;
     Ok(path)
 }
 
+/// Positions in `headers` of the page's key columns (by schema name), for
+/// telling rows already in an appended file; empty when the page has no
+/// key or the view lacks one of them, and then whole rows are compared.
+fn append_key_columns(kind: PageKind, headers: &Option<Vec<String>>) -> Vec<usize> {
+    let (Some(schema), Some(hs)) = (crate::schema::for_page(kind), headers) else { return Vec::new() };
+    let keys = crate::gui::router::page_for(&kind).row_key_columns();
+    keys.iter()
+        .map(|&k| schema.columns.get(k).and_then(|c| hs.iter().position(|h| h == c.name)))
+        .collect::<Option<Vec<usize>>>()
+        .unwrap_or_default()
+}
+
+/// `existing` file text followed by the rows of `rendered` (the same export
+/// freshly written) whose key isn't in it yet, and how many those are.
+/// With headers, both must start with the same header row, so rows are never
+/// appended under other columns.
+pub fn append_new_rows(existing: &str, rendered: &str, sep: char, headers: bool, key_cols: &[usize]) -> Result<(String, usize), String> {
+    let old = parse_rows(existing, sep);
+    let new = parse_rows(rendered, sep);
+    if old.is_empty() {
+        return Ok((rendered.to_string(), new.len().saturating_sub(usize::from(headers))));
+    }
+    let skip = usize::from(headers);
+    if headers && old.first() != new.first() {
+        return Err(s!("columns differ from the export's; can't append (export without Append to replace it)"));
+    }
+
+    let key = |r: &[String]| -> Vec<String> {
+        if key_cols.is_empty() {
+            r.iter().map(|c| c.trim().to_string()).collect()
+        } else {
+            key_cols.iter().map(|&c| r.get(c).map(|v| v.trim()).unwrap_or("").to_string()).collect()
+        }
+    };
+    let mut seen: HashSet<Vec<String>> = old.iter().skip(skip).map(|r| key(r)).collect();
+
+    let mut buf = existing.as_bytes().to_vec();
+    if !existing.ends_with('\n') { buf.push(b'\n'); }
+    let mut added = 0;
+    for r in new.iter().skip(skip) {
+        if seen.insert(key(r)) {
+            write_row(&mut buf, r, sep).map_err(|e| e.to_string())?;
+            added += 1;
+        }
+    }
+    Ok((String::from_utf8(buf).unwrap_or_default(), added))
+}
+
 /// Write multiple team files into the directory implied by `export.out_path()`
 /// (which must be a directory when `export.export_type == PerTeam`).
 /// `team_col` is the column index of the "Team" field in `rows` (Players = 3).
@@ -242,7 +482,7 @@ pub fn write_export_per_team(
     headers: &Option<Vec<String>>,
     rows: &[Vec<String>],
     team_col: usize,
-) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+) -> Result<Vec<PathBuf>, Error> {
 
     let export = &options.export;
 
@@ -251,21 +491,99 @@ pub fn write_export_per_team(
     ensure_directory(&outdir)?;
 
     // Group rows by team name from the given column
+    let by_team = group_by_team(rows, &[team_col]);
+
+    // Dedup stems and write each file
+    write_team_groups(options, headers, &outdir, by_team, &divisions_for(export))
+}
+
+/// Rows per team name found in any of `team_cols` (a row lands in each).
+fn group_by_team(rows: &[Vec<String>], team_cols: &[usize]) -> HashMap<String, Vec<Vec<String>>> {
     let mut by_team: HashMap<String, Vec<Vec<String>>> = HashMap::new();
     for r in rows {
-        if let Some(team) = r.get(team_col) {
-            by_team.entry(team.clone()).or_default().push(r.clone());
+        for &ci in team_cols {
+            // Blank: no team in that role (e.g. a signing has no other team)
+            if let Some(team) = r.get(ci).filter(|t| !t.is_empty()) {
+                by_team.entry(team.clone()).or_default().push(r.clone());
+            }
         }
     }
+    by_team
+}
 
-    // Dedup stems and write each file
-    let mut seen: HashMap<String, usize> = HashMap::new();
+/// Per-team row order from the export options, applied to `row_ix`: by the
+/// `sort_by` column (numbers by value), then with `chronological` by season
+/// and week. Both sorts are stable, so `sort_by` holds within a week and
+/// ties keep the scrape order, or for players (`#` and `Name` columns) go
+/// by number and name so namesakes come out the same way every time.
+/// Columns are found by header name.
+pub fn sort_team_rows(export: &ExportOptions, headers: Option<&[String]>, rows: &[Vec<String>], row_ix: &mut [usize]) {
+    let Some(hs) = headers else { return };
+    let col = |name: &str| hs.iter().position(|h| h.eq_ignore_ascii_case(name));
+
+    if let Some(c) = export.sort_by.as_deref().and_then(col) {
+        if let (Some(nc), Some(mc)) = (col("#"), col("Name")) {
+            let cell = |i: usize, c: usize| rows.get(i).and_then(|r| r.get(c)).map(|v| v.trim()).unwrap_or("");
+            row_ix.sort_by_cached_key(|&i| {
+                let number = cell(i, nc).trim_start_matches('#').parse::<u32>().unwrap_or(u32::MAX);
+                (number, cell(i, mc).to_string())
+            });
+        }
+        let numeric = ColumnType::infer(row_ix.iter().filter_map(|&i| rows.get(i)?.get(c)).map(String::as_str)).is_numeric();
+        SortKey { col: c, descending: export.sort_descending }.sort_row_ix(rows, row_ix, numeric);
+    }
+    if let (true, Some(sc), Some(wc)) = (export.chronological, col("S"), col("W")) {
+        let num = |i: usize, c: usize| rows.get(i).and_then(|r| r.get(c)).and_then(|v| v.trim().parse::<u32>().ok());
+        // Rows without a season/week go last
+        row_ix.sort_by_key(|&i| (num(i, sc).unwrap_or(u32::MAX), num(i, wc).unwrap_or(u32::MAX)));
+    }
+}
+
+/// Per-team export finished, but some files could not be written
+/// (e.g. locked by a spreadsheet, or missing permissions).
+/// Returned as the error of the per-team writers so callers can't miss it.
+#[derive(Debug)]
+pub struct PartialExport {
+    pub written: Vec<PathBuf>,
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+impl std::fmt::Display for PartialExport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Exported {} file(s); {} failed:", self.written.len(), self.failed.len())?;
+        for (path, err) in &self.failed {
+            write!(f, "\n  {}: {}", path.display(), err)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for PartialExport {}
+
+/// Write each team's rows to its own file, continuing past failures.
+/// Dedups stems within this run; collects any write errors into `PartialExport`.
+/// With `divisions`, each team's file goes into its division's subdirectory.
+fn write_team_groups(
+    options: &AppOptions,
+    headers: &Option<Vec<String>>,
+    outdir: &Path,
+    by_team: HashMap<String, Vec<Vec<String>>>,
+    divisions: &Divisions,
+) -> Result<Vec<PathBuf>, Error> {
     let mut written = Vec::with_capacity(by_team.len());
-    let ext = export.format.ext();
+    let mut failed = Vec::new();
+
+    for (path, team_rows) in team_targets(options, outdir, by_team, divisions) {
+        let dir = path.parent().unwrap_or(outdir).to_path_buf();
+        if let Err(e) = fs::create_dir_all(&dir) {
+            loge!("Export: create_dir_all failed {}: {}", dir.display(), e);
+            failed.push((dir, e.to_string()));
+            continue;
+        }
 
-    for (team_name, team_rows) in by_team {
-        let base_stem = sanitize::sanitize_team_filename(&team_name, 0);
-        let path = resolve_team_filename(&outdir, &base_stem, &mut seen, ext);
+        let mut order: Vec<usize> = (0..team_rows.len()).collect();
+        sort_team_rows(&options.export, headers.as_deref(), &team_rows, &mut order);
+        let team_rows: Vec<Vec<String>> = order.into_iter().map(|i| team_rows[i].clone()).collect();
 
         let contents = to_export_string(
             options,
@@ -273,20 +591,150 @@ pub fn write_export_per_team(
             &team_rows,
         );
 
-        fs::write(&path, contents)?;
-        written.push(path);
+        match write_atomic(&path, contents.as_bytes(), options.export.backup) {
+            Ok(()) => written.push(path),
+            Err(e) => {
+                loge!("Export: per-team write failed {}: {}", path.display(), e);
+                failed.push((path, e.to_string()));
+            }
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(written)
+    } else {
+        Err(Error::PartialExport(PartialExport { written, failed }))
+    }
+}
+
+/// The file for each team's rows, in team name order: the team's division
+/// directory, its sanitized name, " (2)" … for names that collide.
+fn team_targets(
+    options: &AppOptions,
+    outdir: &Path,
+    by_team: HashMap<String, Vec<Vec<String>>>,
+    divisions: &Divisions,
+) -> Vec<(PathBuf, Vec<Vec<String>>)> {
+    let mut seen: HashMap<PathBuf, HashMap<String, usize>> = HashMap::new();
+    let ext = options.export.format.ext();
+    let mut by_team: Vec<_> = by_team.into_iter().collect();
+    by_team.sort_by(|a, b| a.0.cmp(&b.0));
+
+    by_team.into_iter().map(|(team_name, team_rows)| {
+        let dir = division_dir(outdir, &team_name, divisions);
+        let base_stem = sanitize::sanitize_team_filename(&team_name, 0);
+        let path = resolve_team_filename(&dir, &base_stem, seen.entry(dir.clone()).or_default(), ext);
+        (path, team_rows)
+    }).collect()
+}
+
+/// The files a per-team export of `rows` would write, with their row counts,
+/// without writing anything (CLI `--dry-run`).
+pub fn planned_team_files(
+    options: &AppOptions,
+    rows: &[Vec<String>],
+    team_cols: &[usize],
+    divisions: &Divisions,
+) -> Vec<(PathBuf, usize)> {
+    let outdir = options.export.out_path();
+    team_targets(options, &outdir, group_by_team(rows, team_cols), divisions)
+        .into_iter()
+        .map(|(path, rows)| (path, rows.len()))
+        .collect()
+}
+
+/* ---------- division grouping ---------- */
+
+/// Team → division map to group this export by; empty unless
+/// `export.by_division` is on and the cached team list knows divisions.
+pub fn divisions_for(export: &ExportOptions) -> Divisions {
+    if !export.by_division { return Divisions::new(); }
+    let divisions = get_teams::divisions();
+    if divisions.is_empty() {
+        logd!("Export: by division, but no division data in the team list; not grouping");
+    }
+    divisions
+}
+
+/// `outdir/<division>` for a team with a known division, else `outdir`.
+pub fn division_dir(outdir: &Path, team: &str, divisions: &Divisions) -> PathBuf {
+    match divisions.get(team) {
+        Some(div) => outdir.join(sanitize::sanitize_team_filename(div, 0)),
+        None => outdir.to_path_buf(),
     }
+}
 
-    Ok(written)
+/// Columns holding team names: one for roster-like pages, two for fixtures
+/// (home, away), injuries (victim, offender) and transactions (team, other). Found by header name, so
+/// reordered ("As shown") exports work; default layout without headers.
+fn team_columns(kind: PageKind, headers: &Option<Vec<String>>) -> Vec<usize> {
+    let names: &[&str] = match kind {
+        GameResults => &["Home", "Away"],
+        PageKind::Injuries | PageKind::InjuryVictims => &["Victim Team", "Offender Team"],
+        PageKind::Transactions => &["Team", "Other Team"],
+        _ => &["Team"],
+    };
+    match headers {
+        Some(h) => names.iter().filter_map(|n| h.iter().position(|c| c == n)).collect(),
+        None => match kind {
+            Players => vec![3],
+            GameResults => vec![2, 5],
+            PageKind::Injuries | PageKind::InjuryVictims => vec![2, 8],
+            PageKind::Transactions => vec![2, 6],
+            PageKind::TeamSummary => vec![0],
+            PageKind::Leaders => vec![3],
+            PageKind::ResultsTable => vec![2],
+            PageKind::ScheduleMetrics => vec![0],
+            PageKind::TeamInfo => vec![1],
+            _ => vec![],
+        },
+    }
+}
+
+/// Append a division column per team column ("Division"; "Home Division",
+/// "Away Division"; "Victim Division", "Offender Division"). Unknown teams
+/// get a blank cell. No-op with an empty map.
+pub fn add_division_columns(
+    kind: PageKind,
+    headers: &mut Option<Vec<String>>,
+    rows: &mut [Vec<String>],
+    divisions: &Divisions,
+) {
+    if divisions.is_empty() { return; }
+    let cols = team_columns(kind, headers);
+    if let Some(h) = headers {
+        for &ci in &cols {
+            let team = h.get(ci).map(String::as_str).unwrap_or("Team");
+            h.push(match team.trim_end_matches("Team").trim() {
+                "" => s!("Division"),
+                side => format!("{} Division", side),
+            });
+        }
+    }
+    for r in rows.iter_mut() {
+        let divs: Vec<String> = cols.iter()
+            .map(|&ci| r.get(ci).and_then(|t| divisions.get(t)).cloned().unwrap_or_default())
+            .collect();
+        r.extend(divs);
+    }
+}
+
+/// Remove the column whose header is `name` (no-op without headers or match).
+pub fn drop_column_named(headers: &mut Option<Vec<String>>, rows: &mut [Vec<String>], name: &str) {
+    let Some(ix) = headers.as_ref().and_then(|h| h.iter().position(|c| c == name)) else { return };
+    if let Some(h) = headers { h.remove(ix); }
+    for r in rows.iter_mut() {
+        if ix < r.len() { r.remove(ix); }
+    }
 }
 
 /* ---------- path utils ---------- */
 
-pub fn ensure_directory(dir: &Path) -> Result<(), Box<dyn Error>> {
+pub fn ensure_directory(dir: &Path) -> Result<(), Error> {
     if dir.exists() && !dir.is_dir() {
         return Err(format!("Path exists but is not a directory: {}", dir.display()).into());
     }
-    if !dir.exists() { fs::create_dir_all(dir)?; }
+    if !dir.exists() { fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?; }
     Ok(())
 }
 
@@ -297,8 +745,9 @@ pub fn resolve_team_filename(
     seen_names: &mut HashMap<String, usize>,
     ext: &str,                         // "csv" | "tsv" | ...
 ) -> PathBuf {
-    // How many times have we seen this base?
-    let count = seen_names.entry(stem.to_string()).or_insert(0);
+    // How many times have we seen this base? Case-insensitive, as the
+    // file systems on Windows and macOS are ("Alpha" and "ALPHA" collide).
+    let count = seen_names.entry(stem.to_lowercase()).or_insert(0);
 
     // First occurrence: "<stem>.ext"
     // Subsequent:       "<stem> (N).ext" with N starting at 2
@@ -344,18 +793,81 @@ where
     C: IntoIterator<Item = &'a str>,
 {
     let path = options.out_path();
-    let f = File::create(&path)?;
-    let mut w = BufWriter::new(f);
     let delim = options.delimiter().unwrap_or(',');
 
-    if let Some(hs) = headers {
-        write_line(&mut w, hs, delim)?;
+    write_atomic_with(&path, options.backup, |w| {
+        if let Some(hs) = headers {
+            write_line(w, hs, delim)?;
+        }
+        for row in rows {
+            write_line(w, row, delim)?;
+        }
+        Ok(())
+    })?;
+    Ok(path)
+}
+
+/* ---------- atomic writes ---------- */
+
+/// Replace `path` with `contents` in one step: written to a temporary file
+/// next to it, then renamed over it, so a crash mid-write leaves the old
+/// file or the new one, never half of each. `backup` keeps the old one.
+pub fn write_atomic(path: &Path, contents: &[u8], backup: Backup) -> io::Result<()> {
+    write_atomic_with(path, backup, |w| w.write_all(contents))
+}
+
+/// `write_atomic`, streaming: `write` fills the temporary file.
+pub fn write_atomic_with<F>(path: &Path, backup: Backup, write: F) -> io::Result<()>
+where
+    F: FnOnce(&mut BufWriter<File>) -> io::Result<()>,
+{
+    let tmp = temp_path(path);
+    let filled = File::options().write(true).create_new(true).open(&tmp).and_then(|f| {
+        let mut w = BufWriter::new(f);
+        write(&mut w)?;
+        w.into_inner().map_err(|e| e.into_error())?.sync_all()
+    });
+    let done = filled
+        .and_then(|_| keep_backup(path, backup))
+        .and_then(|_| fs::rename(&tmp, path));
+    if done.is_err() {
+        let _ = fs::remove_file(&tmp);
     }
-    for row in rows {
-        write_line(&mut w, row, delim)?;
+    done
+}
+
+/// `.<name>.<pid>.<n>.tmp` in the same directory (a rename can't cross file
+/// systems); `n` counts calls, so concurrent writes of one file don't share it.
+fn temp_path(path: &Path) -> PathBuf {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let n = NEXT.fetch_add(1, Ordering::Relaxed);
+    path.with_file_name(format!(".{}.{}.{}.tmp", name, std::process::id(), n))
+}
+
+/// Where `backup` keeps the current `path` before it's replaced, if anywhere.
+pub fn backup_path(path: &Path, backup: Backup) -> Option<PathBuf> {
+    let name = path.file_name()?.to_string_lossy().into_owned();
+    match backup {
+        Backup::Off => None,
+        Backup::Bak => Some(path.with_file_name(format!("{}.bak", name))),
+        Backup::Timestamped => {
+            let secs = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            Some(path.with_file_name(format!("{}.{}.bak", name, secs)))
+        }
+    }
+}
+
+/// Copy an existing `path` to its backup (copied, so `path` stays in place
+/// until the rename replaces it).
+fn keep_backup(path: &Path, backup: Backup) -> io::Result<()> {
+    match backup_path(path, backup) {
+        Some(bak) if path.is_file() => fs::copy(path, &bak).map(|_| ()),
+        _ => Ok(()),
     }
-    w.flush()?;
-    Ok(path)
 }
 
 /* ---------- tiny CSV/TSV helpers (match your existing rules) ---------- */
@@ -413,14 +925,18 @@ fn write_line<'a, I, W: Write>(w: &mut W, cells: I, delim: char) -> io::Result<(
     Ok(())
 }
 
-fn push_escaped(buf: &mut String, cell: &str, _delim: char) {
-    // Mirror your existing rules (quotes, newlines, etc.)
-    buf.push_str(cell);
+fn push_escaped(buf: &mut String, cell: &str, delim: char) {
+    if needs_quotes(cell, delim) {
+        buf.push('"');
+        buf.push_str(&cell.replace('"', "\"\""));
+        buf.push('"');
+    } else {
+        buf.push_str(cell);
+    }
 }
 
-fn write_escaped<W: Write>(w: &mut W, cell: &str, _delim: char) -> io::Result<()> {
-    // Mirror your existing rules
-    w.write_all(cell.as_bytes())
+fn write_escaped<W: Write>(w: &mut W, cell: &str, delim: char) -> io::Result<()> {
+    write_cell(w, cell, delim)
 }
 
 /// Write per-team files for pages that have two team columns (e.g., Game Results: home and away).
@@ -431,51 +947,37 @@ pub fn write_export_per_team_results(
     rows: &[Vec<String>],
     home_col: usize,
     away_col: usize,
-) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+) -> Result<Vec<PathBuf>, Error> {
     let export = &options.export;
     let outdir = export.out_path();
     ensure_directory(&outdir)?;
 
     // Group rows by team name, considering both home and away columns
-    let mut by_team: HashMap<String, Vec<Vec<String>>> = HashMap::new();
-    for r in rows {
-        if let Some(home) = r.get(home_col) {
-            by_team.entry(home.clone()).or_default().push(r.clone());
-        }
-        if let Some(away) = r.get(away_col) {
-            by_team.entry(away.clone()).or_default().push(r.clone());
-        }
-    }
-
-    let mut seen: HashMap<String, usize> = HashMap::new();
-    let mut written = Vec::with_capacity(by_team.len());
-    let ext = export.format.ext();
-
-    for (team_name, team_rows) in by_team {
-        let base_stem = sanitize::sanitize_team_filename(&team_name, 0);
-        let path = resolve_team_filename(&outdir, &base_stem, &mut seen, ext);
-
-        let contents = to_export_string(
-            options,
-            headers,
-            &team_rows,
-        );
-
-        fs::write(&path, contents)?;
-        written.push(path);
-    }
+    let by_team = group_by_team(rows, &[home_col, away_col]);
 
-    Ok(written)
+    write_team_groups(options, headers, &outdir, by_team, &divisions_for(export))
 }
 
 /// Central export entry for both CLI and GUI tests without any network.
 /// Chooses the right writer based on page kind and ExportOptions.
+/// Writes `manifest.json` alongside when enabled (no season recorded here).
 pub fn export_dataset(
     options: &AppOptions,
     kind: PageKind,
     headers: &Option<Vec<String>>,
     rows: &[Vec<String>],
-) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+) -> Result<Vec<PathBuf>, Error> {
+    export_dataset_with(options, kind, headers, rows, &divisions_for(&options.export))
+}
+
+/// `export_dataset` with an explicit team → division map (empty: no grouping).
+pub fn export_dataset_with(
+    options: &AppOptions,
+    kind: PageKind,
+    headers: &Option<Vec<String>>,
+    rows: &[Vec<String>],
+    divisions: &Divisions,
+) -> Result<Vec<PathBuf>, Error> {
     let export = &options.export;
 
     // Adjust headers/rows for page-agnostic optional fields
@@ -485,25 +987,110 @@ pub fn export_dataset(
         for r in &mut rws { if !r.is_empty() { r.pop(); } }
     }
 
-    // For Players, skip_optional maps to keep_hash = false
+    // For Players, skip_optional maps to keep_hash = false and no player id
     let mut local = options.clone();
     if export.skip_optional && matches!(kind, Players) {
         local.export.keep_hash = false;
+        drop_column_named(&mut hdrs, &mut rws, crate::scrape::PLAYER_ID_HEADER);
     }
     let opts = &local;
 
-    match export.export_type {
+    let written = match export.export_type {
         crate::config::options::ExportType::SingleFile => {
+            add_division_columns(kind, &mut hdrs, &mut rws, divisions);
             let path = write_export_single(opts, &hdrs, &rws)?;
-            Ok(vec![path])
+            vec![path]
         }
         crate::config::options::ExportType::PerTeam => {
-            match kind {
-                Players => write_export_per_team(opts, &hdrs, &rws, 3),
-                GameResults => write_export_per_team_results(opts, &hdrs, &rws, 2, 5),
-                PageKind::Injuries => write_export_per_team_results(opts, &hdrs, &rws, 2, 8),
-                _ => write_export_per_team(opts, &hdrs, &rws, 0), // best-effort
-            }
+            let outdir = export.out_path();
+            ensure_directory(&outdir)?;
+            // By name, so dropped optional columns don't shift them
+            let mut cols = team_columns(kind, &hdrs);
+            if cols.is_empty() { cols = vec![0]; } // best-effort
+            write_team_groups(opts, &hdrs, &outdir, group_by_team(&rws, &cols), divisions)?
         }
+    };
+
+    crate::manifest::write_for_export(kind, None, export, &written)
+        .map_err(|e| Error::io(&crate::manifest::manifest_dir(export), e))?;
+    match export.export_type {
+        crate::config::options::ExportType::PerTeam => zip_export(export, &export.out_path(), written),
+        crate::config::options::ExportType::SingleFile => Ok(written),
+    }
+}
+
+/// With `export.zip`: pack the files of a per-team or bundle export in `dir`
+/// (and the manifests next to them) into `<dir>.zip`, then remove them.
+/// Returns the archive, or `written` unchanged when zip is off.
+pub fn zip_export(export: &ExportOptions, dir: &Path, written: Vec<PathBuf>) -> Result<Vec<PathBuf>, Error> {
+    if !export.zip || written.is_empty() {
+        return Ok(written);
+    }
+    let mut files = written;
+    let mut dirs: Vec<PathBuf> = files.iter().filter_map(|p| p.parent().map(Path::to_path_buf)).collect();
+    dirs.push(dir.to_path_buf());
+    dirs.sort();
+    dirs.dedup();
+    files.extend(dirs.iter().map(|d| d.join(crate::manifest::MANIFEST_FILE)).filter(|m| m.exists()));
+
+    let entries = files.iter()
+        .map(|p| fs::read(p).map(|bytes| (crate::zip::entry_name(dir, p), bytes)).map_err(|e| Error::io(p, e)))
+        .collect::<Result<Vec<_>, Error>>()?;
+    let archive = dir.with_extension("zip");
+    crate::zip::archive(&entries)
+        .and_then(|zip| write_atomic(&archive, &zip, export.backup))
+        .map_err(|e| Error::io(&archive, e))?;
+
+    for p in &files {
+        let _ = fs::remove_file(p);
+    }
+    // Deepest first; only the ones left empty go
+    for d in dirs.iter().rev() {
+        let _ = fs::remove_dir(d);
+    }
+    logf!("Export: Zipped {} file(s) → {}", entries.len(), archive.display());
+    Ok(vec![archive])
+}
+
+/// Pages an export bundle takes from the cache, in this order.
+pub const BUNDLE_PAGES: [PageKind; 13] = [
+    PageKind::Teams, Players, PageKind::TeamSummary, PageKind::Leaders, PageKind::TeamInfo, GameResults,
+    PageKind::Injuries, PageKind::InjuryVictims, PageKind::InjurySummary, PageKind::Standings,
+    PageKind::ResultsTable, PageKind::ScheduleMetrics, PageKind::Transactions,
+];
+
+/// Export several pages at once into `dir`, one subdirectory per page
+/// (`<dir>/players/all.tsv`, `<dir>/teams/teams.tsv`, …). Each page goes
+/// through `export_dataset_with` with its default file name and otherwise
+/// the given options; per-team only where the page supports it. Pages
+/// without rows are skipped. Returns the files written.
+pub fn export_bundle(
+    options: &AppOptions,
+    dir: &Path,
+    datasets: Vec<(PageKind, DataSet)>,
+) -> Result<Vec<PathBuf>, Error> {
+    let divisions = divisions_for(&options.export);
+    let mut written = Vec::new();
+
+    // Zipped once as a whole below, not page by page
+    let mut paged = options.clone();
+    paged.export.zip = false;
+
+    for (kind, mut ds) in datasets {
+        if ds.rows.is_empty() { continue; }
+        crate::schema::inject_headers(kind, &mut ds);
+
+        let mut local = paged.clone();
+        local.scrape.page = kind;
+        local.export.set_dir_with_default_file(dir.join(kind.to_string()), kind);
+        if !crate::gui::router::page_for(&kind).per_team_applicable() {
+            local.export.export_type = crate::config::options::ExportType::SingleFile;
+        }
+
+        let files = export_dataset_with(&local, kind, &ds.headers, &ds.rows, &divisions)
+            .map_err(|e| format!("{}: {}", kind, e))?;
+        logd!("Export: Bundle {} → {} file(s)", kind, files.len());
+        written.extend(files);
     }
+    zip_export(&options.export, dir, written)
 }
@@ -1,55 +1,178 @@
-// src/teams.rs
-//! Teams data *facade* (cache-aware).
+// src/get_teams.rs
+//! Teams data *facade* (cache-aware). The one place that knows the team list.
 //!
 //! Purpose:
-//! - Provide callers (GUI, other modules) with `Vec<(u32, String)>` of `(team_id, team_name)`.
+//! - Provide callers (GUI, CLI, scrapers, validators, exports) with `Vec<(u32, String)>` of `(team_id, team_name)`.
 //! - **Load from cache** if present (`store::load_dataset(PageKind::Teams)`).
-//! - Otherwise **scrape & cache** by calling `scrape::collect_teams(None)` and persisting the returned dataset.
+//! - Otherwise **scrape & cache** by calling `scrape::collect_teams(None)` and persisting the returned dataset,
+//!   only where a caller asks for it (`load`, `list_or_scrape`, `refresh`); `list` never fetches.
+//! - One fallback when neither works (`fallback`: ids 0–31 named "Team N").
+//! - Change notification: `generation()` moves whenever the cached team list
+//!   is saved, by anyone (GUI scrape, CLI, the players scrape refreshing it).
 //!
 //! Responsibilities:
 //! - Cache read/write (`store::load_dataset` / `store::save_dataset`).
-//! - Convert the raw `DataSet` rows into `(u32, String)` pairs (`dataset_to_pairs`).
+//! - Convert the raw `DataSet` rows into `(u32, String)` pairs (`pairs`).
+//! - Team → division lookup (`divisions`, `by_division`) from the cached list's `Division` column.
+//! - The "unknown team" validation error (`unknown_team`), which tells a
+//!   scrape that the cached list is out of date and worth a `refresh`.
 //!
 //! Non-Responsibilities (by design):
-//! - **No HTML parsing** (that lives in `src/specs/teams.rs`).
+//! - **No HTML parsing** (that lives in `src/scrape/teams.rs`).
 //! - **No network fetching** (delegated through `scrape::collect_teams`).
 //!
-//! TL;DR: `teams.rs` decides *when* to scrape vs. reuse cached data and exposes a simple, ready-to-use list.
-use std::error::Error;
+//! TL;DR: `get_teams.rs` decides *when* to scrape vs. reuse cached data and exposes a simple, ready-to-use list.
+use std::collections::{BTreeMap, HashMap};
 use crate::config::options::PageKind::Teams;
-use crate::{scrape, store, store::DataSet};
+use crate::{error::Error, scrape, store, store::DataSet};
 
-fn dataset_to_pairs(ds: &store::DataSet) -> Vec<(u32, String)> {
+/// `(id, name)` pairs from a Teams dataset (id in column 0, name in column 1).
+pub fn pairs(ds: &DataSet) -> Vec<(u32, String)> {
     ds.rows.iter().filter_map(|r| {
-        let id = r.get(0).and_then(|s| s.parse::<u32>().ok())?;
+        let id = r.first().and_then(|s| s.parse::<u32>().ok())?;
         let name = r.get(1).cloned().unwrap_or_default();
         Some((id, name))
     }).collect()
 }
 
-/// Load cached teams if present; otherwise scrape and cache.
-pub fn load() -> Result<Vec<(u32, String)>, Box<dyn Error>> {
-    if let Ok(ds) = store::load_dataset(&Teams) {
-        if !ds.rows.is_empty() {
-            return Ok(ds.rows
-                .iter()
-                .filter_map(
-                    |r| {
-                        let id = r.get(0)?.parse::<u32>().ok()?;
-                        Some((id, r.get(1).cloned().unwrap_or_default()))
-                    }
-                )
-                .collect()
-            );
+/// Cached teams only; `None` when there is no (non-empty) cache. Never fetches.
+pub fn cached() -> Option<Vec<(u32, String)>> {
+    let ds = store::load_dataset(&Teams).ok()?;
+    let v = pairs(&ds);
+    (!v.is_empty()).then_some(v)
+}
+
+/// Team name → division/conference name.
+pub type Divisions = HashMap<String, String>;
+
+/// Divisions from the cached team list, if it has a `Division` column
+/// (empty otherwise; never fetches). Teams with a blank division are left out.
+pub fn divisions() -> Divisions {
+    let Ok(ds) = store::load_dataset(&Teams) else { return Divisions::new() };
+    let Some(dc) = ds.header_index("Division") else { return Divisions::new() };
+    ds.rows.iter()
+        .filter_map(|r| {
+            let div = r.get(dc).filter(|d| !d.trim().is_empty())?;
+            Some((r.get(1)?.clone(), div.clone()))
+        })
+        .collect()
+}
+
+/// Team ids per division, divisions in name order (team panel groups,
+/// CLI `--division`). Teams without a division are left out.
+pub fn by_division(teams: &[(u32, String)], divisions: &Divisions) -> BTreeMap<String, Vec<u32>> {
+    let mut out: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+    for (id, name) in teams {
+        if let Some(div) = divisions.get(name) {
+            out.entry(div.clone()).or_default().push(*id);
         }
     }
-    refresh()
+    out
+}
+
+/// Load cached teams if present; otherwise scrape and cache.
+pub fn load() -> Result<Vec<(u32, String)>, Error> {
+    match cached() {
+        Some(v) => Ok(v),
+        None => refresh(),
+    }
 }
 
 /// Force refresh from the site and update cache.
-pub fn refresh() -> Result<Vec<(u32, String)>, Box<dyn Error>> {
+pub fn refresh() -> Result<Vec<(u32, String)>, Error> {
     let ds = scrape::collect_teams(None)?;
-    // persist in the same raw “dataset” format you use elsewhere
-    store::save_dataset(&Teams, &DataSet { headers: ds.headers.clone(), rows: ds.rows.clone() })?;
-    Ok(dataset_to_pairs(&ds))
+    save(&ds).map_err(|e| Error::io(&store::store_dir(), e))?;
+    Ok(pairs(&ds))
+}
+
+/// Persist a freshly scraped Teams dataset (notifies `generation` watchers).
+pub fn save(ds: &DataSet) -> std::io::Result<()> {
+    store::save_dataset(&Teams, ds).map(|_| ())
+}
+
+/// The cached team list, or `fallback()` without one. Never fetches, so
+/// it's cheap enough to call per request (`serve`).
+pub fn list() -> Vec<(u32, String)> {
+    cached().unwrap_or_else(|| {
+        logd!("Teams: no cached team list, using placeholders");
+        fallback()
+    })
+}
+
+/// The team list, never failing: `load()` (which scrapes it when nothing
+/// is cached), or `fallback()` if that fails.
+pub fn list_or_scrape() -> Vec<(u32, String)> {
+    match load() {
+        Ok(v) if !v.is_empty() => v,
+        Ok(_) => {
+            loge!("Teams: empty team list, using placeholders");
+            fallback()
+        }
+        Err(e) => {
+            loge!("Teams: could not load team list: {}", e);
+            fallback()
+        }
+    }
+}
+
+/// Placeholder list when nothing better is available: ids 0–31, "Team N".
+pub fn fallback() -> Vec<(u32, String)> {
+    (0u32..32).map(|id| (id, format!("Team {}", id))).collect()
+}
+
+/// The team whose name contains `query` (case-insensitive; a full-name
+/// match wins over partial ones). Errors name the candidates when several
+/// match.
+pub fn find(teams: &[(u32, String)], query: &str) -> Result<u32, String> {
+    let q = query.trim().to_lowercase();
+    if q.is_empty() {
+        return Err(s!("Empty team name"));
+    }
+    if let Some((id, _)) = teams.iter().find(|(_, name)| name.to_lowercase() == q) {
+        return Ok(*id);
+    }
+    let hits: Vec<&(u32, String)> = teams.iter().filter(|(_, name)| name.to_lowercase().contains(&q)).collect();
+    match hits.as_slice() {
+        [(id, _)] => Ok(*id),
+        [] => Err(format!("No team name contains '{}' (see --list-teams)", query.trim())),
+        _ => {
+            let names: Vec<String> = hits.iter().map(|(id, name)| format!("{} ({})", name, id)).collect();
+            Err(format!("Team name '{}' is ambiguous: {}", query.trim(), names.join(", ")))
+        }
+    }
+}
+
+/// Start of the validation error for a team name the list doesn't have.
+const UNKNOWN_TEAM: &str = "Unknown team name";
+
+/// Validation error for `name` (found in `what`) missing from the team list.
+pub fn unknown_team(name: &str, what: &str) -> String {
+    format!("{} '{}' in {}", UNKNOWN_TEAM, name, what)
+}
+
+/// Whether a validation error is `unknown_team`'s: the team list is likely
+/// stale (a new or renamed team), so refresh it and validate again.
+pub fn is_unknown_team(msg: &str) -> bool {
+    msg.starts_with(UNKNOWN_TEAM)
+}
+
+/// Bumped each time the cached team list is saved. Compare against a value
+/// kept from earlier to know when to reload (see `cached`).
+pub fn generation() -> u64 {
+    store::generation(&Teams)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_matches_substrings_and_reports_ambiguity() {
+        let teams = vec![(3, s!("Bad Dogs")), (7, s!("Bad Dogs United")), (9, s!("Orc Raiders"))];
+        assert_eq!(find(&teams, "raid"), Ok(9));
+        assert_eq!(find(&teams, "bad dogs"), Ok(3));
+        assert_eq!(find(&teams, "DOGS U"), Ok(7));
+        assert!(find(&teams, "bad").unwrap_err().contains("Bad Dogs United (7)"));
+        assert!(find(&teams, "elves").is_err());
+    }
 }
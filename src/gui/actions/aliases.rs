@@ -0,0 +1,94 @@
+// src/gui/actions/aliases.rs
+//
+// Resolve names: injury victims that match no player get an alias to the
+// right player; the alias is saved, applied to the cached pages and the
+// Injury Victims join is rebuilt.
+
+use std::{collections::HashMap, time::SystemTime};
+
+use crate::{
+    alias::Aliases,
+    config::options::PageKind,
+    gui::app::App,
+    join,
+    store,
+};
+
+/// State of the Resolve names window.
+pub struct AliasEditor {
+    pub aliases: Aliases,
+    /// Victims without a player, as (team, name)
+    pub unmatched: Vec<(String, String)>,
+    /// Player names per team, sorted, to pick from
+    pub rosters: HashMap<String, Vec<String>>,
+    /// Picked canonical name per unmatched row
+    pub picks: Vec<String>,
+}
+
+impl AliasEditor {
+    fn build(app: &App) -> Self {
+        let aliases = Aliases::load();
+        let dataset = |k: PageKind| app.raw_data.get(&k).map(|r| r.dataset());
+
+        let mut rosters: HashMap<String, Vec<String>> = HashMap::new();
+        let mut unmatched = Vec::new();
+        if let Some(players) = dataset(PageKind::Players) {
+            let (name, team) = (players.header_index("Name").unwrap_or(0), players.header_index("Team").unwrap_or(3));
+            for r in &players.rows {
+                if let (Some(n), Some(t)) = (r.get(name), r.get(team)) {
+                    rosters.entry(t.clone()).or_default().push(n.clone());
+                }
+            }
+            for names in rosters.values_mut() {
+                names.sort_by_key(|n| n.to_lowercase());
+                names.dedup();
+            }
+            if let Some(injuries) = dataset(PageKind::Injuries) {
+                let (_, stats) = join::injuries_with_victims(injuries, players, &aliases);
+                for m in stats.missing {
+                    if !m.1.trim().is_empty() && !unmatched.contains(&m) { unmatched.push(m); }
+                }
+            }
+        }
+        let picks = vec![String::new(); unmatched.len()];
+        Self { aliases, unmatched, rosters, picks }
+    }
+}
+
+/// Open the window with the victims the join can't place.
+pub fn open(app: &mut App) {
+    let editor = AliasEditor::build(app);
+    logd!("Aliases: {} unmatched victim(s), {} alias(es)", editor.unmatched.len(), editor.aliases.iter().count());
+    app.alias_editor = Some(editor);
+}
+
+/// Save `variant` → `canonical` (empty `canonical`: remove the alias), apply
+/// the aliases to the cached pages and refresh the window.
+pub fn save(app: &mut App, variant: &str, canonical: &str) {
+    if let Err(e) = store::save_name_alias(variant, canonical) {
+        loge!("Aliases: Save failed: {}", e);
+        app.status(format!("Alias not saved: {}", e));
+        return;
+    }
+    if canonical.is_empty() {
+        logf!("Aliases: Removed {:?}", variant);
+        app.status(format!("Alias removed: {}", variant));
+    } else {
+        logf!("Aliases: {:?} → {:?}", variant, canonical);
+        app.status(format!("{} is now {}", variant, canonical));
+    }
+
+    let aliases = Aliases::load();
+    for kind in [PageKind::Players, PageKind::Injuries, PageKind::Transactions] {
+        let Some(raw) = app.raw_data.get_mut(&kind) else { continue };
+        if raw.update(|ds| aliases.apply(kind, ds)) == 0 { continue; }
+        match raw.save() {
+            Ok(_) => { app.saved_at.insert(kind, SystemTime::now()); }
+            Err(e) => loge!("Cache: Save failed {:?}: {}", kind, e),
+        }
+        app.row_ix_cache.retain(|(k, _), _| *k != kind);
+    }
+    super::scrape::refresh_derived(app, PageKind::Injuries);
+    app.rebuild_view();
+    app.alias_editor = Some(AliasEditor::build(app));
+}
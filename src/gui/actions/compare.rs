@@ -0,0 +1,86 @@
+// src/gui/actions/compare.rs
+//
+// Roster comparison of two teams (see `compare::rosters`), computed from
+// the cached Players data whenever the window draws. Copy and Export use
+// the export settings; the file goes next to the Players exports.
+
+use std::path::PathBuf;
+
+use eframe::egui;
+use crate::{
+    compare::{self, RosterComparison},
+    config::options::{ExportOptions, PageKind},
+    core::sanitize::sanitize_team_filename,
+    file,
+    gui::app::App,
+};
+
+/// State of the Compare teams window: the two teams picked.
+pub struct CompareTeams {
+    pub teams: [String; 2],
+}
+
+/// Open the window for the two selected teams, or the first two of the list.
+pub fn open(app: &mut App) {
+    let sel = &app.state.gui.selected_team_ids;
+    let picked: Vec<String> = if sel.len() == 2 {
+        sel.iter().filter_map(|id| app.teams.iter().find(|(t, _)| t == id)).map(|(_, n)| n.clone()).collect()
+    } else {
+        app.teams.iter().take(2).map(|(_, n)| n.clone()).collect()
+    };
+    let [a, b] = <[String; 2]>::try_from(picked).unwrap_or_default();
+    logf!("Compare: Open {:?} vs {:?}", a, b);
+    app.compare = Some(CompareTeams { teams: [a, b] });
+}
+
+/// The comparison of the window's teams; None without Players data.
+pub fn comparison(app: &App) -> Option<RosterComparison> {
+    let window = app.compare.as_ref()?;
+    let players = app.raw_data.get(&PageKind::Players)?.dataset();
+    compare::rosters(players, &window.teams[0], &window.teams[1])
+}
+
+/// The comparison in the export format (header renames of Players apply
+/// to files only).
+fn export_string(app: &App, cmp: &RosterComparison, for_file: bool) -> String {
+    let ds = cmp.to_dataset();
+    let mut options = app.state.options.clone();
+    options.scrape.page = PageKind::Players;
+    if !for_file { options.export.header_names.clear(); }
+    file::to_export_string(&options, &ds.headers, &ds.rows)
+}
+
+pub fn copy(app: &mut App, ctx: &egui::Context) {
+    let Some(cmp) = comparison(app) else {
+        app.status("Nothing to compare — scrape Players first");
+        return;
+    };
+    ctx.copy_text(export_string(app, &cmp, false));
+    app.status(format!("Copied {} vs {}", cmp.teams[0], cmp.teams[1]));
+}
+
+/// Write `compare_<a>_vs_<b>.<ext>` into the default Players folder.
+pub fn export(app: &mut App) -> Option<PathBuf> {
+    let Some(cmp) = comparison(app) else {
+        app.status("Nothing to compare — scrape Players first");
+        return None;
+    };
+    let dir = ExportOptions::default_dir_for(PageKind::Players);
+    let stem = format!("compare_{}_vs_{}", sanitize_team_filename(&cmp.teams[0], 0), sanitize_team_filename(&cmp.teams[1], 1));
+    let path = dir.join(format!("{}.{}", stem, app.state.options.export.format.ext()));
+    let contents = export_string(app, &cmp, true);
+    let res = file::ensure_directory(&dir)
+        .and_then(|_| file::write_atomic(&path, contents.as_bytes(), app.state.options.export.backup).map_err(Into::into));
+    match res {
+        Ok(()) => {
+            logf!("Export: Comparison → {}", path.display());
+            app.status(format!("Exported to {}", path.display()));
+            Some(path)
+        }
+        Err(e) => {
+            loge!("Export: Comparison failed: {}", e);
+            app.status(format!("Export error: {e}"));
+            None
+        }
+    }
+}
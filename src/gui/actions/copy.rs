@@ -1,10 +1,19 @@
 // src/gui/actions/copy.rs
 use eframe::egui;
-use crate::{gui::app::App, file};
+use crate::{gui::app::App, file, config::options::{ExportFormat, FormulaEscape}};
 
+/// Copy the current view in the export format (CSV/TSV).
 pub fn copy(app: &mut App, ui_ctx: &egui::Context) {
-    
-    if app.row_ix.is_empty() {
+    let format = app.state.options.export.format;
+    copy_as(app, ui_ctx, format);
+}
+
+/// Copy the current view as `format`: delimited text, or a Markdown /
+/// BBCode table for Discord and the forum.
+pub fn copy_as(app: &mut App, ui_ctx: &egui::Context, format: ExportFormat) {
+
+    let row_ix = super::export_row_ix(app);
+    if row_ix.is_empty() {
         app.status("Nothing to copy");
         logd!("Copy: Clicked, but there's nothing to copy");
         return;
@@ -20,23 +29,38 @@ pub fn copy(app: &mut App, ui_ctx: &egui::Context) {
         };
 
         // Clipboard path: small clone of just the selected rows.
-        let selected_rows: Vec<Vec<String>> = app
-            .row_ix
+        let selected_rows: Vec<Vec<String>> = row_ix
             .iter()
             .filter_map(|&ix| raw_ds.rows.get(ix).cloned())
             .collect();
 
-        let (h, r) = page.view_for_export(&app.state, &raw_ds.headers, &selected_rows);
+        let (h, r) = super::export_view(app, &raw_ds.headers, &selected_rows);
         logf!(
-            "Copy: page={:?}, rows={}, headers={}",
+            "Copy: page={:?}, format={}, rows={}, headers={}",
             page.kind(),
+            format,
             r.len(),
             h.as_ref().map(|x| x.len()).unwrap_or(0)
         );
 
-        file::to_export_string(&app.state.options, &h, &r)
+        let mut options = app.state.options.clone();
+        options.export.format = format;
+        // Header renames are for exported files; copies keep the table's headers
+        options.export.header_names.clear();
+        // Spreadsheets split pasted text on tabs or commas only
+        options.export.custom_delimiter = None;
+        // Pasted into a spreadsheet: formula-like cells as text, if wanted
+        options.export.formula_escape = match options.export.formula_escape.applies(true) {
+            true => FormulaEscape::Always,
+            false => FormulaEscape::Off,
+        };
+        file::to_export_string(&options, &h, &r)
     };
 
     ui_ctx.copy_text(txt);
-    app.status("Copied to clipboard");
+    app.status(match format {
+        ExportFormat::Markdown => "Copied to clipboard as Markdown",
+        ExportFormat::BbCode => "Copied to clipboard as BBCode",
+        _ => "Copied to clipboard",
+    });
 }
\ No newline at end of file
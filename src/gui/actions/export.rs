@@ -1,10 +1,19 @@
 // src/gui/actions/export.rs
-use crate::{gui::app::App, config::options::{ExportOptions, ExportType, PageKind},
+use crate::{gui::{app::App, router}, config::options::{ExportOptions, ExportType, PageKind},
+            config::consts::{DEFAULT_BUNDLE_SUBDIR, DEFAULT_OUT_DIR},
             data::{Selection, SelectionView}, core::sanitize::sanitize_team_filename,
             file::{self, ColumnProjection}};
-use std::{fs, path::PathBuf};
+use std::{fs, path::{Path, PathBuf}};
 use super::current_raw;
 
+/// Per-team files that could not be written by the last export,
+/// kept so the user can retry just those (e.g. after closing Excel).
+pub struct FailedExport {
+    pub kind: PageKind,
+    pub dir: PathBuf,
+    pub team_ids: Vec<u32>,
+}
+
 pub fn export(app: &mut App) {
     // normalize out_path first (mutates app) before any &app borrows
     if app.out_path_dirty {
@@ -17,13 +26,16 @@ pub fn export(app: &mut App) {
     }
 
     let kind   =  app.current_page_kind();
-    let page   =  app.current_page();
     let opts   = &app.state.options;
     let export = &opts.export;
 
+    // Files that failed in this run (per-team only); replaces any previous list.
+    let mut failed: Option<FailedExport> = None;
+
     let status_msg = match export.export_type {
         ExportType::SingleFile => {
-            if app.row_ix.is_empty() {
+            let row_ix = super::export_row_ix(app);
+            if row_ix.is_empty() {
                 logd!("Export: Clicked, but there's nothing to export");
                 "Nothing to export".to_string()
             } else if current_raw(app).is_none() {
@@ -31,16 +43,17 @@ pub fn export(app: &mut App) {
                 "Nothing to export (no cached data)".to_string()
             } else {
                 // do all work with &DataSet inside this block
-                let result: Result<PathBuf, Box<dyn std::error::Error>> = {
+                let result: crate::error::Result<PathBuf> = {
                     let raw_ds = current_raw(app).unwrap();
 
-                    let selected_rows: Vec<Vec<String>> = app
-                        .row_ix
+                    let selected_rows: Vec<Vec<String>> = row_ix
                         .iter()
                         .filter_map(|&ix| raw_ds.rows.get(ix).cloned())
                         .collect();
 
-                    let (headers, rows) = page.view_for_export(&app.state, &raw_ds.headers, &selected_rows);
+                    let (mut headers, mut rows) = super::export_view(app, &raw_ds.headers, &selected_rows);
+                    let divisions = file::divisions_for(export);
+                    file::add_division_columns(kind, &mut headers, &mut rows, &divisions);
 
                     logf!(
                         "Export: Begin page={:?}, rows={}, headers={}, type=SingleFile",
@@ -55,6 +68,7 @@ pub fn export(app: &mut App) {
                 match result {
                     Ok(path) => {
                         logf!("Export: OK count=1 last={}", path.display());
+                        write_manifest(kind, crate::store::selected_season().or(app.state.season), export, std::slice::from_ref(&path));
                         format!("Exported 1 file. Last: {}", path.display())
                     }
                     Err(e) => {
@@ -70,98 +84,279 @@ pub fn export(app: &mut App) {
                 logd!("Export: PerTeam but no cached dataset");
                 s!("Nothing to export (no cached data)")
             } else {
-                // keep all borrows immutable inside this block
-                let (written, last): (usize, Option<PathBuf>) = {
-                    let raw_ds = current_raw(app).unwrap();
-
-                    // target teams: if ALL selected → all; else the subset
-                    let selected_ids = &app.state.gui.selected_team_ids;
-                    let all_ids: Vec<u32> = app.teams.iter().map(|(id, _)| *id).collect();
-                    let ids_to_export: &[u32] = if selected_ids.len() == app.teams.len() {
-                        &all_ids
-                    } else {
-                        selected_ids
-                    };
-
-                    // ensure target dir
-                    let dir = export.out_path();
-                    if let Err(e) = fs::create_dir_all(&dir) {
-                        loge!("Export: create_dir_all failed: {}", e);
-                        return app.status(&format!("Export error: {e}")); // early status + return
-                    }
-
-                    // column projection matches table toggle
-                    let proj = if matches!(kind, PageKind::GameResults)
-                        && !app.state.gui.game_results_show_match_id
-                    {
-                        ColumnProjection::DropLast
-                    } else {
-                        ColumnProjection::KeepAll
-                    };
-
-                    let mut written = 0usize;
-                    let mut last_path: Option<PathBuf> = None;
-
-                    for &team_id in ids_to_export {
-                        let team_name = match app.teams.iter().find(|(id, _)| *id == team_id) {
-                            Some((_, name)) => name.as_str(),
-                            None => continue,
-                        };
-
-                        // one-team selection view
-                        let one = [team_id];
-                        let sel  = Selection { ids: &one, teams: &app.teams };
-                        let view = SelectionView::from_raw(page, &app.raw_data[&kind], sel);
-
-                        if view.row_ix.is_empty() {
-                            continue;
-                        }
-
-                        // file path
-                        let stem = sanitize_team_filename(team_name, team_id);
-                        let ext  = export.format.ext();
-                        let file_name = if ext.is_empty() { stem.clone() } else { format!("{stem}.{ext}") };
-                        let path = ExportOptions::join_dir_and_filename(&dir, &file_name);
-
-                        // stream selection → file (no row cloning)
-                        match file::stream_write_table_to_path(
-                            &path,
-                            &raw_ds.headers,
-                            &raw_ds.rows,
-                            &view.row_ix,
-                            export.delimiter(),
-                            proj,
-                        ) {
-                            Ok(_) => {
-                                written += 1;
-                                last_path = Some(path.clone());
-                                logd!("Export: per-team OK → {}", path.display());
-                            }
-                            Err(e) => {
-                                loge!("Export: per-team write failed {}: {}", path.display(), e);
-                            }
-                        }
-                    }
-
-                    (written, last_path)
+                // target teams: if ALL selected → all; else the subset
+                let selected_ids = &app.state.gui.selected_team_ids;
+                let ids_to_export: Vec<u32> = if selected_ids.len() == app.teams.len() {
+                    app.teams.iter().map(|(id, _)| *id).collect()
+                } else {
+                    selected_ids.clone()
                 };
+                let dir = export.out_path();
 
-                if written > 0 {
-                    if let Some(p) = last {
-                        logf!("Export: OK count={} last={}", written, p.display());
-                        format!("Exported {} file(s). Last: {}", written, p.display())
-                    } else {
-                        logf!("Export: OK count={}", written);
-                        format!("Exported {} file(s).", written)
+                match write_per_team(app, kind, &dir, &ids_to_export) {
+                    Ok((written, failed_ids)) => {
+                        let msg = per_team_status(&written, failed_ids.len());
+                        if !failed_ids.is_empty() {
+                            failed = Some(FailedExport { kind, dir, team_ids: failed_ids });
+                            msg
+                        } else {
+                            write_manifest(kind, crate::store::selected_season().or(app.state.season), export, &written);
+                            zip_status(export, &dir, written).unwrap_or(msg)
+                        }
                     }
-                } else {
-                    logd!("Export: PerTeam produced no files (no rows for chosen teams)");
-                    "Nothing to export".to_string()
+                    Err(msg) => msg,
                 }
             }
         }
     };
 
     // mutate app only after the dataset borrows are gone
+    app.failed_export = failed;
     app.status(status_msg);
 }
+
+/// Re-run the per-team export for just the files that failed last time.
+/// Uses the page and directory of the original export, not the current tab.
+pub fn retry_failed(app: &mut App) {
+    let Some(prev) = app.failed_export.take() else {
+        app.status("Nothing to retry");
+        return;
+    };
+    logf!("Export: Retry page={:?} teams={:?}", prev.kind, prev.team_ids);
+
+    let status_msg = match write_per_team(app, prev.kind, &prev.dir, &prev.team_ids) {
+        Ok((written, failed_ids)) => {
+            let msg = per_team_status(&written, failed_ids.len());
+            if !failed_ids.is_empty() {
+                app.failed_export = Some(FailedExport { team_ids: failed_ids, ..prev });
+            }
+            msg
+        }
+        Err(msg) => {
+            app.failed_export = Some(prev);
+            msg
+        }
+    };
+    app.status(status_msg);
+}
+
+/// Export bundle: every cached page into `out/bundle/`, one folder per page,
+/// with the current export options (format, headers, per-team, manifest).
+pub fn export_bundle(app: &mut App) {
+    let datasets: Vec<(PageKind, crate::store::DataSet)> = file::BUNDLE_PAGES.iter()
+        .filter_map(|k| app.raw_data.get(k).map(|r| (*k, r.dataset().clone())))
+        .collect();
+    if datasets.is_empty() {
+        app.status("Nothing to export (no cached data)");
+        return;
+    }
+    let dir = Path::new(DEFAULT_OUT_DIR).join(DEFAULT_BUNDLE_SUBDIR);
+    logf!("Export: Bundle begin pages={} → {}", datasets.len(), dir.display());
+
+    let msg = match file::export_bundle(&app.state.options, &dir, datasets) {
+        Ok(written) => {
+            logf!("Export: Bundle OK count={}", written.len());
+            match written.as_slice() {
+                [zip] if app.state.options.export.zip => format!("Exported bundle: {}", zip.display()),
+                _ => format!("Exported bundle: {} file(s) in {}", written.len(), dir.display()),
+            }
+        }
+        Err(e) => {
+            loge!("Export: Bundle error: {}", e);
+            format!("Export error: {e}")
+        }
+    };
+    app.status(msg);
+}
+
+/// Print view (HTML) of the chosen week of the Game Results cache, next to
+/// the other results exports. Returns the file written, for opening.
+pub fn print_week(app: &mut App) -> Option<PathBuf> {
+    let week = app.state.gui.print_week;
+    let Some(raw) = app.raw_data.get(&PageKind::GameResults) else {
+        app.status("No game results yet — scrape first");
+        return None;
+    };
+    let sched = crate::schedule::WeekSchedule::from_results(raw.dataset(), None, week);
+    if sched.fixtures.is_empty() {
+        app.status(format!("No games in week {}", week));
+        return None;
+    }
+    let path = ExportOptions::default_dir_for(PageKind::GameResults).join(format!("week_{}.html", week));
+    let res = fs::create_dir_all(ExportOptions::default_dir_for(PageKind::GameResults))
+        .and_then(|_| file::write_atomic(&path, sched.to_html().as_bytes(), app.state.options.export.backup));
+    match res {
+        Ok(()) => {
+            logf!("Export: Print view → {}", path.display());
+            app.status(format!("Print view: {}", path.display()));
+            Some(path)
+        }
+        Err(e) => {
+            loge!("Export: Print view failed: {}", e);
+            app.status(format!("Export error: {e}"));
+            None
+        }
+    }
+}
+
+/// Write one file per team into `dir` (or its division subdirectory),
+/// continuing past write failures.
+/// Returns (paths written, team ids whose file failed).
+fn write_per_team(
+    app: &App,
+    kind: PageKind,
+    dir: &Path,
+    ids: &[u32],
+) -> Result<(Vec<PathBuf>, Vec<u32>), String> {
+    let Some(raw) = app.raw_data.get(&kind) else {
+        return Err(s!("Nothing to export (no cached data)"));
+    };
+    let raw_ds = raw.dataset();
+    let page = router::page_for(&kind);
+    let export = &app.state.options.export;
+
+    // ensure target dir
+    if let Err(e) = fs::create_dir_all(dir) {
+        loge!("Export: create_dir_all failed: {}", e);
+        return Err(format!("Export error: {e}"));
+    }
+
+    // column projection matches table toggle (match id / player id are trailing columns)
+    let hidden_last = raw_ds.headers.as_ref()
+        .map(|h| h.len().saturating_sub(1))
+        .is_some_and(|last| page.hidden_columns(&app.state, raw_ds.headers.as_deref()) == [last]);
+    let proj = if export.as_shown {
+        ColumnProjection::Columns(app.shown_export_columns(kind))
+    } else if !app.user_hidden_columns(kind, raw_ds.headers.as_deref()).is_empty() {
+        ColumnProjection::Columns(app.export_columns(kind))
+    } else if (matches!(kind, PageKind::GameResults)
+        && !app.state.gui.game_results_show_match_id)
+        || hidden_last
+    {
+        ColumnProjection::DropLast
+    } else {
+        ColumnProjection::KeepAll
+    };
+
+    let divisions = file::divisions_for(export);
+    let out_headers = file::export_headers(export, kind, &raw_ds.headers);
+    let mut written: Vec<PathBuf> = Vec::new();
+    let mut failed_ids: Vec<u32> = Vec::new();
+
+    for &team_id in ids {
+        let team_name = match app.teams.iter().find(|(id, _)| *id == team_id) {
+            Some((_, name)) => name.as_str(),
+            None => continue,
+        };
+
+        // one-team selection view
+        let one = [team_id];
+        let sel  = Selection { ids: &one, teams: &app.teams };
+        let mut view = SelectionView::from_raw(page, raw, sel);
+        // same filter/sort as the table
+        if let Some(keep) = page.row_filter(&app.state) {
+            view.row_ix.retain(|&i| raw_ds.rows.get(i).is_some_and(|r| keep(r)));
+        }
+        if let Some(f) = app.text_filter.get(&kind) {
+            f.retain(&raw_ds.rows, &mut view.row_ix);
+        }
+        if let Some(sort) = app.col_sort.get(&kind) {
+            let numeric = !page.non_numeric_columns().contains(&sort.col);
+            sort.sort_row_ix_typed(&raw_ds.rows, raw.typed(), &mut view.row_ix, numeric);
+        }
+        // Export order options (sort column, by week) over the table's
+        file::sort_team_rows(export, raw_ds.headers.as_deref(), &raw_ds.rows, &mut view.row_ix);
+        if let Some(picked) = super::selected_only(app) {
+            view.row_ix.retain(|&i| picked.contains(i));
+        }
+
+        if view.row_ix.is_empty() {
+            continue;
+        }
+
+        // file path
+        let stem = sanitize_team_filename(team_name, team_id);
+        let ext  = export.format.ext();
+        let file_name = if ext.is_empty() { stem.clone() } else { format!("{stem}.{ext}") };
+        let team_dir = file::division_dir(dir, team_name, &divisions);
+        if let Err(e) = fs::create_dir_all(&team_dir) {
+            loge!("Export: create_dir_all failed {}: {}", team_dir.display(), e);
+            failed_ids.push(team_id);
+            continue;
+        }
+        let path = ExportOptions::join_dir_and_filename(&team_dir, &file_name);
+
+        let res = if export.format.is_markup() || !export.number_format.is_plain() || export.formula_escape.applies(false) {
+            // Markdown / BBCode need whole columns (widths), numbers in
+            // other separators their column types, and formula escaping
+            // rewrites cells, so render in memory
+            let rows: Vec<Vec<String>> = view.row_ix.iter()
+                .filter_map(|&ix| raw_ds.rows.get(ix).cloned())
+                .collect();
+            let (h, r) = proj.apply(&raw_ds.headers, &rows);
+            file::write_atomic(&path, file::to_export_string(&app.state.options, &h, &r).as_bytes(), export.backup)
+                .map(|_| path.clone())
+        } else {
+            // stream selection → file (no row cloning)
+            file::stream_write_table_to_path(
+                &path,
+                &out_headers,
+                &raw_ds.rows,
+                &view.row_ix,
+                export.delimiter(),
+                proj.clone(),
+                export.backup,
+            )
+        };
+        match res {
+            Ok(_) => {
+                logd!("Export: per-team OK → {}", path.display());
+                written.push(path);
+            }
+            Err(e) => {
+                loge!("Export: per-team write failed {}: {}", path.display(), e);
+                failed_ids.push(team_id);
+            }
+        }
+    }
+
+    Ok((written, failed_ids))
+}
+
+fn per_team_status(written: &[PathBuf], failed: usize) -> String {
+    let count = written.len();
+    if failed > 0 {
+        loge!("Export: {} per-team file(s) failed", failed);
+        return format!("Exported {} file(s); {} failed — use Retry failed", count, failed);
+    }
+    if let Some(p) = written.last() {
+        logf!("Export: OK count={} last={}", count, p.display());
+        format!("Exported {} file(s). Last: {}", count, p.display())
+    } else {
+        logd!("Export: PerTeam produced no files (no rows for chosen teams)");
+        "Nothing to export".to_string()
+    }
+}
+
+/// With zip on, pack a complete per-team export into `<dir>.zip`.
+/// The status line for it, or `None` when zip is off or nothing was written.
+fn zip_status(export: &ExportOptions, dir: &Path, written: Vec<PathBuf>) -> Option<String> {
+    if !export.zip || written.is_empty() {
+        return None;
+    }
+    let count = written.len();
+    Some(match file::zip_export(export, dir, written) {
+        Ok(zip) => format!("Exported {} file(s) into {}", count, zip.first()?.display()),
+        Err(e) => {
+            loge!("Export: Zip failed: {}", e);
+            format!("Export error: {e}")
+        }
+    })
+}
+
+/// Best-effort manifest after a complete export; a failure here doesn't fail the export.
+fn write_manifest(kind: PageKind, season: Option<u32>, export: &ExportOptions, written: &[PathBuf]) {
+    if let Err(e) = crate::manifest::write_for_export(kind, season, export, written) {
+        loge!("Export: Manifest failed: {}", e);
+    }
+}
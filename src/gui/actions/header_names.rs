@@ -0,0 +1,47 @@
+// src/gui/actions/header_names.rs
+//
+// Export header names: per-page renames of the site's abbreviations (S, W,
+// DUR, …) for exported files. Saved in `.store/settings`; the cache, the
+// table and copies keep the canonical headers.
+
+use crate::{config::options::PageKind, gui::app::App, store};
+
+/// State of the Export header names window.
+pub struct HeaderNamesEditor {
+    pub kind: PageKind,
+    /// (canonical header, name to export it as; empty = unchanged)
+    pub rows: Vec<(String, String)>,
+}
+
+/// Open the window for the current page's headers.
+pub fn open(app: &mut App) {
+    let kind = app.current_page_kind();
+    let names = app.state.options.export.header_names.get(&kind);
+    let headers = app.headers.clone()
+        .or_else(|| crate::schema::for_page(kind).map(|p| p.names().into_iter().map(String::from).collect()))
+        .unwrap_or_default();
+    let rows = headers.into_iter()
+        .map(|h| {
+            let name = names.and_then(|n| n.get(&h)).cloned().unwrap_or_default();
+            (h, name)
+        })
+        .collect();
+    app.header_names = Some(HeaderNamesEditor { kind, rows });
+}
+
+/// Save the window's names and use them for the next exports.
+pub fn save(app: &mut App) {
+    let Some(editor) = app.header_names.as_ref() else { return };
+    let kind = editor.kind;
+    for (header, name) in &editor.rows {
+        if let Err(e) = store::save_header_name(&kind, header, name) {
+            loge!("Settings: Header name {:?} not saved: {}", header, e);
+            app.status(format!("Header names not saved: {}", e));
+            return;
+        }
+    }
+    app.state.options.export.header_names = store::header_names();
+    let n = app.state.options.export.header_names.get(&kind).map_or(0, |n| n.len());
+    logf!("Export: {} header name(s) for {:?}", n, kind);
+    app.status(format!("Exports of {} rename {} header(s)", kind, n));
+}
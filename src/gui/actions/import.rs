@@ -0,0 +1,100 @@
+// src/gui/actions/import.rs
+//
+// Import from clipboard/file: check pasted CSV/TSV against the page, show
+// what it would change, and on confirmation merge it into the cache.
+
+use std::{fs, time::SystemTime};
+
+use crate::{
+    config::options::PageKind,
+    data::RawData,
+    diff::DataDiff,
+    gui::{app::App, pages::row_key_between, router},
+    import,
+    store::{self, DataSet},
+};
+
+/// State of the import window (one page at a time).
+pub struct ImportDialog {
+    pub kind: PageKind,
+    /// Pasted or loaded CSV/TSV
+    pub text: String,
+    pub path: String,
+    /// Result of the last Check: the merged dataset and what it changes
+    pub preview: Option<Result<(DataSet, DataDiff), String>>,
+}
+
+/// Open the import window for the current page.
+pub fn open(app: &mut App) {
+    let kind = app.current_page_kind();
+    logd!("Import: Open for {:?}", kind);
+    app.import = Some(ImportDialog { kind, text: String::new(), path: String::new(), preview: None });
+}
+
+/// Read the file named in the window into its text box.
+pub fn load_file(dialog: &mut ImportDialog) {
+    let path = dialog.path.trim().trim_matches('"');
+    match fs::read_to_string(path) {
+        Ok(text) => {
+            logf!("Import: Loaded {} ({} bytes)", path, text.len());
+            dialog.text = text;
+            dialog.preview = None;
+        }
+        Err(e) => dialog.preview = Some(Err(format!("Can't read {}: {}", path, e))),
+    }
+}
+
+/// Parse and validate the text, and merge it into a copy of the cache.
+pub fn check(app: &mut App) {
+    let Some(dialog) = app.import.as_ref() else { return };
+    let kind = dialog.kind;
+    // As cached, without the user columns
+    let cached = app.raw_data.get(&kind).map(|r| r.base_dataset());
+
+    let preview = import::parse(kind, &dialog.text, cached.as_deref()).map(|imported| {
+        let empty = DataSet { headers: None, rows: Vec::new() };
+        let cached = cached.as_deref().unwrap_or(&empty);
+        let key = row_key_between(router::page_for(&kind), cached, &imported);
+        let mut merged = import::merge(cached, imported, &key);
+        let injuries = app.raw_data.get(&PageKind::Injuries).map(|r| r.base_dataset());
+        crate::derive::apply(kind, &mut merged, injuries.as_deref());
+        let diff = DataDiff::between(cached, &merged, &key);
+        (merged, diff)
+    });
+    match &preview {
+        Ok((_, diff)) => logd!("Import: Check {:?}: {}", kind, diff.summary()),
+        Err(e) => logd!("Import: Check {:?} failed: {}", kind, e),
+    }
+    if let Some(dialog) = app.import.as_mut() {
+        dialog.preview = Some(preview);
+    }
+}
+
+/// Confirmed: replace the cache with the checked merge and close the window.
+/// The data from before is kept for Undo last scrape.
+pub fn apply(app: &mut App) {
+    let Some(ImportDialog { kind, preview: Some(Ok((merged, diff))), .. }) = app.import.take() else { return };
+
+    if let Some(old) = app.raw_data.get(&kind).map(|r| r.base_dataset()).filter(|ds| !ds.rows.is_empty()) {
+        match store::save_previous(&kind, &old) {
+            Ok(_) => { app.undoable.insert(kind); }
+            Err(e) => loge!("Cache: Keeping previous {:?} failed: {}", kind, e),
+        }
+    }
+    match store::save_dataset(&kind, &merged) {
+        Ok(p) => {
+            logf!("Import: Merged into {:?} ({}) → {}", kind, diff.summary(), p.display());
+            app.saved_at.insert(kind, SystemTime::now());
+        }
+        Err(e) => loge!("Cache: Save failed {:?}: {}", kind, e),
+    }
+
+    app.status(format!("Imported into {}: {}", kind, diff.summary()));
+    app.raw_data.insert(kind, RawData::new(kind, merged));
+    app.prev_data.remove(&kind);
+    app.last_diff = Some((kind, diff));
+    super::scrape::refresh_derived(app, kind);
+    app.row_ix_cache.retain(|(k, _), _| *k != kind);
+    app.state.gui.row_selection.remove(&kind);
+    app.rebuild_view();
+}
@@ -3,18 +3,60 @@
 // Folder module facade: re-export public entrypoints.
 // Submodules stay private; consumers only see actions::{copy,export,scrape}.
 
+pub mod aliases; // src/gui/actions/aliases.rs
+pub mod compare; // src/gui/actions/compare.rs
 pub mod copy;    // src/gui/actions/copy.rs
 pub mod export;  // src/gui/actions/export.rs
+pub mod header_names; // src/gui/actions/header_names.rs
+pub mod import;  // src/gui/actions/import.rs
 pub mod scrape;  // src/gui/actions/scrape.rs
+pub mod season;  // src/gui/actions/season.rs
+pub mod settings; // src/gui/actions/settings.rs
+pub mod trend;   // src/gui/actions/trend.rs
+pub mod user_columns; // src/gui/actions/user_columns.rs
 
 pub use copy::copy;
 pub use export::export;
 pub use scrape::scrape;
 
-use crate::{gui::app::App, store::DataSet};
+use crate::{data::RowSelection, file::ColumnProjection, gui::app::App, store::DataSet};
 
 #[inline]
 pub(super) fn current_raw(app: &App) -> Option<&DataSet> {
     let kind = app.current_page_kind();
     app.raw_data.get(&kind).map(|rd| rd.dataset())
-}
\ No newline at end of file
+}
+
+/// The current page's row selection when Copy/Export is set to
+/// "Selected rows only" and something is selected.
+pub(super) fn selected_only(app: &App) -> Option<&RowSelection> {
+    let sel = app.state.gui.row_selection.get(&app.current_page_kind())?;
+    (app.state.gui.selected_rows_only && !sel.is_empty()).then_some(sel)
+}
+
+/// Rows Copy/Export work on: the table view, or just its selected rows
+/// (still in view order, so the team filter and sort apply).
+pub(super) fn export_row_ix(app: &App) -> Vec<usize> {
+    match selected_only(app) {
+        Some(sel) => sel.in_view(&app.row_ix),
+        None => app.row_ix.to_vec(),
+    }
+}
+
+/// Copy/Export view of the selected `rows`: the page's export view, or with
+/// "As shown" the table's column order without hidden/excluded columns.
+/// Columns hidden via the Columns menu are left out either way.
+pub(super) fn export_view(
+    app: &App,
+    headers: &Option<Vec<String>>,
+    rows: &Vec<Vec<String>>,
+) -> (Option<Vec<String>>, Vec<Vec<String>>) {
+    let kind = app.current_page_kind();
+    if app.state.options.export.as_shown {
+        ColumnProjection::Columns(app.shown_export_columns(kind)).apply(headers, rows)
+    } else if !app.user_hidden_columns(kind, headers.as_deref()).is_empty() {
+        ColumnProjection::Columns(app.export_columns(kind)).apply(headers, rows)
+    } else {
+        app.current_page().view_for_export(&app.state, headers, rows)
+    }
+}
@@ -1,33 +1,120 @@
 // src/gui/actions/scrape.rs
-use std::thread::{self};
+use std::{
+    sync::{atomic::{AtomicBool, Ordering}, Arc, Mutex},
+    thread::{self},
+    time::{Duration, Instant, SystemTime},
+};
 
 use crate::{
-    config::options::PageKind::{self},
+    config::options::{PageKind, TeamSelector},
     data,
-    gui::{self, app::App, progress::GuiProgress, pages::Page},
+    diff::DataDiff,
+    get_teams,
+    gui::{self, app::App, progress::{Counts, GuiProgress}, pages::Page},
     progress::Progress,
     store,
 };
 
 pub enum ScrapeOutcome {
-    Ok { kind: PageKind, ds: store::DataSet },
+    /// `failed`: team ids whose fetch failed (partial scrape), for Retry failed;
+    /// `done`: the ones fetched (both empty for pages not scraped team by team)
+    Ok { kind: PageKind, ds: store::DataSet, failed: Vec<u32>, done: Vec<u32> },
     Err { kind: PageKind, msg: String },
+    Cancelled { kind: PageKind },
+}
+
+/// One page's scrape on its own worker thread. Pages scrape side by side,
+/// each with its own status line, progress counts and Cancel.
+pub struct ScrapeJob {
+    handle: thread::JoinHandle<ScrapeOutcome>,
+    pub status: Arc<Mutex<String>>,
+    pub counts: Arc<Mutex<Counts>>,
+    cancel: Arc<AtomicBool>,
+    /// Started by refresh-on-focus
+    pub auto: bool,
 }
 
-/// Ensure headers exist in a freshly scraped dataset by using the page's defaults when missing.
+impl ScrapeJob {
+    pub fn cancelled(&self) -> bool { self.cancel.load(Ordering::Relaxed) }
+}
+
+/// Ensure headers exist in a freshly scraped dataset by using the page schema when missing.
 pub fn inject_headers_if_missing(page: &dyn Page, ds: &mut store::DataSet) {
-    if ds.headers.is_none() {
-        if let Some(hs) = page.default_headers() {
-            ds.headers = Some(hs.iter().map(|s| s.to_string()).collect());
-        }
-    }
+    crate::schema::inject_headers(page.kind(), ds);
 }
 
 // Call this from the button click
 pub fn scrape(app: &mut App) {
+    // Align scrape options
+    app.sync_gui_selection_into_scrape();
+    start(app);
+}
+
+/// Re-scrape only the teams that failed in the last scrape of this page;
+/// the results merge into the existing data like any partial scrape.
+pub fn retry_failed(app: &mut App) {
+    let kind = app.current_page_kind();
+    let Some((_, ids)) = app.failed_scrape.as_ref().filter(|(k, _)| *k == kind) else {
+        app.status("Nothing to retry");
+        return;
+    };
+    logf!("Scrape: Retry failed teams {:?}", ids);
+    app.state.options.scrape.teams = TeamSelector::Ids(ids.clone());
+    start(app);
+}
+
+/// Undo the last scrape of this page: the cache goes back to the copy kept
+/// before it (see `store::restore_previous`). Undoing again redoes the scrape.
+pub fn undo_last(app: &mut App) {
+    let page = app.current_page();
+    let kind = page.kind();
+    let mut ds = match store::restore_previous(&kind) {
+        Ok(ds) => ds,
+        Err(e) => {
+            loge!("Cache: Restore previous {:?} failed: {}", kind, e);
+            app.status(format!("Nothing to undo ({})", e));
+            return;
+        }
+    };
+    inject_headers_if_missing(page, &mut ds);
+    logf!("Cache: Restored previous {:?} (rows={})", kind, ds.row_count());
+    app.status(format!("Restored the data from before the last scrape ({} rows)", ds.row_count()));
+
+    if !store::has_previous(&kind) { app.undoable.remove(&kind); }
+    // The delta base and change report were for the scrape just undone
+    app.prev_data.remove(&kind);
+    if app.last_diff.as_ref().is_some_and(|(k, _)| *k == kind) { app.last_diff = None; }
+
+    app.raw_data.insert(kind, data::RawData::new(kind, ds));
+    app.saved_at.insert(kind, SystemTime::now());
+    refresh_derived(app, kind);
+    app.row_ix_cache.retain(|(k, _), _| *k != kind);
+    app.state.gui.row_selection.remove(&kind);
+    app.rebuild_view();
+}
+
+/// Stop the running scrape of `kind`: no more teams are fetched and what
+/// was fetched is discarded, leaving the page's data as it was.
+pub fn cancel(app: &mut App, kind: PageKind) {
+    let Some(job) = app.scrapes.get(&kind) else { return };
+    logf!("Scrape: Cancel {:?}", kind);
+    job.cancel.store(true, Ordering::Relaxed);
+    *job.status.lock().unwrap() = s!("Cancelling…");
+}
+
+/// Run the scrape for the current page with the scrape options as they are,
+/// alongside any other pages' scrapes.
+fn start(app: &mut App) {
     let page   = app.current_page();
     let kind   = page.kind();
-    let status = app.status.clone();
+    if app.scrapes.contains_key(&kind) { return; }
+    if let Some(season) = store::selected_season() {
+        app.status(format!("Season {} is archived; switch to the current season to scrape", season));
+        return;
+    }
+    let status = Arc::new(Mutex::new(s!("Waiting for server response…")));
+    let counts = Arc::new(Mutex::new(Counts::default()));
+    let cancel = Arc::new(AtomicBool::new(false));
 
     // Paranoia
     debug_assert_eq!(
@@ -35,25 +122,26 @@ pub fn scrape(app: &mut App) {
         "scrape.page drifted from current tab"
     );
 
-    // Align scrape options
-    app.sync_gui_selection_into_scrape();
-
     // Snapshot just what we need (avoid borrowing App across threads)
     let state = app.state.clone();                  // If AppState: Clone
     let teams = app.teams.clone();                  // If needed by validation
 
-    app.running = true;                    // ← enable spinner
-    app.status("Waiting for server response…");
+    app.last_counts.remove(&kind);
     logf!("Scrape: Begin page={:?} teams={:?}", kind, app.state.options.scrape.teams);
 
+    let (job_status, job_counts, job_cancel) = (status.clone(), counts.clone(), cancel.clone());
     let handle = thread::spawn(move || {
         let page = gui::router::page_for(&kind);
-        // Progress into the same status line
-        let mut gp = GuiProgress::new(status);
+        // Progress into this job's status line
+        let mut gp = GuiProgress::new(job_status, job_counts, job_cancel);
         // let prog: Option<&mut dyn Progress> = Some(&mut gp);
 
         // 1) → This is where the scrape happens ←
-        let mut ds = match page.scrape(&state, Some(&mut gp)) {
+        let scraped = page.scrape(&state, Some(&mut gp));
+        if gp.cancelled() {
+            return ScrapeOutcome::Cancelled { kind };
+        }
+        let mut ds = match scraped {
             Ok(ds) => ds,
             Err(e) => return ScrapeOutcome::Err { kind, msg: e.to_string() },
         };
@@ -70,18 +158,20 @@ pub fn scrape(app: &mut App) {
             };
         
 
-        // Page-level validation (uses teams if your impl needs it)
-        if let Err(msg) = page.validate_scrape(&state, &teams, &ds) {
-            return ScrapeOutcome::Err { kind, msg: format!("Validation failed: {msg}") };
+        // Page-level validation (uses teams if your impl needs it). A team
+        // the list doesn't know means the list is stale: refresh it, and
+        // validate once more against the fresh one.
+        let mut valid = page.validate_scrape(&state, &teams, &ds);
+        if let Some(msg) = valid.as_ref().err().filter(|m| get_teams::is_unknown_team(m)).cloned() {
+            logf!("Scrape: {}; refreshing the team list", msg);
+            gp.log("Team list out of date, refreshing teams…");
+            valid = match get_teams::refresh() {
+                Ok(fresh) => page.validate_scrape(&state, &fresh, &ds),
+                Err(e) => Err(format!("{msg} (refreshing teams failed: {e})")),
+            };
         }
-
-        // If this page yields the season (e.g., Game Results), persist it for other pages.
-        if let PageKind::GameResults = kind {
-            if let Some(first) = ds.rows.get(0).and_then(|r| r.get(0)) {
-                if let Ok(season) = first.trim().parse::<u32>() {
-                    let _ = store::save_season(season);
-                }
-            }
+        if let Err(msg) = valid {
+            return ScrapeOutcome::Err { kind, msg: format!("Validation failed: {msg}") };
         }
 
         let page_text = match kind {
@@ -91,23 +181,72 @@ pub fn scrape(app: &mut App) {
             PageKind::SeasonStats   => "season stats",
             PageKind::CareerStats   => "career stats",
             PageKind::Injuries      => "injury events",
+            PageKind::Player        => "player fields",
+            PageKind::Standings     => "teams in the standings",
+            PageKind::Transactions  => "roster moves",
+            PageKind::TeamSummary   => "teams summarized",
+            PageKind::Leaders       => "leaders ranked",
+            PageKind::ResultsTable  => "teams tallied",
+            PageKind::ScheduleMetrics => "schedules measured",
+            PageKind::TeamInfo      => "teams",
+            PageKind::InjuryVictims => "injuries matched",
+            PageKind::InjurySummary => "injury summary rows",
         };
 
-        gp.log(&format!("Found {} {}", ds.row_count(), page_text));
+        gp.log(&format!("Found {} {}{}", ds.row_count(), page_text, gp.failure_note()));
 
-        ScrapeOutcome::Ok { kind, ds }
+        ScrapeOutcome::Ok { kind, ds, failed: gp.failed_ids(), done: gp.done_ids() }
 
 
     });
 
-    app.scrape_handle = Some(handle);
+    app.scrapes.insert(kind, ScrapeJob { handle, status, counts, cancel, auto: false });
+}
+
+/// The running scrapes' counts together (window title, taskbar); None
+/// while none runs. The total stays 0 until every scrape knows its own.
+pub fn running_counts(app: &App) -> Option<Counts> {
+    if app.scrapes.is_empty() { return None; }
+    let mut sum = Counts::default();
+    let mut known = true;
+    for job in app.scrapes.values() {
+        let c = *job.counts.lock().unwrap();
+        sum.done += c.done;
+        sum.failed += c.failed;
+        sum.total += c.total;
+        known &= c.total > 0;
+    }
+    if !known { sum.total = 0; }
+    Some(sum)
+}
+
+/// Window regained focus: re-scrape the current page if enabled and its cache
+/// is older than the configured age. At most one automatic refresh per that
+/// interval, whatever the outcome, so we never hammer the server.
+pub fn refresh_on_focus(app: &mut App) {
+    let gui = &app.state.gui;
+    if !gui.refresh_on_focus || app.running() || store::selected_season().is_some() { return; }
+
+    let max_age = Duration::from_secs(u64::from(gui.refresh_after_min.max(1)) * 60);
+    let kind = app.current_page_kind();
+
+    // Only refresh what has been scraped before
+    let Some(saved) = app.saved_at.get(&kind) else { return };
+    let age = SystemTime::now().duration_since(*saved).unwrap_or_default();
+    if age < max_age { return; }
+    if app.last_auto_refresh.is_some_and(|t| t.elapsed() < max_age) { return; }
+
+    logf!("Scrape: Auto-refresh {:?} on focus (cache age {}m)", kind, age.as_secs() / 60);
+    app.last_auto_refresh = Some(Instant::now());
+    scrape(app);
+    if let Some(job) = app.scrapes.get_mut(&kind) { job.auto = true; }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{config::state::AppState, store::DataSet};
-    use std::error::Error;
+    use crate::error::Error;
 
     struct DummyPage;
     impl Page for DummyPage {
@@ -115,7 +254,7 @@ mod tests {
         fn kind(&self) -> PageKind { PageKind::GameResults }
         fn default_headers(&self) -> Option<&'static [&'static str]> {
             Some(&["A","B","C"]) }
-        fn scrape(&self, _s: &AppState, _p: Option<&mut dyn Progress>) -> Result<DataSet, Box<dyn Error>> {
+        fn scrape(&self, _s: &AppState, _p: Option<&mut dyn Progress>) -> Result<DataSet, Error> {
             Ok(DataSet { headers: None, rows: vec![vec!["1".into(),"2".into(),"3".into()]] })
         }
     }
@@ -132,44 +271,262 @@ mod tests {
 
 // Call this once per frame (early in your update)
 pub fn poll(app: &mut App) {
-    let Some(handle) = app.scrape_handle.as_ref() else { return; };
-
-    if !handle.is_finished() {
-        // still working; keep the spinner alive
-        return;
+    // Finished jobs; the rest are still working (keep their spinners alive).
+    // Held while the new-season prompt is open, so nothing of the new
+    // season is saved before it's answered.
+    if app.new_season.is_some() { return; }
+    let done: Vec<PageKind> = app.scrapes.iter()
+        .filter(|(_, job)| job.handle.is_finished())
+        .map(|(kind, _)| *kind)
+        .collect();
+    for kind in done {
+        let Some(job) = app.scrapes.remove(&kind) else { continue };
+        finish(app, kind, job);
     }
+}
 
-    // finished: join and consume the handle
-    let outcome = app.scrape_handle.take().unwrap().join();
-    app.running = false;
+/// Take in a finished job: merge its rows into its own page's data (whichever
+/// tab is showing) and move its last status line to the app's.
+fn finish(app: &mut App, kind: PageKind, job: ScrapeJob) {
+    let cancelled = job.cancelled();
+    let outcome = job.handle.join();
+    app.last_counts.insert(kind, *job.counts.lock().unwrap());
+
+    // Other pages' results are labelled with their tab
+    let title = gui::router::page_for(&kind).title();
+    let showing = kind == app.current_page_kind();
+    let labelled = |msg: String| if showing { msg } else { format!("{}: {}", title, msg) };
+    let last = job.status.lock().unwrap().clone();
+    app.status(labelled(last));
 
     match outcome {
-        Ok(ScrapeOutcome::Ok { kind, ds: new_ds }) => {
-            // accept into cache
-            let page = app.current_page(); // router page for `kind`
-            let entry = app.raw_data.entry(kind)
-                .or_insert_with(|| data::RawData::new(kind, store::DataSet { headers: None, rows: Vec::new() }));
-            entry.merge_from_scrape(page, new_ds);
-
-            // persist
-            if let Some(entry2) = app.raw_data.get_mut(&kind) {
-                let save_ref = entry2.dataset_mut_for_io();
-                match store::save_dataset(&kind, save_ref) {
-                    Ok(p) => logf!("Cache: Saved {:?} → {}", kind, p.display()),
-                    Err(e) => loge!("Cache: Save failed {:?}: {}", kind, e),
+        Ok(ScrapeOutcome::Cancelled { .. }) => {
+            logf!("Scrape: {:?} cancelled", kind);
+            app.status(labelled(s!("Scrape cancelled; data unchanged")));
+        }
+        Ok(ScrapeOutcome::Ok { .. }) if cancelled => {
+            logf!("Scrape: {:?} cancelled after it finished; result dropped", kind);
+            app.status(labelled(s!("Scrape cancelled; data unchanged")));
+        }
+        Ok(ScrapeOutcome::Ok { kind, ds, failed, done }) => {
+            // Game Results from a new season: ask about archiving the last
+            // one before anything of the new one is saved (`answer_new_season`)
+            match new_season(app, kind, &ds) {
+                Some((old, new)) => {
+                    logf!("Scrape: Season {} began; asking to archive season {}", new, old);
+                    app.status(format!("Season {} began: archive season {} first?", new, old));
+                    app.new_season = Some(NewSeason { old, new, kind, ds, failed, done });
                 }
+                None => accept(app, kind, ds, failed, done),
             }
-
-            // invalidate row-index cache for this page + rebuild view
-            app.row_ix_cache.retain(|(k, _), _| *k != kind);
-            app.rebuild_view();
-            // app.status("Ready");
         }
         Ok(ScrapeOutcome::Err { msg, .. }) => {
-            app.status(msg);
+            app.status(labelled(msg));
         }
         Err(e) => {
             app.status(format!("Worker panicked: {e:?}"));
         }
     }
 }
+
+/// Game Results scraped into a new season, waiting for the user to say
+/// whether to archive the last season first (see `answer_new_season`).
+pub struct NewSeason {
+    pub old: u32,
+    pub new: u32,
+    kind: PageKind,
+    ds: store::DataSet,
+    failed: Vec<u32>,
+    done: Vec<u32>,
+}
+
+/// The season a scraped page is from: Game Results' first row.
+fn season_of(kind: PageKind, ds: &store::DataSet) -> Option<u32> {
+    if kind != PageKind::GameResults { return None; }
+    ds.rows.first()?.first()?.trim().parse().ok()
+}
+
+/// `(last, new)` when `ds` starts a season after the current one whose
+/// data isn't archived yet.
+fn new_season(app: &App, kind: PageKind, ds: &store::DataSet) -> Option<(u32, u32)> {
+    let (old, new) = (app.state.season?, season_of(kind, ds)?);
+    (new > old && !store::season_dir(old).exists()).then_some((old, new))
+}
+
+/// Take in the scrape held back by the new-season prompt, archiving the
+/// last season first when `archive`.
+pub fn answer_new_season(app: &mut App, archive: bool) {
+    let Some(NewSeason { old, kind, ds, failed, done, .. }) = app.new_season.take() else { return };
+    if archive {
+        gui::actions::season::archive(app, old);
+    }
+    accept(app, kind, ds, failed, done);
+}
+
+/// Merge a scrape's rows into its page's data and cache, and everything
+/// that follows from it (undo copy, derived pages, change report).
+fn accept(app: &mut App, kind: PageKind, mut new_ds: store::DataSet, failed: Vec<u32>, done: Vec<u32>) {
+    let season = season_of(kind, &new_ds);
+
+    // Per-team status for the team panel
+    if !done.is_empty() || !failed.is_empty() {
+        if let Err(e) = store::mark_teams_scraped(&kind, &done, &failed) {
+            loge!("Cache: Could not record team scrapes {:?}: {}", kind, e);
+        }
+        app.team_scrapes.remove(&kind);
+    }
+
+    // Teams to offer for Retry failed (a clean scrape clears this page's list)
+    if !failed.is_empty() {
+        app.failed_scrape = Some((kind, failed));
+    } else if app.failed_scrape.as_ref().is_some_and(|(k, _)| *k == kind) {
+        app.failed_scrape = None;
+    }
+
+    // accept into cache
+    let page = gui::router::page_for(&kind);
+
+    // Pre-scrape data, for the change report and the delta view
+    let old = app.raw_data.get(&kind)
+        .map(|r| r.dataset().clone())
+        .filter(|ds| !ds.rows.is_empty());
+
+    // Kept on disk for Undo last scrape (and as the delta view's base),
+    // as cached: without the user columns
+    if let (Some(old), Some(raw)) = (old.as_ref(), app.raw_data.get(&kind)) {
+        match store::save_previous(&kind, &raw.base_dataset()) {
+            Ok(p) => {
+                logd!("Cache: Kept previous {:?} → {}", kind, p.display());
+                app.undoable.insert(kind);
+            }
+            Err(e) => loge!("Cache: Keeping previous {:?} failed: {}", kind, e),
+        }
+        if page.has_delta_view() {
+            app.prev_data.insert(kind, old.clone());
+        }
+    }
+
+    // Resolved spellings, so the merge and the change report see one name per player
+    let renamed = crate::alias::Aliases::load().apply(kind, &mut new_ds);
+    if renamed > 0 { logf!("Aliases: {} name(s) in {:?} renamed", renamed, kind); }
+
+    let entry = app.raw_data.entry(kind)
+        .or_insert_with(|| data::RawData::new(kind, store::DataSet { headers: None, rows: Vec::new() }));
+    entry.merge_from_scrape(page, new_ds);
+    derive_columns(app, kind);
+
+    // persist
+    if let Some(entry2) = app.raw_data.get(&kind) {
+        match entry2.save() {
+            Ok(p) => {
+                logf!("Cache: Saved {:?} → {}", kind, p.display());
+                app.saved_at.insert(kind, SystemTime::now());
+                if let Err(e) = store::mark_scraped(&kind) {
+                    loge!("Cache: Could not record scrape time {:?}: {}", kind, e);
+                }
+                app.scraped_at.insert(kind, SystemTime::now());
+            }
+            Err(e) => loge!("Cache: Save failed {:?}: {}", kind, e),
+        }
+    }
+
+    // Pages computed from this one
+    refresh_derived(app, kind);
+    // Logos may have been downloaded
+    if kind == PageKind::TeamInfo { app.logos.clear(); }
+    // The season these results are from, for the other pages (a new one
+    // was offered for archiving before this, see `new_season`)
+    if let Some(new) = season {
+        let before = app.state.season;
+        if let Err(e) = store::save_season(new) { loge!("Cache: Could not save season {}: {}", new, e); }
+        app.state.season = Some(new);
+        // Declined: 📦 still offers the last season
+        if let Some(old) = before.filter(|&old| old < new && !store::season_dir(old).exists()) {
+            app.archive_as = Some(old);
+        }
+        app.seasons = store::seasons();
+    }
+
+    // What changed (nothing to compare on a first scrape)
+    app.last_diff = match (old, app.raw_data.get(&kind)) {
+        (Some(old), Some(now)) => {
+            let key = gui::pages::row_key_between(page, &old, now.dataset());
+            let d = DataDiff::between(&old, now.dataset(), &key);
+            logf!("Diff: {:?} {}", kind, d.summary());
+            if !d.is_empty() && crate::notify::webhook_url().is_some() {
+                let d = d.clone();
+                thread::spawn(move || crate::notify::notify(kind, &d));
+            }
+            Some((kind, d))
+        }
+        _ => None,
+    };
+
+    // invalidate row-index cache for this page + rebuild view
+    app.row_ix_cache.retain(|(k, _), _| *k != kind);
+    app.state.gui.row_selection.remove(&kind);
+    app.rebuild_view();
+}
+
+/// Recompute the pages derived from `scraped` (Team Summary and Leaders from Players,
+/// Results Table and Schedule Metrics from Game Results, Injury Victims
+/// from Injuries and Players, Injury Summary and Players' Out until from
+/// Injuries) from the data in memory, and cache them.
+pub(super) fn refresh_derived(app: &mut App, scraped: PageKind) {
+    // Players' Out until follows the injury log
+    if scraped == PageKind::Injuries && derive_columns(app, PageKind::Players) {
+        match app.raw_data.get(&PageKind::Players).map(|r| r.save()) {
+            Some(Ok(_)) => { app.saved_at.insert(PageKind::Players, SystemTime::now()); }
+            Some(Err(e)) => loge!("Cache: Save failed {:?}: {}", PageKind::Players, e),
+            None => {}
+        }
+        app.row_ix_cache.retain(|(k, _), _| *k != PageKind::Players);
+    }
+
+    // As cached: the user columns are the derived pages' own business
+    let dataset = |k: PageKind| app.raw_data.get(&k).map(|r| r.base_dataset()).filter(|ds| !ds.rows.is_empty());
+    let mut fresh: Vec<(PageKind, store::DataSet)> = Vec::new();
+    if let (true, Some(players)) = (scraped == PageKind::Players, dataset(PageKind::Players)) {
+        fresh.push((PageKind::TeamSummary, crate::aggregate::team_summary(&players)));
+        fresh.push((PageKind::Leaders, crate::leaders::from_players(&players, crate::leaders::MAX_TOP)));
+    }
+    if let (true, Some(results)) = (scraped == PageKind::GameResults, dataset(PageKind::GameResults)) {
+        fresh.push((PageKind::ResultsTable, crate::results_table::from_results(&results, &app.teams, None)));
+        fresh.push((PageKind::ScheduleMetrics, crate::schedule_metrics::from_results(&results, None)));
+    }
+    let victims_stale = matches!(scraped, PageKind::Players | PageKind::Injuries);
+    if let (true, Some(injuries), Some(players)) = (victims_stale, dataset(PageKind::Injuries), dataset(PageKind::Players)) {
+        let (ds, stats) = crate::join::injuries_with_victims(&injuries, &players, &crate::alias::Aliases::load());
+        logf!("Injury victims: {} exact, {} fuzzy, {} ambiguous, {} unmatched", stats.exact, stats.fuzzy, stats.ambiguous.len(), stats.missing.len());
+        fresh.push((PageKind::InjuryVictims, ds));
+    }
+    if let (true, Some(injuries)) = (scraped == PageKind::Injuries, dataset(PageKind::Injuries)) {
+        fresh.push((PageKind::InjurySummary, crate::injury_summary::from_injuries(&injuries)));
+    }
+
+    for (kind, ds) in fresh {
+        match store::save_dataset(&kind, &ds) {
+            Ok(p) => {
+                logf!("Cache: Saved {:?} → {}", kind, p.display());
+                app.saved_at.insert(kind, SystemTime::now());
+                // As fresh as the scrape they're computed from
+                app.scraped_at.insert(kind, SystemTime::now());
+            }
+            Err(e) => loge!("Cache: Save failed {:?}: {}", kind, e),
+        }
+        app.raw_data.insert(kind, data::RawData::new(kind, ds));
+        app.row_ix_cache.retain(|(k, _), _| *k != kind);
+        app.state.gui.row_selection.remove(&kind);
+    }
+}
+
+/// Recompute the derived columns of `kind`'s cached data (Players from the
+/// cached Injuries). Returns whether there was data to update.
+pub(super) fn derive_columns(app: &mut App, kind: PageKind) -> bool {
+    let injuries = match kind {
+        PageKind::Players => app.raw_data.get(&PageKind::Injuries).map(|r| r.base_dataset().into_owned()),
+        _ => None,
+    };
+    let Some(raw) = app.raw_data.get_mut(&kind).filter(|r| !r.dataset().rows.is_empty()) else { return false };
+    raw.update(|ds| crate::derive::apply(kind, ds, injuries.as_ref()))
+}
@@ -0,0 +1,46 @@
+// src/gui/actions/season.rs
+//
+// Season menu: switch every page to an archived season's cache (see
+// `store::select_season`) or back to the current one. Archived seasons
+// are read-only as far as the site goes: scraping waits for the current
+// season, Copy/Export/Import work on whichever is shown. Archiving is the
+// user's call (📦, `archive`); a new season doesn't do it by itself.
+
+use crate::{gui::app::App, store};
+
+/// Show `season`'s data (None: the current season).
+pub fn select(app: &mut App, season: Option<u32>) {
+    if season == store::selected_season() { return; }
+    if !app.scrapes.is_empty() {
+        app.status("Wait for the scrape to finish before switching seasons");
+        return;
+    }
+    store::select_season(season);
+    logf!("UI: Season → {}", label(store::selected_season()));
+    app.reload_cache();
+    app.status(format!("Showing {}", label(store::selected_season())));
+}
+
+/// Copy the cached pages into `season`'s archive (see `store::archive_season`).
+pub fn archive(app: &mut App, season: u32) {
+    match store::archive_season(season) {
+        Ok(pages) => {
+            logf!("UI: Archived {} page(s) as season {}", pages, season);
+            app.archive_as = None;
+            app.seasons = store::seasons();
+            app.status(format!("Archived {} page(s) as season {}", pages, season));
+        }
+        Err(e) => {
+            loge!("UI: Archiving season {} failed: {}", season, e);
+            app.status(format!("Archiving season {} failed: {}", season, e));
+        }
+    }
+}
+
+/// "season 4" for an archived season, "the current season" otherwise.
+pub fn label(season: Option<u32>) -> String {
+    match season {
+        Some(s) => format!("season {}", s),
+        None => s!("the current season"),
+    }
+}
@@ -0,0 +1,225 @@
+// src/gui/actions/settings.rs
+//
+// Settings window: the persisted options in one place — how the site is
+// reached, each page's own export settings, appearance, and the cache.
+// The window edits copies; Save checks, applies and writes them to
+// `.store/settings`. Storage also checks the cache for damage and repairs
+// it (see `integrity`).
+
+use std::{path::{Path, PathBuf}, time::SystemTime};
+
+use crate::{
+    config::{
+        consts::STORE_DIR,
+        options::{ExportFormat, PageExportDefaults, PageKind},
+        state::Appearance,
+    },
+    core::net::{self, NetSettings},
+    data::RawData,
+    gui::{app::App, router},
+    integrity::{self, PageCheck},
+    store::{self, DataSet},
+};
+
+/// A page's own export settings as edited (folder as typed; blank = shared).
+pub struct PageDefaultsDraft {
+    pub kind: PageKind,
+    pub title: &'static str,
+    pub format: Option<ExportFormat>,
+    pub include_headers: Option<bool>,
+    pub dir: String,
+}
+
+impl PageDefaultsDraft {
+    fn to_defaults(&self) -> PageExportDefaults {
+        let dir = self.dir.trim();
+        PageExportDefaults {
+            format: self.format,
+            include_headers: self.include_headers,
+            dir: (!dir.is_empty()).then(|| PathBuf::from(dir)),
+        }
+    }
+}
+
+/// State of the Settings window.
+pub struct SettingsDialog {
+    pub net: NetSettings,
+    pub export_defaults: Vec<PageDefaultsDraft>,
+    pub appearance: Appearance,
+    /// Snapshots kept per page (0 = off)
+    pub snapshots: usize,
+    pub stale_after_hours: u32,
+    /// `data_dir` setting as typed (blank = `.store`), and portable mode;
+    /// both take effect on the next start
+    pub data_dir: String,
+    pub portable: bool,
+    /// The last Verify's findings, per cached page
+    pub cache_check: Option<Vec<PageCheck>>,
+}
+
+impl SettingsDialog {
+    /// What keeps Save from going through, if anything.
+    pub fn problem(&self) -> Option<String> {
+        if let Err(e) = self.net.site() { return Some(e); }
+        if self.data_dir.contains(['\n', '\r']) { return Some(s!("The data folder can't span lines")); }
+        self.export_defaults.iter()
+            .find(|d| d.dir.contains(['\n', '\r']))
+            .map(|d| format!("{}: the folder can't span lines", d.title))
+    }
+}
+
+/// Open the window with the settings in use.
+pub fn open(app: &mut App) {
+    let page_defaults = &app.state.options.export.page_defaults;
+    let export_defaults = router::all_pages().iter().map(|p| {
+        let d = page_defaults.get(&p.kind()).cloned().unwrap_or_default();
+        PageDefaultsDraft {
+            kind: p.kind(),
+            title: p.title(),
+            format: d.format,
+            include_headers: d.include_headers,
+            dir: d.dir.map(|p| p.to_string_lossy().into_owned()).unwrap_or_default(),
+        }
+    }).collect();
+    app.settings = Some(SettingsDialog {
+        net: net::settings(),
+        export_defaults,
+        appearance: app.state.gui.appearance,
+        snapshots: store::snapshot_keep(),
+        stale_after_hours: app.state.gui.stale_after_hours,
+        data_dir: store::configured_data_dir().map(|p| p.to_string_lossy().into_owned()).unwrap_or_default(),
+        portable: store::is_portable(),
+        cache_check: None,
+    });
+}
+
+/// Apply and save the window's settings; the window stays open on errors.
+pub fn save(app: &mut App, ctx: &eframe::egui::Context) {
+    let Some(dialog) = app.settings.as_ref() else { return };
+    if let Some(p) = dialog.problem() {
+        app.status(format!("Settings not saved: {}", p));
+        return;
+    }
+    let net = dialog.net.clone();
+    let appearance = dialog.appearance;
+    let (snapshots, stale_after_hours) = (dialog.snapshots, dialog.stale_after_hours);
+    let data_dir = Some(PathBuf::from(dialog.data_dir.trim())).filter(|p| !p.as_os_str().is_empty());
+    let portable = dialog.portable;
+    let defaults: Vec<(PageKind, PageExportDefaults)> = dialog.export_defaults.iter()
+        .map(|d| (d.kind, d.to_defaults()))
+        .collect();
+
+    // Network: used by the next request
+    if let Err(e) = net::configure(net.clone()) {
+        app.status(format!("Settings not saved: {}", e));
+        return;
+    }
+
+    // Export defaults: the open page switches to its new ones right away
+    let cur = app.current_page_kind();
+    let export = &mut app.state.options.export;
+    export.leave_page_defaults(cur);
+    for (kind, d) in &defaults {
+        if d.is_empty() {
+            export.page_defaults.remove(kind);
+        } else {
+            export.page_defaults.insert(*kind, d.clone());
+        }
+    }
+    export.enter_page_defaults(cur);
+    if !app.out_path_dirty {
+        app.out_path_text = app.state.options.export.out_path().to_string_lossy().into_owned();
+    }
+
+    app.state.gui.appearance = appearance;
+    app.apply_appearance(ctx);
+    app.state.gui.stale_after_hours = stale_after_hours.max(1);
+
+    let snapshots = if snapshots == 0 { String::new() } else { snapshots.to_string() };
+    let res = store::save_net_settings(&net)
+        .and_then(|_| store::save_appearance(&appearance))
+        .and_then(|_| store::save_stale_after_hours(stale_after_hours.max(1)))
+        .and_then(|_| store::save_setting(store::SNAPSHOTS_KEY, &snapshots))
+        .and_then(|p| defaults.iter().try_fold(p, |_, (kind, d)| store::save_page_export_defaults(kind, d)));
+    let res = res.and_then(|p| move_data_dir(data_dir.as_deref(), portable).map(|moved| (p, moved)));
+    match res {
+        Ok((p, moved)) => {
+            logf!("Settings: Saved → {}", p.display());
+            app.status(if moved { "Settings saved; restart to use the new data folder" } else { "Settings saved" });
+            app.settings = None;
+        }
+        Err(e) => {
+            loge!("Settings: Save failed: {}", e);
+            app.status(format!("Settings not saved: {}", e));
+        }
+    }
+}
+
+/// Check the cached pages; the window lists what it finds.
+pub fn verify_cache(app: &mut App) {
+    let checks = integrity::check_cache();
+    let bad = checks.iter().filter(|c| !c.is_ok()).count();
+    logf!("Cache: Verified {} page(s), {} with problems", checks.len(), bad);
+    if let Some(dialog) = app.settings.as_mut() {
+        dialog.cache_check = Some(checks);
+    }
+}
+
+/// Drop the damaged rows the last Verify found and show what's left, then
+/// check again. Undo last scrape brings a page's old data back.
+pub fn repair_cache(app: &mut App) {
+    let Some(checks) = app.settings.as_ref().and_then(|d| d.cache_check.as_ref()) else { return };
+    let kinds: Vec<PageKind> = checks.iter().filter(|c| c.repairable() > 0).map(|c| c.kind).collect();
+    let mut dropped = 0;
+    for kind in kinds {
+        match integrity::repair(kind) {
+            Ok(n) => dropped += n,
+            Err(e) => {
+                loge!("Cache: Repair {:?} failed: {}", kind, e);
+                continue;
+            }
+        }
+        let shown = router::all_pages().iter().any(|p| p.kind() == kind);
+        match store::load_dataset(&kind) {
+            Ok(ds) if shown => show_repaired(app, kind, ds),
+            Ok(_) => {}
+            Err(e) => loge!("Cache: Reload {:?} failed: {}", kind, e),
+        }
+    }
+    app.rebuild_view();
+    app.status(format!("Cache repaired: dropped {} row(s)", dropped));
+    verify_cache(app);
+}
+
+/// Replace the page's data with its repaired cache.
+fn show_repaired(app: &mut App, kind: PageKind, mut ds: DataSet) {
+    crate::schema::inject_headers(kind, &mut ds);
+    app.raw_data.insert(kind, RawData::new(kind, ds));
+    app.undoable.insert(kind);
+    // The delta base and change report were for the data before
+    app.prev_data.remove(&kind);
+    if app.last_diff.as_ref().is_some_and(|(k, _)| *k == kind) { app.last_diff = None; }
+    app.saved_at.insert(kind, SystemTime::now());
+    super::scrape::refresh_derived(app, kind);
+    app.row_ix_cache.retain(|(k, _), _| *k != kind);
+    app.state.gui.row_selection.remove(&kind);
+}
+
+/// Save the data folder and portable mode if they changed, copying the
+/// data in use over so the next start finds it. Returns whether they did.
+fn move_data_dir(data_dir: Option<&Path>, portable: bool) -> std::io::Result<bool> {
+    let same_dir = match (data_dir, store::configured_data_dir()) {
+        (Some(a), Some(b)) => store::same_dir(a, &b),
+        (a, b) => a.is_none() && b.is_none(),
+    };
+    if same_dir && portable == store::is_portable() { return Ok(false); }
+
+    store::save_data_dir(data_dir)?;
+    store::set_portable(portable)?;
+    let target = if portable { store::exe_store_dir() } else { None }
+        .or_else(|| data_dir.map(Path::to_path_buf))
+        .unwrap_or_else(|| PathBuf::from(STORE_DIR));
+    let copied = store::copy_store(&store::store_dir(), &target)?;
+    logf!("Settings: Data folder → {} (portable: {}), {} files copied", target.display(), portable, copied);
+    Ok(true)
+}
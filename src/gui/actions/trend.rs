@@ -0,0 +1,122 @@
+// src/gui/actions/trend.rs
+//
+// Trend chart of one player's stat across the Players snapshots (see
+// `trend`). Snapshots are read when the window opens; the player list comes
+// from the cached Players data. Save PNG takes a screenshot of the window
+// and crops it to the chart; the file goes next to the Players exports.
+
+use std::path::PathBuf;
+
+use eframe::egui;
+use crate::{
+    config::options::{ExportOptions, PageKind},
+    core::sanitize::sanitize_team_filename,
+    file,
+    gui::app::App,
+    store::{self, DataSet},
+    trend::{self, PlayerKey},
+};
+
+/// Players name and team columns (schema order: Name, #, Race, Team).
+const NAME_COL: usize = 0;
+const TEAM_COL: usize = 3;
+
+/// A player the chart can show.
+pub struct TrendPlayer {
+    pub key: PlayerKey,
+    pub name: String,
+    pub team: String,
+}
+
+/// State of the Trend window.
+pub struct TrendChart {
+    /// Players snapshots with their time, oldest first
+    pub snapshots: Vec<(u64, DataSet)>,
+    /// Players of the cached data, in table order
+    pub players: Vec<TrendPlayer>,
+    /// Stats to pick from
+    pub stats: Vec<String>,
+    /// Team filter of the player list (None: all teams)
+    pub team: Option<String>,
+    /// Index into `players`
+    pub player: Option<usize>,
+    pub stat: String,
+    /// Where the chart was last drawn (for cropping the screenshot)
+    pub chart_rect: Option<egui::Rect>,
+    /// File the requested screenshot goes to
+    pub png_pending: Option<PathBuf>,
+}
+
+impl TrendChart {
+    /// The chart's points, as (unix seconds, value).
+    pub fn series(&self) -> Vec<(u64, f64)> {
+        match self.player.and_then(|i| self.players.get(i)) {
+            Some(p) => trend::series(&self.snapshots, &p.key, &self.stat),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Open the window for the first selected Players row, or the first player.
+pub fn open(app: &mut App) {
+    let Some(raw) = app.raw_data.get(&PageKind::Players) else {
+        app.status("Nothing to chart — scrape Players first");
+        return;
+    };
+    let ds = raw.base_dataset();
+    let players: Vec<TrendPlayer> = ds.rows.iter().map(|r| {
+        let cell = |c: usize| r.get(c).map(|s| s.trim().to_string()).unwrap_or_default();
+        TrendPlayer { key: PlayerKey::of(&ds, r), name: cell(NAME_COL), team: cell(TEAM_COL) }
+    }).collect();
+    let stats = trend::stat_names(&ds);
+    drop(ds);
+
+    let selected = app.state.gui.row_selection.get(&PageKind::Players).and_then(|s| s.first());
+    let player = selected.filter(|&i| i < players.len()).or((!players.is_empty()).then_some(0));
+    let stat = stats.iter().find(|s| s.as_str() == "TV").or(stats.first()).cloned().unwrap_or_default();
+    let snapshots = store::load_snapshots(&PageKind::Players);
+    logf!("Trend: Open with {} snapshot(s)", snapshots.len());
+    app.trend = Some(TrendChart {
+        snapshots, players, stats, team: None, player, stat, chart_rect: None, png_pending: None,
+    });
+}
+
+/// Ask for a screenshot to save as `<team>_<player>_<stat>.png` in the
+/// default Players folder's `charts`; `write_png` writes it when it comes.
+pub fn save_png(app: &mut App, ctx: &egui::Context) {
+    let Some(chart) = app.trend.as_mut() else { return };
+    let Some(p) = chart.player.and_then(|i| chart.players.get(i)) else {
+        app.status("Pick a player to chart first");
+        return;
+    };
+    let stem = format!("{}_{}_{}",
+        sanitize_team_filename(&p.team, 0), sanitize_team_filename(&p.name, 1), sanitize_team_filename(&chart.stat, 2));
+    let path = ExportOptions::default_dir_for(PageKind::Players).join("charts").join(format!("{}.png", stem));
+    chart.png_pending = Some(path);
+    ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot(egui::UserData::default()));
+}
+
+/// Write the chart part of a screenshot to the pending file.
+pub fn write_png(app: &mut App, ctx: &egui::Context, screenshot: &egui::ColorImage) {
+    let Some(chart) = app.trend.as_mut() else { return };
+    let (Some(path), Some(rect)) = (chart.png_pending.take(), chart.chart_rect) else { return };
+
+    let img = screenshot.region(&rect, Some(ctx.pixels_per_point()));
+    let [w, h] = img.size;
+    let pixels: Vec<u8> = img.pixels.iter().flat_map(|c| c.to_array()).collect();
+    let res = match image::RgbaImage::from_raw(w as u32, h as u32, pixels) {
+        Some(rgba) => path.parent().map_or(Ok(()), file::ensure_directory)
+            .and_then(|_| rgba.save(&path).map_err(Into::into)),
+        None => Err("empty chart".into()),
+    };
+    match res {
+        Ok(()) => {
+            logf!("Export: Trend chart → {}", path.display());
+            app.status(format!("Saved chart to {}", path.display()));
+        }
+        Err(e) => {
+            loge!("Export: Trend chart failed: {}", e);
+            app.status(format!("Chart not saved: {e}"));
+        }
+    }
+}
@@ -0,0 +1,93 @@
+// src/gui/actions/user_columns.rs
+//
+// User columns: formulas over a page's columns (`expr`), saved per page in
+// `.store/settings` and added to the table, copies and exports. The cache
+// keeps the scraped columns only.
+
+use crate::{config::options::PageKind, expr::Expr, gui::app::App, store};
+
+/// State of the User columns window.
+pub struct UserColumnsEditor {
+    pub kind: PageKind,
+    /// The page's own columns, as cached (what formulas can refer to)
+    pub columns: Vec<String>,
+    /// (name, formula) as edited
+    pub rows: Vec<(String, String)>,
+    /// Names saved when the window opened (removed ones get deleted)
+    saved: Vec<String>,
+}
+
+impl UserColumnsEditor {
+    /// What's wrong with row `i`, if anything: a formula that doesn't parse,
+    /// a name that's taken, or a column the page doesn't have (user columns
+    /// count when their name sorts before this one's, as they're added in
+    /// name order).
+    pub fn problem(&self, i: usize) -> Option<String> {
+        let (name, formula) = &self.rows[i];
+        let (name, formula) = (name.trim(), formula.trim());
+        if name.is_empty() && formula.is_empty() { return None; }
+        if name.is_empty() { return Some(s!("needs a name")); }
+        if name.contains('=') { return Some(s!("no = in names")); }
+        let taken = self.columns.iter().any(|c| c.eq_ignore_ascii_case(name))
+            || self.rows.iter().enumerate().any(|(j, (n, _))| j != i && n.trim().eq_ignore_ascii_case(name));
+        if taken { return Some(format!("{} is taken", name)); }
+        let expr = match Expr::parse(formula) {
+            Ok(e) => e,
+            Err(e) => return Some(e),
+        };
+        let mut known = self.columns.clone();
+        known.extend(self.rows.iter().map(|(n, _)| n.trim().to_string()).filter(|n| !n.is_empty() && n.as_str() < name));
+        expr.missing_column(&known).map(|c| format!("no column {:?}", c))
+    }
+}
+
+/// Open the window for the current page.
+pub fn open(app: &mut App) {
+    let kind = app.current_page_kind();
+    let columns = app.raw_data.get(&kind)
+        .and_then(|r| r.base_dataset().headers.clone())
+        .or_else(|| crate::schema::for_page(kind).map(|p| p.names().into_iter().map(String::from).collect()))
+        .unwrap_or_default();
+    let rows: Vec<(String, String)> = store::user_columns().remove(&kind).unwrap_or_default().into_iter().collect();
+    let saved = rows.iter().map(|(n, _)| n.clone()).collect();
+    app.user_columns = Some(UserColumnsEditor { kind, columns, rows, saved });
+}
+
+/// Save the window's columns and recompute them on the page's data.
+pub fn save(app: &mut App) {
+    let Some(editor) = app.user_columns.as_mut() else { return };
+    let kind = editor.kind;
+    if let Some(p) = (0..editor.rows.len()).find_map(|i| editor.problem(i)) {
+        app.status(format!("User columns not saved: {}", p));
+        return;
+    }
+    editor.rows.retain(|(n, f)| !n.trim().is_empty() && !f.trim().is_empty());
+
+    let keep: Vec<String> = editor.rows.iter().map(|(n, _)| n.trim().to_string()).collect();
+    // Removed (and renamed) ones first: an empty formula deletes
+    let mut changes: Vec<(String, String)> = editor.saved.iter()
+        .filter(|n| !keep.contains(n))
+        .map(|n| (n.clone(), String::new()))
+        .collect();
+    changes.extend(editor.rows.iter().cloned());
+
+    for (name, formula) in &changes {
+        if let Err(e) = store::save_user_column(&kind, name, formula) {
+            loge!("Settings: User column {:?} not saved: {}", name, e);
+            app.status(format!("User columns not saved: {}", e));
+            return;
+        }
+    }
+    let n = keep.len();
+    if let Some(editor) = app.user_columns.as_mut() {
+        editor.saved = keep;
+    }
+
+    if let Some(raw) = app.raw_data.get_mut(&kind) {
+        raw.refresh_user_columns();
+    }
+    app.row_ix_cache.retain(|(k, _), _| *k != kind);
+    app.rebuild_view();
+    logf!("Settings: {} user column(s) for {:?}", n, kind);
+    app.status(format!("{} has {} user column(s)", kind, n));
+}
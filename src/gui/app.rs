@@ -1,8 +1,9 @@
 // src/gui/app.rs
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, HashSet},
     error::Error,
-    sync::{Arc, Mutex}, thread,
+    sync::{Arc, Mutex},
+    time::{Instant, SystemTime},
 };
 
 use eframe::egui;
@@ -11,24 +12,34 @@ use crate::{
     store,
     get_teams,
     config::{
-        state::{AppState, GuiState},
+        consts::DEFAULT_FONT_SIZE,
+        state::{AppState, GuiState, Theme},
         options::{ TeamSelector, PageKind::{ self, * }}}
 };
 
 use super::{
     components::*,
+    progress::Counts,
     pages::Page,
     router,
+    shortcuts::{self, Shortcut},
 };
 
-use crate::data::{RawData, Selection, SelectionView};
-use super::actions::scrape::ScrapeOutcome;
+use crate::diff::DataDiff;
+use crate::data::{CompactTable, RawData, Selection, SelectionView, SortKey, TextFilter};
+use super::components::team_panel::TeamSort;
+use super::actions::{aliases::AliasEditor, compare::CompareTeams, export::FailedExport, header_names::HeaderNamesEditor, import::ImportDialog, scrape::ScrapeJob, settings::SettingsDialog, trend::TrendChart, user_columns::UserColumnsEditor};
 
 pub fn run(options: eframe::NativeOptions) -> Result<(), Box<dyn Error>> {
+    store::migrate_default_store();
     eframe::run_native(
-        "Brutalball Scraper",
+        crate::config::consts::APP_TITLE,
         options,
-        Box::new(|_cc| Ok(Box::new(App::new(AppState::default())))),
+        Box::new(|cc| {
+            let app = App::new(AppState::default());
+            app.apply_appearance(&cc.egui_ctx);
+            Ok(Box::new(app))
+        }),
     )?;
     Ok(())
 }
@@ -39,7 +50,17 @@ pub struct App {
 
     // Teams & selection UI (selection lives inside state.gui)
     pub teams: Vec<(u32, String)>,
+    // get_teams::generation() that `teams` was taken at
+    pub teams_generation: u64,
+    // Team name → division, from the same team list (empty if it has none)
+    pub divisions: get_teams::Divisions,
+    // Position in the team panel's display order of the last clicked team
     pub last_clicked: Option<usize>,
+    // Team panel search text and order
+    pub team_search: String,
+    pub team_sort: TeamSort,
+    // Team panel keyboard cursor (display position): arrows move, Space toggles
+    pub team_cursor: Option<usize>,
 
     // Output text field UX (we map this <-> ExportOptions)
     pub out_path_text: String,
@@ -51,20 +72,101 @@ pub struct App {
 
     // Status/progress (workers write here)
     pub status: Arc<Mutex<String>>,
-    pub running: bool,
-    pub scrape_handle: Option<thread::JoinHandle<ScrapeOutcome>>,
+    /// Scrapes running now, one per page at most
+    pub scrapes: HashMap<PageKind, ScrapeJob>,
+    /// Item counts of each page's last scrape (its final tally)
+    pub last_counts: HashMap<PageKind, Counts>,
+
+    // Refresh-on-focus: cache age per page, focus edge, throttle
+    pub saved_at: HashMap<PageKind, SystemTime>,
+    /// When each page was last scraped (tab age, stale tint)
+    pub scraped_at: HashMap<PageKind, SystemTime>,
+    pub was_focused: bool,
+    pub last_auto_refresh: Option<Instant>,
+
+    // Per-team files the last export could not write (offered for retry)
+    pub failed_export: Option<FailedExport>,
 
     // Per-page canonical data + cached views
     pub raw_data: HashMap<PageKind, RawData>,
 
+    // Pre-scrape snapshots for pages with a delta view
+    pub prev_data: HashMap<PageKind, store::DataSet>,
+    // Pages with a pre-scrape copy on disk to go back to (Undo last scrape)
+    pub undoable: HashSet<PageKind>,
+    // Delta rows for the current page (same indices as raw rows), when shown
+    pub delta_rows: Option<Arc<CompactTable>>,
+
+    // Changes found by the last scrape ("What changed" panel)
+    pub last_diff: Option<(PageKind, DataDiff)>,
+
+    // Open player drill-down window, if any
+    pub player_detail: Option<player_detail::PlayerDetail>,
+
+    /// Teams whose fetch failed in the last scrape of a page (Retry failed)
+    pub failed_scrape: Option<(PageKind, Vec<u32>)>,
+    /// Seasons with cached data, newest first (the season menu)
+    pub seasons: Vec<u32>,
+    /// Season the cached pages are archived as (📦): the last one once Game
+    /// Results show a new season, else None for the current one
+    pub archive_as: Option<u32>,
+    /// Game Results of a new season held back until the user says whether
+    /// to archive the last one (new-season prompt)
+    pub new_season: Option<crate::gui::actions::scrape::NewSeason>,
+
+    // About window (versions, changelog) open
+    pub show_about: bool,
+
+    // Open "Import from clipboard/file" window, if any
+    pub import: Option<ImportDialog>,
+
+    // Open "Resolve names" window, if any
+    pub alias_editor: Option<AliasEditor>,
+
+    // Open "Export header names" window, if any
+    pub header_names: Option<HeaderNamesEditor>,
+
+    // Open "User columns" window, if any
+    pub user_columns: Option<UserColumnsEditor>,
+
+    // Open "Compare teams" window, if any
+    pub compare: Option<CompareTeams>,
+
+    // Open "Trend" window, if any
+    pub trend: Option<TrendChart>,
+
+    // Open "Settings" window, if any
+    pub settings: Option<SettingsDialog>,
+
+    /// Team logos for the team list, loaded on first use from `.store/logos/`
+    /// (None: no logo, or not a PNG). Cleared after a Team Info scrape.
+    pub logos: HashMap<u32, Option<egui::TextureHandle>>,
+
+    /// Per-team scrape records per page (`store::team_scrapes`), loaded on
+    /// first use by the team list; a page's is dropped when it's scraped
+    pub team_scrapes: HashMap<PageKind, HashMap<u32, store::TeamScrape>>,
+
+    /// Scrape progress last shown in the window title and taskbar
+    pub window_progress: Option<Counts>,
+
+    /// Named team selections (`.store/settings`), and the name being typed
+    pub team_presets: BTreeMap<String, Vec<u32>>,
+    pub preset_name: String,
+
     /// Cache of row indices per (page, selection key).
-    /// Invalidation: bump state.teams_version on team list changes.
+    /// Invalidation: cleared by set_teams when the team list changes.
     /// Clear per-page on scrape merge (see Export button handler).
     pub row_ix_cache: HashMap<(PageKind, u32), Arc<Vec<usize>>>,
 
     // Column order per page (visual only). Values are source column indexes.
     pub col_order: HashMap<PageKind, Vec<usize>>,
 
+    // Search box filter per page (applied to row_ix, not to the cache)
+    pub text_filter: HashMap<PageKind, TextFilter>,
+
+    // Active header sort per page (applied to row_ix, not to the cache)
+    pub col_sort: HashMap<PageKind, SortKey>,
+
     // Column widths per page, keyed by source column index (f32 px-ish)
     pub col_widths: HashMap<PageKind, Vec<f32>>,
 
@@ -78,13 +180,71 @@ pub struct App {
     pub dragging_ghost_width: f32,
 }
 
+/// The pages' data as cached for the season in use, with what goes along.
+struct LoadedCache {
+    raw_data: HashMap<PageKind, RawData>,
+    saved_at: HashMap<PageKind, SystemTime>,
+    scraped_at: HashMap<PageKind, SystemTime>,
+    prev_data: HashMap<PageKind, store::DataSet>,
+    undoable: HashSet<PageKind>,
+}
+
+fn load_cache() -> LoadedCache {
+    // Canonical cache
+    let mut raw_data: HashMap<PageKind, RawData> = HashMap::new();
+
+    // Load cache for all pages
+    for p in router::all_pages() {
+        let k = p.kind();
+
+        match store::load_dataset(&k) {
+            Ok(ds) => {
+                if ds.rows.is_empty() {
+                    logd!("Cache: {:?} is empty, skipping", k);
+                    continue
+                }
+                if p.validate_cache(&ds) {
+                    logf!("Cache: Loaded {:?} (rows={}, headers={})",
+                        k, ds.row_count(), ds.header_count());
+                    raw_data.insert(k, RawData::new(k, ds));
+                } else {
+                    loge!("Cache: Invalid shape for {:?}, ignoring", k);
+                }
+            }
+            Err(e) => logd!("Cache: Missing {:?} ({})", k, e),
+        }
+    }
+
+    // Cache age, for refresh on focus
+    let saved_at: HashMap<PageKind, SystemTime> = router::all_pages().iter()
+        .filter_map(|p| store::saved_at(&p.kind()).map(|t| (p.kind(), t)))
+        .collect();
+    // Last scrape, for the tabs' stale indicators
+    let scraped_at: HashMap<PageKind, SystemTime> = router::all_pages().iter()
+        .filter(|p| saved_at.contains_key(&p.kind()))
+        .filter_map(|p| store::scraped_at(&p.kind()).map(|t| (p.kind(), t)))
+        .collect();
+
+    // Previous snapshots for delta views
+    let mut prev_data: HashMap<PageKind, store::DataSet> = HashMap::new();
+    for p in router::all_pages().iter().filter(|p| p.has_delta_view()) {
+        if let Ok(ds) = store::load_previous(&p.kind()) {
+            prev_data.insert(p.kind(), ds);
+        }
+    }
+    let undoable: HashSet<PageKind> = router::all_pages().iter()
+        .map(|p| p.kind())
+        .filter(store::has_previous)
+        .collect();
+
+    LoadedCache { raw_data, saved_at, scraped_at, prev_data, undoable }
+}
+
 impl App {
     pub fn new(mut state: AppState) -> Self {
         // Teams list (fallback)
-        let teams = match get_teams::load() {
-            Ok(v) if !v.is_empty() => v,
-            _ => (0u32..32).map(|id| (id, format!("Team {}", id))).collect(),
-        };
+        let teams = get_teams::list_or_scrape();
+        let teams_generation = get_teams::generation();
 
         // Default selection: all
         state.gui = GuiState {
@@ -92,36 +252,33 @@ impl App {
             ..GuiState::default()
         };
 
+        // Columns the user hid last session
+        let settings = store::load_settings();
+        for p in router::all_pages() {
+            if let Some(v) = settings.get(&store::hidden_columns_key(&p.kind())) {
+                let names: Vec<String> = v.split('\t').filter(|n| !n.is_empty()).map(String::from).collect();
+                if !names.is_empty() { state.gui.hidden_columns.insert(p.kind(), names); }
+            }
+        }
+
+        state.gui.stale_after_hours = store::stale_after_hours();
+        state.gui.leaders_top = store::leaders_top();
+        state.gui.appearance = store::appearance();
+        if let Err(e) = crate::core::net::configure(store::net_settings()) {
+            loge!("Settings: {}; using the default site", e);
+        }
+        state.options.export.header_names = store::header_names();
+        state.options.export.page_defaults = store::page_export_defaults();
+        let first = router::all_pages()[state.gui.current_page_index].kind();
+        state.options.export.enter_page_defaults(first);
+
         let mut status = s!("Idle");
 
         // Initial out path text
         let out_path_text = state.options.export.out_path().to_string_lossy().into();
 
-        // Canonical cache
-        let mut raw_data: HashMap<PageKind, RawData> = HashMap::new();
-
-        // Load cache for all pages
-        for p in router::all_pages() {
-            let k = p.kind();
-
-            match store::load_dataset(&k) {
-                Ok(ds) => {
-                    if ds.rows.is_empty() {
-                        logd!("Cache: {:?} is empty, skipping", k);
-                        continue
-                    }
-                    if p.validate_cache(&ds) {
-                        logf!("Cache: Loaded {:?} (rows={}, headers={})",
-                            k, ds.row_count(), ds.header_count());
-                        raw_data.insert(k, RawData::new(k, ds));
-                        status = s!("Loaded local data");
-                    } else {
-                        loge!("Cache: Invalid shape for {:?}, ignoring", k);
-                    }
-                }
-                Err(e) => logd!("Cache: Missing {:?} ({})", k, e),
-            }
-        }
+        let LoadedCache { raw_data, saved_at, scraped_at, prev_data, undoable } = load_cache();
+        if !raw_data.is_empty() { status = s!("Loaded local data"); }
 
         logf!("Init: teams={}, default page={:?}", teams.len(), Players);
 
@@ -162,17 +319,51 @@ impl App {
         let mut app = Self {
             state,
             teams,
+            teams_generation,
+            divisions: get_teams::divisions(),
             last_clicked: None,
+            team_search: String::new(),
+            team_sort: TeamSort::default(),
+            team_cursor: None,
             out_path_text,
             out_path_dirty: false,
             headers,
             row_ix,
             status: Arc::new(Mutex::new(status)),
-            running: false,
-            scrape_handle: None,
+            scrapes: HashMap::new(),
+            last_counts: HashMap::new(),
+            saved_at,
+            scraped_at,
+            was_focused: true,
+            last_auto_refresh: None,
+            failed_export: None,
             raw_data,
+            prev_data,
+            undoable,
+            delta_rows: None,
+            last_diff: None,
+            player_detail: None,
+            show_about: false,
+            import: None,
+            alias_editor: None,
+            header_names: None,
+            user_columns: None,
+            compare: None,
+            trend: None,
+            settings: None,
+            logos: HashMap::new(),
+            team_scrapes: HashMap::new(),
+            window_progress: None,
+            team_presets: store::team_presets(),
+            preset_name: String::new(),
+            failed_scrape: None,
+            seasons: store::seasons(),
+            archive_as: None,
+            new_season: None,
             row_ix_cache,
             col_order: HashMap::new(),
+            text_filter: HashMap::new(),
+            col_sort: HashMap::new(),
             col_widths: HashMap::new(),
             dragging_source_col: None,
             dragging_preview_to: None,
@@ -200,8 +391,88 @@ impl App {
         app
     }
 
+    /// Load every page's data again, for the season now in use (see
+    /// `store::select_season`); selections, diffs and row caches start over.
+    pub fn reload_cache(&mut self) {
+        let LoadedCache { raw_data, saved_at, scraped_at, prev_data, undoable } = load_cache();
+        self.raw_data = raw_data;
+        self.saved_at = saved_at;
+        self.scraped_at = scraped_at;
+        self.prev_data = prev_data;
+        self.undoable = undoable;
+        self.last_diff = None;
+        self.failed_scrape = None;
+        self.team_scrapes.clear();
+        self.seasons = store::seasons();
+        self.row_ix_cache.clear();
+        self.state.gui.row_selection.clear();
+        self.rebuild_view();
+    }
+
     /* ---------- tiny helpers ---------- */
 
+    /// Source columns of a page as shown in the table (dragged order),
+    /// minus hidden and export-excluded ones. For "As shown" exports.
+    pub fn shown_export_columns(&self, kind: PageKind) -> Vec<usize> {
+        let cols = self.source_column_count(kind);
+        let order = self.col_order.get(&kind)
+            .filter(|o| o.len() == cols)
+            .cloned()
+            .unwrap_or_else(|| (0..cols).collect());
+        self.without_excluded(kind, order)
+    }
+
+    /// Source columns of a page in dataset order, minus export-excluded and
+    /// user-hidden ones. For regular exports when the user hid columns.
+    pub fn export_columns(&self, kind: PageKind) -> Vec<usize> {
+        self.without_excluded(kind, (0..self.source_column_count(kind)).collect())
+    }
+
+    fn source_column_count(&self, kind: PageKind) -> usize {
+        let ds = self.raw_data.get(&kind).map(|r| r.dataset());
+        ds.and_then(|d| d.headers.as_ref().map(|h| h.len()).or_else(|| d.rows.first().map(|r| r.len())))
+            .unwrap_or(0)
+    }
+
+    fn without_excluded(&self, kind: PageKind, cols: Vec<usize>) -> Vec<usize> {
+        let page = router::page_for(&kind);
+        let headers = self.raw_data.get(&kind).and_then(|r| r.dataset().headers.as_deref());
+        let mut excluded = page.export_excluded_columns(&self.state, headers);
+        excluded.extend(self.user_hidden_columns(kind, headers));
+        cols.into_iter().filter(|c| !excluded.contains(c)).collect()
+    }
+
+    /// Indices of the columns the user hid on a page (Columns menu).
+    pub fn user_hidden_columns(&self, kind: PageKind, headers: Option<&[String]>) -> Vec<usize> {
+        let (Some(names), Some(hs)) = (self.state.gui.hidden_columns.get(&kind), headers) else {
+            return Vec::new();
+        };
+        hs.iter().enumerate()
+            .filter(|(_, h)| names.contains(h))
+            .map(|(ci, _)| ci)
+            .collect()
+    }
+
+    /// Columns left out of the table: the page's own toggles plus the user's.
+    pub fn hidden_columns_for(&self, kind: PageKind, headers: Option<&[String]>) -> Vec<usize> {
+        let mut hidden = router::page_for(&kind).hidden_columns(&self.state, headers);
+        for ci in self.user_hidden_columns(kind, headers) {
+            if !hidden.contains(&ci) { hidden.push(ci); }
+        }
+        hidden
+    }
+
+    /// Hide or show a column by header name and persist the choice.
+    pub fn set_column_hidden(&mut self, kind: PageKind, name: &str, hide: bool) {
+        let names = self.state.gui.hidden_columns.entry(kind).or_default();
+        names.retain(|n| n != name);
+        if hide { names.push(name.to_string()); }
+        let value = names.join("\t");
+        if let Err(e) = store::save_setting(&store::hidden_columns_key(&kind), &value) {
+            loge!("Settings: failed to save hidden columns for {:?}: {}", kind, e);
+        }
+    }
+
     #[inline]
     pub fn current_index(&self) -> usize { self.state.gui.current_page_index }
 
@@ -214,6 +485,30 @@ impl App {
     #[inline]
     pub fn current_page(&self) -> &'static dyn Page { router::all_pages()[self.current_index()] }
 
+    /// Whether the current page is being scraped.
+    #[inline]
+    pub fn running(&self) -> bool { self.scrapes.contains_key(&self.current_page_kind()) }
+
+    /// Theme and font size from the appearance settings (the table reads
+    /// its row height itself). Text styles scale from egui's defaults.
+    pub fn apply_appearance(&self, ctx: &egui::Context) {
+        let a = self.state.gui.appearance;
+        ctx.set_theme(match a.theme {
+            Theme::System => egui::ThemePreference::System,
+            Theme::Dark => egui::ThemePreference::Dark,
+            Theme::Light => egui::ThemePreference::Light,
+        });
+        let scale = a.font_size / DEFAULT_FONT_SIZE;
+        let defaults = egui::Style::default().text_styles;
+        ctx.all_styles_mut(|style| {
+            for (ts, font) in style.text_styles.iter_mut() {
+                if let Some(d) = defaults.get(ts) {
+                    font.size = d.size * scale;
+                }
+            }
+        });
+    }
+
     #[inline]
     pub fn status<T: Into<String>>(&self, msg: T) {
         *self.status.lock().unwrap() = msg.into();
@@ -277,6 +572,18 @@ impl App {
         self.rebuild_view();
     }
 
+    /// Pick up a team list saved since we last looked (Teams scrape, or the
+    /// players scrape refreshing it in the background).
+    pub fn sync_teams(&mut self) {
+        let generation = get_teams::generation();
+        if generation == self.teams_generation { return; }
+        self.teams_generation = generation;
+        self.divisions = get_teams::divisions();
+        if let Some(teams) = get_teams::cached() {
+            self.set_teams(teams);
+        }
+    }
+
     /// Recompute headers/rows for the current page from canonical raw_data,
     /// applying the current GUI team selection.
     /// Uses a row-index cache if present.
@@ -306,6 +613,38 @@ impl App {
                 self.row_ix_cache.insert(key, arc_ix.clone());
                 self.row_ix = arc_ix;
             }
+
+            // Page toggles, text filter and header sort refine the projection;
+            // the cache keeps the plain selection.
+            let keep = page.row_filter(&self.state);
+            let filter = self.text_filter.get(&kind).filter(|f| f.is_active());
+            let sort = self.col_sort.get(&kind);
+            if keep.is_some() || filter.is_some() || sort.is_some() {
+                let mut ix = (*self.row_ix).clone();
+                if let Some(keep) = keep {
+                    let rows = &raw.dataset().rows;
+                    ix.retain(|&i| rows.get(i).is_some_and(|r| keep(r)));
+                }
+                if let Some(f) = filter {
+                    f.retain(&raw.dataset().rows, &mut ix);
+                }
+                if let Some(sort) = sort {
+                    let numeric = !page.non_numeric_columns().contains(&sort.col);
+                    sort.sort_row_ix_typed(&raw.dataset().rows, raw.typed(), &mut ix, numeric);
+                }
+                self.row_ix = Arc::new(ix);
+            }
+
+            self.delta_rows = match self.prev_data.get(&kind) {
+                Some(prev) if self.state.gui.show_delta && page.has_delta_view() => {
+                    let key = super::pages::row_key_between(page, prev, raw.dataset());
+                    let delta = crate::data::delta_rows(
+                        prev, raw.dataset(), &key, page.non_numeric_columns());
+                    Some(Arc::new(CompactTable::from_rows(delta)))
+                }
+                _ => None,
+            };
+
             // Ensure column order is initialized or resized to current cols
             let cols = self.headers.as_ref()
                 .map(|h| h.len())
@@ -320,6 +659,7 @@ impl App {
                 .default_headers()
                 .map(|hs| hs.iter().map(|s| s!(*s)).collect());
             self.row_ix = Arc::new(Vec::new());
+            self.delta_rows = None;
             // Reset column order for empty dataset / defaults
             let cols = self.headers.as_ref().map(|h| h.len()).unwrap_or(0);
             if cols > 0 {
@@ -327,18 +667,43 @@ impl App {
             }
         }
     }
+
+    /// Run the actions whose shortcuts were pressed (see `shortcuts`).
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        for s in shortcuts::pressed(ctx) {
+            logd!("UI: Shortcut {:?}", s);
+            match s {
+                Shortcut::Copy => super::actions::copy(self, ctx),
+                Shortcut::Export => super::actions::export(self),
+                Shortcut::Scrape if !self.running() => super::actions::scrape(self),
+                Shortcut::Scrape => {}
+                Shortcut::SelectAll => team_panel::select_all(self),
+                Shortcut::SelectNone => team_panel::select_none(self),
+            }
+        }
+    }
 }
 
 impl eframe::App for App {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
 
         crate::gui::actions::scrape::poll(self);
+        self.sync_teams();
+        crate::gui::taskbar::update(self, ctx, frame);
 
-        if self.running {
+        let focused = ctx.input(|i| i.focused);
+        if focused && !self.was_focused {
+            crate::gui::actions::scrape::refresh_on_focus(self);
+        }
+        self.was_focused = focused;
+
+        if !self.scrapes.is_empty() {
             // Repaint while spinner animates; throttle a bit to save CPU
             ctx.request_repaint_after(std::time::Duration::from_millis(60));
         }
 
+        self.handle_shortcuts(ctx);
+
         egui::SidePanel::left("teams")
             .resizable(false)
             .min_width(self.state.gui.team_panel_width)
@@ -348,6 +713,12 @@ impl eframe::App for App {
                 team_panel::draw(ui, self);
             });
 
+        egui::TopBottomPanel::bottom("log")
+            .resizable(true)
+            .show(ctx, |ui| {
+                log_panel::draw(ui, self);
+            });
+
         egui::CentralPanel::default().show(ctx, |ui| {
             tabs::draw(ui, self);
 
@@ -357,7 +728,24 @@ impl eframe::App for App {
 
             ui.separator();
 
+            changes_panel::draw(ui, self);
+
+            injury_bars::draw(ui, self);
+
+            search_bar::draw(ui, self);
+
             data_table::draw(ui, self);
         });
+
+        player_detail::draw(ctx, self);
+        about::draw(ctx, self);
+        import_dialog::draw(ctx, self);
+        alias_editor::draw(ctx, self);
+        header_names::draw(ctx, self);
+        user_columns::draw(ctx, self);
+        compare_teams::draw(ctx, self);
+        trend_chart::draw(ctx, self);
+        settings::draw(ctx, self);
+        new_season::draw(ctx, self);
     }
 }
@@ -0,0 +1,107 @@
+// src/gui/components/about.rs
+//
+// About window: app and parser version, which build scraped each cached
+// page, and the latest changelog entries (CHANGELOG.md, built in).
+// When someone reports a misparse, this is what to ask them for; "Copy
+// diagnostic info" gathers it (see `diagnostics`).
+
+use eframe::egui;
+use crate::{
+    config::consts::PARSER_VERSION,
+    gui::app::App,
+    store::{CacheMeta, CACHED_PAGES},
+};
+
+const CHANGELOG: &str = include_str!("../../../CHANGELOG.md");
+
+/// Releases shown (plus "Unreleased" when present)
+const RELEASES: usize = 2;
+
+pub fn draw(ctx: &egui::Context, app: &mut App) {
+    if !app.show_about { return; }
+
+    let mut open = true;
+    egui::Window::new("About")
+        .id(egui::Id::new("about"))
+        .open(&mut open)
+        .default_width(460.0)
+        .show(ctx, |ui| {
+            ui.heading(format!("bb_scrape {}", env!("CARGO_PKG_VERSION")));
+            ui.label(format!("Parser version {}", PARSER_VERSION));
+            ui.horizontal(|ui| {
+                ui.hyperlink(env!("CARGO_PKG_REPOSITORY"));
+                if ui.button("Copy diagnostic info")
+                    .on_hover_text("Versions, settings (no secrets), cache and the last log lines, to paste into an issue")
+                    .clicked()
+                {
+                    ui.ctx().copy_text(crate::diagnostics::report());
+                    logf!("UI: Copied diagnostic info");
+                    app.status("Diagnostic info copied");
+                }
+            });
+
+            ui.separator();
+            ui.strong("Cached data");
+            egui::Grid::new("about_cache").striped(true).show(ui, |ui| {
+                for kind in CACHED_PAGES {
+                    if crate::store::saved_at(&kind).is_none() { continue; }
+                    ui.label(kind.to_string());
+                    match CacheMeta::load(&kind) {
+                        Some(m) if m.is_outdated() => {
+                            ui.colored_label(ui.visuals().warn_fg_color,
+                                format!("v{}, parser {} — re-scrape for parser fixes", m.app_version, m.parser_version));
+                        }
+                        Some(m) => { ui.label(format!("v{}, parser {}", m.app_version, m.parser_version)); }
+                        None => { ui.weak("unknown (scraped before versions were recorded)"); }
+                    }
+                    ui.end_row();
+                }
+            });
+
+            ui.separator();
+            ui.strong("Changes");
+            egui::ScrollArea::vertical()
+                .id_salt("about_changelog")
+                .max_height(320.0)
+                .show(ui, |ui| {
+                    for line in recent_changelog(CHANGELOG, RELEASES) {
+                        changelog_line(ui, line);
+                    }
+                });
+        });
+
+    if !open {
+        app.show_about = false;
+    }
+}
+
+/// Lines of the changelog from the first `## ` section through `releases`
+/// versioned sections ("Unreleased" doesn't count).
+fn recent_changelog(text: &str, releases: usize) -> impl Iterator<Item = &str> {
+    let mut versions = 0;
+    text.lines()
+        .skip_while(|l| !l.starts_with("## "))
+        .take_while(move |l| {
+            if l.starts_with("## ") && !l.contains("Unreleased") {
+                versions += 1;
+            }
+            versions <= releases
+        })
+        .filter(|l| !l.trim().is_empty())
+}
+
+fn changelog_line(ui: &mut egui::Ui, line: &str) {
+    if let Some(h) = line.strip_prefix("## ") {
+        ui.add_space(6.0);
+        ui.strong(h.replace(['[', ']'], ""));
+    } else if let Some(h) = line.strip_prefix("### ") {
+        ui.label(egui::RichText::new(h).italics());
+    } else {
+        let indent = line.len() - line.trim_start().len();
+        let text = line.trim_start().trim_start_matches("- ").replace("**", "").replace('`', "");
+        ui.horizontal_wrapped(|ui| {
+            ui.add_space(8.0 + indent as f32 * 4.0);
+            ui.label(format!("• {}", text));
+        });
+    }
+}
@@ -1,16 +1,28 @@
 // src/gui/components/action_buttons.rs
 
-use eframe::egui::{self, Checkbox, widgets::Spinner};
+use eframe::egui::{self, Checkbox, ProgressBar, widgets::Spinner};
 use crate::{
-    gui::app::App,
+    gui::{app::App, router, shortcuts::Shortcut},
     config::options::{
+        Backup,
         ExportFormat,
+        ExportOptions,
         ExportType::{PerTeam, SingleFile},
+        FormulaEscape,
+        NumberFormat,
+        PageExportDefaults,
+        PageKind,
     },
 };
 
+/// Number formats offered, as (decimal, thousands) separators
+const NUMBER_FORMATS: [(char, Option<char>); 6] = [
+    ('.', None), ('.', Some(',')), ('.', Some('\'')),
+    (',', None), (',', Some('.')), (',', Some(' ')),
+];
+
 #[derive(Clone, Copy, PartialEq, Eq)]
-enum UiFormat { Csv, Tsv }
+enum UiFormat { Csv, Tsv, Markdown, BbCode }
 
 pub fn draw(ui: &mut egui::Ui, app: &mut App) {
 
@@ -24,6 +36,8 @@ pub fn draw(ui: &mut egui::Ui, app: &mut App) {
         let prev_fmt = match export.format {
             ExportFormat::Csv => UiFormat::Csv,
             ExportFormat::Tsv => UiFormat::Tsv,
+            ExportFormat::Markdown => UiFormat::Markdown,
+            ExportFormat::BbCode => UiFormat::BbCode,
         };
         let mut fmt = prev_fmt;
 
@@ -31,12 +45,18 @@ pub fn draw(ui: &mut egui::Ui, app: &mut App) {
             ui.label("Format:");
             ui.selectable_value(&mut fmt, UiFormat::Tsv, "TSV");
             ui.selectable_value(&mut fmt, UiFormat::Csv, "CSV");
+            ui.selectable_value(&mut fmt, UiFormat::Markdown, "Markdown")
+                .on_hover_text("Padded pipe table (Discord, GitHub)");
+            ui.selectable_value(&mut fmt, UiFormat::BbCode, "BBCode")
+                .on_hover_text("[table] markup (forums)");
         });
 
         if fmt != prev_fmt {
             export.format = match fmt {
                 UiFormat::Csv => ExportFormat::Csv,
                 UiFormat::Tsv => ExportFormat::Tsv,
+                UiFormat::Markdown => ExportFormat::Markdown,
+                UiFormat::BbCode => ExportFormat::BbCode,
             };
             logf!("UI: Export format → {:?}", export.format);
 
@@ -48,15 +68,151 @@ pub fn draw(ui: &mut egui::Ui, app: &mut App) {
             }
         }
 
+        // Own separator for CSV/TSV (e.g. ; or |); rejected input is dropped
+        if !export.format.is_markup() {
+            ui.horizontal(|ui| {
+                ui.label("Delimiter:");
+                let mut text = export.custom_delimiter.map(String::from).unwrap_or_default();
+                let hint = if export.format == ExportFormat::Tsv { "tab" } else { "," };
+                let resp = ui.add(egui::TextEdit::singleline(&mut text)
+                    .char_limit(1)
+                    .desired_width(24.0)
+                    .hint_text(hint))
+                    .on_hover_text("A single character instead of the format's own, e.g. ; or |\nEmpty: the format's default");
+                if resp.changed() {
+                    let parsed = if text.is_empty() { Ok(None) } else { ExportOptions::parse_delimiter(&text).map(Some) };
+                    match parsed {
+                        Ok(d) => {
+                            export.custom_delimiter = d;
+                            logf!("UI: Delimiter → {:?}", d);
+                        }
+                        Err(e) => logf!("UI: Delimiter not changed: {}", e),
+                    }
+                }
+            });
+        }
+
         let before_headers = export.include_headers;
         ui.checkbox(&mut export.include_headers, "Include headers");
         if export.include_headers != before_headers {
             logf!("UI: Include_headers → {}", export.include_headers);
         }
+
+        // This page's own format, headers and DIR (used whenever it's open)
+        let current = export.current_page_defaults();
+        let own = export.page_defaults.get(&cur_kind).cloned();
+        let (text, hover) = match &own {
+            None => (format!("Keep for {}", page.title()), format!("Use this format, headers setting and folder whenever {} is open", page.title())),
+            Some(d) if *d == current => (format!("★ {} defaults", page.title()), s!("Click to forget them and use the shared settings on this page")),
+            Some(_) => (format!("Update {} defaults", page.title()), s!("Keep the current format, headers setting and folder for this page")),
+        };
+        if ui.small_button(text).on_hover_text(hover).clicked() {
+            let keep = if own.as_ref() == Some(&current) { PageExportDefaults::default() } else { current };
+            if keep.is_empty() {
+                export.page_defaults.remove(&cur_kind);
+            } else {
+                export.page_defaults.insert(cur_kind, keep.clone());
+            }
+            logf!("UI: Export defaults for {:?} → {:?}", cur_kind, keep);
+            if let Err(e) = crate::store::save_page_export_defaults(&cur_kind, &keep) {
+                loge!("Settings: Could not save export defaults: {}", e);
+            }
+        }
+
+        if ui.checkbox(&mut export.write_manifest, "Manifest")
+            .on_hover_text("Also write manifest.json with file list, row counts and checksums")
+            .changed()
+        {
+            logf!("UI: Write_manifest → {}", export.write_manifest);
+        }
+
+        if ui.checkbox(&mut export.as_shown, "As shown")
+            .on_hover_text("Copy/Export with the table's column order; hidden columns are left out")
+            .changed()
+        {
+            logf!("UI: As_shown → {}", export.as_shown);
+        }
+
+        if ui.checkbox(&mut export.by_division, "By division")
+            .on_hover_text("Per-team: one folder per division. Single file: add a Division column.\nNeeds divisions in the team list.")
+            .changed()
+        {
+            logf!("UI: By_division → {}", export.by_division);
+        }
+
+        if ui.checkbox(&mut export.zip, "Zip")
+            .on_hover_text("Per-team and bundle exports: one .zip next to the folder instead of loose files")
+            .changed()
+        {
+            logf!("UI: Zip → {}", export.zip);
+        }
+
+        let mut backup = export.backup != Backup::Off;
+        if ui.checkbox(&mut backup, "Backup")
+            .on_hover_text("Keep the file an export replaces as <name>.bak")
+            .changed()
+        {
+            export.backup = if backup { Backup::Bak } else { Backup::Off };
+            logf!("UI: Backup → {:?}", export.backup);
+        }
+
+        // Decimal/thousands separators of numeric columns
+        let sample = |f: NumberFormat| f.apply("1234.5").unwrap_or_default();
+        let before = export.number_format;
+        egui::ComboBox::from_id_salt("number_format")
+            .selected_text(format!("Numbers: {}", sample(export.number_format)))
+            .show_ui(ui, |ui| {
+                for (decimal, thousands) in NUMBER_FORMATS {
+                    let f = NumberFormat { decimal, thousands };
+                    ui.selectable_value(&mut export.number_format, f, sample(f));
+                }
+            })
+            .response
+            .on_hover_text("Decimal and thousands separators in numeric columns (e.g. 1.234,5 for comma-decimal Excel)");
+        if export.number_format != before {
+            logf!("UI: Number_format → {:?}", export.number_format);
+        }
+
+        let label = |f: FormulaEscape| match f {
+            FormulaEscape::Off => "Formulas: as is",
+            FormulaEscape::Clipboard => "Formulas: escape in copies",
+            FormulaEscape::Always => "Formulas: escape everywhere",
+        };
+        let before = export.formula_escape;
+        egui::ComboBox::from_id_salt("formula_escape")
+            .selected_text(label(export.formula_escape))
+            .show_ui(ui, |ui| {
+                for f in [FormulaEscape::Off, FormulaEscape::Clipboard, FormulaEscape::Always] {
+                    ui.selectable_value(&mut export.formula_escape, f, label(f));
+                }
+            })
+            .response
+            .on_hover_text("Cells starting with = + - or @ (not numbers) get a leading ' so spreadsheets such as Google Sheets show them as text instead of running them as formulas");
+        if export.formula_escape != before {
+            logf!("UI: Formula_escape → {:?}", export.formula_escape);
+        }
     }
 
+    // --- Selected rows only (table click / Ctrl+click / Shift+click) ---
+    let picked = app.state.gui.row_selection.get(&cur_kind).map_or(0, |s| s.len());
+    ui.horizontal(|ui| {
+        let gui = &mut app.state.gui;
+        if ui.add_enabled(picked > 0, Checkbox::new(&mut gui.selected_rows_only, format!("Selected rows only ({picked})")))
+            .on_hover_text("Copy/Export only the rows picked in the table (click, Ctrl+click, Shift+click)")
+            .changed()
+        {
+            logf!("UI: Selected_rows_only → {}", gui.selected_rows_only);
+        }
+        if ui.add_enabled(picked > 0, egui::Button::new("Clear selection")).clicked() {
+            gui.row_selection.remove(&cur_kind);
+            logd!("UI: Row selection cleared ({:?})", cur_kind);
+        }
+    });
+
     // Page-specific controls
-    let _changed = page.draw_controls(ui, &mut app.state);
+    if page.draw_controls(ui, &mut app.state) {
+        app.rebuild_view();
+    }
     // Needs re-binding because of mut/borrow conflict from the line above
     let export = &mut app.state.options.export;
 
@@ -84,6 +240,15 @@ pub fn draw(ui: &mut egui::Ui, app: &mut App) {
             export.export_type = SingleFile;
         }
 
+        // Single CSV/TSV file: add new rows to it instead of replacing it
+        let can_append = matches!(export.export_type, SingleFile) && !export.format.is_markup();
+        if ui.add_enabled(can_append, Checkbox::new(&mut export.append, "Append"))
+            .on_hover_text("Add only the rows the file doesn't have yet (matched by the page's key columns, else whole rows).\nThe file must have the same columns.")
+            .changed()
+        {
+            logf!("UI: Append → {}", export.append);
+        }
+
         ui.label("Output:");
         if ui
             .add(egui::TextEdit::singleline(&mut app.out_path_text)
@@ -105,43 +270,254 @@ pub fn draw(ui: &mut egui::Ui, app: &mut App) {
         open_output_folder(app);
     }
 
+    // --- Per-team: row order within each file ---
+    if per_team_applicable && matches!(app.state.options.export.export_type, PerTeam) {
+        let headers = app.headers.clone().unwrap_or_default();
+        let export = &mut app.state.options.export;
+        let before = (export.sort_by.clone(), export.sort_descending, export.chronological);
+        ui.horizontal(|ui| {
+            ui.label("Sort files by:");
+            egui::ComboBox::from_id_salt("per_team_sort_by")
+                .selected_text(export.sort_by.as_deref().unwrap_or("scrape order"))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut export.sort_by, None, "scrape order");
+                    for h in &headers {
+                        ui.selectable_value(&mut export.sort_by, Some(h.clone()), h);
+                    }
+                });
+            ui.add_enabled(export.sort_by.is_some(), Checkbox::new(&mut export.sort_descending, "Descending"));
+            if headers.iter().any(|h| h == "W") {
+                ui.checkbox(&mut export.chronological, "By week")
+                    .on_hover_text("Each team's rows in season/week order; the sort applies within a week");
+            }
+        });
+        if (export.sort_by.clone(), export.sort_descending, export.chronological) != before {
+            logf!("UI: Per-team order → sort_by={:?} desc={} chronological={}",
+                export.sort_by, export.sort_descending, export.chronological);
+        }
+    }
+
+    // Game Results: print view of one week (HTML, opened in the browser)
+    if matches!(cur_kind, crate::config::options::PageKind::GameResults) {
+        let mut print_clicked = false;
+        ui.horizontal(|ui| {
+            ui.label("Print week:");
+            ui.add(egui::DragValue::new(&mut app.state.gui.print_week).range(1..=99));
+            print_clicked = ui.button("🖶 Print view")
+                .on_hover_text("Fixtures and results of this week as a printable page (Print → Save as PDF for a PDF)")
+                .clicked();
+        });
+        if print_clicked {
+            if let Some(path) = crate::gui::actions::export::print_week(app) {
+                if let Err(e) = open_folder_in_explorer(&path) {
+                    loge!("Failed to open print view: {}", e);
+                }
+            }
+        }
+    }
+
+    // Refresh current page on focus when its cache is stale
+    ui.horizontal(|ui| {
+        let gui = &mut app.state.gui;
+        if ui.checkbox(&mut gui.refresh_on_focus, "Refresh on focus if older than")
+            .on_hover_text("Re-scrape this page when the window regains focus and the cache is stale")
+            .changed()
+        {
+            logf!("UI: Refresh_on_focus → {}", gui.refresh_on_focus);
+        }
+        ui.add_enabled(
+            gui.refresh_on_focus,
+            egui::DragValue::new(&mut gui.refresh_after_min).range(5..=1440).suffix(" min"));
+    });
+
     // Actions: Copy / Export / Scrape
     use crate::gui::actions;
     ui.horizontal(|ui| {
 
         // Copy
-        let button_copy = ui.button("Copy");
+        let button_copy = ui.button("Copy")
+            .on_hover_text(Shortcut::Copy.hint(ui.ctx(), "Copy the table view"));
         if button_copy.clicked() {
             actions::copy(app, ui.ctx());
         }
+        ui.menu_button("Copy as…", |ui| {
+            if ui.button("Markdown table").on_hover_text("For Discord").clicked() {
+                actions::copy::copy_as(app, ui.ctx(), ExportFormat::Markdown);
+            }
+            if ui.button("BBCode table").on_hover_text("For the league forum").clicked() {
+                actions::copy::copy_as(app, ui.ctx(), ExportFormat::BbCode);
+            }
+        });
+
+        // Hand-edited rows back into the cache
+        if ui.button("Import…")
+            .on_hover_text("Merge CSV/TSV rows from the clipboard or a file into this page's data")
+            .clicked()
+        {
+            actions::import::open(app);
+        }
+
+        // Victim names that match no player
+        if matches!(cur_kind, PageKind::Injuries | PageKind::InjuryVictims)
+            && ui.button("Resolve names…")
+                .on_hover_text("Alias victim names that match no player to the right player")
+                .clicked()
+        {
+            actions::aliases::open(app);
+        }
+
+        // Names the exported files use for this page's headers
+        if ui.button("Header names…")
+            .on_hover_text("Rename this page's headers in exported files (the cache keeps the originals)")
+            .clicked()
+        {
+            actions::header_names::open(app);
+        }
+
+        // Formula columns of this page
+        if ui.button("User columns…")
+            .on_hover_text("Add columns computed from this page's others (e.g. Kills / Games), shown and exported")
+            .clicked()
+        {
+            actions::user_columns::open(app);
+        }
+
+        // Two teams' rosters side by side (Players data)
+        if matches!(cur_kind, PageKind::Players | PageKind::TeamSummary)
+            && ui.button("Compare…")
+                .on_hover_text("Compare two teams: rosters side by side with average stats and the difference")
+                .clicked()
+        {
+            actions::compare::open(app);
+        }
+
+        // A player's stat over the Players snapshots
+        if cur_kind == PageKind::Players
+            && ui.button("Trend…")
+                .on_hover_text("Chart a player's stat over the Players snapshots (select a row to start with that player)")
+                .clicked()
+        {
+            actions::trend::open(app);
+        }
 
         // Export
-        let button_export = ui.button("Export");
+        let button_export = ui.button("Export")
+            .on_hover_text(Shortcut::Export.hint(ui.ctx(), "Export the table view"));
         if button_export.clicked() {
             actions::export(app);
         }
+        if ui.button("Export bundle")
+            .on_hover_text("Every cached page into out/bundle/, one folder per page")
+            .clicked()
+        {
+            actions::export::export_bundle(app);
+        }
+
+        // Retry only the per-team files that failed last time
+        let retry_n = app.failed_export.as_ref().map(|f| f.team_ids.len());
+        if let Some(n) = retry_n {
+            if ui.button("Retry failed")
+                .on_hover_text(format!("Write the {} file(s) that failed again", n))
+                .clicked()
+            {
+                actions::export::retry_failed(app);
+            }
+        }
 
         // Scrape
         let red = egui::Color32::from_rgb(220, 30, 30);
         let black = egui::Color32::BLACK;
 
         let button_scrape = ui.add_enabled(
-            !app.running,
+            !app.running(),
             egui::Button::new(
                 egui::RichText::new("SCRAPE")
                 .color(black)
                 .strong())
-            .fill(red));
+            .fill(red))
+            .on_hover_text(Shortcut::Scrape.hint(ui.ctx(), "Scrape this page for the selected teams"));
 
         if button_scrape.clicked() { 
             actions::scrape(app); 
         }
 
-        if app.running {
-            ui.add(Spinner::new().size(16.0));
+        // Suggest a re-scrape once this page's data is stale
+        let stale_age = app.scraped_at.get(&app.current_page_kind())
+            .filter(|at| !app.running() && crate::store::is_stale(**at, app.state.gui.stale_after_hours))
+            .and_then(|at| std::time::SystemTime::now().duration_since(*at).ok());
+        if let Some(age) = stale_age {
+            ui.colored_label(ui.visuals().warn_fg_color, format!("Scraped {} — re-scrape?", crate::store::fmt_age(age)));
+        }
+
+        // Re-fetch only the teams that failed in the last scrape of this page
+        let kind = app.current_page_kind();
+        let failed_n = app.failed_scrape.as_ref()
+            .filter(|(k, _)| *k == kind)
+            .map(|(_, ids)| ids.len());
+        if let Some(n) = failed_n {
+            if ui.add_enabled(!app.running(), egui::Button::new(format!("Retry failed teams ({})", n)))
+                .on_hover_text("Scrape just the teams that failed last time and merge them in")
+                .clicked()
+            {
+                actions::scrape::retry_failed(app);
+            }
+        }
+
+        // Swap the cache back to before the last scrape of this page
+        if app.undoable.contains(&kind)
+            && ui.add_enabled(!app.running(), egui::Button::new("Undo last scrape"))
+                .on_hover_text("Go back to this page's data from before the last scrape (again to redo)")
+                .clicked()
+        {
+            actions::scrape::undo_last(app);
         }
 
-        let status = app.status.lock().unwrap().clone();
+        // Progress of this page's scrape: a bar once the scraper knows its
+        // item count (spinner until then), with Cancel; the last scrape's
+        // tally stays on its page
+        let job = app.scrapes.get(&kind);
+        let counts = job.map(|j| *j.counts.lock().unwrap())
+            .or_else(|| app.last_counts.get(&kind).copied())
+            .unwrap_or_default();
+        let failed_fill = ui.visuals().error_fg_color.gamma_multiply(0.6);
+        let bar = |fraction: f32| {
+            let bar = ProgressBar::new(fraction).desired_width(180.0).text(counts.label());
+            if counts.failed > 0 { bar.fill(failed_fill) } else { bar }
+        };
+        let mut cancel = false;
+        if let Some(job) = job {
+            match counts.fraction() {
+                Some(f) => { ui.add(bar(f)); }
+                None => { ui.add(Spinner::new().size(16.0)); }
+            }
+            cancel = ui.add_enabled(!job.cancelled(), egui::Button::new("Cancel"))
+                .on_hover_text("Stop this scrape and keep the page's data as it was")
+                .clicked();
+            if job.auto {
+                ui.weak("refreshing…");
+            }
+        } else if counts.finished && counts.total > 0 {
+            ui.add(bar(counts.done as f32 / counts.total as f32))
+                .on_hover_text("Items fetched in the last scrape of this page");
+        }
+
+        // Scrapes of other pages go on in the background
+        let others: Vec<&str> = router::all_pages().iter()
+            .filter(|p| p.kind() != kind && app.scrapes.contains_key(&p.kind()))
+            .map(|p| p.title())
+            .collect();
+        if !others.is_empty() {
+            ui.weak(format!("Also scraping: {}", others.join(", ")));
+        }
+
+        // This page's scrape reports in its own line
+        let status = match job {
+            Some(job) => job.status.lock().unwrap().clone(),
+            None => app.status.lock().unwrap().clone(),
+        };
+        if cancel {
+            actions::scrape::cancel(app, kind);
+        }
 
         ui.label(status);
     });
@@ -217,7 +593,7 @@ fn find_nearest_existing_parent(path: &std::path::Path) -> std::path::PathBuf {
 }
 
 /// Cross-platform function to open a folder in the system file explorer.
-fn open_folder_in_explorer(path: &std::path::Path) -> Result<(), String> {
+pub(crate) fn open_folder_in_explorer(path: &std::path::Path) -> Result<(), String> {
     #[cfg(target_os = "windows")]
     {
         std::process::Command::new("explorer")
@@ -0,0 +1,75 @@
+// src/gui/components/alias_editor.rs
+//
+// "Resolve names" window: injury victims whose name matches no player on
+// their team, each with a pick of that team's players to alias it to, and
+// the saved aliases (✖ removes one).
+
+use eframe::egui;
+use crate::gui::{actions::aliases, app::App};
+
+pub fn draw(ctx: &egui::Context, app: &mut App) {
+    let Some(editor) = app.alias_editor.as_mut() else { return };
+
+    let mut open = true;
+    // (variant, canonical); empty canonical removes the alias
+    let mut save: Option<(String, String)> = None;
+    egui::Window::new("Resolve names")
+        .id(egui::Id::new("alias_editor"))
+        .open(&mut open)
+        .default_width(460.0)
+        .show(ctx, |ui| {
+            ui.label("Victims that match no player on their team. Pick the player they are; \
+                the alias is used for every later scrape, merge and join.");
+            ui.separator();
+
+            if editor.unmatched.is_empty() {
+                ui.weak("Every victim matches a player.");
+            }
+            egui::ScrollArea::vertical()
+                .id_salt("alias_unmatched_scroll")
+                .max_height(240.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("alias_unmatched").striped(true).show(ui, |ui| {
+                        for (i, (team, name)) in editor.unmatched.iter().enumerate() {
+                            ui.label(name);
+                            ui.weak(team);
+                            let roster = editor.rosters.get(team).map(Vec::as_slice).unwrap_or(&[]);
+                            let pick = &mut editor.picks[i];
+                            egui::ComboBox::from_id_salt(("alias_pick", i))
+                                .selected_text(if pick.is_empty() { "Player…" } else { pick.as_str() })
+                                .show_ui(ui, |ui| {
+                                    for player in roster {
+                                        ui.selectable_value(pick, player.clone(), player);
+                                    }
+                                });
+                            if ui.add_enabled(!pick.is_empty(), egui::Button::new("Resolve")).clicked() {
+                                save = Some((name.clone(), pick.clone()));
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+
+            if !editor.aliases.is_empty() {
+                ui.separator();
+                ui.strong("Aliases");
+                egui::Grid::new("alias_saved").striped(true).show(ui, |ui| {
+                    for (variant, canonical) in editor.aliases.iter() {
+                        ui.label(variant);
+                        ui.label(format!("→ {}", canonical));
+                        if ui.small_button("✖").on_hover_text("Remove this alias").clicked() {
+                            save = Some((variant.to_string(), String::new()));
+                        }
+                        ui.end_row();
+                    }
+                });
+            }
+        });
+
+    if let Some((variant, canonical)) = save {
+        aliases::save(app, &variant, &canonical);
+    }
+    if !open {
+        app.alias_editor = None;
+    }
+}
@@ -0,0 +1,41 @@
+// src/gui/components/changes_panel.rs
+//
+// Collapsible "What changed" report for the last scrape of the current page.
+
+use eframe::egui::{self, Color32, RichText};
+use crate::gui::app::App;
+
+pub fn draw(ui: &mut egui::Ui, app: &mut App) {
+    let kind = app.current_page_kind();
+    let Some((diff_kind, diff)) = app.last_diff.as_ref() else { return; };
+    if *diff_kind != kind { return; }
+
+    let mut dismiss = false;
+    ui.horizontal(|ui| {
+        egui::CollapsingHeader::new(format!("What changed — {}", diff.summary()))
+            .id_salt(("what_changed", kind))
+            .default_open(false)
+            .show(ui, |ui| {
+                egui::ScrollArea::vertical()
+                    .id_salt("what_changed_scroll")
+                    .max_height(160.0)
+                    .show(ui, |ui| {
+                        for line in diff.report_lines() {
+                            let rt = RichText::new(&line).monospace();
+                            let rt = match line.chars().next() {
+                                Some('+') => rt.color(Color32::from_rgb(0x6A, 0xC8, 0x6A)),
+                                Some('-') => rt.color(Color32::from_rgb(0xDC, 0x61, 0x49)),
+                                _ => rt,
+                            };
+                            ui.label(rt);
+                        }
+                    });
+            });
+        dismiss = ui.small_button("✖").on_hover_text("Dismiss").clicked();
+    });
+
+    if dismiss {
+        app.last_diff = None;
+    }
+    ui.separator();
+}
@@ -0,0 +1,114 @@
+// src/gui/components/compare_teams.rs
+//
+// "Compare teams" window: pick two teams, see each team's average per stat
+// and the difference, then both rosters side by side. Built from the
+// cached Players data (see `actions::compare`).
+
+use eframe::egui;
+use crate::{compare::RosterComparison, gui::{actions::compare, app::App}};
+
+pub fn draw(ctx: &egui::Context, app: &mut App) {
+    if app.compare.is_none() { return; }
+    let names: Vec<String> = app.teams.iter().map(|(_, n)| n.clone()).collect();
+    let cmp = compare::comparison(app);
+    let Some(window) = app.compare.as_mut() else { return };
+
+    let mut open = true;
+    let (mut copy, mut export) = (false, false);
+    egui::Window::new("Compare teams")
+        .id(egui::Id::new("compare_teams"))
+        .open(&mut open)
+        .default_width(640.0)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                for (i, team) in window.teams.iter_mut().enumerate() {
+                    if i == 1 { ui.label("vs"); }
+                    egui::ComboBox::from_id_salt(("compare_team", i))
+                        .selected_text(team.as_str())
+                        .show_ui(ui, |ui| {
+                            for n in &names {
+                                ui.selectable_value(team, n.clone(), n);
+                            }
+                        });
+                }
+                if ui.small_button("⇄").on_hover_text("Swap the teams").clicked() {
+                    window.teams.swap(0, 1);
+                }
+            });
+            ui.separator();
+
+            let Some(cmp) = cmp.as_ref() else {
+                ui.label("No Players data — scrape Players first.");
+                return;
+            };
+            draw_averages(ui, cmp);
+            ui.separator();
+            ui.columns(2, |cols| {
+                for (i, col) in cols.iter_mut().enumerate() {
+                    draw_roster(col, cmp, i);
+                }
+            });
+            ui.separator();
+            ui.horizontal(|ui| {
+                copy = ui.button("Copy").on_hover_text("Copy the comparison in the export format").clicked();
+                export = ui.button("Export")
+                    .on_hover_text("Write both rosters, the averages and the difference next to the Players exports")
+                    .clicked();
+            });
+        });
+
+    if copy { compare::copy(app, ctx); }
+    if export { compare::export(app); }
+    if !open {
+        app.compare = None;
+    }
+}
+
+/// Stat | first team | second team | Δ (first − second).
+fn draw_averages(ui: &mut egui::Ui, cmp: &RosterComparison) {
+    let cell = |x: Option<f64>| x.map(|x| format!("{:.2}", x)).unwrap_or_default();
+    let deltas = cmp.deltas();
+    egui::ScrollArea::vertical()
+        .id_salt("compare_averages_scroll")
+        .max_height(200.0)
+        .show(ui, |ui| {
+            egui::Grid::new("compare_averages").striped(true).show(ui, |ui| {
+                ui.strong("Average");
+                ui.strong(cmp.teams[0].as_str());
+                ui.strong(cmp.teams[1].as_str());
+                ui.strong("Δ");
+                ui.end_row();
+                for (si, stat) in cmp.stats.iter().enumerate() {
+                    ui.label(stat.as_str());
+                    ui.label(cell(cmp.averages[0][si]));
+                    ui.label(cell(cmp.averages[1][si]));
+                    match deltas[si] {
+                        Some(d) if d > 0.0 => ui.label(format!("+{:.2}", d)),
+                        Some(d) if d < 0.0 => ui.label(format!("{:.2}", d)),
+                        d => ui.weak(cell(d)),
+                    };
+                    ui.end_row();
+                }
+            });
+        });
+}
+
+/// Team `side`'s players with their stats.
+fn draw_roster(ui: &mut egui::Ui, cmp: &RosterComparison, side: usize) {
+    ui.strong(format!("{} ({} players)", cmp.teams[side], cmp.rosters[side].len()));
+    egui::ScrollArea::both()
+        .id_salt(("compare_roster_scroll", side))
+        .max_height(280.0)
+        .show(ui, |ui| {
+            egui::Grid::new(("compare_roster", side)).striped(true).show(ui, |ui| {
+                ui.strong("Name");
+                for stat in &cmp.stats { ui.strong(stat.as_str()); }
+                ui.end_row();
+                for (name, cells) in &cmp.rosters[side] {
+                    ui.label(name.as_str());
+                    for c in cells { ui.label(c.as_str()); }
+                    ui.end_row();
+                }
+            });
+        });
+}
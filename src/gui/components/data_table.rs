@@ -5,7 +5,9 @@
 
 use eframe::egui::{self, Align, Layout, RichText, TextWrapMode, Sense, CursorIcon, Pos2, Vec2, Stroke, StrokeKind};
 use egui_extras::{Column, TableBuilder};
+use crate::data::SortKey;
 use crate::gui::app::App;
+use super::row_menu::{self, RowAction};
 
 pub fn draw(ui: &mut egui::Ui, app: &mut App) {
     let page = app.current_page();
@@ -37,32 +39,33 @@ pub fn draw(ui: &mut egui::Ui, app: &mut App) {
         .clone();
     if ord_local.len() != cols { ord_local = (0..cols).collect(); }
 
+    // Hidden columns (page toggles, Columns menu) stay in the order but aren't drawn
+    let hidden = app.hidden_columns_for(kind, app.headers.as_deref());
+    let mut visible: Vec<usize> = ord_local.iter().copied().filter(|c| !hidden.contains(c)).collect();
+
     // Keep columns fixed during drag; reorder only on drop
     // (order will be used and possibly updated inside inner_table)
 
-    // Column widths following source columns across reorders
-    let widths_entry = app.col_widths.entry(kind).or_insert_with(|| {
-        if let Some(ws) = page.preferred_column_widths() {
-            ws.iter().map(|&w| w as f32).collect()
-        } else {
-            let mut v = Vec::with_capacity(cols);
-            if cols >= 1 { v.push(180.0); }
-            if cols >= 2 { v.push(30.0); }
-            if cols >= 3 { v.push(140.0); }
-            if cols >= 4 { v.push(160.0); }
-            for _ in v.len()..cols { v.push(30.0); }
-            v
+    // Column widths following source columns across reorders.
+    // First sized from a sample of the data; until there is data, the page's
+    // preferred widths (or rough guesses) are used without being kept.
+    if !app.col_widths.contains_key(&kind) {
+        if let Some(ds) = app.raw_data.get(&kind).map(|r| r.dataset()).filter(|ds| !ds.rows.is_empty()) {
+            let ws = crate::data::sample_column_widths(app.headers.as_deref(), &ds.rows, cols);
+            app.col_widths.insert(kind, ws);
         }
-    });
-    if widths_entry.len() != cols {
-        let new = if let Some(ws) = page.preferred_column_widths() {
-            ws.iter().map(|&w| w as f32).collect::<Vec<f32>>()
-        } else {
-            vec![80.0; cols]
-        };
-        *widths_entry = new;
     }
-    let per_source_widths = widths_entry.clone();
+    let per_source_widths = match app.col_widths.get_mut(&kind) {
+        Some(ws) => {
+            if ws.len() != cols {
+                let ds = app.raw_data.get(&kind).map(|r| r.dataset());
+                *ws = crate::data::sample_column_widths(
+                    app.headers.as_deref(), ds.map_or(&[][..], |d| &d.rows), cols);
+            }
+            ws.clone()
+        }
+        None => fallback_widths(page, cols),
+    };
 
     // Ensure scroll bars allocate space (not floating over content), and tune size
     {
@@ -86,12 +89,29 @@ pub fn draw(ui: &mut egui::Ui, app: &mut App) {
         .min_scrolled_height(avail_h)
         .max_height(avail_h)
         .show(ui, |ui| {
-            inner_table(ui, app, &ctx, page, kind, &mut ord_local, per_source_widths.clone(), cols, false);
+            let shown = visible.len();
+            inner_table(ui, app, &ctx, page, kind, &mut visible, per_source_widths.clone(), shown, false);
         });
-    app.col_order.insert(kind, ord_local);
+    // Re-append hidden columns after any reorder of the visible ones
+    visible.extend(ord_local.iter().copied().filter(|c| hidden.contains(c)));
+    app.col_order.insert(kind, visible);
     return;
 }
 
+/// Widths before any data is loaded: page preference, else rough guesses.
+fn fallback_widths(page: &dyn crate::gui::pages::Page, cols: usize) -> Vec<f32> {
+    if let Some(ws) = page.preferred_column_widths() {
+        return ws.iter().map(|&w| w as f32).collect();
+    }
+    let mut v = Vec::with_capacity(cols);
+    if cols >= 1 { v.push(180.0); }
+    if cols >= 2 { v.push(30.0); }
+    if cols >= 3 { v.push(140.0); }
+    if cols >= 4 { v.push(160.0); }
+    for _ in v.len()..cols { v.push(30.0); }
+    v
+}
+
 fn inner_table(
     ui: &mut egui::Ui,
     app: &mut App,
@@ -110,7 +130,8 @@ fn inner_table(
         .min_scrolled_height(0.0)
         // Reset egui_extras table state when column order changes so
         // widths come from our per-source cache instead of staying with positions.
-        .id_salt(("table_state", kind, &*ord));
+        .id_salt(("table_state", kind, &*ord))
+        .sense(Sense::click());
     if outer_scroll { table = table.vscroll(false); }
     for (_disp_ix, &src_ci) in display_ord.iter().enumerate() {
         let w = per_source_widths.get(src_ci).copied().unwrap_or(80.0);
@@ -134,8 +155,24 @@ fn inner_table(
         .map(|ci| !non_numeric.contains(&ci))
         .collect();
 
+    // Players: a badge on the names of players out injured (Out until filled)
+    let out_col = match kind {
+        crate::config::options::PageKind::Players => app.headers.as_deref()
+            .and_then(|hs| hs.iter().position(|h| h == crate::schema::PLAYERS_DERIVED[0].name)),
+        _ => None,
+    };
+
+    // Header clicks only record the new sort; the view is rebuilt once the
+    // table has finished borrowing the raw rows.
+    let mut sort_changed = false;
+    let mut double_clicked: Option<usize> = None;
+    let mut hide_col: Option<String> = None;
+    let mut row_action: Option<RowAction> = None;
+    // (display row, ctrl, shift) of a click that changes the row selection
+    let mut row_click: Option<(usize, bool, bool)> = None;
+
     table
-        .header(24.0, |mut header| {
+        .header(app.state.gui.appearance.row_height() + 4.0, |mut header| {
             // Keep columns static during drag; draw overlays instead
             let display_ord = ord.clone();
 
@@ -149,9 +186,15 @@ fn inner_table(
                         ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
 
                         // Cursor and label
-                        let label_text = if let Some(hs) = app.headers.as_ref() {
+                        let label_source = if let Some(hs) = app.headers.as_ref() {
                             hs.get(src_ci).cloned().unwrap_or_else(|| format!("Col {}", src_ci + 1))
                         } else { format!("Col {}", src_ci + 1) };
+                        let label_text = match app.col_sort.get(&kind) {
+                            Some(k) if k.col == src_ci => {
+                                format!("{} {}", label_source, if k.descending { "⬇" } else { "⬆" })
+                            }
+                            _ => label_source.clone(),
+                        };
 
                         // alignment
                         let is_numeric = numeric_cols.get(src_ci).copied().unwrap_or(false);
@@ -185,6 +228,21 @@ fn inner_table(
                             ui.output_mut(|o| o.cursor_icon = CursorIcon::Grabbing);
                         }
                         if resp.drag_stopped() { any_drag_stopped = true; }
+                        if resp.clicked() {
+                            match SortKey::cycle(app.col_sort.get(&kind).copied(), src_ci) {
+                                Some(next) => { app.col_sort.insert(kind, next); }
+                                None => { app.col_sort.remove(&kind); }
+                            }
+                            sort_changed = true;
+                        }
+                        // Right-click: hide this column (Columns menu brings it back)
+                        if cols > 1 && app.headers.is_some() {
+                            resp.context_menu(|ui| {
+                                if ui.button("Hide column").clicked() {
+                                    hide_col = Some(label_source.clone());
+                                }
+                            });
+                        }
 
                         // Persist realized width ONLY when not dragging to avoid
                         // dragging-time oscillations and accidental width adoption.
@@ -255,23 +313,38 @@ fn inner_table(
             }
         })
         .body(|body| {
-            body.rows(20.0, app.row_ix.len(), |mut row| {
+            body.rows(app.state.gui.appearance.row_height(), app.row_ix.len(), |mut row| {
                 let row_idx = row.index();
                 if let (Some(raw), Some(&src_ix)) = (raw_opt, app.row_ix.get(row_idx)) {
+                    // Delta view: show the change cells (raw ones for rows it
+                    // doesn't have), but links/menu use the raw row
+                    let delta = app.delta_rows.as_deref().filter(|d| src_ix < d.len());
                     if let Some(data) = raw.rows.get(src_ix) {
                         // Use committed order for body (no live reordering)
                         let display_ord = ord.clone();
+                        row.set_selected(app.state.gui.row_selection.get(&kind).is_some_and(|s| s.contains(src_ix)));
+                        let mut opened_link = false;
 
                         for disp_ix in 0..cols {
                             let ci = display_ord.get(disp_ix).copied().unwrap_or(disp_ix);
-                            let cell_opt = data.get(ci);
-                            row.col(|ui| {
+                            let cell_opt = match delta {
+                                Some(d) => d.cell(src_ix, ci),
+                                None => data.get(ci).map(|c| c.as_str()),
+                            };
+                            // Cells standing for a site page (team, match, player)
+                            let link = cell_opt.filter(|c| !c.is_empty())
+                                .and_then(|_| page.cell_link(app.headers.as_deref(), data, ci, &app.teams));
+                            let (_, cell_resp) = row.col(|ui| {
                                 ui.scope(|ui| {
                                     ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
                                     if let Some(cell) = cell_opt {
-                                        let mut rt = RichText::new(cell);
-                                        // Per-page coloring: Injuries -> Type and Bounty columns
-                        if kind == crate::config::options::PageKind::Injuries {
+                                        let out = out_col.filter(|_| ci == 0).and_then(|oc| data.get(oc)).filter(|w| !w.is_empty());
+                                        let mut rt = match out {
+                                            Some(_) => RichText::new(format!("🚑 {}", cell)).color(egui::Color32::from_rgb(0xDC,0x61,0x49)),
+                                            None => RichText::new(cell),
+                                        };
+                                        // Per-page coloring: Injuries (and Injury Victims) -> Type and Bounty columns
+                        if matches!(kind, crate::config::options::PageKind::Injuries | crate::config::options::PageKind::InjuryVictims) {
                             if ci == 7 { // Type
                                                 let u = cell.to_ascii_uppercase();
                                                 // Colors matched to site CSS (from brustyle3.css sample):
@@ -286,18 +359,69 @@ fn inner_table(
                                                 }
                                             }
                                         }
+                                        if link.is_some() {
+                                            rt = rt.color(ui.visuals().hyperlink_color);
+                                        }
+                                        // Links aren't text-selectable, so clicks reach the cell
+                                        let label = egui::Label::new(rt).selectable(link.is_none());
                                         if numeric_cols.get(ci).copied().unwrap_or(false) {
-                                            ui.centered_and_justified(|ui| { ui.label(rt); });
+                                            ui.centered_and_justified(|ui| { ui.add(label); });
                                         } else {
-                                            ui.with_layout(Layout::left_to_right(Align::Center), |ui| { ui.label(rt); });
+                                            ui.with_layout(Layout::left_to_right(Align::Center), |ui| { ui.add(label); });
                                         }
                                     }
                                 });
                             });
+                            // Ctrl+click opens a link cell (plain clicks select/double-click as usual)
+                            let cell_resp = match &link {
+                                Some(path) => {
+                                    let url = crate::core::net::full_url(path);
+                                    if cell_resp.clicked() && ctx.input(|i| i.modifiers.command) {
+                                        logf!("UI: Open {}", url);
+                                        ctx.open_url(egui::OpenUrl::new_tab(&url));
+                                        opened_link = true;
+                                    }
+                                    cell_resp.on_hover_text(format!("Ctrl+click to open {}", url))
+                                }
+                                None => cell_resp,
+                            };
+                            // Right-click: copy / open on site / only this team
+                            cell_resp.context_menu(|ui| {
+                                if let Some(a) = row_menu::draw(ui, app, page, data, &display_ord, ci) {
+                                    row_action = Some(a);
+                                }
+                            });
                         }
                         // no body cell for gutter
+                        if row.response().clicked() && !opened_link {
+                            let m = ctx.input(|i| i.modifiers);
+                            row_click = Some((row_idx, m.command, m.shift));
+                        }
+                        if row.response().double_clicked() {
+                            double_clicked = Some(src_ix);
+                        }
                     }
                 }
             });
         });
+
+    if let Some(name) = hide_col {
+        app.set_column_hidden(kind, &name, true);
+    }
+
+    if let Some((pos, ctrl, shift)) = row_click {
+        let view = app.row_ix.clone();
+        app.state.gui.row_selection.entry(kind).or_default().click(&view, pos, ctrl, shift);
+    }
+
+    if let Some(RowAction::OnlyTeam(id)) = row_action {
+        super::team_panel::select_only(app, id);
+    }
+
+    // Players: double-click opens the player's detail page
+    if let (Some(src_ix), crate::config::options::PageKind::Players) = (double_clicked, kind) {
+        crate::gui::components::player_detail::open_for_row(app, src_ix);
+    }
+
+    if sort_changed { app.rebuild_view(); }
 }
@@ -0,0 +1,52 @@
+// src/gui/components/header_names.rs
+//
+// "Export header names" window: the current page's headers, each with the
+// name exports should write instead (blank keeps it). Copies and the
+// cache keep the canonical headers.
+
+use eframe::egui;
+use crate::gui::{actions::header_names, app::App};
+
+pub fn draw(ctx: &egui::Context, app: &mut App) {
+    let Some(editor) = app.header_names.as_mut() else { return };
+
+    let mut open = true;
+    let mut save = false;
+    egui::Window::new(format!("Export header names — {}", editor.kind))
+        .id(egui::Id::new("header_names"))
+        .open(&mut open)
+        .default_width(360.0)
+        .show(ctx, |ui| {
+            ui.label("Names to write in exported files instead of the site's headers. \
+                Blank keeps the header as it is.");
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .id_salt("header_names_scroll")
+                .max_height(320.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("header_names_grid").striped(true).show(ui, |ui| {
+                        for (header, name) in editor.rows.iter_mut() {
+                            ui.label(header.as_str());
+                            ui.add(egui::TextEdit::singleline(name).hint_text(header.as_str()).desired_width(200.0));
+                            ui.end_row();
+                        }
+                    });
+                });
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                save = ui.button("Save").clicked();
+                if ui.button("Clear all").on_hover_text("Back to the site's headers (Save to apply)").clicked() {
+                    for (_, name) in editor.rows.iter_mut() { name.clear(); }
+                }
+            });
+        });
+
+    if save {
+        header_names::save(app);
+    }
+    if !open {
+        app.header_names = None;
+    }
+}
@@ -0,0 +1,92 @@
+// src/gui/components/import_dialog.rs
+//
+// "Import from clipboard/file" window: paste rows copied from a spreadsheet
+// (or load an exported CSV/TSV), Check them against the page, then confirm
+// the merge after seeing what it changes.
+
+use eframe::egui::{self, Color32, RichText};
+use crate::gui::{actions::import, app::App};
+
+/// Report lines listed before "… and N more".
+const PREVIEW_LINES: usize = 50;
+
+pub fn draw(ctx: &egui::Context, app: &mut App) {
+    let Some(dialog) = app.import.as_mut() else { return };
+
+    let mut open = true;
+    let (mut check, mut apply, mut cancel) = (false, false, false);
+    egui::Window::new(format!("Import into {}", dialog.kind))
+        .id(egui::Id::new("import_dialog"))
+        .open(&mut open)
+        .default_width(520.0)
+        .show(ctx, |ui| {
+            ui.label("Paste CSV or TSV rows (Ctrl+V), with or without the header row. \
+                Rows replace the cached rows with the same key; new rows are added.");
+            egui::ScrollArea::vertical()
+                .id_salt("import_text_scroll")
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    if ui.add(egui::TextEdit::multiline(&mut dialog.text)
+                        .font(egui::TextStyle::Monospace)
+                        .desired_rows(8)
+                        .desired_width(f32::INFINITY))
+                        .changed()
+                    {
+                        dialog.preview = None;
+                    }
+                });
+
+            ui.horizontal(|ui| {
+                ui.label("File:");
+                ui.add(egui::TextEdit::singleline(&mut dialog.path).hint_text("path to .csv / .tsv"));
+                if ui.add_enabled(!dialog.path.trim().is_empty(), egui::Button::new("Load")).clicked() {
+                    import::load_file(dialog);
+                }
+            });
+
+            ui.separator();
+            match &dialog.preview {
+                None => {}
+                Some(Err(e)) => { ui.colored_label(ui.visuals().error_fg_color, e); }
+                Some(Ok((_, diff))) => {
+                    ui.strong(diff.summary());
+                    let lines = diff.report_lines();
+                    egui::ScrollArea::vertical()
+                        .id_salt("import_preview_scroll")
+                        .max_height(160.0)
+                        .show(ui, |ui| {
+                            for line in lines.iter().take(PREVIEW_LINES) {
+                                let rt = RichText::new(line).monospace();
+                                let rt = match line.chars().next() {
+                                    Some('+') => rt.color(Color32::from_rgb(0x6A, 0xC8, 0x6A)),
+                                    _ => rt,
+                                };
+                                ui.label(rt);
+                            }
+                            if lines.len() > PREVIEW_LINES {
+                                ui.weak(format!("… and {} more", lines.len() - PREVIEW_LINES));
+                            }
+                        });
+                }
+            }
+
+            ui.horizontal(|ui| {
+                check = ui.add_enabled(!dialog.text.trim().is_empty(), egui::Button::new("Check")).clicked();
+                let ready = dialog.preview.as_ref().is_some_and(|p| p.as_ref().is_ok_and(|(_, d)| !d.is_empty()));
+                apply = ui.add_enabled(ready, egui::Button::new("Merge into cache"))
+                    .on_hover_text("Save the merged data (Undo last scrape puts the old data back)")
+                    .clicked();
+                cancel = ui.button("Cancel").clicked();
+            });
+        });
+
+    if check {
+        import::check(app);
+    }
+    if apply {
+        import::apply(app);
+    }
+    if cancel || !open {
+        app.import = None;
+    }
+}
@@ -0,0 +1,69 @@
+// src/gui/components/injury_bars.rs
+//
+// Collapsible bar charts above the Injury Summary table: injuries per team,
+// per type and per week, each bar labelled with its count and SR lost.
+// Follows the table view (team selection, search).
+
+use eframe::egui;
+use crate::{
+    config::options::PageKind,
+    gui::app::App,
+    store::DataSet,
+    injury_summary::{GROUP_COL, GROUP_TEAM, GROUP_TOTAL, GROUP_TYPE, GROUP_WEEK, INJURIES_COL, KEY_COL, SR_LOST_COL},
+};
+
+const BAR_WIDTH: f32 = 160.0;
+const BAR_HEIGHT: f32 = 12.0;
+
+pub fn draw(ui: &mut egui::Ui, app: &mut App) {
+    if app.current_page_kind() != PageKind::InjurySummary { return; }
+    let Some(raw) = app.raw_data.get(&PageKind::InjurySummary) else { return };
+    let ds = raw.dataset();
+
+    let bars = |group: &str| group_bars(ds, &app.row_ix, group);
+    let total = bars(GROUP_TOTAL).first().map(|&(_, n, sr)| format!("{} injuries, {} SR lost", n, sr));
+
+    egui::CollapsingHeader::new(format!("Charts — {}", total.as_deref().unwrap_or("no injuries")))
+        .id_salt("injury_bars")
+        .default_open(true)
+        .show(ui, |ui| {
+            ui.columns(3, |cols| {
+                for (col, group) in cols.iter_mut().zip([GROUP_TEAM, GROUP_TYPE, GROUP_WEEK]) {
+                    col.strong(format!("Per {}", group.to_lowercase()));
+                    draw_bars(col, group, &bars(group));
+                }
+            });
+        });
+    ui.separator();
+}
+
+/// (key, injuries, SR lost) of a group's rows among `row_ix`, in that order.
+fn group_bars<'a>(ds: &'a DataSet, row_ix: &[usize], group: &str) -> Vec<(&'a str, usize, &'a str)> {
+    row_ix.iter().filter_map(|&i| ds.rows.get(i))
+        .filter(|r| r.get(GROUP_COL).is_some_and(|g| g == group))
+        .map(|r| {
+            let cell = |c: usize| r.get(c).map(String::as_str).unwrap_or("");
+            (cell(KEY_COL), cell(INJURIES_COL).parse().unwrap_or(0), cell(SR_LOST_COL))
+        })
+        .collect()
+}
+
+fn draw_bars(ui: &mut egui::Ui, group: &str, bars: &[(&str, usize, &str)]) {
+    let max = bars.iter().map(|&(_, n, _)| n).max().unwrap_or(0).max(1);
+    let fill = ui.visuals().selection.bg_fill;
+    egui::ScrollArea::vertical()
+        .id_salt(("injury_bars_scroll", group))
+        .max_height(180.0)
+        .show(ui, |ui| {
+            egui::Grid::new(("injury_bars_grid", group)).show(ui, |ui| {
+                for &(key, n, sr) in bars {
+                    ui.label(key);
+                    let (rect, _) = ui.allocate_exact_size(egui::vec2(BAR_WIDTH, BAR_HEIGHT), egui::Sense::hover());
+                    let bar = egui::Rect::from_min_size(rect.min, egui::vec2(BAR_WIDTH * n as f32 / max as f32, BAR_HEIGHT));
+                    ui.painter().rect_filled(bar, 2.0, fill);
+                    ui.label(format!("{} ({} SR)", n, sr));
+                    ui.end_row();
+                }
+            });
+        });
+}
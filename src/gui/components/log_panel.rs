@@ -0,0 +1,64 @@
+// src/gui/components/log_panel.rs
+//
+// Collapsible log at the bottom of the window: the recent lines of the log
+// (see `log::recent`), filtered by level, with a button to copy them.
+
+use eframe::egui::{self, Color32, RichText};
+use crate::{gui::app::App, log::{self, Level}};
+
+pub fn draw(ui: &mut egui::Ui, app: &mut App) {
+    let lines = log::recent();
+    let errors = lines.iter().filter(|l| l.level == Level::Error).count();
+    let title = match errors {
+        0 => s!("Log"),
+        n => format!("Log — {} error(s)", n),
+    };
+
+    egui::CollapsingHeader::new(title)
+        .id_salt("log_panel")
+        .default_open(false)
+        .show(ui, |ui| {
+            let mut min = app.state.gui.log_level;
+            let shown: Vec<&log::LogLine> = lines.iter().filter(|l| l.level >= min).collect();
+
+            ui.horizontal(|ui| {
+                ui.label("Show:");
+                for lvl in Level::ALL {
+                    if ui.selectable_value(&mut min, lvl, lvl.as_str())
+                        .on_hover_text(format!("{} and above", lvl.as_str()))
+                        .changed()
+                    {
+                        logd!("UI: Log level → {:?}", lvl);
+                    }
+                }
+                app.state.gui.log_level = min;
+                ui.separator();
+                if ui.add_enabled(!shown.is_empty(), egui::Button::new("Copy"))
+                    .on_hover_text("Copy the lines shown")
+                    .clicked()
+                {
+                    let text: Vec<String> = shown.iter().map(|l| l.text()).collect();
+                    ui.ctx().copy_text(text.join("\n"));
+                    app.status(format!("Copied {} log line(s) to clipboard", shown.len()));
+                }
+                ui.weak(format!("{} line(s)", shown.len()));
+            });
+
+            egui::ScrollArea::vertical()
+                .id_salt("log_panel_scroll")
+                .max_height(180.0)
+                .auto_shrink([false, true])
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    for l in &shown {
+                        let rt = RichText::new(l.text()).monospace();
+                        let rt = match l.level {
+                            Level::Error => rt.color(Color32::from_rgb(0xDC, 0x61, 0x49)),
+                            Level::Debug => rt.weak(),
+                            Level::Info => rt,
+                        };
+                        ui.label(rt);
+                    }
+                });
+        });
+}
@@ -2,4 +2,19 @@
 pub mod data_table;
 pub mod action_buttons;
 pub mod tabs;
-pub mod team_panel;
\ No newline at end of file
+pub mod team_panel;
+pub mod search_bar;
+pub mod changes_panel;
+pub mod injury_bars;
+pub mod player_detail;
+pub mod about;
+pub mod import_dialog;
+pub mod alias_editor;
+pub mod header_names;
+pub mod user_columns;
+pub mod compare_teams;
+pub mod trend_chart;
+pub mod row_menu;
+pub mod settings;
+pub mod log_panel;
+pub mod new_season;
@@ -0,0 +1,34 @@
+// src/gui/components/new_season.rs
+//
+// New-season prompt: Game Results came back from a season after the
+// current one. Before any of it is saved, offer to archive the cached
+// pages as the last season (`store::archive_season`), so its archive
+// holds that season's data only. The scrape waits for the answer.
+
+use eframe::egui;
+use crate::gui::{actions::scrape, app::App};
+
+pub fn draw(ctx: &egui::Context, app: &mut App) {
+    let Some((old, new)) = app.new_season.as_ref().map(|p| (p.old, p.new)) else { return };
+
+    let mut answer = None;
+    egui::Window::new(format!("Season {} began", new))
+        .id(egui::Id::new("new_season"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.label(format!(
+                "The cache still holds season {}. Archive it now, before the new season's scrapes replace it? \
+                 Archived seasons stay in the season menu.", old));
+            ui.horizontal(|ui| {
+                if ui.button(format!("Archive season {}", old)).clicked() { answer = Some(true); }
+                if ui.button("Don't archive").clicked() { answer = Some(false); }
+            });
+        });
+
+    if let Some(archive) = answer {
+        logf!("UI: New season {}: archive season {} → {}", new, old, archive);
+        scrape::answer_new_season(app, archive);
+    }
+}
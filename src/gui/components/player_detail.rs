@@ -0,0 +1,103 @@
+// src/gui/components/player_detail.rs
+//
+// Drill-down window for one player (double-click a row in the Players table).
+// The detail page is fetched on a worker thread; the window shows a spinner
+// until it arrives, then the fields grouped by section.
+
+use std::thread::{self, JoinHandle};
+
+use eframe::egui::{self, widgets::Spinner};
+use crate::{gui::app::App, scrape, store::DataSet};
+
+pub struct PlayerDetail {
+    pub player_id: u32,
+    pub title: String,
+    pub data: Option<Result<DataSet, String>>,
+    handle: Option<JoinHandle<Result<DataSet, String>>>,
+}
+
+/// Open (or replace) the detail window for the player on source row `src_ix`.
+pub fn open_for_row(app: &mut App, src_ix: usize) {
+    let kind = app.current_page_kind();
+    let page = app.current_page();
+    let Some(row) = app.raw_data.get(&kind).and_then(|r| r.dataset().rows.get(src_ix)) else { return };
+
+    let id = page.stable_id_column(app.headers.as_deref())
+        .and_then(|c| row.get(c))
+        .and_then(|v| v.trim().parse::<u32>().ok());
+    let Some(player_id) = id else {
+        app.status("No player id for this row (re-scrape Players to get ids)");
+        return;
+    };
+    let name = row.first().cloned().unwrap_or_default();
+
+    logf!("Player: Open detail id={} ({})", player_id, name);
+    let handle = thread::spawn(move || {
+        scrape::collect_player(player_id, None).map_err(|e| e.to_string())
+    });
+    app.player_detail = Some(PlayerDetail {
+        player_id,
+        title: format!("{} (#{})", name, player_id),
+        data: None,
+        handle: Some(handle),
+    });
+}
+
+pub fn draw(ctx: &egui::Context, app: &mut App) {
+    let Some(detail) = app.player_detail.as_mut() else { return };
+
+    if detail.handle.as_ref().is_some_and(|h| h.is_finished()) {
+        let res = detail.handle.take().unwrap().join()
+            .unwrap_or_else(|e| Err(format!("Worker panicked: {e:?}")));
+        if let Err(e) = &res { loge!("Player: Detail {} failed: {}", detail.player_id, e); }
+        detail.data = Some(res);
+    }
+    if detail.handle.is_some() {
+        ctx.request_repaint_after(std::time::Duration::from_millis(60));
+    }
+
+    let mut open = true;
+    egui::Window::new(&detail.title)
+        .id(egui::Id::new("player_detail"))
+        .open(&mut open)
+        .default_width(360.0)
+        .show(ctx, |ui| match &detail.data {
+            None => { ui.add(Spinner::new().size(16.0)); }
+            Some(Err(e)) => { ui.label(e); }
+            Some(Ok(ds)) => {
+                egui::ScrollArea::vertical()
+                    .id_salt("player_detail_scroll")
+                    .max_height(480.0)
+                    .show(ui, |ui| draw_sections(ui, ds));
+            }
+        });
+
+    if !open {
+        app.player_detail = None;
+    }
+}
+
+/// Rows are (Player id, Section, Field, Value); one collapsible grid per section.
+fn draw_sections(ui: &mut egui::Ui, ds: &DataSet) {
+    let mut start = 0;
+    while start < ds.rows.len() {
+        let section = ds.rows[start].get(1).cloned().unwrap_or_default();
+        let end = ds.rows[start..].iter()
+            .position(|r| r.get(1) != Some(&section))
+            .map_or(ds.rows.len(), |n| start + n);
+
+        egui::CollapsingHeader::new(&section)
+            .id_salt(("player_section", start))
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::Grid::new(("player_grid", start)).striped(true).show(ui, |ui| {
+                    for r in &ds.rows[start..end] {
+                        ui.label(r.get(2).map(String::as_str).unwrap_or(""));
+                        ui.label(r.get(3).map(String::as_str).unwrap_or(""));
+                        ui.end_row();
+                    }
+                });
+            });
+        start = end;
+    }
+}
@@ -0,0 +1,90 @@
+// src/gui/components/row_menu.rs
+//
+// Right-click menu on a table cell: copy the row or the cell, open the site
+// page of the cell or the row, or narrow the team selection to one of its teams.
+// Drawn inside the table body, where `App` is only borrowed; anything that
+// changes the app comes back as a `RowAction` to apply after the table.
+
+use eframe::egui;
+use crate::{
+    config::options::ExportFormat,
+    core::net,
+    file,
+    gui::{app::App, pages::{self, Page}},
+};
+
+pub enum RowAction {
+    /// Select just this team
+    OnlyTeam(u32),
+}
+
+/// Menu for source column `ci` of `row`; `shown` = visible columns in display order.
+pub fn draw(
+    ui: &mut egui::Ui,
+    app: &App,
+    page: &dyn Page,
+    row: &[String],
+    shown: &[usize],
+    ci: usize,
+) -> Option<RowAction> {
+    let mut action = None;
+
+    if ui.button("Copy row").clicked() {
+        let cells: Vec<String> = shown.iter().map(|&c| row.get(c).cloned().unwrap_or_default()).collect();
+        ui.ctx().copy_text(row_text(app, cells));
+        app.status("Copied row to clipboard");
+    }
+    let cell = row.get(ci).map(String::as_str).unwrap_or("");
+    if ui.add_enabled(!cell.is_empty(), egui::Button::new("Copy cell")).clicked() {
+        ui.ctx().copy_text(s!(cell));
+        app.status("Copied cell to clipboard");
+    }
+
+    ui.separator();
+
+    // The cell's own page (a team, match or player), when it's a link
+    let cell_link = page.cell_link(app.headers.as_deref(), row, ci, &app.teams);
+    if let Some(path) = &cell_link {
+        let url = net::full_url(path);
+        if ui.button(format!("Open {} on site", cell)).on_hover_text(&url).clicked() {
+            logf!("UI: Open {}", url);
+            ui.ctx().open_url(egui::OpenUrl::new_tab(url));
+        }
+    }
+
+    match page.source_path(app.headers.as_deref(), row, &app.teams).filter(|p| cell_link.as_ref() != Some(p)) {
+        Some(path) => {
+            let url = net::full_url(&path);
+            if ui.button("Open on site").on_hover_text(&url).clicked() {
+                logf!("UI: Open {}", url);
+                ui.ctx().open_url(egui::OpenUrl::new_tab(url));
+            }
+        }
+        None if cell_link.is_none() => { ui.add_enabled(false, egui::Button::new("Open on site")); }
+        None => {}
+    }
+
+    // One entry per team named in the row (both sides of a fixture)
+    let mut listed: Vec<u32> = Vec::new();
+    for &tc in page.team_columns() {
+        let Some(name) = row.get(tc).filter(|n| !n.is_empty()) else { continue };
+        let Some(id) = pages::team_id_by_name(&app.teams, name) else { continue };
+        if listed.contains(&id) { continue; }
+        listed.push(id);
+        if ui.add_enabled(!app.running(), egui::Button::new(format!("Only {}", name))).clicked() {
+            action = Some(RowAction::OnlyTeam(id));
+        }
+    }
+
+    action
+}
+
+/// One row as delimited text in the export format (TSV for Markdown/BBCode).
+fn row_text(app: &App, cells: Vec<String>) -> String {
+    let mut options = app.state.options.clone();
+    options.export.include_headers = false;
+    if options.export.format.is_markup() {
+        options.export.format = ExportFormat::Tsv;
+    }
+    file::to_export_string(&options, &None, &[cells]).trim_end().to_string()
+}
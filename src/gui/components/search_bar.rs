@@ -0,0 +1,79 @@
+// src/gui/components/search_bar.rs
+//
+// Text filter above the table. Edits App::text_filter for the current page
+// and rebuilds the view; filtering itself happens in App::rebuild_view.
+// Also hosts the Columns menu (per-page column visibility).
+
+use eframe::egui;
+use crate::gui::app::App;
+
+pub fn draw(ui: &mut egui::Ui, app: &mut App) {
+    let kind = app.current_page_kind();
+    let headers = app.headers.clone().unwrap_or_default();
+    let mut changed = false;
+
+    ui.horizontal(|ui| {
+        let filter = app.text_filter.entry(kind).or_default();
+
+        ui.label("Filter:");
+        changed |= ui.add(
+            egui::TextEdit::singleline(&mut filter.query)
+                .hint_text("Search rows…")
+                .desired_width(220.0))
+            .changed();
+
+        let selected = filter.column
+            .and_then(|ci| headers.get(ci).cloned())
+            .unwrap_or_else(|| s!("All columns"));
+        egui::ComboBox::from_id_salt(("filter_column", kind))
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                changed |= ui.selectable_value(&mut filter.column, None, "All columns").changed();
+                for (ci, h) in headers.iter().enumerate() {
+                    changed |= ui.selectable_value(&mut filter.column, Some(ci), h.as_str()).changed();
+                }
+            });
+
+        if filter.is_active() && ui.button("✖").on_hover_text("Clear filter").clicked() {
+            filter.query.clear();
+            changed = true;
+        }
+
+        ui.separator();
+        columns_menu(ui, app, &headers);
+    });
+
+    if changed {
+        app.rebuild_view();
+    }
+}
+
+/// Show/hide columns of the current page. Hidden columns are left out of the
+/// table and of Copy/Export; the choice is saved per page.
+fn columns_menu(ui: &mut egui::Ui, app: &mut App, headers: &[String]) {
+    let kind = app.current_page_kind();
+    let hidden = app.user_hidden_columns(kind, Some(headers));
+    let label = if hidden.is_empty() { s!("⚙ Columns") } else { format!("⚙ Columns ({} hidden)", hidden.len()) };
+
+    ui.add_enabled_ui(!headers.is_empty(), |ui| {
+        ui.menu_button(label, |ui| {
+            let visible = headers.len() - hidden.len();
+            for (ci, h) in headers.iter().enumerate() {
+                let mut shown = !hidden.contains(&ci);
+                // Keep at least one column
+                let locked = shown && visible <= 1;
+                if ui.add_enabled(!locked, egui::Checkbox::new(&mut shown, h.as_str())).changed() {
+                    app.set_column_hidden(kind, h, !shown);
+                }
+            }
+            if !hidden.is_empty() {
+                ui.separator();
+                if ui.button("Show all").clicked() {
+                    for &ci in &hidden {
+                        app.set_column_hidden(kind, &headers[ci], false);
+                    }
+                }
+            }
+        });
+    });
+}
@@ -0,0 +1,212 @@
+// src/gui/components/settings.rs
+//
+// Settings window (modal, ⚙ next to About): Network, Export defaults,
+// Appearance and Storage. Edits a copy; Save applies and persists it
+// (see `actions::settings`), Cancel or Escape drops it. Storage's Verify
+// and Repair act on the cache right away.
+
+use eframe::egui;
+use crate::{
+    config::{
+        consts::{DEFAULT_FONT_SIZE, FONT_SIZE_RANGE},
+        options::ExportFormat,
+        state::{Density, Theme},
+    },
+    core::net::NetSettings,
+    gui::{actions::settings, app::App},
+    integrity::PageCheck,
+    store,
+};
+
+/// Problems listed per page; the rest are counted
+const PROBLEMS_SHOWN: usize = 5;
+
+const FORMATS: [ExportFormat; 4] = [ExportFormat::Tsv, ExportFormat::Csv, ExportFormat::Markdown, ExportFormat::BbCode];
+
+pub fn draw(ctx: &egui::Context, app: &mut App) {
+    let Some(dialog) = app.settings.as_mut() else { return };
+    let mut save = false;
+    let mut cancel = false;
+    let mut verify = false;
+    let mut repair = false;
+    let modal = egui::Modal::new(egui::Id::new("settings")).show(ctx, |ui| {
+        ui.set_width(560.0);
+        ui.heading("Settings");
+        egui::ScrollArea::vertical().max_height(ui.ctx().screen_rect().height() * 0.7).show(ui, |ui| {
+            egui::CollapsingHeader::new("Network").default_open(true).show(ui, |ui| {
+                let net = &mut dialog.net;
+                egui::Grid::new("settings_net").num_columns(2).show(ui, |ui| {
+                    ui.label("Site");
+                    ui.add(egui::TextEdit::singleline(&mut net.base_url).desired_width(320.0))
+                        .on_hover_text("http://host[:port]/prefix/");
+                    ui.end_row();
+                    ui.label("Timeout");
+                    ui.add(egui::DragValue::new(&mut net.timeout_secs).range(1..=300).suffix(" s"));
+                    ui.end_row();
+                    ui.label("Retries");
+                    ui.add(egui::DragValue::new(&mut net.retries).range(0..=10))
+                        .on_hover_text("Retries of a page that arrives cut off");
+                    ui.end_row();
+                    ui.label("Delay");
+                    ui.add(egui::DragValue::new(&mut net.delay_ms).range(0..=10_000).speed(10).suffix(" ms"))
+                        .on_hover_text("Pause between requests");
+                    ui.end_row();
+                });
+                if let Err(e) = net.site() {
+                    ui.colored_label(ui.visuals().error_fg_color, e);
+                }
+                if ui.small_button("Defaults").clicked() {
+                    *net = NetSettings::default();
+                }
+            });
+
+            egui::CollapsingHeader::new("Export defaults").show(ui, |ui| {
+                ui.label("A page's own format, headers and folder; blank or Shared uses the shared settings.");
+                egui::Grid::new("settings_export").num_columns(4).striped(true).show(ui, |ui| {
+                    for d in dialog.export_defaults.iter_mut() {
+                        ui.label(d.title);
+                        egui::ComboBox::from_id_salt(("settings_format", d.kind))
+                            .selected_text(d.format.map(|f| f.to_string()).unwrap_or_else(|| s!("Shared")))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut d.format, None, "Shared");
+                                for f in FORMATS {
+                                    ui.selectable_value(&mut d.format, Some(f), f.to_string());
+                                }
+                            });
+                        let headers = match d.include_headers { None => "Shared", Some(true) => "Headers", Some(false) => "No headers" };
+                        egui::ComboBox::from_id_salt(("settings_headers", d.kind))
+                            .selected_text(headers)
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut d.include_headers, None, "Shared");
+                                ui.selectable_value(&mut d.include_headers, Some(true), "Headers");
+                                ui.selectable_value(&mut d.include_headers, Some(false), "No headers");
+                            });
+                        ui.add(egui::TextEdit::singleline(&mut d.dir).hint_text("Shared folder").desired_width(180.0));
+                        ui.end_row();
+                    }
+                });
+            });
+
+            egui::CollapsingHeader::new("Appearance").show(ui, |ui| {
+                let a = &mut dialog.appearance;
+                egui::Grid::new("settings_appearance").num_columns(2).show(ui, |ui| {
+                    ui.label("Theme");
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut a.theme, Theme::System, "System")
+                            .on_hover_text("Follow the system's light/dark setting");
+                        ui.radio_value(&mut a.theme, Theme::Dark, "Dark");
+                        ui.radio_value(&mut a.theme, Theme::Light, "Light");
+                    });
+                    ui.end_row();
+                    ui.label("Table rows");
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut a.density, Density::Compact, "Compact");
+                        ui.radio_value(&mut a.density, Density::Normal, "Normal");
+                    });
+                    ui.end_row();
+                    ui.label("Font size");
+                    ui.horizontal(|ui| {
+                        ui.add(egui::DragValue::new(&mut a.font_size).range(FONT_SIZE_RANGE).speed(0.5).suffix(" pt"));
+                        if ui.small_button("Reset").clicked() {
+                            a.font_size = DEFAULT_FONT_SIZE;
+                        }
+                    });
+                    ui.end_row();
+                });
+            });
+
+            egui::CollapsingHeader::new("Storage").show(ui, |ui| {
+                egui::Grid::new("settings_storage").num_columns(2).show(ui, |ui| {
+                    ui.label("Cache");
+                    ui.horizontal(|ui| {
+                        let dir = store::store_dir();
+                        ui.monospace(dir.display().to_string());
+                        if ui.small_button("Open").clicked() {
+                            if let Err(e) = super::action_buttons::open_folder_in_explorer(&dir) {
+                                loge!("Settings: Could not open {}: {}", dir.display(), e);
+                            }
+                        }
+                    });
+                    ui.end_row();
+                    ui.label("Data folder");
+                    ui.add_enabled(!dialog.portable, egui::TextEdit::singleline(&mut dialog.data_dir)
+                        .hint_text(".store in the working folder")
+                        .desired_width(280.0));
+                    ui.end_row();
+                    ui.label("");
+                    ui.checkbox(&mut dialog.portable, "Portable: keep the data next to the program")
+                        .on_hover_text("Uses .store next to the executable, whatever the data folder says");
+                    ui.end_row();
+                    ui.label("");
+                    ui.weak("Folder changes copy the data over and apply on the next start.");
+                    ui.end_row();
+                    ui.label("Snapshots");
+                    ui.add(egui::DragValue::new(&mut dialog.snapshots).range(0..=100))
+                        .on_hover_text("Earlier scrapes kept per page, for trends (0 = off)");
+                    ui.end_row();
+                    ui.label("Stale after");
+                    ui.add(egui::DragValue::new(&mut dialog.stale_after_hours).range(1..=24 * 30).suffix(" h"))
+                        .on_hover_text("Tint the tabs of pages scraped longer ago than this");
+                    ui.end_row();
+                    ui.label("Integrity");
+                    ui.horizontal(|ui| {
+                        verify = ui.small_button("Verify")
+                            .on_hover_text("Look for damaged rows (wrong width, blank, duplicate) and pages")
+                            .clicked();
+                        let repairable: usize = dialog.cache_check.iter().flatten().map(PageCheck::repairable).sum();
+                        if repairable > 0 {
+                            repair = ui.small_button(format!("Repair ({} rows)", repairable))
+                                .on_hover_text("Drop the damaged rows; Undo last scrape brings them back")
+                                .clicked();
+                        }
+                    });
+                    ui.end_row();
+                });
+                if let Some(checks) = &dialog.cache_check {
+                    cache_report(ui, checks);
+                }
+            });
+        });
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            let problem = dialog.problem();
+            save = ui.add_enabled(problem.is_none(), egui::Button::new("Save"))
+                .on_disabled_hover_text(problem.unwrap_or_default())
+                .clicked();
+            cancel = ui.button("Cancel").clicked();
+        });
+    });
+
+    if save {
+        settings::save(app, ctx);
+    } else if cancel || modal.should_close() {
+        app.settings = None;
+    } else if verify {
+        settings::verify_cache(app);
+    } else if repair {
+        settings::repair_cache(app);
+    }
+}
+
+/// The last Verify's findings: one line per cached page, its problems below.
+fn cache_report(ui: &mut egui::Ui, checks: &[PageCheck]) {
+    if checks.is_empty() {
+        ui.weak("Nothing cached yet.");
+        return;
+    }
+    for check in checks {
+        let line = format!("{}: {}", check.kind, check.summary());
+        if check.is_ok() {
+            ui.label(line);
+            continue;
+        }
+        ui.colored_label(ui.visuals().warn_fg_color, line);
+        for p in check.problems.iter().take(PROBLEMS_SHOWN) {
+            ui.weak(format!("    {}", p));
+        }
+        if check.problems.len() > PROBLEMS_SHOWN {
+            ui.weak(format!("    … and {} more", check.problems.len() - PROBLEMS_SHOWN));
+        }
+    }
+}
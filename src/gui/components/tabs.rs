@@ -7,8 +7,8 @@
 // filter_rows_for_selection, and set app.headers/app.rows accordingly.
 
 use eframe::egui;
-use std::path::{Path, PathBuf};
-use crate::gui::{app::App, router};
+use std::{path::{Path, PathBuf}, time::SystemTime};
+use crate::{gui::{app::App, router}, store};
 use crate::config::options::{ExportOptions, ExportType};
 
 fn norm(p: &Path) -> PathBuf { p.components().collect() }
@@ -22,11 +22,34 @@ pub fn draw(ui: &mut egui::Ui, app: &mut App) {
 
         for (idx, page) in pages.iter().enumerate() {
             let selected = idx == cur;
+            // ⏳ while the page scrapes in the background; time since the
+            // last scrape, tinted once it's older than the stale threshold
+            let kind = page.kind();
+            let mut title = if app.scrapes.contains_key(&kind) {
+                format!("⏳ {}", page.title())
+            } else {
+                s!(page.title())
+            };
+            let scraped = app.scraped_at.get(&kind).copied();
+            let age = scraped.map(|at| SystemTime::now().duration_since(at).map(store::fmt_age).unwrap_or_else(|_| s!("just now")));
+            let stale = scraped.is_some_and(|at| store::is_stale(at, app.state.gui.stale_after_hours));
+            if let Some(age) = age.as_ref() {
+                title.push_str(&format!(" · {}", age));
+            }
+            let mut text = egui::RichText::new(title);
+            if stale {
+                text = text.color(ui.visuals().warn_fg_color);
+            }
+            let hover = match (age, stale) {
+                (Some(age), true) => format!("{} — updated {}; older than {}h, scrape to refresh", page.title(), age, app.state.gui.stale_after_hours),
+                (Some(age), false) => format!("{} — updated {}", page.title(), age),
+                (None, _) => format!("{} — not scraped yet", page.title()),
+            };
 
-            if ui.selectable_label(selected, page.title()).clicked() && !selected {
+            if ui.selectable_label(selected, text).on_hover_text(hover).clicked() && !selected {
                 let prev = app.current_page_kind();
                 app.set_current_index(idx);
-                let new_kind = page.kind();
+                let new_kind = kind;
                 logf!("UI: Tab switch {:?} → {:?}", prev, new_kind);
                 app.status("Ready");
 
@@ -34,6 +57,13 @@ pub fn draw(ui: &mut egui::Ui, app: &mut App) {
                 app.state.options.scrape.page = new_kind;
                 app.rebuild_view();
 
+                // Back to the shared export settings if the previous page
+                // had its own.
+                if app.state.options.export.leave_page_defaults(prev) {
+                    app.out_path_text = app.state.options.export.out_path().to_string_lossy().into_owned();
+                    app.out_path_dirty = false;
+                }
+
                 // ----- DIR migration logic -----
                 // If the *user-visible* DIR is still the default for the previous tab,
                 // move DIR to the new tab's default, but preserve the filename/ext
@@ -76,7 +106,62 @@ pub fn draw(ui: &mut egui::Ui, app: &mut App) {
                     // Important: do NOT touch app.out_path_dirty here.
                     // User edits remain "dirty" until they export or otherwise apply.
                 }
+
+                // The new page's own format, headers and DIR win over both.
+                if app.state.options.export.enter_page_defaults(new_kind) {
+                    app.out_path_text = app.state.options.export.out_path().to_string_lossy().into_owned();
+                    app.out_path_dirty = false;
+                }
             }
         }
+
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui.small_button("ℹ").on_hover_text("About: versions and recent changes").clicked() {
+                app.show_about = !app.show_about;
+            }
+            if ui.small_button("⚙").on_hover_text("Settings: network, export defaults, appearance, storage").clicked() {
+                crate::gui::actions::settings::open(app);
+            }
+            season_menu(ui, app);
+            archive_button(ui, app);
+        });
     });
-}
\ No newline at end of file
+}
+
+/// Archive the cached pages as a season, so the season menu keeps them once
+/// the next season's scrapes replace them.
+fn archive_button(ui: &mut egui::Ui, app: &mut App) {
+    if store::selected_season().is_some() { return; }
+    let Some(season) = app.archive_as.or(app.state.season) else { return };
+    if ui.small_button("📦")
+        .on_hover_text(format!("Archive season {}: keep the cached pages as they are now in the season menu", season))
+        .clicked()
+    {
+        crate::gui::actions::season::archive(app, season);
+    }
+}
+/// Which season's data the pages show; only once there's an archived one.
+fn season_menu(ui: &mut egui::Ui, app: &mut App) {
+    let selected = store::selected_season();
+    if app.seasons.len() < 2 && selected.is_none() { return; }
+    let current = app.state.season;
+    let name = |s: Option<u32>| match (s, current) {
+        (Some(s), _) => format!("Season {}", s),
+        (None, Some(c)) => format!("Season {} (current)", c),
+        (None, None) => s!("Current season"),
+    };
+    let mut picked = selected;
+    egui::ComboBox::from_id_salt("season")
+        .selected_text(name(selected))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut picked, None, name(None));
+            for &s in app.seasons.iter().filter(|&&s| Some(s) != current) {
+                ui.selectable_value(&mut picked, Some(s), name(Some(s)));
+            }
+        })
+        .response
+        .on_hover_text("Season whose cached data the pages show, copy and export; 📦 archives a season");
+    if picked != selected {
+        crate::gui::actions::season::select(app, picked);
+    }
+}
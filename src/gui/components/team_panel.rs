@@ -1,38 +1,149 @@
 // src/gui/components/team_panel.rs
 //
 // Renders the left team list and applies selection changes directly to `app`.
-// Handles ctrl/shift range behavior, status text, and marks current page dirty.
+// Handles ctrl/shift range behavior, search, sort, keyboard navigation,
+// status text, and marks current page dirty. Beside each team: its cached
+// rows on the current page, and a mark if its last scrape failed or is
+// over a week old (pages scraped team by team).
 
 use eframe::egui;
-use crate::gui::app::App;
+use crate::{get_teams, gui::{app::App, shortcuts::Shortcut}, store};
 
-pub fn draw(ui: &mut egui::Ui, app: &mut App) {
-    ui.heading("Teams");
+const LOGO_SIZE: f32 = 16.0;
+
+/// A team's last successful scrape older than this is "not this week".
+const TEAM_STALE_HOURS: u32 = 7 * 24;
+
+/// Team list order. `Division` groups by division when the team list has
+/// them (site order otherwise).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TeamSort {
+    #[default]
+    Division,
+    Name,
+    Id,
+}
+
+// Apply current selection → scrape options, rebuild table, set status.
+fn apply_selection_change(app: &mut App) {
+    app.sync_gui_selection_into_scrape();
+    app.rebuild_view();
+
+    // Don't overwrite progress messages with team selection info
+    if !app.running() {
+        app.set_selection_message();
+    }
+}
+
+/// Select every team (All button, Ctrl+A).
+pub fn select_all(app: &mut App) {
+    app.state.gui.selected_team_ids = app.teams.iter().map(|(id, _)| *id).collect();
+    apply_selection_change(app);
+}
 
-    // Apply current selection → scrape options, rebuild table, set status.
-    let apply_selection_change = |app: &mut App| {
-        app.sync_gui_selection_into_scrape();
-        app.rebuild_view();
+/// Select just `id` (table row menu "Only <team>").
+pub fn select_only(app: &mut App, id: u32) {
+    app.state.gui.selected_team_ids = vec![id];
+    apply_selection_change(app);
+}
+
+/// A team's downloaded logo as a texture, loaded once (see `App::logos`).
+fn logo(app: &mut App, ctx: &egui::Context, id: u32) -> Option<egui::TextureHandle> {
+    app.logos.entry(id).or_insert_with(|| {
+        let path = store::logo_file(id)?;
+        let bytes = std::fs::read(&path).ok()?;
+        let rgba = match image::load_from_memory(&bytes) {
+            Ok(img) => img.to_rgba8(),
+            Err(e) => {
+                logd!("UI: Logo {} not shown ({})", path.display(), e);
+                return None;
+            }
+        };
+        let size = [rgba.width() as usize, rgba.height() as usize];
+        let img = egui::ColorImage::from_rgba_unmultiplied(size, rgba.as_raw());
+        Some(ctx.load_texture(format!("team_logo_{}", id), img, egui::TextureOptions::LINEAR))
+    }).clone()
+}
+
+/// Clear the team selection (None button, Ctrl+D).
+pub fn select_none(app: &mut App) {
+    app.state.gui.selected_team_ids.clear();
+    apply_selection_change(app);
+}
+
+/// Select the teams of a saved preset that are still in the team list.
+fn select_preset(app: &mut App, name: &str) {
+    let Some(ids) = app.team_presets.get(name) else { return };
+    app.state.gui.selected_team_ids = app.teams.iter()
+        .map(|(id, _)| *id)
+        .filter(|id| ids.contains(id))
+        .collect();
+    logf!("UI: Preset {:?} ({} teams)", name, app.state.gui.selected_team_ids.len());
+    apply_selection_change(app);
+}
+
+/// Save the current selection as `name` (empty `ids` deletes it), then reload.
+fn save_preset(app: &mut App, name: &str, ids: &[u32]) {
+    match store::save_team_preset(name, ids) {
+        Ok(_) => logf!("UI: Preset {:?} saved ({} teams)", name.trim(), ids.len()),
+        Err(e) => loge!("UI: Preset {:?}: {}", name, e),
+    }
+    app.team_presets = store::team_presets();
+}
 
-        // Don't overwrite progress messages with team selection info
-        if !app.running {
-            app.set_selection_message();
+/// Saved presets (click to select, right-click to delete) and the name box to
+/// save the current selection under.
+fn draw_presets(ui: &mut egui::Ui, app: &mut App) {
+    let mut picked: Option<String> = None;
+    let mut deleted: Option<String> = None;
+    ui.horizontal_wrapped(|ui| {
+        for (name, ids) in &app.team_presets {
+            let resp = ui.small_button(name).on_hover_text(format!("{} teams; right-click to delete", ids.len()));
+            if resp.clicked() && !app.running() { picked = Some(name.clone()); }
+            resp.context_menu(|ui| {
+                if ui.button("Delete preset").clicked() {
+                    deleted = Some(name.clone());
+                }
+            });
         }
-        
-    };
+    });
+    ui.horizontal(|ui| {
+        ui.add(egui::TextEdit::singleline(&mut app.preset_name).hint_text("Preset name").desired_width(110.0));
+        let can_save = !app.preset_name.trim().is_empty() && !app.state.gui.selected_team_ids.is_empty();
+        if ui.add_enabled(can_save, egui::Button::new("Save"))
+            .on_hover_text("Save the selected teams under this name (replaces a preset of the same name)")
+            .clicked()
+        {
+            let (name, ids) = (app.preset_name.clone(), app.state.gui.selected_team_ids.clone());
+            save_preset(app, &name, &ids);
+            app.preset_name.clear();
+        }
+    });
+
+    if let Some(name) = picked { select_preset(app, &name); }
+    if let Some(name) = deleted { save_preset(app, &name, &[]); }
+}
+
+pub fn draw(ui: &mut egui::Ui, app: &mut App) {
+    ui.heading("Teams");
 
     ui.horizontal(|ui| {
-        if ui.button("All").clicked() {
-            app.state.gui.selected_team_ids = app.teams.iter().map(|(id, _)| *id).collect();
-            apply_selection_change(app);
+        if ui.button("All").on_hover_text(Shortcut::SelectAll.hint(ui.ctx(), "Select all teams")).clicked() {
+            select_all(app);
         }
-        if ui.button("None").clicked() {
-            app.state.gui.selected_team_ids.clear();
-            apply_selection_change(app);
+        if ui.button("None").on_hover_text(Shortcut::SelectNone.hint(ui.ctx(), "Select no teams")).clicked() {
+            select_none(app);
         }
     });
+    draw_presets(ui, app);
 
     ui.separator();
+    draw_search_and_sort(ui, app);
+
+    // Logos next to the names once any were downloaded (Team Info page)
+    let ids: Vec<u32> = app.teams.iter().map(|(id, _)| *id).collect();
+    let logos: Vec<Option<egui::TextureHandle>> = ids.into_iter().map(|id| logo(app, ui.ctx(), id)).collect();
+    let show_logos = logos.iter().any(Option::is_some);
 
     // Match the scroll bar aesthetics used in the main table
     {
@@ -48,6 +159,13 @@ pub fn draw(ui: &mut egui::Ui, app: &mut App) {
         visuals.extreme_bg_color = visuals.panel_fill;
     }
 
+    // Display order: searched and sorted, grouped by division when asked
+    let groups = team_groups(app);
+    let order: Vec<usize> = groups.iter().flat_map(|(_, ix)| ix.iter().copied()).collect();
+    let (mut changed, scroll_to_cursor) = keyboard_nav(ui, app, &order);
+    let marks = team_marks(app);
+    let rows = RowCtx { order: &order, logos: &logos, show_logos, marks: &marks, scroll_to_cursor };
+
     egui::ScrollArea::vertical()
         .id_salt("teams_panel_scroll")
         .show(ui, |ui| {
@@ -55,62 +173,274 @@ pub fn draw(ui: &mut egui::Ui, app: &mut App) {
             let w = ui.available_width();
             ui.set_min_width(w);
             ui.set_width(w);
-            let mut changed = false;
-
-        for (idx, (id, name)) in app.teams.iter().enumerate() {
-            let is_selected = app.state.gui.selected_team_ids.contains(id);
-            let resp = ui.selectable_label(is_selected, name);
-
-            if resp.clicked() && !app.running {
-                let input = ui.input(|i| i.clone());
-                let sel = &mut app.state.gui.selected_team_ids;
-                let ctrl = input.modifiers.ctrl;
-                let shift = input.modifiers.shift;
-
-                if ctrl && shift {
-                    if let Some(last) = app.last_clicked {
-                        let (lo, hi) = if last <= idx { (last, idx) } else { (idx, last) };
-                        for j in lo..=hi {
-                            let tid = app.teams[j].0;
-                            if !sel.contains(&tid) { sel.push(tid); }
-                        }
-                        app.last_clicked = Some(idx);
-                    } else {
-                        // No anchor: fall back to ctrl-toggle on single item
-                        if is_selected { sel.retain(|x| x != id); } else { sel.push(*id); }
-                        app.last_clicked = Some(idx);
+
+            if order.is_empty() {
+                ui.weak("No team matches");
+            }
+
+            let mut pos = 0usize;
+            for (div, ix) in &groups {
+                let Some(div) = div else {
+                    for _ in ix {
+                        changed |= team_row(ui, app, &rows, pos);
+                        pos += 1;
                     }
-                } else if ctrl {
-                    if is_selected { sel.retain(|x| x != id); } else { sel.push(*id); }
-                    app.last_clicked = Some(idx);
-                } else if shift {
-                    if let Some(last) = app.last_clicked {
-                        let (lo, hi) = if last <= idx { (last, idx) } else { (idx, last) };
-                        sel.clear();
-                        for j in lo..=hi { sel.push(app.teams[j].0); }
-                        app.last_clicked = Some(idx);
-                    } else {
-                        // No anchor: behave like single click
-                        sel.clear();
-                        sel.push(*id);
-                        app.last_clicked = Some(idx);
+                    continue;
+                };
+
+                let ids: Vec<u32> = ix.iter().map(|&i| app.teams[i].0).collect();
+                let sel = &app.state.gui.selected_team_ids;
+                let mut all = ids.iter().all(|id| sel.contains(id));
+                let state = egui::collapsing_header::CollapsingState::load_with_default_open(
+                    ui.ctx(), ui.make_persistent_id(("division", div.as_str())), true);
+                state.show_header(ui, |ui| {
+                    let resp = ui.checkbox(&mut all, div.as_str())
+                        .on_hover_text("Select or deselect the whole division");
+                    if resp.changed() && !app.running() {
+                        let sel = &mut app.state.gui.selected_team_ids;
+                        sel.retain(|id| !ids.contains(id));
+                        if all { sel.extend(&ids); }
+                        changed = true;
                     }
-                } else {
-                    sel.clear();
-                    sel.push(*id);
-                    app.last_clicked = Some(idx);
-                }
-                changed = true;
+                }).body(|ui| {
+                    for k in 0..ix.len() {
+                        changed |= team_row(ui, app, &rows, pos + k);
+                    }
+                });
+                pos += ix.len();
             }
-        }
+        });
 
-        if changed {
-            apply_selection_change(app);
-            logf!(
-                "UI: Selection changed ({} teams) — {:?}",
-                app.state.gui.selected_team_ids.len(),
-                &app.state.gui.selected_team_ids
-            );
+    if changed {
+        apply_selection_change(app);
+        logf!(
+            "UI: Selection changed ({} teams) — {:?}",
+            app.state.gui.selected_team_ids.len(),
+            &app.state.gui.selected_team_ids
+        );
+    }
+}
+
+/// Search box (↓ moves into the list) and sort order. Either resets the
+/// click anchor and cursor, since display positions change.
+fn draw_search_and_sort(ui: &mut egui::Ui, app: &mut App) {
+    let has_divisions = !app.divisions.is_empty();
+    let before = (app.team_search.clone(), app.team_sort);
+    ui.horizontal(|ui| {
+        let search = ui.add(egui::TextEdit::singleline(&mut app.team_search)
+            .hint_text("Search teams")
+            .desired_width(110.0));
+        if search.has_focus() && ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown)) {
+            search.surrender_focus();
+            app.team_cursor = Some(0);
+        }
+        if !app.team_search.is_empty() && ui.small_button("✖").on_hover_text("Clear search").clicked() {
+            app.team_search.clear();
         }
     });
+    let label = |sort: TeamSort| match sort {
+        TeamSort::Division if has_divisions => "Division",
+        TeamSort::Division => "Site order",
+        TeamSort::Name => "Name",
+        TeamSort::Id => "Id",
+    };
+    egui::ComboBox::from_id_salt("team_sort")
+        .selected_text(format!("Sort: {}", label(app.team_sort)))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut app.team_sort, TeamSort::Division, label(TeamSort::Division));
+            ui.selectable_value(&mut app.team_sort, TeamSort::Name, "Name");
+            ui.selectable_value(&mut app.team_sort, TeamSort::Id, "Id");
+        });
+
+    if app.team_search != before.0 || app.team_sort != before.1 {
+        app.last_clicked = None;
+        app.team_cursor = app.team_cursor.map(|_| 0);
+        logd!("UI: Team list search {:?}, sort {:?}", app.team_search, app.team_sort);
+    }
+}
+
+/// Arrow keys move the cursor (once the list has it: a click or ↓ from the
+/// search box), Space toggles the team under it, Escape leaves. Not while
+/// typing somewhere. Returns (selection changed, cursor moved).
+fn keyboard_nav(ui: &mut egui::Ui, app: &mut App, order: &[usize]) -> (bool, bool) {
+    let Some(cur) = app.team_cursor else { return (false, false) };
+    if order.is_empty() || ui.ctx().wants_keyboard_input() { return (false, false); }
+    let cur = cur.min(order.len() - 1);
+    let (up, down, space, esc) = ui.input_mut(|i| (
+        i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+        i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+        i.consume_key(egui::Modifiers::NONE, egui::Key::Space),
+        i.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+    ));
+    if esc {
+        app.team_cursor = None;
+        return (false, false);
+    }
+    let next = if up { cur.saturating_sub(1) } else if down { (cur + 1).min(order.len() - 1) } else { cur };
+    app.team_cursor = Some(next);
+
+    let mut changed = false;
+    if space && !app.running() {
+        let id = app.teams[order[next]].0;
+        let sel = &mut app.state.gui.selected_team_ids;
+        if sel.contains(&id) { sel.retain(|x| *x != id); } else { sel.push(id); }
+        app.last_clicked = Some(next);
+        changed = true;
+    }
+    (changed, up || down)
+}
+
+/// Indices into `app.teams` in display order, matching the search: per
+/// division (in name order) then the teams without one under `None` when
+/// sorting by division, else a single `None` group. Empty groups are left out.
+fn team_groups(app: &App) -> Vec<(Option<String>, Vec<usize>)> {
+    let all = || (0..app.teams.len()).collect::<Vec<usize>>();
+    let mut groups: Vec<(Option<String>, Vec<usize>)> = match app.team_sort {
+        TeamSort::Division => {
+            let index_of = |id: u32| app.teams.iter().position(|(t, _)| *t == id);
+            let mut groups: Vec<(Option<String>, Vec<usize>)> = get_teams::by_division(&app.teams, &app.divisions)
+                .into_iter()
+                .map(|(div, ids)| (Some(div), ids.into_iter().filter_map(index_of).collect()))
+                .collect();
+            let rest: Vec<usize> = all().into_iter()
+                .filter(|&i| !app.divisions.contains_key(&app.teams[i].1))
+                .collect();
+            groups.push((None, rest));
+            groups
+        }
+        TeamSort::Name => {
+            let mut ix = all();
+            ix.sort_by_cached_key(|&i| app.teams[i].1.to_lowercase());
+            vec![(None, ix)]
+        }
+        TeamSort::Id => {
+            let mut ix = all();
+            ix.sort_by_key(|&i| app.teams[i].0);
+            vec![(None, ix)]
+        }
+    };
+
+    let needle = app.team_search.trim().to_lowercase();
+    if !needle.is_empty() {
+        for (_, ix) in &mut groups {
+            ix.retain(|&i| app.teams[i].1.to_lowercase().contains(&needle));
+        }
+    }
+    groups.retain(|(_, ix)| !ix.is_empty());
+    groups
+}
+
+/// What every row needs: `order` maps display positions to `app.teams`.
+struct RowCtx<'a> {
+    order: &'a [usize],
+    logos: &'a [Option<egui::TextureHandle>],
+    show_logos: bool,
+    marks: &'a [TeamMark],
+    scroll_to_cursor: bool,
+}
+
+/// Shown beside a team for the current page.
+#[derive(Default)]
+struct TeamMark {
+    /// Cached rows naming the team; None on pages without team columns
+    rows: Option<usize>,
+    /// Last scrape failed (true) or is out of date (false), with the hover text
+    scrape: Option<(bool, String)>,
+}
+
+/// Marks per team, in `app.teams` order. Scrape marks only on pages with
+/// per-team scrape records, so league-wide pages show none.
+fn team_marks(app: &mut App) -> Vec<TeamMark> {
+    let kind = app.current_page_kind();
+    let has_team_cols = !app.current_page().team_columns().is_empty();
+    let records = app.team_scrapes.entry(kind).or_insert_with(|| store::team_scrapes(&kind));
+    let counts = app.raw_data.get(&kind)
+        .map(|r| r.team_row_counts())
+        .filter(|_| has_team_cols);
+    let age = |at: Option<std::time::SystemTime>| match at.and_then(|t| t.elapsed().ok()) {
+        Some(d) => format!("last came through {}", store::fmt_age(d)),
+        None => s!("never came through"),
+    };
+
+    app.teams.iter().map(|(id, name)| {
+        let scrape = match records.get(id) {
+            _ if records.is_empty() => None,
+            Some(t) if t.failed => Some((true, format!("Failed in the last scrape; {}", age(t.scraped_at)))),
+            Some(t) if t.scraped_at.is_some_and(|at| !store::is_stale(at, TEAM_STALE_HOURS)) => None,
+            Some(t) => Some((false, format!("Not scraped this week; {}", age(t.scraped_at)))),
+            None => Some((false, s!("Not scraped yet"))),
+        };
+        TeamMark { rows: counts.map(|c| c.get(name).copied().unwrap_or(0)), scrape }
+    }).collect()
+}
+
+/// One team in the list at display position `pos`; handles click, Ctrl and
+/// Shift, and outlines the keyboard cursor. True if the selection changed.
+fn team_row(ui: &mut egui::Ui, app: &mut App, rows: &RowCtx, pos: usize) -> bool {
+    let order = rows.order;
+    let idx = order[pos];
+    let (id, name) = app.teams[idx].clone();
+    let is_selected = app.state.gui.selected_team_ids.contains(&id);
+    let mark = &rows.marks[idx];
+    let resp = ui.horizontal(|ui| {
+        if rows.show_logos {
+            match &rows.logos[idx] {
+                Some(tex) => { ui.image((tex.id(), egui::vec2(LOGO_SIZE, LOGO_SIZE))); }
+                None => ui.add_space(LOGO_SIZE),
+            }
+        }
+        let resp = ui.selectable_label(is_selected, &name);
+        if let Some(n) = mark.rows {
+            ui.weak(n.to_string()).on_hover_text(format!("{} cached row(s) on this page", n));
+        }
+        match &mark.scrape {
+            Some((true, why)) => { ui.colored_label(ui.visuals().error_fg_color, "✖").on_hover_text(why); }
+            Some((false, why)) => { ui.colored_label(ui.visuals().warn_fg_color, "⚠").on_hover_text(why); }
+            None => {}
+        }
+        resp
+    }).inner;
+
+    if app.team_cursor == Some(pos) {
+        let stroke = ui.visuals().selection.stroke;
+        ui.painter().rect_stroke(resp.rect, 2.0, stroke, egui::StrokeKind::Outside);
+        if rows.scroll_to_cursor { resp.scroll_to_me(None); }
+    }
+
+    if !resp.clicked() || app.running() { return false; }
+    // Keys go to the cursor, not to egui's focus navigation
+    resp.surrender_focus();
+    app.team_cursor = Some(pos);
+
+    let input = ui.input(|i| i.clone());
+    let sel = &mut app.state.gui.selected_team_ids;
+    let ctrl = input.modifiers.ctrl;
+    let shift = input.modifiers.shift;
+    let range = |last: usize| if last <= pos { last..=pos } else { pos..=last };
+
+    if ctrl && shift {
+        if let Some(last) = app.last_clicked {
+            for j in range(last) {
+                let tid = app.teams[order[j]].0;
+                if !sel.contains(&tid) { sel.push(tid); }
+            }
+        } else {
+            // No anchor: fall back to ctrl-toggle on single item
+            if is_selected { sel.retain(|x| *x != id); } else { sel.push(id); }
+        }
+    } else if ctrl {
+        if is_selected { sel.retain(|x| *x != id); } else { sel.push(id); }
+    } else if shift {
+        sel.clear();
+        match app.last_clicked {
+            Some(last) => for j in range(last) { sel.push(app.teams[order[j]].0); },
+            // No anchor: behave like single click
+            None => sel.push(id),
+        }
+    } else {
+        sel.clear();
+        sel.push(id);
+    }
+    app.last_clicked = Some(pos);
+    true
 }
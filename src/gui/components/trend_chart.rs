@@ -0,0 +1,160 @@
+// src/gui/components/trend_chart.rs
+//
+// "Trend" window: pick a team (optional), a player and a stat, see the
+// stat over the Players snapshots as a line chart (one point per snapshot
+// the player is in), and save the chart as a PNG (see `actions::trend`).
+
+use eframe::egui;
+use crate::{aggregate::fmt_num, gui::{actions::trend, app::App}, trend::fmt_date};
+
+const CHART_SIZE: egui::Vec2 = egui::vec2(560.0, 260.0);
+/// Room for the value labels (left) and date labels (bottom)
+const MARGIN_LEFT: f32 = 48.0;
+const MARGIN_BOTTOM: f32 = 20.0;
+const MARGIN: f32 = 10.0;
+
+pub fn draw(ctx: &egui::Context, app: &mut App) {
+    // A screenshot asked for by Save PNG
+    let pending = app.trend.as_ref().is_some_and(|t| t.png_pending.is_some());
+    if pending {
+        let shot = ctx.input(|i| i.raw.events.iter().find_map(|e| match e {
+            egui::Event::Screenshot { image, .. } => Some(image.clone()),
+            _ => None,
+        }));
+        if let Some(image) = shot {
+            trend::write_png(app, ctx, &image);
+        }
+    }
+
+    let Some(chart) = app.trend.as_mut() else { return };
+    let mut open = true;
+    let mut save = false;
+    egui::Window::new("Trend")
+        .id(egui::Id::new("trend_chart"))
+        .open(&mut open)
+        .default_width(CHART_SIZE.x + 20.0)
+        .show(ctx, |ui| {
+            let mut teams: Vec<&str> = chart.players.iter().map(|p| p.team.as_str()).collect();
+            teams.sort_unstable();
+            teams.dedup();
+
+            ui.horizontal(|ui| {
+                let mut team = chart.team.clone();
+                egui::ComboBox::from_id_salt("trend_team")
+                    .selected_text(team.as_deref().unwrap_or("All teams"))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut team, None, "All teams");
+                        for t in &teams {
+                            ui.selectable_value(&mut team, Some(t.to_string()), *t);
+                        }
+                    });
+                if team != chart.team {
+                    // Keep the player if they're on the team, else its first
+                    let on_team = |i: &usize| team.as_ref().is_none_or(|t| chart.players[*i].team == *t);
+                    chart.player = chart.player.filter(on_team).or_else(|| (0..chart.players.len()).find(on_team));
+                    chart.team = team;
+                }
+
+                let current = chart.player.and_then(|i| chart.players.get(i)).map(|p| p.name.as_str()).unwrap_or("—");
+                egui::ComboBox::from_id_salt("trend_player")
+                    .selected_text(current)
+                    .show_ui(ui, |ui| {
+                        for (i, p) in chart.players.iter().enumerate() {
+                            if chart.team.as_ref().is_some_and(|t| p.team != *t) { continue; }
+                            let label = if chart.team.is_some() { p.name.clone() } else { format!("{} ({})", p.name, p.team) };
+                            ui.selectable_value(&mut chart.player, Some(i), label);
+                        }
+                    });
+
+                egui::ComboBox::from_id_salt("trend_stat")
+                    .selected_text(chart.stat.as_str())
+                    .show_ui(ui, |ui| {
+                        for s in &chart.stats {
+                            ui.selectable_value(&mut chart.stat, s.clone(), s);
+                        }
+                    });
+            });
+            ui.separator();
+
+            if chart.snapshots.is_empty() {
+                chart.chart_rect = None;
+                ui.label("No Players snapshots yet. Turn snapshots on (snapshots=<n> in .store/settings, \
+                    or --snapshots <n> on the command line) and each Players scrape adds a point.");
+                return;
+            }
+            let points = chart.series();
+            if points.is_empty() {
+                chart.chart_rect = None;
+                ui.label(format!("No {} values for this player in the {} snapshot(s).", chart.stat, chart.snapshots.len()));
+                return;
+            }
+            chart.chart_rect = Some(draw_chart(ui, &points));
+            ui.label(format!("{} point(s) from {} snapshot(s)", points.len(), chart.snapshots.len()));
+
+            ui.separator();
+            save = ui.button("Save PNG")
+                .on_hover_text("Save the chart next to the Players exports (charts folder)")
+                .clicked();
+        });
+
+    if save { trend::save_png(app, ctx); }
+    if !open {
+        app.trend = None;
+    }
+}
+
+/// Line chart of (time, value) points; returns the rect it takes.
+fn draw_chart(ui: &mut egui::Ui, points: &[(u64, f64)]) -> egui::Rect {
+    let (rect, response) = ui.allocate_exact_size(CHART_SIZE, egui::Sense::hover());
+    let visuals = ui.visuals();
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, visuals.extreme_bg_color);
+
+    let plot = egui::Rect::from_min_max(
+        rect.min + egui::vec2(MARGIN_LEFT, MARGIN),
+        rect.max - egui::vec2(MARGIN, MARGIN_BOTTOM),
+    );
+    let (t0, t1) = (points[0].0, points[points.len() - 1].0);
+    let (mut lo, mut hi) = points.iter().fold((f64::MAX, f64::MIN), |(lo, hi), &(_, y)| (lo.min(y), hi.max(y)));
+    if lo == hi { lo -= 1.0; hi += 1.0; }
+
+    // One point: in the middle
+    let x_of = |t: u64| if t1 == t0 { plot.center().x } else {
+        plot.left() + plot.width() * ((t - t0) as f32 / (t1 - t0) as f32)
+    };
+    let y_of = |y: f64| plot.bottom() - plot.height() * ((y - lo) / (hi - lo)) as f32;
+
+    let grid = egui::Stroke::new(1.0, visuals.weak_text_color().gamma_multiply(0.3));
+    let font = egui::FontId::proportional(11.0);
+    let text = visuals.text_color();
+    for y in [lo, (lo + hi) / 2.0, hi] {
+        let py = y_of(y);
+        painter.hline(plot.x_range(), py, grid);
+        painter.text(egui::pos2(plot.left() - 4.0, py), egui::Align2::RIGHT_CENTER, fmt_num(y), font.clone(), text);
+    }
+    let date_y = plot.bottom() + 4.0;
+    painter.text(egui::pos2(plot.left(), date_y), egui::Align2::LEFT_TOP, fmt_date(t0), font.clone(), text);
+    if t1 != t0 {
+        painter.text(egui::pos2(plot.right(), date_y), egui::Align2::RIGHT_TOP, fmt_date(t1), font.clone(), text);
+    }
+
+    let line = egui::Stroke::new(2.0, visuals.selection.bg_fill);
+    let pos: Vec<egui::Pos2> = points.iter().map(|&(t, y)| egui::pos2(x_of(t), y_of(y))).collect();
+    painter.add(egui::Shape::line(pos.clone(), line));
+    for p in &pos {
+        painter.circle_filled(*p, 3.5, line.color);
+    }
+
+    // Nearest point (by x) under the pointer
+    if let Some(at) = response.hover_pos() {
+        let nearest = (0..pos.len()).min_by(|&a, &b| (pos[a].x - at.x).abs().total_cmp(&(pos[b].x - at.x).abs()));
+        if let Some(i) = nearest {
+            painter.circle_stroke(pos[i], 6.0, egui::Stroke::new(1.5, text));
+            let (t, y) = points[i];
+            response.on_hover_ui_at_pointer(|ui| {
+                ui.label(format!("{}: {}", fmt_date(t), fmt_num(y)));
+            });
+        }
+    }
+    rect
+}
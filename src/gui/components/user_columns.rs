@@ -0,0 +1,65 @@
+// src/gui/components/user_columns.rs
+//
+// "User columns" window: the current page's formula columns, one per row
+// with its name and formula, and what's wrong with it if anything.
+
+use eframe::egui;
+use crate::gui::{actions::user_columns, app::App};
+
+pub fn draw(ctx: &egui::Context, app: &mut App) {
+    let Some(editor) = app.user_columns.as_mut() else { return };
+
+    let mut open = true;
+    let mut save = false;
+    egui::Window::new(format!("User columns — {}", editor.kind))
+        .id(egui::Id::new("user_columns"))
+        .open(&mut open)
+        .default_width(460.0)
+        .show(ctx, |ui| {
+            ui.label("Columns computed from this page's others, e.g. Kills / Games, or \
+                Name & \" (\" & Team & \")\". Names in [brackets] when they aren't one word ([#], [Opp W]); \
+                + - * / and & (joins text). Added in name order, after the page's columns.");
+            ui.separator();
+
+            let mut remove = None;
+            egui::ScrollArea::vertical()
+                .id_salt("user_columns_scroll")
+                .max_height(320.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("user_columns_grid").striped(true).show(ui, |ui| {
+                        for i in 0..editor.rows.len() {
+                            let problem = editor.problem(i);
+                            let (name, formula) = &mut editor.rows[i];
+                            ui.add(egui::TextEdit::singleline(name).hint_text("Name").desired_width(100.0));
+                            ui.add(egui::TextEdit::singleline(formula).hint_text("Formula").desired_width(220.0));
+                            match problem {
+                                Some(p) => { ui.colored_label(ui.visuals().error_fg_color, "⚠").on_hover_text(p); }
+                                None => { ui.label("✔"); }
+                            }
+                            if ui.small_button("✖").on_hover_text("Remove (Save to apply)").clicked() {
+                                remove = Some(i);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+            if let Some(i) = remove {
+                editor.rows.remove(i);
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("+ Column").clicked() {
+                    editor.rows.push((String::new(), String::new()));
+                }
+                save = ui.button("Save").clicked();
+            });
+        });
+
+    if save {
+        user_columns::save(app);
+    }
+    if !open {
+        app.user_columns = None;
+    }
+}
@@ -5,5 +5,7 @@ pub mod actions;
 pub mod router;
 pub mod pages;
 pub mod progress;
+pub mod shortcuts;
+pub mod taskbar;
 
 pub use app::run;
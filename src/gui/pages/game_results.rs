@@ -1,21 +1,64 @@
 // src/gui/pages/game_results.rs
-use std::error::Error;
+use crate::error::Error;
 use std::collections::{HashMap, HashSet};
 use eframe::egui;
 
 use crate::config::options::PageKind;
-use crate::config::state::AppState;
+use crate::config::state::{AppState, GameStatus};
 use crate::progress::Progress;
 use crate::store::DataSet;
-use crate::scrape;
+use crate::{get_teams, scrape};
+use crate::schema::{self, col_index, GAME_RESULTS};
 
-use super::Page;
+use super::{Page, RowFilter};
 
 pub struct GameResultsPage;
 
 pub static PAGE: GameResultsPage = GameResultsPage;
 
-const HEADERS: [&str; 7] = [
+const SEASON_COL: usize = col_index(&GAME_RESULTS, "S");
+const WEEK_COL: usize = col_index(&GAME_RESULTS, "W");
+const HOME_COL: usize = col_index(&GAME_RESULTS, "Home");
+const HOME_SCORE_COL: usize = col_index(&GAME_RESULTS, "H");
+const AWAY_SCORE_COL: usize = col_index(&GAME_RESULTS, "A");
+const AWAY_COL: usize = col_index(&GAME_RESULTS, "Away");
+const MATCH_ID_COL: usize = col_index(&GAME_RESULTS, "Match id");
+
+/// Both scores filled in (future fixtures have blank H/A).
+fn is_played(row: &[String]) -> bool {
+    [HOME_SCORE_COL, AWAY_SCORE_COL].iter().all(|&c| row.get(c).is_some_and(|s| !s.trim().is_empty()))
+}
+
+/// Game Results view toggles: played/upcoming, and week range (inclusive;
+/// rows without a week number are left out while a range is set).
+fn keep_game(row: &[String], status: GameStatus, weeks: Option<(u32, u32)>) -> bool {
+    let status_ok = match status {
+        GameStatus::All => true,
+        GameStatus::Played => is_played(row),
+        GameStatus::Upcoming => !is_played(row),
+    };
+    let week_ok = weeks.is_none_or(|(from, to)| {
+        row.get(WEEK_COL)
+            .and_then(|w| w.trim().parse::<u32>().ok())
+            .is_some_and(|w| (from..=to).contains(&w))
+    });
+    status_ok && week_ok
+}
+
+/// The fixture a row is: season, week, home and away team (the match id
+/// is blank until it's played).
+fn fixture_key(row: &[String]) -> Vec<String> {
+    [SEASON_COL, WEEK_COL, HOME_COL, AWAY_COL].iter()
+        .map(|&c| row.get(c).map(|v| v.trim().to_string()).unwrap_or_default())
+        .collect()
+}
+
+fn week_of(row: &[String]) -> (String, String) {
+    let cell = |c: usize| row.get(c).map(|v| v.trim().to_string()).unwrap_or_default();
+    (cell(SEASON_COL), cell(WEEK_COL))
+}
+
+const HEADERS: [&str; GAME_RESULTS.len()] = [
     "S","W","Home","H","A","Away","Match id"
 ];
 
@@ -27,8 +70,27 @@ impl Page for GameResultsPage {
         Some(&HEADERS)
     }
 
-    // Non-numeric: 2 Home team, 5 Away team. All other columns are numeric.
-    fn non_numeric_columns(&self) -> &'static [usize] { &[2, 5] }
+    // Non-numeric: Home team, Away team. All other columns are numeric.
+    fn non_numeric_columns(&self) -> &'static [usize] { &[HOME_COL, AWAY_COL] }
+
+    // Season + Week + Home team + Away team identify a fixture (match id may be blank).
+    fn row_key_columns(&self) -> &'static [usize] { &[SEASON_COL, WEEK_COL, HOME_COL, AWAY_COL] }
+
+    fn team_columns(&self) -> &'static [usize] { &[HOME_COL, AWAY_COL] }
+
+    /// The match report for played games, else the home team's page.
+    fn source_path(&self, _headers: Option<&[String]>, row: &[String], teams: &[(u32, String)]) -> Option<String> {
+        row.get(MATCH_ID_COL).and_then(|id| super::game_path(id))
+            .or_else(|| super::team_id_by_name(teams, row.get(HOME_COL)?).map(super::team_path))
+    }
+
+    /// Match id → match report; Home/Away → the team's page.
+    fn cell_link(&self, _headers: Option<&[String]>, row: &[String], ci: usize, teams: &[(u32, String)]) -> Option<String> {
+        match ci {
+            MATCH_ID_COL => super::game_path(row.get(MATCH_ID_COL)?),
+            _ => super::team_cell_link(self.team_columns(), row, ci, teams),
+        }
+    }
 
     fn preferred_column_widths(&self) -> Option<&'static [usize]> {
         // Season, Week, Home Team, Home, Away, Away Team, Match id
@@ -44,14 +106,44 @@ impl Page for GameResultsPage {
                 "Include match id")
                 .changed();
         });
+        ui.horizontal(|ui| {
+            let status = &mut state.gui.game_results_status;
+            changed |= ui.selectable_value(status, GameStatus::All, "All games").changed();
+            changed |= ui.selectable_value(status, GameStatus::Played, "Played")
+                .on_hover_text("Only games with both scores in (table, Copy and Export)")
+                .changed();
+            changed |= ui.selectable_value(status, GameStatus::Upcoming, "Upcoming")
+                .on_hover_text("Only fixtures not played yet (blank scores)")
+                .changed();
+
+            ui.separator();
+            let weeks = &mut state.gui.game_results_weeks;
+            let mut on = weeks.is_some();
+            if ui.checkbox(&mut on, "Weeks").changed() {
+                *weeks = on.then_some((1, 99));
+                changed = true;
+            }
+            if let Some((from, to)) = weeks {
+                changed |= ui.add(egui::DragValue::new(from).range(1..=99)).changed();
+                ui.label("to");
+                changed |= ui.add(egui::DragValue::new(to).range(1..=99)).changed();
+                if *to < *from { *to = *from; }
+            }
+        });
         changed
     }
 
+    fn row_filter(&self, state: &AppState) -> Option<RowFilter> {
+        let (status, weeks) = (state.gui.game_results_status, state.gui.game_results_weeks);
+        if status == GameStatus::All && weeks.is_none() { return None; }
+        Some(Box::new(move |r| keep_game(r, status, weeks)))
+    }
+
     fn scrape(
         &self,
         _state: &AppState,
         mut progress: Option<&mut dyn Progress>,
-    ) -> Result<DataSet, Box<dyn Error>> {
+    ) -> Result<DataSet, Error> {
         if let Some(p) = progress.as_deref_mut() {
             p.begin(0);
         }
@@ -60,10 +152,44 @@ impl Page for GameResultsPage {
 
     
 
-    /// Game Results: the scrape is whole-season, so accept it atomically.
-    fn merge(&self, into: &mut DataSet, new: DataSet) {
-        // We already validated `new` in actions::scrape before calling merge.
-        *into = new;
+    /// Game Results: fixture by fixture (season, week, home, away), so a
+    /// week the site renders wrong or a page that loads half-way can't wipe
+    /// good data. Completed games never go back to blank scores; weeks the
+    /// scrape didn't show are kept, other seasons' rows dropped.
+    fn merge(&self, into: &mut DataSet, mut new: DataSet) {
+        if new.headers.is_some() {
+            into.headers = new.headers.take();
+        }
+        let old: HashMap<Vec<String>, &Vec<String>> = into.rows.iter()
+            .map(|r| (fixture_key(r), r))
+            .collect();
+
+        let mut kept_scores = 0;
+        let mut rows: Vec<Vec<String>> = new.rows.into_iter()
+            .map(|r| match old.get(&fixture_key(&r)) {
+                Some(&prev) if is_played(prev) && !is_played(&r) => { kept_scores += 1; prev.clone() }
+                _ => r,
+            })
+            .collect();
+
+        let seasons: HashSet<&str> = rows.iter().filter_map(|r| r.get(SEASON_COL)).map(|s| s.trim()).collect();
+        let weeks: HashSet<(String, String)> = rows.iter().map(|r| week_of(r)).collect();
+        let missing: Vec<Vec<String>> = into.rows.iter()
+            .filter(|r| r.get(SEASON_COL).is_some_and(|s| seasons.contains(s.trim())))
+            .filter(|r| !weeks.contains(&week_of(r)))
+            .cloned()
+            .collect();
+
+        if kept_scores > 0 || !missing.is_empty() {
+            logf!("Game Results: Kept {} completed game(s) the scrape showed without scores, {} row(s) of weeks it didn't show",
+                kept_scores, missing.len());
+        }
+        rows.extend(missing);
+        rows.sort_by_key(|r| {
+            let num = |c: usize| r.get(c).and_then(|v| v.trim().parse::<u32>().ok()).unwrap_or(u32::MAX);
+            (num(SEASON_COL), num(WEEK_COL))
+        });
+        into.rows = rows;
     }
 
 
@@ -99,14 +225,11 @@ impl Page for GameResultsPage {
             .map(|(_, name)| name.as_str())
             .collect();
 
-        // 3) Columns in this page shape:
-        //    0 Season, 1 Week, 2 Home team, 3 Home result, 4 Away result, 5 Away team, 6 Match id
-        //
-        // Keep any row where either the Home team or the Away team is in the selection.
+        // 3) Keep any row where either the Home team or the Away team is in the selection.
         let ix = rows.iter().enumerate()
             .filter(|(_, r)| {
-                r.get(2).map(|s| sel.contains(s.as_str())).unwrap_or(false) ||
-                r.get(5).map(|s| sel.contains(s.as_str())).unwrap_or(false)
+                r.get(HOME_COL).map(|s| sel.contains(s.as_str())).unwrap_or(false) ||
+                r.get(AWAY_COL).map(|s| sel.contains(s.as_str())).unwrap_or(false)
             })
             .map(|(i, _)| i)
             .collect();
@@ -130,11 +253,10 @@ impl Page for GameResultsPage {
             .map(|(_, name)| name.as_str())
             .collect();
 
-        // Columns: 0 Season, 1 Week, 2 Home team, 3 Home, 4 Away, 5 Away team, 6 Match id
         let filtered: Vec<Vec<String>> = rows.iter()
             .filter(|r| {
-                r.get(2).map(|s| sel.contains(s.as_str())).unwrap_or(false)
-                || r.get(5).map(|s| sel.contains(s.as_str())).unwrap_or(false)
+                r.get(HOME_COL).map(|s| sel.contains(s.as_str())).unwrap_or(false)
+                || r.get(AWAY_COL).map(|s| sel.contains(s.as_str())).unwrap_or(false)
             })
             .cloned()
             .collect();
@@ -142,6 +264,14 @@ impl Page for GameResultsPage {
         filtered
     }
 
+    // Match id unless "Include match id"
+    fn export_excluded_columns(&self, state: &AppState, headers: Option<&[String]>) -> Vec<usize> {
+        match headers {
+            Some(h) if !state.gui.game_results_show_match_id && h.len() > MATCH_ID_COL => vec![MATCH_ID_COL],
+            _ => Vec::new(),
+        }
+    }
+
     fn view_for_export(
         
         &self,
@@ -152,25 +282,20 @@ impl Page for GameResultsPage {
         if state.gui.game_results_show_match_id {
             return (headers.clone(), rows.clone());
         }
-        // Drop the last column from headers + rows if present
-        let new_headers = headers.as_ref().map(|hs| {
-            let mut h = hs.clone();
-            if !h.is_empty() { h.pop(); }
-            h
-        });
-        let new_rows = rows.iter().map(|r| {
+        // Drop the Match id column from headers + rows if present
+        let without_id = |r: &Vec<String>| {
             let mut c = r.clone();
-            if !c.is_empty() { c.pop(); }
+            if c.len() > MATCH_ID_COL { c.remove(MATCH_ID_COL); }
             c
-        }).collect();
+        };
+        let new_headers = headers.as_ref().map(without_id);
+        let new_rows = rows.iter().map(without_id).collect();
         (new_headers, new_rows)
     }
 
+    /// The Game Results schema (headers may be missing in old caches).
     fn validate_cache(&self, ds: &DataSet) -> bool {
-        // Allow blank. Otherwise require exactly 7 columns in headers+rows.
-        let hdr_ok = ds.headers.as_ref().map(|h| h.len() == 7).unwrap_or(true);
-        let rows_ok = ds.rows.iter().all(|r| r.len() == 7);
-        hdr_ok && rows_ok
+        schema::validate(PageKind::GameResults, ds).is_ok()
     }
 
     fn validate_scrape(
@@ -202,19 +327,16 @@ impl Page for GameResultsPage {
         // Unordered “game signature” per (S,W): (min(team), max(team))
         let mut seen_game: HashSet<(String, String, String, String)> = HashSet::new();
 
+        // Shape, team names filled, numbers where numeric
+        schema::validate(PageKind::GameResults, new)?;
+
         for r in &new.rows {
-            if r.len() < 7 {
-                return Err("Row has fewer than 7 columns (S,W,Home team,Home,Away,Away team,Match id)".into());
-            }
-            let s     = r[0].trim().to_string();
-            let w     = r[1].trim().to_string();
-            let home  = r[2].trim();
-            let away  = r[5].trim();
-            let mid   = r[6].trim();
-
-            if home.is_empty() || away.is_empty() {
-                return Err(format!("Empty team name in S={} W={}", s, w));
-            }
+            let s     = r[SEASON_COL].trim().to_string();
+            let w     = r[WEEK_COL].trim().to_string();
+            let home  = r[HOME_COL].trim();
+            let away  = r[AWAY_COL].trim();
+            let mid   = r[MATCH_ID_COL].trim();
+
             if home == away {
                 return Err(format!("Home==Away in S={}, W={} ({})", s, w, home));
             }
@@ -235,14 +357,14 @@ impl Page for GameResultsPage {
             let entry = week_mask.entry((s, w)).or_insert(0u32);
 
             let hb = *bit_of.get(home)
-                .ok_or_else(|| format!("Unknown team name '{}' in results", home))?;
+                .ok_or_else(|| get_teams::unknown_team(home, "results"))?;
             if (*entry & hb) != 0 {
                 return Err(format!("Team '{}' appears twice in the same week", home));
             }
             *entry |= hb;
 
             let ab = *bit_of.get(away)
-                .ok_or_else(|| format!("Unknown team name '{}' in results", away))?;
+                .ok_or_else(|| get_teams::unknown_team(away, "results"))?;
             if (*entry & ab) != 0 {
                 return Err(format!("Team '{}' appears twice in the same week", away));
             }
@@ -262,3 +384,35 @@ impl Page for GameResultsPage {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ds(rows: &[[&str; 7]]) -> DataSet {
+        DataSet {
+            headers: Some(HEADERS.iter().map(|h| h.to_string()).collect()),
+            rows: rows.iter().map(|r| r.iter().map(|c| c.to_string()).collect()).collect(),
+        }
+    }
+
+    #[test]
+    fn merge_keeps_completed_games_and_unseen_weeks() {
+        let w1 = ["7", "1", "Reds", "2", "1", "Blues", "101"];
+        let w2 = ["7", "2", "Blues", "0", "0", "Reds", "102"];
+        let w2_blank = ["7", "2", "Blues", "", "", "Reds", ""];
+        let w3 = ["7", "3", "Reds", "", "", "Blues", ""];
+        let w3_played = ["7", "3", "Reds", "3", "3", "Blues", "103"];
+        let old_season = ["6", "9", "Reds", "1", "0", "Blues", "90"];
+
+        let mut cache = ds(&[old_season, w1, w2, w3]);
+        // Week 1 missing, week 2 rendered without scores, week 3 played since
+        PAGE.merge(&mut cache, ds(&[w2_blank, w3_played]));
+        assert_eq!(cache.rows, ds(&[w1, w2, w3_played]).rows);
+
+        // A new season replaces the old one
+        let next = ["8", "1", "Blues", "", "", "Reds", ""];
+        PAGE.merge(&mut cache, ds(&[next]));
+        assert_eq!(cache.rows, ds(&[next]).rows);
+    }
+}
@@ -1,38 +1,52 @@
 // src/gui/pages/injuries.rs
-use std::error::Error;
+use crate::error::Error;
 use std::collections::HashSet;
 
+use eframe::egui;
+
 use crate::{
-    config::options::PageKind,
+    config::options::{InjuryParser, PageKind},
     config::state::AppState,
     progress::Progress,
-    scrape,
+    scrape::{self, INJURY_HEADERS},
     store::DataSet,
 };
 
 pub struct InjuriesPage;
 pub static PAGE: InjuriesPage = InjuriesPage;
 
-const HEADERS: [&str; 12] = [
-    "S","W","Victim Team","Victim","DUR","SR0","SR1","Type","Offender Team","Offender","BRU","Bounty"
-];
-
 impl super::Page for InjuriesPage {
     fn title(&self) -> &'static str { "Injuries" }
     fn kind(&self) -> PageKind { PageKind::Injuries }
 
-    fn default_headers(&self) -> Option<&'static [&'static str]> { Some(&HEADERS) }
+    fn default_headers(&self) -> Option<&'static [&'static str]> { Some(&INJURY_HEADERS) }
 
-    // Non-numeric columns for alignment: teams, names, type, bounty
-    fn non_numeric_columns(&self) -> &'static [usize] { &[2,3,7,8,9,11] }
+    // Non-numeric columns for alignment: teams, names, type, bounty, event
+    fn non_numeric_columns(&self) -> &'static [usize] { &[2,3,7,8,9,11,12] }
 
     fn preferred_column_widths(&self) -> Option<&'static [usize]> {
-        Some(&[20, 20, 160, 160, 30, 30, 30, 140, 160, 160, 30, 120])
+        Some(&[20, 20, 160, 160, 30, 30, 30, 140, 160, 160, 30, 120, 60])
+    }
+
+    // Victim team, offender team
+    fn team_columns(&self) -> &'static [usize] { &[2, 8] }
+
+    fn draw_controls(&self, ui: &mut egui::Ui, state: &mut AppState) -> bool {
+        let parser = &mut state.options.scrape.injury_parser;
+        ui.horizontal(|ui| {
+            ui.label("Parser:").on_hover_text("Which injury-line parser the next scrape uses; they should agree");
+            for p in InjuryParser::ALL {
+                if ui.selectable_value(parser, p, p.to_string()).changed() {
+                    logf!("UI: Injury parser → {}", p);
+                }
+            }
+        });
+        false
     }
 
-    fn scrape(&self, _state: &AppState, mut progress: Option<&mut dyn Progress>) -> Result<DataSet, Box<dyn Error>> {
+    fn scrape(&self, state: &AppState, mut progress: Option<&mut dyn Progress>) -> Result<DataSet, Error> {
         if let Some(p) = progress.as_deref_mut() { p.begin(0); }
-        scrape::collect_injuries(progress)
+        scrape::collect_injuries(&state.options.scrape, progress)
     }
 
     fn filter_row_indices_for_selection(
@@ -0,0 +1,87 @@
+// src/gui/pages/injury_summary.rs
+//
+// Injuries per team, type and week with the SR lost (see `injury_summary`).
+// Derived from the Injuries data, never scraped: "Scrape" recomputes it
+// from the cache, and it's refreshed after every Injuries scrape. The bars
+// above the table are `components::injury_bars`.
+use crate::error::Error;
+
+use crate::{
+    config::options::PageKind,
+    config::state::AppState,
+    injury_summary::{GROUP_COL, GROUP_TEAM, KEY_COL},
+    progress::Progress,
+    scrape,
+    store::DataSet,
+};
+
+pub struct InjurySummaryPage;
+pub static PAGE: InjurySummaryPage = InjurySummaryPage;
+
+impl super::Page for InjurySummaryPage {
+    fn title(&self) -> &'static str { "Injury Summary" }
+    fn kind(&self) -> PageKind { PageKind::InjurySummary }
+
+    fn default_headers(&self) -> Option<&'static [&'static str]> {
+        Some(&["Group", "Key", "Injuries", "SR lost"])
+    }
+
+    fn non_numeric_columns(&self) -> &'static [usize] { &[GROUP_COL, KEY_COL] }
+
+    fn row_key_columns(&self) -> &'static [usize] { &[GROUP_COL, KEY_COL] }
+
+    fn preferred_column_widths(&self) -> Option<&'static [usize]> {
+        Some(&[60, 200, 60, 60])
+    }
+
+    /// Team rows → the team's page.
+    fn cell_link(&self, _headers: Option<&[String]>, row: &[String], ci: usize, teams: &[(u32, String)]) -> Option<String> {
+        let team_row = row.get(GROUP_COL).is_some_and(|g| g == GROUP_TEAM);
+        if ci != KEY_COL || !team_row { return None; }
+        super::team_id_by_name(teams, row.get(KEY_COL)?).map(super::team_path)
+    }
+
+    fn scrape(&self, _state: &AppState, mut progress: Option<&mut dyn Progress>) -> Result<DataSet, Error> {
+        if let Some(p) = progress.as_deref_mut() { p.begin(0); }
+        scrape::collect_injury_summary(progress)
+    }
+
+    /// The selected teams' Team rows; the type, week and total rows are
+    /// league-wide and always shown.
+    fn filter_row_indices_for_selection(
+        &self,
+        selected_ids: &[u32],
+        teams: &[(u32, String)],
+        rows: &Vec<Vec<String>>,
+    ) -> Option<Vec<usize>> {
+        let sel: Vec<&str> = selected_ids.iter()
+            .filter_map(|id| teams.iter().find(|(tid, _)| tid == id))
+            .map(|(_, name)| name.as_str())
+            .collect();
+        let ix = rows.iter().enumerate()
+            .filter(|(_, r)| {
+                r.get(GROUP_COL).is_none_or(|g| g != GROUP_TEAM)
+                    || r.get(KEY_COL).is_some_and(|t| sel.contains(&t.as_str()))
+            })
+            .map(|(i, _)| i)
+            .collect();
+        Some(ix)
+    }
+
+    fn filter_rows_for_selection(
+        &self,
+        selected_ids: &[u32],
+        teams: &[(u32, String)],
+        rows: &Vec<Vec<String>>,
+    ) -> Vec<Vec<String>> {
+        let ix = self.filter_row_indices_for_selection(selected_ids, teams, rows).unwrap_or_default();
+        ix.into_iter().map(|i| rows[i].clone()).collect()
+    }
+
+    fn validate_cache(&self, ds: &DataSet) -> bool {
+        ds.headers.is_some() && crate::schema::validate(PageKind::InjurySummary, ds).is_ok()
+    }
+
+    // League-wide counts, not per team
+    fn per_team_applicable(&self) -> bool { false }
+}
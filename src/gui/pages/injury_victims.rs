@@ -0,0 +1,102 @@
+// src/gui/pages/injury_victims.rs
+//
+// Injuries joined to Players on the victim (see `join::injuries_with_victims`).
+// Derived, never scraped: "Scrape" recomputes it from the cache, and it's
+// refreshed after every Injuries or Players scrape.
+use crate::error::Error;
+use std::collections::HashSet;
+
+use crate::{
+    config::options::PageKind,
+    config::state::AppState,
+    progress::Progress,
+    scrape,
+    store::DataSet,
+};
+
+pub struct InjuryVictimsPage;
+pub static PAGE: InjuryVictimsPage = InjuryVictimsPage;
+
+// The injury columns, then the victim's; Race, TV, OVR and id follow when Players has them
+const HEADERS: [&str; 14] = [
+    "S","W","Victim Team","Victim","DUR","SR0","SR1","Type","Offender Team","Offender","BRU","Bounty","Event",
+    "Victim #",
+];
+const VICTIM_TEAM_COL: usize = 2;
+const OFFENDER_TEAM_COL: usize = 8;
+
+impl super::Page for InjuryVictimsPage {
+    fn title(&self) -> &'static str { "Injury Victims" }
+    fn kind(&self) -> PageKind { PageKind::InjuryVictims }
+
+    fn default_headers(&self) -> Option<&'static [&'static str]> { Some(&HEADERS) }
+
+    // Teams, names, type, bounty, event, victim #, race
+    fn non_numeric_columns(&self) -> &'static [usize] { &[2, 3, 7, 8, 9, 11, 12, 13, 14] }
+
+    fn preferred_column_widths(&self) -> Option<&'static [usize]> {
+        Some(&[20, 20, 160, 160, 30, 30, 30, 140, 160, 160, 30, 120, 60, 40, 120])
+    }
+
+    fn team_columns(&self) -> &'static [usize] { &[VICTIM_TEAM_COL, OFFENDER_TEAM_COL] }
+
+    /// The victim's page when they were matched to a player, else their team's.
+    fn source_path(&self, headers: Option<&[String]>, row: &[String], teams: &[(u32, String)]) -> Option<String> {
+        victim_id(headers, row).and_then(super::player_path)
+            .or_else(|| super::team_id_by_name(teams, row.get(VICTIM_TEAM_COL)?).map(super::team_path))
+    }
+
+    /// Victim → their player page; teams → the team's.
+    fn cell_link(&self, headers: Option<&[String]>, row: &[String], ci: usize, teams: &[(u32, String)]) -> Option<String> {
+        match ci {
+            3 => super::player_path(victim_id(headers, row)?),
+            _ => super::team_cell_link(self.team_columns(), row, ci, teams),
+        }
+    }
+
+    fn scrape(&self, _state: &AppState, mut progress: Option<&mut dyn Progress>) -> Result<DataSet, Error> {
+        if let Some(p) = progress.as_deref_mut() { p.begin(0); }
+        scrape::collect_injury_victims(progress)
+    }
+
+    fn filter_row_indices_for_selection(
+        &self,
+        selected_ids: &[u32],
+        teams: &[(u32, String)],
+        rows: &Vec<Vec<String>>,
+    ) -> Option<Vec<usize>> {
+        if selected_ids.is_empty() { return Some(Vec::new()); }
+        if selected_ids.len() == teams.len() { return Some((0..rows.len()).collect()); }
+        let sel: HashSet<&str> = selected_ids
+            .iter()
+            .filter_map(|id| teams.iter().find(|(tid, _)| tid == id))
+            .map(|(_, name)| name.as_str())
+            .collect();
+        let ix = rows.iter().enumerate().filter(|(_, r)| {
+            r.get(VICTIM_TEAM_COL).is_some_and(|s| sel.contains(s.as_str())) ||
+            r.get(OFFENDER_TEAM_COL).is_some_and(|s| sel.contains(s.as_str()))
+        }).map(|(i, _)| i).collect();
+        Some(ix)
+    }
+
+    fn filter_rows_for_selection(
+        &self,
+        selected_ids: &[u32],
+        teams: &[(u32, String)],
+        rows: &Vec<Vec<String>>,
+    ) -> Vec<Vec<String>> {
+        let ix = self.filter_row_indices_for_selection(selected_ids, teams, rows).unwrap_or_default();
+        ix.into_iter().map(|i| rows[i].clone()).collect()
+    }
+
+    /// Starts with the injury columns and "Victim #"; every row has a victim.
+    fn validate_cache(&self, ds: &DataSet) -> bool {
+        ds.headers.is_some() && crate::schema::validate(PageKind::InjuryVictims, ds).is_ok()
+    }
+}
+
+/// The "Victim id" cell, when the victim was matched.
+fn victim_id<'a>(headers: Option<&[String]>, row: &'a [String]) -> Option<&'a str> {
+    let ci = headers?.iter().position(|h| h == "Victim id")?;
+    row.get(ci).map(String::as_str).filter(|id| !id.is_empty())
+}
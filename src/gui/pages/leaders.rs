@@ -0,0 +1,111 @@
+// src/gui/pages/leaders.rs
+//
+// Top players per stat (see `leaders`). Derived from the Players data,
+// never scraped: "Scrape" recomputes it from the cache, and it's refreshed
+// after every Players scrape. The cache keeps the top `leaders::MAX_TOP`;
+// the Top N control filters those, so changing it needs no recompute.
+use crate::error::Error;
+
+use eframe::egui;
+
+use crate::{
+    config::options::PageKind,
+    config::state::AppState,
+    leaders::{self, RANK_COL},
+    progress::Progress,
+    schema::{col_index, LEADERS},
+    scrape,
+    store::DataSet,
+};
+
+use super::RowFilter;
+
+pub struct LeadersPage;
+pub static PAGE: LeadersPage = LeadersPage;
+
+const STAT_COL: usize = col_index(&LEADERS, "Stat");
+const PLAYER_COL: usize = col_index(&LEADERS, "Player");
+const TEAM_COL: usize = col_index(&LEADERS, "Team");
+
+impl super::Page for LeadersPage {
+    fn title(&self) -> &'static str { "Leaders" }
+    fn kind(&self) -> PageKind { PageKind::Leaders }
+
+    fn default_headers(&self) -> Option<&'static [&'static str]> {
+        Some(&["Stat", "Rank", "Player", "Team", "Value"])
+    }
+
+    fn non_numeric_columns(&self) -> &'static [usize] { &[STAT_COL, PLAYER_COL, TEAM_COL] }
+
+    fn row_key_columns(&self) -> &'static [usize] { &[STAT_COL, PLAYER_COL, TEAM_COL] }
+
+    fn team_columns(&self) -> &'static [usize] { &[TEAM_COL] }
+
+    fn preferred_column_widths(&self) -> Option<&'static [usize]> {
+        Some(&[80, 40, 180, 200, 60])
+    }
+
+    fn draw_controls(&self, ui: &mut egui::Ui, state: &mut AppState) -> bool {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label("Top");
+            let top = ui.add(egui::DragValue::new(&mut state.gui.leaders_top).range(1..=leaders::MAX_TOP))
+                .on_hover_text("Players per stat (table, Copy and Export); ties at the cutoff are all shown");
+            if top.changed() {
+                if let Err(e) = crate::store::save_leaders_top(state.gui.leaders_top) {
+                    loge!("Settings: Could not save leaders top: {}", e);
+                }
+                changed = true;
+            }
+        });
+        changed
+    }
+
+    fn row_filter(&self, state: &AppState) -> Option<RowFilter> {
+        let top = state.gui.leaders_top;
+        Some(Box::new(move |r| {
+            r.get(RANK_COL).and_then(|v| v.trim().parse::<usize>().ok()).is_some_and(|rank| rank <= top)
+        }))
+    }
+
+    fn scrape(&self, _state: &AppState, mut progress: Option<&mut dyn Progress>) -> Result<DataSet, Error> {
+        if let Some(p) = progress.as_deref_mut() { p.begin(0); }
+        scrape::collect_leaders(leaders::MAX_TOP, progress)
+    }
+
+    fn filter_row_indices_for_selection(
+        &self,
+        selected_ids: &[u32],
+        teams: &[(u32, String)],
+        rows: &Vec<Vec<String>>,
+    ) -> Option<Vec<usize>> {
+        let sel: Vec<&str> = selected_ids.iter()
+            .filter_map(|id| teams.iter().find(|(tid, _)| tid == id))
+            .map(|(_, name)| name.as_str())
+            .collect();
+        let ix = rows.iter().enumerate()
+            .filter(|(_, r)| r.get(TEAM_COL).is_some_and(|t| sel.contains(&t.as_str())))
+            .map(|(i, _)| i)
+            .collect();
+        Some(ix)
+    }
+
+    fn filter_rows_for_selection(
+        &self,
+        selected_ids: &[u32],
+        teams: &[(u32, String)],
+        rows: &Vec<Vec<String>>,
+    ) -> Vec<Vec<String>> {
+        let ix = self.filter_row_indices_for_selection(selected_ids, teams, rows).unwrap_or_default();
+        ix.into_iter().map(|i| rows[i].clone()).collect()
+    }
+
+    /// Our own header set (per the schema); ranks league-wide, whichever
+    /// teams are selected.
+    fn validate_cache(&self, ds: &DataSet) -> bool {
+        ds.headers.is_some() && crate::schema::validate(PageKind::Leaders, ds).is_ok()
+    }
+
+    // A league-wide ranking, not a roster
+    fn per_team_applicable(&self) -> bool { false }
+}
@@ -1,6 +1,6 @@
 // src/gui/pages/mod.rs
 use eframe::egui;
-use std::error::Error;
+use crate::error::Error;
 
 use crate::{
     config::{ 
@@ -13,6 +13,18 @@ use crate::{
 pub mod players;
 pub mod game_results;
 pub mod injuries;
+pub mod standings;
+pub mod results_table;
+pub mod schedule_metrics;
+pub mod transactions;
+pub mod team_summary;
+pub mod leaders;
+pub mod team_info;
+pub mod injury_victims;
+pub mod injury_summary;
+
+/// Row predicate from a page's view toggles (see `Page::row_filter`).
+pub type RowFilter = Box<dyn Fn(&[String]) -> bool>;
 
 /// Optional column hints if you later want per-page sizing.
 #[derive(Default, Debug, Clone, Copy)]
@@ -35,6 +47,27 @@ pub trait Page: Send + Sync + 'static {
     /// Default: none (treat all columns as numeric).
     fn non_numeric_columns(&self) -> &'static [usize] { &[] }
 
+    /// Columns identifying the same row across scrapes (diff report, delta view).
+    /// Empty = the whole row is the key.
+    fn row_key_columns(&self) -> &'static [usize] { &[] }
+
+    /// Column holding a stable site id (e.g. Players → player id), if this
+    /// dataset has one. Preferred over `row_key_columns` when both sides have it.
+    fn stable_id_column(&self, _headers: Option<&[String]>) -> Option<usize> { None }
+
+    /// Columns hidden in the table (and dropped from Copy/Export by `view_for_export`).
+    fn hidden_columns(&self, _state: &AppState, _headers: Option<&[String]>) -> Vec<usize> { Vec::new() }
+
+    /// Columns Copy/Export leave out: the hidden ones plus export-only toggles
+    /// (used with "As shown", where columns are picked by index).
+    fn export_excluded_columns(&self, state: &AppState, headers: Option<&[String]>) -> Vec<usize> {
+        self.hidden_columns(state, headers)
+    }
+
+    /// Whether the page offers the "delta since last scrape" view
+    /// (needs numeric stat columns and non-empty `row_key_columns`).
+    fn has_delta_view(&self) -> bool { false }
+
     /// Draw page-specific controls above the table. 
     /// Return true if any control changed, so the app can rebuild the view.
     fn draw_controls(&self, _ui: &mut egui::Ui, _state: &mut AppState) -> bool { false }
@@ -44,7 +77,7 @@ pub trait Page: Send + Sync + 'static {
         &self,
         _state: &AppState,
         progress: Option<&mut dyn Progress>,
-    ) -> Result<DataSet, Box<dyn Error>>;
+    ) -> Result<DataSet, Error>;
 
     /// Merge freshly scraped `new` rows into `into` (canonical cache).
     /// Default behavior: replace everything.
@@ -66,6 +99,10 @@ pub trait Page: Send + Sync + 'static {
         _rows: &Vec<Vec<String>>,
     ) -> Option<Vec<usize>> { None }
 
+    /// Rows the page's own view toggles leave out (e.g. Game Results week
+    /// range). Applied after team selection, for the table, Copy and Export.
+    fn row_filter(&self, _state: &AppState) -> Option<RowFilter> { None }
+
     /// Optional: transform headers/rows for export/copy (e.g. hide columns)
     fn view_for_export(
         &self,
@@ -90,4 +127,59 @@ pub trait Page: Send + Sync + 'static {
     /// Whether "per-team export" is applicable on this page.
     /// If false, the checkbox is grayed out.
     fn per_team_applicable(&self) -> bool { true }
+
+    /// Columns holding team names (row menu "Only <team>", source links).
+    fn team_columns(&self) -> &'static [usize] { &[] }
+
+    /// Site page a row came from, as a path for `net::full_url`
+    /// (e.g. `player.php?i=12`). Default: the page of the row's first team.
+    fn source_path(&self, _headers: Option<&[String]>, row: &[String], teams: &[(u32, String)]) -> Option<String> {
+        self.team_columns().iter().find_map(|&c| team_id_by_name(teams, row.get(c)?)).map(team_path)
+    }
+
+    /// Site page the cell in column `ci` stands for (team → `team.php`,
+    /// match id → `game.php`, …); the table shows these cells as links.
+    /// Default: team pages for the `team_columns`.
+    fn cell_link(&self, _headers: Option<&[String]>, row: &[String], ci: usize, teams: &[(u32, String)]) -> Option<String> {
+        team_cell_link(self.team_columns(), row, ci, teams)
+    }
+}
+
+/// `team.php?i=<id>` when `ci` is one of `team_cols` and names a known team.
+pub fn team_cell_link(team_cols: &[usize], row: &[String], ci: usize, teams: &[(u32, String)]) -> Option<String> {
+    if !team_cols.contains(&ci) { return None; }
+    team_id_by_name(teams, row.get(ci)?).map(team_path)
+}
+
+/// `team.php?i=<id>`
+pub fn team_path(id: u32) -> String { format!("team.php?i={}", id) }
+
+/// `player.php?i=<id>`; None unless `id` is a number.
+pub fn player_path(id: &str) -> Option<String> {
+    id.trim().parse::<u32>().ok().map(|id| format!("player.php?i={}", id))
+}
+
+/// `game.php?i=<id>`; None unless `id` is a number (unplayed games have none).
+pub fn game_path(id: &str) -> Option<String> {
+    id.trim().parse::<u32>().ok().map(|id| format!("game.php?i={}", id))
+}
+
+/// Id of the team called `name` in the (id, name) list.
+pub fn team_id_by_name(teams: &[(u32, String)], name: &str) -> Option<u32> {
+    teams.iter().find(|(_, n)| n == name).map(|(id, _)| *id)
+}
+
+/// Key columns for matching rows of `a` against `b` (diffs, delta view):
+/// the page's stable id column when both datasets have it in the same place
+/// and every row has an id, otherwise the page's `row_key_columns`.
+pub fn row_key_between(page: &dyn Page, a: &DataSet, b: &DataSet) -> Vec<usize> {
+    let ia = page.stable_id_column(a.headers.as_deref());
+    match (ia, page.stable_id_column(b.headers.as_deref())) {
+        (Some(x), Some(y)) if x == y
+            && a.rows.iter().chain(&b.rows).all(|r| r.get(x).is_some_and(|v| !v.is_empty())) =>
+        {
+            vec![x]
+        }
+        _ => page.row_key_columns().to_vec(),
+    }
 }
@@ -1,13 +1,15 @@
 // src/gui/pages/players.rs
 use eframe::egui;
-use std::error::Error;
+use crate::error::Error;
 use std::collections::HashSet;
 
 use crate::{
     config::options::PageKind::{ self, * },
     config::state::AppState,
+    get_teams,
     progress::Progress,
-    scrape,
+    schema::{self, col_index, PLAYERS},
+    scrape::{self, PLAYER_ID_HEADER},
     store::DataSet,
 };
 
@@ -16,12 +18,68 @@ use super::{ Page };
 pub struct PlayersPage;
 pub static PAGE: PlayersPage = PlayersPage;
 
+const NAME_COL: usize = col_index(&PLAYERS, "Name");
+const NUMBER_COL: usize = col_index(&PLAYERS, "#");
+const RACE_COL: usize = col_index(&PLAYERS, "Race");
+const TEAM_COL: usize = col_index(&PLAYERS, "Team");
+
 impl Page for PlayersPage {
     fn kind(&self) -> PageKind { Players }
     fn title(&self) -> &'static str { "Players" }
 
-    // Non-numeric: 0 Name, 2 Race, 3 Team. Column 1 (Number) and 4..end are numeric.
-    fn non_numeric_columns(&self) -> &'static [usize] { &[0, 2, 3] }
+    // Non-numeric: Name, Race, Team. Number and the site columns are numeric.
+    fn non_numeric_columns(&self) -> &'static [usize] { &[NAME_COL, RACE_COL, TEAM_COL] }
+
+    // Name + Number + Team identify a player between scrapes (two players
+    // of a team can share a name; see `scrape::roster_key`).
+    fn row_key_columns(&self) -> &'static [usize] { &[NAME_COL, NUMBER_COL, TEAM_COL] }
+    fn has_delta_view(&self) -> bool { true }
+
+    // Player id (trailing column; absent in caches from before it was scraped)
+    fn stable_id_column(&self, headers: Option<&[String]>) -> Option<usize> {
+        headers?.iter().position(|h| h == PLAYER_ID_HEADER)
+    }
+
+    fn team_columns(&self) -> &'static [usize] { &[TEAM_COL] }
+
+    /// The player's page when the row has an id, else the team's.
+    fn source_path(&self, headers: Option<&[String]>, row: &[String], teams: &[(u32, String)]) -> Option<String> {
+        self.stable_id_column(headers)
+            .and_then(|c| super::player_path(row.get(c)?))
+            .or_else(|| super::team_id_by_name(teams, row.get(TEAM_COL)?).map(super::team_path))
+    }
+
+    /// Name and player id → the player's page; team → the team's.
+    fn cell_link(&self, headers: Option<&[String]>, row: &[String], ci: usize, teams: &[(u32, String)]) -> Option<String> {
+        match self.stable_id_column(headers) {
+            Some(id_col) if ci == NAME_COL || ci == id_col => super::player_path(row.get(id_col)?),
+            _ => super::team_cell_link(self.team_columns(), row, ci, teams),
+        }
+    }
+
+    fn hidden_columns(&self, state: &AppState, headers: Option<&[String]>) -> Vec<usize> {
+        if state.gui.players_show_id { return Vec::new(); }
+        self.stable_id_column(headers).into_iter().collect()
+    }
+
+    fn view_for_export(
+        &self,
+        state: &AppState,
+        headers: &Option<Vec<String>>,
+        rows: &Vec<Vec<String>>,
+    ) -> (Option<Vec<String>>, Vec<Vec<String>>) {
+        let hidden = self.hidden_columns(state, headers.as_deref());
+        if hidden.is_empty() {
+            return (headers.clone(), rows.clone());
+        }
+        let keep = |r: &Vec<String>| -> Vec<String> {
+            r.iter().enumerate()
+                .filter(|(ci, _)| !hidden.contains(ci))
+                .map(|(_, c)| c.clone())
+                .collect()
+        };
+        (headers.as_ref().map(keep), rows.iter().map(keep).collect())
+    }
 
     fn draw_controls(&self, ui: &mut egui::Ui, state: &mut AppState) -> bool {
         // Players-only toggle: Keep '#'
@@ -30,6 +88,15 @@ impl Page for PlayersPage {
             &mut state.options.export.keep_hash,
             "Keep # in player number")
             .changed();
+        changed |= ui.checkbox(
+            &mut state.gui.players_show_id,
+            "Include player id")
+            .changed();
+        changed |= ui.checkbox(
+            &mut state.gui.show_delta,
+            "Δ since last scrape")
+            .on_hover_text("Show per-player changes against the data from before the last scrape")
+            .changed();
         changed
     }
 
@@ -37,7 +104,7 @@ impl Page for PlayersPage {
         &self,
         state: &AppState,
         mut progress: Option<&mut dyn Progress>,
-    ) -> Result<DataSet, Box<dyn Error>> {
+    ) -> Result<DataSet, Error> {
 
         if let Some(p) = progress.as_deref_mut() {
             p.begin(1);
@@ -47,9 +114,58 @@ impl Page for PlayersPage {
         Ok(ds)
     }
 
-    fn merge(&self, into: &mut DataSet, mut new: DataSet) {
-        const TEAM_COL: usize = 3;
+    /// Every player's team must be on the team list; a missing one means
+    /// the list is out of date (new or renamed team). Beyond that: the
+    /// schema (names filled, player ids numeric), one width for all rows,
+    /// numeric jersey numbers, only teams this scrape asked for, and no
+    /// two rows with the same team, number and name.
+    fn validate_scrape(
+        &self,
+        state: &AppState,
+        teams: &[(u32, String)],
+        new: &DataSet,
+    ) -> Result<(), String> {
+        let known: HashSet<&str> = teams.iter().map(|(_, n)| n.as_str()).collect();
+        let unknown = new.rows.iter()
+            .filter_map(|r| r.get(TEAM_COL))
+            .map(|t| t.trim())
+            .find(|t| !t.is_empty() && !known.contains(t));
+        if let Some(team) = unknown {
+            return Err(get_teams::unknown_team(team, "players"));
+        }
 
+        schema::validate(Players, new)?;
+
+        let selection = &state.options.scrape.teams;
+        let scraped: HashSet<&str> = teams.iter()
+            .filter(|(id, _)| selection.contains(*id))
+            .map(|(_, n)| n.as_str())
+            .collect();
+        let width = new.headers.as_ref().map(|h| h.len())
+            .or_else(|| new.rows.first().map(|r| r.len()))
+            .unwrap_or(0);
+
+        for (ri, r) in new.rows.iter().enumerate() {
+            if r.len() != width {
+                return Err(format!("Players: row {} has {} columns, expected {}", ri + 1, r.len(), width));
+            }
+            let number = r[NUMBER_COL].trim().trim_start_matches('#');
+            if !number.is_empty() && number.parse::<u32>().is_err() {
+                return Err(format!("Players: row {}: # is not a number ({:?})", ri + 1, r[NUMBER_COL]));
+            }
+            let team = r[TEAM_COL].trim();
+            if !scraped.contains(team) {
+                return Err(format!("Players: row {}: team '{}' wasn't in the scrape's selection", ri + 1, team));
+            }
+        }
+        if let Some((ri, first)) = scrape::duplicate_roster_key(&new.rows) {
+            let (team, number, name) = scrape::roster_key(&new.rows[ri]);
+            return Err(format!("Players: row {} repeats row {} ({}, #{} {})", ri + 1, first + 1, team, number, name));
+        }
+        Ok(())
+    }
+
+    fn merge(&self, into: &mut DataSet, mut new: DataSet) {
         // If the scrape gave us headers, accept them.
         if new.headers.is_some() {
             into.headers = new.headers.take();
@@ -85,8 +201,6 @@ impl Page for PlayersPage {
         teams: &[(u32, String)],
         rows: &Vec<Vec<String>>,
     ) -> Option<Vec<usize>> {
-        const TEAM_COL: usize = 3;
-
         let selected_names: HashSet<&str> = selected_ids.iter()
             .filter_map(|id| teams.iter().find(|(tid, _)| tid == id))
             .map(|(_, n)| n.as_str())
@@ -117,8 +231,6 @@ impl Page for PlayersPage {
             return Vec::new();
         }
 
-        const TEAM_COL: usize = 3;
-
         use std::collections::HashSet;
         let selected_names: HashSet<&str> = selected_ids.iter()
             .filter_map(|id| teams.iter().find(|(tid, _)| tid == id))
@@ -133,3 +245,50 @@ impl Page for PlayersPage {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::options::TeamSelector;
+
+    fn ds(rows: &[[&str; 6]]) -> DataSet {
+        DataSet {
+            headers: Some(["Name", "#", "Race", "Team", "TV", PLAYER_ID_HEADER].iter().map(|h| s!(*h)).collect()),
+            rows: rows.iter().map(|r| r.iter().map(|c| s!(*c)).collect()).collect(),
+        }
+    }
+
+    #[test]
+    fn validate_scrape_rejects_malformed_rows() {
+        let teams = vec![(0, s!("Reds")), (1, s!("Blues"))];
+        let mut state = AppState::default();
+        state.options.scrape.teams = TeamSelector::One(0);
+        let al = ["Al", "#1", "Orc", "Reds", "100", "7"];
+
+        assert_eq!(PAGE.validate_scrape(&state, &teams, &ds(&[al])), Ok(()));
+        // Blues weren't asked for
+        let bo = ["Bo", "#2", "Elf", "Blues", "90", "8"];
+        assert!(PAGE.validate_scrape(&state, &teams, &ds(&[al, bo])).unwrap_err().contains("selection"));
+        state.options.scrape.teams = TeamSelector::All;
+        assert_eq!(PAGE.validate_scrape(&state, &teams, &ds(&[al, bo])), Ok(()));
+
+        let bad_number = ["Cy", "#x", "Orc", "Reds", "80", "9"];
+        assert!(PAGE.validate_scrape(&state, &teams, &ds(&[al, bad_number])).unwrap_err().contains("# is not a number"));
+        let bad_id = ["Cy", "#3", "Orc", "Reds", "80", "nine"];
+        assert!(PAGE.validate_scrape(&state, &teams, &ds(&[al, bad_id])).is_err());
+
+        let mut short = ds(&[al, bo]);
+        short.rows[1].pop();
+        assert!(PAGE.validate_scrape(&state, &teams, &short).unwrap_err().contains("columns"));
+
+        // Same name, told apart by the number; the same number too is a repeat
+        let al_too = ["Al", "#5", "Orc", "Reds", "60", "11"];
+        assert_eq!(PAGE.validate_scrape(&state, &teams, &ds(&[al, al_too])), Ok(()));
+        let al_again = ["Al", "1", "Orc", "Reds", "60", "11"];
+        assert!(PAGE.validate_scrape(&state, &teams, &ds(&[al, al_again])).unwrap_err().contains("repeats row 1"));
+
+        let greens = ["Di", "#4", "Orc", "Greens", "70", "10"];
+        let err = PAGE.validate_scrape(&state, &teams, &ds(&[al, greens])).unwrap_err();
+        assert!(get_teams::is_unknown_team(&err));
+    }
+}
@@ -0,0 +1,91 @@
+// src/gui/pages/results_table.rs
+//
+// League table tallied from the Game Results (see `results_table`).
+// Derived, never scraped: "Scrape" recomputes it from the cache, and it's
+// refreshed after every Game Results scrape.
+use crate::error::Error;
+
+use crate::{
+    config::options::PageKind,
+    config::state::AppState,
+    progress::Progress,
+    schema::{col_index, RESULTS_TABLE},
+    scrape,
+    store::DataSet,
+};
+
+pub struct ResultsTablePage;
+pub static PAGE: ResultsTablePage = ResultsTablePage;
+
+const ID_COL: usize = col_index(&RESULTS_TABLE, "Id");
+const TEAM_COL: usize = col_index(&RESULTS_TABLE, "Team");
+const STREAK_COL: usize = col_index(&RESULTS_TABLE, "Streak");
+
+impl super::Page for ResultsTablePage {
+    fn title(&self) -> &'static str { "Results Table" }
+    fn kind(&self) -> PageKind { PageKind::ResultsTable }
+
+    fn default_headers(&self) -> Option<&'static [&'static str]> {
+        Some(&["Rank", "Id", "Team", "GP", "W", "T", "L", "PF", "PA", "Diff", "Streak"])
+    }
+
+    fn non_numeric_columns(&self) -> &'static [usize] { &[TEAM_COL, STREAK_COL] }
+
+    fn row_key_columns(&self) -> &'static [usize] { &[TEAM_COL] }
+
+    fn team_columns(&self) -> &'static [usize] { &[TEAM_COL] }
+
+    fn source_path(&self, _headers: Option<&[String]>, row: &[String], _teams: &[(u32, String)]) -> Option<String> {
+        row.get(ID_COL)?.parse::<u32>().ok().map(super::team_path)
+    }
+
+    // Id and Team → the team's page
+    fn cell_link(&self, headers: Option<&[String]>, row: &[String], ci: usize, teams: &[(u32, String)]) -> Option<String> {
+        if ci != ID_COL && ci != TEAM_COL { return None; }
+        self.source_path(headers, row, teams)
+    }
+
+    fn preferred_column_widths(&self) -> Option<&'static [usize]> {
+        Some(&[30, 30, 200, 30, 30, 30, 30, 30, 30, 40, 50])
+    }
+
+    fn scrape(&self, _state: &AppState, mut progress: Option<&mut dyn Progress>) -> Result<DataSet, Error> {
+        if let Some(p) = progress.as_deref_mut() { p.begin(0); }
+        scrape::collect_results_table(progress)
+    }
+
+    fn filter_row_indices_for_selection(
+        &self,
+        selected_ids: &[u32],
+        teams: &[(u32, String)],
+        rows: &Vec<Vec<String>>,
+    ) -> Option<Vec<usize>> {
+        let sel: Vec<&str> = selected_ids.iter()
+            .filter_map(|id| teams.iter().find(|(tid, _)| tid == id))
+            .map(|(_, name)| name.as_str())
+            .collect();
+        let ix = rows.iter().enumerate()
+            .filter(|(_, r)| r.get(TEAM_COL).is_some_and(|t| sel.contains(&t.as_str())))
+            .map(|(i, _)| i)
+            .collect();
+        Some(ix)
+    }
+
+    fn filter_rows_for_selection(
+        &self,
+        selected_ids: &[u32],
+        teams: &[(u32, String)],
+        rows: &Vec<Vec<String>>,
+    ) -> Vec<Vec<String>> {
+        let ix = self.filter_row_indices_for_selection(selected_ids, teams, rows).unwrap_or_default();
+        ix.into_iter().map(|i| rows[i].clone()).collect()
+    }
+
+    /// Our own header set (per the schema); every row a team and its record.
+    fn validate_cache(&self, ds: &DataSet) -> bool {
+        ds.headers.is_some() && crate::schema::validate(PageKind::ResultsTable, ds).is_ok()
+    }
+
+    // One row per team already
+    fn per_team_applicable(&self) -> bool { false }
+}
@@ -0,0 +1,80 @@
+// src/gui/pages/schedule_metrics.rs
+//
+// Per-team schedule metrics from the Game Results (see `schedule_metrics`):
+// home/away balance, strength of schedule, back-to-backs and byes.
+// Derived, never scraped: "Scrape" recomputes it from the cache, and it's
+// refreshed after every Game Results scrape.
+use crate::error::Error;
+
+use crate::{
+    config::options::PageKind,
+    config::state::AppState,
+    progress::Progress,
+    schema::{col_index, SCHEDULE_METRICS},
+    scrape,
+    store::DataSet,
+};
+
+pub struct ScheduleMetricsPage;
+pub static PAGE: ScheduleMetricsPage = ScheduleMetricsPage;
+
+const TEAM_COL: usize = col_index(&SCHEDULE_METRICS, "Team");
+
+impl super::Page for ScheduleMetricsPage {
+    fn title(&self) -> &'static str { "Schedule Metrics" }
+    fn kind(&self) -> PageKind { PageKind::ScheduleMetrics }
+
+    fn default_headers(&self) -> Option<&'static [&'static str]> {
+        Some(&["Team", "Games", "Home", "Away", "Balance", "Opp W", "Opp T", "Opp L", "SOS", "Rem SOS", "B2B", "Byes"])
+    }
+
+    fn non_numeric_columns(&self) -> &'static [usize] { &[TEAM_COL] }
+
+    fn row_key_columns(&self) -> &'static [usize] { &[TEAM_COL] }
+
+    fn team_columns(&self) -> &'static [usize] { &[TEAM_COL] }
+
+    fn preferred_column_widths(&self) -> Option<&'static [usize]> {
+        Some(&[200, 40, 40, 40, 50, 40, 40, 40, 50, 60, 30, 30])
+    }
+
+    fn scrape(&self, _state: &AppState, mut progress: Option<&mut dyn Progress>) -> Result<DataSet, Error> {
+        if let Some(p) = progress.as_deref_mut() { p.begin(0); }
+        scrape::collect_schedule_metrics(progress)
+    }
+
+    fn filter_row_indices_for_selection(
+        &self,
+        selected_ids: &[u32],
+        teams: &[(u32, String)],
+        rows: &Vec<Vec<String>>,
+    ) -> Option<Vec<usize>> {
+        let sel: Vec<&str> = selected_ids.iter()
+            .filter_map(|id| teams.iter().find(|(tid, _)| tid == id))
+            .map(|(_, name)| name.as_str())
+            .collect();
+        let ix = rows.iter().enumerate()
+            .filter(|(_, r)| r.get(TEAM_COL).is_some_and(|t| sel.contains(&t.as_str())))
+            .map(|(i, _)| i)
+            .collect();
+        Some(ix)
+    }
+
+    fn filter_rows_for_selection(
+        &self,
+        selected_ids: &[u32],
+        teams: &[(u32, String)],
+        rows: &Vec<Vec<String>>,
+    ) -> Vec<Vec<String>> {
+        let ix = self.filter_row_indices_for_selection(selected_ids, teams, rows).unwrap_or_default();
+        ix.into_iter().map(|i| rows[i].clone()).collect()
+    }
+
+    /// Our own header set (per the schema); every row a team and its counts.
+    fn validate_cache(&self, ds: &DataSet) -> bool {
+        ds.headers.is_some() && crate::schema::validate(PageKind::ScheduleMetrics, ds).is_ok()
+    }
+
+    // One row per team already
+    fn per_team_applicable(&self) -> bool { false }
+}
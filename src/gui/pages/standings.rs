@@ -0,0 +1,79 @@
+// src/gui/pages/standings.rs
+use crate::error::Error;
+
+use crate::{
+    config::options::PageKind,
+    config::state::AppState,
+    progress::Progress,
+    scrape::{self, STANDINGS_HEADERS},
+    store::DataSet,
+};
+
+pub struct StandingsPage;
+pub static PAGE: StandingsPage = StandingsPage;
+
+impl super::Page for StandingsPage {
+    fn title(&self) -> &'static str { "Standings" }
+    fn kind(&self) -> PageKind { PageKind::Standings }
+
+    fn default_headers(&self) -> Option<&'static [&'static str]> { Some(&STANDINGS_HEADERS) }
+
+    // Team name
+    fn non_numeric_columns(&self) -> &'static [usize] { &[2] }
+
+    // Team id
+    fn row_key_columns(&self) -> &'static [usize] { &[1] }
+
+    fn team_columns(&self) -> &'static [usize] { &[2] }
+
+    fn source_path(&self, _headers: Option<&[String]>, row: &[String], _teams: &[(u32, String)]) -> Option<String> {
+        row.get(1)?.parse::<u32>().ok().map(super::team_path)
+    }
+
+    // Id and Team → the team's page (by id, so renamed teams still resolve)
+    fn cell_link(&self, headers: Option<&[String]>, row: &[String], ci: usize, teams: &[(u32, String)]) -> Option<String> {
+        if ci != 1 && ci != 2 { return None; }
+        self.source_path(headers, row, teams)
+    }
+
+    fn preferred_column_widths(&self) -> Option<&'static [usize]> {
+        Some(&[30, 30, 200, 30, 30, 30, 30, 30])
+    }
+
+    fn scrape(&self, _state: &AppState, mut progress: Option<&mut dyn Progress>) -> Result<DataSet, Error> {
+        if let Some(p) = progress.as_deref_mut() { p.begin(0); }
+        scrape::collect_standings(progress)
+    }
+
+    fn filter_row_indices_for_selection(
+        &self,
+        selected_ids: &[u32],
+        _teams: &[(u32, String)],
+        rows: &Vec<Vec<String>>,
+    ) -> Option<Vec<usize>> {
+        let ix = rows.iter().enumerate()
+            .filter(|(_, r)| r.get(1)
+                .and_then(|id| id.parse::<u32>().ok())
+                .is_some_and(|id| selected_ids.contains(&id)))
+            .map(|(i, _)| i)
+            .collect();
+        Some(ix)
+    }
+
+    fn filter_rows_for_selection(
+        &self,
+        selected_ids: &[u32],
+        teams: &[(u32, String)],
+        rows: &Vec<Vec<String>>,
+    ) -> Vec<Vec<String>> {
+        let ix = self.filter_row_indices_for_selection(selected_ids, teams, rows).unwrap_or_default();
+        ix.into_iter().map(|i| rows[i].clone()).collect()
+    }
+
+    /// Our own header set (per the schema), and every row a numeric team id + name.
+    fn validate_cache(&self, ds: &DataSet) -> bool {
+        ds.headers.is_some() && crate::schema::validate(PageKind::Standings, ds).is_ok()
+    }
+
+    fn per_team_applicable(&self) -> bool { false }
+}
@@ -0,0 +1,107 @@
+// src/gui/pages/team_info.rs
+//
+// Owner, stadium and logo of each team, from the team pages. A scrape of
+// some teams updates just their rows. With "Download logos" the logos are
+// saved under `.store/logos/` and shown in the team list.
+use crate::error::Error;
+
+use eframe::egui;
+
+use crate::{
+    config::options::PageKind,
+    config::state::AppState,
+    progress::Progress,
+    scrape::{self, TEAM_INFO_HEADERS},
+    store::DataSet,
+};
+
+pub struct TeamInfoPage;
+pub static PAGE: TeamInfoPage = TeamInfoPage;
+
+const ID_COL: usize = 0;
+const TEAM_COL: usize = 1;
+
+impl super::Page for TeamInfoPage {
+    fn title(&self) -> &'static str { "Team Info" }
+    fn kind(&self) -> PageKind { PageKind::TeamInfo }
+
+    fn default_headers(&self) -> Option<&'static [&'static str]> { Some(&TEAM_INFO_HEADERS) }
+
+    fn non_numeric_columns(&self) -> &'static [usize] { &[1, 2, 3, 4] }
+
+    fn row_key_columns(&self) -> &'static [usize] { &[ID_COL] }
+
+    fn team_columns(&self) -> &'static [usize] { &[TEAM_COL] }
+
+    fn preferred_column_widths(&self) -> Option<&'static [usize]> {
+        Some(&[30, 200, 160, 160, 260])
+    }
+
+    fn source_path(&self, _headers: Option<&[String]>, row: &[String], _teams: &[(u32, String)]) -> Option<String> {
+        row.get(ID_COL)?.parse::<u32>().ok().map(super::team_path)
+    }
+
+    // Id and Team → the team's page
+    fn cell_link(&self, headers: Option<&[String]>, row: &[String], ci: usize, teams: &[(u32, String)]) -> Option<String> {
+        if ci != ID_COL && ci != TEAM_COL { return None; }
+        self.source_path(headers, row, teams)
+    }
+
+    fn draw_controls(&self, ui: &mut egui::Ui, state: &mut AppState) -> bool {
+        let scrape = &mut state.options.scrape;
+        if ui.checkbox(&mut scrape.download_logos, "Download logos")
+            .on_hover_text("Also save each team's logo when scraping, to show it in the team list")
+            .changed()
+        {
+            logf!("UI: Download_logos → {}", scrape.download_logos);
+        }
+        false
+    }
+
+    fn scrape(&self, state: &AppState, progress: Option<&mut dyn Progress>) -> Result<DataSet, Error> {
+        scrape::collect_team_info(&state.options.scrape, progress)
+    }
+
+    /// Replace the scraped teams' rows, keep the others; ordered by team id.
+    fn merge(&self, into: &mut DataSet, new: DataSet) {
+        let id = |r: &Vec<String>| r.get(ID_COL).and_then(|s| s.parse::<u32>().ok());
+        let fresh: Vec<Option<u32>> = new.rows.iter().map(id).collect();
+        into.rows.retain(|r| !fresh.contains(&id(r)));
+        into.rows.extend(new.rows);
+        into.rows.sort_by_key(id);
+        into.headers = new.headers.or(into.headers.take());
+    }
+
+    fn filter_row_indices_for_selection(
+        &self,
+        selected_ids: &[u32],
+        _teams: &[(u32, String)],
+        rows: &Vec<Vec<String>>,
+    ) -> Option<Vec<usize>> {
+        let ix = rows.iter().enumerate()
+            .filter(|(_, r)| r.get(ID_COL)
+                .and_then(|id| id.parse::<u32>().ok())
+                .is_some_and(|id| selected_ids.contains(&id)))
+            .map(|(i, _)| i)
+            .collect();
+        Some(ix)
+    }
+
+    fn filter_rows_for_selection(
+        &self,
+        selected_ids: &[u32],
+        teams: &[(u32, String)],
+        rows: &Vec<Vec<String>>,
+    ) -> Vec<Vec<String>> {
+        let ix = self.filter_row_indices_for_selection(selected_ids, teams, rows).unwrap_or_default();
+        ix.into_iter().map(|i| rows[i].clone()).collect()
+    }
+
+    /// Our own header set (per the schema), and every row a numeric team id + name.
+    fn validate_cache(&self, ds: &DataSet) -> bool {
+        ds.headers.is_some() && crate::schema::validate(PageKind::TeamInfo, ds).is_ok()
+    }
+
+    // One row per team already
+    fn per_team_applicable(&self) -> bool { false }
+}
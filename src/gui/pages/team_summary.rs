@@ -0,0 +1,74 @@
+// src/gui/pages/team_summary.rs
+//
+// Players aggregated per team (see `aggregate::team_summary`). Derived from
+// the Players data, never scraped: "Scrape" recomputes it from the cache,
+// and it's refreshed after every Players scrape.
+use crate::error::Error;
+
+use crate::{
+    config::options::PageKind,
+    config::state::AppState,
+    progress::Progress,
+    scrape,
+    store::DataSet,
+};
+
+pub struct TeamSummaryPage;
+pub static PAGE: TeamSummaryPage = TeamSummaryPage;
+
+// 0 Team, 1 Players, then "<stat> avg", "<stat> total" pairs
+const TEAM_COL: usize = 0;
+
+impl super::Page for TeamSummaryPage {
+    fn title(&self) -> &'static str { "Team Summary" }
+    fn kind(&self) -> PageKind { PageKind::TeamSummary }
+
+    // Columns follow the Players data; only these two are fixed
+    fn default_headers(&self) -> Option<&'static [&'static str]> { Some(&["Team", "Players"]) }
+
+    fn non_numeric_columns(&self) -> &'static [usize] { &[TEAM_COL] }
+
+    fn row_key_columns(&self) -> &'static [usize] { &[TEAM_COL] }
+
+    fn team_columns(&self) -> &'static [usize] { &[TEAM_COL] }
+
+    fn scrape(&self, _state: &AppState, mut progress: Option<&mut dyn Progress>) -> Result<DataSet, Error> {
+        if let Some(p) = progress.as_deref_mut() { p.begin(0); }
+        scrape::collect_team_summary(progress)
+    }
+
+    fn filter_row_indices_for_selection(
+        &self,
+        selected_ids: &[u32],
+        teams: &[(u32, String)],
+        rows: &Vec<Vec<String>>,
+    ) -> Option<Vec<usize>> {
+        let sel: Vec<&str> = selected_ids.iter()
+            .filter_map(|id| teams.iter().find(|(tid, _)| tid == id))
+            .map(|(_, name)| name.as_str())
+            .collect();
+        let ix = rows.iter().enumerate()
+            .filter(|(_, r)| r.get(TEAM_COL).is_some_and(|t| sel.contains(&t.as_str())))
+            .map(|(i, _)| i)
+            .collect();
+        Some(ix)
+    }
+
+    fn filter_rows_for_selection(
+        &self,
+        selected_ids: &[u32],
+        teams: &[(u32, String)],
+        rows: &Vec<Vec<String>>,
+    ) -> Vec<Vec<String>> {
+        let ix = self.filter_row_indices_for_selection(selected_ids, teams, rows).unwrap_or_default();
+        ix.into_iter().map(|i| rows[i].clone()).collect()
+    }
+
+    /// Starts with Team, Players; every row has a team and a count.
+    fn validate_cache(&self, ds: &DataSet) -> bool {
+        ds.headers.is_some() && crate::schema::validate(PageKind::TeamSummary, ds).is_ok()
+    }
+
+    // One row per team already
+    fn per_team_applicable(&self) -> bool { false }
+}
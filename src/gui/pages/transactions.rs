@@ -0,0 +1,128 @@
+// src/gui/pages/transactions.rs
+use crate::error::Error;
+use std::collections::HashSet;
+
+use crate::{
+    config::options::PageKind,
+    config::state::AppState,
+    progress::Progress,
+    scrape::{self, TRANSACTIONS_HEADERS},
+    store::DataSet,
+};
+
+pub struct TransactionsPage;
+pub static PAGE: TransactionsPage = TransactionsPage;
+
+// 0 S, 1 W, 2 Team, 3 Move, 4 Player, 5 Player id, 6 Other Team, 7 Details
+const TEAM_COL: usize = 2;
+const OTHER_TEAM_COL: usize = 6;
+
+impl super::Page for TransactionsPage {
+    fn title(&self) -> &'static str { "Transactions" }
+    fn kind(&self) -> PageKind { PageKind::Transactions }
+
+    fn default_headers(&self) -> Option<&'static [&'static str]> { Some(&TRANSACTIONS_HEADERS) }
+
+    // Teams, move, player, details
+    fn non_numeric_columns(&self) -> &'static [usize] { &[2, 3, 4, 6, 7] }
+
+    fn preferred_column_widths(&self) -> Option<&'static [usize]> {
+        Some(&[20, 20, 160, 70, 160, 60, 160, 160])
+    }
+
+    fn team_columns(&self) -> &'static [usize] { &[TEAM_COL, OTHER_TEAM_COL] }
+
+    /// The player's page when the row links one, else the team's.
+    fn source_path(&self, _headers: Option<&[String]>, row: &[String], teams: &[(u32, String)]) -> Option<String> {
+        row.get(5).and_then(|id| super::player_path(id))
+            .or_else(|| super::team_id_by_name(teams, row.get(TEAM_COL)?).map(super::team_path))
+    }
+
+    /// Player and player id → the player's page; teams → the team's.
+    fn cell_link(&self, _headers: Option<&[String]>, row: &[String], ci: usize, teams: &[(u32, String)]) -> Option<String> {
+        match ci {
+            4 | 5 => super::player_path(row.get(5)?),
+            _ => super::team_cell_link(self.team_columns(), row, ci, teams),
+        }
+    }
+
+    fn scrape(&self, _state: &AppState, mut progress: Option<&mut dyn Progress>) -> Result<DataSet, Error> {
+        if let Some(p) = progress.as_deref_mut() { p.begin(0); }
+        scrape::collect_transactions(progress)
+    }
+
+    /// The site only lists recent moves: keep what we have and append the
+    /// moves not seen before, so the cache becomes the season's log.
+    fn merge(&self, into: &mut DataSet, mut new: DataSet) {
+        if new.headers.is_some() {
+            into.headers = new.headers.take();
+        }
+        let seen: HashSet<Vec<String>> = into.rows.iter().cloned().collect();
+        into.rows.extend(new.rows.into_iter().filter(|r| !seen.contains(r)));
+    }
+
+    fn filter_row_indices_for_selection(
+        &self,
+        selected_ids: &[u32],
+        teams: &[(u32, String)],
+        rows: &Vec<Vec<String>>,
+    ) -> Option<Vec<usize>> {
+        if selected_ids.is_empty() { return Some(Vec::new()); }
+        if selected_ids.len() == teams.len() { return Some((0..rows.len()).collect()); }
+        let sel: HashSet<&str> = selected_ids
+            .iter()
+            .filter_map(|id| teams.iter().find(|(tid, _)| tid == id))
+            .map(|(_, name)| name.as_str())
+            .collect();
+        // Either side of a trade counts
+        let ix = rows.iter().enumerate().filter(|(_, r)| {
+            r.get(TEAM_COL).is_some_and(|s| sel.contains(s.as_str())) ||
+            r.get(OTHER_TEAM_COL).is_some_and(|s| sel.contains(s.as_str()))
+        }).map(|(i, _)| i).collect();
+        Some(ix)
+    }
+
+    fn filter_rows_for_selection(
+        &self,
+        selected_ids: &[u32],
+        teams: &[(u32, String)],
+        rows: &Vec<Vec<String>>,
+    ) -> Vec<Vec<String>> {
+        let ix = self.filter_row_indices_for_selection(selected_ids, teams, rows).unwrap_or_default();
+        ix.into_iter().map(|i| rows[i].clone()).collect()
+    }
+
+    /// Our own header set, and every row has a move and a player (per the schema).
+    fn validate_cache(&self, ds: &DataSet) -> bool {
+        ds.headers.is_some() && crate::schema::validate(PageKind::Transactions, ds).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gui::pages::Page;
+
+    fn ds(rows: &[[&str; 8]]) -> DataSet {
+        DataSet {
+            headers: Some(TRANSACTIONS_HEADERS.iter().map(|h| h.to_string()).collect()),
+            rows: rows.iter().map(|r| r.iter().map(|c| c.to_string()).collect()).collect(),
+        }
+    }
+
+    #[test]
+    fn merge_appends_only_new_moves() {
+        let signed = ["7", "3", "Reds", "Signed", "Al", "1", "", ""];
+        let cut = ["7", "4", "Blues", "Cut", "Bo", "2", "", ""];
+        let traded = ["7", "5", "Reds", "Traded", "Cy", "3", "Blues", ""];
+
+        let mut cache = ds(&[signed, cut]);
+        // The site has dropped the week-3 signing by now
+        PAGE.merge(&mut cache, ds(&[cut, traded]));
+        assert_eq!(cache.rows, ds(&[signed, cut, traded]).rows);
+
+        let teams = vec![(0, s!("Reds")), (1, s!("Blues")), (2, s!("Greens"))];
+        assert_eq!(PAGE.filter_row_indices_for_selection(&[1], &teams, &cache.rows), Some(vec![1, 2]));
+        assert!(PAGE.validate_cache(&cache));
+    }
+}
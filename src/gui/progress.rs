@@ -1,33 +1,97 @@
 // src/gui/progress.rs
-use std::sync::{ Arc, Mutex };
-use crate::progress::Progress;
+use std::sync::{ atomic::{ AtomicBool, Ordering }, Arc, Mutex };
+use crate::progress::{Progress, Summary};
+
+/// Item counts of the running (or last) scrape, shared with the UI thread
+/// for the progress bar.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Counts {
+    pub done: usize,
+    pub failed: usize,
+    /// 0 until the scraper knows how many items there are
+    pub total: usize,
+    pub finished: bool,
+}
+
+impl Counts {
+    pub fn completed(&self) -> usize { self.done + self.failed }
+
+    /// Share of the items completed (0.0–1.0); None while the total isn't known.
+    pub fn fraction(&self) -> Option<f32> {
+        (self.total > 0).then(|| (self.completed() as f32 / self.total as f32).min(1.0))
+    }
+
+    /// "12/32 · 37%" (" · 2 failed") while running, "30/32 succeeded" (", 2 failed") at the end.
+    pub fn label(&self) -> String {
+        let failed = match (self.failed, self.finished) {
+            (0, _) => String::new(),
+            (n, true) => format!(", {} failed", n),
+            (n, false) => format!(" · {} failed", n),
+        };
+        if self.finished {
+            format!("{}/{} succeeded{}", self.done, self.total, failed)
+        } else {
+            let pct = (self.fraction().unwrap_or(0.0) * 100.0).round() as u32;
+            format!("{}/{} · {}%{}", self.completed(), self.total, pct, failed)
+        }
+    }
+}
 
 pub struct GuiProgress {
     status: Arc<Mutex<String>>,
+    counts: Arc<Mutex<Counts>>,
+    /// Set by the UI's Cancel button
+    cancel: Arc<AtomicBool>,
     done: usize,
     failed: usize,
     total: usize,
+    /// Teams that failed (id, name), for the final status line and Retry failed
+    failed_teams: Vec<(u32, String)>,
+    /// Teams fetched, for the team panel's per-team status
+    done_teams: Vec<u32>,
 }
 
 impl GuiProgress {
-    pub fn new(status: Arc<Mutex<String>>) -> Self {
-        Self { status, done: 0, failed: 0, total: 0 }
+    pub fn new(status: Arc<Mutex<String>>, counts: Arc<Mutex<Counts>>, cancel: Arc<AtomicBool>) -> Self {
+        Self { status, counts, cancel, done: 0, failed: 0, total: 0, failed_teams: Vec::new(), done_teams: Vec::new() }
+    }
+
+    /// Ids of the teams that failed, in the order they failed.
+    pub fn failed_ids(&self) -> Vec<u32> {
+        self.failed_teams.iter().map(|(id, _)| *id).collect()
+    }
+
+    /// Ids of the teams fetched, in the order they came in.
+    pub fn done_ids(&self) -> Vec<u32> { self.done_teams.clone() }
+
+    /// " — 2 team(s) failed: Reds, Blues", or empty when none failed.
+    /// Appended to the scrape's final status so failures aren't overwritten.
+    pub fn failure_note(&self) -> String {
+        if self.failed_teams.is_empty() { return String::new(); }
+        let names: Vec<&str> = self.failed_teams.iter().map(|(_, n)| n.as_str()).collect();
+        format!(" — {} team(s) failed: {}", names.len(), names.join(", "))
     }
     fn set_status(&self, msg: impl Into<String>) {
         let text = msg.into();
         *self.status.lock().unwrap() = text;
     }
+
+    fn publish(&self, finished: bool) {
+        *self.counts.lock().unwrap() = Counts { done: self.done, failed: self.failed, total: self.total, finished };
+    }
 }
 
 impl Progress for GuiProgress {
     fn begin(&mut self, total: usize) {
         self.total = total;
+        self.publish(false);
     }
     fn log(&mut self, msg: &str) {
         self.set_status(s!(msg));
     }
-    fn item_done(&mut self, _team_id: u32, team_name: &str) {
+    fn item_done(&mut self, team_id: u32, team_name: &str) {
         self.done += 1;
+        self.done_teams.push(team_id);
         let completed = self.done + self.failed;
         let failure_suffix = if self.failed > 0 {
             format!(" ({} failed)", self.failed)
@@ -35,22 +99,50 @@ impl Progress for GuiProgress {
             String::new()
         };
         self.set_status(format!("[{}/{}] Fetched: {}{}", completed, self.total, team_name, failure_suffix));
+        self.publish(false);
     }
-    fn item_failed(&mut self, _team_id: u32, team_name: &str) {
+    fn item_failed(&mut self, team_id: u32, team_name: &str, error: &str) {
         self.failed += 1;
+        self.failed_teams.push((team_id, s!(team_name)));
         let completed = self.done + self.failed;
-        self.set_status(format!("[{}/{}] Failed: {} ({} failed)", completed, self.total, team_name, self.failed));
+        self.set_status(format!("[{}/{}] Failed: {}: {} ({} failed)", completed, self.total, team_name, error, self.failed));
+        self.publish(false);
     }
-    fn finish(&mut self) {
+    fn finish(&mut self, summary: &Summary) {
+        self.done = summary.succeeded;
+        self.failed = summary.failed.len();
+        self.publish(true);
         if self.total == 0 {
             self.set_status(s!("Fetch complete")); // no counts if we never began
         } else {
-            let failure_suffix = if self.failed > 0 {
-                format!(" ({} failed)", self.failed)
-            } else {
-                String::new()
-            };
-            self.set_status(format!("Fetch complete ({}/{}){}", self.done, self.total, failure_suffix));
+            self.set_status(format!("Fetch complete ({}/{}){}", summary.succeeded, self.total, self.failure_note()));
         }
     }
-}
\ No newline at end of file
+    fn cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_feed_the_bar_and_the_summary() {
+        let counts = Arc::new(Mutex::new(Counts::default()));
+        let mut gp = GuiProgress::new(Arc::new(Mutex::new(String::new())), counts.clone(), Arc::default());
+        assert_eq!(counts.lock().unwrap().fraction(), None);
+
+        gp.begin(4);
+        gp.item_done(1, "Reds");
+        gp.item_failed(2, "Blues", "timeout");
+        let now = *counts.lock().unwrap();
+        assert_eq!(now.fraction(), Some(0.5));
+        assert_eq!(now.label(), "2/4 · 50% · 1 failed");
+
+        gp.item_done(3, "Greens");
+        gp.item_done(4, "Golds");
+        gp.finish(&Summary { succeeded: 3, failed: vec![(2, s!("Blues"), s!("timeout"))] });
+        assert_eq!(counts.lock().unwrap().label(), "3/4 succeeded, 1 failed");
+    }
+}
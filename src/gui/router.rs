@@ -6,6 +6,15 @@ pub static PAGES: &[&'static dyn Page] = &[
     &pages::players::PAGE,
     &pages::game_results::PAGE,
     &pages::injuries::PAGE,
+    &pages::standings::PAGE,
+    &pages::results_table::PAGE,
+    &pages::schedule_metrics::PAGE,
+    &pages::transactions::PAGE,
+    &pages::team_summary::PAGE,
+    &pages::leaders::PAGE,
+    &pages::team_info::PAGE,
+    &pages::injury_victims::PAGE,
+    &pages::injury_summary::PAGE,
 ];
 
 pub fn all_pages() -> &'static [&'static dyn Page] {
@@ -17,6 +26,15 @@ pub fn page_for(kind: &PageKind) -> &'static dyn Page {
         Players     => &pages::players::PAGE,
         GameResults => &pages::game_results::PAGE,
         Injuries    => &pages::injuries::PAGE,
+        Standings   => &pages::standings::PAGE,
+        ResultsTable => &pages::results_table::PAGE,
+        ScheduleMetrics => &pages::schedule_metrics::PAGE,
+        Transactions => &pages::transactions::PAGE,
+        TeamSummary => &pages::team_summary::PAGE,
+        Leaders     => &pages::leaders::PAGE,
+        TeamInfo    => &pages::team_info::PAGE,
+        InjuryVictims => &pages::injury_victims::PAGE,
+        InjurySummary => &pages::injury_summary::PAGE,
         // Add more as you implement them.
         _ => &pages::players::PAGE,
     }
@@ -0,0 +1,55 @@
+// src/gui/shortcuts.rs
+//
+// Keyboard shortcuts for the main actions. Read once per frame by `App::update`;
+// nothing fires while a text field (output path, search, …) has keyboard focus,
+// so typing and the field's own Ctrl+C/Ctrl+A keep working.
+
+use eframe::egui::{self, Key, KeyboardShortcut, Modifiers};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shortcut {
+    Copy,
+    Export,
+    Scrape,
+    SelectAll,
+    SelectNone,
+}
+
+use Shortcut::*;
+
+impl Shortcut {
+    pub const ALL: [Shortcut; 5] = [Copy, Export, Scrape, SelectAll, SelectNone];
+
+    pub fn keys(self) -> KeyboardShortcut {
+        match self {
+            Copy       => KeyboardShortcut::new(Modifiers::COMMAND, Key::C),
+            Export     => KeyboardShortcut::new(Modifiers::COMMAND, Key::E),
+            Scrape     => KeyboardShortcut::new(Modifiers::NONE, Key::F5),
+            SelectAll  => KeyboardShortcut::new(Modifiers::COMMAND, Key::A),
+            SelectNone => KeyboardShortcut::new(Modifiers::COMMAND, Key::D),
+        }
+    }
+
+    /// Tooltip text for the action's button, e.g. "Copy view (Ctrl+C)".
+    pub fn hint(self, ctx: &egui::Context, what: &str) -> String {
+        format!("{} ({})", what, ctx.format_shortcut(&self.keys()))
+    }
+}
+
+/// Shortcuts pressed this frame, consumed from the input.
+/// Empty while a widget wants keyboard input.
+pub fn pressed(ctx: &egui::Context) -> Vec<Shortcut> {
+    if ctx.wants_keyboard_input() {
+        return Vec::new();
+    }
+    ctx.input_mut(|i| {
+        let mut out: Vec<Shortcut> = Shortcut::ALL.into_iter()
+            .filter(|s| i.consume_shortcut(&s.keys()))
+            .collect();
+        // The platform layer turns Ctrl+C into a Copy event instead of a key press
+        if !out.contains(&Copy) && i.events.iter().any(|e| matches!(e, egui::Event::Copy)) {
+            out.push(Copy);
+        }
+        out
+    })
+}
@@ -0,0 +1,159 @@
+// src/gui/taskbar.rs
+//
+// The running scrapes' progress outside the window, so the app can be
+// minimized and still show how far along it is: in the window title
+// ("Scraping 12/32…") and, on Windows, on the taskbar button
+// (ITaskbarList3: a green bar, red once a team failed). Elsewhere the
+// taskbar part does nothing.
+
+use eframe::egui;
+use crate::config::consts::APP_TITLE;
+use super::{actions::scrape, app::App, progress::Counts};
+
+/// Window title for `counts` (None: no scrape running).
+pub fn title(counts: Option<&Counts>) -> String {
+    match counts {
+        None => s!(APP_TITLE),
+        Some(c) if c.total == 0 => format!("Scraping… — {}", APP_TITLE),
+        Some(c) => format!("Scraping {}/{}… — {}", c.completed(), c.total, APP_TITLE),
+    }
+}
+
+/// Show the running scrapes' progress; call once per frame. Only changes
+/// are sent to the window.
+pub fn update(app: &mut App, ctx: &egui::Context, frame: &eframe::Frame) {
+    let counts = scrape::running_counts(app);
+    if counts == app.window_progress { return; }
+    ctx.send_viewport_cmd(egui::ViewportCommand::Title(title(counts.as_ref())));
+    native::set_progress(frame, counts.as_ref());
+    app.window_progress = counts;
+}
+
+#[cfg(windows)]
+mod native {
+    // ITaskbarList3 by hand: two COM calls don't warrant a Windows API crate.
+    use std::{cell::Cell, ffi::c_void, ptr};
+    use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+    use crate::gui::progress::Counts;
+
+    #[repr(C)]
+    struct Guid(u32, u16, u16, [u8; 8]);
+
+    const CLSID_TASKBAR_LIST: Guid = Guid(0x56FD_F344, 0xFD6D, 0x11D0, [0x95, 0x8A, 0x00, 0x60, 0x97, 0xC9, 0xA0, 0x90]);
+    const IID_ITASKBAR_LIST3: Guid = Guid(0xEA1A_FB91, 0x9E28, 0x4B86, [0x90, 0xE9, 0x9E, 0x9F, 0x8A, 0x5E, 0xEF, 0xAF]);
+    const COINIT_APARTMENTTHREADED: u32 = 0x2;
+    const CLSCTX_INPROC_SERVER: u32 = 0x1;
+    const TBPF_NOPROGRESS: i32 = 0x0;
+    const TBPF_INDETERMINATE: i32 = 0x1;
+    const TBPF_NORMAL: i32 = 0x2;
+    const TBPF_ERROR: i32 = 0x4;
+
+    type Hwnd = *mut c_void;
+
+    /// ITaskbarList3's vtable up to the calls used here (unused slots as
+    /// plain pointers).
+    #[repr(C)]
+    #[allow(dead_code)]
+    struct Vtbl {
+        query_interface: usize,
+        add_ref: usize,
+        release: unsafe extern "system" fn(*mut Obj) -> u32,
+        hr_init: unsafe extern "system" fn(*mut Obj) -> i32,
+        add_tab: usize,
+        delete_tab: usize,
+        activate_tab: usize,
+        set_active_alt: usize,
+        mark_fullscreen_window: usize,
+        set_progress_value: unsafe extern "system" fn(*mut Obj, Hwnd, u64, u64) -> i32,
+        set_progress_state: unsafe extern "system" fn(*mut Obj, Hwnd, i32) -> i32,
+    }
+
+    #[repr(C)]
+    struct Obj { vtbl: *const Vtbl }
+
+    #[link(name = "ole32")]
+    unsafe extern "system" {
+        fn CoInitializeEx(reserved: *mut c_void, coinit: u32) -> i32;
+        fn CoCreateInstance(clsid: *const Guid, outer: *mut c_void, context: u32, iid: *const Guid, out: *mut *mut c_void) -> i32;
+    }
+
+    thread_local! {
+        /// Created on first use on the UI thread and kept; null if the
+        /// shell doesn't offer it.
+        static TASKBAR: Cell<Option<*mut Obj>> = const { Cell::new(None) };
+    }
+
+    fn taskbar() -> *mut Obj {
+        TASKBAR.with(|cell| {
+            if let Some(tb) = cell.get() { return tb; }
+            let mut p: *mut c_void = ptr::null_mut();
+            // SAFETY: COM calls with valid arguments; `p` is only used when
+            // CoCreateInstance succeeded, and released if HrInit fails
+            let tb = unsafe {
+                CoInitializeEx(ptr::null_mut(), COINIT_APARTMENTTHREADED);
+                let created = CoCreateInstance(&CLSID_TASKBAR_LIST, ptr::null_mut(), CLSCTX_INPROC_SERVER, &IID_ITASKBAR_LIST3, &mut p) >= 0;
+                let tb = p as *mut Obj;
+                if !created || tb.is_null() {
+                    ptr::null_mut()
+                } else if ((*(*tb).vtbl).hr_init)(tb) >= 0 {
+                    tb
+                } else {
+                    ((*(*tb).vtbl).release)(tb);
+                    ptr::null_mut()
+                }
+            };
+            if tb.is_null() { logd!("UI: Taskbar progress unavailable"); }
+            cell.set(Some(tb));
+            tb
+        })
+    }
+
+    pub fn set_progress(frame: &eframe::Frame, counts: Option<&Counts>) {
+        let Ok(handle) = frame.window_handle() else { return };
+        let RawWindowHandle::Win32(win) = handle.as_raw() else { return };
+        let hwnd = win.hwnd.get() as Hwnd;
+        let tb = taskbar();
+        if tb.is_null() { return; }
+
+        let (state, value) = match counts {
+            None => (TBPF_NOPROGRESS, None),
+            Some(c) if c.total == 0 => (TBPF_INDETERMINATE, None),
+            Some(c) => {
+                let state = if c.failed > 0 { TBPF_ERROR } else { TBPF_NORMAL };
+                (state, Some((c.completed() as u64, c.total as u64)))
+            }
+        };
+        // SAFETY: `tb` is a live ITaskbarList3 and `hwnd` this app's window
+        unsafe {
+            let vtbl = &*(*tb).vtbl;
+            if let Some((done, total)) = value {
+                (vtbl.set_progress_value)(tb, hwnd, done, total);
+            }
+            (vtbl.set_progress_state)(tb, hwnd, state);
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod native {
+    use crate::gui::progress::Counts;
+
+    /// No taskbar progress here; the title shows it.
+    pub fn set_progress(_frame: &eframe::Frame, _counts: Option<&Counts>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn title_shows_progress_while_scraping() {
+        assert_eq!(title(None), APP_TITLE);
+        let mut c = Counts::default();
+        assert_eq!(title(Some(&c)), format!("Scraping… — {}", APP_TITLE));
+        c.total = 32;
+        c.done = 10;
+        c.failed = 2;
+        assert_eq!(title(Some(&c)), format!("Scraping 12/32… — {}", APP_TITLE));
+    }
+}
@@ -0,0 +1,261 @@
+// src/import.rs
+//
+// Hand-edited rows back into the cache: CSV/TSV copied from a spreadsheet
+// (or read from an exported file) is parsed with the cache's own parser,
+// checked against the page schema and merged into the cached dataset on
+// the page's row key. Rows the import doesn't mention are kept.
+//
+// Third-party files (old season sheets kept by league historians) have
+// their own headers and column order: `parse_mapped` maps their columns
+// onto the page's by name, or as a `Target=Source` mapping says (CLI
+// `--import`).
+
+use std::collections::HashMap;
+
+use crate::{config::options::PageKind, file::parse_rows, schema, store::DataSet};
+
+/// Tab when the first line has one (spreadsheets copy as TSV), else comma.
+pub fn detect_sep(text: &str) -> char {
+    let first = text.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+    if first.contains('\t') { '\t' } else { ',' }
+}
+
+/// Parse pasted or loaded text as rows of `kind`, in the layout of `cached`
+/// (or the page schema when nothing is cached). A leading header row is
+/// dropped when it names the expected columns.
+pub fn parse(kind: PageKind, text: &str, cached: Option<&DataSet>) -> Result<DataSet, String> {
+    let mut rows = parse_rows(text, detect_sep(text));
+    if rows.is_empty() {
+        return Err(s!("Nothing to import"));
+    }
+
+    let headers: Option<Vec<String>> = cached.and_then(|ds| ds.headers.clone())
+        .or_else(|| schema::for_page(kind).filter(|p| p.is_fixed())
+            .map(|p| p.names().into_iter().map(String::from).collect()));
+
+    // Derived columns are recomputed after the merge, so rows may leave them out
+    let base = headers.as_deref().map(|hs| schema::for_page(kind).map_or(hs.len(), |p| p.derived_at(hs)));
+    let first_is_headers = |hs: &[String]| (rows[0].len() == hs.len() || Some(rows[0].len()) == base)
+        && rows[0].iter().zip(hs).all(|(a, b)| a.trim().eq_ignore_ascii_case(b));
+    if headers.as_deref().is_some_and(first_is_headers) {
+        rows.remove(0);
+    }
+    if rows.is_empty() {
+        return Err(s!("Only a header row; nothing to import"));
+    }
+    if let (Some(hs), Some(base)) = (&headers, base) {
+        for r in rows.iter_mut().filter(|r| r.len() == base) {
+            r.resize(hs.len(), String::new());
+        }
+    }
+
+    let width = headers.as_ref().map(Vec::len);
+    if let Some((i, r)) = rows.iter().enumerate().find(|(_, r)| width.is_some_and(|w| r.len() != w)) {
+        let hs = headers.as_deref().unwrap_or_default();
+        return Err(format!("Row {} has {} columns, expected {} ({})", i + 1, r.len(), hs.len(), hs.join(", ")));
+    }
+
+    let ds = DataSet { headers, rows };
+    schema::validate(kind, &ds)?;
+    Ok(ds)
+}
+
+/// `cached` with `imported` merged in on `key_cols`: a row with a known key
+/// replaces that row, any other is appended. Empty `key_cols`: the whole row
+/// is the key, so only new rows are added.
+pub fn merge(cached: &DataSet, imported: DataSet, key_cols: &[usize]) -> DataSet {
+    let key = |r: &[String]| -> Vec<String> {
+        if key_cols.is_empty() { return r.to_vec(); }
+        key_cols.iter().map(|&c| r.get(c).cloned().unwrap_or_default()).collect()
+    };
+    let mut at: HashMap<Vec<String>, usize> = cached.rows.iter().enumerate()
+        .map(|(i, r)| (key(r), i))
+        .collect();
+
+    let mut out = cached.clone();
+    if out.headers.is_none() {
+        out.headers = imported.headers;
+    }
+    for r in imported.rows {
+        let k = key(&r);
+        match at.get(&k) {
+            Some(&i) => out.rows[i] = r,
+            None => {
+                at.insert(k, out.rows.len());
+                out.rows.push(r);
+            }
+        }
+    }
+    out
+}
+
+/* ---------- third-party files ---------- */
+
+/// Where a page column's cells come from in a third-party file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// The file's column (0-based)
+    Column(usize),
+    /// The same value in every row (`--season`)
+    Value(String),
+    Blank,
+}
+
+/// `Target=Source` pairs, separated by commas or lines (`--map`, or a
+/// `--map-file`). A source is a header of the file or a 1-based column
+/// number; an empty one leaves the column blank.
+pub fn parse_mapping(text: &str) -> Result<Vec<(String, String)>, String> {
+    text.split([',', '\n'])
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|p| {
+            let (target, source) = p.split_once('=').ok_or_else(|| format!("Mapping '{}' isn't Target=Source", p))?;
+            Ok((target.trim().to_string(), source.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Header names compare without case, spaces and punctuation ("Home Team"
+/// = "home_team"); names of punctuation only ("#") as they are.
+fn same_name(a: &str, b: &str) -> bool {
+    let norm = |s: &str| s.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect::<String>();
+    match (norm(a), norm(b)) {
+        (na, nb) if na.is_empty() || nb.is_empty() => a.trim() == b.trim(),
+        (na, nb) => na == nb,
+    }
+}
+
+/// The file column `name` stands for: a header, else a 1-based number.
+fn find_column(headers: &[String], name: &str) -> Option<usize> {
+    headers.iter().position(|h| same_name(h, name))
+        .or_else(|| name.parse::<usize>().ok().filter(|n| (1..=headers.len()).contains(n)).map(|n| n - 1))
+}
+
+/// Where each of `targets` (the page's columns) comes from in a file headed
+/// `headers`: `pairs` first, then the file's header of the same name;
+/// anything else stays blank. `season` fills the S column. Fails on a
+/// mapping naming a column that doesn't exist, or a required column of
+/// `kind` left without a source.
+pub fn map_columns(
+    kind: PageKind,
+    targets: &[String],
+    headers: &[String],
+    pairs: &[(String, String)],
+    season: Option<u32>,
+) -> Result<Vec<Source>, String> {
+    if let Some((t, _)) = pairs.iter().find(|(t, _)| !targets.iter().any(|x| same_name(x, t))) {
+        return Err(format!("{} has no column '{}' ({})", kind, t, targets.join(", ")));
+    }
+    if season.is_some() && !targets.iter().any(|t| t == "S") {
+        return Err(format!("{} has no season column for --season", kind));
+    }
+    let required: Vec<&str> = schema::for_page(kind)
+        .map(|p| p.columns.iter().filter(|c| c.required).map(|c| c.name).collect())
+        .unwrap_or_default();
+
+    targets.iter().map(|t| {
+        if let (Some(n), "S") = (season, t.as_str()) {
+            return Ok(Source::Value(n.to_string()));
+        }
+        let source = match pairs.iter().find(|(pt, _)| same_name(pt, t)) {
+            Some((_, s)) if s.is_empty() => Source::Blank,
+            Some((_, s)) => Source::Column(find_column(headers, s)
+                .ok_or_else(|| format!("No column '{}' in the file ({})", s, headers.join(", ")))?),
+            None => headers.iter().position(|h| same_name(h, t)).map_or(Source::Blank, Source::Column),
+        };
+        if source == Source::Blank && required.contains(&t.as_str()) {
+            return Err(format!("Nothing in the file maps to {} (required); map it with \"{}=<file column>\"", t, t));
+        }
+        Ok(source)
+    }).collect()
+}
+
+/// A third-party CSV/TSV (headers on its first line) as rows of `kind`, in
+/// the layout of `cached` (or the page schema when nothing is cached), with
+/// the columns mapped by `map_columns`. Checked against the schema. Also
+/// returns the page's columns and where each came from.
+pub fn parse_mapped(
+    kind: PageKind,
+    text: &str,
+    cached: Option<&DataSet>,
+    pairs: &[(String, String)],
+    season: Option<u32>,
+) -> Result<(DataSet, Vec<(String, Source)>), String> {
+    let mut rows = parse_rows(text, detect_sep(text));
+    if rows.is_empty() {
+        return Err(s!("Nothing to import"));
+    }
+    let headers = rows.remove(0);
+    rows.retain(|r| r.iter().any(|c| !c.trim().is_empty()));
+    if rows.is_empty() {
+        return Err(s!("Only a header row; nothing to import"));
+    }
+
+    let targets: Vec<String> = cached.and_then(|ds| ds.headers.clone())
+        .or_else(|| schema::for_page(kind).map(|p| p.names().into_iter().map(String::from).collect()))
+        .ok_or_else(|| format!("No columns known for {}; scrape it first", kind))?;
+    let sources = map_columns(kind, &targets, &headers, pairs, season)?;
+
+    let rows = rows.iter().map(|r| sources.iter().map(|s| match s {
+        Source::Column(c) => r.get(*c).map(|v| v.trim().to_string()).unwrap_or_default(),
+        Source::Value(v) => v.clone(),
+        Source::Blank => String::new(),
+    }).collect()).collect();
+
+    let ds = DataSet { headers: Some(targets.clone()), rows };
+    schema::validate(kind, &ds)?;
+    Ok((ds, targets.into_iter().zip(sources).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(cells: &[&str]) -> Vec<String> { cells.iter().map(|c| c.to_string()).collect() }
+
+    #[test]
+    fn pasted_rows_replace_by_key_and_append_the_rest() {
+        let cached = DataSet {
+            headers: Some(row(&["Id", "Team", "Division"])),
+            rows: vec![row(&["0", "Alpha", "North"]), row(&["1", "Beta", "South"])],
+        };
+
+        // Copied from a spreadsheet: tabs, header row included
+        let text = "Id\tTeam\tDivision\r\n1\tBeta\tNorth\r\n2\tGamma\tSouth\r\n";
+        let imported = parse(PageKind::Teams, text, Some(&cached)).unwrap();
+        assert_eq!(imported.rows.len(), 2);
+
+        let merged = merge(&cached, imported, &[0]);
+        assert_eq!(merged.rows, vec![
+            row(&["0", "Alpha", "North"]),
+            row(&["1", "Beta", "North"]),
+            row(&["2", "Gamma", "South"]),
+        ]);
+
+        assert!(parse(PageKind::Teams, "1,Beta\n", Some(&cached)).unwrap_err().contains("expected 3"));
+        assert!(parse(PageKind::Teams, "x,Beta,North\n", Some(&cached)).unwrap_err().contains("not a number"));
+        assert!(parse(PageKind::Teams, "Id,Team,Division\n", Some(&cached)).is_err());
+    }
+
+    #[test]
+    fn third_party_columns_map_by_name_or_mapping() {
+        // An old season sheet: own order, own names, no season, an extra column
+        let text = "Week;Home Team;Away Team;Home Score;Away Score;Notes\n".replace(';', ",")
+            + "1,Alpha,Beta,2,1,rain\n2,Beta,Alpha,0,0,\n";
+        let pairs = parse_mapping("W=Week, Home=Home Team, Away=3, H=Home Score, A=Away Score").unwrap();
+        let (ds, sources) = parse_mapped(PageKind::GameResults, &text, None, &pairs, Some(4)).unwrap();
+        assert_eq!(ds.rows, vec![
+            row(&["4", "1", "Alpha", "2", "1", "Beta", ""]),
+            row(&["4", "2", "Beta", "0", "0", "Alpha", ""]),
+        ]);
+        assert_eq!(sources[0], (s!("S"), Source::Value(s!("4"))));
+        assert_eq!(sources[1], (s!("W"), Source::Column(0)));
+
+        // Home and Away are required; Season must go somewhere
+        let err = parse_mapped(PageKind::GameResults, &text, None, &pairs[..1], Some(4)).unwrap_err();
+        assert!(err.contains("Home (required)"), "{}", err);
+        assert!(parse_mapped(PageKind::Teams, "Id,Team\n1,A\n", None, &[], Some(4)).unwrap_err().contains("no season column"));
+        assert!(parse_mapping("Home").is_err());
+        assert!(map_columns(PageKind::GameResults, &[s!("Home")], &[s!("H")], &[(s!("Home"), s!("Nope"))], None).is_err());
+    }
+}
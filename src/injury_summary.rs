@@ -0,0 +1,116 @@
+// src/injury_summary.rs
+//
+// Injury dashboard: the injuries of the Injuries data counted per team, per
+// injury type and per week, with the skill rating they cost. Purely local,
+// like Team Summary, and grouped the same way (`aggregate::summarize`).
+
+use crate::aggregate::summarize;
+use crate::config::options::PageKind;
+use crate::schema::{col_index, INJURIES, INJURY_SUMMARY};
+use crate::scrape::injuries::EVENT_INJURY;
+use crate::store::DataSet;
+
+const INJ_SEASON: usize = col_index(&INJURIES, "S");
+const INJ_WEEK: usize = col_index(&INJURIES, "W");
+const INJ_TEAM: usize = col_index(&INJURIES, "Victim Team");
+const INJ_SR0: usize = col_index(&INJURIES, "SR0");
+const INJ_SR1: usize = col_index(&INJURIES, "SR1");
+const INJ_TYPE: usize = col_index(&INJURIES, "Type");
+const INJ_EVENT: usize = col_index(&INJURIES, "Event");
+
+pub const GROUP_COL: usize = col_index(&INJURY_SUMMARY, "Group");
+pub const KEY_COL: usize = col_index(&INJURY_SUMMARY, "Key");
+pub const INJURIES_COL: usize = col_index(&INJURY_SUMMARY, "Injuries");
+pub const SR_LOST_COL: usize = col_index(&INJURY_SUMMARY, "SR lost");
+
+/// Group column values, in the order the groups are listed.
+pub const GROUP_TEAM: &str = "Team";
+pub const GROUP_TYPE: &str = "Type";
+pub const GROUP_WEEK: &str = "Week";
+pub const GROUP_TOTAL: &str = "Total";
+
+/// "S3 W7", or "W7" without a season.
+fn week_label(season: &str, week: &str) -> String {
+    if season.is_empty() { format!("W{}", week) } else { format!("S{} W{}", season, week) }
+}
+
+/// Teams and types by injuries, most first (ties in order of first
+/// appearance); weeks in order, oldest first; then the total. SR lost is
+/// SR0 − SR1 where both are known (deaths and unchanged ratings cost none).
+/// Returns and other events aren't injuries.
+pub fn from_injuries(injuries: &DataSet) -> DataSet {
+    let headers = crate::schema::for_page(PageKind::InjurySummary)
+        .map(|p| p.names().into_iter().map(String::from).collect());
+
+    let cell = |r: &Vec<String>, c: usize| r.get(c).map(|s| s.trim().to_string()).unwrap_or_default();
+    let num = |r: &Vec<String>, c: usize| r.get(c).and_then(|s| s.trim().parse::<i64>().ok());
+    let mut hurt: Vec<&Vec<String>> = injuries.rows.iter()
+        .filter(|r| r.get(INJ_EVENT).is_none_or(|e| e.is_empty() || e == EVENT_INJURY))
+        .collect();
+    // Chronological, so the weeks come out in order
+    hurt.sort_by_key(|r| (num(r, INJ_SEASON).unwrap_or(0), num(r, INJ_WEEK).unwrap_or(0)));
+
+    // One row per injury: Team, Type, Week, SR lost
+    let flat = DataSet {
+        headers: Some(vec![s!(GROUP_TEAM), s!(GROUP_TYPE), s!(GROUP_WEEK), s!("SR lost")]),
+        rows: hurt.iter().map(|r| {
+            let lost = match (num(r, INJ_SR0), num(r, INJ_SR1)) {
+                (Some(a), Some(b)) => (a - b).max(0),
+                _ => 0,
+            };
+            vec![cell(r, INJ_TEAM), cell(r, INJ_TYPE), week_label(&cell(r, INJ_SEASON), &cell(r, INJ_WEEK)), lost.to_string()]
+        }).collect(),
+    };
+
+    // summarize: key, count, "SR lost avg", "SR lost total"
+    let mut rows = Vec::new();
+    for (col, group) in [GROUP_TEAM, GROUP_TYPE, GROUP_WEEK].into_iter().enumerate() {
+        let others: Vec<usize> = (0..3).filter(|&c| c != col).collect();
+        let mut sums: Vec<Vec<String>> = summarize(&flat, col, "Injuries", &others).rows;
+        if group != GROUP_WEEK {
+            sums.sort_by_key(|r| std::cmp::Reverse(r[1].parse::<usize>().unwrap_or(0)));
+        }
+        rows.extend(sums.into_iter().map(|r| vec![s!(group), r[0].clone(), r[1].clone(), r[3].clone()]));
+    }
+    let lost: i64 = flat.rows.iter().filter_map(|r| r[3].parse::<i64>().ok()).sum();
+    rows.push(vec![s!(GROUP_TOTAL), s!("All"), flat.rows.len().to_string(), lost.to_string()]);
+
+    DataSet { headers, rows }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn injury(s: &str, w: &str, team: &str, sr0: &str, sr1: &str, ty: &str, event: &str) -> Vec<String> {
+        [s, w, team, "X", "2", sr0, sr1, ty, "", "", "", "", event].iter().map(|c| s!(*c)).collect()
+    }
+
+    #[test]
+    fn counts_per_team_type_and_week_with_sr_lost() {
+        let injuries = DataSet {
+            headers: Some(INJURIES.iter().map(|c| s!(c.name)).collect()),
+            rows: vec![
+                injury("2", "5", "Reds", "", "", "KILLED", "Injury"),
+                injury("2", "4", "Blues", "70", "66", "Smashed Knee", "Injury"),
+                injury("2", "4", "Reds", "80", "77", "Smashed Knee", "Injury"),
+                injury("2", "5", "Reds", "", "", "back from Smashed Knee", "Return"),
+                injury("1", "9", "Reds", "60", "60", "Badly Hurt", "Injury"),
+            ],
+        };
+        let sum = from_injuries(&injuries);
+        assert_eq!(crate::schema::validate(PageKind::InjurySummary, &sum), Ok(()));
+        let rows: Vec<Vec<&str>> = sum.rows.iter().map(|r| r.iter().map(String::as_str).collect()).collect();
+        assert_eq!(rows, vec![
+            vec!["Team", "Reds", "3", "3"],
+            vec!["Team", "Blues", "1", "4"],
+            vec!["Type", "Smashed Knee", "2", "7"],
+            vec!["Type", "Badly Hurt", "1", "0"],
+            vec!["Type", "KILLED", "1", "0"],
+            vec!["Week", "S1 W9", "1", "0"],
+            vec!["Week", "S2 W4", "2", "7"],
+            vec!["Week", "S2 W5", "1", "0"],
+            vec!["Total", "All", "4", "7"],
+        ]);
+    }
+}
@@ -0,0 +1,192 @@
+// src/integrity.rs
+//
+// Integrity check of the cached pages (CLI `--verify-cache`, GUI Settings →
+// Storage → Verify). Rows are scanned for the wrong number of columns,
+// blank rows and duplicate keys (the page's `row_key_columns`); what's left
+// then goes through the schema check and the page's `validate_cache`.
+//
+// Repair drops the bad rows and saves the rest, keeping the old data as the
+// page's previous copy (`--restore-previous` undoes it). Whole-page problems
+// (wrong headers, a column of the wrong type) need a re-scrape instead.
+
+use std::{collections::HashMap, fmt, io};
+
+use crate::config::options::PageKind;
+use crate::gui::{pages::Page, router};
+use crate::schema;
+use crate::store::{self, DataSet, CACHED_PAGES};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Problem {
+    /// The cached file or table couldn't be read
+    Unreadable(String),
+    /// The schema or page check rejects the data; a re-scrape fixes it
+    Invalid(String),
+    /// Row `row` (1-based) has `len` columns instead of `expected`
+    Width { row: usize, len: usize, expected: usize },
+    Blank { row: usize },
+    /// Row `row` has the key of row `first`
+    Duplicate { row: usize, first: usize },
+}
+
+impl Problem {
+    /// The row repair drops for this problem (0-based), if it's a row's.
+    fn row_ix(&self) -> Option<usize> {
+        match self {
+            Problem::Width { row, .. } | Problem::Blank { row } | Problem::Duplicate { row, .. } => Some(row - 1),
+            Problem::Unreadable(_) | Problem::Invalid(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::Unreadable(e) => write!(f, "unreadable: {}", e),
+            Problem::Invalid(msg) => write!(f, "{} (re-scrape the page)", msg),
+            Problem::Width { row, len, expected } => write!(f, "row {} has {} columns, expected {}", row, len, expected),
+            Problem::Blank { row } => write!(f, "row {} is blank", row),
+            Problem::Duplicate { row, first } => write!(f, "row {} repeats the key of row {}", row, first),
+        }
+    }
+}
+
+/// One cached page's check.
+#[derive(Clone, Debug)]
+pub struct PageCheck {
+    pub kind: PageKind,
+    pub rows: usize,
+    pub problems: Vec<Problem>,
+}
+
+impl PageCheck {
+    pub fn is_ok(&self) -> bool { self.problems.is_empty() }
+
+    /// Rows a repair would drop.
+    pub fn repairable(&self) -> usize {
+        self.problems.iter().filter(|p| p.row_ix().is_some()).count()
+    }
+
+    /// e.g. "812 rows, OK" / "812 rows, 3 problems (3 repairable)".
+    pub fn summary(&self) -> String {
+        match self.problems.len() {
+            0 => format!("{} rows, OK", self.rows),
+            n => format!("{} rows, {} problem(s) ({} repairable)", self.rows, n, self.repairable()),
+        }
+    }
+}
+
+/// The page behind `kind`, if it has one (Teams and Player don't).
+fn page(kind: PageKind) -> Option<&'static dyn Page> {
+    router::all_pages().iter().copied().find(|p| p.kind() == kind)
+}
+
+/// Problems in `ds`, the cached data of `kind`.
+pub fn check(kind: PageKind, ds: &DataSet) -> Vec<Problem> {
+    let expected = ds.headers.as_ref().map(|h| h.len())
+        .or_else(|| ds.rows.first().map(|r| r.len()))
+        .unwrap_or(0);
+    let keys = page(kind).map(|p| p.row_key_columns()).unwrap_or(&[]);
+    let mut seen: HashMap<Vec<&str>, usize> = HashMap::new();
+    let mut out = Vec::new();
+
+    for (i, r) in ds.rows.iter().enumerate() {
+        let row = i + 1;
+        if r.iter().all(|c| c.trim().is_empty()) {
+            out.push(Problem::Blank { row });
+        } else if r.len() != expected {
+            out.push(Problem::Width { row, len: r.len(), expected });
+        } else if !keys.is_empty() {
+            let key: Vec<&str> = keys.iter().map(|&c| r.get(c).map(|s| s.trim()).unwrap_or("")).collect();
+            match seen.get(&key) {
+                Some(&first) => out.push(Problem::Duplicate { row, first }),
+                None => { seen.insert(key, row); }
+            }
+        }
+    }
+
+    // Whole-page checks on what a repair keeps, so a bad row isn't
+    // reported twice
+    let kept = repaired(ds, &out);
+    if let Err(msg) = schema::validate(kind, &kept) {
+        out.push(Problem::Invalid(msg));
+    } else if page(kind).is_some_and(|p| !p.validate_cache(&kept)) {
+        out.push(Problem::Invalid(format!("{}: the page's own check fails", kind)));
+    }
+    out
+}
+
+/// `ds` without the rows `problems` name.
+fn repaired(ds: &DataSet, problems: &[Problem]) -> DataSet {
+    let drop: Vec<usize> = problems.iter().filter_map(Problem::row_ix).collect();
+    DataSet {
+        headers: ds.headers.clone(),
+        rows: ds.rows.iter().enumerate()
+            .filter(|(i, _)| !drop.contains(i))
+            .map(|(_, r)| r.clone())
+            .collect(),
+    }
+}
+
+/// Check every cached page.
+pub fn check_cache() -> Vec<PageCheck> {
+    CACHED_PAGES.into_iter()
+        .filter(|kind| store::saved_at(kind).is_some())
+        .map(|kind| match store::load_dataset(&kind) {
+            Ok(ds) => PageCheck { kind, rows: ds.row_count(), problems: check(kind, &ds) },
+            Err(e) => PageCheck { kind, rows: 0, problems: vec![Problem::Unreadable(e.to_string())] },
+        })
+        .collect()
+}
+
+/// Drop the bad rows of `kind`'s cache and save the rest; the data from
+/// before goes to the page's previous copy. Returns the rows dropped.
+pub fn repair(kind: PageKind) -> io::Result<usize> {
+    let ds = store::load_dataset(&kind)?;
+    let problems = check(kind, &ds);
+    let dropped = problems.iter().filter(|p| p.row_ix().is_some()).count();
+    if dropped == 0 { return Ok(0); }
+
+    store::save_previous(&kind, &ds)?;
+    store::save_dataset(&kind, &repaired(&ds, &problems))?;
+    logf!("Cache: Repaired {:?}, dropped {} row(s)", kind, dropped);
+    Ok(dropped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ds(rows: &[&[&str]]) -> DataSet {
+        DataSet {
+            headers: Some(["Name", "#", "Race", "Team", "Player id"].iter().map(|h| s!(*h)).collect()),
+            rows: rows.iter().map(|r| r.iter().map(|c| s!(*c)).collect()).collect(),
+        }
+    }
+
+    #[test]
+    fn finds_and_drops_bad_rows() {
+        let data = ds(&[
+            &["Al", "#1", "Orc", "Reds", "7"],
+            &["", "", "", "", ""],
+            &["Bo", "#2", "Elf", "Reds"],
+            &["Al", "#1", "Orc", "Reds", "7"],
+            &["Cy", "#3", "Orc", "Blues", "9"],
+        ]);
+        let problems = check(PageKind::Players, &data);
+        assert_eq!(problems, vec![
+            Problem::Blank { row: 2 },
+            Problem::Width { row: 3, len: 4, expected: 5 },
+            Problem::Duplicate { row: 4, first: 1 },
+        ]);
+        let kept = repaired(&data, &problems);
+        assert_eq!(kept.rows.iter().map(|r| r[0].as_str()).collect::<Vec<_>>(), ["Al", "Cy"]);
+        assert!(check(PageKind::Players, &kept).is_empty());
+
+        // A column of the wrong type is the page's, not a row's
+        let bad_id = ds(&[&["Al", "#1", "Orc", "Reds", "seven"]]);
+        let problems = check(PageKind::Players, &bad_id);
+        assert!(matches!(problems.as_slice(), [Problem::Invalid(_)]));
+        assert_eq!(PageCheck { kind: PageKind::Players, rows: 1, problems }.repairable(), 0);
+    }
+}
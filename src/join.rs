@@ -0,0 +1,246 @@
+// src/join.rs
+//
+// Join two datasets on (team, player name): each left row gets columns from
+// the matching right row. Names don't always agree between pages (case,
+// stray punctuation or brackets, a shortened name), so matching falls back
+// from exact to normalized to a unique prefix within the team. Names are
+// looked up through the user's aliases first (see `alias`). A name two
+// players of the team share matches neither.
+// The Injury Victims page is Injuries joined to Players on the victim.
+
+use crate::{alias::Aliases, config::options::PageKind, store::DataSet};
+
+/// Lowercase, bracket tags ("[out]") and punctuation dropped, single spaces.
+pub fn name_key(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut depth = 0usize;
+    for ch in s.chars() {
+        match ch {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth = depth.saturating_sub(1),
+            _ if depth > 0 => {}
+            c if c.is_alphanumeric() => out.extend(c.to_lowercase()),
+            c if c.is_whitespace() || c == '-' || c == '_' => out.push(' '),
+            _ => {}
+        }
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// How the left rows were matched.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct JoinStats {
+    pub exact: usize,
+    /// Matched after `name_key`, or as the team's only name with that prefix
+    pub fuzzy: usize,
+    /// Left rows without a match (blank columns), as (team, name)
+    pub missing: Vec<(String, String)>,
+    /// Left rows whose name belongs to more than one player of the team
+    /// (blank columns rather than a guess), as (team, name)
+    pub ambiguous: Vec<(String, String)>,
+}
+
+/// Rows of `right` for one team, with normalized names.
+struct TeamIndex<'a> {
+    team: &'a str,
+    names: Vec<(&'a str, String, usize)>,
+}
+
+/// How a left row's `(team, name)` was found on the right.
+#[derive(Clone, Copy)]
+enum Found {
+    /// The right row, and whether the name matched exactly
+    Row(usize, bool),
+    /// The team has several players of that name
+    Ambiguous,
+}
+
+/// The right row for `(team, name)`.
+fn find(index: &[TeamIndex], team: &str, name: &str) -> Option<Found> {
+    let ti = index.iter().find(|t| t.team == team)
+        .or_else(|| index.iter().find(|t| t.team.eq_ignore_ascii_case(team.trim())))?;
+    let exact: Vec<usize> = ti.names.iter().filter(|(n, ..)| *n == name).map(|(.., ri)| *ri).collect();
+    match exact[..] {
+        [ri] => return Some(Found::Row(ri, true)),
+        [_, _, ..] => return Some(Found::Ambiguous),
+        [] => {}
+    }
+    let key = name_key(name);
+    if key.is_empty() { return None; }
+    let same_key: Vec<usize> = ti.names.iter().filter(|(_, k, _)| *k == key).map(|(.., ri)| *ri).collect();
+    match same_key[..] {
+        [ri] => return Some(Found::Row(ri, false)),
+        [_, _, ..] => return Some(Found::Ambiguous),
+        [] => {}
+    }
+    // One name shortened: accept it only when it's unambiguous within the team
+    let mut prefixed = ti.names.iter()
+        .filter(|(_, k, _)| k.starts_with(&key) || key.starts_with(k.as_str()));
+    match (prefixed.next(), prefixed.next()) {
+        (Some((_, _, ri)), None) => Some(Found::Row(*ri, false)),
+        _ => None,
+    }
+}
+
+/// `left` with the `take` columns of the matching `right` row appended under
+/// `new_headers`. Rows are matched on (team, name) columns of either side,
+/// names on both sides taken through `aliases`; unmatched rows get blanks.
+pub fn join_on_team_name(
+    left: &DataSet,
+    (left_team, left_name): (usize, usize),
+    right: &DataSet,
+    (right_team, right_name): (usize, usize),
+    take: &[usize],
+    new_headers: &[String],
+    aliases: &Aliases,
+) -> (DataSet, JoinStats) {
+    let mut index: Vec<TeamIndex> = Vec::new();
+    for (ri, r) in right.rows.iter().enumerate() {
+        let (Some(team), Some(name)) = (r.get(right_team), r.get(right_name)) else { continue };
+        let name = aliases.canonical(name);
+        let entry = (name, name_key(name), ri);
+        match index.iter_mut().find(|t| t.team == team) {
+            Some(t) => t.names.push(entry),
+            None => index.push(TeamIndex { team, names: vec![entry] }),
+        }
+    }
+
+    let mut stats = JoinStats::default();
+    let rows = left.rows.iter().map(|l| {
+        let team = l.get(left_team).map(String::as_str).unwrap_or("");
+        let name = l.get(left_name).map(String::as_str).unwrap_or("");
+        let matched = match find(&index, team, aliases.canonical(name)) {
+            Some(Found::Row(ri, exact)) => {
+                if exact { stats.exact += 1 } else { stats.fuzzy += 1 }
+                Some(ri)
+            }
+            Some(Found::Ambiguous) => { stats.ambiguous.push((s!(team), s!(name))); None }
+            None => { stats.missing.push((s!(team), s!(name))); None }
+        };
+        let mut row = l.clone();
+        row.extend(take.iter().map(|&c| {
+            matched.and_then(|ri| right.rows[ri].get(c)).cloned().unwrap_or_default()
+        }));
+        row
+    }).collect();
+
+    let headers = left.headers.clone().map(|mut h| {
+        h.extend(new_headers.iter().cloned());
+        h
+    });
+    (DataSet { headers, rows }, stats)
+}
+
+// Injuries: 2 Victim Team, 3 Victim. Players: 0 Name, 1 #, 2 Race, 3 Team.
+const INJ_VICTIM_TEAM: usize = 2;
+const INJ_VICTIM: usize = 3;
+const PLAYERS_NAME: usize = 0;
+const PLAYERS_TEAM: usize = 3;
+
+/// Players columns added to each injury, by header; missing ones are skipped.
+pub const VICTIM_COLUMNS: [&str; 4] = ["Race", "TV", "OVR", crate::scrape::PLAYER_ID_HEADER];
+
+/// Injury Victims: each injury with the victim's number, race and key stats
+/// ("Victim #", "Victim Race", "Victim TV", …) from Players. Returns and
+/// other non-injury events (Event column) are left out.
+pub fn injuries_with_victims(injuries: &DataSet, players: &DataSet, aliases: &Aliases) -> (DataSet, JoinStats) {
+    // Injury rows only, without the derived columns (Injury Victims has its own)
+    let mut only_injuries = DataSet {
+        headers: injuries.headers.clone(),
+        rows: match injuries.header_index("Event") {
+            Some(ev) => injuries.rows.iter()
+                .filter(|r| r.get(ev).is_none_or(|e| e == crate::scrape::injuries::EVENT_INJURY))
+                .cloned()
+                .collect(),
+            None => injuries.rows.clone(),
+        },
+    };
+    crate::derive::strip(PageKind::Injuries, &mut only_injuries);
+    let injuries = &only_injuries;
+    let mut take = vec![1];
+    let mut new_headers = vec![s!("Victim #")];
+    for name in VICTIM_COLUMNS {
+        if let Some(c) = players.header_index(name) {
+            take.push(c);
+            new_headers.push(format!("Victim {}", name.trim_start_matches("Player ")));
+        }
+    }
+    join_on_team_name(injuries, (INJ_VICTIM_TEAM, INJ_VICTIM), players, (PLAYERS_TEAM, PLAYERS_NAME), &take, &new_headers, aliases)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ds(headers: &[&str], rows: &[&[&str]]) -> DataSet {
+        DataSet {
+            headers: Some(headers.iter().map(|h| s!(*h)).collect()),
+            rows: rows.iter().map(|r| r.iter().map(|c| s!(*c)).collect()).collect(),
+        }
+    }
+
+    #[test]
+    fn name_key_ignores_case_tags_and_punctuation() {
+        assert_eq!(name_key("  O'Brien  [CAPTAIN] Jr."), "obrien jr");
+        assert_eq!(name_key("Grim-Tooth"), "grim tooth");
+    }
+
+    #[test]
+    fn joins_exact_normalized_and_unique_prefix() {
+        let players = ds(&["Name", "#", "Race", "Team", "TV", "Player id"], &[
+            &["Grim Tooth", "#7", "Orc", "Reds", "120", "11"],
+            &["Al Smith", "#1", "Human", "Reds", "90", "12"],
+            &["Al Smithers", "#2", "Human", "Blues", "95", "13"],
+            &["Bo", "#3", "Elf", "Blues", "80", "14"],
+        ]);
+        let injuries = ds(&["S", "W", "Victim Team", "Victim"], &[
+            &["7", "1", "Reds", "Grim Tooth"],
+            &["7", "2", "Reds", "grim-tooth"],
+            &["7", "3", "Blues", "Al Smith"],
+            &["7", "4", "Blues", "Zed"],
+        ]);
+        let (out, stats) = injuries_with_victims(&injuries, &players, &Aliases::default());
+        assert_eq!(out.headers.unwrap()[4..], ["Victim #", "Victim Race", "Victim TV", "Victim id"]);
+        assert_eq!(out.rows[0][4..], ["#7", "Orc", "120", "11"]);
+        assert_eq!(out.rows[1][4..], ["#7", "Orc", "120", "11"]);
+        // Only Blues player starting with "al smith"
+        assert_eq!(out.rows[2][4..], ["#2", "Human", "95", "13"]);
+        assert_eq!(out.rows[3][4..], ["", "", "", ""]);
+        assert_eq!((stats.exact, stats.fuzzy), (1, 2));
+        assert_eq!(stats.missing, vec![(s!("Blues"), s!("Zed"))]);
+
+        // Returns and other events aren't injuries
+        let events = ds(&["S", "W", "Victim Team", "Victim", "Event"], &[
+            &["7", "1", "Reds", "Grim Tooth", "Injury"],
+            &["7", "3", "Reds", "Grim Tooth", "Return"],
+        ]);
+        let (out, _) = injuries_with_victims(&events, &players, &Aliases::default());
+        assert_eq!(out.rows.len(), 1);
+        assert_eq!(out.rows[0][5..], ["#7", "Orc", "120", "11"]);
+
+        // A resolved spelling finds the player
+        let mut aliases = Aliases::default();
+        aliases.insert("Zed", "Bo");
+        let (out, stats) = injuries_with_victims(&injuries, &players, &aliases);
+        assert_eq!(out.rows[3][4..], ["#3", "Elf", "80", "14"]);
+        assert!(stats.missing.is_empty());
+    }
+
+    #[test]
+    fn leaves_a_name_two_players_share_blank() {
+        let players = ds(&["Name", "#", "Race", "Team", "TV", "Player id"], &[
+            &["Grim", "#7", "Orc", "Reds", "120", "11"],
+            &["Grim", "#9", "Orc", "Reds", "60", "15"],
+            &["Grim", "#2", "Orc", "Blues", "80", "16"],
+        ]);
+        let injuries = ds(&["S", "W", "Victim Team", "Victim"], &[
+            &["7", "1", "Reds", "Grim"],
+            &["7", "1", "Blues", "Grim"],
+        ]);
+        let (out, stats) = injuries_with_victims(&injuries, &players, &Aliases::default());
+        assert_eq!(out.rows[0][4..], ["", "", "", ""]);
+        assert_eq!(out.rows[1][4..], ["#2", "Orc", "80", "16"]);
+        assert_eq!(stats.ambiguous, vec![(s!("Reds"), s!("Grim"))]);
+        assert!(stats.missing.is_empty());
+    }
+}
@@ -0,0 +1,84 @@
+// src/leaders.rs
+//
+// League leaders: for each stat column of the Players data, the top N
+// players league-wide with their team. Purely local, like Team Summary.
+// Season Stats would be the better source, but it isn't scraped yet.
+
+use crate::aggregate::players_stat_columns;
+use crate::config::options::PageKind;
+use crate::store::DataSet;
+use crate::typed::TypedColumns;
+
+/// Players name and team columns (schema order: Name, #, Race, Team).
+const NAME_COL: usize = 0;
+const TEAM_COL: usize = 3;
+
+/// Leaders rank column (schema order: Stat, Rank, Player, Team, Value).
+pub const RANK_COL: usize = 1;
+
+/// Most players per stat the GUI keeps; its Top N setting filters these.
+pub const MAX_TOP: usize = 50;
+
+/// The leaders of each stat, in Players column order: one row per player
+/// ranked `top` or better, highest value first. Ties share a rank (1, 2, 2,
+/// 4), so a tie at the cutoff can make more than `top` rows. Players without
+/// a value aren't ranked. Stats are the numeric columns other than number,
+/// player id, team and derived ones.
+pub fn from_players(players: &DataSet, top: usize) -> DataSet {
+    let headers = crate::schema::for_page(PageKind::Leaders)
+        .map(|p| p.names().into_iter().map(String::from).collect());
+    let Some(names) = players.headers.as_ref() else { return DataSet { headers, rows: Vec::new() } };
+
+    let typed = TypedColumns::for_page(PageKind::Players, players);
+    let mut rows = Vec::new();
+    for c in players_stat_columns(players, &typed) {
+        let mut ranked: Vec<(usize, f64)> = (0..players.rows.len())
+            .filter_map(|i| typed.number(i, c).map(|x| (i, x)))
+            .collect();
+        ranked.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+        let mut rank = 0;
+        for (pos, &(i, x)) in ranked.iter().enumerate() {
+            if pos == 0 || x != ranked[pos - 1].1 { rank = pos + 1; }
+            if rank > top { break; }
+            let r = &players.rows[i];
+            let cell = |ci: usize| r.get(ci).cloned().unwrap_or_default();
+            rows.push(vec![names[c].clone(), rank.to_string(), cell(NAME_COL), cell(TEAM_COL), cell(c)]);
+        }
+    }
+    DataSet { headers, rows }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ds(headers: &[&str], rows: &[&[&str]]) -> DataSet {
+        DataSet {
+            headers: Some(headers.iter().map(|h| s!(*h)).collect()),
+            rows: rows.iter().map(|r| r.iter().map(|c| s!(*c)).collect()).collect(),
+        }
+    }
+
+    #[test]
+    fn top_n_per_stat_with_ties_at_the_cutoff() {
+        let players = ds(&["Name", "#", "Race", "Team", "TV", "SR"], &[
+            &["Al", "#1", "Orc", "Reds", "100", "5"],
+            &["Bo", "#2", "Elf", "Blues", "120", "7"],
+            &["Cy", "#3", "Orc", "Reds", "90", "7"],
+            &["Di", "#4", "Elf", "Greens", "110", ""],
+        ]);
+        let l = from_players(&players, 1);
+        assert_eq!(crate::schema::validate(PageKind::Leaders, &l), Ok(()));
+        let rows: Vec<Vec<&str>> = l.rows.iter().map(|r| r.iter().map(String::as_str).collect()).collect();
+        assert_eq!(rows, vec![
+            vec!["TV", "1", "Bo", "Blues", "120"],
+            vec!["SR", "1", "Bo", "Blues", "7"],
+            vec!["SR", "1", "Cy", "Reds", "7"],
+        ]);
+
+        let l = from_players(&players, 3);
+        let sr: Vec<(&str, &str)> = l.rows.iter().filter(|r| r[0] == "SR").map(|r| (r[1].as_str(), r[2].as_str())).collect();
+        assert_eq!(sr, vec![("1", "Bo"), ("1", "Cy"), ("3", "Al")]);
+    }
+}
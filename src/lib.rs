@@ -11,9 +11,33 @@ pub mod gui;
 pub mod config;
 
 pub mod core;
+pub mod aggregate;
+pub mod alias;
+pub mod compare;
 pub mod data;
+pub mod derive;
+pub mod diagnostics;
+pub mod diff;
+pub mod error;
+pub mod expr;
 pub mod file;
+pub mod import;
+pub mod injury_summary;
+pub mod integrity;
+pub mod join;
+pub mod leaders;
+pub mod manifest;
+pub mod notify;
 pub mod progress;
+pub mod results_table;
+pub mod schedule;
+pub mod schedule_metrics;
+pub mod schema;
+pub mod serve;
+pub mod typed;
 pub mod scrape;
 pub mod store;
+pub mod trend;
+pub mod verify;
+pub mod zip;
 pub mod get_teams;
\ No newline at end of file
@@ -1,13 +1,16 @@
 // src/log.rs
+use std::collections::VecDeque;
 use std::fs::OpenOptions;
 use std::io::Write;
 use std::sync::{Mutex, OnceLock};
 use std::time::Instant;
 
-static LOG_FILE: &str = ".store/bb_scrape.log";
 static LOG_LOCK: Mutex<()> = Mutex::new(());
 static START: OnceLock<Instant> = OnceLock::new();
 static MIN_LEVEL: OnceLock<Level> = OnceLock::new();
+/// The last lines written, for the GUI log panel
+static RECENT: Mutex<VecDeque<LogLine>> = Mutex::new(VecDeque::new());
+const RECENT_MAX: usize = 1000;
 
 fn start() -> Instant {
     *START.get_or_init(Instant::now)
@@ -23,7 +26,40 @@ fn fmt_elapsed(ms: u128) -> String {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
-enum Level { Debug, Info, Error }
+pub enum Level { Debug, Info, Error }
+
+impl Level {
+    pub const ALL: [Level; 3] = [Level::Debug, Level::Info, Level::Error];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Level::Debug => "DEBUG",
+            Level::Info  => "INFO",
+            Level::Error => "ERROR",
+        }
+    }
+}
+
+/// One log line as kept in memory.
+#[derive(Clone, Debug)]
+pub struct LogLine {
+    pub level: Level,
+    /// Time since start, `hh:mm:ss.mmm`
+    pub elapsed: String,
+    pub msg: String,
+}
+
+impl LogLine {
+    /// As written to the log file, without the newline.
+    pub fn text(&self) -> String {
+        format!("[{}][{}] {}", self.elapsed, self.level.as_str(), self.msg)
+    }
+}
+
+/// The most recent log lines (up to 1000), oldest first.
+pub fn recent() -> Vec<LogLine> {
+    RECENT.lock().map(|r| r.iter().cloned().collect()).unwrap_or_default()
+}
 
 fn parse_level(s: &str) -> Option<Level> {
     match s.to_ascii_uppercase().as_str() {
@@ -56,11 +92,16 @@ pub fn write_log(level: &str, msg: &str) {
     let elapsed = fmt_elapsed(start().elapsed().as_millis());
     let line = format!("[{elapsed}][{level}] {msg}\n");
 
+    if let Ok(mut recent) = RECENT.lock() {
+        if recent.len() == RECENT_MAX { recent.pop_front(); }
+        recent.push_back(LogLine { level: level_of(level), elapsed, msg: msg.to_string() });
+    }
+
     if let Ok(_guard) = LOG_LOCK.lock() {
         if let Ok(mut file) = OpenOptions::new()
             .create(true)
             .append(true)
-            .open(LOG_FILE)
+            .open(crate::store::store_dir().join(crate::config::consts::LOG_FILE_NAME))
         {
             let _ = file.write_all(line.as_bytes());
         }
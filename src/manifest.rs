@@ -0,0 +1,184 @@
+// src/manifest.rs
+//
+// Optional `manifest.json` written next to exported files, so downstream
+// automation can check that a run produced everything it should.
+// Hand-rolled JSON (no serde dependency); the shape is small and flat.
+
+use std::{
+    fs,
+    io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::config::consts::{HOST, PARSER_VERSION, PREFIX};
+use crate::config::options::{Backup, ExportOptions, PageKind};
+use crate::file::parse_rows;
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManifestEntry {
+    pub path: PathBuf,
+    /// Data rows (header row not counted)
+    pub rows: usize,
+    pub bytes: u64,
+    /// FNV-1a 64-bit of the file contents, as 16 hex digits
+    pub checksum: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct Manifest {
+    pub page: PageKind,
+    pub season: Option<u32>,
+    pub format: String,
+    pub include_headers: bool,
+    pub files: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    /// Describe already-written export files by reading them back.
+    pub fn from_files(
+        page: PageKind,
+        season: Option<u32>,
+        export: &ExportOptions,
+        paths: &[PathBuf],
+    ) -> io::Result<Self> {
+        let sep = export.delimiter().unwrap_or(',');
+        let mut files = Vec::with_capacity(paths.len());
+        for p in paths {
+            let bytes = fs::read(p)?;
+            let text = String::from_utf8_lossy(&bytes);
+            let rows = data_rows(&text, sep, export);
+            files.push(ManifestEntry {
+                path: p.clone(),
+                rows,
+                bytes: bytes.len() as u64,
+                checksum: format!("{:016x}", fnv1a64(&bytes)),
+            });
+        }
+        Ok(Self {
+            page,
+            season,
+            format: export.format.to_string(),
+            include_headers: export.include_headers,
+            files,
+        })
+    }
+
+    pub fn total_rows(&self) -> usize {
+        self.files.iter().map(|f| f.rows).sum()
+    }
+
+    /// Render as JSON. File paths are made relative to `base` when they live under it.
+    pub fn to_json(&self, base: &Path) -> String {
+        let generated = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        let mut out = s!("{\n");
+        out.push_str(&format!("  \"tool\": {},\n", json_str(&format!("bb_scrape {}", env!("CARGO_PKG_VERSION")))));
+        out.push_str(&format!("  \"parser_version\": {},\n", PARSER_VERSION));
+        out.push_str(&format!("  \"source\": {},\n", json_str(&format!("https://{}{}", HOST, PREFIX))));
+        out.push_str(&format!("  \"page\": {},\n", json_str(&self.page.to_string())));
+        match self.season {
+            Some(s) => out.push_str(&format!("  \"season\": {},\n", s)),
+            None => out.push_str("  \"season\": null,\n"),
+        }
+        out.push_str(&format!("  \"generated_unix\": {},\n", generated));
+        out.push_str(&format!("  \"format\": {},\n", json_str(&self.format)));
+        out.push_str(&format!("  \"include_headers\": {},\n", self.include_headers));
+        out.push_str("  \"checksum\": \"fnv1a64\",\n");
+        out.push_str(&format!("  \"total_rows\": {},\n", self.total_rows()));
+        out.push_str("  \"files\": [");
+        for (i, f) in self.files.iter().enumerate() {
+            let shown = f.path.strip_prefix(base).unwrap_or(&f.path);
+            out.push_str(if i == 0 { "\n" } else { ",\n" });
+            out.push_str(&format!(
+                "    {{ \"path\": {}, \"rows\": {}, \"bytes\": {}, \"checksum\": {} }}",
+                json_str(&shown.to_string_lossy().replace('\\', "/")),
+                f.rows,
+                f.bytes,
+                json_str(&f.checksum),
+            ));
+        }
+        out.push_str(if self.files.is_empty() { "]\n" } else { "\n  ]\n" });
+        out.push_str("}\n");
+        out
+    }
+
+    /// Write `manifest.json` into `dir`. Returns the manifest path.
+    pub fn write_to(&self, dir: &Path) -> io::Result<PathBuf> {
+        let path = dir.join(MANIFEST_FILE);
+        crate::file::write_atomic(&path, self.to_json(dir).as_bytes(), Backup::Off)?;
+        Ok(path)
+    }
+}
+
+/// Directory a manifest belongs in for an export: the per-team directory,
+/// or the parent of the single output file.
+pub fn manifest_dir(export: &ExportOptions) -> PathBuf {
+    use crate::config::options::ExportType::*;
+    let out = export.out_path();
+    match export.export_type {
+        PerTeam => out,
+        SingleFile => out.parent().map(Path::to_path_buf).unwrap_or_default(),
+    }
+}
+
+/// Data rows in an exported file (header row, Markdown separator and
+/// BBCode `[table]` lines not counted).
+fn data_rows(text: &str, sep: char, export: &ExportOptions) -> usize {
+    use crate::config::options::ExportFormat::*;
+    match export.format {
+        Markdown => text.lines().count().saturating_sub(2),
+        BbCode => text.lines().filter(|l| l.starts_with("[tr][td]")).count(),
+        Csv | Tsv => {
+            let rows = parse_rows(text, sep).len();
+            if export.include_headers { rows.saturating_sub(1) } else { rows }
+        }
+    }
+}
+
+fn fnv1a64(bytes: &[u8]) -> u64 {
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    for &b in bytes {
+        h ^= b as u64;
+        h = h.wrapping_mul(0x0100_0000_01b3);
+    }
+    h
+}
+
+pub(crate) fn json_str(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Write the manifest for a finished export when `export.write_manifest` is on.
+/// Returns the manifest path, or `None` when disabled.
+pub fn write_for_export(
+    page: PageKind,
+    season: Option<u32>,
+    export: &ExportOptions,
+    written: &[PathBuf],
+) -> io::Result<Option<PathBuf>> {
+    if !export.write_manifest { return Ok(None); }
+    let m = Manifest::from_files(page, season, export, written)?;
+    let path = m.write_to(&manifest_dir(export))?;
+    logf!("Export: Manifest ({} files, {} rows) → {}", m.files.len(), m.total_rows(), path.display());
+    Ok(Some(path))
+}
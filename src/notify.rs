@@ -0,0 +1,98 @@
+// src/notify.rs
+//
+// Webhook notification after a scrape that changed something: a short
+// summary of the page's DataDiff, POSTed as Discord-compatible JSON
+// (`{"content": …}`) to the URL in `.store/settings` (`webhook_url=`) or
+// given with CLI `--webhook`. Best effort: a failed POST is logged only.
+
+use std::sync::OnceLock;
+
+use crate::{config::options::PageKind, core::net, diff::DataDiff, manifest::json_str, store};
+
+/// Settings key for the webhook URL (missing or empty = off).
+pub const WEBHOOK_KEY: &str = "webhook_url";
+
+/// Discord's message length limit.
+const MAX_CONTENT: usize = 2000;
+
+static WEBHOOK_URL: OnceLock<String> = OnceLock::new();
+
+/// Use `url` for the rest of this run, whatever the settings say (CLI
+/// `--webhook`). Only the first call counts.
+pub fn set_webhook_url(url: &str) {
+    let _ = WEBHOOK_URL.set(url.trim().to_string());
+}
+
+pub fn webhook_url() -> Option<String> {
+    WEBHOOK_URL.get().cloned()
+        .or_else(|| store::load_settings().get(WEBHOOK_KEY).map(|v| v.trim().to_string()))
+        .filter(|u| !u.is_empty())
+}
+
+/// The message for `diff` on `page`: the summary line, then the report
+/// lines in a code block, cut to fit Discord's limit.
+pub fn message(page: PageKind, diff: &DataDiff) -> String {
+    let head = format!("**bb_scrape · {}**: {}", page, diff.summary());
+    let lines = diff.report_lines();
+    if lines.is_empty() {
+        return head;
+    }
+
+    // Room for the fences and a "… and N more" line
+    let budget = MAX_CONTENT.saturating_sub(head.len() + 40);
+    let mut body = String::new();
+    let mut shown = 0;
+    for line in &lines {
+        if body.len() + line.len() + 1 > budget { break; }
+        body.push_str(line);
+        body.push('\n');
+        shown += 1;
+    }
+    let more = match lines.len() - shown {
+        0 => String::new(),
+        n => format!("… and {} more\n", n),
+    };
+    format!("{}\n```\n{}{}```", head, body, more)
+}
+
+/// Discord-compatible webhook body.
+pub fn payload(content: &str) -> String {
+    format!("{{\"username\": \"bb_scrape\", \"content\": {}}}", json_str(content))
+}
+
+/// POST the diff to the webhook, when one is set and there are changes.
+/// Returns whether a notification was sent.
+pub fn notify(page: PageKind, diff: &DataDiff) -> bool {
+    let Some(url) = webhook_url() else { return false };
+    if diff.is_empty() { return false; }
+    match net::http_post_json(&url, &payload(&message(page, diff))) {
+        Ok(()) => {
+            logf!("Webhook: Sent {} ({})", page, diff.summary());
+            true
+        }
+        Err(e) => {
+            loge!("Webhook: {} not sent: {}", page, e);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_fits_discord_limit() {
+        let diff = DataDiff {
+            headers: Some(vec![s!("Team"), s!("W")]),
+            added: (0..500).map(|i| vec![format!("Team {}", i), s!("1")]).collect(),
+            ..DataDiff::default()
+        };
+        let msg = message(PageKind::GameResults, &diff);
+        assert!(msg.starts_with("**bb_scrape · game-results**: 500 added, 0 removed, 0 changed\n```\n+ "));
+        assert!(msg.chars().count() <= MAX_CONTENT);
+        assert!(msg.contains(" more\n```"));
+
+        assert_eq!(payload("a \"b\"\n"), r#"{"username": "bb_scrape", "content": "a \"b\"\n"}"#);
+    }
+}
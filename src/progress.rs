@@ -12,13 +12,37 @@ pub trait Progress {
     /// Called when one logical unit completes successfully (e.g., a team ID was scraped).
     fn item_done(&mut self, _id: u32, _team_name: &str) {}
 
-    /// Called when one logical unit fails (e.g., a team scrape returned no data or errored).
-    fn item_failed(&mut self, _id: u32, _team_name: &str) {}
+    /// Called as soon as one logical unit fails (e.g., a team scrape returned
+    /// no data or errored), with the reason.
+    fn item_failed(&mut self, _id: u32, _team_name: &str, _error: &str) {}
 
-    /// Called at the end, successful or not.
-    fn finish(&mut self) {}
+    /// Called at the end, successful or not, with the tally.
+    fn finish(&mut self, _summary: &Summary) {}
+
+    /// Polled between items; true stops the run early (the items fetched so
+    /// far are still returned).
+    fn cancelled(&self) -> bool { false }
+}
+
+/// Final tally of a multi-item run (e.g. one roster fetch per team).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub succeeded: usize,
+    /// (id, team name, error), in the order they failed
+    pub failed: Vec<(u32, String, String)>,
+}
+
+impl Summary {
+    /// e.g. "32 succeeded" / "30 succeeded, 2 failed: Reds, Blues".
+    pub fn line(&self) -> String {
+        if self.failed.is_empty() {
+            return format!("{} succeeded", self.succeeded);
+        }
+        let names: Vec<&str> = self.failed.iter().map(|(_, name, _)| name.as_str()).collect();
+        format!("{} succeeded, {} failed: {}", self.succeeded, self.failed.len(), names.join(", "))
+    }
 }
 
 /// A no-op progress sink.
 pub struct NullProgress;
-impl Progress for NullProgress {}
\ No newline at end of file
+impl Progress for NullProgress {}
@@ -0,0 +1,142 @@
+// src/results_table.rs
+//
+// League table computed from the Game Results: games played, W-T-L,
+// points for/against and the current streak per team, for one season.
+// Purely local (no requests), so it doesn't depend on the layout of the
+// site's standings page.
+
+use std::collections::HashMap;
+
+use crate::schedule::{latest_season, num, Cols};
+use crate::store::DataSet;
+
+/// A team's record over the played games of a season.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Record {
+    pub(crate) gp: u32,
+    pub(crate) w: u32,
+    pub(crate) t: u32,
+    pub(crate) l: u32,
+    pub(crate) pf: u32,
+    pub(crate) pa: u32,
+    /// Results of the played games, oldest first ('W', 'T', 'L')
+    pub(crate) results: Vec<char>,
+}
+
+impl Record {
+    fn add(&mut self, scored: u32, conceded: u32) {
+        self.gp += 1;
+        self.pf += scored;
+        self.pa += conceded;
+        let r = match scored.cmp(&conceded) {
+            std::cmp::Ordering::Greater => { self.w += 1; 'W' }
+            std::cmp::Ordering::Equal => { self.t += 1; 'T' }
+            std::cmp::Ordering::Less => { self.l += 1; 'L' }
+        };
+        self.results.push(r);
+    }
+
+    fn diff(&self) -> i64 { i64::from(self.pf) - i64::from(self.pa) }
+
+    /// Latest result and how many in a row, e.g. "W3"; blank before any game.
+    fn streak(&self) -> String {
+        let Some(&last) = self.results.last() else { return String::new() };
+        let n = self.results.iter().rev().take_while(|&&r| r == last).count();
+        format!("{}{}", last, n)
+    }
+}
+
+/// The fixtures of `season` (default: the latest season in `results`) in
+/// week order; the site lists a week's games in kickoff order.
+pub(crate) fn season_games(results: &DataSet, season: Option<u32>) -> Vec<&Vec<String>> {
+    let c = Cols::of(results);
+    let season = season.or_else(|| latest_season(results));
+    let mut games: Vec<&Vec<String>> = results.rows.iter()
+        .filter(|r| season.is_none() || num(r, c.season) == season)
+        .collect();
+    games.sort_by_key(|r| num(r, c.week));
+    games
+}
+
+/// Every team with a game in `season` (played or not) and its record, in
+/// order of first appearance.
+pub(crate) fn tally(results: &DataSet, season: Option<u32>) -> Vec<(String, Record)> {
+    let c = Cols::of(results);
+    let mut records: Vec<(String, Record)> = Vec::new();
+    let mut at: HashMap<String, usize> = HashMap::new();
+
+    for r in season_games(results, season) {
+        let name = |ci: usize| r.get(ci).map(|s| s.trim().to_string()).unwrap_or_default();
+        let (home, away) = (name(c.home), name(c.away));
+        if home.is_empty() || away.is_empty() { continue; }
+        let mut ix = |team: String| *at.entry(team.clone()).or_insert_with(|| {
+            records.push((team, Record::default()));
+            records.len() - 1
+        });
+        let (h, a) = (ix(home), ix(away));
+        if let (Some(hs), Some(as_)) = (num(r, c.hs), num(r, c.as_)) {
+            records[h].1.add(hs, as_);
+            records[a].1.add(as_, hs);
+        }
+    }
+    records
+}
+
+/// The table of `season` (default: the latest season in `results`): one row
+/// per team with a game that season, played or not. Ranked by wins, ties,
+/// points difference, points for, then name. Ids from `teams` (blank for
+/// teams the list doesn't have).
+pub fn from_results(results: &DataSet, teams: &[(u32, String)], season: Option<u32>) -> DataSet {
+    let mut records = tally(results, season);
+    records.sort_by(|(na, a), (nb, b)| {
+        (b.w, b.t, b.diff(), b.pf).cmp(&(a.w, a.t, a.diff(), a.pf))
+            .then_with(|| na.to_lowercase().cmp(&nb.to_lowercase()))
+    });
+
+    let headers = crate::schema::for_page(crate::config::options::PageKind::ResultsTable)
+        .map(|p| p.names().into_iter().map(String::from).collect());
+    let rows = records.into_iter().enumerate().map(|(i, (team, rec))| {
+        let id = teams.iter().find(|(_, n)| *n == team).map(|(id, _)| id.to_string()).unwrap_or_default();
+        vec![
+            (i + 1).to_string(), id, team.clone(), rec.gp.to_string(),
+            rec.w.to_string(), rec.t.to_string(), rec.l.to_string(),
+            rec.pf.to_string(), rec.pa.to_string(), rec.diff().to_string(), rec.streak(),
+        ]
+    }).collect();
+
+    DataSet { headers, rows }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results(rows: &[[&str; 7]]) -> DataSet {
+        DataSet {
+            headers: Some(["S", "W", "Home", "H", "A", "Away", "Match id"].iter().map(|h| s!(*h)).collect()),
+            rows: rows.iter().map(|r| r.iter().map(|c| s!(*c)).collect()).collect(),
+        }
+    }
+
+    #[test]
+    fn ranks_records_of_the_latest_season_with_streaks() {
+        let ds = results(&[
+            ["1", "9", "Reds", "3", "0", "Blues", "1"],
+            ["2", "2", "Reds", "1", "2", "Blues", "5"],
+            ["2", "1", "Reds", "2", "0", "Greens", "4"],
+            ["2", "1", "Blues", "1", "1", "Golds", "3"],
+            ["2", "3", "Greens", "", "", "Golds", ""],
+        ]);
+        let teams = vec![(7, s!("Blues")), (1, s!("Reds"))];
+        let t = from_results(&ds, &teams, None);
+        assert_eq!(t.headers.as_ref().unwrap()[..4], ["Rank", "Id", "Team", "GP"]);
+        let rows: Vec<Vec<&str>> = t.rows.iter().map(|r| r.iter().map(String::as_str).collect()).collect();
+        assert_eq!(rows, vec![
+            vec!["1", "7", "Blues", "2", "1", "1", "0", "3", "2", "1", "W1"],
+            vec!["2", "1", "Reds", "2", "1", "0", "1", "3", "2", "1", "L1"],
+            vec!["3", "", "Golds", "1", "0", "1", "0", "1", "1", "0", "T1"],
+            vec!["4", "", "Greens", "1", "0", "0", "1", "0", "2", "-2", "L1"],
+        ]);
+        assert_eq!(crate::schema::validate(crate::config::options::PageKind::ResultsTable, &t), Ok(()));
+    }
+}
@@ -0,0 +1,217 @@
+// src/schedule.rs
+//
+// Print view of one week's fixtures and results, for posting on league
+// boards. Built from the Game Results dataset (cache or fresh scrape).
+// Rendered as a standalone HTML page with print CSS — "Print → Save as PDF"
+// in a browser gives the PDF — or as plain text.
+
+use crate::store::DataSet;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fixture {
+    pub home: String,
+    pub away: String,
+    /// (home, away) score; `None` for games not played yet
+    pub score: Option<(String, String)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WeekSchedule {
+    pub season: Option<u32>,
+    pub week: u32,
+    /// In site order (kickoff order)
+    pub fixtures: Vec<Fixture>,
+}
+
+/// Game Results columns, by header name with the default layout as fallback.
+pub(crate) struct Cols {
+    pub(crate) season: usize,
+    pub(crate) week: usize,
+    pub(crate) home: usize,
+    pub(crate) hs: usize,
+    pub(crate) as_: usize,
+    pub(crate) away: usize,
+}
+
+impl Cols {
+    pub(crate) fn of(ds: &DataSet) -> Self {
+        let at = |name: &str, dflt: usize| ds.header_index(name).unwrap_or(dflt);
+        Cols {
+            season: at("S", 0), week: at("W", 1), home: at("Home", 2),
+            hs: at("H", 3), as_: at("A", 4), away: at("Away", 5),
+        }
+    }
+}
+
+pub(crate) fn num(row: &[String], ci: usize) -> Option<u32> {
+    row.get(ci).and_then(|s| s.trim().parse().ok())
+}
+
+/// Latest season in the results, if the season column is filled.
+pub fn latest_season(ds: &DataSet) -> Option<u32> {
+    let c = Cols::of(ds);
+    ds.rows.iter().filter_map(|r| num(r, c.season)).max()
+}
+
+/// Weeks with at least one game in `season` (all seasons if `None`), ascending.
+pub fn weeks(ds: &DataSet, season: Option<u32>) -> Vec<u32> {
+    let c = Cols::of(ds);
+    let mut out: Vec<u32> = ds.rows.iter()
+        .filter(|r| season.is_none() || num(r, c.season) == season)
+        .filter_map(|r| num(r, c.week))
+        .collect();
+    out.sort_unstable();
+    out.dedup();
+    out
+}
+
+impl WeekSchedule {
+    /// Fixtures of `week` in `season` (default: the latest season in `ds`).
+    pub fn from_results(ds: &DataSet, season: Option<u32>, week: u32) -> Self {
+        let c = Cols::of(ds);
+        let season = season.or_else(|| latest_season(ds));
+        let cell = |r: &[String], ci: usize| r.get(ci).map(|s| s.trim().to_string()).unwrap_or_default();
+        let fixtures = ds.rows.iter()
+            .filter(|r| num(r, c.week) == Some(week))
+            .filter(|r| season.is_none() || num(r, c.season) == season)
+            .map(|r| {
+                let (h, a) = (cell(r, c.hs), cell(r, c.as_));
+                Fixture {
+                    home: cell(r, c.home),
+                    away: cell(r, c.away),
+                    score: (!h.is_empty() && !a.is_empty()).then_some((h, a)),
+                }
+            })
+            .collect();
+        WeekSchedule { season, week, fixtures }
+    }
+
+    pub fn title(&self) -> String {
+        match self.season {
+            Some(s) => format!("Season {} · Week {}", s, self.week),
+            None => format!("Week {}", self.week),
+        }
+    }
+
+    /// Fixed-width text, one game per line.
+    pub fn to_text(&self) -> String {
+        let wh = self.fixtures.iter().map(|f| f.home.chars().count()).max().unwrap_or(0);
+        let mut out = format!("{}\n\n", self.title());
+        for f in &self.fixtures {
+            let mid = match &f.score {
+                Some((h, a)) => format!("{:>3} - {:<3}", h, a),
+                None => s!("   vs    "),
+            };
+            out.push_str(&format!("{:>w$}  {}  {}\n", f.home, mid, f.away, w = wh));
+        }
+        if self.fixtures.is_empty() { out.push_str("No games.\n"); }
+        out
+    }
+
+    /// Standalone HTML page laid out for printing (A4/Letter, black on white).
+    pub fn to_html(&self) -> String {
+        let title = escape_html(&self.title());
+        let mut rows = String::new();
+        for f in &self.fixtures {
+            let (h, a, class) = match &f.score {
+                Some((h, a)) => (escape_html(h), escape_html(a), "played"),
+                None => (s!(""), s!(""), "upcoming"),
+            };
+            let mid = if class == "played" { "–" } else { "vs" };
+            rows.push_str(&format!(
+                "<tr class=\"{}\"><td class=\"home\">{}</td><td class=\"score\">{}</td>\
+                 <td class=\"sep\">{}</td><td class=\"score\">{}</td><td class=\"away\">{}</td></tr>\n",
+                class, escape_html(&f.home), h, mid, a, escape_html(&f.away)));
+        }
+        if self.fixtures.is_empty() {
+            rows.push_str("<tr><td colspan=\"5\" class=\"sep\">No games.</td></tr>\n");
+        }
+        format!(r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+  body {{ font-family: sans-serif; color: #000; background: #fff; margin: 2em; }}
+  h1 {{ font-size: 1.6em; text-align: center; margin: 0 0 0.8em; }}
+  table {{ border-collapse: collapse; margin: 0 auto; font-size: 1.1em; }}
+  td {{ padding: 0.35em 0.6em; border-bottom: 1px solid #999; }}
+  td.home {{ text-align: right; font-weight: bold; }}
+  td.away {{ text-align: left; font-weight: bold; }}
+  td.score {{ text-align: center; min-width: 1.6em; }}
+  td.sep {{ text-align: center; color: #555; }}
+  tr.upcoming td.home, tr.upcoming td.away {{ font-weight: normal; }}
+  @media print {{ body {{ margin: 0; }} @page {{ margin: 1.5cm; }} }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+<table>
+{rows}</table>
+</body>
+</html>
+"#)
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results() -> DataSet {
+        let row = |c: [&str; 7]| c.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        DataSet {
+            headers: Some(row(["S", "W", "Home", "H", "A", "Away", "Match id"])),
+            rows: vec![
+                row(["4", "1", "Old Week", "1", "0", "Team X", "1"]),
+                row(["5", "1", "Reds", "2", "1", "Blues", "10"]),
+                row(["5", "2", "Greens", "", "", "Reds & Co", ""]),
+                row(["5", "2", "Blues", "3", "3", "Whites", "12"]),
+            ],
+        }
+    }
+
+    #[test]
+    fn picks_week_of_latest_season_in_site_order() {
+        let ds = results();
+        assert_eq!(weeks(&ds, Some(5)), vec![1, 2]);
+
+        let w = WeekSchedule::from_results(&ds, None, 2);
+        assert_eq!(w.season, Some(5));
+        assert_eq!(w.fixtures.len(), 2);
+        assert_eq!(w.fixtures[0].home, "Greens");
+        assert_eq!(w.fixtures[0].score, None);
+        assert_eq!(w.fixtures[1].score, Some(("3".into(), "3".into())));
+
+        let w1 = WeekSchedule::from_results(&ds, Some(4), 1);
+        assert_eq!(w1.fixtures[0].home, "Old Week");
+    }
+
+    #[test]
+    fn renders_text_and_escaped_html() {
+        let w = WeekSchedule::from_results(&results(), None, 2);
+        let text = w.to_text();
+        assert!(text.starts_with("Season 5 · Week 2\n"));
+        assert!(text.contains("Greens     vs      Reds & Co\n"));
+        assert!(text.contains(" Blues    3 - 3    Whites\n"));
+
+        let html = w.to_html();
+        assert!(html.contains("<title>Season 5 · Week 2</title>"));
+        assert!(html.contains("Reds &amp; Co"));
+        assert!(html.contains("@media print"));
+    }
+}
@@ -0,0 +1,126 @@
+// src/schedule_metrics.rs
+//
+// Per-team schedule metrics from the Game Results of one season: home/away
+// balance, strength of schedule (the opponents' combined record, from the
+// Results Table tally), and how the games are spread over the weeks
+// (back-to-back weeks, byes). Purely local, like the Results Table.
+
+use std::collections::{BTreeSet, HashMap};
+
+use crate::results_table::{season_games, tally, Record};
+use crate::schedule::{num, Cols};
+use crate::store::DataSet;
+
+#[derive(Default)]
+struct Schedule {
+    home: u32,
+    away: u32,
+    weeks: BTreeSet<u32>,
+    /// Per fixture: opponent, and whether it's been played
+    opponents: Vec<(String, bool)>,
+}
+
+/// Opponents' combined W, T, L over `games`, and their win share (ties
+/// half); None when they haven't played.
+fn strength<'a>(games: impl Iterator<Item = &'a String>, records: &HashMap<&str, &Record>) -> ((u32, u32, u32), Option<f64>) {
+    let (mut w, mut t, mut l) = (0, 0, 0);
+    for opp in games {
+        if let Some(r) = records.get(opp.as_str()) {
+            w += r.w;
+            t += r.t;
+            l += r.l;
+        }
+    }
+    let gp = w + t + l;
+    let pct = (gp > 0).then(|| (f64::from(w) + f64::from(t) / 2.0) / f64::from(gp));
+    ((w, t, l), pct)
+}
+
+/// The metrics of `season` (default: the latest season in `results`), one
+/// row per team with a game that season; hardest schedule first. Opponents
+/// are counted once per fixture, played or not; their record includes the
+/// games against the team itself.
+pub fn from_results(results: &DataSet, season: Option<u32>) -> DataSet {
+    let c = Cols::of(results);
+    let games = season_games(results, season);
+    let records = tally(results, season);
+    let by_team: HashMap<&str, &Record> = records.iter().map(|(t, r)| (t.as_str(), r)).collect();
+
+    let mut schedules: Vec<(String, Schedule)> = records.iter().map(|(t, _)| (t.clone(), Schedule::default())).collect();
+    let at: HashMap<String, usize> = schedules.iter().enumerate().map(|(i, (t, _))| (t.clone(), i)).collect();
+    let (mut first, mut last) = (u32::MAX, 0);
+    for r in &games {
+        let name = |ci: usize| r.get(ci).map(|s| s.trim().to_string()).unwrap_or_default();
+        let (home, away) = (name(c.home), name(c.away));
+        let (Some(&h), Some(&a)) = (at.get(&home), at.get(&away)) else { continue };
+        let played = num(r, c.hs).is_some() && num(r, c.as_).is_some();
+        let week = num(r, c.week);
+        if let Some(w) = week {
+            first = first.min(w);
+            last = last.max(w);
+        }
+        for (ix, opp, is_home) in [(h, away, true), (a, home, false)] {
+            let s = &mut schedules[ix].1;
+            if is_home { s.home += 1; } else { s.away += 1; }
+            s.weeks.extend(week);
+            s.opponents.push((opp, played));
+        }
+    }
+
+    let fmt_pct = |p: Option<f64>| p.map(|p| format!("{:.3}", p)).unwrap_or_default();
+    let mut rows: Vec<(Option<f64>, Vec<String>)> = schedules.into_iter().map(|(team, s)| {
+        let ((w, t, l), sos) = strength(s.opponents.iter().map(|(o, _)| o), &by_team);
+        let (_, rem) = strength(s.opponents.iter().filter(|(_, played)| !played).map(|(o, _)| o), &by_team);
+        let weeks: Vec<u32> = s.weeks.iter().copied().collect();
+        let b2b = weeks.windows(2).filter(|w| w[1] == w[0] + 1).count();
+        let byes = if first <= last { (last - first + 1) as usize - weeks.len() } else { 0 };
+        let row = vec![
+            team, (s.home + s.away).to_string(), s.home.to_string(), s.away.to_string(),
+            (i64::from(s.home) - i64::from(s.away)).to_string(),
+            w.to_string(), t.to_string(), l.to_string(),
+            fmt_pct(sos), fmt_pct(rem), b2b.to_string(), byes.to_string(),
+        ];
+        (sos, row)
+    }).collect();
+    rows.sort_by(|(a, ra), (b, rb)| {
+        b.unwrap_or(-1.0).total_cmp(&a.unwrap_or(-1.0))
+            .then_with(|| ra[0].to_lowercase().cmp(&rb[0].to_lowercase()))
+    });
+
+    let headers = crate::schema::for_page(crate::config::options::PageKind::ScheduleMetrics)
+        .map(|p| p.names().into_iter().map(String::from).collect());
+    DataSet { headers, rows: rows.into_iter().map(|(_, r)| r).collect() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results(rows: &[[&str; 7]]) -> DataSet {
+        DataSet {
+            headers: Some(["S", "W", "Home", "H", "A", "Away", "Match id"].iter().map(|h| s!(*h)).collect()),
+            rows: rows.iter().map(|r| r.iter().map(|c| s!(*c)).collect()).collect(),
+        }
+    }
+
+    #[test]
+    fn balance_strength_and_weeks_per_team() {
+        let ds = results(&[
+            ["1", "1", "Reds", "2", "0", "Blues", "1"],
+            ["1", "1", "Golds", "1", "1", "Greens", "2"],
+            ["1", "2", "Blues", "3", "1", "Golds", "3"],
+            ["1", "4", "Reds", "", "", "Greens", ""],
+        ]);
+        let m = from_results(&ds, None);
+        assert_eq!(crate::schema::validate(crate::config::options::PageKind::ScheduleMetrics, &m), Ok(()));
+        let row = |team: &str| m.rows.iter().find(|r| r[0] == team).unwrap().iter().map(String::as_str).collect::<Vec<_>>();
+
+        // Reds: home to Blues (1-0-1) and Greens (0-1-0, not played yet);
+        // weeks 1 and 4 of 1–4
+        assert_eq!(row("Reds"), vec!["Reds", "2", "2", "0", "2", "1", "1", "1", "0.500", "0.500", "0", "2"]);
+        // Blues: at Reds (1-0-0), home to Golds (0-1-1); weeks 1 and 2 back to back
+        assert_eq!(row("Blues"), vec!["Blues", "2", "1", "1", "0", "1", "1", "1", "0.500", "", "1", "2"]);
+        // Same strength everywhere: by name
+        assert_eq!(m.rows.iter().map(|r| r[0].as_str()).collect::<Vec<_>>(), vec!["Blues", "Golds", "Greens", "Reds"]);
+    }
+}
@@ -0,0 +1,637 @@
+// src/schema.rs
+//
+// What each page's columns mean, for people and tools consuming exports.
+// `cli schema` prints this as Markdown or JSON, so the reference is
+// generated from the same code that produces the columns. Keep the column
+// lists in step with the scrapers (the tests below check the fixed ones).
+//
+// The same registry is what the pages check caches and scrapes against
+// (`validate`), fills in missing headers from (`inject_headers`), and takes
+// column positions from (`col_index`), so a column count or index isn't
+// hard-coded anywhere else.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::config::options::PageKind::{self, *};
+use crate::manifest::json_str;
+
+/// User columns per page, name → formula (`store::user_columns`).
+pub type UserFormulas = HashMap<PageKind, BTreeMap<String, String>>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColumnType {
+    /// Whole number (may be blank where noted)
+    Integer,
+    /// Number with decimals (averages)
+    Decimal,
+    /// Free text
+    Text,
+    /// Numeric site id; stable across name changes
+    Id,
+}
+
+impl ColumnType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ColumnType::Integer => "integer",
+            ColumnType::Decimal => "decimal",
+            ColumnType::Text    => "text",
+            ColumnType::Id      => "id",
+        }
+    }
+
+    pub fn is_numeric(&self) -> bool { !matches!(self, ColumnType::Text) }
+
+    /// Type of a column the schema doesn't declare (site columns), from its
+    /// cells: numeric when at least one is filled and every filled one is a
+    /// number (a leading '#' is ignored, like sorting).
+    pub fn infer<'a>(cells: impl IntoIterator<Item = &'a str>) -> ColumnType {
+        let mut any = false;
+        let mut whole = true;
+        for c in cells {
+            let c = c.trim().trim_start_matches('#');
+            if c.is_empty() { continue; }
+            any = true;
+            if c.parse::<u32>().is_ok() { continue; }
+            if c.parse::<f32>().is_err() { return ColumnType::Text; }
+            whole = false;
+        }
+        match (any, whole) {
+            (false, _) => ColumnType::Text,
+            (true, true) => ColumnType::Integer,
+            (true, false) => ColumnType::Decimal,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ColumnSchema {
+    pub name: &'static str,
+    pub ty: ColumnType,
+    /// Every row has it filled in
+    pub required: bool,
+    pub description: &'static str,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct PageSchema {
+    pub kind: PageKind,
+    pub summary: &'static str,
+    pub columns: &'static [ColumnSchema],
+    /// Columns copied from the site, inserted before column `.0`; not known
+    /// in advance (`.1` describes them)
+    pub site_columns: Option<(usize, &'static str)>,
+    /// Columns computed locally after each scrape (`derive`), always last;
+    /// absent in caches from before they were added
+    pub derived: &'static [ColumnSchema],
+}
+
+const fn col(name: &'static str, ty: ColumnType, description: &'static str) -> ColumnSchema {
+    ColumnSchema { name, ty, required: false, description }
+}
+
+/// A column every row must have filled in.
+const fn req(name: &'static str, ty: ColumnType, description: &'static str) -> ColumnSchema {
+    ColumnSchema { name, ty, required: true, description }
+}
+
+/// Position of `name` in `cols`, for column constants. Fails the build
+/// when the column doesn't exist.
+pub const fn col_index(cols: &[ColumnSchema], name: &str) -> usize {
+    let mut i = 0;
+    while i < cols.len() {
+        let (a, b) = (cols[i].name.as_bytes(), name.as_bytes());
+        if a.len() == b.len() {
+            let mut j = 0;
+            while j < a.len() && a[j] == b[j] { j += 1; }
+            if j == a.len() { return i; }
+        }
+        i += 1;
+    }
+    panic!("no such column in schema");
+}
+
+use ColumnType::{Decimal as DEC, Id as ID, Integer as INT, Text as TEXT};
+
+pub const TEAMS: [ColumnSchema; 3] = [
+    req("Id", ID, "Team id (team.php?i=<id>), 0–31"),
+    req("Team", TEXT, "Team name"),
+    col("Division", TEXT, "Division/conference the league table lists the team under; blank if it has none"),
+];
+
+pub const PLAYERS: [ColumnSchema; 5] = [
+    req("Name", TEXT, "Player name, bracket tags like [CAPTAIN] removed"),
+    col("#", TEXT, "Jersey number with a leading '#' (dropped with --skip-optional)"),
+    col("Race", TEXT, "Player race"),
+    req("Team", TEXT, "Team name"),
+    col("Player id", ID, "Player id (player.php?i=<id>); blank if the site omits the link. Dropped with --skip-optional"),
+];
+
+/// Derived from the cached Injuries (current week: the log's latest).
+pub const PLAYERS_DERIVED: [ColumnSchema; 1] = [
+    col("Out until", INT, "Week the player is back from injury, if that's after the current week (the injury log's latest); blank if available"),
+];
+
+pub const GAME_RESULTS: [ColumnSchema; 7] = [
+    req("S", INT, "Season"),
+    req("W", INT, "Week"),
+    req("Home", TEXT, "Home team name"),
+    col("H", INT, "Home score; blank for games not played yet"),
+    col("A", INT, "Away score; blank for games not played yet"),
+    req("Away", TEXT, "Away team name"),
+    col("Match id", ID, "Match id; blank for games not played yet. Dropped with --skip-optional"),
+];
+
+pub const INJURIES: [ColumnSchema; 13] = [
+    col("S", INT, "Season"),
+    col("W", INT, "Week of the event"),
+    req("Victim Team", TEXT, "Injured player's team (the team named, for other events)"),
+    col("Victim", TEXT, "Injured or returning player; blank for events that name no player"),
+    col("DUR", INT, "Duration in weeks"),
+    col("SR0", INT, "Victim's skill rating before; blank if unchanged or killed"),
+    col("SR1", INT, "Victim's skill rating after; blank if unchanged or killed"),
+    col("Type", TEXT, "Injury type (KILLED for deaths)"),
+    col("Offender Team", TEXT, "Offending player's team"),
+    col("Offender", TEXT, "Offending player"),
+    col("BRU", INT, "Offender's brutality gained"),
+    col("Bounty", TEXT, "BOUNTY COLLECTED when a bounty was paid out, else blank"),
+    req("Event", TEXT, "Injury, Return (recovery notices; wording in Type) or Other (any other week line; text in Type)"),
+];
+
+pub const INJURIES_DERIVED: [ColumnSchema; 1] = [
+    col("Returns", INT, "Week the victim is back (W + DUR); blank for deaths and non-injury events"),
+];
+
+pub const STANDINGS: [ColumnSchema; 8] = [
+    col("Rank", INT, "Position within the division/conference table"),
+    req("Id", ID, "Team id"),
+    req("Team", TEXT, "Team name"),
+    col("W", INT, "Wins"),
+    col("T", INT, "Ties"),
+    col("L", INT, "Losses"),
+    col("PF", INT, "Points for"),
+    col("PA", INT, "Points against"),
+];
+
+pub const TRANSACTIONS: [ColumnSchema; 8] = [
+    col("S", INT, "Season"),
+    col("W", INT, "Week of the move"),
+    col("Team", TEXT, "Team making the move"),
+    req("Move", TEXT, "Signed, Cut or Traded; other move types as shown on the site"),
+    req("Player", TEXT, "Player moved"),
+    col("Player id", ID, "Player id (player.php?i=<id>); blank if the site omits the link"),
+    col("Other Team", TEXT, "Trade partner (or other team involved); blank if none"),
+    col("Details", TEXT, "Fee or notes as shown on the site"),
+];
+
+pub const TEAM_SUMMARY: [ColumnSchema; 2] = [
+    req("Team", TEXT, "Team name"),
+    req("Players", INT, "Players on the roster"),
+];
+
+pub const RESULTS_TABLE: [ColumnSchema; 11] = [
+    req("Rank", INT, "Position by wins, then ties, points difference and points for"),
+    col("Id", ID, "Team id from the team list; blank if the list doesn't have the team"),
+    req("Team", TEXT, "Team name"),
+    req("GP", INT, "Games played"),
+    req("W", INT, "Wins"),
+    req("T", INT, "Ties"),
+    req("L", INT, "Losses"),
+    req("PF", INT, "Points for"),
+    req("PA", INT, "Points against"),
+    req("Diff", DEC, "Points difference (PF − PA); negative when behind"),
+    col("Streak", TEXT, "Latest result and how many in a row, e.g. W3 or L1; blank before the first game"),
+];
+
+pub const SCHEDULE_METRICS: [ColumnSchema; 12] = [
+    req("Team", TEXT, "Team name"),
+    req("Games", INT, "Fixtures this season, played or not"),
+    req("Home", INT, "Home fixtures"),
+    req("Away", INT, "Away fixtures"),
+    req("Balance", DEC, "Home − Away; negative with more away games"),
+    req("Opp W", INT, "Opponents' wins, summed per fixture"),
+    req("Opp T", INT, "Opponents' ties, summed per fixture"),
+    req("Opp L", INT, "Opponents' losses, summed per fixture"),
+    col("SOS", DEC, "Strength of schedule: the opponents' combined win share (ties half), 0–1; blank before they've played"),
+    col("Rem SOS", DEC, "The same over the fixtures not played yet; blank when none are left"),
+    req("B2B", INT, "Back-to-back weeks: pairs of consecutive weeks with a game"),
+    req("Byes", INT, "Weeks of the season (first to last week with any game) without a game"),
+];
+
+pub const LEADERS: [ColumnSchema; 5] = [
+    req("Stat", TEXT, "Players column ranked"),
+    req("Rank", INT, "Position by value, highest first; tied players share it (1, 2, 2, 4)"),
+    req("Player", TEXT, "Player name"),
+    col("Team", TEXT, "The player's team"),
+    req("Value", DEC, "The player's value of the stat, as in Players"),
+];
+
+pub const TEAM_INFO: [ColumnSchema; 5] = [
+    req("Id", ID, "Team id"),
+    req("Team", TEXT, "Team name"),
+    col("Owner", TEXT, "Team owner; blank if the team page doesn't show one"),
+    col("Stadium", TEXT, "Home stadium; blank if the team page doesn't show one"),
+    col("Logo", TEXT, "Absolute URL of the team logo; blank if none"),
+];
+
+pub const INJURY_SUMMARY: [ColumnSchema; 4] = [
+    req("Group", TEXT, "Team, Type or Week, then a Total row"),
+    req("Key", TEXT, "The victim team, injury type or week (S<season> W<week>); All for the total"),
+    req("Injuries", INT, "Injuries in the group (returns and other events don't count)"),
+    req("SR lost", INT, "Skill rating lost by those injuries (SR0 − SR1 where both are known)"),
+];
+
+pub const INJURY_VICTIMS: [ColumnSchema; 14] = [
+    col("S", INT, "Season"),
+    col("W", INT, "Week of the injury"),
+    col("Victim Team", TEXT, "Injured player's team"),
+    req("Victim", TEXT, "Injured player"),
+    col("DUR", INT, "Duration in weeks"),
+    col("SR0", INT, "Victim's skill rating before; blank if unchanged or killed"),
+    col("SR1", INT, "Victim's skill rating after; blank if unchanged or killed"),
+    col("Type", TEXT, "Injury type (KILLED for deaths)"),
+    col("Offender Team", TEXT, "Offending player's team"),
+    col("Offender", TEXT, "Offending player"),
+    col("BRU", INT, "Offender's brutality gained"),
+    col("Bounty", TEXT, "BOUNTY COLLECTED when a bounty was paid out, else blank"),
+    col("Event", TEXT, "Always Injury: other events are left out"),
+    col("Victim #", TEXT, "Victim's number from Players (#27); blank if the victim wasn't found"),
+];
+
+pub const PLAYER: [ColumnSchema; 4] = [
+    req("Player id", ID, "Player id (player.php?i=<id>)"),
+    req("Section", TEXT, "Heading of the table on the player page (e.g. Attributes, Contract, History)"),
+    req("Field", TEXT, "Row label, or column header for history tables"),
+    col("Value", TEXT, "Value as shown on the site"),
+];
+
+static REGISTRY: [PageSchema; 14] = [
+    PageSchema {
+        kind: Teams,
+        summary: "All teams with their ids and divisions.",
+        columns: &TEAMS,
+        site_columns: None,
+        derived: &[],
+    },
+    PageSchema {
+        kind: Players,
+        summary: "Team rosters, one row per player.",
+        columns: &PLAYERS,
+        site_columns: Some((4, "The roster table's remaining columns, as headed on the site (attributes and stats)")),
+        derived: &PLAYERS_DERIVED,
+    },
+    PageSchema {
+        kind: GameResults,
+        summary: "Every fixture of the season, including games not played yet.",
+        columns: &GAME_RESULTS,
+        site_columns: None,
+        derived: &[],
+    },
+    PageSchema {
+        kind: Injuries,
+        summary: "Injury log of the season, one row per injury.",
+        columns: &INJURIES,
+        site_columns: None,
+        derived: &INJURIES_DERIVED,
+    },
+    PageSchema {
+        kind: Standings,
+        summary: "League table, one row per team; rank restarts in each division.",
+        columns: &STANDINGS,
+        site_columns: None,
+        derived: &[],
+    },
+    PageSchema {
+        kind: ResultsTable,
+        summary: "League table of the latest season computed from the cached Game Results (no requests), one row per team with a game that season.",
+        columns: &RESULTS_TABLE,
+        site_columns: None,
+        derived: &[],
+    },
+    PageSchema {
+        kind: ScheduleMetrics,
+        summary: "Home/away balance, strength of schedule and spread over the weeks per team, computed from the cached Game Results of the latest season (no requests).",
+        columns: &SCHEDULE_METRICS,
+        site_columns: None,
+        derived: &[],
+    },
+    PageSchema {
+        kind: Transactions,
+        summary: "Roster moves (signings, cuts, trades), one row per move; kept across scrapes, as the site only lists recent ones.",
+        columns: &TRANSACTIONS,
+        site_columns: None,
+        derived: &[],
+    },
+    PageSchema {
+        kind: TeamSummary,
+        summary: "Players aggregated per team, computed from the cached Players data (no requests).",
+        columns: &TEAM_SUMMARY,
+        site_columns: Some((2, "`<column> avg` and `<column> total` for each numeric Players column (TV, OVR, …); blanks don't count towards averages")),
+        derived: &[],
+    },
+    PageSchema {
+        kind: Leaders,
+        summary: "Top players league-wide per numeric Players column (the Team Summary stats), computed from the cached Players data (no requests); one row per player and stat, in Players column order. Top N is 5 by default (`--top`); ties at the cutoff are all listed.",
+        columns: &LEADERS,
+        site_columns: None,
+        derived: &[],
+    },
+    PageSchema {
+        kind: TeamInfo,
+        summary: "Metadata from each team page, one row per team; scraping some teams (-t/-i) updates just their rows. With --logos the logos are also saved to `.store/logos/<team id>.<ext>`.",
+        columns: &TEAM_INFO,
+        site_columns: None,
+        derived: &[],
+    },
+    PageSchema {
+        kind: InjuryVictims,
+        summary: "Injuries joined to the cached Players data on (victim team, victim name); names are matched ignoring case, punctuation and bracket tags, or as the team's only name with that prefix.",
+        columns: &INJURY_VICTIMS,
+        site_columns: Some((14, "`Victim Race`, `Victim TV`, `Victim OVR` and `Victim id`, for those the Players data has; blank if the victim wasn't found")),
+        derived: &[],
+    },
+    PageSchema {
+        kind: InjurySummary,
+        summary: "Injuries per team (victim team), per injury type and per week with the skill rating lost, then the total, computed from the cached Injuries data (no requests). Teams and types by injuries, most first; weeks oldest first.",
+        columns: &INJURY_SUMMARY,
+        site_columns: None,
+        derived: &[],
+    },
+    PageSchema {
+        kind: Player,
+        summary: "One player's detail page in long form (one row per field).",
+        columns: &PLAYER,
+        site_columns: None,
+        derived: &[],
+    },
+];
+
+/// All documented pages, in CLI page order.
+pub fn registry() -> &'static [PageSchema] { &REGISTRY }
+
+pub fn for_page(kind: PageKind) -> Option<&'static PageSchema> {
+    REGISTRY.iter().find(|s| s.kind == kind)
+}
+
+impl PageSchema {
+    pub fn names(&self) -> Vec<&'static str> {
+        self.columns.iter().map(|c| c.name).collect()
+    }
+
+    /// Same columns in every dataset (no site columns).
+    pub fn is_fixed(&self) -> bool { self.site_columns.is_none() }
+
+    /// Where the derived columns start in `headers` (its length if none).
+    pub fn derived_at(&self, headers: &[String]) -> usize {
+        headers.iter().position(|h| self.derived.iter().any(|c| c.name == h)).unwrap_or(headers.len())
+    }
+
+    /// Declared columns that always come first, in order.
+    fn leading(&self) -> &'static [ColumnSchema] {
+        let at = self.site_columns.map(|(at, _)| at).unwrap_or(self.columns.len());
+        &self.columns[..at]
+    }
+
+    /// Where each declared column is in a dataset with these `headers`
+    /// (`None` when absent, e.g. a trailing column older caches don't have).
+    fn positions(&self, headers: Option<&[String]>) -> Vec<Option<usize>> {
+        let leading = self.leading().len();
+        self.columns.iter().enumerate().map(|(i, c)| match headers {
+            Some(hs) if i >= leading => hs.iter().position(|h| h == c.name),
+            _ => Some(i),
+        }).collect()
+    }
+}
+
+/// Check `ds` against the page's schema: header names, row width, required
+/// cells filled and numbers where the column is numeric. Pages without a
+/// schema pass. The error names the first problem found.
+pub fn validate(kind: PageKind, ds: &crate::store::DataSet) -> Result<(), String> {
+    let Some(p) = for_page(kind) else { return Ok(()) };
+    let leading = p.leading();
+
+    if let Some(hs) = &ds.headers {
+        let (base, derived) = hs.split_at(p.derived_at(hs));
+        let base_ok = if p.is_fixed() {
+            base.iter().map(String::as_str).eq(p.names())
+        } else {
+            base.len() >= leading.len() && base.iter().zip(leading).all(|(h, c)| h == c.name)
+        };
+        let derived_ok = derived.is_empty() || derived.iter().map(String::as_str).eq(p.derived.iter().map(|c| c.name));
+        if !(base_ok && derived_ok) {
+            return Err(format!("{}: headers don't match the {} columns", title_of(kind), kind));
+        }
+    }
+
+    let width = ds.headers.as_ref().map(|h| h.len()).unwrap_or(p.columns.len());
+    let positions = p.positions(ds.headers.as_deref());
+    for (ri, r) in ds.rows.iter().enumerate() {
+        let width_ok = if p.is_fixed() { r.len() == width } else { r.len() >= leading.len() };
+        if !width_ok {
+            return Err(format!("{}: row {} has {} columns, expected {}", title_of(kind), ri + 1, r.len(), width));
+        }
+        for (c, pos) in p.columns.iter().zip(&positions) {
+            let Some(cell) = pos.and_then(|ci| r.get(ci)) else { continue };
+            if c.required && cell.trim().is_empty() {
+                return Err(format!("{}: row {}: {} is empty", title_of(kind), ri + 1, c.name));
+            }
+        }
+    }
+
+    let typed = crate::typed::TypedColumns::for_page(kind, ds);
+    if let Some(&(ri, ci)) = typed.invalid_cells(ds).first() {
+        let name = ds.headers.as_ref().and_then(|h| h.get(ci)).map(String::as_str)
+            .or_else(|| p.columns.get(ci).map(|c| c.name))
+            .unwrap_or("?");
+        return Err(format!("{}: row {}: {} is not a number ({:?})", title_of(kind), ri + 1, name, ds.rows[ri][ci]));
+    }
+    Ok(())
+}
+
+/// Fill in missing headers from the schema on pages whose columns are fixed.
+pub fn inject_headers(kind: PageKind, ds: &mut crate::store::DataSet) {
+    if ds.headers.is_some() { return; }
+    if let Some(p) = for_page(kind).filter(|p| p.is_fixed()) {
+        let mut names = p.names();
+        // Rows that already carry the derived columns
+        if ds.rows.first().is_some_and(|r| r.len() == names.len() + p.derived.len()) {
+            names.extend(p.derived.iter().map(|c| c.name));
+        }
+        ds.headers = Some(names.into_iter().map(String::from).collect());
+    }
+}
+
+/// Type of each column of a `kind` dataset: declared columns by header name
+/// (or by position when there are no headers), the rest inferred from `rows`.
+pub fn column_types(kind: PageKind, headers: Option<&[String]>, rows: &[Vec<String>]) -> Vec<ColumnType> {
+    let cols = headers.map(|h| h.len())
+        .or_else(|| rows.iter().map(|r| r.len()).max())
+        .unwrap_or(0);
+    let declared = for_page(kind).map(|p| p.columns).unwrap_or(&[]);
+    let derived = for_page(kind).map(|p| p.derived).unwrap_or(&[]);
+    // Without headers, positions only hold up to the first site column
+    let site_at = for_page(kind).and_then(|p| p.site_columns).map(|(at, _)| at);
+    (0..cols).map(|ci| {
+        let by_schema = match headers {
+            Some(hs) => declared.iter().chain(derived).find(|c| c.name == hs[ci]),
+            None => declared.get(ci).filter(|_| site_at.is_none_or(|at| ci < at)),
+        };
+        by_schema.map(|c| c.ty).unwrap_or_else(|| {
+            ColumnType::infer(rows.iter().filter_map(|r| r.get(ci)).map(String::as_str))
+        })
+    }).collect()
+}
+
+/// Markdown reference: one section and table per page, the page's `user`
+/// columns last.
+pub fn to_markdown(pages: &[PageSchema], user: &UserFormulas) -> String {
+    let mut out = s!("# bb_scrape export columns\n");
+    for p in pages {
+        out.push_str(&format!("\n## {} (`--page {}`)\n\n{}\n\n", title_of(p.kind), p.kind, p.summary));
+        out.push_str("| Column | Type | Meaning |\n|---|---|---|\n");
+        for (i, c) in p.columns.iter().enumerate() {
+            if let Some((_, what)) = p.site_columns.filter(|(at, _)| *at == i) {
+                out.push_str(&format!("| *(site columns)* | varies | {} |\n", what));
+            }
+            let ty = if c.required { format!("{}, required", c.ty.as_str()) } else { s!(c.ty.as_str()) };
+            out.push_str(&format!("| `{}` | {} | {} |\n", c.name, ty, c.description.replace('|', "\\|")));
+        }
+        // Site columns after the fixed ones
+        if let Some((_, what)) = p.site_columns.filter(|(at, _)| *at == p.columns.len()) {
+            out.push_str(&format!("| *(site columns)* | varies | {} |\n", what));
+        }
+        for c in p.derived {
+            out.push_str(&format!("| `{}` | {}, derived | {} |\n", c.name, c.ty.as_str(), c.description.replace('|', "\\|")));
+        }
+        for (name, formula) in user.get(&p.kind).into_iter().flatten() {
+            out.push_str(&format!("| `{}` | user formula | `{}` |\n", name, formula.replace('|', "\\|")));
+        }
+    }
+    out
+}
+
+/// JSON reference: `{"pages":[{"page","title","summary","columns":[…],"site_columns","user_columns":[…]}]}`.
+pub fn to_json(pages: &[PageSchema], user: &UserFormulas) -> String {
+    let mut out = s!("{\n  \"pages\": [");
+    for (pi, p) in pages.iter().enumerate() {
+        out.push_str(if pi == 0 { "\n" } else { ",\n" });
+        out.push_str(&format!(
+            "    {{\n      \"page\": {},\n      \"title\": {},\n      \"summary\": {},\n      \"columns\": [",
+            json_str(&p.kind.to_string()), json_str(title_of(p.kind)), json_str(p.summary)));
+        let derived = p.derived.iter().map(|c| (c, true));
+        for (ci, (c, is_derived)) in p.columns.iter().map(|c| (c, false)).chain(derived).enumerate() {
+            out.push_str(if ci == 0 { "\n" } else { ",\n" });
+            out.push_str(&format!(
+                "        {{ \"name\": {}, \"type\": {}, \"required\": {}, \"description\": {}{} }}",
+                json_str(c.name), json_str(c.ty.as_str()), c.required, json_str(c.description),
+                if is_derived { ", \"derived\": true" } else { "" }));
+        }
+        out.push_str("\n      ],\n      \"site_columns\": ");
+        match p.site_columns {
+            Some((at, what)) => out.push_str(&format!(
+                "{{ \"before\": {}, \"description\": {} }}", at, json_str(what))),
+            None => out.push_str("null"),
+        }
+        let formulas: Vec<String> = user.get(&p.kind).into_iter().flatten()
+            .map(|(name, formula)| format!("{{ \"name\": {}, \"formula\": {} }}", json_str(name), json_str(formula)))
+            .collect();
+        out.push_str(&format!(",\n      \"user_columns\": [{}]", formulas.join(", ")));
+        out.push_str("\n    }");
+    }
+    out.push_str("\n  ]\n}\n");
+    out
+}
+
+fn title_of(kind: PageKind) -> &'static str {
+    match kind {
+        Teams       => "Teams",
+        Players     => "Players",
+        SeasonStats => "Season Stats",
+        CareerStats => "Career Stats",
+        GameResults => "Game Results",
+        Injuries    => "Injuries",
+        Standings   => "Standings",
+        Transactions => "Transactions",
+        TeamSummary => "Team Summary",
+        ResultsTable => "Results Table",
+        ScheduleMetrics => "Schedule Metrics",
+        Leaders     => "Leaders",
+        TeamInfo    => "Team Info",
+        InjuryVictims => "Injury Victims",
+        InjurySummary => "Injury Summary",
+        Player      => "Player detail",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gui::router;
+
+    fn names(p: &PageSchema) -> Vec<&'static str> {
+        p.columns.iter().map(|c| c.name).collect()
+    }
+
+    #[test]
+    fn matches_the_scrapers_fixed_headers() {
+        for page in router::all_pages() {
+            if let (Some(hs), Some(s)) = (page.default_headers(), for_page(page.kind())) {
+                assert_eq!(names(s), hs.to_vec(), "{:?}", page.kind());
+            }
+        }
+        assert_eq!(names(for_page(Standings).unwrap()), crate::scrape::STANDINGS_HEADERS.to_vec());
+        assert_eq!(names(for_page(TeamInfo).unwrap()), crate::scrape::TEAM_INFO_HEADERS.to_vec());
+        assert_eq!(names(for_page(Player).unwrap()), crate::scrape::PLAYER_DETAIL_HEADERS.to_vec());
+        assert_eq!(for_page(Players).unwrap().columns.last().unwrap().name, crate::scrape::PLAYER_ID_HEADER);
+    }
+
+    #[test]
+    fn validates_against_the_registry() {
+        use crate::store::DataSet;
+        let row = |cells: &[&str]| cells.iter().map(|c| s!(*c)).collect::<Vec<_>>();
+        let mut ds = DataSet {
+            headers: None,
+            rows: vec![row(&["12", "3", "Reds", "2", "1", "Blues", "991"]), row(&["12", "4", "Reds", "", "", "Greens", ""])],
+        };
+        assert_eq!(validate(GameResults, &ds), Ok(()));
+        inject_headers(GameResults, &mut ds);
+        assert_eq!(ds.headers.as_ref().map(|h| h.len()), Some(GAME_RESULTS.len()));
+        assert_eq!(validate(GameResults, &ds), Ok(()));
+
+        ds.rows[1][5].clear();
+        assert_eq!(validate(GameResults, &ds), Err(s!("Game Results: row 2: Away is empty")));
+        ds.rows[1][5] = s!("Greens");
+        ds.rows[0][3] = s!("two");
+        assert!(validate(GameResults, &ds).unwrap_err().contains("H is not a number"));
+        ds.rows[0].pop();
+        assert!(validate(GameResults, &ds).unwrap_err().contains("row 1 has 6 columns"));
+
+        // Site columns: leading names must match, the rest is free
+        let mut sum = DataSet { headers: Some(vec![s!("Team"), s!("Players"), s!("TV avg")]), rows: vec![row(&["Reds", "11", "98.5"])] };
+        assert_eq!(validate(TeamSummary, &sum), Ok(()));
+        let mut bare = DataSet { headers: None, rows: vec![row(&["Reds", "11"])] };
+        inject_headers(TeamSummary, &mut bare);
+        assert!(bare.headers.is_none());
+        sum.headers = Some(vec![s!("Players"), s!("Team"), s!("TV avg")]);
+        assert!(validate(TeamSummary, &sum).is_err());
+
+        assert_eq!(col_index(&PLAYERS, "Team"), 3);
+    }
+
+    #[test]
+    fn renders_markdown_and_json() {
+        let user: UserFormulas = HashMap::from([(Players, BTreeMap::from([(s!("KPG"), s!("Kills / GP"))]))]);
+        let md = to_markdown(registry(), &user);
+        assert!(md.contains("## Game Results (`--page game-results`)"));
+        assert!(md.contains("| `H` | integer | Home score; blank for games not played yet |"));
+        assert!(md.contains("| *(site columns)* | varies |"));
+        assert!(md.contains("| `KPG` | user formula | `Kills / GP` |"));
+
+        let json = to_json(&registry()[..1], &user);
+        assert!(json.contains("\"page\": \"teams\""));
+        assert!(json.contains("{ \"name\": \"Id\", \"type\": \"id\", \"required\": true"));
+        assert!(json.contains("\"site_columns\": null"));
+        assert!(json.contains("\"user_columns\": []"));
+        assert!(json.trim_end().ends_with('}'));
+    }
+}
@@ -1,5 +1,5 @@
-// src/specs/game_results.rs
-use std::error::Error;
+// src/scrape/game_results.rs
+use crate::error::Error;
 
 use crate::core::{html, net};
 use crate::core::html::{next_tag_block_ci, inner_after_open_tag, strip_tags};
@@ -14,7 +14,7 @@ pub struct GameResultsBundle {
 /// Scrape the full-season schedule/results from /season.php.
 /// - Includes future games (blank scores, no match id).
 /// - Columns: Season, Week, Home team, Home, Away, Away team, Match id
-pub fn fetch() -> Result<GameResultsBundle, Box<dyn Error>> {
+pub fn fetch() -> Result<GameResultsBundle, Error> {
     let html_doc = net::http_get("season.php")?;
     let t = std::time::Instant::now();
     let out = parse_doc(&html_doc);
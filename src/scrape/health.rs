@@ -0,0 +1,195 @@
+// src/scrape/health.rs
+//! Site-format canary (CLI `--health-check`).
+//!
+//! Fetches one page per supported spec, runs the spec's parser on it and
+//! reports whether the anchors the parser relies on are still there (league
+//! table, teamroster table, week tables, …). Nothing is cached or exported.
+
+use std::{collections::HashMap, thread};
+
+use crate::error::Error;
+
+use crate::{
+    config::options::PageKind::{self, *},
+    core::{html::slice_between_ci, net},
+};
+
+use super::*;
+
+/// Pages with a spec parser of their own, in check order. Player is left out
+/// (it needs an id); the derived pages have nothing to fetch.
+pub const CHECKED_PAGES: [PageKind; 7] = [Teams, Standings, Players, TeamInfo, GameResults, Injuries, Transactions];
+
+/// Team page used for the roster and team info checks.
+const SAMPLE_TEAM: u32 = 0;
+
+/// One thing a parser expects to find on its page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub what: &'static str,
+    pub ok: bool,
+    /// Count found, or why it failed
+    pub note: String,
+}
+
+impl Check {
+    fn new(what: &'static str, ok: bool, note: impl Into<String>) -> Self {
+        Self { what, ok, note: note.into() }
+    }
+
+    /// Passes with `n` items found, fails on none.
+    fn count(what: &'static str, n: usize, unit: &str) -> Self {
+        Self::new(what, n > 0, format!("{} {}", n, unit))
+    }
+
+    fn parsed<T>(what: &'static str, r: &Result<T, Error>) -> Self {
+        match r {
+            Ok(_) => Self::new(what, true, ""),
+            Err(e) => Self::new(what, false, e.to_string()),
+        }
+    }
+}
+
+/// Outcome for one page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageHealth {
+    pub page: PageKind,
+    pub path: String,
+    pub checks: Vec<Check>,
+}
+
+impl PageHealth {
+    pub fn is_ok(&self) -> bool {
+        self.checks.iter().all(|c| c.ok)
+    }
+}
+
+/// Site path whose HTML `page` is parsed from.
+fn path_for(page: PageKind) -> String {
+    match page {
+        Teams | Standings => s!("/index.php"),
+        Players | TeamInfo => format!("team.php?i={}", SAMPLE_TEAM),
+        GameResults => s!("season.php"),
+        Injuries => s!("injury.php"),
+        Transactions => s!("/transactions.php"),
+        _ => String::new(),
+    }
+}
+
+/// Fetch and check every page of [`CHECKED_PAGES`]; pages sharing a URL share
+/// one request. Injury lines are matched against the team list from the
+/// league page just fetched.
+pub fn check_all() -> Vec<PageHealth> {
+    let mut docs: HashMap<String, Result<String, String>> = HashMap::new();
+    let mut out = Vec::with_capacity(CHECKED_PAGES.len());
+    let mut teams: Vec<(u32, String)> = Vec::new();
+
+    for page in CHECKED_PAGES {
+        let path = path_for(page);
+        if !docs.contains_key(&path) {
+            if !docs.is_empty() {
+                thread::sleep(net::settings().delay()); // be polite
+            }
+            logd!("Health: GET {}", path);
+            docs.insert(path.clone(), net::http_get(&path).map_err(|e| e.to_string()));
+        }
+        let health = match &docs[&path] {
+            Ok(doc) => {
+                if page == Teams {
+                    teams = team_list(doc);
+                }
+                check_doc(page, doc, &teams)
+            }
+            Err(e) => vec![Check::new("page fetched", false, e.clone())],
+        };
+        out.push(PageHealth { page, path, checks: health });
+    }
+    out
+}
+
+fn team_list(doc: &str) -> Vec<(u32, String)> {
+    teams::parse(doc)
+        .map(|b| b.rows.into_iter()
+            .filter_map(|r| Some((r.first()?.parse().ok()?, r.get(1)?.clone())))
+            .collect())
+        .unwrap_or_default()
+}
+
+/// The checks for `page` on an already fetched document (no network).
+pub fn check_doc(page: PageKind, doc: &str, teams: &[(u32, String)]) -> Vec<Check> {
+    match page {
+        Teams => {
+            let league = teams::scrape_from_league_table(doc).map(|r| r.len()).unwrap_or(0);
+            vec![Check::count("league table present", league, "team link(s)")]
+        }
+        Standings => {
+            let parsed = standings::parse(doc);
+            let rows = parsed.as_ref().map(|b| b.rows.len()).unwrap_or(0);
+            let mut v = vec![Check::parsed("league table with W/T/L columns", &parsed)];
+            if parsed.is_ok() {
+                v.push(Check::count("standings rows", rows, "team(s)"));
+            }
+            v
+        }
+        Players => {
+            let table = slice_between_ci(doc, "<table class=teamroster", "</table>").is_some();
+            let parsed = players::parse(doc, SAMPLE_TEAM);
+            let rows = parsed.as_ref().map(|b| b.rows.len()).unwrap_or(0);
+            vec![
+                Check::new("teamroster table present", table, ""),
+                Check::parsed("team name agrees (title, tab, header)", &parsed),
+                Check::count("player rows", rows, "player(s)"),
+            ]
+        }
+        TeamInfo => {
+            let parsed = team_info::parse(doc, SAMPLE_TEAM);
+            let labels = parsed.as_ref()
+                .map(|row| !(row[2].is_empty() && row[3].is_empty()))
+                .unwrap_or(false);
+            vec![
+                Check::parsed("team page parsed", &parsed),
+                Check::new("owner or stadium label found", labels, ""),
+            ]
+        }
+        GameResults => {
+            let bundle = game_results::parse_doc(doc);
+            let mut weeks: Vec<&str> = bundle.rows.iter().filter_map(|r| r.get(1).map(String::as_str)).collect();
+            weeks.sort_unstable();
+            weeks.dedup();
+            vec![
+                Check::count("week tables found", weeks.len(), "week(s)"),
+                Check::count("game rows", bundle.rows.len(), "game(s)"),
+            ]
+        }
+        Injuries => {
+            // No injuries yet is fine; lines the parser can't read are not
+            let lines = doc.split("<br>").filter(|c| c.contains(" DUR ")).count();
+            let rows = injuries::parse_doc_fast(doc, "", teams).len();
+            vec![Check::new("injury lines parsed", rows == lines, format!("{} of {}", rows, lines))]
+        }
+        Transactions => {
+            let parsed = transactions::parse(doc, "");
+            vec![Check::parsed("transactions table present", &parsed)]
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_anchors_fail_the_page() {
+        let doc = "<html><body><p>Down for maintenance</p></body></html>";
+        for page in CHECKED_PAGES.into_iter().filter(|p| *p != Injuries) {
+            let checks = check_doc(page, doc, &[]);
+            assert!(checks.iter().any(|c| !c.ok), "{} passed on an empty page", page);
+        }
+
+        let league = r#"<table><tr><th>Team</th><th>W</th><th>T</th><th>L</th></tr>
+            <tr><td class="namecheck"><a href="team.php?i=3">Orcland Raiders</a></td><td>1</td><td>0</td><td>2</td></tr></table>"#;
+        assert!(check_doc(Teams, league, &[]).iter().all(|c| c.ok));
+        assert!(check_doc(Standings, league, &[]).iter().all(|c| c.ok));
+    }
+}
@@ -1,13 +1,125 @@
 // src/scrape/injuries.rs
-use std::error::Error;
+use crate::error::Error;
 
 use crate::core::{html, net, sanitize};
-use std::time::Instant;
 use crate::store::DataSet;
+use crate::config::options::{InjuryParser, ScrapeOptions};
 use crate::get_teams;
 use crate::core::VisChars;
 use std::collections::HashMap;
 
+/// Injuries columns; Event says which kind of line the row came from.
+pub const INJURY_HEADERS: [&str; 13] = [
+    "S","W","Victim Team","Victim","DUR","SR0","SR1","Type","Offender Team","Offender","BRU","Bounty","Event"
+];
+
+/// Event column values.
+pub const EVENT_INJURY: &str = "Injury";
+pub const EVENT_RETURN: &str = "Return";
+pub const EVENT_OTHER: &str = "Other";
+
+/// One kind of line on the injuries page: `matches` recognizes it, `parse`
+/// turns it into the columns before Event. Lines are offered to
+/// [`CLASSIFIERS`] in order and the first match wins; a new kind of event is
+/// a new entry there.
+struct LineClassifier {
+    event: &'static str,
+    matches: fn(&str) -> bool,
+    parse: fn(&str, &LineCtx<'_>) -> Option<Vec<String>>,
+}
+
+/// What the line parsers need besides the line.
+struct LineCtx<'a> {
+    season: &'a str,
+    teams: &'a [(u32, String)],
+    tindex: TeamIndex<'a>,
+    parser: InjuryParser,
+}
+
+const CLASSIFIERS: [LineClassifier; 3] = [
+    LineClassifier { event: EVENT_INJURY, matches: is_injury_line, parse: parse_injury_line },
+    LineClassifier { event: EVENT_RETURN, matches: is_return_line, parse: parse_return_line },
+    LineClassifier { event: EVENT_OTHER, matches: is_week_line, parse: parse_other_line },
+];
+
+/// Wording of recovery notices ("returns from injury", "has recovered", …).
+const RETURN_WORDS: [&str; 5] = [" returns", " returned", " recovered", " is back", " healed"];
+
+fn classify(chunk: &str) -> Option<&'static LineClassifier> {
+    CLASSIFIERS.iter().find(|c| (c.matches)(chunk))
+}
+
+fn is_injury_line(chunk: &str) -> bool {
+    chunk.contains(" DUR ")
+}
+
+fn parse_injury_line(line: &str, ctx: &LineCtx<'_>) -> Option<Vec<String>> {
+    match ctx.parser {
+        InjuryParser::Slow => parse_line_slow(line, ctx.season, ctx.teams),
+        InjuryParser::FastBase => parse_line_fast_base(line, ctx.season, ctx.teams),
+        InjuryParser::Fast => parse_line_fast_idx(line, ctx.season, &ctx.tindex),
+    }
+}
+
+fn is_return_line(chunk: &str) -> bool {
+    is_week_line(chunk) && return_word(&strip_tags_keep_text(chunk).to_ascii_lowercase()).is_some()
+}
+
+/// Any other line that starts with a week (`W<n> …`).
+fn is_week_line(chunk: &str) -> bool {
+    split_week(&strip_tags_keep_text(chunk)).is_some()
+}
+
+/// Byte offset of the first recovery word in lowercased text.
+fn return_word(lc: &str) -> Option<usize> {
+    RETURN_WORDS.iter().filter_map(|w| lc.find(w)).min()
+}
+
+/// `W12 rest` → ("12", "rest").
+fn split_week(txt: &str) -> Option<(&str, &str)> {
+    let t = txt.trim_start().strip_prefix('W')?;
+    let end = t.find(|c: char| !c.is_ascii_digit()).unwrap_or(t.len());
+    if end == 0 { return None; }
+    Some((&t[..end], t[end..].trim()))
+}
+
+/// Team and player before a recovery word; the wording goes to Type.
+fn parse_return_line(line: &str, ctx: &LineCtx<'_>) -> Option<Vec<String>> {
+    let txt = strip_tags_keep_text(line);
+    let (week, rest) = split_week(&txt)?;
+    let at = return_word(&rest.to_ascii_lowercase())?;
+    let pre = rest[..at].trim();
+    let (team, name) = match ctx.tindex.split_prefix(pre) {
+        Some((tn, rem)) => (tn.to_string(), rem.trim().to_string()),
+        None => {
+            let parts: Vec<&str> = pre.split_whitespace().collect();
+            if parts.len() < 2 { return None; }
+            let (a, b) = parts.split_at(parts.len() - 2);
+            (a.join(" "), b.join(" "))
+        }
+    };
+    let mut row = vec![String::new(); INJURY_HEADERS.len() - 1];
+    row[0] = ctx.season.to_string();
+    row[1] = week.to_string();
+    row[2] = team;
+    row[3] = name;
+    row[7] = rest[at..].trim().to_string();
+    Some(row)
+}
+
+/// A week line naming a known team first; the rest of the text goes to Type.
+fn parse_other_line(line: &str, ctx: &LineCtx<'_>) -> Option<Vec<String>> {
+    let txt = strip_tags_keep_text(line);
+    let (week, rest) = split_week(&txt)?;
+    let (team, text) = ctx.tindex.split_prefix(rest)?;
+    let mut row = vec![String::new(); INJURY_HEADERS.len() - 1];
+    row[0] = ctx.season.to_string();
+    row[1] = week.to_string();
+    row[2] = team.to_string();
+    row[7] = text.trim().to_string();
+    Some(row)
+}
+
 fn strip_tags_keep_text(s: &str) -> String {
     // Convert HTML entities first (&nbsp; -> ' ') then strip tags and normalize whitespace
     html::strip_tags(sanitize::normalize_entities(s))
@@ -163,7 +275,10 @@ fn parse_line_slow(line: &str, season: &str, teams: &[(u32, String)]) -> Option<
 
 // archived slower LTI/ALT parsers removed
 
-pub fn collect_injuries(mut _progress: Option<&mut dyn crate::progress::Progress>) -> Result<DataSet, Box<dyn Error>> {
+pub fn collect_injuries(
+    scrape: &ScrapeOptions,
+    mut _progress: Option<&mut dyn crate::progress::Progress>,
+) -> Result<DataSet, Error> {
     logd!("Injuries: HTTP GET injury.php");
     let doc = net::http_get("injury.php")?;
     logd!("Injuries: fetched {} bytes", doc.len());
@@ -180,36 +295,34 @@ pub fn collect_injuries(mut _progress: Option<&mut dyn crate::progress::Progress
         if let Ok(Some(s)) = crate::store::load_season() { season = s.to_string(); }
     }
     
-    let teams = get_teams::load().unwrap_or_default();
+    let teams = get_teams::list_or_scrape();
     logd!("Injuries: team list loaded ({} teams)", teams.len());
-    let tindex = TeamIndex::new(&teams);
-    let mut rows: Vec<Vec<String>> = Vec::new();
-    let mut seen = 0usize;
+    let rows = parse_doc_events(&doc, &season, &teams, scrape.injury_parser);
+    logd!("Injuries: parsed {} event rows", rows.len());
+
+    let headers = Some(INJURY_HEADERS.iter().map(|s| s.to_string()).collect());
+
+    Ok(DataSet { headers, rows })
+}
+
+/// Every line some classifier recognizes, with its Event column (no network).
+/// `parser` reads the injury lines.
+pub fn parse_doc_events(doc: &str, season: &str, teams: &[(u32, String)], parser: InjuryParser) -> Vec<Vec<String>> {
+    let ctx = LineCtx { season, teams, tindex: TeamIndex::new(teams), parser };
+    let mut rows = Vec::new();
 
     // Split by <br> to be robust against missing newlines
     for (i, chunk) in doc.split("<br>").enumerate() {
-        if chunk.contains(" DUR ") {
-            let preview: String = chunk.trim().chars().take(120).collect();
-            logd!("Injuries: consider chunk #{}: {}...", i, preview);
-
-            // Use fast parser by default in production path
-            let now = Instant::now();
-            let res = parse_line_fast_idx(chunk, &season, &tindex);
-            let _dt = now.elapsed();
+        let Some(c) = classify(chunk) else { continue };
+        let preview: String = chunk.trim().chars().take(120).collect();
+        logd!("Injuries: consider {} chunk #{}: {}...", c.event, i, preview);
 
-            match res {
-                Some(r) => { rows.push(r); seen += 1; },
-                None => { logd!("Injuries: parse failed on chunk #{}", i); }
-            }
+        match (c.parse)(chunk, &ctx) {
+            Some(mut r) => { r.push(s!(c.event)); rows.push(r); },
+            None => { logd!("Injuries: parse failed on chunk #{}", i); }
         }
     }
-    logd!("Injuries: parsed {} event rows", seen);
-
-    let headers = Some(vec![
-        "S","W","Victim Team","Victim","DUR","SR0","SR1","Type","Offender Team","Offender","BRU","Bounty"
-    ].iter().map(|s| s.to_string()).collect());
-
-    Ok(DataSet { headers, rows })
+    rows
 }
 
 /// Public helpers for benchmarking parsers on an arbitrary document (no network).
@@ -410,6 +523,15 @@ pub fn parse_doc_fast_idx(doc: &str, season: &str, teams: &[(u32, String)]) -> V
 }
 
 pub fn parse_doc_fast(doc: &str, season: &str, teams: &[(u32, String)]) -> Vec<Vec<String>> { parse_doc_fast_idx(doc, season, teams) }
+
+/// Injury lines only, by `parser` (benchmarks, `bench-injuries`).
+pub fn parse_doc_with(parser: InjuryParser, doc: &str, season: &str, teams: &[(u32, String)]) -> Vec<Vec<String>> {
+    match parser {
+        InjuryParser::Slow => parse_doc_current(doc, season, teams),
+        InjuryParser::FastBase => parse_doc_fast_base(doc, season, teams),
+        InjuryParser::Fast => parse_doc_fast_idx(doc, season, teams),
+    }
+}
 // removed slower LTI parser
 
 #[cfg(test)]
@@ -421,7 +543,30 @@ mod tests {
     }
 
     fn load_teams() -> Vec<(u32, String)> {
-        crate::get_teams::load().unwrap_or_else(|_| crate::scrape::list_teams())
+        crate::get_teams::list()
+    }
+
+    #[test]
+    fn classifies_returns_and_other_week_lines() {
+        let teams = vec![(3, s!("Orcland Raiders")), (7, s!("Dwarf Giants"))];
+        let doc = "<b>Injury report</b><br>\
+            W4 Orcland Raiders Grim Tooth DUR 2 Broken Ribs by Dwarf Giants Borin Stone BRU 1 SR Drops from 60 to 58<br>\
+            W5 Orcland Raiders Grim Tooth returns from injury<br>\
+            W5 Dwarf Giants <i>Apothecary</i> hired<br>\
+            W6 Nobody we know did something<br>";
+        let rows = parse_doc_events(doc, "12", &teams, InjuryParser::default());
+        let events: Vec<&str> = rows.iter().map(|r| r[12].as_str()).collect();
+        assert_eq!(events, [EVENT_INJURY, EVENT_RETURN, EVENT_OTHER]);
+        assert!(rows.iter().all(|r| r.len() == INJURY_HEADERS.len()));
+
+        assert_eq!(&rows[0][..5], ["12", "4", "Orcland Raiders", "Grim Tooth", "2"]);
+        assert_eq!((rows[0][5].as_str(), rows[0][6].as_str()), ("60", "58"));
+        assert_eq!(&rows[1][1..4], ["5", "Orcland Raiders", "Grim Tooth"]);
+        assert_eq!(rows[1][7], "returns from injury");
+        for parser in InjuryParser::ALL {
+            assert_eq!(parse_doc_events(doc, "12", &teams, parser), rows, "{}", parser);
+        }
+        assert_eq!((rows[2][2].as_str(), rows[2][3].as_str(), rows[2][7].as_str()), ("Dwarf Giants", "", "Apothecary hired"));
     }
 
     #[test]
@@ -3,12 +3,33 @@ mod scrape;
 mod teams;
 mod players;
 mod game_results;
+mod player;
+mod standings;
+mod transactions;
+mod team_info;
 pub mod injuries;
+pub mod health;
+pub mod pagination;
 // pub mod career_stats; 
 // pub mod season_stats; 
 // pub mod injuries;
-pub use scrape::list_teams;
 pub use scrape::collect_teams;
 pub use scrape::collect_players;
 pub use scrape::collect_game_results;
-pub use injuries::collect_injuries;
+pub use scrape::collect_player;
+pub use scrape::collect_standings;
+pub use scrape::collect_transactions;
+pub use scrape::collect_team_summary;
+pub use scrape::collect_leaders;
+pub use scrape::collect_results_table;
+pub use scrape::collect_schedule_metrics;
+pub use scrape::collect_team_info;
+pub use scrape::collect_injury_victims;
+pub use scrape::collect_injury_summary;
+pub use scrape::{plan, ScrapePlan};
+pub use injuries::{collect_injuries, INJURY_HEADERS};
+pub use players::{duplicate_roster_key, roster_key, PLAYER_ID_HEADER};
+pub use player::PLAYER_DETAIL_HEADERS;
+pub use standings::STANDINGS_HEADERS;
+pub use transactions::TRANSACTIONS_HEADERS;
+pub use team_info::TEAM_INFO_HEADERS;
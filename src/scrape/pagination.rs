@@ -0,0 +1,135 @@
+// src/scrape/pagination.rs
+//! Pages the site splits over several requests (`?page=2`, "Next »" links).
+//!
+//! A spec declares how to find the next page ([`NextPage`]); [`collect`]
+//! fetches from the first page, parses each with the spec's own parser and
+//! follows the rule until it runs out, concatenating the rows.
+
+use std::thread;
+
+use crate::error::Error;
+
+use crate::{
+    core::{html, net, select::attr_value},
+    progress::Progress,
+};
+
+/// Hard stop, should a next-page rule never run out.
+pub const MAX_PAGES: usize = 50;
+
+/// How a spec finds the page after the current one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NextPage {
+    /// Not paginated
+    None,
+    /// The link whose text starts with this label (case-insensitive), e.g. "next"
+    LinkText(&'static str),
+    /// Bump this query parameter (`page=1`, `page=2`, …) until a page has no rows
+    QueryParam(&'static str),
+}
+
+impl NextPage {
+    /// Site path of the page after `path`, or `None` on the last page.
+    pub fn next_path(&self, path: &str, doc: &str) -> Option<String> {
+        match *self {
+            NextPage::None => None,
+            NextPage::LinkText(label) => link_with_text(doc, label),
+            NextPage::QueryParam(param) => Some(bump_param(path, param)),
+        }
+    }
+}
+
+/// Fetch `first` and every following page, parsing each with `parse`.
+/// Stops on the last page, an empty page, a page seen before or
+/// [`MAX_PAGES`]. Only an error on the first page fails the whole run;
+/// later ones end it with what was collected so far.
+pub fn collect<F>(
+    what: &str,
+    first: &str,
+    next: NextPage,
+    mut parse: F,
+    mut progress: Option<&mut dyn Progress>,
+) -> Result<Vec<Vec<String>>, Error>
+where
+    F: FnMut(&str) -> Result<Vec<Vec<String>>, Error>,
+{
+    let mut rows = Vec::new();
+    let mut seen = vec![s!(first)];
+    let mut path = s!(first);
+
+    for n in 1..=MAX_PAGES {
+        let page = net::http_get(&path).and_then(|doc| Ok((parse(&doc)?, doc)));
+        let (page_rows, doc) = match page {
+            Ok(p) => p,
+            Err(e) if n == 1 => return Err(e),
+            Err(e) => {
+                logd!("{}: page {} ({}): {}; stopping", what, n, path, e);
+                break;
+            }
+        };
+        let got = page_rows.len();
+        rows.extend(page_rows);
+        logd!("{}: page {} ({}): {} rows", what, n, path, got);
+        if let Some(p) = progress.as_deref_mut().filter(|_| next != NextPage::None) {
+            p.log(&format!("{}: page {}, {} rows so far", what, n, rows.len()));
+        }
+
+        if got == 0 { break; }
+        let Some(p) = next.next_path(&path, &doc) else { break };
+        if seen.contains(&p) { break; }
+        seen.push(p.clone());
+        path = p;
+        thread::sleep(net::settings().delay()); // be polite
+    }
+    Ok(rows)
+}
+
+/// Href of the first `<a>` whose text starts with `label`, as a site path.
+fn link_with_text(doc: &str, label: &str) -> Option<String> {
+    let label = label.to_ascii_lowercase();
+    let mut at = 0;
+    while let Some((s, e)) = html::next_tag_block_ci(doc, "<a", "</a>", at) {
+        at = e;
+        let block = &doc[s..e];
+        let text = html::strip_tags(html::inner_after_open_tag(block)).to_ascii_lowercase();
+        if !text.starts_with(&label) { continue; }
+        let open = &block[..block.find('>').unwrap_or(block.len())];
+        let href = attr_value(open, "href")?.replace("&amp;", "&");
+        let base = net::full_url("");
+        return Some(href.strip_prefix(&base).map(str::to_string).unwrap_or(href));
+    }
+    None
+}
+
+/// `path` with `param` one higher (missing counts as page 1).
+fn bump_param(path: &str, param: &str) -> String {
+    let (base, query) = path.split_once('?').unwrap_or((path, ""));
+    let mut found = false;
+    let mut parts: Vec<String> = query.split('&').filter(|p| !p.is_empty()).map(|p| match p.split_once('=') {
+        Some((k, v)) if k == param => {
+            found = true;
+            format!("{}={}", k, v.parse::<u32>().unwrap_or(1) + 1)
+        }
+        _ => p.to_string(),
+    }).collect();
+    if !found {
+        parts.push(format!("{}=2", param));
+    }
+    format!("{}?{}", base, parts.join("&"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_page_rules() {
+        assert_eq!(bump_param("stats.php", "page"), "stats.php?page=2");
+        assert_eq!(bump_param("stats.php?s=4&page=2", "page"), "stats.php?s=4&page=3");
+
+        let doc = r#"<a href="stats.php?page=1">1</a> <a href='stats.php?s=4&amp;page=3'>Next &raquo;</a>"#;
+        assert_eq!(NextPage::LinkText("next").next_path("stats.php?page=2", doc).as_deref(), Some("stats.php?s=4&page=3"));
+        assert_eq!(NextPage::LinkText("next").next_path("x", "<a href=a.php>Prev</a>"), None);
+        assert_eq!(NextPage::None.next_path("x", doc), None);
+    }
+}
@@ -0,0 +1,135 @@
+// src/scrape/player.rs
+//
+// Single player detail page (player.php?i=<id>): attribute sheet,
+// contract/salary and history. The page has no stable classes to select on,
+// so every innermost table is read and flattened into long form:
+// (Player id, Section, Field, Value).
+//
+// - Label/value tables (two cells per row) → one row per label.
+// - Tables with a header row → one row per cell, Field = "<row> <column>".
+
+use crate::error::Error;
+
+use crate::core::{net, html::{slice_between_ci, strip_tags}, select::{leaf_tables, TableText}};
+use crate::core::sanitize::normalize_entities;
+
+pub const PLAYER_DETAIL_HEADERS: [&str; 4] = ["Player id", "Section", "Field", "Value"];
+
+pub struct PlayerBundle {
+    pub headers: Option<Vec<String>>,
+    pub rows: Vec<Vec<String>>,
+}
+
+pub fn fetch(player_id: u32) -> Result<PlayerBundle, Error> {
+    let doc = net::http_get(&format!("player.php?i={}", player_id))?;
+    parse(&doc, player_id)
+}
+
+pub fn parse(doc: &str, player_id: u32) -> Result<PlayerBundle, Error> {
+    let doc = normalize_entities(doc);
+    let id = player_id.to_string();
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut push = |section: &str, field: String, value: String| {
+        rows.push(vec![id.clone(), s!(section), field, value]);
+    };
+
+    if let Some(name) = slice_between_ci(&doc, "<title>", "</title>")
+        .map(|s| strip_tags(s).trim().to_string())
+        .filter(|s| !s.is_empty())
+    {
+        push("Player", s!("Name"), name);
+    }
+
+    for (n, t) in leaf_tables(&doc).into_iter().enumerate() {
+        let (section, body) = section_of(&t, n);
+        if body.is_empty() { continue; }
+
+        if t.header_rows == 0 && body.iter().all(|r| r.len() == 2) {
+            for r in body {
+                let label = r[0].trim_end_matches(':').trim().to_string();
+                if label.is_empty() && r[1].is_empty() { continue; }
+                push(&section, label, r[1].clone());
+            }
+            continue;
+        }
+
+        let header = if t.header_rows > 0 { t.rows.get(t.header_rows - 1) } else { None };
+        for (ri, r) in body.iter().enumerate() {
+            let row_label = match r.first() {
+                Some(c) if !c.is_empty() && header.is_some() => c.clone(),
+                _ => (ri + 1).to_string(),
+            };
+            let skip = usize::from(header.is_some() && r.len() > 1);
+            for (ci, cell) in r.iter().enumerate().skip(skip) {
+                let col = header.and_then(|h| h.get(ci)).filter(|c| !c.is_empty())
+                    .cloned()
+                    .unwrap_or_else(|| (ci + 1).to_string());
+                push(&section, format!("{} {}", row_label, col), cell.clone());
+            }
+        }
+    }
+
+    if rows.is_empty() {
+        return Err(Error::parse(format!("Player {}", player_id), "no player data found"));
+    }
+
+    let headers = Some(PLAYER_DETAIL_HEADERS.iter().map(|h| s!(*h)).collect());
+    Ok(PlayerBundle { headers, rows })
+}
+
+/// Section name and the rows below any header rows. A lone single-cell first
+/// row in a wider table doubles as the section title.
+fn section_of(t: &TableText, n: usize) -> (String, &[Vec<String>]) {
+    let wide = t.rows.iter().any(|r| r.len() > 1);
+    let title_row = match t.rows.first() {
+        Some(r) if wide && t.header_rows <= 1 && r.len() == 1 && !r[0].is_empty() => Some(r[0].clone()),
+        _ => None,
+    };
+    let name = t.heading.clone()
+        .or(title_row.clone())
+        .unwrap_or_else(|| format!("Table {}", n + 1));
+    let mut skip = t.header_rows;
+    if title_row.is_some() && t.header_rows == 0 { skip = 1; }
+    (name, &t.rows[skip.min(t.rows.len())..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = r#"<html><head><title>Grim Tusk</title></head><body>
+        <table><tr><td>
+          <table><tr><td colspan=2>Attributes</td></tr>
+            <tr><td>ST:</td><td>4</td></tr><tr><td>AG:</td><td>2</td></tr></table>
+          <h3>Contract</h3>
+          <table><tr><td>Salary</td><td>120&nbsp;000</td></tr></table>
+          <h3>History</h3>
+          <table><tr><th>Season<th>Team<th>TD</tr>
+            <tr><td>7<td>Orcland<td>3</tr><tr><td>8<td>Orcland<td>5</tr></table>
+        </td></tr></table></body></html>"#;
+
+    fn fields(b: &PlayerBundle, section: &str) -> Vec<(String, String)> {
+        b.rows.iter()
+            .filter(|r| r[1] == section)
+            .map(|r| (r[2].clone(), r[3].clone()))
+            .collect()
+    }
+
+    #[test]
+    fn flattens_sections() {
+        let b = parse(DOC, 42).unwrap();
+        assert!(b.rows.iter().all(|r| r[0] == "42" && r.len() == 4));
+        assert_eq!(fields(&b, "Player"), vec![(s!("Name"), s!("Grim Tusk"))]);
+        assert_eq!(fields(&b, "Attributes"), vec![(s!("ST"), s!("4")), (s!("AG"), s!("2"))]);
+        assert_eq!(fields(&b, "Contract")[0].0, "Salary");
+        assert_eq!(fields(&b, "History"), vec![
+            (s!("7 Team"), s!("Orcland")), (s!("7 TD"), s!("3")),
+            (s!("8 Team"), s!("Orcland")), (s!("8 TD"), s!("5")),
+        ]);
+    }
+
+    #[test]
+    fn empty_page_is_an_error() {
+        assert!(parse("<html><body>Nope</body></html>", 1).is_err());
+    }
+}
@@ -1,11 +1,36 @@
-// src/specs/players.rs
+// src/scrape/players.rs
 
-use std::error::Error;
+use std::collections::HashMap;
+
+use crate::error::Error;
+use crate::schema::{col_index, PLAYERS};
 
 use crate::core::{net, html};
 use crate::core::html::{slice_between_ci, next_tag_block_ci, inner_after_open_tag, strip_tags};
 use crate::core::sanitize::{normalize_entities, normalize_ws, letters_only_trim};
 
+/// Header of the trailing player id column (from the `player.php?i=<id>` link).
+pub const PLAYER_ID_HEADER: &str = "Player id";
+
+/// A player's key within the Players data: (team, jersey number without
+/// the '#', name). A roster can hold two players of the same name; the
+/// number tells them apart.
+pub fn roster_key(row: &[String]) -> (&str, &str, &str) {
+    let cell = |c: usize| row.get(c).map(|s| s.trim()).unwrap_or("");
+    (cell(TEAM_COL), cell(NUMBER_COL).trim_start_matches('#'), cell(NAME_COL))
+}
+
+/// The first row repeating an earlier row's `roster_key`, as (row, earlier
+/// row), 0-based.
+pub fn duplicate_roster_key(rows: &[Vec<String>]) -> Option<(usize, usize)> {
+    let mut seen: HashMap<(&str, &str, &str), usize> = HashMap::new();
+    rows.iter().enumerate().find_map(|(i, r)| seen.insert(roster_key(r), i).map(|first| (i, first)))
+}
+
+const NAME_COL: usize = col_index(&PLAYERS, "Name");
+const NUMBER_COL: usize = col_index(&PLAYERS, "#");
+const TEAM_COL: usize = col_index(&PLAYERS, "Team");
+
 pub struct RosterBundle {
     pub headers: Option<Vec<String>>,
     pub rows: Vec<Vec<String>>,
@@ -13,20 +38,25 @@ pub struct RosterBundle {
 
 pub fn fetch_and_extract(
     team_id: u32,
-) -> Result<RosterBundle, Box<dyn Error>> {
+) -> Result<RosterBundle, Error> {
     let path = format!("team.php?i={}", team_id);
     let html_doc = net::http_get(&path)?; // see core/net.rs
+    parse(&html_doc, team_id)
+}
 
+/// Roster of `team_id` from its team page (no network).
+pub fn parse(html_doc: &str, team_id: u32) -> Result<RosterBundle, Error> {
     // Extract and validate team name from three locations
-    let team_name = extract_and_validate_team_name(&html_doc, team_id)?;
+    let team_name = extract_and_validate_team_name(html_doc, team_id)?;
 
-    let table = slice_between_ci(&html_doc, "<table class=teamroster", "</table>")
-        .ok_or("teamroster table not found")?;
+    let table = slice_between_ci(html_doc, "<table class=teamroster", "</table>")
+        .ok_or_else(|| Error::parse("Roster", "teamroster table not found"))?;
 
     // Headers (<th> not necessarily wrapped in <tr>)
     let site_headers = read_site_headers_row(table);
 
-    // Always construct headers: Name, Number, Race, Team, then the site's tail
+    // Always construct headers: Name, Number, Race, Team, then the site's tail,
+    // then our Player id
     let headers = {
         let mut hdr = vec![
             s!("Name"), 
@@ -44,6 +74,7 @@ pub fn fetch_and_extract(
             };
             hdr.extend(tail);
         }
+        hdr.push(s!(PLAYER_ID_HEADER));
         Some(hdr)
     };
 
@@ -60,6 +91,9 @@ pub fn fetch_and_extract(
         let is_player = lc.contains(r#"class="playerrow""#) || lc.contains(r#"class="playerrow1""#);
         if !is_player { continue; }
 
+        // Stable id from the name link; blank if the site ever drops it
+        let player_id = extract_player_id(tr).unwrap_or_default();
+
         // <td> cells
         let mut cells = Vec::new();
         let mut td_pos = 0usize;
@@ -78,13 +112,14 @@ pub fn fetch_and_extract(
         name = normalize_ws(&name);
         race = normalize_ws(&race);
 
-        // Row: Name, #Number, Race, Team, rest...
-        let mut row = Vec::with_capacity(4 + cells.len());
+        // Row: Name, #Number, Race, Team, rest..., Player id
+        let mut row = Vec::with_capacity(5 + cells.len());
         row.push(name);
         row.push(num);
         row.push(race);
         row.push(team_name.clone());
         row.extend(cells);
+        row.push(player_id);
         rows_out.push(row);
     }
 
@@ -96,7 +131,7 @@ pub fn fetch_and_extract(
 /// Extract and validate team name from three locations in the HTML document.
 /// All three must be present and agree, otherwise returns an error to abort the scrape.
 /// This prevents data corruption when site format changes.
-fn extract_and_validate_team_name(doc: &str, team_id: u32) -> Result<String, Box<dyn Error>> {
+pub(super) fn extract_and_validate_team_name(doc: &str, team_id: u32) -> Result<String, Error> {
     let from_title = extract_from_title(doc);
     let from_active_tab = extract_from_active_tab(doc);
     let from_menu_header = extract_from_menu_header(doc);
@@ -112,7 +147,7 @@ fn extract_and_validate_team_name(doc: &str, team_id: u32) -> Result<String, Box
                 Site format may have changed. Aborting scrape to prevent data corruption.",
                 team_id, title, tab, header
             );
-            Err(msg.into())
+            Err(Error::parse("Roster", msg))
         }
     }
 }
@@ -243,6 +278,14 @@ mod tests {
         assert_eq!(split_first_cell("Name"), ("Name".into(), "".into(), "".into()));
     }
 
+    #[test]
+    fn extract_player_id_from_name_link() {
+        let tr = r#"<tr class="playerrow"><td><a href="player.php?i=4711">Name #27 Race</a></td><td>3</td></tr>"#;
+        assert_eq!(extract_player_id(tr).as_deref(), Some("4711"));
+        assert_eq!(extract_player_id(r#"<tr><td>Name #27</td></tr>"#), None);
+        assert_eq!(extract_player_id(r#"<a HREF='Player.php?i=9&x=1'>"#).as_deref(), Some("9"));
+    }
+
     #[test]
     fn remove_bracket_tags_works() {
         assert_eq!(remove_bracket_tags("[CAPTAIN] Name [out]"), "Name");
@@ -384,6 +427,14 @@ fn split_first_cell(fused: &str) -> (String, String, String) {
     }
 }
 
+/// Numeric id from the first `player.php?i=<id>` link in a roster row.
+fn extract_player_id(tr: &str) -> Option<String> {
+    let lc = html::to_lower(tr);
+    let at = lc.find("player.php?i=")? + "player.php?i=".len();
+    let id: String = tr[at..].chars().take_while(|c| c.is_ascii_digit()).collect();
+    if id.is_empty() { None } else { Some(id) }
+}
+
 /// Remove any `[ ... ]` segments (e.g. `[CAPTAIN]`, `[unavailable ...]`).
 fn remove_bracket_tags(s: &str) -> String {
     let mut out = String::with_capacity(s.len());
@@ -1,15 +1,17 @@
-// src/scrape.rs
+// src/scrape/scrape.rs
 use std::{
-    error::Error, thread, time::Duration,
-    sync::{ mpsc, Arc, atomic::{ AtomicUsize, Ordering }}
+    thread, time::Duration,
+    sync::{ mpsc, Arc, atomic::{ AtomicBool, AtomicUsize, Ordering }}
 };
 
 use crate::{
-    config::options::{PageKind::*, ScrapeOptions, TeamSelector},
-    config::consts::{ WORKERS, REQUEST_PAUSE_MS, JITTER_MS },
+    config::options::{PageKind::{self, *}, ScrapeOptions, TeamSelector},
+    config::consts::{ WORKERS, JITTER_MS },
 
-    progress::Progress, 
-    store::{ self, DataSet },
+    core::net,
+    error::Error,
+    progress::{Progress, Summary},
+    store::DataSet,
     get_teams, 
 };
 
@@ -23,18 +25,8 @@ fn resolve_ids(sel: &TeamSelector) -> Vec<u32> {
     }
 }
 
-pub fn list_teams() -> Vec<(u32, String)> {
-    match get_teams::load() {
-        Ok(v) => v,
-        Err(e) => {
-            eprintln!("Warning: could not load team list: {}", e);
-            (0u32..32).map(|id| (id, format!("Team {}", id))).collect()
-        }
-    }
-}
-
 pub fn collect_teams(mut progress: Option<&mut dyn Progress>)
-    -> Result<DataSet, Box<dyn Error>>
+    -> Result<DataSet, Error>
 {
     if let Some(p) = progress.as_deref_mut() {
         p.log("Refreshing teams…");
@@ -47,21 +39,55 @@ pub fn collect_teams(mut progress: Option<&mut dyn Progress>)
 /// Always returns canonical base data: headers present; numbers with '#'.
 pub fn collect_players(
     scrape: &ScrapeOptions,
-    mut progress: Option<&mut dyn Progress>,
-) -> Result<DataSet, Box<dyn Error>> {
+    progress: Option<&mut dyn Progress>,
+) -> Result<DataSet, Error> {
 
     if let Ok(bundle) = teams::fetch() {
         // cache, but ignore any IO error (best-effort)
-        let _ = store::save_dataset(
-            &Teams, 
-            &DataSet { headers: bundle.headers, rows: bundle.rows }
-        );
+        let _ = get_teams::save(&DataSet { headers: bundle.headers, rows: bundle.rows });
     }
 
     let ids = resolve_ids(&scrape.teams);
+    let per_team = fetch_per_team("Players", &ids, players::fetch_and_extract, progress)?;
 
+    let headers = per_team.iter().find_map(|(_, b)| b.headers.clone());
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for (_, mut bundle) in per_team {
+        rows.append(&mut bundle.rows);
+    }
+
+    Ok(DataSet { headers, rows })
+}
+
+/// Owner, stadium and logo of the selected teams, one row each; with
+/// `download_logos`, the logos are saved to `.store/logos/` as well.
+pub fn collect_team_info(
+    scrape: &ScrapeOptions,
+    progress: Option<&mut dyn Progress>,
+) -> Result<DataSet, Error> {
+    let ids = resolve_ids(&scrape.teams);
+    let logos = scrape.download_logos;
+    let per_team = fetch_per_team("Team info", &ids, move |id| team_info::fetch(id, logos), progress)?;
+    let headers = Some(team_info::TEAM_INFO_HEADERS.iter().map(|h| s!(*h)).collect());
+    Ok(DataSet { headers, rows: per_team.into_iter().map(|(_, row)| row).collect() })
+}
+
+/// Fetch `ids` one team page at a time on `WORKERS` threads, pausing
+/// politely after each. Failed teams are logged and reported to `progress`;
+/// the rest come back sorted by team id. When every team failed, that's an
+/// error: the first team's, as a `NetError` when it was one.
+fn fetch_per_team<T, F>(
+    what: &str,
+    ids: &[u32],
+    fetch: F,
+    mut progress: Option<&mut dyn Progress>,
+) -> Result<Vec<(u32, T)>, Error>
+where
+    T: Send + 'static,
+    F: Fn(u32) -> Result<T, Error> + Send + Sync + 'static,
+{
     // Load team names for progress reporting
-    let team_names: std::collections::HashMap<u32, String> = list_teams()
+    let team_names: std::collections::HashMap<u32, String> = get_teams::list()
         .into_iter()
         .collect();
 
@@ -70,12 +96,13 @@ pub fn collect_players(
     }
 
     // Concurrency
-    type FetchOk = (u32, players::RosterBundle);
-    type FetchErr = (u32, String);
-    
-    let ids_arc = Arc::new(ids.clone());
+    type FetchErr = (u32, Error);
+
+    let ids_arc = Arc::new(ids.to_vec());
+    let fetch = Arc::new(fetch);
     let counter = Arc::new(AtomicUsize::new(0));
-    let (res_tx, res_rx) = mpsc::channel::<Result<FetchOk, FetchErr>>();
+    let stop = Arc::new(AtomicBool::new(false));
+    let (res_tx, res_rx) = mpsc::channel::<Result<(u32, T), FetchErr>>();
 
     let workers = WORKERS.min(ids.len()).max(1);
 
@@ -84,23 +111,25 @@ pub fn collect_players(
     for _ in 0..workers {
         let ids = Arc::clone(&ids_arc);
         let idx = Arc::clone(&counter);
+        let fetch = Arc::clone(&fetch);
+        let stop = Arc::clone(&stop);
         let tx = res_tx.clone();
 
         thread::spawn(
             move || {
                 loop {
                     let i = idx.fetch_add(1, Ordering::Relaxed);
-                    if i >= ids.len() {
+                    if i >= ids.len() || stop.load(Ordering::Relaxed) {
                         break;
                     }
                     let team_id = ids[i];
-                    let result = match players::fetch_and_extract(team_id) {
-                        Ok(bundle) => Ok((team_id, bundle)),
-                        Err(e) => Err((team_id, e.to_string())),
+                    let result = match fetch(team_id) {
+                        Ok(item) => Ok((team_id, item)),
+                        Err(e) => Err((team_id, e)),
                     };
                     let _ = tx.send(result);
                     let jitter = (team_id as u64) % JITTER_MS;
-                    thread::sleep(Duration::from_millis(REQUEST_PAUSE_MS + jitter)); // be polite
+                    thread::sleep(net::settings().delay() + Duration::from_millis(jitter)); // be polite
                 }
             }
         );
@@ -108,52 +137,239 @@ pub fn collect_players(
     drop(res_tx); // main thread is sole receiver now
 
     // Aggregate results
-    let mut headers: Option<Vec<String>> = None;
-    let mut per_team: Vec<(u32, Vec<Vec<String>>)> = Vec::new();
+    let mut per_team: Vec<(u32, T)> = Vec::new();
+    let mut summary = Summary::default();
+    let name_of = |id: u32| team_names.get(&id).cloned().unwrap_or_else(|| s!("Unknown Team"));
+    let mut first_err: Option<Error> = None;
 
     for _ in 0..ids_arc.len() {
+        if progress.as_deref().is_some_and(|p| p.cancelled()) {
+            stop.store(true, Ordering::Relaxed);
+            logf!("{}: Cancelled", what);
+            break;
+        }
         match res_rx.recv() {
-            Ok(Ok((id, bundle))) => {
-                if headers.is_none() {
-                    headers = bundle.headers.clone();
-                }
-                per_team.push((id, bundle.rows));
+            Ok(Ok((id, item))) => {
+                per_team.push((id, item));
+                summary.succeeded += 1;
                 if let Some(p) = progress.as_deref_mut() {
-                    let team_name = team_names.get(&id)
-                        .map(|s| s.as_str())
-                        .unwrap_or("Unknown Team");
-                    p.item_done(id, team_name);
+                    p.item_done(id, &name_of(id));
                 }
             }
-            Ok(Err((id, msg))) => {
+            Ok(Err((id, e))) => {
+                let msg = e.to_string();
+                if first_err.is_none() { first_err = Some(Error::Team { id, source: Box::new(e) }); }
+                let team_name = name_of(id);
+                loge!("Team {id} ({team_name}): {msg}");
                 if let Some(p) = progress.as_deref_mut() {
-                    let team_name = team_names.get(&id)
-                        .map(|s| s.as_str())
-                        .unwrap_or("Unknown Team");
-                    p.item_failed(id, team_name);
-
-                    loge!("Team {id}: {msg}");
+                    p.item_failed(id, &team_name, &msg);
                 }
+                summary.failed.push((id, team_name, msg));
             }
             Err(_) => break, // workers ended early; bail gracefully
         }
     }
 
+    logf!("{}: {}", what, summary.line());
     if let Some(p) = progress.as_deref_mut() {
-        p.finish();
+        p.finish(&summary);
+    }
+
+    if per_team.is_empty() {
+        // A network failure says more than "every team failed"
+        if let Some(e) = first_err {
+            if e.net().is_some() { return Err(e); }
+            return Err(format!("{}: every team failed ({})", what, e).into());
+        }
     }
 
     // Sort
     per_team.sort_by_key(|(id, _)| *id);
-    let mut rows: Vec<Vec<String>> = Vec::new();
-    for (_, mut team_rows) in per_team {
-        rows.append(&mut team_rows);
+    Ok(per_team)
+}
+
+pub fn collect_game_results(_progress: Option<&mut dyn Progress>,) -> Result<DataSet, Error> {
+    let bundle = scrape::game_results::fetch()?;
+    Ok(DataSet { headers: bundle.headers, rows: bundle.rows })
+}
+
+/// Detail sheet of one player (attributes, contract, history) in long form.
+pub fn collect_player(player_id: u32, progress: Option<&mut dyn Progress>) -> Result<DataSet, Error> {
+    if let Some(p) = progress {
+        p.log(&format!("Fetching player {}…", player_id));
     }
+    let bundle = player::fetch(player_id)?;
+    Ok(DataSet { headers: bundle.headers, rows: bundle.rows })
+}
 
-    Ok(DataSet { headers, rows })
+/// League table with each team's record (rank, W/T/L, points for/against).
+pub fn collect_standings(progress: Option<&mut dyn Progress>) -> Result<DataSet, Error> {
+    if let Some(p) = progress {
+        p.log("Fetching standings…");
+    }
+    let bundle = standings::fetch()?;
+    Ok(DataSet { headers: bundle.headers, rows: bundle.rows })
 }
 
-pub fn collect_game_results(_progress: Option<&mut dyn Progress>,) -> Result<DataSet, Box<dyn Error>> {
-    let bundle = scrape::game_results::fetch()?;
+/// Roster moves (signings, cuts, trades) currently listed on the site.
+pub fn collect_transactions(mut progress: Option<&mut dyn Progress>) -> Result<DataSet, Error> {
+    if let Some(p) = progress.as_deref_mut() {
+        p.log("Fetching transactions…");
+    }
+    let bundle = transactions::fetch(progress)?;
     Ok(DataSet { headers: bundle.headers, rows: bundle.rows })
 }
+
+/// Team Summary from the cached Players data (no requests).
+pub fn collect_team_summary(progress: Option<&mut dyn Progress>) -> Result<DataSet, Error> {
+    if let Some(p) = progress {
+        p.log("Summarizing cached players…");
+    }
+    let players = crate::store::load_dataset(&Players)
+        .ok()
+        .filter(|ds| !ds.rows.is_empty())
+        .ok_or("no cached Players data; scrape Players first")?;
+    Ok(crate::aggregate::team_summary(&players))
+}
+
+/// Leaders (top `top` players per stat) from the cached Players data (no
+/// requests).
+pub fn collect_leaders(top: usize, progress: Option<&mut dyn Progress>) -> Result<DataSet, Error> {
+    if let Some(p) = progress {
+        p.log("Ranking cached players…");
+    }
+    let players = crate::store::load_dataset(&Players)
+        .ok()
+        .filter(|ds| !ds.rows.is_empty())
+        .ok_or("no cached Players data; scrape Players first")?;
+    Ok(crate::leaders::from_players(&players, top))
+}
+
+/// Results Table from the cached Game Results (no requests).
+pub fn collect_results_table(progress: Option<&mut dyn Progress>) -> Result<DataSet, Error> {
+    if let Some(p) = progress {
+        p.log("Tallying cached game results…");
+    }
+    let results = crate::store::load_dataset(&GameResults)
+        .ok()
+        .filter(|ds| !ds.rows.is_empty())
+        .ok_or("no cached Game Results data; scrape Game Results first")?;
+    Ok(crate::results_table::from_results(&results, &crate::get_teams::cached().unwrap_or_default(), None))
+}
+
+/// Schedule Metrics from the cached Game Results (no requests).
+pub fn collect_schedule_metrics(progress: Option<&mut dyn Progress>) -> Result<DataSet, Error> {
+    if let Some(p) = progress {
+        p.log("Measuring cached schedules…");
+    }
+    let results = crate::store::load_dataset(&GameResults)
+        .ok()
+        .filter(|ds| !ds.rows.is_empty())
+        .ok_or("no cached Game Results data; scrape Game Results first")?;
+    Ok(crate::schedule_metrics::from_results(&results, None))
+}
+
+/// Injury Victims from the cached Injuries and Players data (no requests).
+/// Victims not found among the players keep blank player columns.
+pub fn collect_injury_victims(progress: Option<&mut dyn Progress>) -> Result<DataSet, Error> {
+    let cached = |kind: PageKind| crate::store::load_dataset(&kind).ok().filter(|ds| !ds.rows.is_empty());
+    let injuries = cached(Injuries).ok_or("no cached Injuries data; scrape Injuries first")?;
+    let players = cached(Players).ok_or("no cached Players data; scrape Players first")?;
+    let (ds, stats) = crate::join::injuries_with_victims(&injuries, &players, &crate::alias::Aliases::load());
+    logf!("Injury victims: {} exact, {} fuzzy, {} ambiguous, {} unmatched", stats.exact, stats.fuzzy, stats.ambiguous.len(), stats.missing.len());
+    for (team, name) in &stats.missing {
+        logd!("Injury victims: no player {:?} on {:?}", name, team);
+    }
+    for (team, name) in &stats.ambiguous {
+        logd!("Injury victims: {:?} has several players named {:?}", team, name);
+    }
+    if let Some(p) = progress.filter(|_| !stats.missing.is_empty()) {
+        p.log(&format!("{} victim(s) not found among the players (retired, or renamed)", stats.missing.len()));
+    }
+    Ok(ds)
+}
+
+/// Injury Summary from the cached Injuries data (no requests).
+pub fn collect_injury_summary(progress: Option<&mut dyn Progress>) -> Result<DataSet, Error> {
+    if let Some(p) = progress {
+        p.log("Counting cached injuries…");
+    }
+    let injuries = crate::store::load_dataset(&Injuries)
+        .ok()
+        .filter(|ds| !ds.rows.is_empty())
+        .ok_or("no cached Injuries data; scrape Injuries first")?;
+    Ok(crate::injury_summary::from_injuries(&injuries))
+}
+
+/// What a scrape would request, without doing it (CLI `--plan`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScrapePlan {
+    pub page: PageKind,
+    /// Absolute URLs, in request order
+    pub urls: Vec<String>,
+    /// Parallel fetchers used for the per-team requests
+    pub workers: usize,
+    /// Polite pauses between requests (wall time, spread over the workers)
+    pub throttle: Duration,
+}
+
+/// The requests `page` would make for the current scrape options.
+/// Mirrors the collectors above; keep them in step.
+pub fn plan(page: PageKind, scrape: &ScrapeOptions) -> Result<ScrapePlan, Error> {
+    let single = |path: &str| ScrapePlan {
+        page,
+        urls: vec![net::full_url(path)],
+        workers: 1,
+        throttle: Duration::ZERO,
+    };
+
+    Ok(match page {
+        Teams | Standings => single("/index.php"),
+        GameResults => single("season.php"),
+        Injuries => single("injury.php"),
+        Transactions => single("transactions.php"),
+        // Computed from the cached pages
+        TeamSummary | Leaders | InjuryVictims | InjurySummary | ResultsTable | ScheduleMetrics => ScrapePlan { page, urls: Vec::new(), workers: 0, throttle: Duration::ZERO },
+        Player => {
+            let id = scrape.player_id.ok_or("--page player needs --player-id <id>")?;
+            single(&format!("player.php?i={}", id))
+        }
+        Players | TeamInfo => {
+            let ids = resolve_ids(&scrape.teams);
+            let workers = WORKERS.min(ids.len()).max(1);
+
+            // Each worker pauses after every team (see collect_players)
+            let delay_ms = net::settings().delay_ms;
+            let mut per_worker = vec![0u64; workers];
+            for (i, id) in ids.iter().enumerate() {
+                per_worker[i % workers] += delay_ms + (*id as u64) % JITTER_MS;
+            }
+            let throttle = Duration::from_millis(per_worker.into_iter().max().unwrap_or(0));
+
+            // Players: team list refresh first (best-effort), then one roster per team
+            let mut urls = if page == Players { vec![net::full_url("/index.php")] } else { Vec::new() };
+            urls.extend(ids.iter().map(|id| net::full_url(&format!("team.php?i={}", id))));
+            ScrapePlan { page, urls, workers, throttle }
+        }
+        SeasonStats | CareerStats => return Err(format!("{} scraper not implemented yet", page).into()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_lists_team_urls_without_fetching() {
+        let opts = ScrapeOptions { teams: TeamSelector::Ids(vec![3, 5]), ..Default::default() };
+        let p = plan(Players, &opts).unwrap();
+        assert_eq!(p.urls.len(), 3);
+        assert!(p.urls[0].ends_with("/index.php"));
+        assert!(p.urls[1].ends_with("/team.php?i=3"));
+        assert!(p.urls[2].ends_with("/team.php?i=5"));
+        assert_eq!(p.workers, 2);
+        assert!(p.throttle >= Duration::from_millis(crate::config::consts::REQUEST_PAUSE_MS));
+
+        assert!(plan(Player, &ScrapeOptions::default()).is_err());
+    }
+}
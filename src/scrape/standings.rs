@@ -0,0 +1,154 @@
+// src/scrape/standings.rs
+//! Scraping *spec* for Standings (the league table on `/index.php`).
+//!
+//! The same table `teams.rs` reads names from; here we keep the record too.
+//! Columns are found by their header text (the site's `<th>` row), so a
+//! reordered or extended table still maps. Header rows may repeat (one per
+//! division); without a rank column, rank is the position since the last one.
+//!
+//! Output columns: see `STANDINGS_HEADERS`. No caching here.
+
+use crate::error::Error;
+use crate::core::{net, html};
+use crate::core::html::{next_tag_block_ci, strip_tags};
+use crate::core::sanitize::{normalize_entities, normalize_ws};
+
+pub const STANDINGS_HEADERS: [&str; 8] = ["Rank", "Id", "Team", "W", "T", "L", "PF", "PA"];
+
+pub struct StandingsBundle {
+    pub headers: Option<Vec<String>>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// Site header → output column (index into STANDINGS_HEADERS)
+pub(super) fn column_for(header: &str) -> Option<usize> {
+    let h = header.trim().trim_end_matches('.').to_ascii_lowercase();
+    Some(match h.as_str() {
+        "#" | "rank" | "pos" | "position" => 0,
+        "w" | "won" | "win" | "wins" => 3,
+        "t" | "d" | "tie" | "ties" | "tied" | "draw" | "draws" | "drawn" => 4,
+        "l" | "lost" | "loss" | "losses" => 5,
+        "pf" | "f" | "for" | "tdf" | "td+" | "tf" | "points for" => 6,
+        "pa" | "a" | "against" | "tda" | "td-" | "ta" | "points against" => 7,
+        _ => return None,
+    })
+}
+
+pub fn fetch() -> Result<StandingsBundle, Error> {
+    let doc = net::http_get("/index.php")?;
+    parse(&doc)
+}
+
+pub fn parse(doc: &str) -> Result<StandingsBundle, Error> {
+    let (ts, te) = next_tag_block_ci(doc, "<table", "</table>", 0)
+        .ok_or_else(|| Error::parse("League table", "table not found"))?;
+    let table = &doc[ts..te];
+
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut map: Vec<Option<usize>> = Vec::new();
+    let mut pos_in_group = 0usize;
+    let mut any_record = false;
+
+    let mut at = 0usize;
+    while let Some((tr_s, tr_e)) = next_tag_block_ci(table, "<tr", "</tr>", at) {
+        let tr = &table[tr_s..tr_e];
+        at = tr_e;
+
+        let ths = cells(tr, "th");
+        if !ths.is_empty() {
+            map = ths.iter().map(|h| column_for(&cell_text(h))).collect();
+            pos_in_group = 0;
+            continue;
+        }
+
+        let tds = cells(tr, "td");
+        let Some(name_ix) = tds.iter().position(|c| html::to_lower(c).contains("namecheck")) else { continue };
+        let Some((id, name)) = team_link(tds[name_ix]) else { continue };
+
+        pos_in_group += 1;
+        let mut row = vec![String::new(); STANDINGS_HEADERS.len()];
+        row[1] = id.to_string();
+        row[2] = name;
+        for (ci, cell) in tds.iter().enumerate() {
+            if let Some(Some(out)) = map.get(ci) {
+                row[*out] = cell_text(cell);
+            }
+        }
+        if row[0].is_empty() { row[0] = pos_in_group.to_string(); }
+        any_record |= !row[3].is_empty();
+        rows.push(row);
+    }
+
+    if rows.is_empty() {
+        return Err(Error::parse("League table", "no teams found"));
+    }
+    if !any_record {
+        return Err(Error::parse("League table", "no W/T/L columns (site format may have changed)"));
+    }
+
+    Ok(StandingsBundle {
+        headers: Some(STANDINGS_HEADERS.iter().map(|h| s!(*h)).collect()),
+        rows,
+    })
+}
+
+/// Whole `<tag …>…</tag>` blocks of a row, in order.
+pub(super) fn cells<'a>(tr: &'a str, tag: &str) -> Vec<&'a str> {
+    let (open, close) = (format!("<{}", tag), format!("</{}>", tag));
+    let mut out = Vec::new();
+    let mut at = 0usize;
+    while let Some((s, e)) = next_tag_block_ci(tr, &open, &close, at) {
+        out.push(&tr[s..e]);
+        at = e;
+    }
+    out
+}
+
+pub(super) fn cell_text(block: &str) -> String {
+    normalize_ws(&strip_tags(normalize_entities(&html::inner_after_open_tag(block))))
+}
+
+/// `(id, name)` from the first `team.php?i=<id>` link in a cell.
+pub(super) fn team_link(cell: &str) -> Option<(u32, String)> {
+    let lc = html::to_lower(cell);
+    let at = lc.find("team.php?i=")? + "team.php?i=".len();
+    let digits: String = lc[at..].chars().take_while(|c| c.is_ascii_digit()).collect();
+    let id = digits.parse().ok()?;
+    let gt = at + lc[at..].find('>')? + 1;
+    let end = gt + lc[gt..].find("</a>")?;
+    let name = normalize_ws(&strip_tags(normalize_entities(&cell[gt..end])));
+    (!name.is_empty()).then_some((id, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = r#"<html><body><table class="league">
+        <tr><th>Division A</th><th>W</th><th>D</th><th>L</th><th>TD+</th><th>TD-</th></tr>
+        <tr><td class="namecheck"><a href="team.php?i=3">Orcland&nbsp;Raiders</a></td>
+            <td>6</td><td>0</td><td>2</td><td>20</td><td>11</td></tr>
+        <tr><td class="namecheck"><a href='team.php?i=12'>Elf Rangers</a></td>
+            <td>4</td><td>1</td><td>3</td><td>14</td><td>12</td></tr>
+        <tr><th>Division B</th><th>W</th><th>D</th><th>L</th><th>TD+</th><th>TD-</th></tr>
+        <tr><td class="namecheck"><a href="team.php?i=7">Dwarf Giants</a></td>
+            <td>5</td><td>2</td><td>1</td><td>17</td><td>9</td></tr>
+        </table></body></html>"#;
+
+    #[test]
+    fn maps_columns_by_header_and_ranks_per_division() {
+        let b = parse(DOC).unwrap();
+        let row = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(b.rows, vec![
+            row(&["1", "3", "Orcland Raiders", "6", "0", "2", "20", "11"]),
+            row(&["2", "12", "Elf Rangers", "4", "1", "3", "14", "12"]),
+            row(&["1", "7", "Dwarf Giants", "5", "2", "1", "17", "9"]),
+        ]);
+    }
+
+    #[test]
+    fn names_only_table_is_an_error() {
+        let doc = r#"<table><tr><td class="namecheck"><a href="team.php?i=1">A</a></td></tr></table>"#;
+        assert!(parse(doc).is_err());
+    }
+}
@@ -0,0 +1,181 @@
+// src/scrape/team_info.rs
+//
+// Team metadata from the team page (team.php?i=<id>): owner, stadium and
+// logo. The roster on the same page is the Players scrape.
+//
+// Owner and stadium are read from label/value rows ("Stadium: …"), or from
+// the team heading ("<Team> (6 - 0 - 2) Team owner <name>"). A field the page
+// doesn't show is left blank rather than failing the team.
+
+use std::{fs, path::PathBuf};
+
+use crate::error::Error;
+
+use crate::config::{consts::{HOST, PREFIX}, options::Backup};
+use crate::core::{net, html::{next_tag_block_ci, strip_tags, to_lower}, select::{attr_value, leaf_tables}};
+use crate::core::sanitize::normalize_entities;
+use crate::store;
+
+pub const TEAM_INFO_HEADERS: [&str; 5] = ["Id", "Team", "Owner", "Stadium", "Logo"];
+
+const OWNER_LABELS: [&str; 2] = ["team owner", "owner"];
+const STADIUM_LABELS: [&str; 2] = ["stadium", "home ground"];
+
+/// One row of Team Info for `team_id`; with `logos`, also download the logo
+/// into `.store/logos/` (a failed download is logged, not fatal).
+pub fn fetch(team_id: u32, logos: bool) -> Result<Vec<String>, Error> {
+    let doc = net::http_get(&format!("team.php?i={}", team_id))?;
+    let row = parse(&doc, team_id)?;
+    if let Some(path) = logo_site_path(&row[4]).filter(|_| logos) {
+        match download_logo(team_id, &path) {
+            Ok(p) => logd!("Team info: Logo {} → {}", team_id, p.display()),
+            Err(e) => loge!("Team info: Logo of team {} ({}): {}", team_id, path, e),
+        }
+    }
+    Ok(row)
+}
+
+/// Id, Team, Owner, Stadium, Logo (absolute URL) from a team page.
+pub fn parse(doc: &str, team_id: u32) -> Result<Vec<String>, Error> {
+    let team = super::players::extract_and_validate_team_name(doc, team_id)?;
+    let doc = normalize_entities(doc);
+    let owner = labeled(&doc, &OWNER_LABELS).unwrap_or_default();
+    let stadium = labeled(&doc, &STADIUM_LABELS).unwrap_or_default();
+    let logo = logo_src(&doc)
+        .map(|src| match site_relative(&src) {
+            Some(path) => net::full_url(&path),
+            None => src,
+        })
+        .unwrap_or_default();
+    Ok(vec![team_id.to_string(), team, owner, stadium, logo])
+}
+
+/// Value for the first of `labels` found: a two-cell row labelled with it,
+/// else the text after it in a heading.
+fn labeled(doc: &str, labels: &[&str]) -> Option<String> {
+    let tables = leaf_tables(doc);
+    for label in labels {
+        let found = tables.iter()
+            .flat_map(|t| t.rows.iter())
+            .filter(|r| r.len() == 2)
+            .find(|r| r[0].trim_end_matches(':').trim().eq_ignore_ascii_case(label))
+            .map(|r| r[1].trim().to_string());
+        if let Some(v) = found.filter(|v| !v.is_empty()) {
+            return Some(v);
+        }
+    }
+    for open in ["<h5", "<h4", "<h3"] {
+        let close = format!("</{}", &open[1..]);
+        let mut pos = 0;
+        while let Some((s, e)) = next_tag_block_ci(doc, open, &close, pos) {
+            pos = e;
+            let text = strip_tags(&doc[s..e]);
+            if let Some(v) = labels.iter().find_map(|l| after_label(&text, l)) {
+                return Some(v);
+            }
+        }
+    }
+    None
+}
+
+/// Text after `label` (whole words, any case) up to a " | " separator.
+fn after_label(text: &str, label: &str) -> Option<String> {
+    let lc = to_lower(text);
+    let mut from = 0;
+    while let Some(rel) = lc[from..].find(label) {
+        let at = from + rel;
+        from = at + label.len();
+        let before_ok = at == 0 || !lc.as_bytes()[at - 1].is_ascii_alphanumeric();
+        let rest = &text[from..];
+        if !before_ok || rest.starts_with(|c: char| c.is_alphanumeric()) { continue; }
+        let v = rest.trim_start_matches([':', ' ']).split(" | ").next().unwrap_or("").trim();
+        if !v.is_empty() { return Some(v.to_string()); }
+    }
+    None
+}
+
+/// `src` of the team logo: the first image whose src, alt or class says "logo".
+fn logo_src(doc: &str) -> Option<String> {
+    let lc = to_lower(doc);
+    let mut from = 0;
+    while let Some(rel) = lc[from..].find("<img") {
+        let at = from + rel;
+        let end = lc[at..].find('>').map_or(lc.len(), |e| at + e);
+        from = end;
+        let tag = &doc[at + 4..end];
+        if !to_lower(tag).contains("logo") { continue; }
+        if let Some(src) = attr_value(tag, "src").filter(|s| !s.is_empty()) {
+            return Some(src);
+        }
+    }
+    None
+}
+
+/// Path for `net::http_get*` when `src` is on the site (relative, or an
+/// absolute URL/path under it); None for other hosts.
+fn site_relative(src: &str) -> Option<String> {
+    let lc = to_lower(src);
+    let rest = match ["http://", "https://", "//"].iter().find_map(|p| lc.strip_prefix(p)) {
+        Some(r) if r.starts_with(HOST) => &src[src.len() - r.len() + HOST.len()..],
+        Some(_) => return None,
+        None => src,
+    };
+    if !rest.starts_with('/') {
+        return Some(rest.to_string());
+    }
+    rest.strip_prefix(PREFIX).map(|r| r.to_string())
+}
+
+/// Site path of a logo URL from the Logo column.
+pub fn logo_site_path(logo: &str) -> Option<String> {
+    if logo.is_empty() { None } else { site_relative(logo) }
+}
+
+/// Save the logo as `.store/logos/<team id>.<ext>`, replacing an older one.
+fn download_logo(team_id: u32, path: &str) -> Result<PathBuf, Error> {
+    let bytes = net::http_get_bytes(path)?;
+    let ext = path.split(['?', '#']).next()
+        .and_then(|p| p.rsplit_once('.'))
+        .map(|(_, e)| e.to_ascii_lowercase())
+        .filter(|e| !e.is_empty() && e.len() <= 4 && e.chars().all(|c| c.is_ascii_alphanumeric()))
+        .unwrap_or_else(|| s!("png"));
+    let dir = store::logo_dir();
+    fs::create_dir_all(&dir).map_err(|e| Error::io(&dir, e))?;
+    if let Some(old) = store::logo_file(team_id) {
+        let _ = fs::remove_file(old);
+    }
+    let out = dir.join(format!("{}.{}", team_id, ext));
+    crate::file::write_atomic(&out, &bytes, Backup::Off).map_err(|e| Error::io(&out, e))?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = r#"<html><head><title>Failurewood Hills</title></head><body>
+        <table><tr><td class="teamenuhead">Failurewood Hills</td>
+        <td class="teamenuactive"><a href="team.php?i=6">Failurewood Hills</a></td></tr></table>
+        <img src="img/banner.png"><img class="teamlogo" src="/brutalball/logos/6.png?v=2">
+        <h5>Failurewood Hills (6 - 0 - 2) Team owner Foo Bar</h5>
+        <table><tr><td>Stadium:</td><td>The&nbsp;Pit</td></tr><tr><td>Fans</td><td>900</td></tr></table>
+        </body></html>"#;
+
+    #[test]
+    fn reads_owner_stadium_and_logo() {
+        let row = parse(DOC, 6).unwrap();
+        let logo = net::full_url("logos/6.png?v=2");
+        assert_eq!(row, vec!["6", "Failurewood Hills", "Foo Bar", "The Pit", logo.as_str()]);
+        assert_eq!(logo_site_path(&row[4]).as_deref(), Some("logos/6.png?v=2"));
+    }
+
+    #[test]
+    fn logo_paths_only_on_the_site() {
+        assert_eq!(site_relative("img/l.png").as_deref(), Some("img/l.png"));
+        assert_eq!(site_relative(&format!("http://{}{}x.gif", HOST, PREFIX)).as_deref(), Some("x.gif"));
+        assert_eq!(site_relative("https://imgur.com/a.png"), None);
+        assert_eq!(site_relative("/elsewhere/a.png"), None);
+        assert_eq!(after_label("Ownership Team owner: Foo | Div A", "team owner").as_deref(), Some("Foo"));
+        assert_eq!(after_label("Owners lounge", "owner"), None);
+    }
+}
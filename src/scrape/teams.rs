@@ -1,4 +1,4 @@
-// src/specs/teams.rs
+// src/scrape/teams.rs
 //! Scraping *spec* for Teams.
 //!
 //! Purpose:
@@ -15,10 +15,12 @@
 //! - **No caching / persistence.**
 //! - **No GUI state, filtering, or export rules.**
 //!
-//! TL;DR: `specs/teams.rs` knows *how to read the page* and produce a raw table; it does not decide *when* or *whether* to save/use it.
+//! TL;DR: `scrape/teams.rs` knows *how to read the page* and produce a raw table; it does not decide *when* or *whether* to save/use it.
 
-use std::error::Error;
+use std::collections::HashMap;
+use crate::error::Error;
 use crate::core::{ net, html };
+use super::standings;
 use crate::core::html::{ next_tag_block_ci, strip_tags };
 
 pub struct TeamsBundle {
@@ -26,30 +28,77 @@ pub struct TeamsBundle {
     pub rows: Vec<Vec<String>>,
 }
 
-pub fn fetch() -> Result<TeamsBundle, Box<dyn Error>> {
+pub fn fetch() -> Result<TeamsBundle, Error> {
     let html_doc = net::http_get("/index.php")?;
+    parse(&html_doc)
+}
 
+/// Team list from the league page (no network).
+pub fn parse(html_doc: &str) -> Result<TeamsBundle, Error> {
     // 1) Try the league table first (preferred, has full team names).
-    let mut rows = scrape_from_league_table(&html_doc)?;
+    let mut rows = scrape_from_league_table(html_doc)?;
 
     // 2) Fallback: mega-menu short names (legacy behavior).
     if rows.is_empty() {
-        rows = scrape_from_mega_menu(&html_doc)?;
+        rows = scrape_from_mega_menu(html_doc)?;
     }
 
     // tidy
     rows.sort_by_key(|r| r.get(0).and_then(|s| s.parse::<u32>().ok()).unwrap_or(u32::MAX));
     rows.dedup_by(|a, b| a.get(0) == b.get(0));
 
+    // 3) Division/conference each team is listed under (blank if none)
+    let divisions = league_divisions(html_doc);
+    for r in &mut rows {
+        let div = r.first().and_then(|id| id.parse::<u32>().ok()).and_then(|id| divisions.get(&id));
+        r.push(div.cloned().unwrap_or_default());
+    }
+
     Ok(TeamsBundle {
-        headers: Some(vec![s!("Id"), s!("Team")]),
+        headers: Some(vec![s!("Id"), s!("Team"), s!(DIVISION_HEADER)]),
         rows,
     })
 }
 
+/// Teams column naming each team's division (see `get_teams::divisions`).
+pub const DIVISION_HEADER: &str = "Division";
+
+/// Team id → division, from the league table's header rows: each group of
+/// teams is headed by a `<th>` row whose first cell names the division
+/// ("Division A", "NFC North"). A plain column heading there ("Team") is
+/// not a division.
+fn league_divisions(doc: &str) -> HashMap<u32, String> {
+    let mut out = HashMap::new();
+    let Some((ts, te)) = next_tag_block_ci(doc, "<table", "</table>", 0) else { return out };
+    let table = &doc[ts..te];
+
+    let mut current: Option<String> = None;
+    let mut at = 0usize;
+    while let Some((tr_s, tr_e)) = next_tag_block_ci(table, "<tr", "</tr>", at) {
+        let tr = &table[tr_s..tr_e];
+        at = tr_e;
+
+        let ths = standings::cells(tr, "th");
+        if let Some(first) = ths.first() {
+            let label = standings::cell_text(first);
+            let generic = matches!(label.to_ascii_lowercase().as_str(), "" | "team" | "teams" | "name" | "club")
+                || standings::column_for(&label).is_some();
+            current = (!generic).then_some(label);
+            continue;
+        }
+        let Some(div) = &current else { continue };
+        let tds = standings::cells(tr, "td");
+        let Some(cell) = tds.iter().find(|c| html::to_lower(c).contains("namecheck")) else { continue };
+        if let Some((id, _)) = standings::team_link(cell) {
+            out.insert(id, div.clone());
+        }
+    }
+    out
+}
+
 /// Parse from the main league table:
 ///   <td class="namecheck"><a href="team.php?i=31">Eduslum Marching Band</a> ...</td>
-fn scrape_from_league_table(doc: &str) -> Result<Vec<Vec<String>>, Box<dyn Error>> {
+pub(super) fn scrape_from_league_table(doc: &str) -> Result<Vec<Vec<String>>, Error> {
     let mut out: Vec<Vec<String>> = Vec::new();
 
     // Grab the first <table>...</table> block; the page uses a single centered table.
@@ -169,7 +218,7 @@ fn scrape_from_league_table(doc: &str) -> Result<Vec<Vec<String>>, Box<dyn Error
 }
 
 /// Fallback: parse from the mega-menu (short names) if league table parsing yielded nothing.
-fn scrape_from_mega_menu(html_doc: &str) -> Result<Vec<Vec<String>>, Box<dyn Error>> {
+fn scrape_from_mega_menu(html_doc: &str) -> Result<Vec<Vec<String>>, Error> {
     let bytes = html_doc.as_bytes();
     let mut i = 0usize;
     let n = bytes.len();
@@ -281,3 +330,25 @@ fn scrape_from_mega_menu(html_doc: &str) -> Result<Vec<Vec<String>>, Box<dyn Err
 
     Ok(rows)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn teams_get_the_division_they_are_listed_under() {
+        let doc = r#"<table>
+            <tr><th>Division A</th><th>W</th></tr>
+            <tr><td class="namecheck"><a href="team.php?i=3">Orcland Raiders</a></td><td>6</td></tr>
+            <tr><th>Division B</th><th>W</th></tr>
+            <tr><td class="namecheck"><a href="team.php?i=7">Dwarf Giants</a></td><td>5</td></tr>
+            </table>"#;
+        let b = parse(doc).unwrap();
+        assert_eq!(b.headers.unwrap(), ["Id", "Team", "Division"]);
+        assert_eq!(b.rows, [["3", "Orcland Raiders", "Division A"], ["7", "Dwarf Giants", "Division B"]]);
+
+        let flat = r#"<table><tr><th>Team</th><th>W</th></tr>
+            <tr><td class="namecheck"><a href="team.php?i=3">Orcland Raiders</a></td><td>6</td></tr></table>"#;
+        assert_eq!(parse(flat).unwrap().rows, [["3", "Orcland Raiders", ""]]);
+    }
+}
@@ -0,0 +1,183 @@
+// src/scrape/transactions.rs
+//! Scraping *spec* for Transactions (roster moves on `/transactions.php`):
+//! signings, cuts and trades.
+//!
+//! Columns are found by their header text, like Standings. The move type is
+//! normalized (Signed / Cut / Traded) when recognized and kept as shown
+//! otherwise. Without a season column, the season recorded by the last
+//! Game Results scrape is used.
+//!
+//! The site only lists recent moves (any further pages are followed, see
+//! `NEXT_PAGE`); the page's merge keeps older ones, so the cache grows into a
+//! season log.
+//!
+//! Output columns: see `TRANSACTIONS_HEADERS`. No caching here.
+
+use crate::error::Error;
+use crate::core::html;
+use crate::core::html::next_tag_block_ci;
+use crate::progress::Progress;
+use super::pagination::{self, NextPage};
+use super::standings::{cells, cell_text, team_link};
+
+pub const TRANSACTIONS_HEADERS: [&str; 8] =
+    ["S", "W", "Team", "Move", "Player", "Player id", "Other Team", "Details"];
+
+pub struct TransactionsBundle {
+    pub headers: Option<Vec<String>>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// Site header → output column (index into TRANSACTIONS_HEADERS)
+fn column_for(header: &str) -> Option<usize> {
+    let h = header.trim().trim_end_matches('.').to_ascii_lowercase();
+    Some(match h.as_str() {
+        "s" | "season" => 0,
+        "w" | "wk" | "week" => 1,
+        "team" => 2,
+        "move" | "type" | "action" | "transaction" => 3,
+        "player" | "name" => 4,
+        "from" | "to" | "with" | "other team" | "partner" | "trade partner" => 6,
+        "details" | "detail" | "notes" | "note" | "fee" | "price" | "comment" => 7,
+        _ => return None,
+    })
+}
+
+/// "signs", "Signing", "released", "TRADE" → Signed / Cut / Traded.
+pub fn normalize_move(raw: &str) -> String {
+    let lc = raw.trim().to_ascii_lowercase();
+    let kind = if lc.starts_with("sign") || lc.starts_with("hire") || lc.starts_with("draft") {
+        "Signed"
+    } else if lc.starts_with("cut") || lc.starts_with("releas") || lc.starts_with("fire") || lc.starts_with("waive") {
+        "Cut"
+    } else if lc.starts_with("trade") {
+        "Traded"
+    } else {
+        return raw.trim().to_string();
+    };
+    s!(kind)
+}
+
+/// Older moves may sit on further pages behind a "Next" link.
+pub const NEXT_PAGE: NextPage = NextPage::LinkText("next");
+
+pub fn fetch(progress: Option<&mut dyn Progress>) -> Result<TransactionsBundle, Error> {
+    let season = crate::store::load_season().ok().flatten()
+        .map(|s| s.to_string())
+        .unwrap_or_default();
+    let rows = pagination::collect("Transactions", "/transactions.php", NEXT_PAGE, |doc| {
+        parse(doc, &season).map(|b| b.rows)
+    }, progress)?;
+    Ok(TransactionsBundle {
+        headers: Some(TRANSACTIONS_HEADERS.iter().map(|h| s!(*h)).collect()),
+        rows,
+    })
+}
+
+/// `season` fills the S column when the table has none.
+pub fn parse(doc: &str, season: &str) -> Result<TransactionsBundle, Error> {
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut found_table = false;
+
+    let mut at_table = 0usize;
+    while let Some((ts, te)) = next_tag_block_ci(doc, "<table", "</table>", at_table) {
+        at_table = te;
+        let table = &doc[ts..te];
+
+        let mut map: Vec<Option<usize>> = Vec::new();
+        let mut at = 0usize;
+        while let Some((tr_s, tr_e)) = next_tag_block_ci(table, "<tr", "</tr>", at) {
+            let tr = &table[tr_s..tr_e];
+            at = tr_e;
+
+            let ths = cells(tr, "th");
+            if !ths.is_empty() {
+                map = ths.iter().map(|h| column_for(&cell_text(h))).collect();
+                // A moves table names the player and the kind of move
+                found_table |= map.contains(&Some(3)) && map.contains(&Some(4));
+                continue;
+            }
+            if !(map.contains(&Some(3)) && map.contains(&Some(4))) { continue; }
+
+            let tds = cells(tr, "td");
+            let mut row = vec![String::new(); TRANSACTIONS_HEADERS.len()];
+            for (ci, cell) in tds.iter().enumerate() {
+                match map.get(ci) {
+                    Some(Some(out @ (2 | 6))) => {
+                        row[*out] = team_link(cell).map(|(_, n)| n).unwrap_or_else(|| cell_text(cell));
+                    }
+                    Some(Some(4)) => {
+                        row[4] = cell_text(cell);
+                        row[5] = player_link_id(cell).map(|id| id.to_string()).unwrap_or_default();
+                    }
+                    Some(Some(out)) => row[*out] = cell_text(cell),
+                    _ => {}
+                }
+            }
+            if row[4].is_empty() || row[3].is_empty() { continue; }
+            row[3] = normalize_move(&row[3]);
+            if row[0].is_empty() { row[0] = season.to_string(); }
+            rows.push(row);
+        }
+    }
+
+    if !found_table {
+        return Err(Error::parse("Transactions", "table not found (site format may have changed)"));
+    }
+
+    Ok(TransactionsBundle {
+        headers: Some(TRANSACTIONS_HEADERS.iter().map(|h| s!(*h)).collect()),
+        rows,
+    })
+}
+
+/// Id from the first `player.php?i=<id>` link in a cell.
+fn player_link_id(cell: &str) -> Option<u32> {
+    let lc = html::to_lower(cell);
+    let at = lc.find("player.php?i=")? + "player.php?i=".len();
+    let digits: String = lc[at..].chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = r#"<html><body>
+        <table class="nav"><tr><td><a href="index.php">Home</a></td></tr></table>
+        <table class="moves">
+        <tr><th>Wk</th><th>Team</th><th>Type</th><th>Player</th><th>From</th><th>Fee</th></tr>
+        <tr><td>3</td><td><a href="team.php?i=3">Orcland&nbsp;Raiders</a></td><td>signs</td>
+            <td><a href="player.php?i=4411">Grub Ironjaw</a></td><td></td><td>60k</td></tr>
+        <tr><td>3</td><td><a href="team.php?i=12">Elf Rangers</a></td><td>Released</td>
+            <td><a href="player.php?i=907">Lith Fairwind</a></td><td></td><td></td></tr>
+        <tr><td>4</td><td><a href="team.php?i=7">Dwarf Giants</a></td><td>TRADE</td>
+            <td>Borin Stonefist</td><td><a href="team.php?i=3">Orcland Raiders</a></td><td></td></tr>
+        <tr><td>4</td><td><a href="team.php?i=7">Dwarf Giants</a></td><td>Retires</td>
+            <td><a href="player.php?i=12">Old Gimli</a></td><td></td><td></td></tr>
+        </table></body></html>"#;
+
+    #[test]
+    fn maps_columns_by_header_and_normalizes_moves() {
+        let b = parse(DOC, "7").unwrap();
+        let row = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(b.rows, vec![
+            row(&["7", "3", "Orcland Raiders", "Signed", "Grub Ironjaw", "4411", "", "60k"]),
+            row(&["7", "3", "Elf Rangers", "Cut", "Lith Fairwind", "907", "", ""]),
+            row(&["7", "4", "Dwarf Giants", "Traded", "Borin Stonefist", "", "Orcland Raiders", ""]),
+            row(&["7", "4", "Dwarf Giants", "Retires", "Old Gimli", "12", "", ""]),
+        ]);
+    }
+
+    #[test]
+    fn page_without_a_moves_table_is_an_error() {
+        let doc = r#"<table><tr><th>Team</th><th>W</th></tr><tr><td>A</td><td>1</td></tr></table>"#;
+        assert!(parse(doc, "").is_err());
+    }
+
+    #[test]
+    fn empty_moves_table_is_no_moves() {
+        let doc = "<table><tr><th>Week</th><th>Team</th><th>Move</th><th>Player</th></tr></table>";
+        assert!(parse(doc, "1").unwrap().rows.is_empty());
+    }
+}
@@ -0,0 +1,326 @@
+// src/serve.rs
+//
+// `--serve`: a small read-only HTTP server over the cache, so other tools
+// (league website, Discord bot) can read the scraped data without touching
+// files. std only, one connection at a time, GET/HEAD only; a client gets
+// `CLIENT_DEADLINE` in all to send its request and take the response, and
+// `MAX_REQUEST` bytes of request, so a slow one is dropped rather than
+// holding the server. The cache (team list included) is read
+// on every request and never scraped, so a `--watch` running alongside
+// shows up as is.
+//
+//   /                       pages on offer (JSON)
+//   /players.json           cached dataset as an array of objects
+//   /results.csv?team=5     as CSV (or .tsv), narrowed to teams (team=1,2 or team=1&team=2)
+
+use std::{
+    error::Error,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    config::options::PageKind,
+    file::{self, BUNDLE_PAGES},
+    get_teams,
+    gui::router,
+    manifest::json_str,
+    schema,
+    store::{self, DataSet},
+};
+
+pub const DEFAULT_PORT: u16 = 8080;
+
+/// Longest a client may take to send its request and take the response.
+const CLIENT_DEADLINE: Duration = Duration::from_secs(10);
+/// Most bytes read of a request (request line and headers).
+const MAX_REQUEST: u64 = 16 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl Response {
+    fn ok(content_type: &'static str, body: String) -> Self {
+        Self { status: 200, content_type, body }
+    }
+
+    fn error(status: u16, msg: &str) -> Self {
+        Self { status, content_type: "application/json", body: format!("{{\"error\": {}}}\n", json_str(msg)) }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            _ => "Error",
+        }
+    }
+
+    fn write_to<W: Write>(&self, mut w: W, head_only: bool) -> io::Result<()> {
+        write!(w, "HTTP/1.1 {} {}\r\n", self.status, self.reason())?;
+        write!(w, "Content-Type: {}; charset=utf-8\r\n", self.content_type)?;
+        write!(w, "Content-Length: {}\r\n", self.body.len())?;
+        write!(w, "Access-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n")?;
+        if !head_only {
+            w.write_all(self.body.as_bytes())?;
+        }
+        w.flush()
+    }
+}
+
+/// `--serve` argument as a socket address: a bare port listens on localhost
+/// only; give `0.0.0.0:<port>` to serve the network.
+pub fn bind_addr(arg: &str) -> String {
+    let arg = arg.trim();
+    if arg.is_empty() {
+        format!("127.0.0.1:{}", DEFAULT_PORT)
+    } else if arg.bytes().all(|b| b.is_ascii_digit()) {
+        format!("127.0.0.1:{}", arg)
+    } else {
+        arg.to_string()
+    }
+}
+
+/// Serve until interrupted. A failed connection is logged, not fatal.
+pub fn run(addr: &str) -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind(addr).map_err(|e| format!("Can't listen on {}: {}", addr, e))?;
+    eprintln!("Serving the cache on http://{}/ (Ctrl+C to stop)", listener.local_addr()?);
+    logf!("Serve: Listening on {}", addr);
+
+    for stream in listener.incoming() {
+        let res = stream.and_then(handle_connection);
+        if let Err(e) = res {
+            logd!("Serve: Connection failed: {}", e);
+        }
+    }
+    Ok(())
+}
+
+fn handle_connection(stream: TcpStream) -> io::Result<()> {
+    serve_connection(&stream, Instant::now() + CLIENT_DEADLINE)
+}
+
+fn serve_connection(stream: &TcpStream, deadline: Instant) -> io::Result<()> {
+    let mut reader = BufReader::new(Timed { stream, deadline }.take(MAX_REQUEST));
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+    // Headers are not used; read them so the client isn't cut off mid-send
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 2 {
+        line.clear();
+    }
+
+    let mut parts = request.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or("/"));
+    let resp = match method {
+        "GET" | "HEAD" => {
+            let load = |k: PageKind| store::load_dataset(&k).ok().filter(|ds| !ds.rows.is_empty());
+            // Cached list only: `list` never goes to the site
+            respond(target, &load, &get_teams::list())
+        }
+        _ => Response::error(405, "Only GET and HEAD"),
+    };
+    logd!("Serve: {} {} → {}", method, target, resp.status);
+    resp.write_to(Timed { stream, deadline }, method == "HEAD")
+}
+
+/// A client connection whose reads and writes all count against one
+/// `deadline`: each waits at most for what is left of it, so sending a byte
+/// at a time doesn't buy more time.
+struct Timed<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Timed<'_> {
+    fn time_left(&self) -> io::Result<Duration> {
+        let left = self.deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "Client ran out of time"));
+        }
+        Ok(left)
+    }
+}
+
+impl Read for Timed<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.stream.set_read_timeout(Some(self.time_left()?))?;
+        self.stream.read(buf)
+    }
+}
+
+impl Write for Timed<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.stream.set_write_timeout(Some(self.time_left()?))?;
+        self.stream.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+/// The response for request `target` (path and query), datasets from `load`.
+pub fn respond(target: &str, load: &dyn Fn(PageKind) -> Option<DataSet>, teams: &[(u32, String)]) -> Response {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let path = path.trim_start_matches('/');
+    if path.is_empty() || path == "index.json" {
+        return index(load);
+    }
+
+    let Some((name, ext)) = path.rsplit_once('.') else {
+        return Response::error(404, "Try /<page>.json, /<page>.csv or /<page>.tsv");
+    };
+    let kind = match name {
+        "results" => PageKind::GameResults,
+        n => match PageKind::from_str(n) {
+            Ok(k) => k,
+            Err(e) => return Response::error(404, &e),
+        },
+    };
+    let Some(mut ds) = load(kind) else {
+        return Response::error(404, &format!("{} is not cached; scrape it first", kind));
+    };
+    schema::inject_headers(kind, &mut ds);
+
+    let mut ids = Vec::new();
+    for (k, v) in query.split('&').filter_map(|p| p.split_once('=')) {
+        if k != "team" { continue; }
+        for id in percent_decode(v).split([',', '+', ' ']).filter(|s| !s.is_empty()) {
+            match id.parse::<u32>() {
+                Ok(id) => ids.push(id),
+                Err(_) => return Response::error(400, &format!("Invalid team id: {}", id)),
+            }
+        }
+    }
+    if !ids.is_empty() {
+        ds.rows = router::page_for(&kind).filter_rows_for_selection(&ids, teams, &ds.rows);
+    }
+
+    match ext {
+        "json" => Response::ok("application/json", to_json(&ds)),
+        "csv" => Response::ok("text/csv", delimited(&ds, ',')),
+        "tsv" => Response::ok("text/tab-separated-values", delimited(&ds, '\t')),
+        _ => Response::error(404, &format!("Unknown format: .{} (json, csv, tsv)", ext)),
+    }
+}
+
+/// `v` with `%XX` escapes decoded (`1%2C2` → `1,2`); a malformed escape is
+/// kept as is.
+fn percent_decode(v: &str) -> String {
+    let bytes = v.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3)
+            .filter(|h| h.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(b)) => { out.push(b); i += 3; }
+            (b, _) => { out.push(b); i += 1; }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// `{"pages": [{"page": "players", "rows": 412}, …]}` for the cached pages.
+fn index(load: &dyn Fn(PageKind) -> Option<DataSet>) -> Response {
+    let pages: Vec<String> = BUNDLE_PAGES.iter()
+        .filter_map(|&k| load(k).map(|ds| format!("    {{\"page\": {}, \"rows\": {}}}", json_str(&k.to_string()), ds.rows.len())))
+        .collect();
+    let body = if pages.is_empty() {
+        s!("{\"pages\": []}\n")
+    } else {
+        format!("{{\"pages\": [\n{}\n]}}\n", pages.join(",\n"))
+    };
+    Response::ok("application/json", body)
+}
+
+/// Rows as objects keyed by header (arrays when there are no headers).
+fn to_json(ds: &DataSet) -> String {
+    let rows: Vec<String> = ds.rows.iter().map(|r| match &ds.headers {
+        Some(hs) => {
+            let fields: Vec<String> = hs.iter().zip(r)
+                .map(|(h, v)| format!("{}: {}", json_str(h), json_str(v)))
+                .collect();
+            format!("  {{{}}}", fields.join(", "))
+        }
+        None => format!("  [{}]", r.iter().map(|v| json_str(v)).collect::<Vec<_>>().join(", ")),
+    }).collect();
+    if rows.is_empty() { s!("[]\n") } else { format!("[\n{}\n]\n", rows.join(",\n")) }
+}
+
+fn delimited(ds: &DataSet, sep: char) -> String {
+    let mut out = Vec::new();
+    for r in ds.headers.iter().chain(&ds.rows) {
+        let _ = file::write_row(&mut out, r, sep);
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(cells: &[&str]) -> Vec<String> { cells.iter().map(|c| c.to_string()).collect() }
+
+    #[test]
+    fn routes_pages_formats_and_team_filter() {
+        let teams = vec![(3, s!("Alpha")), (5, s!("Beta \"B\""))];
+        let load = |k: PageKind| (k == PageKind::GameResults).then(|| DataSet {
+            headers: Some(row(&["Season", "Week", "Home team", "Home", "Away", "Away team", "Match id"])),
+            rows: vec![
+                row(&["7", "1", "Alpha", "2", "1", "Gamma", "11"]),
+                row(&["7", "1", "Delta", "0", "0", "Beta \"B\"", "12"]),
+            ],
+        });
+
+        let csv = respond("/results.csv?team=5", &load, &teams);
+        assert_eq!(csv.status, 200);
+        assert_eq!(csv.body, "Season,Week,Home team,Home,Away,Away team,Match id\n7,1,Delta,0,0,\"Beta \"\"B\"\"\",12\n");
+
+        let json = respond("/game-results.json", &load, &teams);
+        assert!(json.body.starts_with("[\n  {\"Season\": \"7\", \"Week\": \"1\", \"Home team\": \"Alpha\""));
+        assert!(json.body.contains("\"Away team\": \"Beta \\\"B\\\"\""));
+
+        assert!(respond("/", &load, &teams).body.contains("{\"page\": \"game-results\", \"rows\": 2}"));
+        assert_eq!(respond("/players.json", &load, &teams).status, 404);
+        assert_eq!(respond("/results.xml", &load, &teams).status, 404);
+        assert_eq!(respond("/results.csv?team=x", &load, &teams).status, 400);
+        assert_eq!(respond("/results.csv?team=3%2C5", &load, &teams).body.lines().count(), 3);
+        assert_eq!(respond("/results.csv?team=3%2c5", &load, &teams).body, respond("/results.csv?team=3,5", &load, &teams).body);
+        assert_eq!(percent_decode("a%2"), "a%2");
+        assert_eq!(percent_decode("%+1%41"), "%+1A");
+        assert_eq!(bind_addr("9000"), "127.0.0.1:9000");
+    }
+
+    #[test]
+    fn trickling_client_is_dropped_at_the_deadline() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = std::thread::spawn(move || {
+            let mut s = TcpStream::connect(addr).unwrap();
+            let _ = s.write_all(b"GET / HTTP/1.1\r\n");
+            // A header line every 50 ms: each read succeeds, the request never ends
+            for _ in 0..40 {
+                if s.write_all(b"X: y\r\n").is_err() { break; }
+                std::thread::sleep(Duration::from_millis(50));
+            }
+        });
+        let (stream, _) = listener.accept().unwrap();
+        let start = Instant::now();
+        assert!(serve_connection(&stream, start + Duration::from_millis(300)).is_err());
+        assert!(start.elapsed() < Duration::from_secs(1));
+        drop(stream);
+        client.join().unwrap();
+    }
+}
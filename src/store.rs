@@ -1,19 +1,135 @@
 // src/store.rs
 use std::{
+    collections::{BTreeMap, HashMap},
     fs::{ self, File },
     io::{ BufWriter, Result },
-    path::PathBuf,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock, RwLock},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use crate::file::{parse_rows, write_row};
-use crate::config::options::PageKind::{self, *};
-use crate::config::consts::{STORE_DIR, STORE_SEP};
+use crate::config::options::{PageExportDefaults, PageKind::{self, *}};
+use crate::config::consts::{DEFAULT_LEADERS_TOP, DEFAULT_STALE_AFTER_HOURS, FONT_SIZE_RANGE, LOG_FILE_NAME, PARSER_VERSION, PORTABLE_MARKER, STORE_DIR, STORE_SEP};
+use crate::config::state::Appearance;
+use crate::core::net::NetSettings;
+
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+/// Storage backend for canonical page datasets.
+/// The default is flat files under `.store/`; builds with the `sqlite`
+/// feature can opt into a single database via `BB_STORE_BACKEND=sqlite`.
+pub trait Backend: Send + Sync {
+    /// Load the dataset for a page. Missing data is an `Err` (like a missing file).
+    fn load(&self, kind: &PageKind) -> Result<DataSet>;
+
+    /// Replace the stored dataset for a page. Returns where it was written.
+    fn save(&self, kind: &PageKind, ds: &DataSet) -> Result<PathBuf>;
+
+    /// When the page was last saved, if the backend knows.
+    fn saved_at(&self, _kind: &PageKind) -> Option<SystemTime> { None }
+}
+
+static BACKEND: OnceLock<Box<dyn Backend>> = OnceLock::new();
+
+/// The active backend, chosen once per process.
+pub fn backend() -> &'static dyn Backend {
+    BACKEND.get_or_init(|| {
+        let wanted = std::env::var("BB_STORE_BACKEND").unwrap_or_default();
+        match wanted.trim().to_ascii_lowercase().as_str() {
+            #[cfg(feature = "sqlite")]
+            "sqlite" => match sqlite::SqliteBackend::open(store_dir().join("store.db")) {
+                Ok(db) => Box::new(db) as Box<dyn Backend>,
+                Err(e) => {
+                    loge!("Store: SQLite unavailable ({}), using files", e);
+                    Box::new(FileBackend)
+                }
+            },
+            "" | "file" | "files" => Box::new(FileBackend),
+            other => {
+                loge!("Store: Unknown backend '{}', using files", other);
+                Box::new(FileBackend)
+            }
+        }
+    }).as_ref()
+}
+
+/// The backend of the season in use: `backend()` for the current season
+/// (and the shared team list), flat files for an archived one.
+fn season_backend(kind: &PageKind) -> &'static dyn Backend {
+    if selected_season().is_some() && *kind != Teams { &FileBackend } else { backend() }
+}
 
 /// Load cached dataset for a given page (if present).
 /// Assumes first row is headers when present.
 pub fn load_dataset(kind: &PageKind) -> Result<DataSet> {
-    let path = store_path(kind);
-    let text = fs::read_to_string(&path)?;
+    season_backend(kind).load(kind)
+}
+
+/// Persist a canonical dataset for a given page.
+/// Always writes headers first (if present), then rows.
+/// Also records which build wrote it (see `CacheMeta`).
+pub fn save_dataset(kind: &PageKind, ds: &DataSet) -> Result<PathBuf> {
+    let p = season_backend(kind).save(kind, ds)?;
+    bump_generation(kind);
+    let scraped_at = CacheMeta::load(kind).and_then(|m| m.scraped_at);
+    if let Err(e) = (CacheMeta { scraped_at, ..CacheMeta::current() }).save(kind) {
+        loge!("Store: Could not write cache metadata for {:?}: {}", kind, e);
+    }
+    let keep = snapshot_keep();
+    if keep > 0 {
+        match save_snapshot(kind, ds, keep) {
+            Ok(s) => logd!("Store: Snapshot {}", s.display()),
+            Err(e) => loge!("Store: Could not write snapshot for {:?}: {}", kind, e),
+        }
+    }
+    Ok(p)
+}
+
+fn generations() -> &'static Mutex<HashMap<PageKind, u64>> {
+    static GENERATIONS: OnceLock<Mutex<HashMap<PageKind, u64>>> = OnceLock::new();
+    GENERATIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn bump_generation(kind: &PageKind) {
+    if let Ok(mut g) = generations().lock() {
+        *g.entry(*kind).or_insert(0) += 1;
+    }
+}
+
+/// Counter bumped on every successful `save_dataset` for a page in this
+/// process. Poll and compare to notice that a page's cache changed.
+pub fn generation(kind: &PageKind) -> u64 {
+    generations().lock().map(|g| g.get(kind).copied().unwrap_or(0)).unwrap_or(0)
+}
+
+/// When the cached dataset for a page was last saved (cache age).
+pub fn saved_at(kind: &PageKind) -> Option<SystemTime> {
+    season_backend(kind).saved_at(kind)
+}
+
+/// Flat-file backend: one delimited file per page in `.store/`.
+pub struct FileBackend;
+
+impl Backend for FileBackend {
+    fn load(&self, kind: &PageKind) -> Result<DataSet> {
+        read_table(&store_path(kind))
+    }
+
+    fn save(&self, kind: &PageKind, ds: &DataSet) -> Result<PathBuf> {
+        let path = store_path(kind);
+        write_table(&path, ds)?;
+        Ok(path)
+    }
+
+    fn saved_at(&self, kind: &PageKind) -> Option<SystemTime> {
+        fs::metadata(store_path(kind)).and_then(|m| m.modified()).ok()
+    }
+}
+
+fn read_table(path: &Path) -> Result<DataSet> {
+    let text = fs::read_to_string(path)?;
     let mut rows = parse_rows(&text, STORE_SEP);
 
     let headers = if !rows.is_empty() {
@@ -25,16 +141,12 @@ pub fn load_dataset(kind: &PageKind) -> Result<DataSet> {
     Ok(DataSet { headers, rows })
 }
 
-/// Persist a canonical dataset for a given page.
-/// Always writes headers first (if present), then rows.
-pub fn save_dataset(kind: &PageKind, ds: &DataSet) -> Result<PathBuf> {
-    let dir = store_dir();
-    if !dir.exists() {
-        fs::create_dir_all(&dir)?;
+fn write_table(path: &Path, ds: &DataSet) -> Result<()> {
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
     }
 
-    let path = store_path(kind);
-    let file = File::create(&path)?;
+    let file = File::create(path)?;
     let mut w = BufWriter::new(file);
 
     if let Some(h) = &ds.headers {
@@ -45,34 +157,505 @@ pub fn save_dataset(kind: &PageKind, ds: &DataSet) -> Result<PathBuf> {
     }
     // BufWriter drops will flush; explicit flush not required
 
+    Ok(())
+}
+
+// ---- Previous snapshot (for "delta since last scrape") ----
+
+/// Always a flat file next to the page cache, whichever backend is active.
+fn previous_path(kind: &PageKind) -> PathBuf {
+    page_dir(kind).join(format!("{}.prev", page_filename(kind)))
+}
+
+/// Keep a copy of the dataset as it was before the latest scrape.
+pub fn save_previous(kind: &PageKind, ds: &DataSet) -> Result<PathBuf> {
+    let path = previous_path(kind);
+    write_table(&path, ds)?;
+    Ok(path)
+}
+
+/// Load the pre-scrape snapshot for a page (if one was kept).
+pub fn load_previous(kind: &PageKind) -> Result<DataSet> {
+    read_table(&previous_path(kind))
+}
+
+/// Whether there's a pre-scrape snapshot to go back to.
+pub fn has_previous(kind: &PageKind) -> bool {
+    previous_path(kind).is_file()
+}
+
+/// Undo the last scrape: the pre-scrape snapshot becomes the cache again,
+/// and the replaced cache becomes the snapshot (so a second restore redoes it).
+/// Returns the restored dataset.
+pub fn restore_previous(kind: &PageKind) -> Result<DataSet> {
+    let prev = load_previous(kind)?;
+    let current = load_dataset(kind).ok().filter(|ds| !ds.rows.is_empty());
+    save_dataset(kind, &prev)?;
+    match current {
+        Some(ds) => { save_previous(kind, &ds)?; }
+        None => { let _ = fs::remove_file(previous_path(kind)); }
+    }
+    Ok(prev)
+}
+
+// ---- Snapshots ----
+//
+// With snapshots on, every dataset saved to the cache is also archived as
+// `.store/snapshots/<page>/<unix time>.csv`, keeping the newest N per page.
+// Snapshots are only ever added or pruned, never rewritten.
+
+static SNAPSHOT_KEEP: OnceLock<usize> = OnceLock::new();
+
+/// Settings key for how many snapshots to keep per page (0 or missing = off).
+pub const SNAPSHOTS_KEY: &str = "snapshots";
+
+/// Keep `keep` snapshots per page for the rest of this run, whatever the
+/// settings say (CLI `--snapshots`). Only the first call counts.
+pub fn set_snapshot_keep(keep: usize) {
+    let _ = SNAPSHOT_KEEP.set(keep);
+}
+
+/// How many snapshots to keep per page; 0 = snapshots off.
+pub fn snapshot_keep() -> usize {
+    if let Some(&keep) = SNAPSHOT_KEEP.get() { return keep; }
+    load_settings().get(SNAPSHOTS_KEY)
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// Where the snapshots of a page go.
+pub fn snapshot_dir(kind: &PageKind) -> PathBuf {
+    page_dir(kind).join("snapshots").join(page_filename(kind))
+}
+
+/// Snapshots of a page, oldest first.
+pub fn list_snapshots(kind: &PageKind) -> Vec<PathBuf> {
+    list_snapshots_in(&snapshot_dir(kind))
+}
+
+/// Archive `ds` as a new snapshot of the page, then drop all but the newest `keep`.
+pub fn save_snapshot(kind: &PageKind, ds: &DataSet, keep: usize) -> Result<PathBuf> {
+    let path = write_snapshot(&snapshot_dir(kind), ds)?;
+    prune_snapshots(&snapshot_dir(kind), keep);
     Ok(path)
 }
 
-fn store_dir() -> PathBuf {
-    PathBuf::from(STORE_DIR)
+/// When a snapshot was taken, in unix seconds (from its file name).
+pub fn snapshot_time(path: &Path) -> Option<u64> {
+    snapshot_key(path).map(|(secs, _)| secs)
+}
+
+/// Read one archived snapshot.
+pub fn load_snapshot(path: &Path) -> Result<DataSet> {
+    read_table(path)
 }
 
+/// Every snapshot of a page with its time (unix seconds), oldest first.
+/// Unreadable ones are logged and left out.
+pub fn load_snapshots(kind: &PageKind) -> Vec<(u64, DataSet)> {
+    list_snapshots(kind).into_iter()
+        .filter_map(|p| match load_snapshot(&p) {
+            Ok(ds) => Some((snapshot_time(&p)?, ds)),
+            Err(e) => { loge!("Store: Could not read snapshot {}: {}", p.display(), e); None }
+        })
+        .collect()
+}
+
+/// (unix time, same-second counter) from `<secs>.csv` or `<secs>-<n>.csv`.
+fn snapshot_key(path: &Path) -> Option<(u64, u32)> {
+    let stem = path.file_name()?.to_str()?.strip_suffix(".csv")?;
+    match stem.split_once('-') {
+        Some((secs, n)) => Some((secs.parse().ok()?, n.parse().ok()?)),
+        None => Some((stem.parse().ok()?, 0)),
+    }
+}
+
+fn list_snapshots_in(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else { return Vec::new() };
+    let mut found: Vec<((u64, u32), PathBuf)> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter_map(|p| snapshot_key(&p).map(|k| (k, p)))
+        .collect();
+    found.sort();
+    found.into_iter().map(|(_, p)| p).collect()
+}
+
+/// Write a new CSV file in `dir`; never replaces an existing snapshot.
+fn write_snapshot(dir: &Path, ds: &DataSet) -> Result<PathBuf> {
+    use std::io::{ErrorKind, Write};
+    fs::create_dir_all(dir)?;
+    let secs = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut n = 0u32;
+    loop {
+        let name = match n {
+            0 => format!("{}.csv", secs),
+            n => format!("{}-{}.csv", secs, n),
+        };
+        let path = dir.join(name);
+        match File::options().write(true).create_new(true).open(&path) {
+            Ok(f) => {
+                let mut w = BufWriter::new(f);
+                if let Some(h) = &ds.headers {
+                    write_row(&mut w, h, ',')?;
+                }
+                for r in &ds.rows {
+                    write_row(&mut w, r, ',')?;
+                }
+                w.flush()?;
+                return Ok(path);
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => n += 1,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn prune_snapshots(dir: &Path, keep: usize) {
+    let all = list_snapshots_in(dir);
+    let excess = all.len().saturating_sub(keep);
+    for old in &all[..excess] {
+        if let Err(e) = fs::remove_file(old) {
+            loge!("Store: Could not remove old snapshot {}: {}", old.display(), e);
+        }
+    }
+}
+
+// ---- Data directory ----
+
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Settings key (in the default `.store/settings`) for a data directory
+/// elsewhere.
+const DATA_DIR_KEY: &str = "data_dir";
+
+/// Use `dir` for the cache and settings for the rest of this run (CLI
+/// `--data-dir`). Only counts before anything has touched the store.
+pub fn set_data_dir(dir: PathBuf) {
+    let _ = DATA_DIR.set(dir);
+}
+
+/// Where the cache, settings and log live: `--data-dir`, else `.store`
+/// next to the executable in portable mode, else the `data_dir` setting,
+/// else `.store` in the working directory.
+pub(crate) fn store_dir() -> PathBuf {
+    DATA_DIR.get_or_init(|| {
+        exe_store_dir().filter(|_| is_portable())
+            .or_else(configured_data_dir)
+            .unwrap_or_else(|| PathBuf::from(STORE_DIR))
+    }).clone()
+}
+
+fn portable_marker() -> Option<PathBuf> {
+    Some(std::env::current_exe().ok()?.parent()?.join(PORTABLE_MARKER))
+}
+
+/// Portable mode: the marker file sits next to the executable.
+pub fn is_portable() -> bool {
+    portable_marker().is_some_and(|m| m.is_file())
+}
+
+/// `.store` next to the executable (the data directory in portable mode).
+pub fn exe_store_dir() -> Option<PathBuf> {
+    Some(std::env::current_exe().ok()?.parent()?.join(STORE_DIR))
+}
+
+/// Turn portable mode on or off (creates or removes the marker file next
+/// to the executable). Takes effect on the next start.
+pub fn set_portable(on: bool) -> Result<PathBuf> {
+    let marker = portable_marker().ok_or_else(|| std::io::Error::other("Executable not found"))?;
+    if on {
+        std::fs::write(&marker, "Keep bb_scrape's data in .store next to the executable.\n")?;
+    } else if marker.exists() {
+        std::fs::remove_file(&marker)?;
+    }
+    Ok(marker)
+}
+
+/// The `data_dir` setting, if set.
+pub fn configured_data_dir() -> Option<PathBuf> {
+    read_kv(&Path::new(STORE_DIR).join("settings")).get(DATA_DIR_KEY)
+        .map(|v| PathBuf::from(v.trim()))
+        .filter(|p| !p.as_os_str().is_empty())
+}
+
+/// Save the `data_dir` setting (None = the default `.store`). It's kept in
+/// the default `.store/settings`, the one place looked at before the data
+/// directory is known. Takes effect on the next start.
+pub fn save_data_dir(dir: Option<&Path>) -> Result<PathBuf> {
+    let dir = dir.map(|d| d.to_string_lossy().into_owned()).unwrap_or_default();
+    if dir.contains(['\n', '\r']) {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("Invalid folder: {:?}", dir)));
+    }
+    save_setting_in(Path::new(STORE_DIR), DATA_DIR_KEY, dir.trim())
+}
+
+/// First start with a data directory other than `.store`: copy the files
+/// of `.store` over (cache, snapshots, logos, settings) unless it's
+/// already in use. `.store` is left as it was. Called at startup.
+pub fn migrate_default_store() {
+    let from = PathBuf::from(STORE_DIR);
+    let to = store_dir();
+    if same_dir(&from, &to) { return; }
+    match copy_store(&from, &to) {
+        Ok(0) => {}
+        Ok(n) => logf!("Store: Copied {} files from {} to {}", n, from.display(), to.display()),
+        Err(e) => loge!("Store: Could not copy {} to {}: {}", from.display(), to.display(), e),
+    }
+}
+
+/// Copy the files of `from` into `to` if `to` is missing or empty (the
+/// log stays behind). Returns how many files were copied. A `to` inside
+/// `from` is refused: the copy would walk into itself.
+pub fn copy_store(from: &Path, to: &Path) -> Result<usize> {
+    if !from.is_dir() { return Ok(0); }
+    if to.read_dir().is_ok_and(|mut d| d.next().is_some()) { return Ok(0); }
+    if resolved(to).starts_with(resolved(from)) {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput,
+            format!("{} is inside {}; pick a data folder outside it", to.display(), from.display())));
+    }
+    copy_dir(from, to)
+}
+
+/// `path` with the part that exists canonicalized and the rest appended,
+/// so a folder not created yet compares with existing ones.
+fn resolved(path: &Path) -> PathBuf {
+    let mut existing = path;
+    let mut rest = Vec::new();
+    loop {
+        if let Ok(real) = fs::canonicalize(existing) {
+            return rest.iter().rev().fold(real, |p, name| p.join(name));
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                rest.push(name.to_os_string());
+                existing = if parent.as_os_str().is_empty() { Path::new(".") } else { parent };
+            }
+            _ => return path.to_path_buf(),
+        }
+    }
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<usize> {
+    fs::create_dir_all(to)?;
+    let mut n = 0;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            n += copy_dir(&path, &to.join(entry.file_name()))?;
+        } else if entry.file_name() != LOG_FILE_NAME {
+            fs::copy(&path, to.join(entry.file_name()))?;
+            n += 1;
+        }
+    }
+    Ok(n)
+}
+
+/// Whether `a` and `b` are the same directory (by path when either is missing).
+pub fn same_dir(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a.components().eq(b.components()),
+    }
+}
+
+/// Every page with a cache of its own.
+pub const CACHED_PAGES: [PageKind; 14] = [Teams, Players, GameResults, Injuries, Standings, ResultsTable, ScheduleMetrics, Transactions, TeamSummary, Leaders, TeamInfo, InjuryVictims, InjurySummary, Player];
+
 fn store_path(kind: &PageKind) -> PathBuf {
-    store_dir().join(page_filename(kind))
+    page_dir(kind).join(page_filename(kind))
 }
 
-fn page_filename(kind: &PageKind) -> &'static str {
+pub(crate) fn page_filename(kind: &PageKind) -> &'static str {
     match kind {
         Teams         => "teams",
         Players       => "players",
         SeasonStats   => "season_stats",
         CareerStats   => "career_stats",
         Injuries      => "injuries",
+        Player        => "player",
+        Standings     => "standings",
+        Transactions  => "transactions",
+        TeamSummary   => "team_summary",
+        ResultsTable  => "results_table",
+        ScheduleMetrics => "schedule_metrics",
+        Leaders       => "leaders",
+        TeamInfo      => "team_info",
+        InjuryVictims => "injury_victims",
+        InjurySummary => "injury_summary",
         GameResults   => "game_results",
     }
 }
 
+// ---- Cache metadata ----
+
+/// Which build scraped a cached page, and when: `.store/<page>.meta`
+/// (`key=value` lines), a flat file whichever backend is active. Caches from
+/// before this was recorded have none.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CacheMeta {
+    pub app_version: String,
+    pub parser_version: u32,
+    /// Last scrape (saves after merges, imports and the like keep it)
+    pub scraped_at: Option<SystemTime>,
+}
+
+impl CacheMeta {
+    /// This build.
+    pub fn current() -> Self {
+        Self { app_version: s!(env!("CARGO_PKG_VERSION")), parser_version: PARSER_VERSION, scraped_at: None }
+    }
+
+    /// Scraped by an older parser than this build's; a re-scrape may fix misparses.
+    pub fn is_outdated(&self) -> bool {
+        self.parser_version < PARSER_VERSION
+    }
+
+    pub fn load(kind: &PageKind) -> Option<Self> {
+        Self::load_from(&meta_path(kind))
+    }
+
+    fn save(&self, kind: &PageKind) -> Result<PathBuf> {
+        let path = meta_path(kind);
+        self.save_to(&path)?;
+        Ok(path)
+    }
+
+    fn load_from(path: &Path) -> Option<Self> {
+        let kv = read_kv(path);
+        Some(Self {
+            app_version: kv.get("app_version")?.clone(),
+            parser_version: kv.get("parser_version")?.trim().parse().ok()?,
+            scraped_at: kv.get("scraped_at")
+                .and_then(|s| s.trim().parse().ok())
+                .map(|secs| UNIX_EPOCH + Duration::from_secs(secs)),
+        })
+    }
+
+    fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() { fs::create_dir_all(dir)?; }
+        let mut text = format!("app_version={}\nparser_version={}\n", self.app_version, self.parser_version);
+        if let Some(secs) = self.scraped_at.and_then(|t| t.duration_since(UNIX_EPOCH).ok()) {
+            text.push_str(&format!("scraped_at={}\n", secs.as_secs()));
+        }
+        fs::write(path, text)
+    }
+}
+
+/// When a page was last scraped. Caches from before scrape times were
+/// recorded (and pages only ever computed locally) go by their last save.
+pub fn scraped_at(kind: &PageKind) -> Option<SystemTime> {
+    CacheMeta::load(kind).and_then(|m| m.scraped_at).or_else(|| saved_at(kind))
+}
+
+/// Record that `kind` was scraped just now; call after saving the scrape.
+pub fn mark_scraped(kind: &PageKind) -> Result<PathBuf> {
+    (CacheMeta { scraped_at: Some(SystemTime::now()), ..CacheMeta::current() }).save(kind)
+}
+
+/// One team's part in a page's scrapes: when it last came through, and
+/// whether the latest attempt failed. Pages scraped team by team only.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TeamScrape {
+    pub scraped_at: Option<SystemTime>,
+    pub failed: bool,
+}
+
+/// Per-team scrape record of `kind` (`.store/<page>.teams`); empty for
+/// pages that aren't scraped team by team, or never were.
+pub fn team_scrapes(kind: &PageKind) -> HashMap<u32, TeamScrape> {
+    read_team_scrapes(&team_scrapes_path(kind))
+}
+
+/// Record a team-by-team scrape of `kind`: `done` came through just now,
+/// `failed` didn't (keeping when they last did). Other teams are unchanged.
+pub fn mark_teams_scraped(kind: &PageKind, done: &[u32], failed: &[u32]) -> Result<PathBuf> {
+    let path = team_scrapes_path(kind);
+    write_team_scrapes(&path, done, failed, SystemTime::now())?;
+    Ok(path)
+}
+
+fn team_scrapes_path(kind: &PageKind) -> PathBuf {
+    page_dir(kind).join(format!("{}.teams", page_filename(kind)))
+}
+
+/// `<id>.scraped_at=<unix secs>` and `<id>.failed=1` lines.
+fn read_team_scrapes(path: &Path) -> HashMap<u32, TeamScrape> {
+    let mut out: HashMap<u32, TeamScrape> = HashMap::new();
+    for (k, v) in read_kv(path) {
+        let Some((id, field)) = k.split_once('.') else { continue };
+        let Ok(id) = id.parse::<u32>() else { continue };
+        let entry = out.entry(id).or_default();
+        match field {
+            "scraped_at" => entry.scraped_at = v.trim().parse().ok().map(|secs| UNIX_EPOCH + Duration::from_secs(secs)),
+            "failed" => entry.failed = v.trim() == "1",
+            _ => {}
+        }
+    }
+    out
+}
+
+fn write_team_scrapes(path: &Path, done: &[u32], failed: &[u32], now: SystemTime) -> Result<()> {
+    let mut all: BTreeMap<u32, TeamScrape> = read_team_scrapes(path).into_iter().collect();
+    for &id in done {
+        all.insert(id, TeamScrape { scraped_at: Some(now), failed: false });
+    }
+    for &id in failed {
+        all.entry(id).or_default().failed = true;
+    }
+    let mut text = String::new();
+    for (id, t) in &all {
+        if let Some(secs) = t.scraped_at.and_then(|at| at.duration_since(UNIX_EPOCH).ok()) {
+            text.push_str(&format!("{}.scraped_at={}\n", id, secs.as_secs()));
+        }
+        if t.failed { text.push_str(&format!("{}.failed=1\n", id)); }
+    }
+    if let Some(dir) = path.parent() { fs::create_dir_all(dir)?; }
+    fs::write(path, text)
+}
+
+/// Whether data scraped at `at` is older than `hours`.
+pub fn is_stale(at: SystemTime, hours: u32) -> bool {
+    SystemTime::now().duration_since(at).is_ok_and(|age| age > Duration::from_secs(u64::from(hours) * 3_600))
+}
+
+/// "42s ago", "5m ago", "3h ago", "2d ago".
+pub fn fmt_age(d: Duration) -> String {
+    let s = d.as_secs();
+    match s {
+        0..60 => format!("{}s ago", s),
+        60..3_600 => format!("{}m ago", s / 60),
+        3_600..86_400 => format!("{}h ago", s / 3_600),
+        _ => format!("{}d ago", s / 86_400),
+    }
+}
+
+fn meta_path(kind: &PageKind) -> PathBuf {
+    page_dir(kind).join(format!("{}.meta", page_filename(kind)))
+}
+
+/// `key=value` lines; missing file = empty.
+fn read_kv(path: &Path) -> HashMap<String, String> {
+    let Ok(s) = std::fs::read_to_string(path) else { return HashMap::new(); };
+    s.lines()
+        .filter_map(|l| l.split_once('='))
+        .map(|(k, v)| (k.trim().to_string(), v.to_string()))
+        .collect()
+}
+
 // ---- Season persistence ----
 
 pub fn season_path() -> PathBuf { store_dir().join("season") }
 
-/// Save current season number to `.store/season`.
+/// Save current season number to `.store/season`. Archiving the last
+/// season's data is up to the user (`archive_season`).
 pub fn save_season(season: u32) -> Result<PathBuf> {
+    if let Some(old) = load_season()?.filter(|&old| old < season) {
+        logf!("Store: Season {} began (was {}); archive season {} to keep its data apart", season, old, old);
+    }
     let dir = store_dir();
     if !dir.exists() { std::fs::create_dir_all(&dir)?; }
     let p = season_path();
@@ -88,6 +671,341 @@ pub fn load_season() -> Result<Option<u32>> {
     Ok(s.trim().parse::<u32>().ok())
 }
 
+// ---- Seasons ----
+//
+// The current season's data sits in the store folder itself, earlier
+// seasons' in `seasons/<n>/`: the same files, flat whichever backend is
+// active. `select_season` switches which one the page data, previous
+// copies, metadata and snapshots come from (GUI season menu, CLI
+// `--season`); team list, settings and log stay shared (`page_dir`).
+
+static SELECTED_SEASON: RwLock<Option<u32>> = RwLock::new(None);
+
+pub fn season_dir(season: u32) -> PathBuf {
+    store_dir().join("seasons").join(season.to_string())
+}
+
+/// Where the data of `kind` for the season in use lives; the team list
+/// is the same for every season.
+fn page_dir(kind: &PageKind) -> PathBuf {
+    if *kind == Teams { return store_dir(); }
+    selected_season().map(season_dir).unwrap_or_else(store_dir)
+}
+
+/// The archived season in use; `None` for the current one.
+pub fn selected_season() -> Option<u32> {
+    *SELECTED_SEASON.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Use `season`'s data from now on; `None` or the current season goes
+/// back to the current data.
+pub fn select_season(season: Option<u32>) {
+    let current = load_season().ok().flatten();
+    let season = season.filter(|&s| Some(s) != current);
+    *SELECTED_SEASON.write().unwrap_or_else(|e| e.into_inner()) = season;
+    for kind in CACHED_PAGES { bump_generation(&kind); }
+}
+
+/// Seasons with data, newest first: the archived ones and the current.
+pub fn seasons() -> Vec<u32> {
+    let mut out: Vec<u32> = fs::read_dir(store_dir().join("seasons")).into_iter().flatten()
+        .filter_map(|e| e.ok()?.file_name().to_str()?.parse().ok())
+        .chain(load_season().ok().flatten())
+        .collect();
+    out.sort_unstable_by(|a, b| b.cmp(a));
+    out.dedup();
+    out
+}
+
+/// Copy the current season's cached pages (with their metadata) into
+/// `seasons/<season>/`, the team list aside and rows of other seasons left
+/// out (`keep_season`). Returns the pages copied.
+/// Only on request: GUI "Archive season", CLI `--archive-season`.
+pub fn archive_season(season: u32) -> Result<usize> {
+    let dir = season_dir(season);
+    let mut pages = 0;
+    for kind in CACHED_PAGES.into_iter().filter(|k| *k != Teams) {
+        let Ok(mut ds) = backend().load(&kind) else { continue };
+        keep_season(&mut ds, season);
+        if ds.rows.is_empty() { continue; }
+        write_table(&dir.join(page_filename(&kind)), &ds)?;
+        if let Some(meta) = CacheMeta::load_from(&store_dir().join(format!("{}.meta", page_filename(&kind)))) {
+            meta.save_to(&dir.join(format!("{}.meta", page_filename(&kind))))?;
+        }
+        pages += 1;
+    }
+    Ok(pages)
+}
+
+/// Drop the rows of other seasons from a page with a season column (`S`:
+/// Game Results, Injuries, Transactions), which keep rows across seasons.
+/// Rows without a readable season stay.
+fn keep_season(ds: &mut DataSet, season: u32) {
+    let Some(sc) = ds.header_index("S") else { return };
+    ds.rows.retain(|r| r.get(sc).and_then(|s| s.trim().parse::<u32>().ok()).is_none_or(|s| s == season));
+}
+
+// ---- GUI settings ----
+
+pub fn settings_path() -> PathBuf { store_dir().join("settings") }
+
+/// Load `.store/settings` (one `key=value` per line). Missing file = empty.
+pub fn load_settings() -> HashMap<String, String> {
+    read_kv(&settings_path())
+}
+
+/// Set one key in `.store/settings`, keeping the others.
+/// An empty value removes the key.
+pub fn save_setting(key: &str, value: &str) -> Result<PathBuf> {
+    save_setting_in(&store_dir(), key, value)
+}
+
+fn save_setting_in(dir: &Path, key: &str, value: &str) -> Result<PathBuf> {
+    if !dir.exists() { std::fs::create_dir_all(dir)?; }
+    let p = dir.join("settings");
+    let mut all: BTreeMap<String, String> = read_kv(&p).into_iter().collect();
+    if value.is_empty() { all.remove(key); } else { all.insert(key.to_string(), value.to_string()); }
+    let text: String = all.iter().map(|(k, v)| format!("{}={}\n", k, v)).collect();
+    std::fs::write(&p, text)?;
+    Ok(p)
+}
+
+/// Settings key for the staleness threshold, in hours.
+const STALE_AFTER_KEY: &str = "stale_after_hours";
+
+/// Pages scraped longer ago than this many hours are stale (tinted tab,
+/// `--cache-info`).
+pub fn stale_after_hours() -> u32 {
+    load_settings().get(STALE_AFTER_KEY)
+        .and_then(|v| v.trim().parse().ok())
+        .filter(|&h| h > 0)
+        .unwrap_or(DEFAULT_STALE_AFTER_HOURS)
+}
+
+pub fn save_stale_after_hours(hours: u32) -> Result<PathBuf> {
+    save_setting(STALE_AFTER_KEY, &hours.to_string())
+}
+
+/// Settings key for the players per stat on the Leaders page.
+const LEADERS_TOP_KEY: &str = "leaders_top";
+
+pub fn leaders_top() -> usize {
+    load_settings().get(LEADERS_TOP_KEY)
+        .and_then(|v| v.trim().parse().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(DEFAULT_LEADERS_TOP)
+}
+
+pub fn save_leaders_top(top: usize) -> Result<PathBuf> {
+    save_setting(LEADERS_TOP_KEY, &top.to_string())
+}
+
+/// Settings keys for the GUI's look.
+const THEME_KEY: &str = "theme";
+const DENSITY_KEY: &str = "table_density";
+const FONT_SIZE_KEY: &str = "font_size";
+
+/// Theme, table density and font size; defaults for what's unset or unreadable.
+pub fn appearance() -> Appearance {
+    let settings = load_settings();
+    let get = |key: &str| settings.get(key).map(|v| v.trim());
+    let default = Appearance::default();
+    Appearance {
+        theme: get(THEME_KEY).and_then(|v| v.parse().ok()).unwrap_or(default.theme),
+        density: get(DENSITY_KEY).and_then(|v| v.parse().ok()).unwrap_or(default.density),
+        font_size: get(FONT_SIZE_KEY).and_then(|v| v.parse().ok())
+            .filter(|s| FONT_SIZE_RANGE.contains(s))
+            .unwrap_or(default.font_size),
+    }
+}
+
+pub fn save_appearance(a: &Appearance) -> Result<PathBuf> {
+    save_setting(THEME_KEY, &a.theme.to_string())?;
+    save_setting(DENSITY_KEY, &a.density.to_string())?;
+    save_setting(FONT_SIZE_KEY, &a.font_size.to_string())
+}
+
+/// Settings keys for how the site is reached (see `NetSettings`).
+const NET_BASE_URL_KEY: &str = "net.base_url";
+const NET_TIMEOUT_KEY: &str = "net.timeout_secs";
+const NET_RETRIES_KEY: &str = "net.retries";
+const NET_DELAY_KEY: &str = "net.delay_ms";
+
+/// Site address, timeout, retries and delay; defaults for what's unset or
+/// unreadable.
+pub fn net_settings() -> NetSettings {
+    let settings = load_settings();
+    let get = |key: &str| settings.get(key).map(|v| v.trim()).filter(|v| !v.is_empty());
+    let default = NetSettings::default();
+    NetSettings {
+        base_url: get(NET_BASE_URL_KEY).map(String::from).unwrap_or(default.base_url),
+        timeout_secs: get(NET_TIMEOUT_KEY).and_then(|v| v.parse().ok()).filter(|&t| t > 0).unwrap_or(default.timeout_secs),
+        retries: get(NET_RETRIES_KEY).and_then(|v| v.parse().ok()).unwrap_or(default.retries),
+        delay_ms: get(NET_DELAY_KEY).and_then(|v| v.parse().ok()).unwrap_or(default.delay_ms),
+    }
+}
+
+/// Save `s`; values equal to the defaults are removed, so later default
+/// changes apply.
+pub fn save_net_settings(s: &NetSettings) -> Result<PathBuf> {
+    let d = NetSettings::default();
+    let keep = |v: String, default: String| if v == default { String::new() } else { v };
+    save_setting(NET_BASE_URL_KEY, &keep(s.base_url.trim().to_string(), d.base_url))?;
+    save_setting(NET_TIMEOUT_KEY, &keep(s.timeout_secs.to_string(), d.timeout_secs.to_string()))?;
+    save_setting(NET_RETRIES_KEY, &keep(s.retries.to_string(), d.retries.to_string()))?;
+    save_setting(NET_DELAY_KEY, &keep(s.delay_ms.to_string(), d.delay_ms.to_string()))
+}
+
+/// Settings key for the columns the user hid on a page.
+pub fn hidden_columns_key(kind: &PageKind) -> String {
+    format!("hidden_columns.{}", page_filename(kind))
+}
+
+/// Settings key prefix for named team selections: `team_preset.<name>=1,5,7`.
+const TEAM_PRESET_PREFIX: &str = "team_preset.";
+
+/// Saved team selections by name (GUI team panel, CLI `--teams-preset`).
+pub fn team_presets() -> BTreeMap<String, Vec<u32>> {
+    load_settings().into_iter()
+        .filter_map(|(k, v)| {
+            let name = k.strip_prefix(TEAM_PRESET_PREFIX)?.to_string();
+            let ids = v.split(',').filter_map(|id| id.trim().parse().ok()).collect();
+            Some((name, ids))
+        })
+        .collect()
+}
+
+/// Save `ids` as the preset `name`, replacing one of that name; no ids
+/// deletes it. Names are trimmed and can't contain `=` or line breaks.
+pub fn save_team_preset(name: &str, ids: &[u32]) -> Result<PathBuf> {
+    let name = name.trim();
+    if name.is_empty() || name.contains(['=', '\n', '\r']) {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("Invalid preset name: {:?}", name)));
+    }
+    let value: Vec<String> = ids.iter().map(u32::to_string).collect();
+    save_setting(&format!("{}{}", TEAM_PRESET_PREFIX, name), &value.join(","))
+}
+
+/// Settings key prefix for player name aliases: `alias.<normalized variant>=<canonical>`.
+const NAME_ALIAS_PREFIX: &str = "alias.";
+
+/// Saved player name aliases, normalized variant → canonical name (see `alias`).
+pub fn name_aliases() -> BTreeMap<String, String> {
+    load_settings().into_iter()
+        .filter_map(|(k, v)| Some((k.strip_prefix(NAME_ALIAS_PREFIX)?.to_string(), v.trim().to_string())))
+        .filter(|(k, v)| !k.is_empty() && !v.is_empty())
+        .collect()
+}
+
+/// Save `variant` as an alias of `canonical`; an empty `canonical` deletes it.
+/// The variant is stored normalized (`join::name_key`).
+pub fn save_name_alias(variant: &str, canonical: &str) -> Result<PathBuf> {
+    let key = crate::join::name_key(variant);
+    if key.is_empty() || canonical.contains(['\n', '\r']) {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("Invalid alias: {:?}", variant)));
+    }
+    save_setting(&format!("{}{}", NAME_ALIAS_PREFIX, key), canonical.trim())
+}
+
+/// Settings key prefix for export header names: `header_name.<page>.<header>=<name>`.
+const HEADER_NAME_PREFIX: &str = "header_name.";
+
+/// Header renames for exports, per page: canonical header → name written
+/// instead. The cache keeps the canonical headers.
+pub fn header_names() -> HashMap<PageKind, BTreeMap<String, String>> {
+    let mut out: HashMap<PageKind, BTreeMap<String, String>> = HashMap::new();
+    for (k, v) in load_settings() {
+        let Some((page, header)) = k.strip_prefix(HEADER_NAME_PREFIX).and_then(|r| r.split_once('.')) else { continue };
+        let Ok(kind) = page.parse::<PageKind>() else { continue };
+        if header.is_empty() || v.trim().is_empty() { continue; }
+        out.entry(kind).or_default().insert(header.to_string(), v.trim().to_string());
+    }
+    out
+}
+
+/// Export `header` of `kind` as `name`; an empty `name` (or the header
+/// itself) goes back to the canonical one.
+pub fn save_header_name(kind: &PageKind, header: &str, name: &str) -> Result<PathBuf> {
+    let name = name.trim();
+    if header.is_empty() || header.contains(['=', '\n', '\r']) || name.contains(['\n', '\r']) {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("Invalid header name: {:?}", name)));
+    }
+    let value = if name == header { "" } else { name };
+    save_setting(&format!("{}{}.{}", HEADER_NAME_PREFIX, page_filename(kind), header), value)
+}
+
+/// Settings key prefix for user columns: `user_column.<page>.<name>=<formula>`.
+const USER_COLUMN_PREFIX: &str = "user_column.";
+
+/// User columns per page: column name → formula (see `expr`), in name order.
+pub fn user_columns() -> HashMap<PageKind, BTreeMap<String, String>> {
+    let mut out: HashMap<PageKind, BTreeMap<String, String>> = HashMap::new();
+    for (k, v) in load_settings() {
+        let Some((page, name)) = k.strip_prefix(USER_COLUMN_PREFIX).and_then(|r| r.split_once('.')) else { continue };
+        let Ok(kind) = page.parse::<PageKind>() else { continue };
+        if name.trim().is_empty() || v.trim().is_empty() { continue; }
+        out.entry(kind).or_default().insert(name.trim().to_string(), v.trim().to_string());
+    }
+    out
+}
+
+/// Save the user column `name` of `kind`; an empty `formula` deletes it.
+pub fn save_user_column(kind: &PageKind, name: &str, formula: &str) -> Result<PathBuf> {
+    let name = name.trim();
+    if name.is_empty() || name.contains(['=', '\n', '\r']) || formula.contains(['\n', '\r']) {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("Invalid column name: {:?}", name)));
+    }
+    save_setting(&format!("{}{}.{}", USER_COLUMN_PREFIX, page_filename(kind), name), formula.trim())
+}
+
+/// Settings key prefix for a page's own export settings:
+/// `export_defaults.<page>.format|headers|dir=<value>`.
+const EXPORT_DEFAULTS_PREFIX: &str = "export_defaults.";
+
+/// Export settings pages keep for themselves (GUI; see `PageExportDefaults`).
+pub fn page_export_defaults() -> HashMap<PageKind, PageExportDefaults> {
+    let mut out: HashMap<PageKind, PageExportDefaults> = HashMap::new();
+    for (k, v) in load_settings() {
+        let Some((page, field)) = k.strip_prefix(EXPORT_DEFAULTS_PREFIX).and_then(|r| r.split_once('.')) else { continue };
+        let Ok(kind) = page.parse::<PageKind>() else { continue };
+        let d = out.entry(kind).or_default();
+        match field {
+            "format" => d.format = v.parse().ok(),
+            "headers" => d.include_headers = v.trim().parse().ok(),
+            "dir" => d.dir = Some(PathBuf::from(v.trim())).filter(|p| !p.as_os_str().is_empty()),
+            _ => {}
+        }
+    }
+    out.retain(|_, d| !d.is_empty());
+    out
+}
+
+/// Save `kind`'s own export settings; unset fields (all, to forget them)
+/// are removed.
+pub fn save_page_export_defaults(kind: &PageKind, d: &PageExportDefaults) -> Result<PathBuf> {
+    let key = |field: &str| format!("{}{}.{}", EXPORT_DEFAULTS_PREFIX, page_filename(kind), field);
+    save_setting(&key("format"), &d.format.map(|f| f.to_string()).unwrap_or_default())?;
+    save_setting(&key("headers"), &d.include_headers.map(|h| h.to_string()).unwrap_or_default())?;
+    let dir = d.dir.as_ref().map(|p| p.to_string_lossy().into_owned()).unwrap_or_default();
+    if dir.contains(['\n', '\r']) {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("Invalid folder: {:?}", dir)));
+    }
+    save_setting(&key("dir"), &dir)
+}
+
+// ---- Team logos ----
+
+/// Downloaded team logos, `<team id>.<ext>` (Team Info scrape with logos).
+pub fn logo_dir() -> PathBuf { store_dir().join("logos") }
+
+/// The downloaded logo of a team, if any.
+pub fn logo_file(team_id: u32) -> Option<PathBuf> {
+    let prefix = format!("{}.", team_id);
+    fs::read_dir(logo_dir()).ok()?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .find(|p| p.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with(&prefix)))
+}
+
 #[derive(Clone, Debug)]
 pub struct DataSet {
     pub headers: Option<Vec<String>>,
@@ -144,7 +1062,168 @@ impl DataSet {
         page.filter_rows_for_selection(selected_team_ids, teams, &self.rows)
     }
 
+    /// Fingerprint of headers + rows, for cheap "did anything change?" checks
+    /// within one process (not stable across Rust versions; don't persist it).
+    pub fn content_hash(&self) -> u64 {
+        use std::hash::{DefaultHasher, Hash, Hasher};
+        let mut h = DefaultHasher::new();
+        self.headers.hash(&mut h);
+        self.rows.hash(&mut h);
+        h.finish()
+    }
+
     /// Convenience counters.
     pub fn row_count(&self) -> usize { self.rows.len() }
     pub fn header_count(&self) -> usize { self.headers.as_ref().map(|h| h.len()).unwrap_or(0) }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Goes through the same codec as save_dataset/load_dataset (FileBackend),
+    // but in a temp dir so the real `.store/` is left alone.
+    fn round_trip(name: &str, ds: &DataSet) -> DataSet {
+        let path = std::env::temp_dir().join(format!("bb_scrape_store_{}", name));
+        write_table(&path, ds).unwrap();
+        let back = read_table(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        back
+    }
+
+    fn row(cells: &[&str]) -> Vec<String> {
+        cells.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn copies_the_store_into_an_unused_data_dir_only() {
+        let base = std::env::temp_dir().join(format!("bb_scrape_data_dir_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        let (from, to) = (base.join("old"), base.join("new"));
+        fs::create_dir_all(from.join("logos")).unwrap();
+        fs::write(from.join("players"), "x").unwrap();
+        fs::write(from.join("logos").join("3.png"), "png").unwrap();
+        fs::write(from.join(LOG_FILE_NAME), "log").unwrap();
+
+        assert_eq!(copy_store(&from, &to).unwrap(), 2);
+        assert_eq!(fs::read_to_string(to.join("logos").join("3.png")).unwrap(), "png");
+        assert!(!to.join(LOG_FILE_NAME).exists());
+        assert!(from.join("players").exists());
+
+        // Already in use: left alone
+        fs::write(from.join("teams"), "y").unwrap();
+        assert_eq!(copy_store(&from, &to).unwrap(), 0);
+        assert!(!to.join("teams").exists());
+
+        // Inside the store: refused, nothing created
+        let inside = from.join("data").join("new");
+        assert_eq!(copy_store(&from, &inside).unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+        assert!(!from.join("data").exists());
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn snapshots_never_overwrite_and_keep_the_newest() {
+        let dir = std::env::temp_dir().join(format!("bb_scrape_snapshots_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let ds = |v: &str| DataSet { headers: Some(row(&["Team", "Note"])), rows: vec![row(&["Reds", v])] };
+
+        let written: Vec<PathBuf> = ["a", "b", "c"].iter()
+            .map(|v| write_snapshot(&dir, &ds(v)).unwrap())
+            .collect();
+        assert_eq!(list_snapshots_in(&dir), written);
+
+        prune_snapshots(&dir, 2);
+        assert_eq!(list_snapshots_in(&dir), written[1..]);
+        let text = fs::read_to_string(&written[2]).unwrap();
+        assert_eq!(parse_rows(&text, ','), vec![row(&["Team", "Note"]), row(&["Reds", "c"])]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn round_trips_awkward_cells() {
+        let ds = DataSet {
+            headers: Some(row(&["Name", "Note", "Team"])),
+            rows: vec![
+                row(&["Line\nbreak", "CR\r\nLF", "Lone\rCR"]),
+                row(&["\"Quoted\"", "a \"b\" c", "\"\""]),
+                row(&["comma, inside", "tab\tinside", ",;,"]),
+                row(&["Åsa Ödegård", "ドラゴン", "🏈 emoji"]),
+                row(&["  padded  ", " ", "trailing "]),
+                row(&["", "", ""]),
+            ],
+        };
+        let back = round_trip("awkward", &ds);
+        assert_eq!((back.headers, back.rows), (ds.headers, ds.rows));
+    }
+
+    #[test]
+    fn keeps_rows_that_look_blank() {
+        let ds = DataSet {
+            headers: Some(row(&["Only"])),
+            rows: vec![row(&["a"]), row(&[""]), row(&["\n"]), row(&[""])],
+        };
+        let back = round_trip("blank", &ds);
+        assert_eq!((back.headers, back.rows), (ds.headers, ds.rows));
+    }
+
+    #[test]
+    fn cache_meta_keeps_the_scrape_time() {
+        let path = std::env::temp_dir().join(format!("bb_scrape_meta_{}", std::process::id()));
+        let at = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let meta = CacheMeta { scraped_at: Some(at), ..CacheMeta::current() };
+        meta.save_to(&path).unwrap();
+        assert_eq!(CacheMeta::load_from(&path), Some(meta));
+
+        // Written before scrape times were recorded
+        fs::write(&path, "app_version=0.1.0\nparser_version=2\n").unwrap();
+        assert_eq!(CacheMeta::load_from(&path).unwrap().scraped_at, None);
+        let _ = fs::remove_file(&path);
+
+        assert!(is_stale(at, 24));
+        assert!(!is_stale(SystemTime::now(), 24));
+    }
+
+    #[test]
+    fn team_scrapes_keep_the_last_success_of_failed_teams() {
+        let path = std::env::temp_dir().join(format!("bb_scrape_teams_{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let t1 = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let t2 = t1 + Duration::from_secs(3_600);
+
+        write_team_scrapes(&path, &[1, 2], &[3], t1).unwrap();
+        write_team_scrapes(&path, &[1], &[2], t2).unwrap();
+        let teams = read_team_scrapes(&path);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(teams[&1], TeamScrape { scraped_at: Some(t2), failed: false });
+        assert_eq!(teams[&2], TeamScrape { scraped_at: Some(t1), failed: true });
+        assert_eq!(teams[&3], TeamScrape { scraped_at: None, failed: true });
+        assert_eq!(teams.len(), 3);
+    }
+
+    #[test]
+    fn tolerates_crlf_bom_and_blank_lines_on_load() {
+        let path = std::env::temp_dir().join("bb_scrape_store_crlf");
+        fs::write(&path, "\u{feff}A,B\r\n1,\"x\r\ny\"\r\n\r\n2,z\r\n").unwrap();
+        let ds = read_table(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        assert_eq!(ds.headers, Some(row(&["A", "B"])));
+        assert_eq!(ds.rows, vec![row(&["1", "x\r\ny"]), row(&["2", "z"])]);
+    }
+
+    #[test]
+    fn season_archives_leave_out_other_seasons_rows() {
+        let mut ds = DataSet {
+            headers: Some(row(&["S", "W", "Home"])),
+            rows: vec![row(&["4", "13", "Reds"]), row(&["5", "1", "Reds"]), row(&["", "2", "Blues"])],
+        };
+        keep_season(&mut ds, 4);
+        assert_eq!(ds.rows, vec![row(&["4", "13", "Reds"]), row(&["", "2", "Blues"])]);
+
+        // Pages without a season column are kept whole
+        let mut players = DataSet { headers: Some(row(&["Name", "Team"])), rows: vec![row(&["Grim", "Reds"])] };
+        keep_season(&mut players, 4);
+        assert_eq!(players.rows.len(), 1);
+    }
+}
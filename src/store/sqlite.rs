@@ -0,0 +1,176 @@
+// src/store/sqlite.rs
+//
+// Optional SQLite backend (feature = "sqlite").
+// One table holds every page; each row is stored as a single encoded line
+// using the same codec as the flat files, so cells round-trip identically.
+// A second table records which pages exist, so a page saved with no rows
+// is still there. On first use of a page, an existing `.store/<page>` file
+// is migrated in, once.
+
+use std::{
+    fs,
+    io::{ Error, ErrorKind, Result },
+    path::PathBuf,
+    sync::{ Mutex, MutexGuard },
+};
+
+use rusqlite::{ params, Connection, OptionalExtension };
+
+use crate::config::consts::STORE_SEP;
+use crate::config::options::PageKind;
+use crate::file::{ parse_rows, write_row };
+use super::{ Backend, DataSet, FileBackend, page_filename };
+
+pub struct SqliteBackend {
+    path: PathBuf,
+    conn: Mutex<Connection>,
+}
+
+fn db_err(e: rusqlite::Error) -> Error {
+    Error::other(e)
+}
+
+fn encode(row: &[String]) -> Result<String> {
+    let mut buf: Vec<u8> = Vec::new();
+    write_row(&mut buf, row, STORE_SEP)?;
+    String::from_utf8(buf).map_err(Error::other)
+}
+
+impl SqliteBackend {
+    pub fn open(path: PathBuf) -> Result<Self> {
+        if let Some(dir) = path.parent() {
+            if !dir.exists() { fs::create_dir_all(dir)?; }
+        }
+
+        let conn = Connection::open(&path).map_err(db_err)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS rows (
+                page      TEXT    NOT NULL,
+                ix        INTEGER NOT NULL,
+                is_header INTEGER NOT NULL,
+                line      TEXT    NOT NULL,
+                PRIMARY KEY (page, ix)
+            );
+            CREATE TABLE IF NOT EXISTS pages (
+                page  TEXT    PRIMARY KEY,
+                saved INTEGER NOT NULL
+            );
+            INSERT OR IGNORE INTO pages (page, saved) SELECT DISTINCT page, 1 FROM rows;"
+        ).map_err(db_err)?;
+
+        logf!("Store: SQLite backend at {}", path.display());
+        Ok(Self { path, conn: Mutex::new(conn) })
+    }
+
+    fn conn(&self) -> Result<MutexGuard<'_, Connection>> {
+        self.conn.lock().map_err(|_| Error::other("store database lock poisoned"))
+    }
+
+    /// The page's entry in `pages`: None if it was never looked at, else
+    /// whether it was ever saved (false: migration found no flat file).
+    fn page_state(&self, kind: &PageKind) -> Result<Option<bool>> {
+        self.conn()?
+            .query_row(
+                "SELECT saved FROM pages WHERE page = ?1",
+                params![page_filename(kind)],
+                |r| r.get::<_, i64>(0),
+            )
+            .optional()
+            .map(|saved| saved.map(|s| s != 0))
+            .map_err(db_err)
+    }
+
+    /// Import the flat-file cache for a page the first time it is used;
+    /// afterwards the database is the only source, even for an empty page.
+    /// True when the database has data for the page (migrated now or saved
+    /// before); false when it was never saved and there was no file to take.
+    fn migrate_from_file(&self, kind: &PageKind) -> Result<bool> {
+        if let Some(saved) = self.page_state(kind)? { return Ok(saved); }
+        if let Ok(ds) = FileBackend.load(kind) {
+            logf!("Store: Migrating {:?} from flat file into SQLite ({} rows)", kind, ds.row_count());
+            self.save(kind, &ds)?;
+            return Ok(true);
+        }
+        self.conn()?
+            .execute("INSERT OR IGNORE INTO pages (page, saved) VALUES (?1, 0)", params![page_filename(kind)])
+            .map_err(db_err)?;
+        Ok(false)
+    }
+}
+
+impl Backend for SqliteBackend {
+    fn load(&self, kind: &PageKind) -> Result<DataSet> {
+        if !self.migrate_from_file(kind)? {
+            return Err(Error::new(ErrorKind::NotFound, format!("no {:?} data in store", kind)));
+        }
+
+        let conn = self.conn()?;
+        let mut stmt = conn
+            .prepare("SELECT is_header, line FROM rows WHERE page = ?1 ORDER BY ix")
+            .map_err(db_err)?;
+        let lines = stmt
+            .query_map(params![page_filename(kind)], |r| {
+                Ok((r.get::<_, i64>(0)? != 0, r.get::<_, String>(1)?))
+            })
+            .map_err(db_err)?;
+
+        let mut headers = None;
+        let mut rows = Vec::new();
+        for line in lines {
+            let (is_header, line) = line.map_err(db_err)?;
+            let row = parse_rows(&line, STORE_SEP).into_iter().next().unwrap_or_default();
+            if is_header { headers = Some(row); } else { rows.push(row); }
+        }
+        Ok(DataSet { headers, rows })
+    }
+
+    fn save(&self, kind: &PageKind, ds: &DataSet) -> Result<PathBuf> {
+        let page = page_filename(kind);
+        let mut conn = self.conn()?;
+        let tx = conn.transaction().map_err(db_err)?;
+
+        tx.execute("DELETE FROM rows WHERE page = ?1", params![page]).map_err(db_err)?;
+        tx.execute("INSERT OR REPLACE INTO pages (page, saved) VALUES (?1, 1)", params![page]).map_err(db_err)?;
+        {
+            let mut stmt = tx
+                .prepare("INSERT INTO rows (page, ix, is_header, line) VALUES (?1, ?2, ?3, ?4)")
+                .map_err(db_err)?;
+            let mut ix: i64 = 0;
+            if let Some(h) = &ds.headers {
+                stmt.execute(params![page, ix, 1, encode(h)?]).map_err(db_err)?;
+                ix += 1;
+            }
+            for r in &ds.rows {
+                stmt.execute(params![page, ix, 0, encode(r)?]).map_err(db_err)?;
+                ix += 1;
+            }
+        }
+        tx.commit().map_err(db_err)?;
+
+        Ok(self.path.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_page_saved_empty_stays_empty() {
+        let path = std::env::temp_dir().join(format!("bb_scrape_sqlite_{}.db", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let db = SqliteBackend::open(path.clone()).unwrap();
+        let headers = Some(vec!["Team".to_string(), "Id".to_string()]);
+
+        db.save(&PageKind::Teams, &DataSet { headers: headers.clone(), rows: vec![] }).unwrap();
+        let back = db.load(&PageKind::Teams).unwrap();
+        assert_eq!(back.headers, headers);
+        assert!(back.rows.is_empty());
+
+        // Still there after reopening (no second migration)
+        drop(db);
+        let db = SqliteBackend::open(path.clone()).unwrap();
+        assert!(db.load(&PageKind::Teams).unwrap().rows.is_empty());
+        let _ = fs::remove_file(&path);
+    }
+}
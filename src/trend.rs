@@ -0,0 +1,122 @@
+// src/trend.rs
+//
+// A player's stat over time, from the Players snapshot archive
+// (`store::load_snapshots`): one point per snapshot the player is in.
+// Snapshots have to be on (`snapshots=<n>` in `.store/settings`).
+
+use crate::aggregate::players_stat_columns;
+use crate::config::options::PageKind;
+use crate::scrape::{roster_key, PLAYER_ID_HEADER};
+use crate::store::DataSet;
+use crate::typed::TypedColumns;
+
+/// How a player is found again in older data: by player id when the data
+/// has one (it survives renames and trades), else by team, number and name
+/// (see `scrape::roster_key`).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PlayerKey {
+    Id(String),
+    Roster { team: String, number: String, name: String },
+}
+
+impl PlayerKey {
+    /// The key of `row` of `players`.
+    pub fn of(players: &DataSet, row: &[String]) -> Self {
+        let id = players.header_index(PLAYER_ID_HEADER)
+            .and_then(|c| row.get(c))
+            .map(|id| id.trim())
+            .filter(|id| !id.is_empty());
+        match id {
+            Some(id) => PlayerKey::Id(id.to_string()),
+            None => {
+                let (team, number, name) = roster_key(row);
+                PlayerKey::Roster { team: team.to_string(), number: number.to_string(), name: name.to_string() }
+            }
+        }
+    }
+
+    /// The player's row in `players`, if they're in it.
+    pub fn find<'a>(&self, players: &'a DataSet) -> Option<&'a Vec<String>> {
+        match self {
+            PlayerKey::Id(id) => {
+                let c = players.header_index(PLAYER_ID_HEADER)?;
+                players.rows.iter().find(|r| r.get(c).is_some_and(|v| v.trim() == id))
+            }
+            PlayerKey::Roster { team, number, name } => players.rows.iter()
+                .find(|r| roster_key(r) == (team.as_str(), number.as_str(), name.as_str())),
+        }
+    }
+}
+
+/// Headers of the stats a player can be charted by (the numeric Players
+/// columns, as compared and ranked elsewhere).
+pub fn stat_names(players: &DataSet) -> Vec<String> {
+    let Some(headers) = players.headers.as_ref() else { return Vec::new() };
+    let typed = TypedColumns::for_page(PageKind::Players, players);
+    players_stat_columns(players, &typed).into_iter().filter_map(|c| headers.get(c).cloned()).collect()
+}
+
+/// The player's `stat` in each snapshot they're in with a value, oldest
+/// first, as (unix seconds, value).
+pub fn series(snapshots: &[(u64, DataSet)], player: &PlayerKey, stat: &str) -> Vec<(u64, f64)> {
+    snapshots.iter().filter_map(|(at, ds)| {
+        let c = ds.header_index(stat)?;
+        let cell = player.find(ds)?.get(c)?;
+        let x = cell.trim().trim_start_matches('#').parse().ok()?;
+        Some((*at, x))
+    }).collect()
+}
+
+/// "2026-10-16" (UTC) for unix seconds.
+pub fn fmt_date(secs: u64) -> String {
+    // Days to civil date (proleptic Gregorian), after Howard Hinnant
+    let z = (secs / 86_400) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ds(headers: &[&str], rows: &[&[&str]]) -> DataSet {
+        DataSet {
+            headers: Some(headers.iter().map(|h| s!(*h)).collect()),
+            rows: rows.iter().map(|r| r.iter().map(|c| s!(*c)).collect()).collect(),
+        }
+    }
+
+    #[test]
+    fn series_follows_the_player_id_across_snapshots() {
+        let h = ["Name", "#", "Race", "Team", "TV", "Player id"];
+        let snapshots = vec![
+            (100, ds(&h, &[&["Grim", "#7", "Orc", "Reds", "90", "11"]])),
+            (200, ds(&h, &[&["Al", "#2", "Elf", "Reds", "50", "12"]])),
+            // Traded and renamed: still player 11
+            (300, ds(&h, &[&["Grim Tooth", "#1", "Orc", "Blues", "110", "11"]])),
+            (400, ds(&["Name", "#", "Race", "Team", "TV"], &[&["Grim", "#7", "Orc", "Reds", "120"]])),
+        ];
+        let now = &snapshots[0].1;
+        let key = PlayerKey::of(now, &now.rows[0]);
+        assert_eq!(key, PlayerKey::Id(s!("11")));
+        assert_eq!(series(&snapshots, &key, "TV"), vec![(100, 90.0), (300, 110.0)]);
+
+        let by_roster = PlayerKey::Roster { team: s!("Reds"), number: s!("7"), name: s!("Grim") };
+        assert_eq!(series(&snapshots, &by_roster, "TV"), vec![(100, 90.0), (400, 120.0)]);
+        assert_eq!(stat_names(now), vec!["TV"]);
+    }
+
+    #[test]
+    fn dates_from_unix_seconds() {
+        assert_eq!(fmt_date(0), "1970-01-01");
+        assert_eq!(fmt_date(951_782_400), "2000-02-29");
+        assert_eq!(fmt_date(1_791_331_200), "2026-10-07");
+    }
+}
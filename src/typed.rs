@@ -0,0 +1,140 @@
+// src/typed.rs
+//
+// Parsed view of a dataset's columns. Cells stay strings in DataSet (the
+// cache and exports use them as they are); this layer parses each numeric
+// column once, typed by the page schema, so sorting, aggregation and
+// validation don't re-parse the same cells on every use.
+
+use crate::config::options::PageKind;
+use crate::schema::{self, ColumnType};
+use crate::store::DataSet;
+
+/// One parsed column. Blank or unparsable cells are `None`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypedColumn {
+    U32(Vec<Option<u32>>),
+    F32(Vec<Option<f32>>),
+    Text,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TypedColumns {
+    cols: Vec<TypedColumn>,
+}
+
+/// Number part of a cell: trimmed, a leading '#' ignored (e.g. "#27").
+fn clean(s: &str) -> &str {
+    s.trim().trim_start_matches('#')
+}
+
+impl TypedColumns {
+    /// Parse `ds` with one type per column; columns past `types` are text.
+    pub fn new(ds: &DataSet, types: &[ColumnType]) -> Self {
+        fn cell(r: &[String], ci: usize) -> &str {
+            r.get(ci).map(|s| clean(s)).unwrap_or("")
+        }
+        let cols = types.iter().enumerate().map(|(ci, ty)| match ty {
+            ColumnType::Integer | ColumnType::Id =>
+                TypedColumn::U32(ds.rows.iter().map(|r| cell(r, ci).parse().ok()).collect()),
+            ColumnType::Decimal =>
+                TypedColumn::F32(ds.rows.iter().map(|r| cell(r, ci).parse().ok()).collect()),
+            ColumnType::Text => TypedColumn::Text,
+        }).collect();
+        Self { cols }
+    }
+
+    /// Types from the page schema; columns it doesn't declare are inferred.
+    pub fn for_page(kind: PageKind, ds: &DataSet) -> Self {
+        Self::new(ds, &schema::column_types(kind, ds.headers.as_deref(), &ds.rows))
+    }
+
+    /// Every column's type inferred from its cells (datasets without a schema).
+    pub fn inferred(ds: &DataSet) -> Self {
+        let cols = ds.headers.as_ref().map(|h| h.len())
+            .or_else(|| ds.rows.iter().map(|r| r.len()).max())
+            .unwrap_or(0);
+        let types: Vec<ColumnType> = (0..cols)
+            .map(|ci| ColumnType::infer(ds.rows.iter().filter_map(|r| r.get(ci)).map(String::as_str)))
+            .collect();
+        Self::new(ds, &types)
+    }
+
+    pub fn column(&self, col: usize) -> Option<&TypedColumn> { self.cols.get(col) }
+
+    pub fn is_numeric(&self, col: usize) -> bool {
+        matches!(self.cols.get(col), Some(TypedColumn::U32(_) | TypedColumn::F32(_)))
+    }
+
+    /// Numeric columns, in order.
+    pub fn numeric_columns(&self) -> Vec<usize> {
+        (0..self.cols.len()).filter(|&c| self.is_numeric(c)).collect()
+    }
+
+    /// Cell as a number; `None` for text columns and blank/unparsable cells.
+    pub fn number(&self, row: usize, col: usize) -> Option<f64> {
+        match self.cols.get(col)? {
+            TypedColumn::U32(v) => v.get(row).copied().flatten().map(f64::from),
+            TypedColumn::F32(v) => v.get(row).copied().flatten().map(f64::from),
+            TypedColumn::Text => None,
+        }
+    }
+
+    /// Cell of a whole-number column.
+    pub fn u32_at(&self, row: usize, col: usize) -> Option<u32> {
+        match self.cols.get(col)? {
+            TypedColumn::U32(v) => v.get(row).copied().flatten(),
+            _ => None,
+        }
+    }
+
+    /// Filled cells of numeric columns that aren't numbers of the column's
+    /// type, as (row, column). Blank cells are fine.
+    pub fn invalid_cells(&self, ds: &DataSet) -> Vec<(usize, usize)> {
+        let mut out = Vec::new();
+        for (ri, r) in ds.rows.iter().enumerate() {
+            for ci in self.numeric_columns() {
+                let filled = r.get(ci).is_some_and(|s| !clean(s).is_empty());
+                if filled && self.number(ri, ci).is_none() {
+                    out.push((ri, ci));
+                }
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ds(headers: &[&str], rows: &[&[&str]]) -> DataSet {
+        DataSet {
+            headers: Some(headers.iter().map(|h| s!(*h)).collect()),
+            rows: rows.iter().map(|r| r.iter().map(|c| s!(*c)).collect()).collect(),
+        }
+    }
+
+    #[test]
+    fn schema_types_with_site_columns_inferred() {
+        let d = ds(&["Name", "#", "Race", "Team", "TV", "Note", "Player id"], &[
+            &["Al", "#1", "Orc", "Reds", "100", "x", "7"],
+            &["Bo", "#2", "Elf", "Reds", "82.5", "", ""],
+        ]);
+        let t = TypedColumns::for_page(PageKind::Players, &d);
+        // "#" is declared text; TV is a site column with decimals
+        assert_eq!(t.numeric_columns(), vec![4, 6]);
+        assert_eq!(t.number(1, 4), Some(82.5));
+        assert_eq!(t.u32_at(0, 6), Some(7));
+        assert_eq!(t.u32_at(1, 6), None);
+        assert_eq!(t.number(0, 1), None);
+        assert!(t.invalid_cells(&d).is_empty());
+    }
+
+    #[test]
+    fn declared_integers_flag_bad_cells() {
+        let d = ds(&["Team", "Players"], &[&["Reds", "11"], &["Blues", "x"], &["Greens", ""]]);
+        let t = TypedColumns::for_page(PageKind::TeamSummary, &d);
+        assert_eq!(t.column(1), Some(&TypedColumn::U32(vec![Some(11), None, None])));
+        assert_eq!(t.invalid_cells(&d), vec![(1, 1)]);
+    }
+}
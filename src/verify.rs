@@ -0,0 +1,135 @@
+// src/verify.rs
+//
+// Spot check of the cache against the live site (`cli verify --sample N`).
+// A few cached rows per page are looked up again in a fresh fetch of just
+// the source pages they came from; rows that are gone or differ are
+// reported. Cheap compared to a full re-scrape, and enough to notice a
+// corrupted cache or a parser that silently drifted.
+//
+// Differences aren't necessarily errors: the site moves on between scrapes
+// (games get played, players gain SPP). Read the report with the cache age
+// in mind.
+
+use crate::diff::DataDiff;
+use crate::store::DataSet;
+
+/// Small seeded PRNG (xorshift64*) for picking sample rows; no crate needed.
+pub struct Sampler(u64);
+
+impl Sampler {
+    pub fn new(seed: u64) -> Self {
+        // xorshift gets stuck on 0
+        Sampler((seed ^ 0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    /// Seed from the clock, for a different sample each run.
+    pub fn from_clock() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        Self::new(nanos)
+    }
+
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.0 = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// `n` distinct indices below `len`, ascending (all of them if `n >= len`).
+    pub fn pick(&mut self, len: usize, n: usize) -> Vec<usize> {
+        if n >= len { return (0..len).collect(); }
+        // Partial Fisher–Yates over 0..len
+        let mut ix: Vec<usize> = (0..len).collect();
+        for i in 0..n {
+            let j = i + (self.next() % (len - i) as u64) as usize;
+            ix.swap(i, j);
+        }
+        ix.truncate(n);
+        ix.sort_unstable();
+        ix
+    }
+}
+
+/// Outcome of one page's spot check.
+#[derive(Clone, Debug)]
+pub struct SampleCheck {
+    /// Rows looked up
+    pub sampled: usize,
+    /// `removed`: sampled rows not found on the site; `changed`: found but
+    /// different. `added` is always empty (unsampled rows aren't compared).
+    pub diff: DataDiff,
+}
+
+impl SampleCheck {
+    pub fn is_ok(&self) -> bool { self.diff.is_empty() }
+
+    pub fn mismatches(&self) -> usize {
+        self.diff.removed.len() + self.diff.changed.len()
+    }
+
+    /// e.g. "5 sampled, all match" / "5 sampled, 1 missing, 2 changed".
+    pub fn summary(&self) -> String {
+        if self.is_ok() {
+            return format!("{} sampled, all match", self.sampled);
+        }
+        format!("{} sampled, {} missing, {} changed",
+            self.sampled, self.diff.removed.len(), self.diff.changed.len())
+    }
+}
+
+/// Compare the `sample` rows of `cached` against a fresh fetch, matching
+/// rows on `key_cols` (empty = whole row, so any change shows as missing).
+pub fn check_sample(cached: &DataSet, fresh: &DataSet, sample: &[usize], key_cols: &[usize]) -> SampleCheck {
+    let picked = DataSet {
+        headers: cached.headers.clone(),
+        rows: sample.iter().filter_map(|&i| cached.rows.get(i).cloned()).collect(),
+    };
+    let mut diff = DataDiff::between(&picked, fresh, key_cols);
+    diff.added.clear();
+    SampleCheck { sampled: picked.rows.len(), diff }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ds(rows: &[[&str; 3]]) -> DataSet {
+        DataSet {
+            headers: Some(vec![s!("Name"), s!("Team"), s!("SPP")]),
+            rows: rows.iter().map(|r| r.iter().map(|c| c.to_string()).collect()).collect(),
+        }
+    }
+
+    #[test]
+    fn pick_is_distinct_sorted_and_seeded() {
+        let a = Sampler::new(7).pick(100, 10);
+        assert_eq!(a.len(), 10);
+        assert!(a.windows(2).all(|w| w[0] < w[1]));
+        assert!(a.iter().all(|&i| i < 100));
+        assert_eq!(a, Sampler::new(7).pick(100, 10));
+        assert_eq!(Sampler::new(0).pick(3, 5), vec![0, 1, 2]);
+        assert!(Sampler::new(0).pick(0, 5).is_empty());
+    }
+
+    #[test]
+    fn reports_missing_and_changed_sampled_rows_only() {
+        let cached = ds(&[["Al", "Reds", "10"], ["Bo", "Reds", "4"], ["Cy", "Blues", "7"]]);
+        let fresh = ds(&[["Al", "Reds", "12"], ["Cy", "Blues", "7"], ["Di", "Blues", "1"]]);
+
+        let all = check_sample(&cached, &fresh, &[0, 1, 2], &[0, 1]);
+        assert_eq!(all.sampled, 3);
+        assert_eq!(all.diff.removed.len(), 1); // Bo
+        assert_eq!(all.diff.changed.len(), 1); // Al's SPP
+        assert!(all.diff.added.is_empty()); // Di isn't in the sample
+        assert_eq!(all.summary(), "3 sampled, 1 missing, 1 changed");
+
+        let ok = check_sample(&cached, &fresh, &[2], &[0, 1]);
+        assert!(ok.is_ok());
+        assert_eq!(ok.summary(), "1 sampled, all match");
+    }
+}
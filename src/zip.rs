@@ -0,0 +1,151 @@
+// src/zip.rs
+//
+// Minimal zip writer for export archives (no dependency). Entries are
+// stored, not deflated: exports are small text files and the point is one
+// artifact to share, not size. Names are UTF-8 with `/` separators.
+
+use std::{
+    io,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// CRC-32 (IEEE) lookup table, built at compile time.
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut c = i as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 { 0xEDB8_8320 ^ (c >> 1) } else { c >> 1 };
+            k += 1;
+        }
+        table[i] = c;
+        i += 1;
+    }
+    table
+};
+
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut c = !0u32;
+    for &b in bytes {
+        c = CRC_TABLE[((c ^ b as u32) & 0xFF) as usize] ^ (c >> 8);
+    }
+    !c
+}
+
+/// The whole archive for `entries` (name, contents), as bytes.
+pub fn archive(entries: &[(String, Vec<u8>)]) -> io::Result<Vec<u8>> {
+    let too_big = || io::Error::new(io::ErrorKind::InvalidInput, "zip: over 4 GiB or 65535 entries");
+    let (time, date) = dos_time(SystemTime::now());
+    let mut out = Vec::new();
+    let mut central = Vec::new();
+
+    for (name, data) in entries {
+        let offset = u32::try_from(out.len()).map_err(|_| too_big())?;
+        let size = u32::try_from(data.len()).map_err(|_| too_big())?;
+        let name_len = u16::try_from(name.len()).map_err(|_| too_big())?;
+        let crc = crc32(data);
+
+        // Shared by the local and the central header, from "version needed" on
+        let mut common = Vec::with_capacity(26);
+        common.extend(20u16.to_le_bytes());     // version needed (2.0)
+        common.extend(0x0800u16.to_le_bytes()); // flags: UTF-8 names
+        common.extend(0u16.to_le_bytes());      // method: stored
+        common.extend(time.to_le_bytes());
+        common.extend(date.to_le_bytes());
+        common.extend(crc.to_le_bytes());
+        common.extend(size.to_le_bytes());      // compressed
+        common.extend(size.to_le_bytes());      // uncompressed
+        common.extend(name_len.to_le_bytes());
+        common.extend(0u16.to_le_bytes());      // extra field length
+
+        out.extend(0x0403_4b50u32.to_le_bytes());
+        out.extend(&common);
+        out.extend(name.as_bytes());
+        out.extend(data);
+
+        central.extend(0x0201_4b50u32.to_le_bytes());
+        central.extend(20u16.to_le_bytes());    // version made by
+        central.extend(&common);
+        central.extend(0u16.to_le_bytes());     // comment length
+        central.extend(0u16.to_le_bytes());     // disk number
+        central.extend(0u16.to_le_bytes());     // internal attributes
+        central.extend(0u32.to_le_bytes());     // external attributes
+        central.extend(offset.to_le_bytes());
+        central.extend(name.as_bytes());
+    }
+
+    let count = u16::try_from(entries.len()).map_err(|_| too_big())?;
+    let cd_offset = u32::try_from(out.len()).map_err(|_| too_big())?;
+    let cd_size = u32::try_from(central.len()).map_err(|_| too_big())?;
+    out.extend(central);
+    out.extend(0x0605_4b50u32.to_le_bytes());
+    out.extend(0u16.to_le_bytes());             // this disk
+    out.extend(0u16.to_le_bytes());             // disk with the central directory
+    out.extend(count.to_le_bytes());
+    out.extend(count.to_le_bytes());
+    out.extend(cd_size.to_le_bytes());
+    out.extend(cd_offset.to_le_bytes());
+    out.extend(0u16.to_le_bytes());             // comment length
+    Ok(out)
+}
+
+/// Entry name of `path` inside an archive rooted at `base`.
+pub fn entry_name(base: &Path, path: &Path) -> String {
+    let rel = path.strip_prefix(base).unwrap_or(path);
+    rel.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// MS-DOS (time, date) of `t` in UTC; 1980 is the earliest it can hold.
+fn dos_time(t: SystemTime) -> (u16, u16) {
+    let secs = t.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    if year < 1980 {
+        return (0, (1 << 5) | 1);
+    }
+    let time = ((rem / 3_600) << 11) | (((rem % 3_600) / 60) << 5) | ((rem % 60) / 2);
+    let date = (((year - 1980) as u16) << 9) | ((month as u16) << 5) | day as u16;
+    (time as u16, date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn stored_archive_layout() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+
+        let zip = archive(&[(s!("a/b.tsv"), b"x\ty\n".to_vec()), (s!("c.tsv"), Vec::new())]).unwrap();
+        assert_eq!(&zip[..4], b"PK\x03\x04");
+        assert_eq!(&zip[30..37], b"a/b.tsv");
+        assert_eq!(&zip[37..41], b"x\ty\n");
+        // End of central directory: two entries
+        let eocd = zip.len() - 22;
+        assert_eq!(&zip[eocd..eocd + 4], b"PK\x05\x06");
+        assert_eq!(u16::from_le_bytes([zip[eocd + 10], zip[eocd + 11]]), 2);
+
+        // 2024-02-29 13:45:30 UTC
+        let t = UNIX_EPOCH + Duration::from_secs(1_709_214_330);
+        assert_eq!(dos_time(t), ((13 << 11) | (45 << 5) | 15, (44 << 9) | (2 << 5) | 29));
+        assert_eq!(entry_name(Path::new("out"), Path::new("out/players/Alpha.tsv")), "players/Alpha.tsv");
+    }
+}
@@ -0,0 +1,72 @@
+// tests/diff_report.rs
+//
+// DataDiff: keyed matching of cached vs freshly scraped rows.
+//
+use bb_scrape::diff::DataDiff;
+use bb_scrape::store::DataSet;
+
+fn s(v: &[&str]) -> Vec<String> { v.iter().map(|x| x.to_string()).collect() }
+
+fn players(rows: &[&[&str]]) -> DataSet {
+    DataSet {
+        headers: Some(s(&["Name", "Number", "Race", "Team", "SPP"])),
+        rows: rows.iter().map(|r| s(r)).collect(),
+    }
+}
+
+#[test]
+fn keyed_diff_reports_added_removed_changed() {
+    let old = players(&[
+        &["Alice", "#7", "Orc", "Alpha", "10"],
+        &["Bob",   "#9", "Elf", "Alpha", "4"],
+    ]);
+    let new = players(&[
+        &["Alice", "#7", "Orc", "Alpha", "15"],
+        &["Cleo",  "#1", "Orc", "Beta",  "0"],
+    ]);
+
+    let d = DataDiff::between(&old, &new, &[0, 1, 3]);
+    assert_eq!(d.summary(), "1 added, 1 removed, 1 changed");
+    assert_eq!(d.added, vec![s(&["Cleo", "#1", "Orc", "Beta", "0"])]);
+    assert_eq!(d.removed, vec![s(&["Bob", "#9", "Elf", "Alpha", "4"])]);
+    assert_eq!(d.changed[0].cells, vec![(4, "10".to_string(), "15".to_string())]);
+
+    let lines = d.report_lines();
+    assert_eq!(lines, vec![
+        "+ Cleo / #1 / Beta",
+        "- Bob / #9 / Alpha",
+        "~ Alice / #7 / Alpha: SPP 10 → 15",
+    ]);
+}
+
+#[test]
+fn identical_data_is_empty_and_unkeyed_has_no_changes() {
+    let a = players(&[&["Alice", "#7", "Orc", "Alpha", "10"]]);
+    assert!(DataDiff::between(&a, &a.clone(), &[0, 1, 3]).is_empty());
+
+    // Without key columns a changed cell means remove + add
+    let b = players(&[&["Alice", "#7", "Orc", "Alpha", "11"]]);
+    let d = DataDiff::between(&a, &b, &[]);
+    assert_eq!((d.added.len(), d.removed.len(), d.changed.len()), (1, 1, 0));
+}
+
+#[test]
+fn player_id_key_survives_renames() {
+    use bb_scrape::gui::pages::{players, row_key_between};
+
+    let with_id = |rows: &[&[&str]]| DataSet {
+        headers: Some(s(&["Name", "#", "Race", "Team", "SPP", "Player id"])),
+        rows: rows.iter().map(|r| s(r)).collect(),
+    };
+    let old = with_id(&[&["Alice", "#7", "Orc", "Alpha", "10", "501"]]);
+    let new = with_id(&[&["Alicia", "#7", "Orc", "Alpha", "12", "501"]]);
+
+    let key = row_key_between(&players::PAGE, &old, &new);
+    assert_eq!(key, vec![5]);
+    let d = DataDiff::between(&old, &new, &key);
+    assert_eq!(d.summary(), "0 added, 0 removed, 1 changed");
+
+    // Old cache without ids → falls back to name/number/team
+    let legacy = players(&[&["Alice", "#7", "Orc", "Alpha", "10"]]);
+    assert_eq!(row_key_between(&players::PAGE, &legacy, &new), vec![0, 1, 3]);
+}
@@ -3,7 +3,8 @@ use std::fs;
 use std::path::PathBuf;
 
 use bb_scrape::config::options::{AppOptions, ExportFormat, ExportType, PageKind};
-use bb_scrape::file::export_dataset;
+use bb_scrape::file::{export_dataset, export_dataset_with};
+use bb_scrape::get_teams::Divisions;
 
 fn tmp_dir(name: &str) -> PathBuf {
     let mut p = std::env::temp_dir();
@@ -85,3 +86,259 @@ fn players_skip_optional_strips_hash() {
     assert!(s.contains(",27,")); // no '#'
 }
 
+
+#[test]
+fn per_team_continues_past_failed_file() {
+    use bb_scrape::error::Error;
+
+    let mut opts = AppOptions::default();
+    opts.export.export_type = ExportType::PerTeam;
+    opts.export.format = ExportFormat::Csv;
+    let dir = tmp_dir("per_team_partial");
+    opts.export.set_path(dir.to_str().unwrap());
+
+    // A directory squatting on Alpha's file name makes that one write fail
+    fs::create_dir_all(dir.join("Alpha.csv")).unwrap();
+
+    let headers = Some(vec!["Name".into(), "#".into(), "Race".into(), "Team".into()]);
+    let rows = vec![
+        vec!["A".into(), "#1".into(), "Elf".into(), "Alpha".into()],
+        vec!["B".into(), "#2".into(), "Orc".into(), "Beta".into()],
+    ];
+
+    let err = export_dataset(&opts, PageKind::Players, &headers, &rows).unwrap_err();
+    let Error::PartialExport(partial) = &err else { panic!("PartialExport: {err}") };
+    assert_eq!(partial.written.len(), 1);
+    assert!(partial.written[0].ends_with("Beta.csv"));
+    assert_eq!(partial.failed.len(), 1);
+    assert!(partial.failed[0].0.ends_with("Alpha.csv"));
+    assert!(err.to_string().contains("1 failed"));
+}
+
+#[test]
+fn per_team_manifest_lists_files_with_row_counts() {
+    let mut opts = AppOptions::default();
+    opts.export.export_type = ExportType::PerTeam;
+    opts.export.format = ExportFormat::Csv;
+    opts.export.write_manifest = true;
+    let dir = tmp_dir("manifest");
+    opts.export.set_path(dir.to_str().unwrap());
+
+    let headers = Some(vec!["Name".into(), "#Number".into(), "Race".into(), "Team".into()]);
+    let rows = vec![
+        vec!["A".into(), "#7".into(), "Elf".into(), "Alpha".into()],
+        vec!["B".into(), "#8".into(), "Orc".into(), "Alpha".into()],
+        vec!["C".into(), "#9".into(), "Orc".into(), "Beta".into()],
+    ];
+    let written = export_dataset(&opts, PageKind::Players, &headers, &rows).unwrap();
+    assert_eq!(written.len(), 2);
+
+    let json = fs::read_to_string(dir.join("manifest.json")).unwrap();
+    assert!(json.contains("\"page\": \"players\""));
+    assert!(json.contains("\"parser_version\": "));
+    assert!(json.contains("\"total_rows\": 3"));
+    assert!(json.contains("\"path\": \"Alpha.csv\", \"rows\": 2"));
+    assert!(json.contains("\"path\": \"Beta.csv\", \"rows\": 1"));
+    // Manifest itself is not an export file
+    assert!(!written.iter().any(|p| p.ends_with("manifest.json")));
+}
+
+#[test]
+fn skip_optional_drops_player_id_column() {
+    let mut opts = AppOptions::default();
+    opts.export.format = ExportFormat::Csv;
+    opts.export.skip_optional = true;
+    let dir = tmp_dir("skip_player_id");
+    opts.export.set_path(dir.join("p.csv").to_str().unwrap());
+
+    let headers = Some(vec!["Name".into(), "#".into(), "Race".into(), "Team".into(), "SPP".into(), "Player id".into()]);
+    let rows = vec![vec!["A".into(), "#7".into(), "Elf".into(), "Alpha".into(), "12".into(), "4711".into()]];
+    let written = export_dataset(&opts, PageKind::Players, &headers, &rows).unwrap();
+
+    let text = fs::read_to_string(&written[0]).unwrap();
+    assert_eq!(text.lines().collect::<Vec<_>>(), vec!["Name,#,Race,Team,SPP", "A,7,Elf,Alpha,12"]);
+}
+
+fn divisions() -> Divisions {
+    [("Alpha", "North"), ("Beta", "South"), ("Gamma", "North")].iter()
+        .map(|(t, d)| (t.to_string(), d.to_string()))
+        .collect()
+}
+
+#[test]
+fn by_division_per_team_uses_subdirectories() {
+    let mut opts = AppOptions::default();
+    opts.export.export_type = ExportType::PerTeam;
+    opts.export.format = ExportFormat::Csv;
+    opts.export.by_division = true;
+    let dir = tmp_dir("by_division_dirs");
+    opts.export.set_path(dir.to_str().unwrap());
+
+    let headers = Some(vec!["Name".into(), "#".into(), "Race".into(), "Team".into()]);
+    let rows = vec![
+        vec!["A".into(), "#7".into(), "Elf".into(), "Alpha".into()],
+        vec!["B".into(), "#8".into(), "Orc".into(), "Beta".into()],
+        vec!["C".into(), "#9".into(), "Orc".into(), "Nomads".into()],
+    ];
+    let mut written = export_dataset_with(&opts, PageKind::Players, &headers, &rows, &divisions()).unwrap();
+    written.sort();
+    let mut expected = vec![
+        dir.join("North").join("Alpha.csv"),
+        dir.join("South").join("Beta.csv"),
+        dir.join("Nomads.csv"), // no division known: top level
+    ];
+    expected.sort();
+    assert_eq!(written, expected);
+    assert!(written.iter().all(|p| p.is_file()));
+}
+
+#[test]
+fn by_division_single_file_adds_division_columns() {
+    let mut opts = AppOptions::default();
+    opts.export.format = ExportFormat::Csv;
+    opts.export.by_division = true;
+    let dir = tmp_dir("by_division_single");
+    opts.export.set_path(dir.join("r.csv").to_str().unwrap());
+
+    let headers = Some(vec![
+        "S".into(), "W".into(), "Home".into(), "H".into(),
+        "A".into(), "Away".into(), "Match id".into(),
+    ]);
+    let rows = vec![
+        vec!["5".into(), "1".into(), "Alpha".into(), "2".into(), "1".into(), "Beta".into(), "10".into()],
+        vec!["5".into(), "1".into(), "Nomads".into(), "0".into(), "0".into(), "Gamma".into(), "11".into()],
+    ];
+    let written = export_dataset_with(&opts, PageKind::GameResults, &headers, &rows, &divisions()).unwrap();
+
+    let text = fs::read_to_string(&written[0]).unwrap();
+    assert_eq!(text.lines().collect::<Vec<_>>(), vec![
+        "S,W,Home,H,A,Away,Match id,Home Division,Away Division",
+        "5,1,Alpha,2,1,Beta,10,North,South",
+        "5,1,Nomads,0,0,Gamma,11,,North",
+    ]);
+}
+
+#[test]
+fn append_adds_only_rows_the_file_lacks() {
+    let mut opts = AppOptions::default();
+    opts.scrape.page = PageKind::GameResults;
+    opts.export.format = ExportFormat::Csv;
+    opts.export.append = true;
+    let dir = tmp_dir("append");
+    opts.export.set_path(dir.join("r.csv").to_str().unwrap());
+
+    let headers = Some(vec![
+        "S".into(), "W".into(), "Home".into(), "H".into(),
+        "A".into(), "Away".into(), "Match id".into(),
+    ]);
+    let week1 = vec![
+        vec!["5".into(), "1".into(), "Alpha".into(), "2".into(), "1".into(), "Beta".into(), "10".into()],
+    ];
+    export_dataset(&opts, PageKind::GameResults, &headers, &week1).unwrap();
+
+    // Week 1 again (key already there, even with another score) and week 2
+    let both = vec![
+        vec!["5".into(), "1".into(), "Alpha".into(), "3".into(), "1".into(), "Beta".into(), "10".into()],
+        vec!["5".into(), "2".into(), "Beta".into(), "0".into(), "0".into(), "Alpha".into(), "12".into()],
+    ];
+    let written = export_dataset(&opts, PageKind::GameResults, &headers, &both).unwrap();
+    let text = fs::read_to_string(&written[0]).unwrap();
+    assert_eq!(text.lines().collect::<Vec<_>>(), vec![
+        "S,W,Home,H,A,Away,Match id",
+        "5,1,Alpha,2,1,Beta,10",
+        "5,2,Beta,0,0,Alpha,12",
+    ]);
+
+    // Other columns: refused, file untouched
+    let fewer = Some(vec!["S".into(), "W".into(), "Home".into(), "Away".into()]);
+    let rows = vec![vec!["5".into(), "3".into(), "Alpha".into(), "Beta".into()]];
+    assert!(export_dataset(&opts, PageKind::GameResults, &fewer, &rows).is_err());
+    assert_eq!(fs::read_to_string(&written[0]).unwrap(), text);
+}
+
+#[test]
+fn bundle_writes_each_page_into_its_own_folder() {
+    use bb_scrape::file::export_bundle;
+    use bb_scrape::store::DataSet;
+
+    let mut opts = AppOptions::default();
+    opts.export.format = ExportFormat::Csv;
+    opts.export.export_type = ExportType::PerTeam;
+    opts.export.write_manifest = true;
+    let dir = tmp_dir("bundle");
+
+    let teams = DataSet {
+        headers: Some(vec!["Id".into(), "Team".into(), "Division".into()]),
+        rows: vec![vec!["0".into(), "Alpha".into(), "North".into()]],
+    };
+    let standings = DataSet {
+        headers: Some(vec!["Team".into(), "W".into()]),
+        rows: vec![vec!["Alpha".into(), "3".into()]],
+    };
+    let empty = DataSet { headers: None, rows: Vec::new() };
+
+    let written = export_bundle(&opts, &dir, vec![
+        (PageKind::Standings, standings),
+        (PageKind::Teams, teams),
+        (PageKind::Injuries, empty),
+    ]).unwrap();
+
+    // Standings can't be split per team: one file with its default name
+    assert_eq!(written[0], dir.join("standings").join("standings.csv"));
+    assert!(written.iter().skip(1).all(|p| p.starts_with(dir.join("teams"))));
+    assert!(!dir.join("injuries").exists());
+    assert!(dir.join("standings").join("manifest.json").exists());
+    assert!(fs::read_to_string(&written[0]).unwrap().contains("Alpha,3"));
+}
+
+#[test]
+fn per_team_zip_replaces_the_files_with_one_archive() {
+    let mut opts = AppOptions::default();
+    opts.export.export_type = ExportType::PerTeam;
+    opts.export.format = ExportFormat::Csv;
+    opts.export.write_manifest = true;
+    opts.export.zip = true;
+    let base = tmp_dir("zip");
+    let dir = base.join("players");
+    opts.export.set_path(dir.to_str().unwrap());
+
+    let headers = Some(vec!["Name".into(), "#Number".into(), "Race".into(), "Team".into()]);
+    let rows = vec![
+        vec!["A".into(), "#7".into(), "Elf".into(), "Alpha".into()],
+        vec!["B".into(), "#8".into(), "Orc".into(), "Beta".into()],
+    ];
+    let written = export_dataset(&opts, PageKind::Players, &headers, &rows).unwrap();
+
+    assert_eq!(written, vec![base.join("players.zip")]);
+    assert!(!dir.exists());
+    let zip = fs::read(&written[0]).unwrap();
+    assert_eq!(&zip[..4], b"PK\x03\x04");
+    let text = String::from_utf8_lossy(&zip);
+    for name in ["Alpha.csv", "Beta.csv", "manifest.json", "A,#7,Elf,Alpha"] {
+        assert!(text.contains(name), "{} missing from the archive", name);
+    }
+}
+
+#[test]
+fn per_team_rows_sorted_by_column_within_weeks() {
+    let mut opts = AppOptions::default();
+    opts.export.export_type = ExportType::PerTeam;
+    opts.export.format = ExportFormat::Csv;
+    let dir = tmp_dir("per_team_sorted");
+    opts.export.set_path(dir.to_str().unwrap());
+
+    opts.export.sort_by = Some("match id".into());
+    opts.export.sort_descending = true;
+    opts.export.chronological = true;
+
+    let headers = Some(["S", "W", "Home team", "Home", "Away", "Away team", "Match id"].map(String::from).to_vec());
+    let game = |w: &str, home: &str, away: &str, id: &str| ["7", w, home, "1", "0", away, id].map(String::from).to_vec();
+    let rows = vec![game("3", "Alpha", "Beta", "12"), game("1", "Gamma", "Alpha", "9"), game("1", "Alpha", "Delta", "10")];
+    let written = export_dataset(&opts, PageKind::GameResults, &headers, &rows).unwrap();
+    let alpha = written.iter().find(|p| p.file_name().unwrap().to_string_lossy().contains("Alpha")).unwrap();
+    let ids: Vec<String> = fs::read_to_string(alpha).unwrap().lines().skip(1)
+        .map(|l| l.rsplit(',').next().unwrap().to_string())
+        .collect();
+    // Week 1 first, highest match id first within the week
+    assert_eq!(ids, ["10", "9", "12"]);
+}
@@ -3,7 +3,7 @@
 // Tests for ExportOptions path/extension logic.
 //
 use std::path::{Path, PathBuf};
-use bb_scrape::config::options::{ExportOptions, ExportFormat, ExportType, PageKind};
+use bb_scrape::config::options::{AppOptions, ExportOptions, ExportFormat, ExportType, FormulaEscape, PageExportDefaults, PageKind};
 use bb_scrape::config::options::PageKind::{Players, GameResults};
 
 #[test]
@@ -101,4 +101,62 @@ fn no_dir_migration_when_textbox_dir_is_custom() {
     // --- end mirror ---
 
     assert_eq!(text_after, "out/custom/hello.csv");
-}
\ No newline at end of file
+}
+#[test]
+fn page_defaults_apply_on_enter_and_restore_on_leave() {
+    let mut export = ExportOptions::default();
+    export.format = ExportFormat::Tsv;
+    export.page_defaults.insert(GameResults, PageExportDefaults {
+        format: Some(ExportFormat::Csv),
+        include_headers: Some(false),
+        dir: None,
+    });
+
+    // Players has none of its own: shared settings stay
+    assert!(!export.enter_page_defaults(Players));
+    assert_eq!(export.format, ExportFormat::Tsv);
+    assert!(!export.leave_page_defaults(Players));
+
+    // Game Results uses its own, and hands the shared ones back on leave
+    assert!(export.enter_page_defaults(GameResults));
+    assert_eq!(export.format, ExportFormat::Csv);
+    assert!(!export.include_headers);
+    assert!(export.leave_page_defaults(GameResults));
+    assert_eq!(export.format, ExportFormat::Tsv);
+    assert!(export.include_headers);
+}
+
+#[test]
+fn formula_like_cells_escaped_where_configured() {
+    assert_eq!(FormulaEscape::escape("=SUM(A1)").as_deref(), Some("'=SUM(A1)"));
+    assert_eq!(FormulaEscape::escape("+1 555").as_deref(), Some("'+1 555"));
+    assert_eq!(FormulaEscape::escape("@Coach").as_deref(), Some("'@Coach"));
+    assert_eq!(FormulaEscape::escape("-3"), None);
+    assert_eq!(FormulaEscape::escape(" +1.5 "), None);
+    assert_eq!(FormulaEscape::escape("Orcs"), None);
+    assert_eq!(FormulaEscape::escape(""), None);
+    // Lone signs, control characters and whitespace in front
+    for cell in ["=", "+", "-", "@"] {
+        assert_eq!(FormulaEscape::escape(cell), Some(format!("'{}", cell)));
+    }
+    assert_eq!(FormulaEscape::escape("\tcmd").as_deref(), Some("'\tcmd"));
+    assert_eq!(FormulaEscape::escape("\rcmd").as_deref(), Some("'\rcmd"));
+    assert_eq!(FormulaEscape::escape("  =1+1").as_deref(), Some("'  =1+1"));
+
+    // Default: copies only
+    assert!(FormulaEscape::default().applies(true));
+    assert!(!FormulaEscape::default().applies(false));
+
+    let mut opts = AppOptions::default();
+    opts.scrape.page = PageKind::Transactions;
+    opts.export.format = ExportFormat::Csv;
+    let headers = Some(vec!["Team".to_string(), "Note".to_string()]);
+    let rows = vec![vec!["Alpha".to_string(), "=HYPERLINK(\"x\")".to_string()]];
+
+    let plain = bb_scrape::file::to_export_string(&opts, &headers, &rows);
+    assert!(plain.contains(",\"=HYPERLINK"));
+
+    opts.export.formula_escape = FormulaEscape::Always;
+    let escaped = bb_scrape::file::to_export_string(&opts, &headers, &rows);
+    assert!(escaped.contains(",\"'=HYPERLINK"));
+}
@@ -5,6 +5,7 @@
 use std::fs;
 use std::path::PathBuf;
 use bb_scrape::file::{self, ColumnProjection};
+use bb_scrape::config::options::Backup;
 use bb_scrape::store::DataSet;
 
 fn tmp(path: &str) -> PathBuf {
@@ -26,7 +27,7 @@ fn stream_projection_csv() {
     // KeepAll
     let p1 = tmp("bb_stream_keepall.csv");
     file::stream_write_table_to_path(
-        &p1, &ds.headers, &ds.rows, &row_ix, Some(','), ColumnProjection::KeepAll
+        &p1, &ds.headers, &ds.rows, &row_ix, Some(','), ColumnProjection::KeepAll, Backup::Off
     ).unwrap();
     let s1 = fs::read_to_string(&p1).unwrap();
     assert!(s1.contains("A,B,C"));
@@ -36,10 +37,71 @@ fn stream_projection_csv() {
     // DropLast
     let p2 = tmp("bb_stream_drop.csv");
     file::stream_write_table_to_path(
-        &p2, &ds.headers, &ds.rows, &row_ix, Some(','), ColumnProjection::DropLast
+        &p2, &ds.headers, &ds.rows, &row_ix, Some(','), ColumnProjection::DropLast, Backup::Off
     ).unwrap();
     let s2 = fs::read_to_string(&p2).unwrap();
     assert!(s2.contains("A,B"));
     assert!(s2.contains("1,2\n"));
     assert!(!s2.contains(",3"));
 }
+
+#[test]
+fn stream_projection_columns_reorders_and_drops() {
+    let headers = Some(vec!["A".into(), "B".into(), "C".into()]);
+    let rows = vec![vec!["1".into(), "2".into(), "3".into()]];
+    let ds = DataSet { headers, rows };
+
+    // As shown: C first, B hidden
+    let proj = ColumnProjection::Columns(vec![2, 0]);
+    let p = tmp("bb_stream_columns.csv");
+    file::stream_write_table_to_path(&p, &ds.headers, &ds.rows, &[0], Some(','), proj.clone(), Backup::Off).unwrap();
+    assert_eq!(fs::read_to_string(&p).unwrap(), "C,A\n3,1\n");
+
+    // Same projection for the in-memory path (Copy / single file)
+    let (h, r) = proj.apply(&ds.headers, &ds.rows);
+    assert_eq!(h.unwrap(), vec!["C", "A"]);
+    assert_eq!(r, vec![vec!["3", "1"]]);
+}
+
+#[test]
+fn atomic_write_replaces_the_file_and_keeps_a_backup() {
+    let p = tmp("bb_atomic.tsv");
+    let bak = file::backup_path(&p, Backup::Bak).unwrap();
+    let _ = fs::remove_file(&bak);
+    fs::write(&p, "old\n").unwrap();
+
+    file::write_atomic(&p, b"new\n", Backup::Bak).unwrap();
+    assert_eq!(fs::read_to_string(&p).unwrap(), "new\n");
+    assert_eq!(fs::read_to_string(&bak).unwrap(), "old\n");
+
+    // Off: the backup stays as it was
+    file::write_atomic(&p, b"newer\n", Backup::Off).unwrap();
+    assert_eq!(fs::read_to_string(&p).unwrap(), "newer\n");
+    assert_eq!(fs::read_to_string(&bak).unwrap(), "old\n");
+
+    // No temporary files left behind
+    let leftovers = fs::read_dir(p.parent().unwrap()).unwrap()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_name().to_string_lossy().starts_with(".bb_atomic.tsv."))
+        .count();
+    assert_eq!(leftovers, 0);
+}
+
+// Unix only: Windows may refuse a rename over a file another rename is replacing
+#[cfg(unix)]
+#[test]
+fn concurrent_atomic_writes_of_one_file_all_land() {
+    let p = tmp("bb_atomic_concurrent.tsv");
+    let writers: Vec<_> = (0..8)
+        .map(|i| {
+            let p = p.clone();
+            std::thread::spawn(move || file::write_atomic(&p, format!("{}\n", i).as_bytes(), Backup::Off))
+        })
+        .collect();
+    for w in writers {
+        w.join().unwrap().unwrap();
+    }
+    let last: u32 = fs::read_to_string(&p).unwrap().trim().parse().unwrap();
+    assert!(last < 8);
+    let _ = fs::remove_file(&p);
+}
@@ -0,0 +1,92 @@
+// tests/markup_tables.rs
+//
+// Copy/export as Markdown and BBCode tables.
+//
+use bb_scrape::config::options::{AppOptions, ExportFormat, PageKind};
+use bb_scrape::file::{markup_table, to_export_string};
+
+fn sample() -> (Option<Vec<String>>, Vec<Vec<String>>) {
+    let headers = Some(vec!["Team".to_string(), "W".to_string(), "Note".to_string()]);
+    let rows = vec![
+        vec!["Reds".to_string(), "12".to_string(), "a|b".to_string()],
+        vec!["Blue Sharks".to_string(), "3".to_string(), "line\nbreak".to_string()],
+    ];
+    (headers, rows)
+}
+
+#[test]
+fn markdown_is_padded_and_right_aligns_numbers() {
+    let (h, r) = sample();
+    let mut opts = AppOptions::default();
+    opts.scrape.page = PageKind::Standings;
+    opts.export.format = ExportFormat::Markdown;
+
+    let md = to_export_string(&opts, &h, &r);
+    assert_eq!(md, "\
+| Team        |   W | Note       |
+|-------------|----:|------------|
+| Reds        |  12 | a\\|b       |
+| Blue Sharks |   3 | line break |
+");
+}
+
+#[test]
+fn bbcode_has_header_and_data_rows() {
+    let (h, r) = sample();
+    let mut opts = AppOptions::default();
+    opts.scrape.page = PageKind::Standings;
+    opts.export.format = ExportFormat::BbCode;
+
+    let bb = to_export_string(&opts, &h, &r);
+    assert_eq!(bb, "\
+[table]
+[tr][th]Team[/th][th]W[/th][th]Note[/th][/tr]
+[tr][td]Reds[/td][td]12[/td][td]a|b[/td][/tr]
+[tr][td]Blue Sharks[/td][td]3[/td][td]line break[/td][/tr]
+[/table]
+");
+
+    // Without headers: data rows only
+    opts.export.include_headers = false;
+    assert!(!to_export_string(&opts, &h, &r).contains("[th]"));
+}
+
+#[test]
+fn markdown_without_headers_keeps_a_blank_header_row() {
+    let md = markup_table(ExportFormat::Markdown, None, &[vec!["x", "1"]]);
+    assert_eq!(md, "|     |     |\n|-----|----:|\n| x   |   1 |\n");
+}
+
+#[test]
+fn header_names_rename_only_the_exported_page() {
+    let (h, r) = sample();
+    let mut opts = AppOptions::default();
+    opts.scrape.page = PageKind::Standings;
+    opts.export.header_names.insert(PageKind::Standings, [("W".to_string(), "Wins".to_string())].into());
+    opts.export.header_names.insert(PageKind::Players, [("Team".to_string(), "Club".to_string())].into());
+
+    let tsv = to_export_string(&opts, &h, &r);
+    assert_eq!(tsv.lines().next(), Some("Team\tWins\tNote"));
+    assert_eq!(h.as_ref().unwrap()[1], "W");
+}
+
+#[test]
+fn number_format_rewrites_numeric_columns_only() {
+    use bb_scrape::config::options::NumberFormat;
+    let h = Some(vec!["Team".to_string(), "Avg".to_string(), "Note".to_string()]);
+    let r = vec![
+        vec!["Reds".to_string(), "1234.5".to_string(), "1.5 weeks".to_string()],
+        vec!["1999".to_string(), "-7".to_string(), "".to_string()],
+    ];
+    let mut opts = AppOptions::default();
+    opts.scrape.page = PageKind::Standings;
+    opts.export.number_format = NumberFormat { decimal: ',', thousands: Some('.') };
+
+    let tsv = to_export_string(&opts, &h, &r);
+    assert_eq!(tsv, "Team\tAvg\tNote\nReds\t1.234,5\t1.5 weeks\n1999\t-7\t\n");
+
+    let f = NumberFormat { decimal: '.', thousands: Some(' ') };
+    assert_eq!(f.apply("1234567.25").as_deref(), Some("1 234 567.25"));
+    assert_eq!(f.apply("12e3"), None);
+    assert!(NumberFormat::default().is_plain());
+}
@@ -0,0 +1,178 @@
+// tests/pathological_names.rs
+//
+// Team/player names with separators, quotes, emoji or RTL text must neither
+// break file creation (per-team exports) nor the CSV/TSV structure.
+//
+use std::fs;
+use std::path::PathBuf;
+
+use bb_scrape::config::options::{AppOptions, ExportFormat, ExportOptions, ExportType, PageKind};
+use bb_scrape::core::sanitize::sanitize_team_filename;
+use bb_scrape::file::{export_dataset, parse_rows, planned_team_files, to_export_string};
+
+const NASTY: [&str; 12] = [
+    "../../etc/passwd",
+    r"C:\Windows\System32",
+    "Team \"Quoted\" FC",
+    "Alpha, Beta; Gamma\tDelta",
+    "Line\nBreak\r\nTeam",
+    "🔥 Fire Ants 🐜",
+    "\u{202E}sdrawkcaB\u{202C} Boys",
+    "מכבי תל אביב",
+    "CON",
+    "aux",
+    "   ...   ",
+    "<Why?>|*:",
+];
+
+fn tmp_dir(name: &str) -> PathBuf {
+    let mut p = std::env::temp_dir();
+    p.push(format!("bb_names_{}", name));
+    let _ = fs::remove_dir_all(&p);
+    fs::create_dir_all(&p).unwrap();
+    p
+}
+
+#[test]
+fn filenames_are_safe_on_every_platform() {
+    for (i, name) in NASTY.iter().enumerate() {
+        let stem = sanitize_team_filename(name, i as u32);
+        assert!(!stem.is_empty(), "{name:?}");
+        assert!(stem.chars().count() <= 64, "{name:?} → {stem:?}");
+        for bad in ['/', '\\', ':', '*', '?', '"', '<', '>', '|', '.', '\n', '\r', '\t', '\u{202E}'] {
+            assert!(!stem.contains(bad), "{name:?} → {stem:?} contains {bad:?}");
+        }
+        assert!(!stem.starts_with('_'), "{name:?} → {stem:?}");
+    }
+    assert_eq!(sanitize_team_filename("../../etc/passwd", 0), "etcpasswd");
+    assert_eq!(sanitize_team_filename("🔥 Fire Ants 🐜", 0), "Fire_Ants");
+    assert_eq!(sanitize_team_filename("מכבי תל אביב", 0), "מכבי_תל_אביב");
+    assert_eq!(sanitize_team_filename("Ærø Ålborg", 0), "Ærø_Ålborg");
+    assert_eq!(sanitize_team_filename("CON", 0), "CON_");
+    assert_eq!(sanitize_team_filename("aux", 0), "aux_");
+    assert_eq!(sanitize_team_filename("   ...   ", 7), "team_7");
+    assert_eq!(sanitize_team_filename(&"x".repeat(300), 0).len(), 64);
+}
+
+#[test]
+fn csv_and_tsv_keep_their_shape() {
+    let headers = Some(vec!["Name".to_string(), "Team".to_string()]);
+    let rows: Vec<Vec<String>> = NASTY.iter()
+        .map(|n| vec![n.to_string(), format!("{n} II")])
+        .collect();
+
+    for format in [ExportFormat::Csv, ExportFormat::Tsv] {
+        let mut opts = AppOptions::default();
+        opts.export.format = format;
+        let text = to_export_string(&opts, &headers, &rows);
+        let back = parse_rows(&text, format.delimiter().unwrap());
+        assert_eq!(back.len(), rows.len() + 1, "{format}");
+        assert_eq!(&back[1..], &rows[..], "{format}");
+    }
+}
+
+#[test]
+fn custom_delimiters_keep_the_shape() {
+    let headers = Some(vec!["Name".to_string(), "Team".to_string()]);
+    let rows: Vec<Vec<String>> = NASTY.iter()
+        .map(|n| vec![n.to_string(), format!("{n} II")])
+        .collect();
+
+    for d in [";", "|", "space", "tab"] {
+        let mut opts = AppOptions::default();
+        opts.export.format = ExportFormat::Csv;
+        opts.export.custom_delimiter = Some(ExportOptions::parse_delimiter(d).unwrap());
+        let sep = opts.export.delimiter().unwrap();
+        let text = to_export_string(&opts, &headers, &rows);
+        let back = parse_rows(&text, sep);
+        assert_eq!(back.len(), rows.len() + 1, "{d}");
+        assert_eq!(&back[1..], &rows[..], "{d}");
+    }
+
+    for bad in ["\"", "\n", ";;", ""] {
+        assert!(ExportOptions::parse_delimiter(bad).is_err(), "{bad:?}");
+    }
+    // Markup formats have no delimiter to replace
+    let mut opts = AppOptions::default();
+    opts.export.format = ExportFormat::Markdown;
+    opts.export.custom_delimiter = Some(';');
+    assert_eq!(opts.export.delimiter(), None);
+}
+
+#[test]
+fn per_team_export_stays_inside_the_directory() {
+    let dir = tmp_dir("per_team");
+    let mut opts = AppOptions::default();
+    opts.export.export_type = ExportType::PerTeam;
+    opts.export.format = ExportFormat::Csv;
+    opts.export.set_path(dir.to_str().unwrap());
+
+    let headers = Some(vec![
+        "S".to_string(), "W".to_string(), "Home".to_string(), "H".to_string(),
+        "A".to_string(), "Away".to_string(), "Match id".to_string(),
+    ]);
+    // Every nasty name plays a "Normal" team; "CON" and "aux" are distinct files.
+    let rows: Vec<Vec<String>> = NASTY.iter().enumerate()
+        .map(|(i, n)| vec![
+            "5".to_string(), "1".to_string(), n.to_string(), "1".to_string(),
+            "0".to_string(), "Normal".to_string(), i.to_string(),
+        ])
+        .collect();
+
+    let written = export_dataset(&opts, PageKind::GameResults, &headers, &rows).unwrap();
+    assert_eq!(written.len(), NASTY.len() + 1);
+    for p in &written {
+        assert_eq!(p.parent().unwrap(), dir.as_path(), "{}", p.display());
+        assert!(p.is_file(), "{}", p.display());
+    }
+}
+
+#[test]
+fn names_differing_only_in_case_get_separate_files() {
+    let dir = tmp_dir("case");
+    let mut opts = AppOptions::default();
+    opts.export.export_type = ExportType::PerTeam;
+    opts.export.format = ExportFormat::Csv;
+    opts.export.set_path(dir.to_str().unwrap());
+
+    let headers = Some(vec![
+        "S".to_string(), "W".to_string(), "Home".to_string(), "H".to_string(),
+        "A".to_string(), "Away".to_string(), "Match id".to_string(),
+    ]);
+    let rows = vec![vec![
+        "5".to_string(), "1".to_string(), "Alpha".to_string(), "1".to_string(),
+        "0".to_string(), "ALPHA".to_string(), "1".to_string(),
+    ]];
+    let written = export_dataset(&opts, PageKind::GameResults, &headers, &rows).unwrap();
+    assert_eq!(written.len(), 2);
+    let mut names: Vec<String> = written.iter()
+        .map(|p| p.file_name().unwrap().to_string_lossy().to_lowercase())
+        .collect();
+    names.sort();
+    names.dedup();
+    assert_eq!(names.len(), 2, "{names:?}");
+}
+
+#[test]
+fn dry_run_plans_the_files_the_export_writes() {
+    let dir = tmp_dir("planned");
+    let mut opts = AppOptions::default();
+    opts.export.export_type = ExportType::PerTeam;
+    opts.export.format = ExportFormat::Csv;
+    opts.export.set_path(dir.to_str().unwrap());
+
+    // Players layout: the team is column 3
+    let headers = Some(["Name", "#", "Race", "Team"].iter().map(|h| h.to_string()).collect());
+    let rows: Vec<Vec<String>> = [("Al", "Alpha"), ("Bo", "ALPHA"), ("Cy", "Alpha"), ("Di", "../Beta")]
+        .iter().map(|(n, t)| vec![n.to_string(), "#1".to_string(), "Orc".to_string(), t.to_string()]).collect();
+
+    let planned = planned_team_files(&opts, &rows, &[3], &Default::default());
+    assert!(fs::read_dir(&dir).unwrap().next().is_none(), "nothing written");
+    assert_eq!(planned.iter().map(|(_, n)| n).sum::<usize>(), 4);
+
+    let mut written = export_dataset(&opts, PageKind::Players, &headers, &rows).unwrap();
+    let mut planned: Vec<PathBuf> = planned.into_iter().map(|(p, _)| p).collect();
+    written.sort();
+    planned.sort();
+    assert_eq!(planned, written);
+}
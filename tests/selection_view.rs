@@ -2,11 +2,11 @@
 //
 // Minimal Page impl to test SelectionView behavior without UI.
 //
-use std::error::Error;
+use bb_scrape::error::Error;
 use bb_scrape::data::{RawData, Selection, SelectionView};
 use bb_scrape::store::DataSet;
 use bb_scrape::config::options::PageKind;
-use bb_scrape::config::state::AppState;
+use bb_scrape::config::state::{AppState, GameStatus};
 use bb_scrape::progress::Progress;
 use bb_scrape::gui::pages::Page;
 
@@ -18,7 +18,7 @@ impl Page for TestPage {
         &self,
         _state: &AppState,
         _progress: Option<&mut dyn Progress>,
-    ) -> Result<DataSet, Box<dyn Error>> {
+    ) -> Result<DataSet, Error> {
         Ok(DataSet { headers: None, rows: Vec::new() })
     }
     fn filter_rows_for_selection(
@@ -74,3 +74,284 @@ fn selection_view_none_all_partial() {
     let idx: Vec<usize> = view.row_ix.clone();
     assert_eq!(idx, vec![0,2,3]);
 }
+
+#[test]
+fn sort_key_numeric_text_and_stability() {
+    use bb_scrape::data::SortKey;
+
+    let rows: Vec<Vec<String>> = vec![
+        vec!["bob".into(), "#10".into()],
+        vec!["Alice".into(), "#2".into()],
+        vec!["carl".into(), "".into()],
+        vec!["dave".into(), "#2".into()],
+    ];
+
+    // Numeric ascending: 2, 2 (stable: Alice before dave), 10, blank last
+    let mut ix = vec![0, 1, 2, 3];
+    SortKey { col: 1, descending: false }.sort_row_ix(&rows, &mut ix, true);
+    assert_eq!(ix, vec![1, 3, 0, 2]);
+
+    // Numeric descending keeps blanks last and ties in original order
+    let mut ix = vec![0, 1, 2, 3];
+    SortKey { col: 1, descending: true }.sort_row_ix(&rows, &mut ix, true);
+    assert_eq!(ix, vec![0, 1, 3, 2]);
+
+    // Text is case-insensitive
+    let mut ix = vec![0, 1, 2, 3];
+    SortKey { col: 0, descending: false }.sort_row_ix(&rows, &mut ix, false);
+    assert_eq!(ix, vec![1, 0, 2, 3]);
+
+    // Click cycle: asc → desc → off
+    let k = SortKey::cycle(None, 1);
+    assert_eq!(k, Some(SortKey { col: 1, descending: false }));
+    let k = SortKey::cycle(k, 1);
+    assert_eq!(k, Some(SortKey { col: 1, descending: true }));
+    assert_eq!(SortKey::cycle(k, 1), None);
+}
+
+#[test]
+fn delta_rows_diffs_numeric_columns_by_key() {
+    use bb_scrape::data::delta_rows;
+    let s = |v: &[&str]| v.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+    let prev = DataSet { headers: None, rows: vec![
+        s(&["Alice", "#7", "Orc", "Alpha", "3", "10"]),
+        s(&["Bob",   "#9", "Elf", "Alpha", "4", "12"]),
+    ]};
+    let cur = DataSet { headers: None, rows: vec![
+        s(&["Bob",   "#9", "Elf", "Alpha", "5", "12"]),
+        s(&["Alice", "#7", "Orc", "Alpha", "4", "15"]),
+        s(&["Cleo",  "#1", "Orc", "Alpha", "1", "2"]),
+    ]};
+
+    let d = delta_rows(&prev, &cur, &[0, 1, 3], &[2]);
+    assert_eq!(d.len(), cur.rows.len());
+    assert_eq!(d[0], s(&["Bob", "#9", "Elf", "Alpha", "+1", ""]));
+    assert_eq!(d[1], s(&["Alice", "#7", "Orc", "Alpha", "+1", "+5"]));
+    // New player: counted from zero
+    assert_eq!(d[2], s(&["Cleo", "#1", "Orc", "Alpha", "+1", "+2"]));
+}
+
+#[test]
+fn text_filter_any_column_or_one_column() {
+    use bb_scrape::data::TextFilter;
+    let s = |v: &[&str]| v.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+    let rows = vec![
+        s(&["Alice", "Orc", "Alpha"]),
+        s(&["Bob", "Elf", "Orcland"]),
+        s(&["Cleo", "Human", "Beta"]),
+    ];
+
+    let mut ix: Vec<usize> = (0..rows.len()).collect();
+    TextFilter { query: "  orc ".to_string(), column: None }.retain(&rows, &mut ix);
+    assert_eq!(ix, vec![0, 1]);
+
+    let mut ix: Vec<usize> = (0..rows.len()).collect();
+    TextFilter { query: "ORC".to_string(), column: Some(1) }.retain(&rows, &mut ix);
+    assert_eq!(ix, vec![0]);
+
+    // Empty query is a no-op
+    let mut ix = vec![2, 0];
+    TextFilter::default().retain(&rows, &mut ix);
+    assert_eq!(ix, vec![2, 0]);
+}
+
+#[test]
+fn column_widths_follow_sampled_content() {
+    use bb_scrape::data::sample_column_widths;
+    let s = |v: &[&str]| v.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+    let headers = s(&["Name", "#", "Team"]);
+    let mut rows: Vec<Vec<String>> = (0..20).map(|_| s(&["Al", "#7", "Orcland Raiders"])).collect();
+    // One outlier doesn't widen the column
+    rows.push(s(&["A very very very long player name indeed", "#1", "X"]));
+
+    let w = sample_column_widths(Some(&headers), &rows, 3);
+    assert_eq!(w.len(), 3);
+    assert!(w[0] < w[2], "p90 name length is short: {:?}", w);
+    // Header wins over shorter content
+    assert!(w[0] >= sample_column_widths(None, &rows, 1)[0]);
+    // Columns beyond the data still get a usable width
+    assert!(sample_column_widths(None, &[], 2).iter().all(|&x| x >= 24.0));
+}
+
+#[test]
+fn game_results_played_only_filter() {
+    use bb_scrape::gui::router;
+    let s = |v: &[&str]| v.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+    let page = router::page_for(&PageKind::GameResults);
+    let mut state = AppState::default();
+    assert!(page.row_filter(&state).is_none());
+
+    state.gui.game_results_status = GameStatus::Played;
+    let keep = page.row_filter(&state).unwrap();
+    assert!(keep(&s(&["5", "1", "Reds", "2", "0", "Blues", "10"])));
+    assert!(keep(&s(&["5", "1", "Reds", "0", "0", "Blues", "11"])));
+    assert!(!keep(&s(&["5", "2", "Reds", "", "", "Blues", ""])));
+    assert!(!keep(&s(&["5", "2", "Reds", " ", "1", "Blues", ""])));
+}
+
+#[test]
+fn game_results_status_and_week_range_compose() {
+    use bb_scrape::gui::router;
+    let game = |w: &str, h: &str, a: &str| -> Vec<String> {
+        ["12", w, "Reds", h, a, "Blues", ""].iter().map(|c| c.to_string()).collect()
+    };
+    let rows = [game("1", "2", "1"), game("2", "0", "0"), game("3", "", ""), game("4", "", "")];
+    let page = router::page_for(&PageKind::GameResults);
+    let mut state = AppState::default();
+    let kept = |state: &AppState| -> Vec<usize> {
+        let keep = page.row_filter(state).unwrap();
+        (0..rows.len()).filter(|&i| keep(&rows[i])).collect()
+    };
+
+    state.gui.game_results_status = GameStatus::Upcoming;
+    assert_eq!(kept(&state), vec![2, 3]);
+    state.gui.game_results_weeks = Some((2, 3));
+    assert_eq!(kept(&state), vec![2]);
+    state.gui.game_results_status = GameStatus::All;
+    assert_eq!(kept(&state), vec![1, 2]);
+}
+
+#[test]
+fn source_paths_per_page() {
+    use bb_scrape::gui::router;
+    let s = |v: &[&str]| v.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+    let teams = vec![(3, "Reds".to_string()), (7, "Blues".to_string())];
+
+    // Game Results: match report once played, home team before
+    let results = router::page_for(&PageKind::GameResults);
+    assert_eq!(results.source_path(None, &s(&["5", "1", "Reds", "2", "0", "Blues", "10"]), &teams).as_deref(), Some("game.php?i=10"));
+    assert_eq!(results.source_path(None, &s(&["5", "2", "Blues", "", "", "Reds", ""]), &teams).as_deref(), Some("team.php?i=7"));
+    assert_eq!(results.team_columns(), &[2, 5]);
+
+    // Players: player id column when present, else the team
+    let players = router::page_for(&PageKind::Players);
+    let headers = s(&["Name", "#", "Race", "Team", "Player id"]);
+    assert_eq!(players.source_path(Some(&headers), &s(&["Al", "#1", "Orc", "Reds", "4411"]), &teams).as_deref(), Some("player.php?i=4411"));
+    assert_eq!(players.source_path(Some(&headers[..4]), &s(&["Al", "#1", "Orc", "Reds"]), &teams).as_deref(), Some("team.php?i=3"));
+
+    // Unknown team, no ids: nothing to open
+    let injuries = router::page_for(&PageKind::Injuries);
+    let row = s(&["5", "1", "Greens", "Al", "2", "", "", "BH", "Purples", "Bo", "1", ""]);
+    assert!(injuries.source_path(None, &row, &teams).is_none());
+}
+
+#[test]
+fn cell_links_per_page() {
+    use bb_scrape::gui::router;
+    let s = |v: &[&str]| v.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+    let teams = vec![(3, "Reds".to_string()), (7, "Blues".to_string())];
+
+    let results = router::page_for(&PageKind::GameResults);
+    let game = s(&["5", "1", "Reds", "2", "0", "Blues", "10"]);
+    let link = |ci| results.cell_link(None, &game, ci, &teams);
+    assert_eq!(link(6).as_deref(), Some("game.php?i=10"));
+    assert_eq!(link(2).as_deref(), Some("team.php?i=3"));
+    assert_eq!(link(5).as_deref(), Some("team.php?i=7"));
+    assert_eq!(link(3), None);
+
+    let players = router::page_for(&PageKind::Players);
+    let headers = s(&["Name", "#", "Race", "Team", "Player id"]);
+    let row = s(&["Al", "#1", "Orc", "Reds", "4411"]);
+    assert_eq!(players.cell_link(Some(&headers), &row, 0, &teams).as_deref(), Some("player.php?i=4411"));
+    assert_eq!(players.cell_link(Some(&headers), &row, 3, &teams).as_deref(), Some("team.php?i=3"));
+    assert_eq!(players.cell_link(Some(&headers), &row, 2, &teams), None);
+
+    // Standings link by id, whatever the team is called now
+    let standings = router::page_for(&PageKind::Standings);
+    let row = s(&["1", "12", "Renamed FC", "6", "0", "2", "20", "11"]);
+    assert_eq!(standings.cell_link(None, &row, 2, &teams).as_deref(), Some("team.php?i=12"));
+}
+
+#[test]
+fn row_selection_clicks_and_view_order() {
+    use bb_scrape::data::RowSelection;
+    // Table sorted so source rows show as 4, 2, 7, 0, 5
+    let view = [4, 2, 7, 0, 5];
+    let mut sel = RowSelection::default();
+
+    sel.click(&view, 1, false, false);
+    assert_eq!(sel.in_view(&view), vec![2]);
+
+    // Shift: range from the anchor (row 2) to display row 3
+    sel.click(&view, 3, false, true);
+    assert_eq!(sel.in_view(&view), vec![2, 7, 0]);
+
+    // Ctrl toggles single rows and moves the anchor
+    sel.click(&view, 2, true, false);
+    sel.click(&view, 4, true, false);
+    assert_eq!(sel.in_view(&view), vec![2, 0, 5]);
+
+    // Ctrl+Shift adds a range instead of replacing
+    sel.click(&view, 0, true, true);
+    assert_eq!(sel.in_view(&view), vec![4, 2, 7, 0, 5]);
+
+    // Only rows still in the view count (e.g. after a team filter)
+    assert_eq!(sel.in_view(&[7, 5, 9]), vec![7, 5]);
+    assert_eq!(sel.len(), 5);
+
+    sel.click(&view, 2, false, false);
+    assert_eq!(sel.in_view(&view), vec![7]);
+    sel.clear();
+    assert!(sel.is_empty());
+}
+
+#[test]
+fn compact_table_interns_cells_and_reads_back() {
+    use bb_scrape::data::CompactTable;
+    let rows = vec![
+        vec!["Alice".to_string(), "0".to_string(), "Orc".to_string()],
+        vec!["Bob".to_string(), "0".to_string()],
+        vec!["Cleo".to_string(), "".to_string(), "Orc".to_string()],
+    ];
+    let t = CompactTable::from_rows(&rows);
+
+    assert_eq!(t.len(), 3);
+    // "0" and "Orc" are stored once
+    assert_eq!(t.distinct(), 6);
+    assert_eq!(t.cell(0, 2), Some("Orc"));
+    assert_eq!(t.cell(1, 2), None);
+    assert_eq!(t.cell(2, 1), Some(""));
+    assert_eq!(t.cell(3, 0), None);
+    assert_eq!(t.row(1).map(|r| r.collect::<Vec<_>>()), Some(vec!["Bob", "0"]));
+    assert_eq!(t.to_rows(), rows);
+    assert!(CompactTable::from_rows(Vec::<Vec<String>>::new()).is_empty());
+}
+
+#[test]
+fn sort_and_filter_keep_large_views_in_order() {
+    use bb_scrape::data::{SortKey, TextFilter};
+    let rows: Vec<Vec<String>> = (0..20_000)
+        .map(|i| vec![format!("P{}", i % 7), ((i * 7919) % 1000).to_string()])
+        .collect();
+    let mut ix: Vec<usize> = (0..rows.len()).collect();
+    SortKey { col: 1, descending: false }.sort_row_ix(&rows, &mut ix, true);
+    assert!(ix.windows(2).all(|w| {
+        let n = |i: usize| rows[i][1].parse::<u32>().unwrap();
+        // Stable: equal values keep their source order
+        n(w[0]) < n(w[1]) || (n(w[0]) == n(w[1]) && w[0] < w[1])
+    }));
+
+    TextFilter { query: "p3".to_string(), column: Some(0) }.retain(&rows, &mut ix);
+    assert_eq!(ix.len(), rows.iter().filter(|r| r[0] == "P3").count());
+}
+
+#[test]
+fn typed_sort_uses_schema_numbers() {
+    use bb_scrape::data::SortKey;
+    use bb_scrape::typed::TypedColumns;
+    let ds = DataSet {
+        headers: Some(vec!["Team".to_string(), "Players".to_string()]),
+        rows: vec![
+            vec!["Reds".to_string(), "12".to_string()],
+            vec!["Blues".to_string(), "".to_string()],
+            vec!["Greens".to_string(), "9".to_string()],
+        ],
+    };
+    let typed = TypedColumns::for_page(PageKind::TeamSummary, &ds);
+    let mut ix = vec![0, 1, 2];
+    SortKey { col: 1, descending: true }.sort_row_ix_typed(&ds.rows, &typed, &mut ix, true);
+    // Blank cells after the numbers in both directions
+    assert_eq!(ix, vec![0, 2, 1]);
+    SortKey { col: 0, descending: false }.sort_row_ix_typed(&ds.rows, &typed, &mut ix, false);
+    assert_eq!(ix, vec![1, 2, 0]);
+}